@@ -0,0 +1,368 @@
+//! `uniq bug-report` — collects what's needed to diagnose a problem into one
+//! `.tar.gz` to attach to an issue: the end of the log and the config, both
+//! with API keys and tokens removed, the project's saved session, the
+//! sidecar's health, and details of the environment.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use uniq_core::config::REDACTED;
+use uniq_core::session::Session;
+use uniq_core::UniqConfig;
+use uniq_sidecar::client::SidecarClient;
+use uniq_sidecar::manager::SidecarManager;
+
+/// Only the end of a long log goes in, since that's where the problem is.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// How long to wait for the sidecar to start and answer a health check.
+const SIDECAR_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// How API keys from well-known providers start.
+const KEY_PREFIXES: [&str; 9] = [
+    "sk-",
+    "sk_",
+    "ghp_",
+    "gho_",
+    "ghs_",
+    "github_pat_",
+    "xoxb-",
+    "xoxp-",
+    "AIza",
+];
+
+/// Shorter strings are left alone, so an odd config value like `x` doesn't
+/// blank out every `x` in the log.
+const MIN_SECRET_LEN: usize = 8;
+
+/// What to put in the report.
+pub struct Options {
+    /// Where to write the archive; a timestamped file in the working
+    /// directory if unset.
+    pub output: Option<PathBuf>,
+    /// Project whose session and `.uniq.toml` to include.
+    pub project: Option<PathBuf>,
+    pub profile: Option<String>,
+    pub sidecar_dir: PathBuf,
+    pub log_path: PathBuf,
+    /// Start the sidecar to check its health.
+    pub check_sidecar: bool,
+}
+
+/// Write the report and return where it went.
+pub async fn run(options: Options) -> Result<PathBuf> {
+    let stamp = Utc::now().format("%Y%m%d-%H%M%S");
+    let name = format!("uniq-bug-report-{}", stamp);
+    let output = options
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("{}.tar.gz", name)));
+
+    // The sidecar goes first so anything it logs while starting is in the
+    // log collected after it.
+    let mut files = vec![
+        ("sidecar.txt", sidecar_report(&options).await.into_bytes()),
+        ("environment.txt", environment(&options).into_bytes()),
+        ("config.toml", config(&options).into_bytes()),
+    ];
+    let project = options.project.as_deref().unwrap_or(Path::new("."));
+    match session(project) {
+        Some(contents) => files.push(("session.json", contents)),
+        None => files.push((
+            "session.txt",
+            format!("No saved session for {}\n", project.display()).into_bytes(),
+        )),
+    }
+    let secrets = load_config(&options)
+        .map(|config| config.secrets())
+        .unwrap_or_default();
+    files.push(("uniq.log", log_tail(&options.log_path, &secrets)));
+    // Right after a rotation the current log holds little, so the one
+    // before it goes in too.
+    let mut previous = options.log_path.clone().into_os_string();
+    previous.push(".1");
+    let previous = PathBuf::from(previous);
+    if previous.exists() {
+        files.push(("uniq.log.1", log_tail(&previous, &secrets)));
+    }
+
+    write_archive(&output, &name, &files)
+        .with_context(|| format!("Could not write {}", output.display()))?;
+    Ok(output)
+}
+
+/// Pack `files` into a gzipped tarball under a directory named `name`.
+fn write_archive(output: &Path, name: &str, files: &[(&str, Vec<u8>)]) -> Result<()> {
+    let encoder = GzEncoder::new(File::create(output)?, Compression::default());
+    let mut archive = tar::Builder::new(encoder);
+    let now = Utc::now().timestamp().max(0) as u64;
+    for (file, contents) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(now);
+        header.set_cksum();
+        archive.append_data(
+            &mut header,
+            format!("{}/{}", name, file),
+            contents.as_slice(),
+        )?;
+    }
+    archive.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// The last [`MAX_LOG_BYTES`] of the log with secrets removed, or why there
+/// isn't one.
+fn log_tail(path: &Path, secrets: &[String]) -> Vec<u8> {
+    let read = || -> std::io::Result<Vec<u8>> {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+        if len > MAX_LOG_BYTES {
+            file.seek(SeekFrom::Start(len - MAX_LOG_BYTES))?;
+        }
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        Ok(redact_log(&String::from_utf8_lossy(&contents), secrets).into_bytes())
+    };
+    read().unwrap_or_else(|e| format!("Could not read {}: {}\n", path.display(), e).into_bytes())
+}
+
+/// `log` with secrets replaced by [`REDACTED`]: the config's own
+/// (`secrets`), anything shaped like a provider's API key, bearer tokens,
+/// and values given for a key that names a secret, like `api_key=…` or
+/// `"token": "…"`.
+fn redact_log(log: &str, secrets: &[String]) -> String {
+    let mut log = log.to_string();
+    for secret in secrets.iter().filter(|s| s.len() >= MIN_SECRET_LEN) {
+        log = log.replace(secret.as_str(), REDACTED);
+    }
+
+    let mut redacted = String::with_capacity(log.len());
+    let mut rest = log.as_str();
+    while !rest.is_empty() {
+        let start = rest.find(is_word_char).unwrap_or(rest.len());
+        redacted.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c| !is_word_char(c)).unwrap_or(rest.len());
+        let (word, after) = rest.split_at(end);
+        rest = after;
+        if word.len() >= MIN_SECRET_LEN && KEY_PREFIXES.iter().any(|p| word.starts_with(p)) {
+            redacted.push_str(REDACTED);
+            continue;
+        }
+        redacted.push_str(word);
+
+        // What follows `Bearer ` or `api_key=` is the secret itself.
+        let separator = if word.eq_ignore_ascii_case("bearer") {
+            let spaces = rest.len() - rest.trim_start_matches(' ').len();
+            (spaces > 0).then_some(spaces)
+        } else if names_secret(word) {
+            key_separator(rest)
+        } else {
+            None
+        };
+        let Some(separator) = separator else {
+            continue;
+        };
+        redacted.push_str(&rest[..separator]);
+        rest = &rest[separator..];
+        let value = rest
+            .find(|c: char| c.is_whitespace() || "\"',;&)]}".contains(c))
+            .unwrap_or(rest.len());
+        if value > 0 {
+            redacted.push_str(REDACTED);
+            rest = &rest[value..];
+        }
+    }
+    redacted
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Whether `key` names a secret. Only the end counts, so `input_tokens`
+/// and the like are kept.
+fn names_secret(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    [
+        "api_key", "api-key", "apikey", "token", "secret", "password",
+    ]
+    .iter()
+    .any(|name| key.ends_with(name))
+}
+
+/// Length of the `=`, `: ` or `":"` that starts `rest`, if it joins a key
+/// to its value.
+fn key_separator(rest: &str) -> Option<usize> {
+    let len = rest
+        .find(|c: char| !matches!(c, ' ' | '"' | '\'' | '=' | ':'))
+        .unwrap_or(rest.len());
+    (rest[..len].matches(['=', ':']).count() == 1).then_some(len)
+}
+
+/// The config in effect, as the TUI would load it, without secrets.
+fn config(options: &Options) -> String {
+    match load_config(options).and_then(|config| config.to_redacted_toml()) {
+        Ok(toml) => toml,
+        Err(e) => format!("# Could not load config: {}\n", e),
+    }
+}
+
+/// The config as the TUI would load it for the report's project and profile.
+fn load_config(options: &Options) -> uniq_core::error::Result<UniqConfig> {
+    match &options.project {
+        Some(project) => UniqConfig::load_for_project(project, options.profile.as_deref()),
+        None => UniqConfig::load_profile(options.profile.as_deref()),
+    }
+}
+
+/// The project's session file as saved, so a corrupt one is kept as is.
+fn session(project: &Path) -> Option<Vec<u8>> {
+    std::fs::read(Session::path_for(project).ok()?).ok()
+}
+
+/// Start the sidecar, ask it for its health, and stop it again.
+async fn sidecar_report(options: &Options) -> String {
+    let mut report = format!("Sidecar directory: {}\n", options.sidecar_dir.display());
+    if !options.check_sidecar {
+        report.push_str("Health check skipped (--no-sidecar)\n");
+        return report;
+    }
+
+    let api_keys = load_config(options)
+        .map(|config| config.api_keys)
+        .unwrap_or_default();
+    let mut manager = SidecarManager::new(options.sidecar_dir.clone()).with_api_keys(&api_keys);
+    let check = async {
+        manager
+            .start()
+            .await
+            .context("Could not start the sidecar")?;
+        let client = SidecarClient::new(manager.base_url());
+        client.health().await.context("Health check failed")
+    };
+    match tokio::time::timeout(SIDECAR_TIMEOUT, check).await {
+        Ok(Ok(health)) => {
+            let _ = writeln!(report, "Status: {}", health.status);
+            let _ = writeln!(report, "Version: {}", health.version);
+            let _ = writeln!(report, "Anthropic API key found: {}", health.claude);
+            let _ = writeln!(report, "OpenAI API key found: {}", health.openai);
+        }
+        Ok(Err(e)) => {
+            let _ = writeln!(report, "Error: {:#}", e);
+        }
+        Err(_) => {
+            let _ = writeln!(
+                report,
+                "Error: no answer within {}s",
+                SIDECAR_TIMEOUT.as_secs()
+            );
+        }
+    }
+    if let Err(e) = manager.shutdown().await {
+        let _ = writeln!(report, "Shutdown failed: {:#}", e);
+    }
+    report
+}
+
+/// Versions and settings of what uniq runs on.
+fn environment(options: &Options) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "uniq: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(
+        report,
+        "OS: {} {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::consts::FAMILY
+    );
+    let _ = writeln!(report, "Generated: {}", Utc::now().to_rfc3339());
+    let _ = writeln!(
+        report,
+        "Profile: {}",
+        options.profile.as_deref().unwrap_or("(none)")
+    );
+    for name in [
+        "TERM",
+        "TERM_PROGRAM",
+        "COLORTERM",
+        "LANG",
+        "LC_ALL",
+        "SHELL",
+    ] {
+        let value = std::env::var(name).unwrap_or_default();
+        let _ = writeln!(report, "{}: {}", name, value);
+    }
+    report.push('\n');
+    for (program, args) in [
+        ("git", &["--version"][..]),
+        ("uv", &["--version"]),
+        ("python3", &["--version"]),
+    ] {
+        let _ = writeln!(report, "{}: {}", program, version(program, args));
+    }
+    report
+}
+
+/// First line `program args` prints, or why it couldn't be run.
+fn version(program: &str, args: &[&str]) -> String {
+    match Command::new(program).args(args).output() {
+        Ok(output) => {
+            let text = if output.stdout.is_empty() {
+                output.stderr
+            } else {
+                output.stdout
+            };
+            String::from_utf8_lossy(&text)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        }
+        Err(e) => format!("not available ({})", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_secrets_are_redacted() {
+        let secrets = ["my-own-secret-key".to_string(), "x".to_string()];
+        let log = [
+            "INFO loaded key my-own-secret-key from the config",
+            "DEBUG POST https://api.anthropic.com x-api-key=sk-ant-api03-abcdefgh123",
+            "DEBUG retrying with x-api-key: 0123456789",
+            "DEBUG headers: Authorization: Bearer eyJhbGciOi.J9.x-y",
+            r#"{"level":"WARN","fields":{"api_key":"abc123","input_tokens":1234}}"#,
+            "ERROR login failed password: hunter22, retrying",
+            "INFO used 1200 tokens on sk-",
+        ]
+        .join("\n");
+        let redacted = redact_log(&log, &secrets);
+        let lines: Vec<&str> = redacted.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "INFO loaded key <redacted> from the config",
+                "DEBUG POST https://api.anthropic.com x-api-key=<redacted>",
+                "DEBUG retrying with x-api-key: <redacted>",
+                "DEBUG headers: Authorization: Bearer <redacted>",
+                r#"{"level":"WARN","fields":{"api_key":"<redacted>","input_tokens":1234}}"#,
+                "ERROR login failed password: <redacted>, retrying",
+                "INFO used 1200 tokens on sk-",
+            ]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! The log file, rotated by size and date so trace-level runs don't fill
+//! the disk. `uniq.log` is always the current file; rotated ones are
+//! `uniq.log.1` (newest) to `uniq.log.<keep>`.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDate};
+use uniq_core::config::LogConfig;
+
+/// A log file that moves itself aside when it gets too big or a new day
+/// starts.
+pub struct RotatingLog {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    /// Day of the first write to the current file.
+    day: NaiveDate,
+    max_size: u64,
+    daily: bool,
+    keep: usize,
+}
+
+impl RotatingLog {
+    /// Open the log at `path` for appending, rotating it first if it's
+    /// already due.
+    pub fn open(path: &Path, config: &LogConfig) -> io::Result<Self> {
+        let (file, size, day) = Self::open_file(path)?;
+        let mut log = Self {
+            path: path.to_path_buf(),
+            file,
+            size,
+            day,
+            max_size: config.max_size_mb * 1024 * 1024,
+            daily: config.daily,
+            keep: config.keep,
+        };
+        if log.due(0) {
+            log.rotate()?;
+        }
+        Ok(log)
+    }
+
+    /// The file at `path`, its size and the day it was last written.
+    fn open_file(path: &Path) -> io::Result<(File, u64, NaiveDate)> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let metadata = file.metadata()?;
+        let day = metadata
+            .modified()
+            .map(|time| DateTime::<Local>::from(time).date_naive())
+            .unwrap_or_else(|_| Local::now().date_naive());
+        Ok((file, metadata.len(), day))
+    }
+
+    /// Whether the file should be rotated before writing `len` more bytes.
+    /// An empty file never is, so one huge write can't rotate forever.
+    fn due(&self, len: usize) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        let too_big = self.max_size > 0 && self.size + len as u64 > self.max_size;
+        let new_day = self.daily && Local::now().date_naive() != self.day;
+        too_big || new_day
+    }
+
+    /// Shift `uniq.log.N` to `.N+1`, dropping the ones past `keep`, move
+    /// the current file to `.1` and start a new one.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let _ = std::fs::remove_file(self.rotated(self.keep));
+        for n in (1..self.keep).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                std::fs::rename(from, self.rotated(n + 1))?;
+            }
+        }
+        if self.keep > 0 {
+            std::fs::rename(&self.path, self.rotated(1))?;
+        } else {
+            std::fs::remove_file(&self.path)?;
+        }
+        let (file, size, _) = Self::open_file(&self.path)?;
+        self.file = file;
+        self.size = size;
+        self.day = Local::now().date_naive();
+        Ok(())
+    }
+
+    /// Path of the `n`th rotated file.
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+}
+
+impl Write for RotatingLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.due(buf.len()) {
+            self.rotate()?;
+        }
+        if self.size == 0 {
+            self.day = Local::now().date_naive();
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("uniq-log-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn read(path: PathBuf) -> String {
+        std::fs::read_to_string(path).unwrap_or_default()
+    }
+
+    #[test]
+    fn rotates_by_size_and_keeps_the_newest() {
+        let dir = log_dir("size");
+        let path = dir.join("uniq.log");
+        let config = LogConfig {
+            max_size_mb: 0,
+            daily: false,
+            keep: 2,
+            ..Default::default()
+        };
+        let mut log = RotatingLog::open(&path, &config).unwrap();
+        log.max_size = 10;
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            log.write_all(line.as_bytes()).unwrap();
+        }
+        assert_eq!(read(dir.join("uniq.log")), "fourth\n");
+        assert_eq!(read(dir.join("uniq.log.1")), "third\n");
+        assert_eq!(read(dir.join("uniq.log.2")), "second\n");
+        assert!(!dir.join("uniq.log.3").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rotates_a_file_from_an_earlier_day() {
+        let dir = log_dir("daily");
+        let path = dir.join("uniq.log");
+        let config = LogConfig {
+            max_size_mb: 0,
+            daily: true,
+            keep: 3,
+            ..Default::default()
+        };
+        let mut log = RotatingLog::open(&path, &config).unwrap();
+        log.write_all(b"yesterday\n").unwrap();
+        log.day = log.day.pred_opt().unwrap();
+        log.write_all(b"today\n").unwrap();
+        log.write_all(b"still today\n").unwrap();
+        assert_eq!(read(dir.join("uniq.log")), "today\nstill today\n");
+        assert_eq!(read(dir.join("uniq.log.1")), "yesterday\n");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
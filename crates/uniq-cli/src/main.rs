@@ -1,6 +1,13 @@
-use anyhow::Result;
-use clap::Parser;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
 use tracing_subscriber::EnvFilter;
+use uniq_core::config::LogFormat;
+
+mod bug_report;
+mod logging;
+mod report;
 
 /// uniq — Research-driven AI technique discovery and implementation engine.
 ///
@@ -11,26 +18,87 @@ use tracing_subscriber::EnvFilter;
 #[command(name = "uniq", version, about)]
 struct Cli {
     /// Path to the project to analyze (can also be set in the TUI).
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     project: Option<String>,
 
     /// Description of what AI capability to add (can also be set in the TUI).
     #[arg(short, long)]
     description: Option<String>,
 
-    /// Path to the sidecar directory (defaults to ./sidecar relative to the binary).
-    #[arg(long)]
+    /// Path to the sidecar directory (defaults to the configured one, or a
+    /// `sidecar` directory found beside the working directory or binary).
+    #[arg(long, global = true)]
     sidecar_dir: Option<String>,
 
+    /// Config profile to use, from the `[profile.<name>]` tables in the
+    /// config file.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     /// Increase logging verbosity (-v, -vv, -vvv).
-    #[arg(short, long, action = clap::ArgAction::Count)]
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
     verbose: u8,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Collect the log, config (without API keys), the project's session,
+    /// sidecar health and environment details into an archive to attach to
+    /// a bug report.
+    BugReport {
+        /// Where to write the archive (default:
+        /// `uniq-bug-report-<time>.tar.gz` in the working directory).
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Don't start the sidecar to check its health.
+        #[arg(long)]
+        no_sidecar: bool,
+    },
+
+    /// Write a report on the project's saved session: the literature
+    /// reviewed, techniques, variants, benchmark results and merges.
+    Report {
+        /// Where to write the report (default: a timestamped file in the
+        /// exports directory).
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Report format (default: from the output's extension, else
+        /// Markdown).
+        #[arg(short, long, value_enum)]
+        format: Option<report::Format>,
+    },
+
+    /// Print the JSON Schemas of the sidecar protocol, which the sidecar's
+    /// Pydantic models are checked against.
+    Schema {
+        /// Write the schemas to this file instead of standard output.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // Load config. Without a config file, first-run setup runs in the TUI.
+    let first_run = !uniq_core::UniqConfig::exists();
+    let config = match uniq_core::UniqConfig::load_profile(cli.profile.as_deref()) {
+        Ok(config) => config,
+        // Carrying on without the profile asked for could use the wrong
+        // keys or models. A bug report goes ahead, with the error in it.
+        Err(e) if cli.profile.is_some() && cli.command.is_none() => return Err(e.into()),
+        Err(e) => {
+            eprintln!("Warning: Failed to load config: {}. Using defaults.", e);
+            uniq_core::UniqConfig::default()
+        }
+    };
+
     // Set up logging.
     let filter = match cli.verbose {
         0 => "warn",
@@ -41,25 +109,22 @@ async fn main() -> Result<()> {
     // Log to a file to avoid corrupting the TUI output. If the log file
     // can't be opened, silently discard logs rather than polluting the
     // alternate screen buffer.
-    let log_dir = dirs::cache_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("uniq");
-    let _ = std::fs::create_dir_all(&log_dir);
-    let log_path = log_dir.join("uniq.log");
-    let log_file = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_path);
-
-    match log_file {
+    let log_path = log_path();
+    if let Some(log_dir) = log_path.parent() {
+        let _ = std::fs::create_dir_all(log_dir);
+    }
+    match logging::RotatingLog::open(&log_path, &config.log) {
         Ok(file) => {
-            tracing_subscriber::fmt()
+            let subscriber = tracing_subscriber::fmt()
                 .with_env_filter(
                     EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(filter)),
                 )
                 .with_writer(std::sync::Mutex::new(file))
-                .with_ansi(false)
-                .init();
+                .with_ansi(false);
+            match config.log.format {
+                LogFormat::Text => subscriber.init(),
+                LogFormat::Json => subscriber.json().init(),
+            }
         }
         Err(_) => {
             // Fallback: discard all logs to avoid TUI corruption.
@@ -70,24 +135,49 @@ async fn main() -> Result<()> {
         }
     }
 
-    // Load config.
-    let _config = uniq_core::UniqConfig::load().unwrap_or_else(|e| {
-        eprintln!("Warning: Failed to load config: {}. Using defaults.", e);
-        uniq_core::UniqConfig::default()
-    });
+    if let Some(Command::Report { output, format }) = cli.command {
+        let project = PathBuf::from(cli.project.as_deref().unwrap_or("."));
+        let path = report::run(&project, output, format)?;
+        println!("Wrote {}", path.display());
+        return Ok(());
+    }
+
+    if let Some(Command::Schema { output }) = cli.command {
+        let schema = uniq_sidecar::schema::protocol_schema_json();
+        match output {
+            Some(path) => {
+                std::fs::write(&path, schema)
+                    .with_context(|| format!("Could not write {}", path.display()))?;
+                println!("Wrote {}", path.display());
+            }
+            None => print!("{}", schema),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::BugReport { output, no_sidecar }) = cli.command {
+        let options = bug_report::Options {
+            output,
+            project: cli.project.as_ref().map(PathBuf::from),
+            sidecar_dir: sidecar_dir(cli.sidecar_dir.as_deref(), &config)?,
+            profile: cli.profile,
+            log_path,
+            check_sidecar: !no_sidecar,
+        };
+        let path = bug_report::run(options).await?;
+        println!("Wrote {}", path.display());
+        println!(
+            "API keys and tokens are removed from the config and log; look over the rest before sharing it."
+        );
+        return Ok(());
+    }
 
     tracing::info!("Starting uniq v{}", env!("CARGO_PKG_VERSION"));
 
-    // Determine sidecar directory.
-    let sidecar_dir = if let Some(ref dir) = cli.sidecar_dir {
-        std::path::PathBuf::from(dir)
-    } else {
-        // Default: look for ./sidecar relative to current directory.
-        std::env::current_dir()?.join("sidecar")
-    };
+    let sidecar_dir = sidecar_dir(cli.sidecar_dir.as_deref(), &config)?;
 
     // Start the TUI.
-    let mut app = uniq_tui::App::new(sidecar_dir);
+    let mut app = uniq_tui::App::new(sidecar_dir, config);
 
     // Pre-fill project info from CLI args if provided.
     if let Some(ref project) = cli.project {
@@ -96,9 +186,33 @@ async fn main() -> Result<()> {
     if let Some(ref description) = cli.description {
         app.set_initial_description(description.clone());
     }
+    if first_run {
+        app.start_onboarding();
+    }
 
     app.run().await?;
 
     tracing::info!("uniq exited cleanly");
     Ok(())
 }
+
+/// The log file, `~/.cache/uniq/uniq.log` on Linux.
+fn log_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("uniq")
+        .join("uniq.log")
+}
+
+/// Sidecar directory: the flag, then the configured one, then wherever one
+/// can be found, defaulting to ./sidecar.
+fn sidecar_dir(flag: Option<&str>, config: &uniq_core::UniqConfig) -> Result<PathBuf> {
+    Ok(match (flag, &config.sidecar.dir) {
+        (Some(dir), _) => PathBuf::from(dir),
+        (None, Some(dir)) => dir.clone(),
+        (None, None) => match uniq_sidecar::manager::detect_sidecar_dir() {
+            Some(dir) => dir,
+            None => std::env::current_dir()?.join("sidecar"),
+        },
+    })
+}
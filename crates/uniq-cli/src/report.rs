@@ -0,0 +1,46 @@
+//! `uniq report` — writes the report on a project's saved session without
+//! opening the TUI, e.g. to attach to a PR or send to the team.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use uniq_core::report::{self, ReportFormat};
+use uniq_core::session::Session;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+/// Write the report on `project`'s session to `output`, or to the exports
+/// directory, and return where it went. Without a `format`, it's taken from
+/// `output`'s extension, defaulting to Markdown.
+pub fn run(project: &Path, output: Option<PathBuf>, format: Option<Format>) -> Result<PathBuf> {
+    let session = Session::load(project)?.ok_or_else(|| {
+        anyhow!(
+            "No saved session for {}; run uniq on it first",
+            project.display()
+        )
+    })?;
+    let format = match format {
+        Some(Format::Markdown) => ReportFormat::Markdown,
+        Some(Format::Html) => ReportFormat::Html,
+        None => match output.as_deref().and_then(Path::extension) {
+            Some(ext) if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") => {
+                ReportFormat::Html
+            }
+            _ => ReportFormat::Markdown,
+        },
+    };
+    match output {
+        Some(path) => {
+            let contents = report::render(&session, &report::diff_stats(&session), format);
+            std::fs::write(&path, contents)
+                .with_context(|| format!("Could not write {}", path.display()))?;
+            Ok(path)
+        }
+        None => Ok(report::export_report(&session, format)?),
+    }
+}
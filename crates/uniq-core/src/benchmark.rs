@@ -1,8 +1,12 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub mod runner;
+
 /// Individual metric result.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MetricValue {
     pub name: String,
     pub value: f64,
@@ -11,7 +15,7 @@ pub struct MetricValue {
 }
 
 /// Results from automated code execution.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ExecutionMetrics {
     /// Whether the project built/compiled successfully.
     pub build_success: bool,
@@ -19,7 +23,7 @@ pub struct ExecutionMetrics {
     /// Build error output if failed.
     pub build_error: Option<String>,
 
-    /// Test pass rate (0.0 - 1.0).
+    /// Test pass rate (0.0 - 1.0), leaving out `flaky_tests`.
     pub test_pass_rate: Option<f64>,
 
     /// Number of tests passed / total.
@@ -34,10 +38,102 @@ pub struct ExecutionMetrics {
 
     /// Custom metrics (model accuracy, F1, RMSE, etc.).
     pub custom_metrics: HashMap<String, MetricValue>,
+
+    /// Runtime of each repetition, when the variant was run more than once.
+    #[serde(default)]
+    pub runtime_samples: Vec<f64>,
+
+    /// Peak memory of each repetition.
+    #[serde(default)]
+    pub memory_samples: Vec<f64>,
+
+    /// Standard deviation of `runtime_samples` (`runtime_ms` is their mean).
+    #[serde(default)]
+    pub runtime_std_ms: Option<f64>,
+
+    /// Standard deviation of `memory_samples` (`memory_mb` is their mean).
+    #[serde(default)]
+    pub memory_std_mb: Option<f64>,
+
+    /// Tests that both passed and failed across repeated runs.
+    #[serde(default)]
+    pub flaky_tests: Vec<String>,
+}
+
+/// How a variant's execution metrics compare with the unmodified project.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricDeltas {
+    /// Runtime change in percent (negative is faster).
+    pub runtime_pct: Option<f64>,
+    /// Peak memory change in percent (negative is leaner).
+    pub memory_pct: Option<f64>,
+    /// Test pass rate change in percentage points.
+    pub test_pass_rate_pts: Option<f64>,
+    /// Whether the runtime difference is larger than run-to-run noise.
+    /// `None` unless both sides were run more than once.
+    pub runtime_significant: Option<bool>,
+    /// Whether the memory difference is larger than run-to-run noise.
+    pub memory_significant: Option<bool>,
+}
+
+/// Sample mean and, with two or more samples, standard deviation.
+pub fn mean_std(samples: &[f64]) -> Option<(f64, Option<f64>)> {
+    if samples.is_empty() {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let std = (samples.len() > 1).then(|| {
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        var.sqrt()
+    });
+    Some((mean, std))
+}
+
+/// Welch's t statistic above which two sample sets are treated as really
+/// different (roughly 95% confidence for a handful of samples).
+const SIGNIFICANT_T: f64 = 2.0;
+
+/// Whether the means of `a` and `b` differ by more than their noise.
+fn differs_significantly(a: &[f64], b: &[f64]) -> Option<bool> {
+    let (mean_a, std_a) = mean_std(a)?;
+    let (mean_b, std_b) = mean_std(b)?;
+    let se = (std_a?.powi(2) / a.len() as f64 + std_b?.powi(2) / b.len() as f64).sqrt();
+    if se == 0.0 {
+        return Some(mean_a != mean_b);
+    }
+    Some(((mean_a - mean_b) / se).abs() > SIGNIFICANT_T)
+}
+
+impl ExecutionMetrics {
+    /// Deltas of these metrics against `baseline`. A delta is `None` when
+    /// either side lacks the metric.
+    pub fn deltas_from(&self, baseline: &ExecutionMetrics) -> MetricDeltas {
+        let pct = |value: Option<f64>, base: Option<f64>| match (value, base) {
+            (Some(value), Some(base)) if base > 0.0 => Some((value - base) / base * 100.0),
+            _ => None,
+        };
+        MetricDeltas {
+            runtime_pct: pct(self.runtime_ms, baseline.runtime_ms),
+            memory_pct: pct(self.memory_mb, baseline.memory_mb),
+            test_pass_rate_pts: self
+                .test_pass_rate
+                .zip(baseline.test_pass_rate)
+                .map(|(rate, base)| (rate - base) * 100.0),
+            runtime_significant: differs_significantly(
+                &self.runtime_samples,
+                &baseline.runtime_samples,
+            ),
+            memory_significant: differs_significantly(
+                &self.memory_samples,
+                &baseline.memory_samples,
+            ),
+        }
+    }
 }
 
 /// Scores from LLM-as-judge evaluation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct JudgeScores {
     /// Code quality (0-10).
     pub code_quality: f64,
@@ -59,6 +155,126 @@ pub struct JudgeScores {
 
     /// Free-form explanation from the judge.
     pub explanation: String,
+
+    /// Number of judge samples aggregated into these scores.
+    #[serde(default = "default_judge_samples")]
+    pub samples: u32,
+
+    /// Range (max - min) of the samples' overall scores.
+    #[serde(default)]
+    pub spread: f64,
+
+    /// Whether the samples disagreed enough that the ranking is unreliable.
+    #[serde(default)]
+    pub disagreement: bool,
+}
+
+fn default_judge_samples() -> u32 {
+    1
+}
+
+/// How scores from several judge samples are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JudgeAggregate {
+    #[default]
+    Mean,
+    Median,
+}
+
+/// How the judge evaluates variants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JudgeMode {
+    /// Score each variant on its own, 0-10.
+    #[default]
+    Absolute,
+    /// Compare every pair of variants and rank them by Elo rating.
+    Pairwise,
+}
+
+/// Which judges score each variant. Every model in `models` (or the
+/// sidecar's default model, if empty) is sampled `samples` times.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JudgeEnsemble {
+    #[serde(default)]
+    pub models: Vec<String>,
+    #[serde(default = "default_judge_samples")]
+    pub samples: u32,
+    #[serde(default)]
+    pub aggregate: JudgeAggregate,
+    #[serde(default)]
+    pub mode: JudgeMode,
+}
+
+impl Default for JudgeEnsemble {
+    fn default() -> Self {
+        Self {
+            models: Vec::new(),
+            samples: default_judge_samples(),
+            aggregate: JudgeAggregate::default(),
+            mode: JudgeMode::default(),
+        }
+    }
+}
+
+/// Which side of a pairwise comparison the judge preferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairwiseWinner {
+    A,
+    B,
+    Tie,
+}
+
+/// The judge's verdict on one pair of variants.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PairwiseComparison {
+    pub branch_a: String,
+    pub branch_b: String,
+    pub winner: PairwiseWinner,
+    #[serde(default)]
+    pub explanation: String,
+}
+
+/// Rating every variant starts a tournament with.
+pub const ELO_INITIAL: f64 = 1500.0;
+
+/// How far one comparison moves a rating.
+const ELO_K: f64 = 32.0;
+
+/// Elo ratings of `branches` after playing `comparisons` in order.
+/// Comparisons involving other branches are ignored.
+pub fn elo_ratings(
+    branches: &[String],
+    comparisons: &[PairwiseComparison],
+) -> HashMap<String, f64> {
+    let mut ratings: HashMap<String, f64> =
+        branches.iter().map(|b| (b.clone(), ELO_INITIAL)).collect();
+    for comparison in comparisons {
+        let (Some(&a), Some(&b)) = (
+            ratings.get(&comparison.branch_a),
+            ratings.get(&comparison.branch_b),
+        ) else {
+            continue;
+        };
+        let expected_a = 1.0 / (1.0 + 10f64.powf((b - a) / 400.0));
+        let score_a = match comparison.winner {
+            PairwiseWinner::A => 1.0,
+            PairwiseWinner::B => 0.0,
+            PairwiseWinner::Tie => 0.5,
+        };
+        let change = ELO_K * (score_a - expected_a);
+        ratings.insert(comparison.branch_a.clone(), a + change);
+        ratings.insert(comparison.branch_b.clone(), b - change);
+    }
+    ratings
+}
+
+/// An Elo rating on a 0-100 scale: the chance of winning a comparison
+/// against a variant with the initial rating.
+pub fn elo_score(elo: f64) -> f64 {
+    100.0 / (1.0 + 10f64.powf((ELO_INITIAL - elo) / 400.0))
 }
 
 /// User-provided rating for a variant.
@@ -71,8 +287,52 @@ pub struct UserRating {
     pub notes: String,
 }
 
-/// Complete benchmark results for a single variant.
+/// Results of one completed benchmark run of a variant.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRun {
+    pub at: DateTime<Utc>,
+    pub results: BenchmarkResults,
+}
+
+/// Where a variant is in a benchmark run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkStage {
+    Queued,
+    Building,
+    Testing,
+    /// Running the configured run command.
+    Running,
+    Judging,
+    Done,
+    /// The benchmark itself errored; no results were recorded.
+    Failed,
+}
+
+impl BenchmarkStage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BenchmarkStage::Queued => "Queued",
+            BenchmarkStage::Building => "Building",
+            BenchmarkStage::Testing => "Testing",
+            BenchmarkStage::Running => "Running",
+            BenchmarkStage::Judging => "Judging",
+            BenchmarkStage::Done => "Done",
+            BenchmarkStage::Failed => "Failed",
+        }
+    }
+
+    /// Whether the variant is actively being worked on.
+    pub fn is_active(&self) -> bool {
+        !matches!(
+            self,
+            BenchmarkStage::Queued | BenchmarkStage::Done | BenchmarkStage::Failed
+        )
+    }
+}
+
+/// Complete benchmark results for a single variant.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BenchmarkResults {
     /// Automated execution metrics.
     pub execution: Option<ExecutionMetrics>,
@@ -85,52 +345,247 @@ pub struct BenchmarkResults {
 
     /// Composite score (weighted combination of all available scores, 0-100).
     pub composite_score: Option<f64>,
+
+    /// Elo rating from the latest pairwise tournament.
+    #[serde(default)]
+    pub elo: Option<f64>,
 }
 
-impl BenchmarkResults {
-    /// Calculate the composite score from available sub-scores.
-    pub fn compute_composite(&mut self) {
-        let mut total = 0.0;
-        let mut weight = 0.0;
+/// Relative weight of each score family in the composite. Families a
+/// variant has no score for are left out and the rest rescaled.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreWeights {
+    #[serde(default = "default_execution_weight")]
+    pub execution: f64,
+    #[serde(default = "default_judge_weight")]
+    pub judge: f64,
+    #[serde(default = "default_user_weight")]
+    pub user: f64,
+}
 
-        // Execution: 40% weight
-        if let Some(ref exec) = self.execution {
-            let mut exec_score = 0.0;
-            if exec.build_success {
-                exec_score += 30.0;
-            }
-            if let Some(rate) = exec.test_pass_rate {
-                exec_score += rate * 70.0;
-            }
-            total += exec_score * 0.4;
-            weight += 0.4;
-        }
+fn default_execution_weight() -> f64 {
+    0.4
+}
+fn default_judge_weight() -> f64 {
+    0.4
+}
+fn default_user_weight() -> f64 {
+    0.2
+}
 
-        // Judge: 40% weight
-        if let Some(ref judge) = self.judge {
-            total += (judge.overall / 10.0) * 100.0 * 0.4;
-            weight += 0.4;
+impl ScoreWeights {
+    /// Check that every weight is a non-negative number and that they
+    /// don't all come to zero, which would leave no composite at all.
+    pub fn validate(&self) -> crate::error::Result<()> {
+        let weights = [
+            ("execution", self.execution),
+            ("judge", self.judge),
+            ("user", self.user),
+        ];
+        if let Some((name, weight)) = weights.iter().find(|(_, w)| !w.is_finite() || *w < 0.0) {
+            return Err(crate::error::UniqError::Config(format!(
+                "Score weight {name} must be a number of at least 0, not {weight}"
+            )));
+        }
+        if weights.iter().all(|(_, w)| *w == 0.0) {
+            return Err(crate::error::UniqError::Config(
+                "Score weights can't all be 0".into(),
+            ));
         }
+        Ok(())
+    }
+}
 
-        // User: 20% weight
-        if let Some(ref user) = self.user_rating {
-            total += (user.stars as f64 / 5.0) * 100.0 * 0.2;
-            weight += 0.2;
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            execution: default_execution_weight(),
+            judge: default_judge_weight(),
+            user: default_user_weight(),
         }
+    }
+}
 
+/// One family's contribution to a composite score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreComponent {
+    pub name: &'static str,
+    /// Score on a 0-100 scale, or `None` if the variant has none yet.
+    pub score: Option<f64>,
+    /// Configured weight.
+    pub weight: f64,
+}
+
+impl BenchmarkResults {
+    /// Each score family on a 0-100 scale with its weight.
+    pub fn breakdown(&self, weights: &ScoreWeights) -> Vec<ScoreComponent> {
+        let execution = self.execution.as_ref().map(|exec| {
+            let build = if exec.build_success { 30.0 } else { 0.0 };
+            build + exec.test_pass_rate.unwrap_or(0.0) * 70.0
+        });
+        vec![
+            ScoreComponent {
+                name: "Execution",
+                score: execution,
+                weight: weights.execution,
+            },
+            ScoreComponent {
+                name: "Judge",
+                // Pairwise judging rates variants against each other instead
+                // of scoring them, so their Elo stands in.
+                score: self
+                    .judge
+                    .as_ref()
+                    .map(|j| j.overall * 10.0)
+                    .or_else(|| self.elo.map(elo_score)),
+                weight: weights.judge,
+            },
+            ScoreComponent {
+                name: "User",
+                score: self
+                    .user_rating
+                    .as_ref()
+                    .map(|u| u.stars as f64 / 5.0 * 100.0),
+                weight: weights.user,
+            },
+        ]
+    }
+
+    /// Calculate the composite score from available sub-scores.
+    pub fn compute_composite(&mut self, weights: &ScoreWeights) {
+        let mut total = 0.0;
+        let mut weight = 0.0;
+        for component in self.breakdown(weights) {
+            if let Some(score) = component.score {
+                total += score * component.weight;
+                weight += component.weight;
+            }
+        }
         if weight > 0.0 {
             self.composite_score = Some(total / weight);
         }
     }
 }
 
-impl Default for BenchmarkResults {
-    fn default() -> Self {
-        Self {
-            execution: None,
-            judge: None,
-            user_rating: None,
-            composite_score: None,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(
+        runtime_ms: Option<f64>,
+        memory_mb: Option<f64>,
+        rate: Option<f64>,
+    ) -> ExecutionMetrics {
+        ExecutionMetrics {
+            build_success: true,
+            build_error: None,
+            test_pass_rate: rate,
+            tests_passed: None,
+            tests_total: None,
+            runtime_ms,
+            memory_mb,
+            custom_metrics: HashMap::new(),
+            runtime_samples: Vec::new(),
+            memory_samples: Vec::new(),
+            runtime_std_ms: None,
+            memory_std_mb: None,
+            flaky_tests: Vec::new(),
         }
     }
+
+    #[test]
+    fn test_deltas_from_baseline() {
+        let baseline = metrics(Some(200.0), Some(0.0), Some(0.8));
+        let variant = metrics(Some(150.0), Some(64.0), Some(1.0));
+        let deltas = variant.deltas_from(&baseline);
+        assert_eq!(deltas.runtime_pct, Some(-25.0));
+        assert_eq!(deltas.memory_pct, None);
+        assert!((deltas.test_pass_rate_pts.unwrap() - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_significance_needs_repetitions() {
+        let mut baseline = metrics(Some(100.0), None, None);
+        let mut variant = metrics(Some(101.0), None, None);
+        assert_eq!(variant.deltas_from(&baseline).runtime_significant, None);
+
+        baseline.runtime_samples = vec![98.0, 100.0, 102.0];
+        variant.runtime_samples = vec![99.0, 101.0, 103.0];
+        assert_eq!(
+            variant.deltas_from(&baseline).runtime_significant,
+            Some(false)
+        );
+
+        variant.runtime_samples = vec![150.0, 151.0, 152.0];
+        assert_eq!(
+            variant.deltas_from(&baseline).runtime_significant,
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_elo_ratings() {
+        let branches = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let play = |a: &str, b: &str, winner| PairwiseComparison {
+            branch_a: a.into(),
+            branch_b: b.into(),
+            winner,
+            explanation: String::new(),
+        };
+        let ratings = elo_ratings(
+            &branches,
+            &[
+                play("a", "b", PairwiseWinner::A),
+                play("a", "c", PairwiseWinner::A),
+                play("b", "c", PairwiseWinner::Tie),
+                play("a", "z", PairwiseWinner::B),
+            ],
+        );
+        assert_eq!(ratings.len(), 3);
+        assert!(ratings["a"] > ratings["b"] && ratings["a"] > ratings["c"]);
+        let total: f64 = ratings.values().sum();
+        assert!((total - 3.0 * ELO_INITIAL).abs() < 1e-9);
+    }
+
+    #[test]
+    fn score_weights_must_be_non_negative_and_not_all_zero() {
+        assert!(ScoreWeights::default().validate().is_ok());
+        let only_execution = ScoreWeights {
+            execution: 1.0,
+            judge: 0.0,
+            user: 0.0,
+        };
+        assert!(only_execution.validate().is_ok());
+        for weights in [
+            ScoreWeights {
+                judge: -0.5,
+                ..only_execution
+            },
+            ScoreWeights {
+                execution: f64::NAN,
+                ..only_execution
+            },
+            ScoreWeights {
+                execution: 0.0,
+                ..only_execution
+            },
+        ] {
+            assert!(weights.validate().is_err(), "{weights:?}");
+        }
+    }
+
+    #[test]
+    fn elo_stands_in_for_judge_scores() {
+        let weights = ScoreWeights::default();
+        let mut results = BenchmarkResults {
+            elo: Some(ELO_INITIAL),
+            ..Default::default()
+        };
+        results.compute_composite(&weights);
+        assert_eq!(results.composite_score, Some(50.0));
+
+        results.elo = Some(ELO_INITIAL + 400.0);
+        results.compute_composite(&weights);
+        assert!((results.composite_score.unwrap() - 100.0 / 1.1).abs() < 1e-9);
+    }
 }
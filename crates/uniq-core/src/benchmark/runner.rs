@@ -0,0 +1,718 @@
+//! Native execution benchmarks: build, test and run a variant in its own
+//! worktree, without the Python sidecar.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::benchmark::{mean_std, BenchmarkStage, ExecutionMetrics, MetricValue};
+use crate::config::BenchmarkConfig;
+use crate::error::{Result, UniqError};
+use crate::git::TempWorktree;
+
+/// Captured output is cut to this many trailing bytes.
+const MAX_OUTPUT: usize = 4000;
+
+/// How often a running command is checked for exit and memory use.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often GPU use is sampled. `nvidia-smi` is too slow to call at
+/// `POLL_INTERVAL`.
+const GPU_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Shell commands used to benchmark a variant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BenchmarkCommands {
+    pub build: Option<String>,
+    pub test: Option<String>,
+    pub run: Option<String>,
+}
+
+impl BenchmarkCommands {
+    /// Commands from `config`, falling back to ones detected from the
+    /// manifest in `project_dir`.
+    pub fn resolve(config: &BenchmarkConfig, project_dir: &Path) -> Self {
+        Self {
+            build: config
+                .build_command
+                .clone()
+                .or_else(|| detect_build_command(project_dir)),
+            test: config
+                .test_command
+                .clone()
+                .or_else(|| detect_test_command(project_dir)),
+            run: config.run_command.clone(),
+        }
+    }
+}
+
+fn detect_build_command(dir: &Path) -> Option<String> {
+    let command = if dir.join("Cargo.toml").exists() {
+        "cargo build"
+    } else if dir.join("package.json").exists() {
+        "npm run build"
+    } else if dir.join("pyproject.toml").exists() {
+        "uv run python -m compileall -q ."
+    } else if dir.join("Makefile").exists() {
+        "make"
+    } else if dir.join("go.mod").exists() {
+        "go build ./..."
+    } else {
+        return None;
+    };
+    Some(command.to_string())
+}
+
+fn detect_test_command(dir: &Path) -> Option<String> {
+    let command = if dir.join("Cargo.toml").exists() {
+        "cargo test"
+    } else if dir.join("package.json").exists() {
+        "npm test"
+    } else if dir.join("pyproject.toml").exists() {
+        "uv run pytest"
+    } else if dir.join("Makefile").exists() {
+        "make test"
+    } else if dir.join("go.mod").exists() {
+        "go test ./..."
+    } else {
+        return None;
+    };
+    Some(command.to_string())
+}
+
+/// Outcome of one command.
+#[derive(Debug, Clone)]
+pub struct CommandRun {
+    pub command: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub duration_ms: f64,
+    /// Peak resident memory of the command's process group (Linux only).
+    pub peak_memory_mb: Option<f64>,
+    /// Peak GPU usage while the command ran, when an NVIDIA GPU is present.
+    pub gpu: Option<GpuUsage>,
+    /// Tail of the captured stdout.
+    pub stdout: String,
+    /// Tail of the captured stderr.
+    pub stderr: String,
+}
+
+impl CommandRun {
+    /// Captured stdout and stderr together.
+    pub fn output(&self) -> String {
+        format!("{}\n{}", self.stdout, self.stderr)
+    }
+
+    /// Short description of why the command failed.
+    pub fn failure(&self) -> String {
+        if self.timed_out {
+            return format!("`{}` timed out", self.command);
+        }
+        let output = if self.stderr.trim().is_empty() {
+            &self.stdout
+        } else {
+            &self.stderr
+        };
+        tail(output.trim(), 500).to_string()
+    }
+}
+
+/// Peak GPU utilization and memory seen while a command ran. Measured
+/// GPU-wide, so other work on the same GPUs is counted too.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GpuUsage {
+    /// Highest utilization of any GPU, in percent.
+    pub utilization_pct: f64,
+    /// Highest VRAM use across all GPUs above what was in use at the start.
+    pub vram_mb: f64,
+}
+
+/// Everything the runner did for one variant.
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    pub build: Option<CommandRun>,
+    pub test: Option<CommandRun>,
+    pub run: Option<CommandRun>,
+    /// Further runs of the tests when repetitions are enabled.
+    pub test_repeats: Vec<CommandRun>,
+    /// Further runs of the run command when repetitions are enabled.
+    pub run_repeats: Vec<CommandRun>,
+}
+
+impl RunReport {
+    /// Every run of the run command, whose time is the runtime. Without a
+    /// run command there's no runtime: build and test times say nothing
+    /// about how fast the variant itself is.
+    fn runtime_runs(&self) -> Vec<&CommandRun> {
+        self.run.iter().chain(&self.run_repeats).collect()
+    }
+
+    /// Summarise as execution metrics, in the same shape the sidecar reports.
+    pub fn to_metrics(&self) -> ExecutionMetrics {
+        let build_success = self.build.as_ref().is_none_or(|b| b.success);
+        let test_output = self.test.as_ref().map(CommandRun::output);
+        let (tests_passed, tests_total) =
+            test_output.as_deref().and_then(parse_test_counts).unzip();
+
+        let outputs: Vec<String> = self
+            .test
+            .iter()
+            .chain(&self.test_repeats)
+            .filter(|t| !t.timed_out)
+            .map(CommandRun::output)
+            .collect();
+        let flaky_tests = flaky_tests(&outputs);
+
+        // Flaky tests are left out of the pass rate, whichever way they
+        // went in the first run.
+        let test_pass_rate = match (tests_passed, tests_total) {
+            (Some(passed), Some(total)) => {
+                let failed_first = test_output
+                    .as_deref()
+                    .map(parse_failed_tests)
+                    .unwrap_or_default();
+                let flaky_failed = flaky_tests
+                    .iter()
+                    .filter(|t| failed_first.contains(*t))
+                    .count() as u32;
+                let flaky_passed = flaky_tests.len() as u32 - flaky_failed;
+                let passed = passed.saturating_sub(flaky_passed);
+                let total = total.saturating_sub(flaky_tests.len() as u32);
+                (total > 0).then(|| passed as f64 / total as f64)
+            }
+            _ => self
+                .test
+                .as_ref()
+                .map(|t| if t.success { 1.0 } else { 0.0 }),
+        };
+
+        let commands = [&self.build, &self.test, &self.run];
+        let mut memory_mb = commands
+            .iter()
+            .filter_map(|c| c.as_ref()?.peak_memory_mb)
+            .reduce(f64::max);
+        let mut runtime_ms = self.run.as_ref().map(|c| c.duration_ms);
+        let gpu = commands
+            .iter()
+            .filter_map(|c| c.as_ref()?.gpu)
+            .chain(
+                self.test_repeats
+                    .iter()
+                    .chain(&self.run_repeats)
+                    .filter_map(|c| c.gpu),
+            )
+            .reduce(|a, b| GpuUsage {
+                utilization_pct: a.utilization_pct.max(b.utilization_pct),
+                vram_mb: a.vram_mb.max(b.vram_mb),
+            });
+
+        // With repetitions, report the mean of the run command's runs.
+        let (mut runtime_samples, mut memory_samples) = (Vec::new(), Vec::new());
+        let (mut runtime_std_ms, mut memory_std_mb) = (None, None);
+        let runs = self.runtime_runs();
+        if runs.len() > 1 {
+            runtime_samples = runs.iter().map(|c| c.duration_ms).collect();
+            memory_samples = runs.iter().filter_map(|c| c.peak_memory_mb).collect();
+            if let Some((mean, std)) = mean_std(&runtime_samples) {
+                runtime_ms = Some(mean);
+                runtime_std_ms = std;
+            }
+            if let Some((mean, std)) = mean_std(&memory_samples) {
+                memory_mb = Some(mean);
+                memory_std_mb = std;
+            }
+        }
+
+        ExecutionMetrics {
+            build_success,
+            build_error: self
+                .build
+                .as_ref()
+                .filter(|b| !b.success)
+                .map(CommandRun::failure),
+            test_pass_rate,
+            tests_passed,
+            tests_total,
+            runtime_ms,
+            memory_mb,
+            custom_metrics: gpu.map(gpu_metrics).unwrap_or_default(),
+            runtime_samples,
+            memory_samples,
+            runtime_std_ms,
+            memory_std_mb,
+            flaky_tests,
+        }
+    }
+}
+
+fn gpu_metrics(gpu: GpuUsage) -> HashMap<String, MetricValue> {
+    let metric = |name: &str, value, unit: &str| MetricValue {
+        name: name.to_string(),
+        value,
+        unit: unit.to_string(),
+        higher_is_better: false,
+    };
+    HashMap::from([
+        (
+            "gpu_utilization".to_string(),
+            metric("GPU utilization", gpu.utilization_pct, "%"),
+        ),
+        ("gpu_vram".to_string(), metric("VRAM", gpu.vram_mb, "MB")),
+    ])
+}
+
+/// Build, test and run `branch` in a temporary worktree, reporting each
+/// stage to `on_stage` as it starts. `timeout` bounds the whole variant;
+/// later steps get whatever time is left. The tests and run command are
+/// repeated until each has run `config.repetitions` times, to measure
+/// spread and catch flaky tests.
+///
+/// Setting `cancel` kills the running command; [`UniqError::Cancelled`] is
+/// returned once the worktree has been removed.
+pub fn run_variant(
+    repo_path: &Path,
+    branch: &str,
+    config: &BenchmarkConfig,
+    timeout: Duration,
+    cancel: &AtomicBool,
+    mut on_stage: impl FnMut(BenchmarkStage),
+) -> Result<RunReport> {
+    let worktree = TempWorktree::create(repo_path, branch)?;
+    let commands = BenchmarkCommands::resolve(config, &worktree.path);
+    let deadline = Instant::now() + timeout;
+    let mut step = |stage, command: &Option<String>| {
+        let command = command.as_deref()?;
+        on_stage(stage);
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        Some(run_command(command, &worktree.path, remaining, cancel))
+    };
+    let cancelled = || {
+        if cancel.load(Ordering::Relaxed) {
+            Err(UniqError::Cancelled)
+        } else {
+            Ok(())
+        }
+    };
+
+    let mut report = RunReport {
+        build: step(BenchmarkStage::Building, &commands.build),
+        ..Default::default()
+    };
+    cancelled()?;
+    if report.build.as_ref().is_some_and(|b| !b.success) {
+        return Ok(report);
+    }
+    report.test = step(BenchmarkStage::Testing, &commands.test);
+    cancelled()?;
+    report.run = step(BenchmarkStage::Running, &commands.run);
+    cancelled()?;
+
+    for _ in 1..config.repetitions {
+        if Instant::now() >= deadline {
+            break;
+        }
+        // Failing tests are repeated too: that's how flakes show up.
+        if report.test.as_ref().is_some_and(|t| !t.timed_out) {
+            match step(BenchmarkStage::Testing, &commands.test) {
+                Some(run) if !run.timed_out => report.test_repeats.push(run),
+                _ => break,
+            }
+        }
+        // A failed or timed-out run would skew the mean, so stop there.
+        if report.run.as_ref().is_some_and(|r| r.success) {
+            match step(BenchmarkStage::Running, &commands.run) {
+                Some(run) if run.success => report.run_repeats.push(run),
+                _ => break,
+            }
+        }
+        cancelled()?;
+    }
+    Ok(report)
+}
+
+/// Run a shell command in `dir`, killing it after `timeout` or once
+/// `cancel` is set.
+pub fn run_command(
+    command: &str,
+    dir: &Path,
+    timeout: Duration,
+    cancel: &AtomicBool,
+) -> CommandRun {
+    let start = Instant::now();
+    let mut run = CommandRun {
+        command: command.to_string(),
+        success: false,
+        exit_code: None,
+        timed_out: false,
+        duration_ms: 0.0,
+        peak_memory_mb: None,
+        gpu: None,
+        stdout: String::new(),
+        stderr: String::new(),
+    };
+
+    let mut child = match shell(command)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            run.stderr = format!("Could not start command: {}", e);
+            return run;
+        }
+    };
+
+    // Drain the pipes on their own threads so a chatty command can't block
+    // on a full pipe while we wait for it.
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let gpu = sample_gpu();
+
+    let status = loop {
+        if let Some(mb) = process_group_rss_mb(child.id()) {
+            run.peak_memory_mb = Some(run.peak_memory_mb.map_or(mb, |peak| peak.max(mb)));
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if start.elapsed() >= timeout => {
+                run.timed_out = true;
+                kill(&mut child);
+                break None;
+            }
+            Ok(None) if cancel.load(Ordering::Relaxed) => {
+                kill(&mut child);
+                break None;
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(_) => break None,
+        }
+    };
+
+    run.duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+    run.stdout = stdout.join().unwrap_or_default();
+    run.stderr = stderr.join().unwrap_or_default();
+    if let Some((stop, sampler)) = gpu {
+        let _ = stop.send(());
+        run.gpu = sampler.join().ok().flatten();
+    }
+    if let Some(status) = status {
+        run.success = status.success();
+        run.exit_code = status.code();
+    }
+    run
+}
+
+#[cfg(unix)]
+pub(crate) fn shell(command: &str) -> Command {
+    use std::os::unix::process::CommandExt;
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    // Own process group, so the whole tree can be measured and killed.
+    cmd.process_group(0);
+    cmd
+}
+
+#[cfg(not(unix))]
+pub(crate) fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+pub(crate) fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-s", "KILL", "--", &format!("-{}", child.id())])
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        let text = String::from_utf8_lossy(&buf);
+        tail(&text, MAX_OUTPUT).to_string()
+    })
+}
+
+/// The last `max` bytes of `s`, on a char boundary.
+fn tail(s: &str, max: usize) -> &str {
+    let mut start = s.len().saturating_sub(max);
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+    &s[start..]
+}
+
+/// Start sampling GPU use on a background thread, if `nvidia-smi` works
+/// here. Sampling stops when the returned sender is used or dropped.
+fn sample_gpu() -> Option<(mpsc::Sender<()>, JoinHandle<Option<GpuUsage>>)> {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    let available = *AVAILABLE.get_or_init(|| query_gpus().is_some());
+    if !available {
+        return None;
+    }
+    let (stop, stopped) = mpsc::channel::<()>();
+    let sampler = thread::spawn(move || {
+        let start_vram: f64 = query_gpus()?.iter().map(|(_, vram)| vram).sum();
+        let mut peak: Option<GpuUsage> = None;
+        loop {
+            if let Some(gpus) = query_gpus() {
+                let sample = GpuUsage {
+                    utilization_pct: gpus.iter().map(|(util, _)| *util).fold(0.0, f64::max),
+                    vram_mb: (gpus.iter().map(|(_, vram)| vram).sum::<f64>() - start_vram).max(0.0),
+                };
+                let p = peak.get_or_insert(sample);
+                p.utilization_pct = p.utilization_pct.max(sample.utilization_pct);
+                p.vram_mb = p.vram_mb.max(sample.vram_mb);
+            }
+            if !matches!(
+                stopped.recv_timeout(GPU_POLL_INTERVAL),
+                Err(mpsc::RecvTimeoutError::Timeout)
+            ) {
+                return peak;
+            }
+        }
+    });
+    Some((stop, sampler))
+}
+
+/// Utilization (%) and used memory (MB) of each GPU, from `nvidia-smi`.
+fn query_gpus() -> Option<Vec<(f64, f64)>> {
+    let output = Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=utilization.gpu,memory.used",
+            "--format=csv,noheader,nounits",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    parse_gpu_query(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_gpu_query(output: &str) -> Option<Vec<(f64, f64)>> {
+    let gpus: Vec<(f64, f64)> = output
+        .lines()
+        .filter_map(|line| {
+            let (util, vram) = line.split_once(',')?;
+            Some((util.trim().parse().ok()?, vram.trim().parse().ok()?))
+        })
+        .collect();
+    (!gpus.is_empty()).then_some(gpus)
+}
+
+/// Resident memory of every process in group `pgid`, in MB.
+#[cfg(target_os = "linux")]
+fn process_group_rss_mb(pgid: u32) -> Option<f64> {
+    const PAGE_SIZE: f64 = 4096.0;
+    let mut pages = 0u64;
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let path = entry.path();
+        let Ok(stat) = std::fs::read_to_string(path.join("stat")) else {
+            continue;
+        };
+        // Fields after the parenthesised command name: state, ppid, pgrp.
+        let Some((_, rest)) = stat.rsplit_once(')') else {
+            continue;
+        };
+        if rest.split_whitespace().nth(2) != Some(&pgid.to_string()) {
+            continue;
+        }
+        if let Ok(statm) = std::fs::read_to_string(path.join("statm")) {
+            pages += statm
+                .split_whitespace()
+                .nth(1)
+                .and_then(|p| p.parse::<u64>().ok())
+                .unwrap_or(0);
+        }
+    }
+    (pages > 0).then(|| pages as f64 * PAGE_SIZE / (1024.0 * 1024.0))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_group_rss_mb(_pgid: u32) -> Option<f64> {
+    None
+}
+
+/// Passed and total test counts from cargo or pytest output.
+pub fn parse_test_counts(output: &str) -> Option<(u32, u32)> {
+    let count = |line: &str, word: &str| -> u32 {
+        line.split([',', ';'])
+            .filter_map(|part| {
+                let mut words = part.split_whitespace().rev();
+                (words.next()? == word).then(|| words.next()?.parse::<u32>().ok())?
+            })
+            .sum()
+    };
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut found = false;
+    for line in output.lines() {
+        // cargo: "test result: ok. 4 passed; 0 failed; ..."
+        // pytest: "==== 3 passed, 1 failed in 0.12s ===="
+        let line = line
+            .trim()
+            .trim_start_matches("test result: ")
+            .trim_start_matches(['o', 'k', 'F', 'A', 'I', 'L', 'E', 'D', '.'])
+            .trim_matches(['=', ' ']);
+        let line = line.split(" in ").next().unwrap_or(line);
+        let (p, f) = (count(line, "passed"), count(line, "failed"));
+        if p + f > 0 {
+            found = true;
+            passed += p;
+            failed += f;
+        }
+    }
+    found.then_some((passed, passed + failed))
+}
+
+/// Names of failed tests in cargo or pytest output.
+pub fn parse_failed_tests(output: &str) -> BTreeSet<String> {
+    let mut failed = BTreeSet::new();
+    let mut in_failure_list = false;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(name) = trimmed
+            .strip_prefix("test ")
+            .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+        {
+            // cargo: "test module::name ... FAILED"
+            failed.insert(name.to_string());
+        } else if let Some(rest) = trimmed.strip_prefix("FAILED ") {
+            // pytest summary: "FAILED tests/test_x.py::test_y - AssertionError"
+            let name = rest.split(" - ").next().unwrap_or(rest);
+            failed.insert(name.trim().to_string());
+        } else if trimmed == "failures:" {
+            in_failure_list = true;
+        } else if in_failure_list {
+            // cargo lists failed test names, one per indented line, before
+            // the "test result" line.
+            if trimmed.is_empty() || trimmed.contains(char::is_whitespace) {
+                in_failure_list = false;
+            } else {
+                failed.insert(trimmed.to_string());
+            }
+        }
+    }
+    failed
+}
+
+/// Tests that failed in some of `outputs` (each the output of one test
+/// run) and passed in others.
+pub fn flaky_tests(outputs: &[String]) -> Vec<String> {
+    if outputs.len() < 2 {
+        return Vec::new();
+    }
+    let runs: Vec<BTreeSet<String>> = outputs.iter().map(|o| parse_failed_tests(o)).collect();
+    let ever: BTreeSet<&String> = runs.iter().flatten().collect();
+    ever.into_iter()
+        .filter(|name| runs.iter().any(|failed| !failed.contains(*name)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_test_counts() {
+        let cargo = "test result: ok. 4 passed; 0 failed; 0 ignored\n\
+                     test result: FAILED. 2 passed; 1 failed; 0 ignored";
+        assert_eq!(parse_test_counts(cargo), Some((6, 7)));
+        let pytest = "===== 3 passed, 1 failed in 0.12s =====";
+        assert_eq!(parse_test_counts(pytest), Some((3, 4)));
+        assert_eq!(parse_test_counts("no tests here"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cancelling_kills_the_command() {
+        let cancel = AtomicBool::new(false);
+        let run = thread::scope(|scope| {
+            let run = scope.spawn(|| {
+                run_command("sleep 30", Path::new("."), Duration::from_secs(60), &cancel)
+            });
+            thread::sleep(Duration::from_millis(300));
+            cancel.store(true, Ordering::Relaxed);
+            run.join().unwrap()
+        });
+        assert!(!run.success && !run.timed_out);
+        assert!(run.duration_ms < 5000.0, "{}", run.duration_ms);
+    }
+
+    #[test]
+    fn test_runtime_is_the_run_commands_only() {
+        let command = |command: &str, duration_ms| CommandRun {
+            command: command.to_string(),
+            success: true,
+            exit_code: Some(0),
+            timed_out: false,
+            duration_ms,
+            peak_memory_mb: None,
+            gpu: None,
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        let mut report = RunReport {
+            build: Some(command("make", 900.0)),
+            test: Some(command("make test", 300.0)),
+            test_repeats: vec![command("make test", 500.0)],
+            ..Default::default()
+        };
+        let metrics = report.to_metrics();
+        assert_eq!(metrics.runtime_ms, None);
+        assert!(metrics.runtime_samples.is_empty());
+
+        report.run = Some(command("./app", 40.0));
+        report.run_repeats = vec![command("./app", 60.0)];
+        let metrics = report.to_metrics();
+        assert_eq!(metrics.runtime_ms, Some(50.0));
+        assert_eq!(metrics.runtime_samples, [40.0, 60.0]);
+    }
+
+    #[test]
+    fn test_parse_gpu_query() {
+        assert_eq!(
+            parse_gpu_query("87, 2048\n3, 512\n"),
+            Some(vec![(87.0, 2048.0), (3.0, 512.0)])
+        );
+        assert_eq!(parse_gpu_query("[N/A], [N/A]"), None);
+    }
+
+    #[test]
+    fn test_flaky_tests() {
+        let first = "test a::steady ... FAILED\ntest a::flaky ... FAILED\n\n\
+                     failures:\n\n---- a::flaky stdout ----\npanicked\n\n\
+                     failures:\n    a::flaky\n    a::steady\n\ntest result: FAILED.";
+        let second = "failures:\n    a::steady\n\ntest result: FAILED.";
+        let pytest = "FAILED tests/test_x.py::test_y - AssertionError";
+        assert_eq!(
+            parse_failed_tests(first).into_iter().collect::<Vec<_>>(),
+            ["a::flaky", "a::steady"]
+        );
+        assert_eq!(
+            parse_failed_tests(pytest).into_iter().collect::<Vec<_>>(),
+            ["tests/test_x.py::test_y"]
+        );
+        let outputs = [first.to_string(), second.to_string()];
+        assert_eq!(flaky_tests(&outputs), ["a::flaky"]);
+        assert!(flaky_tests(&outputs[..1]).is_empty());
+    }
+}
@@ -1,7 +1,19 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::benchmark::{JudgeEnsemble, ScoreWeights};
+use crate::llm::{LlmBackend, LlmProvider, LlmRoutes, StageModels};
+use crate::variant::{ImplementationStyle, Variant};
+
+mod overlay;
+
+pub use overlay::{Overlay, PROJECT_SECTIONS};
+
+/// Per-project settings, in the project root. See [`UniqConfig::load_for_project`].
+pub const PROJECT_CONFIG_FILE: &str = ".uniq.toml";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UniqConfig {
     #[serde(default)]
     pub api_keys: ApiKeysConfig,
@@ -14,15 +26,205 @@ pub struct UniqConfig {
 
     #[serde(default)]
     pub benchmark: BenchmarkConfig,
+
+    #[serde(default)]
+    pub ui: UiConfig,
+
+    #[serde(default)]
+    pub sidecar: SidecarConfig,
+
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+
+    #[serde(default)]
+    pub log: LogConfig,
+
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+
+    #[serde(default)]
+    pub hooks: HooksConfig,
+
+    #[serde(default)]
+    pub prompts: PromptsConfig,
+
+    /// Key chords per action name (e.g. `quit = "ctrl+q"` or
+    /// `next_phase = ["right", "l"]`), replacing that action's defaults.
+    #[serde(default)]
+    pub keys: HashMap<String, KeyBinding>,
+
+    /// Named sets of settings over the ones above, e.g.
+    /// `[profile.work.generation]`, chosen with `--profile work`.
+    #[serde(default)]
+    pub profile: HashMap<String, toml::Table>,
+
+    /// The profile this config was loaded with.
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+
+    /// Values from the project or the environment, kept out of the file on
+    /// save.
+    #[serde(skip)]
+    pub overlay: Overlay,
 }
 
+/// One key chord or several for the same action.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyBinding {
+    pub fn chords(&self) -> &[String] {
+        match self {
+            KeyBinding::One(chord) => std::slice::from_ref(chord),
+            KeyBinding::Many(chords) => chords,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ApiKeysConfig {
     #[serde(default)]
     pub anthropic: String,
 
     #[serde(default)]
     pub semantic_scholar: String,
+
+    /// For the OpenAI backend. Not needed for Ollama.
+    #[serde(default)]
+    pub openai: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SidecarConfig {
+    /// Python sidecar directory, as found by first-run setup. The
+    /// `--sidecar-dir` flag takes precedence.
+    #[serde(default)]
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    /// Keep anonymous usage stats in a local file (see
+    /// [`UsageMetrics`](crate::metrics::UsageMetrics)). Off unless turned on.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Rotation of the log file, `~/.cache/uniq/uniq.log` on Linux.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogConfig {
+    /// Start a new log file once the current one reaches this many
+    /// megabytes. 0 turns size rotation off.
+    #[serde(default = "default_log_max_size")]
+    pub max_size_mb: u64,
+
+    /// Also start a new file on the first write of each day.
+    #[serde(default = "default_true")]
+    pub daily: bool,
+
+    /// Rotated files to keep (`uniq.log.1` is the newest); older ones are
+    /// deleted.
+    #[serde(default = "default_log_keep")]
+    pub keep: usize,
+
+    /// Plain text lines, or one JSON object per line for log tools.
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// External commands that add paper sources and benchmark metrics (see
+/// [`PluginRegistry`](crate::plugin::PluginRegistry)). Only read from the
+/// user's config, never a project's, since they run arbitrary commands.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginsConfig {
+    /// Searched alongside Semantic Scholar and arXiv.
+    #[serde(default)]
+    pub research: Vec<PluginCommand>,
+
+    /// Run in each benchmarked variant's worktree after its execution
+    /// benchmark; what they report is added to its custom metrics.
+    #[serde(default)]
+    pub metrics: Vec<PluginCommand>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginCommand {
+    /// Shown in the UI and the log, and prefixed to the IDs of the papers
+    /// a research plugin finds.
+    pub name: String,
+
+    /// Shell command to run. It gets a JSON request on stdin and answers
+    /// with JSON on stdout.
+    pub command: String,
+
+    #[serde(default = "default_plugin_timeout")]
+    pub timeout_seconds: u64,
+}
+
+/// Prompt template files replacing the sidecar's own prompts (see
+/// [`crate::prompts`]), absolute or relative to the directory `config.toml`
+/// is in. Only read from the user's config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptsConfig {
+    /// Technique extraction; must use `{papers}`.
+    #[serde(default)]
+    pub extraction: Option<PathBuf>,
+
+    /// Planning and generating variants; must use `{technique}`.
+    #[serde(default)]
+    pub generation: Option<PathBuf>,
+
+    /// Merging variants; must use `{techniques}` and `{instructions}`.
+    #[serde(default)]
+    pub merge: Option<PathBuf>,
+
+    /// Scoring variants; must use `{diff}`.
+    #[serde(default)]
+    pub judge: Option<PathBuf>,
+}
+
+/// Shell commands run at points in a session (see [`crate::hooks`]). Like
+/// plugins, only read from the user's config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// Run when a variant finishes generating.
+    #[serde(default)]
+    pub on_variant_generated: Option<String>,
+
+    /// Run when a benchmark run finishes, with every variant's results.
+    #[serde(default)]
+    pub on_benchmark_complete: Option<String>,
+
+    /// Run when a merge finishes.
+    #[serde(default)]
+    pub on_merge_complete: Option<String>,
+
+    /// A hook still running after this long is killed.
+    #[serde(default = "default_hook_timeout")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            on_variant_generated: None,
+            on_benchmark_complete: None,
+            on_merge_complete: None,
+            timeout_seconds: default_hook_timeout(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,11 +244,92 @@ pub struct SearchConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationConfig {
+    /// LLM API the sidecar calls: `anthropic`, `openai` or `ollama`. This
+    /// and the backends' settings below are never taken from a project.
+    #[serde(default)]
+    pub backend: LlmProvider,
+
+    /// Model used with the Anthropic backend.
     #[serde(default = "default_claude_model")]
     pub claude_model: String,
 
+    /// Model and API root for the OpenAI backend, e.g. to point it at
+    /// another server with the same API.
+    #[serde(default)]
+    pub openai: BackendConfig,
+
+    /// Model and server for the Ollama backend.
+    #[serde(default)]
+    pub ollama: BackendConfig,
+
+    /// API root for the Anthropic backend, e.g. for a proxy.
+    #[serde(default)]
+    pub anthropic_base_url: Option<String>,
+
+    /// Models for particular stages on the chosen backend, e.g.
+    /// `[generation.models]` with `extraction = "claude-3-5-haiku-latest"`.
+    #[serde(default)]
+    pub models: StageModels,
+
     #[serde(default = "default_max_tokens")]
     pub max_tokens_per_variant: usize,
+
+    /// How many variants may generate at the same time.
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+
+    /// Draft an implementation plan for approval before writing code.
+    #[serde(default)]
+    pub plan_first: bool,
+
+    /// Styles to generate each technique in, one variant per style. Empty
+    /// means a single variant without a style directive.
+    #[serde(default)]
+    pub styles: Vec<ImplementationStyle>,
+
+    /// Install each variant's new dependencies right after generating it.
+    /// Off unless enabled, since the packages are whatever the model asked
+    /// for.
+    #[serde(default)]
+    pub install_dependencies: bool,
+}
+
+/// Settings for one LLM backend. Unset values fall back to the backend's
+/// defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackendConfig {
+    #[serde(default)]
+    pub model: Option<String>,
+
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+impl GenerationConfig {
+    /// The backend requests to the sidecar run on.
+    pub fn llm(&self) -> LlmBackend {
+        let (model, base_url) = match self.backend {
+            LlmProvider::Anthropic => (Some(&self.claude_model), &self.anthropic_base_url),
+            LlmProvider::OpenAi => (self.openai.model.as_ref(), &self.openai.base_url),
+            LlmProvider::Ollama => (self.ollama.model.as_ref(), &self.ollama.base_url),
+        };
+        LlmBackend {
+            provider: self.backend,
+            model: model
+                .filter(|m| !m.trim().is_empty())
+                .cloned()
+                .unwrap_or_else(|| self.backend.default_model().to_string()),
+            base_url: base_url.clone().filter(|url| !url.trim().is_empty()),
+        }
+    }
+
+    /// The backend with each stage's model.
+    pub fn llm_routes(&self) -> LlmRoutes {
+        LlmRoutes {
+            backend: self.llm(),
+            models: self.models.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,8 +337,115 @@ pub struct BenchmarkConfig {
     #[serde(default = "default_timeout")]
     pub timeout_seconds: u64,
 
+    /// Timeouts for particular variants, keyed by variant id, e.g. for
+    /// training-style variants that need longer than `timeout_seconds`.
+    /// Ids aren't reused within a project, so an entry never outlives its
+    /// variant onto a new one.
+    #[serde(default)]
+    pub variant_timeouts: HashMap<String, u64>,
+
     #[serde(default = "default_metrics")]
     pub metrics: Vec<String>,
+
+    /// Times the measured command is run per variant. Runtime and memory
+    /// are reported as the mean, with the spread used to tell real
+    /// differences from noise.
+    #[serde(default = "default_repetitions")]
+    pub repetitions: u32,
+
+    /// Weights of execution, judge and user scores in the composite.
+    #[serde(default)]
+    pub weights: ScoreWeights,
+
+    /// Judge models and samples per variant, and how they're combined.
+    #[serde(default)]
+    pub judge: JudgeEnsemble,
+
+    /// Run execution benchmarks in-process instead of on the sidecar.
+    /// Always used when the sidecar is unavailable.
+    #[serde(default)]
+    pub native_runner: bool,
+
+    /// Build command for the native runner (detected from the project's
+    /// manifest when unset).
+    #[serde(default)]
+    pub build_command: Option<String>,
+
+    /// Test command for the native runner (detected when unset).
+    #[serde(default)]
+    pub test_command: Option<String>,
+
+    /// Optional command whose runtime and memory are measured, e.g. a
+    /// training or inference script.
+    #[serde(default)]
+    pub run_command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// Theme preset: "dark", "light", "solarized" or "high-contrast".
+    #[serde(default = "default_theme")]
+    pub theme: String,
+
+    /// Colors replacing the preset's, keyed by palette slot (e.g.
+    /// `accent = "#ff8800"`). Values are color names or `#rrggbb`.
+    #[serde(default)]
+    pub colors: HashMap<String, String>,
+
+    /// Language of the UI: "en", "de" or "es". Taken from `LANG` when
+    /// unset.
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    /// Key layout: "default", or "vim" for hjkl, `gg`/`G`, `/` and `:`.
+    #[serde(default)]
+    pub keymap: KeymapProfile,
+
+    /// Show the pipeline summary sidebar at startup (toggle with `b`).
+    #[serde(default)]
+    pub sidebar: bool,
+
+    /// Replace spinners with a static marker, for accessibility and for
+    /// terminals that redraw slowly.
+    #[serde(default)]
+    pub reduced_motion: bool,
+
+    /// Show progress of long operations in the terminal's tab or taskbar
+    /// (OSC 9;4). Turn off for terminals that print the sequence.
+    #[serde(default = "default_true")]
+    pub terminal_progress: bool,
+
+    /// Detail panel heights, adjusted with `<`/`>` and saved on exit.
+    #[serde(default)]
+    pub panes: PaneSizes,
+}
+
+/// Heights, in lines, of the detail panels below the research, technique
+/// and benchmark lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaneSizes {
+    pub research_detail: u16,
+    pub technique_detail: u16,
+    pub benchmark_detail: u16,
+}
+
+impl Default for PaneSizes {
+    fn default() -> Self {
+        Self {
+            research_detail: 7,
+            technique_detail: 9,
+            benchmark_detail: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeymapProfile {
+    #[default]
+    Default,
+    Vim,
 }
 
 fn default_max_papers() -> usize {
@@ -71,14 +461,35 @@ fn default_true() -> bool {
     true
 }
 fn default_claude_model() -> String {
-    "claude-sonnet-4-20250514".to_string()
+    LlmProvider::Anthropic.default_model().to_string()
 }
 fn default_max_tokens() -> usize {
     8192
 }
+fn default_max_concurrent() -> usize {
+    3
+}
 fn default_timeout() -> u64 {
     300
 }
+fn default_theme() -> String {
+    "dark".to_string()
+}
+fn default_repetitions() -> u32 {
+    1
+}
+fn default_log_max_size() -> u64 {
+    10
+}
+fn default_log_keep() -> usize {
+    5
+}
+fn default_plugin_timeout() -> u64 {
+    60
+}
+fn default_hook_timeout() -> u64 {
+    30
+}
 fn default_metrics() -> Vec<String> {
     vec![
         "build_success".to_string(),
@@ -88,22 +499,13 @@ fn default_metrics() -> Vec<String> {
     ]
 }
 
-impl Default for UniqConfig {
-    fn default() -> Self {
-        Self {
-            api_keys: ApiKeysConfig::default(),
-            search: SearchConfig::default(),
-            generation: GenerationConfig::default(),
-            benchmark: BenchmarkConfig::default(),
-        }
-    }
-}
-
-impl Default for ApiKeysConfig {
+impl Default for LogConfig {
     fn default() -> Self {
         Self {
-            anthropic: String::new(),
-            semantic_scholar: String::new(),
+            max_size_mb: default_log_max_size(),
+            daily: default_true(),
+            keep: default_log_keep(),
+            format: LogFormat::default(),
         }
     }
 }
@@ -122,8 +524,17 @@ impl Default for SearchConfig {
 impl Default for GenerationConfig {
     fn default() -> Self {
         Self {
+            backend: LlmProvider::default(),
             claude_model: default_claude_model(),
+            openai: BackendConfig::default(),
+            ollama: BackendConfig::default(),
+            anthropic_base_url: None,
+            models: StageModels::default(),
             max_tokens_per_variant: default_max_tokens(),
+            max_concurrent: default_max_concurrent(),
+            plan_first: false,
+            styles: Vec::new(),
+            install_dependencies: false,
         }
     }
 }
@@ -132,29 +543,108 @@ impl Default for BenchmarkConfig {
     fn default() -> Self {
         Self {
             timeout_seconds: default_timeout(),
+            variant_timeouts: HashMap::new(),
             metrics: default_metrics(),
+            repetitions: default_repetitions(),
+            weights: ScoreWeights::default(),
+            judge: JudgeEnsemble::default(),
+            native_runner: false,
+            build_command: None,
+            test_command: None,
+            run_command: None,
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            theme: default_theme(),
+            colors: HashMap::new(),
+            locale: None,
+            keymap: KeymapProfile::default(),
+            sidebar: false,
+            reduced_motion: false,
+            terminal_progress: true,
+            panes: PaneSizes::default(),
         }
     }
 }
 
+impl BenchmarkConfig {
+    /// Timeout for benchmarking `variant`: its own override, then the
+    /// configured one for its id, then the global default.
+    pub fn timeout_for(&self, variant: &Variant) -> u64 {
+        variant
+            .benchmark_timeout
+            .or_else(|| self.variant_timeouts.get(&variant.id.0).copied())
+            .unwrap_or(self.timeout_seconds)
+    }
+}
+
 impl UniqConfig {
-    /// Load config from ~/.config/uniq/config.toml, creating defaults if missing.
+    /// Load config from ~/.config/uniq/config.toml, or defaults if it
+    /// hasn't been written yet (first-run setup writes it), then resolve
+    /// environment references and `UNIQ_*` overrides (see [`Overlay`]).
     pub fn load() -> crate::error::Result<Self> {
-        let config_path = Self::config_path()?;
+        Self::load_layers(None, None)
+    }
+
+    /// Load config with the named profile's settings (`[profile.<name>]`)
+    /// over the rest. It's an error if there's no such profile.
+    pub fn load_profile(profile: Option<&str>) -> crate::error::Result<Self> {
+        Self::load_layers(profile, None)
+    }
 
-        if config_path.exists() {
-            let contents = std::fs::read_to_string(&config_path).map_err(|e| {
-                crate::error::UniqError::Config(format!("Failed to read config: {e}"))
-            })?;
-            let config: UniqConfig = toml::from_str(&contents).map_err(|e| {
-                crate::error::UniqError::Config(format!("Failed to parse config: {e}"))
-            })?;
-            Ok(config)
-        } else {
-            let config = UniqConfig::default();
-            config.save()?;
-            Ok(config)
+    /// Load config for the project at `root`. From lowest to highest
+    /// precedence: defaults, ~/.config/uniq/config.toml, the `profile` in
+    /// it, the project's `.uniq.toml` ([`PROJECT_SECTIONS`] only, less the
+    /// backend and its endpoints), then the environment.
+    pub fn load_for_project(root: &Path, profile: Option<&str>) -> crate::error::Result<Self> {
+        Self::load_layers(profile, Some(&root.join(PROJECT_CONFIG_FILE)))
+    }
+
+    fn load_layers(
+        profile: Option<&str>,
+        project_path: Option<&Path>,
+    ) -> crate::error::Result<Self> {
+        let mut table = Self::read_table(&Self::config_path()?)?.unwrap_or_default();
+        let mut overlay = Overlay::default();
+        if let Some(name) = profile {
+            overlay.merge_profile(&mut table, name)?;
         }
+        if let Some(path) = project_path {
+            if let Some(project) = Self::read_table(path)? {
+                overlay.merge_project(&mut table, project);
+            }
+        }
+        overlay.apply_env(&mut table, std::env::vars())?;
+        let mut config: UniqConfig = table
+            .try_into()
+            .map_err(|e| crate::error::UniqError::Config(format!("Failed to parse config: {e}")))?;
+        config.benchmark.weights.validate()?;
+        config.overlay = overlay;
+        config.active_profile = profile.map(str::to_string);
+        Ok(config)
+    }
+
+    /// The TOML file at `path`, or `None` if there isn't one.
+    fn read_table(path: &Path) -> crate::error::Result<Option<toml::Table>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            crate::error::UniqError::Config(format!("Failed to read {}: {e}", path.display()))
+        })?;
+        let table = toml::from_str(&contents).map_err(|e| {
+            crate::error::UniqError::Config(format!("Failed to parse {}: {e}", path.display()))
+        })?;
+        Ok(Some(table))
+    }
+
+    /// Whether a config file has been written, i.e. this isn't a first run.
+    pub fn exists() -> bool {
+        Self::config_path().is_ok_and(|path| path.exists())
     }
 
     /// Save config to disk.
@@ -165,9 +655,34 @@ impl UniqConfig {
             std::fs::create_dir_all(parent)?;
         }
 
-        let contents = toml::to_string_pretty(self).map_err(|e| {
+        let serialize_error = |e: toml::ser::Error| {
             crate::error::UniqError::Config(format!("Failed to serialize config: {e}"))
+        };
+        let mut table = toml::Table::try_from(self).map_err(serialize_error)?;
+        self.overlay.restore(&mut table);
+        let contents = toml::to_string_pretty(&table).map_err(serialize_error)?;
+        std::fs::write(&config_path, contents)?;
+        Ok(())
+    }
+
+    /// Write `panes` to the config file's `[ui.panes]`, leaving everything
+    /// else in it, comments and formatting included, as it was.
+    pub fn save_pane_sizes(panes: &PaneSizes) -> crate::error::Result<()> {
+        let config_path = Self::config_path()?;
+        let contents = match std::fs::read_to_string(&config_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let contents = with_pane_sizes(&contents, panes).map_err(|e| {
+            crate::error::UniqError::Config(format!(
+                "Failed to parse {}: {e}",
+                config_path.display()
+            ))
         })?;
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
         std::fs::write(&config_path, contents)?;
         Ok(())
     }
@@ -179,4 +694,197 @@ impl UniqConfig {
         })?;
         Ok(config_dir.join("uniq").join("config.toml"))
     }
+
+    /// The config as TOML with API keys and other secrets replaced by
+    /// [`REDACTED`], including any in profiles, for sharing in a bug
+    /// report. Unset secrets stay empty, so it still shows which are set.
+    pub fn to_redacted_toml(&self) -> crate::error::Result<String> {
+        let serialize_error = |e: toml::ser::Error| {
+            crate::error::UniqError::Config(format!("Failed to serialize config: {e}"))
+        };
+        let mut table = toml::Table::try_from(self).map_err(serialize_error)?;
+        redact(&mut table, false, &mut Vec::new());
+        toml::to_string_pretty(&table).map_err(serialize_error)
+    }
+
+    /// The values [`UniqConfig::to_redacted_toml`] hides, so they can be
+    /// scrubbed from other text too.
+    pub fn secrets(&self) -> Vec<String> {
+        let mut secrets = Vec::new();
+        if let Ok(mut table) = toml::Table::try_from(self) {
+            redact(&mut table, false, &mut secrets);
+        }
+        secrets
+    }
+}
+
+/// `contents` of a config file with its `[ui.panes]` set to `panes`.
+fn with_pane_sizes(contents: &str, panes: &PaneSizes) -> Result<String, toml_edit::TomlError> {
+    let mut doc: toml_edit::DocumentMut = contents.parse()?;
+    let sizes = [
+        ("research_detail", panes.research_detail),
+        ("technique_detail", panes.technique_detail),
+        ("benchmark_detail", panes.benchmark_detail),
+    ];
+    for (key, lines) in sizes {
+        doc["ui"]["panes"][key] = toml_edit::value(i64::from(lines));
+    }
+    Ok(doc.to_string())
+}
+
+/// Stands in for a secret in [`UniqConfig::to_redacted_toml`].
+pub const REDACTED: &str = "<redacted>";
+
+/// Whether the value under `key` is a secret wherever it appears.
+fn is_secret(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.ends_with("api_key")
+        || ["token", "secret", "password"]
+            .iter()
+            .any(|word| key.contains(word))
+}
+
+/// Blank out secrets in `table`, adding what they were to `found`:
+/// everything under an `api_keys` or `hooks` table (`secret` is true inside
+/// one) and keys that name a secret. Hook commands often hold webhook URLs,
+/// which are as good as a token.
+fn redact(table: &mut toml::Table, secret: bool, found: &mut Vec<String>) {
+    for (key, value) in table.iter_mut() {
+        let secret = secret || is_secret(key);
+        match value {
+            toml::Value::Table(inner) => {
+                redact(inner, secret || key == "api_keys" || key == "hooks", found)
+            }
+            toml::Value::String(s) if secret && !s.is_empty() => {
+                found.push(std::mem::replace(s, REDACTED.to_string()));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::PipelineStage;
+    use crate::research::{Complexity, TechniqueCard};
+
+    #[test]
+    fn redacted_toml_hides_keys_everywhere() {
+        let mut config = UniqConfig::default();
+        config.api_keys.anthropic = "sk-ant-secret".into();
+        config.hooks.on_merge_complete = Some("curl https://hooks.slack.com/T0/B0/x".into());
+        config.profile.insert(
+            "work".into(),
+            "[api_keys]\nanthropic = \"sk-ant-work\"\n[generation]\nclaude_model = \"m\""
+                .parse()
+                .unwrap(),
+        );
+        let toml = config.to_redacted_toml().unwrap();
+        assert!(!toml.contains("sk-ant"), "{toml}");
+
+        let table: toml::Table = toml.parse().unwrap();
+        assert_eq!(table["api_keys"]["anthropic"].as_str(), Some(REDACTED));
+        assert_eq!(table["api_keys"]["semantic_scholar"].as_str(), Some(""));
+        assert_eq!(table["hooks"]["on_merge_complete"].as_str(), Some(REDACTED));
+        let work = &table["profile"]["work"];
+        assert_eq!(work["api_keys"]["anthropic"].as_str(), Some(REDACTED));
+        assert_eq!(work["generation"]["claude_model"].as_str(), Some("m"));
+        assert_eq!(table["ui"]["keymap"].as_str(), Some("default"));
+
+        let mut secrets = config.secrets();
+        secrets.sort();
+        assert_eq!(
+            secrets,
+            [
+                "curl https://hooks.slack.com/T0/B0/x",
+                "sk-ant-secret",
+                "sk-ant-work"
+            ]
+        );
+    }
+
+    #[test]
+    fn llm_backend_follows_the_selected_provider() {
+        let mut config: GenerationConfig = toml::from_str(
+            "backend = \"ollama\"\n[ollama]\nbase_url = \"http://gpu:11434/v1\"\n[openai]\nmodel = \"gpt-4.1\"",
+        )
+        .unwrap();
+        assert_eq!(
+            config.llm(),
+            LlmBackend {
+                provider: LlmProvider::Ollama,
+                model: "llama3.1".into(),
+                base_url: Some("http://gpu:11434/v1".into()),
+            }
+        );
+        config.backend = LlmProvider::OpenAi;
+        assert_eq!(config.llm().model, "gpt-4.1");
+        assert_eq!(config.llm().base_url, None);
+        config.backend = LlmProvider::Anthropic;
+        assert_eq!(config.llm().model, config.claude_model);
+
+        config.models.judge = Some("claude-3-5-haiku-latest".into());
+        config.models.merge = Some(" ".into());
+        let routes = config.llm_routes();
+        let judge = routes.for_stage(PipelineStage::Judge);
+        assert_eq!(judge.provider, LlmProvider::Anthropic);
+        assert_eq!(judge.model, "claude-3-5-haiku-latest");
+        assert_eq!(routes.for_stage(PipelineStage::Merge), config.llm());
+    }
+
+    #[test]
+    fn variant_timeouts_are_matched_by_id() {
+        let mut variant = Variant::from_technique(
+            3,
+            TechniqueCard {
+                name: "Mixed precision".to_string(),
+                paper_id: "p1".to_string(),
+                paper_title: String::new(),
+                methodology: String::new(),
+                key_components: vec![],
+                required_data_format: String::new(),
+                implementation_complexity: Complexity::Low,
+                hardware_requirements: String::new(),
+                dependencies: vec![],
+                relevance_score: 0.5,
+                integration_approach: String::new(),
+                selected: true,
+                related_papers: vec![],
+            },
+        );
+        let mut config = BenchmarkConfig {
+            timeout_seconds: 300,
+            ..BenchmarkConfig::default()
+        };
+        config
+            .variant_timeouts
+            .insert(variant.branch_name.clone(), 60);
+        assert_eq!(config.timeout_for(&variant), 300);
+
+        config.variant_timeouts.insert("variant-3".into(), 900);
+        assert_eq!(config.timeout_for(&variant), 900);
+
+        variant.benchmark_timeout = Some(1800);
+        assert_eq!(config.timeout_for(&variant), 1800);
+    }
+
+    #[test]
+    fn pane_sizes_are_saved_without_touching_the_rest() {
+        let panes = PaneSizes {
+            research_detail: 4,
+            technique_detail: 12,
+            benchmark_detail: 10,
+        };
+        let contents = "# My settings\n[api_keys]\nanthropic = \"sk\" # work key\n\n[ui]\ntheme = \"light\"\n\n[ui.panes]\nresearch_detail = 7\n";
+        let saved = with_pane_sizes(contents, &panes).unwrap();
+        assert!(saved.starts_with("# My settings\n[api_keys]\nanthropic = \"sk\" # work key\n"));
+        let config: UniqConfig = toml::from_str(&saved).unwrap();
+        assert_eq!(config.ui.panes, panes);
+        assert_eq!(config.ui.theme, "light");
+
+        let config: UniqConfig = toml::from_str(&with_pane_sizes("", &panes).unwrap()).unwrap();
+        assert_eq!(config.ui.panes, panes);
+        assert!(with_pane_sizes("[ui", &panes).is_err());
+    }
 }
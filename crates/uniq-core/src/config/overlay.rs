@@ -0,0 +1,484 @@
+//! Config values that don't come from the top level of `config.toml`: the
+//! chosen `[profile.<name>]`, a project's `.uniq.toml`, `${VAR}` references in strings (`${VAR:-fallback}` for a
+//! default), and `UNIQ_SECTION__KEY` environment overrides for any key,
+//! e.g. `UNIQ_GENERATION__MAX_CONCURRENT=4`.
+//!
+//! All of them are resolved on load and undone on save, so neither project
+//! settings nor secrets passed in through the environment end up in the
+//! user's config file.
+
+use std::collections::HashMap;
+
+use toml::{Table, Value};
+
+use crate::error::{Result, UniqError};
+
+/// Sections a project's `.uniq.toml` may set. API keys, the sidecar, the
+/// UI, metrics, plugins and hooks stay the user's own.
+pub const PROJECT_SECTIONS: [&str; 3] = ["search", "generation", "benchmark"];
+
+/// Keys in [`PROJECT_SECTIONS`] a project may not set either. The backend
+/// and its endpoints decide where the user's API key is sent, so a cloned
+/// repository mustn't be able to point them at its own server.
+pub const USER_ONLY_KEYS: [(&str, &str); 4] = [
+    ("generation", "backend"),
+    ("generation", "anthropic_base_url"),
+    ("generation", "openai"),
+    ("generation", "ollama"),
+];
+
+/// Table of named profiles in `config.toml`.
+const PROFILES: &str = "profile";
+
+/// Prefix of environment variables that override config keys.
+const PREFIX: &str = "UNIQ_";
+/// Separates the levels of a key path in an override's name.
+const SEPARATOR: &str = "__";
+
+/// A value that didn't come from `config.toml`, and what the file had there.
+#[derive(Debug, Clone, PartialEq)]
+struct Resolved {
+    path: Vec<String>,
+    file: Option<Value>,
+    value: Value,
+}
+
+/// Everything in a loaded config that didn't come from `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Overlay {
+    resolved: Vec<Resolved>,
+}
+
+impl Overlay {
+    /// Merge a project's settings into `table` key by key: keys the project
+    /// sets replace the global ones, the rest keep theirs. Only
+    /// [`PROJECT_SECTIONS`] are taken, without the [`USER_ONLY_KEYS`].
+    pub fn merge_project(&mut self, table: &mut Table, mut project: Table) {
+        for section in PROJECT_SECTIONS {
+            if let Some(mut value) = project.remove(section) {
+                if let Some(keys) = value.as_table_mut() {
+                    for (_, key) in USER_ONLY_KEYS.iter().filter(|(s, _)| *s == section) {
+                        keys.remove(*key);
+                    }
+                }
+                self.merge(table, &mut vec![section.to_string()], value);
+            }
+        }
+    }
+
+    /// Merge the profile called `name` over the rest of `table`, the same
+    /// way as [`merge_project`](Self::merge_project) but for any section.
+    pub fn merge_profile(&mut self, table: &mut Table, name: &str) -> Result<()> {
+        let profile = table
+            .get(PROFILES)
+            .and_then(|profiles| profiles.get(name))
+            .and_then(Value::as_table)
+            .cloned()
+            .ok_or_else(|| UniqError::Config(format!("No profile named \"{name}\" in config")))?;
+        for (section, value) in profile {
+            if section != PROFILES {
+                self.merge(table, &mut vec![section], value);
+            }
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, parent: &mut Table, path: &mut Vec<String>, value: Value) {
+        let key = path.last().expect("merge paths are never empty").clone();
+        match value {
+            Value::Table(project) if !matches!(parent.get(&key), Some(v) if !v.is_table()) => {
+                let Value::Table(nested) = parent
+                    .entry(key)
+                    .or_insert_with(|| Value::Table(Table::new()))
+                else {
+                    unreachable!("checked to be a table or missing");
+                };
+                for (name, value) in project {
+                    path.push(name);
+                    self.merge(nested, path, value);
+                    path.pop();
+                }
+            }
+            value => {
+                let previous = parent.insert(key, value.clone());
+                self.record(path.clone(), previous, value);
+            }
+        }
+    }
+
+    /// Expand references in `table`'s strings, then apply the
+    /// `UNIQ_SECTION__KEY` overrides among `vars`. Other `UNIQ_*` variables,
+    /// such as the sidecar's `UNIQ_CLAUDE_MODEL`, aren't config and are left
+    /// alone.
+    pub fn apply_env(
+        &mut self,
+        table: &mut Table,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<()> {
+        let vars: HashMap<String, String> = vars.into_iter().collect();
+        self.interpolate_table(table, &mut Vec::new(), &vars)?;
+
+        let mut overrides: Vec<(&String, &String)> = vars
+            .iter()
+            .filter(|(key, _)| {
+                key.strip_prefix(PREFIX)
+                    .is_some_and(|path| path.contains(SEPARATOR))
+            })
+            .collect();
+        // Apply in a fixed order, so a section override comes before the
+        // keys overridden within it.
+        overrides.sort();
+        for (key, raw) in overrides {
+            let path: Vec<String> = key[PREFIX.len()..]
+                .split(SEPARATOR)
+                .map(str::to_lowercase)
+                .collect();
+            if path.iter().any(String::is_empty) {
+                return Err(UniqError::Config(format!(
+                    "Malformed config override {key}: expected UNIQ_SECTION__KEY"
+                )));
+            }
+            self.set(table, path, raw)?;
+        }
+        Ok(())
+    }
+
+    /// Override the value at `path` with `raw`.
+    fn set(&mut self, table: &mut Table, path: Vec<String>, raw: &str) -> Result<()> {
+        let (key, parents) = path.split_last().expect("override paths are never empty");
+        let mut parent = &mut *table;
+        for name in parents {
+            let entry = parent
+                .entry(name.clone())
+                .or_insert_with(|| Value::Table(Table::new()));
+            parent = entry.as_table_mut().ok_or_else(|| {
+                UniqError::Config(format!(
+                    "Config override for {} reaches into {name}, which is not a section",
+                    path.join(".")
+                ))
+            })?;
+        }
+
+        let value = match parent.get(key) {
+            // Keep strings strings, even when they look like numbers.
+            Some(Value::String(_)) => Value::String(raw.to_string()),
+            _ => parse_value(raw),
+        };
+        let previous = parent.insert(key.clone(), value.clone());
+        self.record(path, previous, value);
+        Ok(())
+    }
+
+    fn interpolate_table(
+        &mut self,
+        table: &mut Table,
+        path: &mut Vec<String>,
+        vars: &HashMap<String, String>,
+    ) -> Result<()> {
+        for (key, value) in table.iter_mut() {
+            // Profiles not in use may refer to variables that aren't set.
+            if path.is_empty() && key == PROFILES {
+                continue;
+            }
+            path.push(key.clone());
+            match value {
+                Value::String(s) if s.contains("${") => {
+                    let expanded = Value::String(interpolate(s, vars)?);
+                    let previous = std::mem::replace(value, expanded.clone());
+                    self.record(path.clone(), Some(previous), expanded);
+                }
+                Value::Table(nested) => self.interpolate_table(nested, path, vars)?,
+                _ => {}
+            }
+            path.pop();
+        }
+        Ok(())
+    }
+
+    /// Note that `path` now holds `value` in place of `previous`. If it was
+    /// already replaced once, the file still has what it had before that.
+    fn record(&mut self, path: Vec<String>, previous: Option<Value>, value: Value) {
+        let file = match self.resolved.iter().position(|r| r.path == path) {
+            Some(i) => self.resolved.remove(i).file,
+            None => previous,
+        };
+        self.resolved.push(Resolved { path, file, value });
+    }
+
+    /// Put back what the file had wherever `table` still holds the value
+    /// that didn't come from it.
+    pub fn restore(&self, table: &mut Table) {
+        for resolved in &self.resolved {
+            let Some((key, parents)) = resolved.path.split_last() else {
+                continue;
+            };
+            let mut parent = Some(&mut *table);
+            for name in parents {
+                parent = parent
+                    .and_then(|t| t.get_mut(name))
+                    .and_then(Value::as_table_mut);
+            }
+            let Some(parent) = parent else {
+                continue;
+            };
+            if parent.get(key) != Some(&resolved.value) {
+                continue;
+            }
+            match &resolved.file {
+                Some(file) => {
+                    parent.insert(key.clone(), file.clone());
+                }
+                None => {
+                    parent.remove(key);
+                }
+            }
+        }
+    }
+}
+
+/// An override's value as TOML (`4`, `true`, `["a", "b"]`), or else as a
+/// plain string.
+fn parse_value(raw: &str) -> Value {
+    format!("value = {raw}")
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Expand `${VAR}` and `${VAR:-fallback}` in `s`.
+fn interpolate(s: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find('}').ok_or_else(|| {
+            UniqError::Config(format!("Unterminated ${{...}} in config value \"{s}\""))
+        })?;
+        let reference = &after[..end];
+        let (name, fallback) = match reference.split_once(":-") {
+            Some((name, fallback)) => (name, Some(fallback)),
+            None => (reference, None),
+        };
+        let value = vars
+            .get(name)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty() || fallback.is_none())
+            .or(fallback)
+            .ok_or_else(|| {
+                UniqError::Config(format!(
+                    "Config refers to environment variable {name}, which is not set"
+                ))
+            })?;
+        out.push_str(value);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_env(table: &mut Table, pairs: &[(&str, &str)]) -> Result<Overlay> {
+        let vars = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string()));
+        let mut overlay = Overlay::default();
+        overlay.apply_env(table, vars)?;
+        Ok(overlay)
+    }
+
+    #[test]
+    fn references_expand_and_fall_back() {
+        let mut table: Table = r#"
+            [api_keys]
+            anthropic = "${ANTHROPIC_API_KEY}"
+            semantic_scholar = "${S2_KEY:-none}"
+        "#
+        .parse()
+        .unwrap();
+        apply_env(&mut table, &[("ANTHROPIC_API_KEY", "sk-test")]).unwrap();
+        assert_eq!(table["api_keys"]["anthropic"].as_str(), Some("sk-test"));
+        assert_eq!(table["api_keys"]["semantic_scholar"].as_str(), Some("none"));
+
+        let mut table: Table = "key = \"${MISSING}\"".parse().unwrap();
+        assert!(apply_env(&mut table, &[]).is_err());
+    }
+
+    #[test]
+    fn overrides_set_nested_keys_with_types() {
+        let mut table: Table = "[generation]\nclaude_model = \"a\"".parse().unwrap();
+        apply_env(
+            &mut table,
+            &[
+                ("UNIQ_GENERATION__MAX_CONCURRENT", "4"),
+                ("UNIQ_GENERATION__CLAUDE_MODEL", "2024"),
+                ("UNIQ_METRICS__ENABLED", "true"),
+                ("OTHER", "ignored"),
+                ("UNIQ_CLAUDE_MODEL", "ignored"),
+                ("UNIQ_UI", "ignored"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(table["generation"]["max_concurrent"].as_integer(), Some(4));
+        assert_eq!(table["generation"]["claude_model"].as_str(), Some("2024"));
+        assert_eq!(table["metrics"]["enabled"].as_bool(), Some(true));
+        assert!(!table.contains_key("other"));
+        assert!(!table.contains_key("claude_model"));
+        assert!(!table.contains_key("ui"));
+
+        assert!(apply_env(&mut table, &[("UNIQ_GENERATION__", "4")]).is_err());
+    }
+
+    #[test]
+    fn project_settings_merge_by_key() {
+        let mut table: Table = r#"
+            [benchmark]
+            timeout_seconds = 300
+            test_command = "pytest"
+            [benchmark.variant_timeouts]
+            variant-1 = 900
+        "#
+        .parse()
+        .unwrap();
+        let project: Table = r#"
+            [api_keys]
+            anthropic = "sk-from-repo"
+            [benchmark]
+            test_command = "make test"
+            [benchmark.variant_timeouts]
+            variant-2 = 3600
+            [search]
+            year_range = [2020, 2026]
+        "#
+        .parse()
+        .unwrap();
+        Overlay::default().merge_project(&mut table, project);
+
+        assert_eq!(
+            table["benchmark"]["timeout_seconds"].as_integer(),
+            Some(300)
+        );
+        assert_eq!(
+            table["benchmark"]["test_command"].as_str(),
+            Some("make test")
+        );
+        let timeouts = &table["benchmark"]["variant_timeouts"];
+        assert_eq!(timeouts["variant-1"].as_integer(), Some(900));
+        assert_eq!(timeouts["variant-2"].as_integer(), Some(3600));
+        assert_eq!(table["search"]["year_range"][0].as_integer(), Some(2020));
+        assert!(!table.contains_key("api_keys"));
+    }
+
+    #[test]
+    fn projects_cannot_redirect_the_backend() {
+        let mut table: Table = r#"
+            [generation]
+            claude_model = "a"
+        "#
+        .parse()
+        .unwrap();
+        let project: Table = r#"
+            [generation]
+            backend = "openai"
+            anthropic_base_url = "https://collector.example"
+            claude_model = "b"
+            [generation.openai]
+            base_url = "https://collector.example/v1"
+            [generation.ollama]
+            base_url = "https://collector.example"
+        "#
+        .parse()
+        .unwrap();
+        Overlay::default().merge_project(&mut table, project);
+
+        let generation = table["generation"].as_table().unwrap();
+        assert_eq!(generation["claude_model"].as_str(), Some("b"));
+        for key in ["backend", "anthropic_base_url", "openai", "ollama"] {
+            assert!(
+                !generation.contains_key(key),
+                "{} taken from the project",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn profiles_override_any_section() {
+        let mut table: Table = r#"
+            [generation]
+            claude_model = "claude-sonnet-4-20250514"
+            max_concurrent = 3
+            [profile.work.api_keys]
+            anthropic = "${WORK_KEY}"
+            [profile.work.generation]
+            max_concurrent = 1
+            [profile.research.generation]
+            claude_model = "claude-opus-4-20250514"
+        "#
+        .parse()
+        .unwrap();
+        let mut overlay = Overlay::default();
+        overlay.merge_profile(&mut table, "research").unwrap();
+        assert!(overlay.merge_profile(&mut table.clone(), "home").is_err());
+        // The work profile's unset variable doesn't matter while unused.
+        overlay.apply_env(&mut table, std::iter::empty()).unwrap();
+
+        let generation = &table["generation"];
+        assert_eq!(
+            generation["claude_model"].as_str(),
+            Some("claude-opus-4-20250514")
+        );
+        assert_eq!(generation["max_concurrent"].as_integer(), Some(3));
+        assert!(!table.contains_key("api_keys"));
+    }
+
+    #[test]
+    fn restore_keeps_other_values_out_of_the_file() {
+        let file: Table = r#"
+            [api_keys]
+            anthropic = "${ANTHROPIC_API_KEY}"
+            [ui]
+            sidebar = true
+            [benchmark]
+            test_command = "pytest"
+        "#
+        .parse()
+        .unwrap();
+        let mut table = file.clone();
+        let mut overlay = Overlay::default();
+        overlay.merge_project(
+            &mut table,
+            "[benchmark]\ntest_command = \"make test\"\nrun_command = \"${RUN}\""
+                .parse()
+                .unwrap(),
+        );
+        let vars = [
+            ("ANTHROPIC_API_KEY", "sk-test"),
+            ("RUN", "python train.py"),
+            ("UNIQ_API_KEYS__SEMANTIC_SCHOLAR", "s2-test"),
+            ("UNIQ_UI__SIDEBAR", "false"),
+        ];
+        overlay
+            .apply_env(
+                &mut table,
+                vars.iter().map(|(k, v)| (k.to_string(), v.to_string())),
+            )
+            .unwrap();
+        assert_eq!(
+            table["benchmark"]["run_command"].as_str(),
+            Some("python train.py")
+        );
+
+        overlay.restore(&mut table);
+        assert_eq!(table, file);
+
+        // A value changed since loading is the user's and is kept.
+        let mut table = file.clone();
+        let overlay = apply_env(&mut table, &[("ANTHROPIC_API_KEY", "sk-test")]).unwrap();
+        table["api_keys"]["anthropic"] = Value::String("sk-new".to_string());
+        overlay.restore(&mut table);
+        assert_eq!(table["api_keys"]["anthropic"].as_str(), Some("sk-new"));
+    }
+}
@@ -0,0 +1,503 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{Result, UniqError};
+
+/// How one conflicting hunk should be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep the first source's side.
+    PreferA,
+    /// Keep the second source's side.
+    PreferB,
+    /// Replace the hunk with these lines, e.g. written by the LLM.
+    Reconciled(Vec<String>),
+}
+
+/// A region two sources changed differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictHunk {
+    pub a: Vec<String>,
+    /// Common ancestor's lines, when git reported them.
+    pub base: Vec<String>,
+    pub b: Vec<String>,
+    pub resolution: Option<Resolution>,
+}
+
+impl ConflictHunk {
+    /// Lines the hunk resolves to, if it has been resolved.
+    pub fn resolved_lines(&self) -> Option<&[String]> {
+        match self.resolution.as_ref()? {
+            Resolution::PreferA => Some(&self.a),
+            Resolution::PreferB => Some(&self.b),
+            Resolution::Reconciled(lines) => Some(lines),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Lines both sources agree on.
+    Clean(Vec<String>),
+    Conflict(ConflictHunk),
+}
+
+/// A file two merge sources conflict on, split into clean and conflicting
+/// segments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileConflict {
+    pub path: String,
+    /// Indices of the two conflicting sources in the merge spec.
+    pub sources: [usize; 2],
+    pub segments: Vec<Segment>,
+    trailing_newline: bool,
+}
+
+impl FileConflict {
+    /// Parse a file with git conflict markers (merge or diff3 style).
+    pub fn parse(path: impl Into<String>, sources: [usize; 2], text: &str) -> Self {
+        enum Side {
+            A,
+            Base,
+            B,
+        }
+
+        let mut segments = Vec::new();
+        let mut clean = Vec::new();
+        let mut hunk: Option<(Side, ConflictHunk)> = None;
+        for line in text.lines() {
+            match hunk {
+                None if line.starts_with("<<<<<<<") => {
+                    if !clean.is_empty() {
+                        segments.push(Segment::Clean(std::mem::take(&mut clean)));
+                    }
+                    hunk = Some((
+                        Side::A,
+                        ConflictHunk {
+                            a: Vec::new(),
+                            base: Vec::new(),
+                            b: Vec::new(),
+                            resolution: None,
+                        },
+                    ));
+                }
+                None => clean.push(line.to_string()),
+                Some((ref mut side, ref mut h)) => {
+                    if line.starts_with("|||||||") {
+                        *side = Side::Base;
+                    } else if line == "=======" {
+                        *side = Side::B;
+                    } else if line.starts_with(">>>>>>>") {
+                        if let Some((_, h)) = hunk.take() {
+                            segments.push(Segment::Conflict(h));
+                        }
+                    } else {
+                        match side {
+                            Side::A => h.a.push(line.to_string()),
+                            Side::Base => h.base.push(line.to_string()),
+                            Side::B => h.b.push(line.to_string()),
+                        }
+                    }
+                }
+            }
+        }
+        if !clean.is_empty() {
+            segments.push(Segment::Clean(clean));
+        }
+        Self {
+            path: path.into(),
+            sources,
+            segments,
+            trailing_newline: text.ends_with('\n'),
+        }
+    }
+
+    pub fn hunks(&self) -> impl Iterator<Item = &ConflictHunk> {
+        self.segments.iter().filter_map(|s| match s {
+            Segment::Conflict(h) => Some(h),
+            Segment::Clean(_) => None,
+        })
+    }
+
+    pub fn hunk_mut(&mut self, index: usize) -> Option<&mut ConflictHunk> {
+        self.segments
+            .iter_mut()
+            .filter_map(|s| match s {
+                Segment::Conflict(h) => Some(h),
+                Segment::Clean(_) => None,
+            })
+            .nth(index)
+    }
+
+    /// Up to `n` clean lines before and after hunk `index`.
+    pub fn context(&self, index: usize, n: usize) -> (Vec<String>, Vec<String>) {
+        let Some(pos) = self
+            .segments
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| matches!(s, Segment::Conflict(_)))
+            .nth(index)
+            .map(|(pos, _)| pos)
+        else {
+            return (Vec::new(), Vec::new());
+        };
+        let clean = |s: Option<&Segment>| match s {
+            Some(Segment::Clean(lines)) => lines.clone(),
+            _ => Vec::new(),
+        };
+        let before = clean(pos.checked_sub(1).and_then(|p| self.segments.get(p)));
+        let after = clean(self.segments.get(pos + 1));
+        (
+            before[before.len().saturating_sub(n)..].to_vec(),
+            after.into_iter().take(n).collect(),
+        )
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.hunks().all(|h| h.resolution.is_some())
+    }
+
+    /// File contents with every hunk resolved, or `None` while any is open.
+    pub fn resolved_content(&self) -> Option<String> {
+        let mut lines: Vec<&str> = Vec::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Clean(clean) => lines.extend(clean.iter().map(String::as_str)),
+                Segment::Conflict(h) => {
+                    lines.extend(h.resolved_lines()?.iter().map(String::as_str))
+                }
+            }
+        }
+        let mut out = lines.join("\n");
+        if self.trailing_newline && !out.is_empty() {
+            out.push('\n');
+        }
+        Some(out)
+    }
+}
+
+/// Conflicting hunks of `paths` between the merge sources on `branches`,
+/// for the merge on `target`. A file several pairs of sources conflict on
+/// is listed once per pair. Files the merge itself rewrote are left out:
+/// its version stands, and resolving them would overwrite it.
+pub fn load_conflicts(
+    repo_path: &Path,
+    target: &str,
+    branches: &[String],
+    paths: &[String],
+) -> Result<Vec<FileConflict>> {
+    let base = merge_base(repo_path, branches)?;
+    let mut files: Vec<FileConflict> = Vec::new();
+    for i in 0..branches.len() {
+        for j in i + 1..branches.len() {
+            let output = git(
+                repo_path,
+                &[
+                    "-c",
+                    "merge.conflictStyle=diff3",
+                    "merge-tree",
+                    "--write-tree",
+                    "--name-only",
+                    "--no-messages",
+                    &branches[i],
+                    &branches[j],
+                ],
+            )?;
+            let mut lines = output.lines();
+            let Some(tree) = lines.next() else {
+                continue;
+            };
+            for path in lines.filter(|p| !p.is_empty()) {
+                if !paths.iter().any(|p| p == path)
+                    || blob(repo_path, target, path)? != blob(repo_path, &base, path)?
+                {
+                    continue;
+                }
+                let text = git(repo_path, &["show", &format!("{}:{}", tree, path)])?;
+                files.push(FileConflict::parse(path, [i, j], &text));
+            }
+        }
+    }
+    files.retain(|f| f.hunks().next().is_some());
+    files.sort_by(|a, b| (&a.path, a.sources).cmp(&(&b.path, b.sources)));
+    Ok(files)
+}
+
+/// Contents to commit to `target` for each file whose hunks are resolved
+/// for every pair of sources that conflict on it. Each pair's resolution,
+/// and the changes of sources no pair conflicts with, are merged into the
+/// file as it is on `target`, so nothing already committed there is lost.
+/// Fails if two resolutions, or a resolution and `target`, change the same
+/// lines differently.
+pub fn resolve(
+    repo_path: &Path,
+    target: &str,
+    branches: &[String],
+    files: &[FileConflict],
+) -> Result<Vec<(String, String)>> {
+    let base = merge_base(repo_path, branches)?;
+    let mut paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    paths.sort_unstable();
+    paths.dedup();
+
+    let mut resolved = Vec::new();
+    for path in paths {
+        let pairs: Vec<&FileConflict> = files.iter().filter(|f| f.path == path).collect();
+        let Some(pieces) = pairs
+            .iter()
+            .map(|f| f.resolved_content())
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        let show = |rev: &str| -> Result<Option<String>> {
+            Ok(match blob(repo_path, rev, path)? {
+                Some(_) => Some(git(repo_path, &["show", &format!("{}:{}", rev, path)])?),
+                None => None,
+            })
+        };
+        let ancestor = show(&base)?.unwrap_or_default();
+        let mut content = show(target)?.unwrap_or_else(|| ancestor.clone());
+        let others = branches
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !pairs.iter().any(|f| f.sources.contains(i)))
+            .map(|(_, branch)| show(branch))
+            .collect::<Result<Vec<_>>>()?;
+        for piece in pieces.into_iter().chain(others.into_iter().flatten()) {
+            content = merge_text(&content, &ancestor, &piece)?.ok_or_else(|| {
+                UniqError::VariantMerge(format!(
+                    "The resolutions of {} change the same lines differently",
+                    path
+                ))
+            })?;
+        }
+        resolved.push((path.to_string(), content));
+    }
+    Ok(resolved)
+}
+
+/// The commit every one of `branches` descends from.
+fn merge_base(repo_path: &Path, branches: &[String]) -> Result<String> {
+    let mut args = vec!["merge-base", "--octopus"];
+    args.extend(branches.iter().map(String::as_str));
+    let output = git(repo_path, &args)?;
+    match output.trim() {
+        "" => Err(UniqError::VariantMerge(
+            "The merge sources have no common ancestor".to_string(),
+        )),
+        base => Ok(base.to_string()),
+    }
+}
+
+/// Id of the blob at `path` in `rev`, or `None` if it has no such file.
+fn blob(repo_path: &Path, rev: &str, path: &str) -> Result<Option<String>> {
+    let spec = format!("{}:{}", rev, path);
+    let output = git(repo_path, &["rev-parse", "--verify", "--quiet", &spec])?;
+    Ok(Some(output.trim().to_string()).filter(|id| !id.is_empty()))
+}
+
+/// Three-way merge of `ours` and `theirs`, or `None` if they conflict.
+fn merge_text(ours: &str, ancestor: &str, theirs: &str) -> Result<Option<String>> {
+    let dir = tempfile::tempdir()?;
+    let write = |name: &str, text: &str| -> Result<String> {
+        let path = dir.path().join(name);
+        std::fs::write(&path, text)?;
+        Ok(path.display().to_string())
+    };
+    let files = [
+        write("ours", ours)?,
+        write("base", ancestor)?,
+        write("theirs", theirs)?,
+    ];
+    let output = Command::new("git")
+        .args(["merge-file", "-p"])
+        .args(&files)
+        .output()
+        .map_err(|e| UniqError::VariantMerge(format!("Could not run git: {}", e)))?;
+    // Exits with the number of conflicts, or a negative status on error.
+    match output.status.code() {
+        Some(0) => Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned())),
+        Some(1..=127) => Ok(None),
+        _ => Err(UniqError::VariantMerge(format!(
+            "git merge-file failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+    }
+}
+
+/// Run git in `dir`. `merge-tree` exits with 1 when it finds conflicts,
+/// which isn't an error here.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| UniqError::VariantMerge(format!("Could not run git: {}", e)))?;
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        return Err(UniqError::VariantMerge(format!(
+            "git {} failed: {}",
+            args.iter().find(|a| !a.starts_with('-')).unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A repository whose `main` has `model.py` and `notes.md`, and a branch
+    /// off it per entry of `variants`, each committing its files.
+    fn project(name: &str, variants: &[(&str, &[(&str, &str)])]) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("uniq-conflict-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let run = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        run(&["init", "-q", "-b", "main"]);
+        std::fs::write(dir.join("model.py"), "a\nb\nc\nd\ne\nf\ng\n").unwrap();
+        std::fs::write(dir.join("notes.md"), "x\n").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-q", "-m", "Baseline"]);
+        for (branch, files) in variants {
+            run(&["checkout", "-q", "-b", branch, "main"]);
+            for (path, text) in files.iter() {
+                std::fs::write(dir.join(path), text).unwrap();
+            }
+            run(&["commit", "-q", "-am", branch]);
+        }
+        run(&["checkout", "-q", "main"]);
+        dir
+    }
+
+    fn branches(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn paths() -> Vec<String> {
+        branches(&["model.py", "notes.md"])
+    }
+
+    #[test]
+    fn test_loads_every_pair_but_not_what_the_merge_wrote() {
+        let dir = project(
+            "load",
+            &[
+                (
+                    "v1",
+                    &[("model.py", "a\nb\nc1\nd\ne\nf\ng\n"), ("notes.md", "x1\n")],
+                ),
+                (
+                    "v2",
+                    &[("model.py", "a\nb\nc2\nd\ne\nf\ng\n"), ("notes.md", "x2\n")],
+                ),
+                ("v3", &[("model.py", "a\nb\nc3\nd\ne\nf\ng\n")]),
+                ("merge", &[("notes.md", "merged\n")]),
+            ],
+        );
+
+        let files =
+            load_conflicts(&dir, "merge", &branches(&["v1", "v2", "v3"]), &paths()).unwrap();
+        let listed: Vec<_> = files.iter().map(|f| (f.path.as_str(), f.sources)).collect();
+        assert_eq!(
+            listed,
+            [
+                ("model.py", [0, 1]),
+                ("model.py", [0, 2]),
+                ("model.py", [1, 2])
+            ]
+        );
+        let hunk = files[0].hunks().next().unwrap();
+        assert_eq!(
+            (hunk.a.as_slice(), hunk.base.as_slice()),
+            (&["c1".to_string()][..], &["c".to_string()][..])
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolutions_keep_the_merge_and_other_sources() {
+        let dir = project(
+            "resolve",
+            &[
+                ("v1", &[("model.py", "a\nb\nc1\nd\ne\nf\ng\n")]),
+                ("v2", &[("model.py", "a\nb\nc2\nd\ne\nf\ng\n")]),
+                ("v3", &[("model.py", "a\nb\nc\nd\ne\nf\ng3\n")]),
+                ("merge", &[("notes.md", "merged\n")]),
+            ],
+        );
+        let sources = branches(&["v1", "v2", "v3"]);
+        let mut files = load_conflicts(&dir, "merge", &sources, &paths()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(resolve(&dir, "merge", &sources, &files).unwrap().is_empty());
+
+        // The merge's branch moved on since the conflicts were loaded.
+        crate::git::commit_file(&dir, "merge", "model.py", "A\nb\nc\nd\ne\nf\ng\n", "Edit")
+            .unwrap();
+        files[0].hunk_mut(0).unwrap().resolution = Some(Resolution::PreferB);
+        let resolved = resolve(&dir, "merge", &sources, &files).unwrap();
+        assert_eq!(
+            resolved,
+            [(
+                "model.py".to_string(),
+                "A\nb\nc2\nd\ne\nf\ng3\n".to_string()
+            )]
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_disagreeing_resolutions_are_refused() {
+        let dir = project(
+            "disagree",
+            &[
+                ("v1", &[("model.py", "a\nb\nc1\nd\ne\nf\ng\n")]),
+                ("v2", &[("model.py", "a\nb\nc2\nd\ne\nf\ng\n")]),
+                ("v3", &[("model.py", "a\nb\nc3\nd\ne\nf\ng\n")]),
+                ("merge", &[("notes.md", "merged\n")]),
+            ],
+        );
+        let sources = branches(&["v1", "v2", "v3"]);
+        let mut files = load_conflicts(&dir, "merge", &sources, &paths()).unwrap();
+        for (file, side) in files.iter_mut().zip([
+            Resolution::PreferA,
+            Resolution::PreferB,
+            Resolution::PreferA,
+        ]) {
+            file.hunk_mut(0).unwrap().resolution = Some(side);
+        }
+        let error = resolve(&dir, "merge", &sources, &files).unwrap_err();
+        assert!(error.to_string().contains("model.py"), "{}", error);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolves_diff3_hunks() {
+        let text = "fn main() {\n\
+                    <<<<<<< a\n    fast();\n\
+                    ||||||| base\n    slow();\n\
+                    =======\n    cached();\n\
+                    >>>>>>> b\n}\n";
+        let mut file = FileConflict::parse("src/main.rs", [0, 1], text);
+        assert_eq!(file.hunks().count(), 1);
+        assert_eq!(file.context(0, 3).0, vec!["fn main() {".to_string()]);
+        assert!(file.resolved_content().is_none());
+
+        file.hunk_mut(0).unwrap().resolution = Some(Resolution::PreferB);
+        assert_eq!(
+            file.resolved_content().as_deref(),
+            Some("fn main() {\n    cached();\n}\n")
+        );
+    }
+}
@@ -23,9 +23,21 @@ pub enum UniqError {
     #[error("Benchmark error: {0}")]
     Benchmark(String),
 
+    #[error("Cancelled")]
+    Cancelled,
+
     #[error("Git operation error: {0}")]
     Git(#[from] git2::Error),
 
+    #[error("Pull request error: {0}")]
+    PullRequest(String),
+
+    #[error("Hook failed: {0}")]
+    Hook(String),
+
+    #[error("Plugin error: {0}")]
+    Plugin(String),
+
     #[error("Sidecar communication error: {0}")]
     Sidecar(String),
 
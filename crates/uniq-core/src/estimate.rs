@@ -0,0 +1,128 @@
+use crate::config::GenerationConfig;
+use crate::llm::{LlmProvider, PipelineStage};
+use crate::research::{Complexity, TechniqueCard};
+
+/// Prompt size per variant: project profile, file tree and technique card.
+const INPUT_TOKENS_PER_VARIANT: usize = 6_000;
+
+/// Output throughput assumed for wall-time estimates.
+const OUTPUT_TOKENS_PER_SECOND: f64 = 60.0;
+
+/// Fixed per-variant overhead (worktree setup, commit) in seconds.
+const OVERHEAD_SECONDS: f64 = 10.0;
+
+/// Rough cost and time of generating variants for a set of techniques.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GenerationEstimate {
+    /// Number of variant generations.
+    pub variants: usize,
+    /// Expected prompt tokens across all variants.
+    pub input_tokens: usize,
+    /// Expected completion tokens across all variants.
+    pub output_tokens: usize,
+    /// Expected API cost in US dollars.
+    pub cost_usd: f64,
+    /// Expected wall time in seconds. Variants generate concurrently (up
+    /// to the configured limit), so this is well below the sum.
+    pub wall_seconds: u64,
+}
+
+/// Share of the output budget a technique typically uses.
+fn output_share(complexity: &Complexity) -> f64 {
+    match complexity {
+        Complexity::Low => 0.4,
+        Complexity::Medium => 0.65,
+        Complexity::High => 0.9,
+    }
+}
+
+/// USD per million (input, output) tokens for a model id. Claude models are
+/// priced by tier and OpenAI ones by family, with unknown models priced as
+/// the provider's default. Local models are free.
+pub fn model_pricing(provider: LlmProvider, model: &str) -> (f64, f64) {
+    match provider {
+        LlmProvider::Anthropic if model.contains("opus") => (15.0, 75.0),
+        LlmProvider::Anthropic if model.contains("haiku") => (0.8, 4.0),
+        LlmProvider::Anthropic => (3.0, 15.0),
+        LlmProvider::OpenAi if model.contains("4o-mini") => (0.15, 0.6),
+        LlmProvider::OpenAi if model.contains("4.1-nano") => (0.1, 0.4),
+        LlmProvider::OpenAi if model.contains("4.1-mini") => (0.4, 1.6),
+        LlmProvider::OpenAi if model.contains("4.1") => (2.0, 8.0),
+        LlmProvider::OpenAi => (2.5, 10.0),
+        LlmProvider::Ollama => (0.0, 0.0),
+    }
+}
+
+/// Cost in US dollars of `input_tokens` and `output_tokens` on `model`.
+pub fn token_cost(
+    provider: LlmProvider,
+    model: &str,
+    input_tokens: usize,
+    output_tokens: usize,
+) -> f64 {
+    let (input_price, output_price) = model_pricing(provider, model);
+    (input_tokens as f64 * input_price + output_tokens as f64 * output_price) / 1_000_000.0
+}
+
+/// Estimate generating one variant per technique with `config`.
+pub fn estimate_generation<'a>(
+    techniques: impl IntoIterator<Item = &'a TechniqueCard>,
+    config: &GenerationConfig,
+) -> GenerationEstimate {
+    let mut estimate = GenerationEstimate::default();
+    let mut slowest: f64 = 0.0;
+    let mut total: f64 = 0.0;
+
+    // Each technique is generated once per configured style.
+    let per_technique = config.styles.len().max(1);
+
+    for technique in techniques {
+        let output = (config.max_tokens_per_variant as f64
+            * output_share(&technique.implementation_complexity)) as usize;
+        let seconds = OVERHEAD_SECONDS + output as f64 / OUTPUT_TOKENS_PER_SECOND;
+        estimate.variants += per_technique;
+        estimate.input_tokens += INPUT_TOKENS_PER_VARIANT * per_technique;
+        estimate.output_tokens += output * per_technique;
+        slowest = slowest.max(seconds);
+        total += seconds * per_technique as f64;
+    }
+
+    let llm = config.llm_routes().for_stage(PipelineStage::Generation);
+    if llm.provider.is_metered() {
+        estimate.cost_usd = token_cost(
+            llm.provider,
+            &llm.model,
+            estimate.input_tokens,
+            estimate.output_tokens,
+        );
+    }
+    let parallel = config.max_concurrent.max(1) as f64;
+    estimate.wall_seconds = slowest.max(total / parallel).ceil() as u64;
+    estimate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn models_are_priced_by_their_own_provider() {
+        let sonnet = model_pricing(LlmProvider::Anthropic, "claude-sonnet-4-20250514");
+        assert_eq!(sonnet, (3.0, 15.0));
+        assert_eq!(
+            model_pricing(LlmProvider::Anthropic, "claude-3-5-haiku-latest"),
+            (0.8, 4.0)
+        );
+        assert_eq!(model_pricing(LlmProvider::OpenAi, "gpt-4o"), (2.5, 10.0));
+        assert_eq!(
+            model_pricing(LlmProvider::OpenAi, "gpt-4o-mini"),
+            (0.15, 0.6)
+        );
+        assert_ne!(model_pricing(LlmProvider::OpenAi, "my-finetune"), sonnet);
+        assert_eq!(model_pricing(LlmProvider::Ollama, "llama3.1"), (0.0, 0.0));
+        assert_eq!(
+            token_cost(LlmProvider::OpenAi, "gpt-4o", 1_000_000, 100_000),
+            3.5
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, UniqError};
+use crate::project::ProjectProfile;
+use crate::research::TechniqueCard;
+use crate::variant::{slugify, Variant};
+
+/// File format for exported documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+impl ExportFormat {
+    /// File extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Directory exports are written to (`~/.local/share/uniq/exports` on Linux).
+pub fn exports_dir() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .ok_or_else(|| UniqError::Config("Could not determine data directory".into()))?;
+    Ok(data_dir.join("uniq").join("exports"))
+}
+
+/// Render technique cards as Markdown implementation briefs.
+pub fn technique_briefs_markdown(
+    cards: &[TechniqueCard],
+    project: Option<&ProjectProfile>,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Technique briefs\n");
+    if let Some(project) = project {
+        let _ = writeln!(out, "**Project:** `{}`  ", project.path.display());
+        if !project.user_request.is_empty() {
+            let _ = writeln!(out, "**Goal:** {}  ", project.user_request);
+        }
+    }
+    let _ = writeln!(
+        out,
+        "_Exported {} · {} technique(s)_\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        cards.len()
+    );
+
+    for (i, card) in cards.iter().enumerate() {
+        write_technique_brief(&mut out, i + 1, card, 2);
+    }
+    out
+}
+
+/// Append `card`'s brief to `out` as technique `number`, its heading at
+/// Markdown `level` and its subsections one below.
+pub(crate) fn write_technique_brief(
+    out: &mut String,
+    number: usize,
+    card: &TechniqueCard,
+    level: usize,
+) {
+    let heading = "#".repeat(level);
+    let sub = "#".repeat(level + 1);
+    let _ = writeln!(out, "{} {}. {}\n", heading, number, card.name);
+    let _ = writeln!(
+        out,
+        "- **Source:** {} (`{}`)",
+        card.paper_title, card.paper_id
+    );
+    for paper in &card.related_papers {
+        let _ = writeln!(out, "- **Also in:** {} (`{}`)", paper.title, paper.id);
+    }
+    let _ = writeln!(
+        out,
+        "- **Complexity:** {} · **Relevance:** {:.0}%",
+        card.implementation_complexity,
+        card.relevance_score * 100.0
+    );
+    let _ = writeln!(out, "- **Hardware:** {}", card.hardware_requirements);
+    let _ = writeln!(out, "- **Input data:** {}", card.required_data_format);
+    let deps = if card.dependencies.is_empty() {
+        "none".to_string()
+    } else {
+        card.dependencies.join(", ")
+    };
+    let _ = writeln!(out, "- **Dependencies:** {}\n", deps);
+
+    let _ = writeln!(out, "{} Methodology\n\n{}\n", sub, card.methodology);
+    if !card.key_components.is_empty() {
+        let _ = writeln!(out, "{} Key components\n", sub);
+        for component in &card.key_components {
+            let _ = writeln!(out, "- {}", component);
+        }
+        let _ = writeln!(out);
+    }
+    let _ = writeln!(
+        out,
+        "{} Integration approach\n\n{}\n",
+        sub, card.integration_approach
+    );
+}
+
+/// Write technique cards to a timestamped file in [`exports_dir`] and
+/// return its path.
+pub fn export_techniques(
+    cards: &[TechniqueCard],
+    project: Option<&ProjectProfile>,
+    format: ExportFormat,
+) -> Result<PathBuf> {
+    let contents = match format {
+        ExportFormat::Markdown => technique_briefs_markdown(cards, project),
+        ExportFormat::Json => serde_json::to_string_pretty(cards)?,
+    };
+
+    let dir = exports_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "techniques-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    ));
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Write each variant's commits since `base` as a patch series, one
+/// subdirectory per variant under a timestamped directory in
+/// [`exports_dir`]. Returns that directory.
+pub fn export_variant_patches(
+    repo_path: &Path,
+    base: &str,
+    variants: &[Variant],
+) -> Result<PathBuf> {
+    let dir = exports_dir()?.join(format!(
+        "patches-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    for variant in variants {
+        crate::git::format_patches(
+            repo_path,
+            &variant.branch_name,
+            base,
+            &dir.join(slugify(&variant.branch_name)),
+        )?;
+    }
+    Ok(dir)
+}
@@ -0,0 +1,66 @@
+/// Score how well `query` fuzzy-matches `candidate`: every query character
+/// must appear in order, ignoring case. Consecutive characters and
+/// characters at word starts score higher; gaps and long candidates score
+/// lower. `None` means no match. Whitespace in the query is ignored.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    fuzzy_match(query, candidate).map(|(score, _)| score)
+}
+
+/// Like [`fuzzy_score`], also returning the char indices of `candidate`
+/// that matched, for highlighting.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let chars: Vec<char> = candidate.chars().collect();
+    let mut score = 0i64;
+    let mut next = 0;
+    let mut prev: Option<usize> = None;
+    let mut positions = Vec::new();
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let pos = (next..chars.len()).find(|&i| eq_ignore_case(chars[i], q))?;
+        score += 1;
+        if pos > 0 && prev == Some(pos - 1) {
+            score += 5;
+        }
+        if is_word_start(&chars, pos) {
+            score += 8;
+        }
+        score -= (pos - next) as i64;
+        positions.push(pos);
+        prev = Some(pos);
+        next = pos + 1;
+    }
+    Some((score - chars.len() as i64 / 8, positions))
+}
+
+fn eq_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+fn is_word_start(chars: &[char], pos: usize) -> bool {
+    match pos.checked_sub(1).map(|p| chars[p]) {
+        None => true,
+        Some(before) => {
+            !before.is_alphanumeric() || (before.is_lowercase() && chars[pos].is_uppercase())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefers_word_starts_and_runs() {
+        assert_eq!(fuzzy_score("xyz", "export_json"), None);
+        assert_eq!(fuzzy_score("", "anything"), Some(-1));
+        assert!(fuzzy_score("EJ", "export_json").is_some());
+        assert!(fuzzy_score("ej", "export_json") > fuzzy_score("ej", "reextract_job"));
+        assert!(fuzzy_score("exp", "export_json") > fuzzy_score("exp", "next_pass"));
+    }
+
+    #[test]
+    fn test_match_positions() {
+        let (_, positions) = fuzzy_match("prj", "projects").unwrap();
+        assert_eq!(positions, vec![0, 1, 3]);
+        assert_eq!(fuzzy_match("prjx", "projects"), None);
+    }
+}
@@ -0,0 +1,695 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use git2::build::TreeUpdateBuilder;
+use git2::{
+    BranchType, DiffFormat, DiffOptions, Email, EmailCreateOptions, ErrorCode, FileMode,
+    IndexAddOption, Repository, Signature, Sort, StatusOptions, WorktreeAddOptions,
+    WorktreePruneOptions,
+};
+
+use crate::error::Result;
+use crate::merge::{MergePreview, SharedFile};
+use crate::variant::slugify;
+
+/// Name of the branch `HEAD` points at (`HEAD` itself when detached).
+pub fn head_branch(repo_path: &Path) -> Result<String> {
+    let repo = Repository::open(repo_path)?;
+    let head = repo.head()?;
+    Ok(head.shorthand().unwrap_or("HEAD").to_string())
+}
+
+/// Whether a project can have variant branches made from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoStatus {
+    /// A repository with commits and nothing uncommitted.
+    Clean,
+    /// Not the root of a git repository.
+    NotARepository,
+    /// A repository without commits, so there's nothing to branch from.
+    NoCommits,
+    /// Files changed since the last commit (up to [`DIRTY_PATHS_SHOWN`] of
+    /// them), which variants branched from it won't have.
+    Dirty(Vec<String>),
+}
+
+/// How many uncommitted paths [`repo_status`] lists.
+pub const DIRTY_PATHS_SHOWN: usize = 5;
+
+/// Check that the project at `path` is a repository variants can branch
+/// from, before anything tries to. Files [`commit_baseline`] would leave
+/// out don't count as uncommitted.
+pub fn repo_status(path: &Path) -> Result<RepoStatus> {
+    let repo = match Repository::open(path) {
+        Ok(repo) => repo,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(RepoStatus::NotARepository),
+        Err(e) => return Err(e.into()),
+    };
+    match repo.head() {
+        Ok(_) => {}
+        Err(e) if matches!(e.code(), ErrorCode::UnbornBranch | ErrorCode::NotFound) => {
+            return Ok(RepoStatus::NoCommits);
+        }
+        Err(e) => return Err(e.into()),
+    }
+    repo.add_ignore_rule(&DEFAULT_IGNORES.join("\n"))?;
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).include_ignored(false);
+    let statuses = repo.statuses(Some(&mut opts))?;
+    if statuses.is_empty() {
+        return Ok(RepoStatus::Clean);
+    }
+    let paths = statuses
+        .iter()
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .take(DIRTY_PATHS_SHOWN)
+        .collect();
+    Ok(RepoStatus::Dirty(paths))
+}
+
+/// Never committed by [`commit_baseline`], so secrets and installed
+/// dependencies don't end up in the baseline. Written to the `.gitignore` of
+/// a repository it creates.
+const DEFAULT_IGNORES: &[&str] = &[
+    ".env",
+    ".env.*",
+    "*.pem",
+    "*.key",
+    ".venv/",
+    "venv/",
+    "node_modules/",
+    "__pycache__/",
+    "target/",
+];
+
+/// Get the project at `path` ready to branch from: make it a repository if
+/// it isn't one and commit everything not ignored as it is now. Only the
+/// repository rooted at `path` is used, never one it happens to be inside.
+/// Untracked files matching [`DEFAULT_IGNORES`] are left out of an existing
+/// repository too, without touching its `.gitignore`.
+pub fn commit_baseline(path: &Path, message: &str) -> Result<()> {
+    let repo = match Repository::open(path) {
+        Ok(repo) => repo,
+        Err(e) if e.code() == ErrorCode::NotFound => {
+            ignore_defaults(path)?;
+            Repository::init(path)?
+        }
+        Err(e) => return Err(e.into()),
+    };
+    // In memory only, for this commit.
+    repo.add_ignore_rule(&DEFAULT_IGNORES.join("\n"))?;
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    // Files deleted since the last commit.
+    index.update_all(["*"], None)?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let parent = match repo.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(e) if matches!(e.code(), ErrorCode::UnbornBranch | ErrorCode::NotFound) => None,
+        Err(e) => return Err(e.into()),
+    };
+    // A fresh machine may not have `user.name` set yet.
+    let signature = repo
+        .signature()
+        .or_else(|_| Signature::now("uniq", "uniq@localhost"))?;
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?;
+    Ok(())
+}
+
+/// Add the [`DEFAULT_IGNORES`] `.gitignore` at `path` doesn't have yet,
+/// creating it if need be.
+fn ignore_defaults(path: &Path) -> Result<()> {
+    let gitignore = path.join(".gitignore");
+    let existing = match std::fs::read_to_string(&gitignore) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let missing: Vec<&str> = DEFAULT_IGNORES
+        .iter()
+        .copied()
+        .filter(|pattern| !existing.lines().any(|line| line.trim() == *pattern))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let mut text = existing;
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    for pattern in missing {
+        text.push_str(pattern);
+        text.push('\n');
+    }
+    std::fs::write(gitignore, text)?;
+    Ok(())
+}
+
+/// Rename a local branch in the repository at `repo_path`.
+pub fn rename_branch(repo_path: &Path, from: &str, to: &str) -> Result<()> {
+    let repo = Repository::open(repo_path)?;
+    let mut branch = repo.find_branch(from, BranchType::Local)?;
+    branch.rename(to, false)?;
+    Ok(())
+}
+
+/// Delete a local branch. Returns `false` if there was no such branch.
+pub fn delete_branch(repo_path: &Path, name: &str) -> Result<bool> {
+    let repo = Repository::open(repo_path)?;
+    let mut branch = match repo.find_branch(name, BranchType::Local) {
+        Ok(branch) => branch,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    branch.delete()?;
+    Ok(true)
+}
+
+/// Commit `contents` as `path` on top of `branch` without touching the
+/// working tree, so it works while another branch is checked out.
+pub fn commit_file(
+    repo_path: &Path,
+    branch: &str,
+    path: &str,
+    contents: &str,
+    message: &str,
+) -> Result<()> {
+    let repo = Repository::open(repo_path)?;
+    let reference = repo
+        .find_branch(branch, BranchType::Local)?
+        .into_reference();
+    let parent = reference.peel_to_commit()?;
+    let blob = repo.blob(contents.as_bytes())?;
+    let tree_id = TreeUpdateBuilder::new()
+        .upsert(path, blob, FileMode::Blob)
+        .create_updated(&repo, &parent.tree()?)?;
+    let tree = repo.find_tree(tree_id)?;
+    let signature = repo
+        .signature()
+        .or_else(|_| Signature::now("uniq", "uniq@localhost"))?;
+    repo.commit(
+        reference.name(),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &[&parent],
+    )?;
+    Ok(())
+}
+
+/// Unified diff of `paths` between two branches (`from` → `to`).
+pub fn diff_branches(repo_path: &Path, from: &str, to: &str, paths: &[String]) -> Result<String> {
+    if paths.is_empty() {
+        return Ok(String::new());
+    }
+    let repo = Repository::open(repo_path)?;
+    let from_tree = repo.revparse_single(from)?.peel_to_tree()?;
+    let to_tree = repo.revparse_single(to)?.peel_to_tree()?;
+    let mut opts = DiffOptions::new();
+    for path in paths {
+        opts.pathspec(path);
+    }
+    let diff = repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut opts))?;
+
+    let mut patch = String::new();
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin());
+        }
+        patch.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })?;
+    Ok(patch)
+}
+
+/// Size of a branch's changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffStat {
+    pub files: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// What `branch` changed since it left `HEAD`'s history.
+pub fn diff_stat(repo_path: &Path, branch: &str) -> Result<DiffStat> {
+    let repo = Repository::open(repo_path)?;
+    let tip = repo.revparse_single(branch)?.peel_to_commit()?;
+    let head = repo.head()?.peel_to_commit()?;
+    let base = repo.find_commit(repo.merge_base(head.id(), tip.id())?)?;
+    let diff = repo.diff_tree_to_tree(Some(&base.tree()?), Some(&tip.tree()?), None)?;
+    let stats = diff.stats()?;
+    Ok(DiffStat {
+        files: stats.files_changed(),
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+    })
+}
+
+/// Predict how merging `branches` would go: which files several of them
+/// changed since their common ancestor, and which of those git couldn't
+/// merge cleanly.
+pub fn preview_merge(repo_path: &Path, branches: &[String]) -> Result<MergePreview> {
+    let repo = Repository::open(repo_path)?;
+    let commits = branches
+        .iter()
+        .map(|b| repo.revparse_single(b)?.peel_to_commit())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let ids: Vec<_> = commits.iter().map(|c| c.id()).collect();
+    let base = repo.find_commit(repo.merge_base_many(&ids)?)?.tree()?;
+    let trees = commits
+        .iter()
+        .map(|c| c.tree())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    // Which sources changed each file.
+    let mut changed: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, tree) in trees.iter().enumerate() {
+        let diff = repo.diff_tree_to_tree(Some(&base), Some(tree), None)?;
+        for delta in diff.deltas() {
+            let file = delta.new_file().path().or_else(|| delta.old_file().path());
+            if let Some(path) = file {
+                changed
+                    .entry(path.to_string_lossy().into_owned())
+                    .or_default()
+                    .push(i);
+            }
+        }
+    }
+
+    // Conflicts between each pair of sources.
+    let mut conflicts = Vec::new();
+    for i in 0..trees.len() {
+        for j in i + 1..trees.len() {
+            let index = repo.merge_trees(&base, &trees[i], &trees[j], None)?;
+            for conflict in index.conflicts()? {
+                let conflict = conflict?;
+                let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+                if let Some(entry) = entry {
+                    conflicts.push(String::from_utf8_lossy(&entry.path).into_owned());
+                }
+            }
+        }
+    }
+
+    let mut preview = MergePreview::default();
+    for (path, sources) in changed {
+        if sources.len() < 2 {
+            preview.unshared += 1;
+            continue;
+        }
+        let conflict = conflicts.contains(&path);
+        preview.shared.push(SharedFile {
+            path,
+            sources,
+            conflict,
+        });
+    }
+    preview.shared.sort_by_key(|f| !f.conflict);
+    Ok(preview)
+}
+
+/// Write the commits on `branch` that aren't on `base` as a numbered
+/// `git format-patch` style series in `out_dir`. Returns the patch files.
+/// Only first parents are followed, so a merge commit becomes one patch
+/// of what it brought in.
+pub fn format_patches(
+    repo_path: &Path,
+    branch: &str,
+    base: &str,
+    out_dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    let repo = Repository::open(repo_path)?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    walk.simplify_first_parent()?;
+    walk.push(repo.revparse_single(branch)?.id())?;
+    walk.hide(repo.revparse_single(base)?.peel_to_commit()?.id())?;
+
+    let commits = walk
+        .map(|oid| repo.find_commit(oid?))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    std::fs::create_dir_all(out_dir)?;
+    let mut files = Vec::with_capacity(commits.len());
+    for (i, commit) in commits.iter().enumerate() {
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+        let summary = commit.summary().unwrap_or("");
+        let email = Email::from_diff(
+            &diff,
+            i + 1,
+            commits.len(),
+            &commit.id(),
+            summary,
+            commit.body().unwrap_or(""),
+            &commit.author(),
+            &mut EmailCreateOptions::new(),
+        )?;
+
+        let path = out_dir.join(format!("{:04}-{}.patch", i + 1, slugify(summary)));
+        std::fs::write(&path, email.as_slice())?;
+        files.push(path);
+    }
+    Ok(files)
+}
+
+/// A throwaway checkout of a branch in its own directory, so commands can
+/// run against a variant without touching the project's working tree.
+/// Every one gets a fresh temporary directory, so runs of the same branch
+/// can overlap. Removed again on drop.
+pub struct TempWorktree {
+    repo_path: PathBuf,
+    name: String,
+    /// Directory the branch is checked out in.
+    pub path: PathBuf,
+    /// Deleted after the worktree is removed.
+    _dir: tempfile::TempDir,
+}
+
+impl TempWorktree {
+    pub fn create(repo_path: &Path, branch: &str) -> Result<Self> {
+        let repo = Repository::open(repo_path)?;
+        let commit = repo.revparse_single(branch)?.peel_to_commit()?;
+        let slug = slugify(branch);
+        let dir = tempfile::Builder::new()
+            .prefix(&format!("uniq-bench-{}-", slug))
+            .tempdir()?;
+        // Unique like the directory, for the worktree and its branch.
+        let name = dir
+            .path()
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        let path = dir.path().join(&slug);
+
+        // A branch can only be checked out once, so the worktree gets its
+        // own temporary branch at the same commit.
+        let temp_branch = repo.branch(&name, &commit, true)?;
+        let mut opts = WorktreeAddOptions::new();
+        opts.reference(Some(temp_branch.get()));
+        repo.worktree(&name, &path, Some(&opts))?;
+
+        Ok(Self {
+            repo_path: repo_path.to_path_buf(),
+            name,
+            path,
+            _dir: dir,
+        })
+    }
+
+    fn remove(repo: &Repository, name: &str, path: &Path) {
+        if let Ok(worktree) = repo.find_worktree(name) {
+            let _ = worktree.prune(Some(
+                WorktreePruneOptions::new()
+                    .valid(true)
+                    .locked(true)
+                    .working_tree(true),
+            ));
+        }
+        let _ = std::fs::remove_dir_all(path);
+        if let Ok(mut branch) = repo.find_branch(name, BranchType::Local) {
+            let _ = branch.delete();
+        }
+    }
+}
+
+impl Drop for TempWorktree {
+    fn drop(&mut self) {
+        if let Ok(repo) = Repository::open(&self.repo_path) {
+            Self::remove(&repo, &self.name, &self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("uniq-git-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.py"), "print('hi')\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn temp_worktrees_of_one_branch_can_overlap() {
+        let dir = project("overlap");
+        commit_baseline(&dir, "Baseline").unwrap();
+        let branch = head_branch(&dir).unwrap();
+
+        let first = TempWorktree::create(&dir, &branch).unwrap();
+        let second = TempWorktree::create(&dir, &branch).unwrap();
+        assert_ne!(first.path, second.path);
+        assert!(first.path.join("main.py").exists());
+        assert!(second.path.join("main.py").exists());
+
+        let path = first.path.clone();
+        drop(first);
+        assert!(!path.exists());
+        assert!(second.path.join("main.py").exists());
+        drop(second);
+        let repo = Repository::open(&dir).unwrap();
+        assert!(repo.worktrees().unwrap().is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn baseline_makes_a_plain_directory_branchable() {
+        let dir = project("plain");
+        assert_eq!(repo_status(&dir).unwrap(), RepoStatus::NotARepository);
+
+        commit_baseline(&dir, "Baseline").unwrap();
+        assert_eq!(repo_status(&dir).unwrap(), RepoStatus::Clean);
+        let repo = Repository::open(&dir).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.summary(), Some("Baseline"));
+        assert!(commit.tree().unwrap().get_name("main.py").is_some());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn baseline_keeps_to_the_project_and_out_of_secrets() {
+        let outer = project("outer");
+        Repository::init(&outer).unwrap();
+        let dir = outer.join("app");
+        std::fs::create_dir_all(dir.join("node_modules/left-pad")).unwrap();
+        std::fs::write(dir.join("main.py"), "print('hi')\n").unwrap();
+        std::fs::write(dir.join(".env"), "API_KEY=secret\n").unwrap();
+        std::fs::write(dir.join("node_modules/left-pad/index.js"), "").unwrap();
+        std::fs::write(dir.join(".gitignore"), "*.log").unwrap();
+
+        commit_baseline(&dir, "Baseline").unwrap();
+        let outer_repo = Repository::open(&outer).unwrap();
+        assert!(outer_repo.index().unwrap().is_empty());
+        assert!(outer_repo.head().is_err());
+
+        let repo = Repository::open(&dir).unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let mut names: Vec<_> = tree
+            .iter()
+            .filter_map(|e| e.name().map(str::to_string))
+            .collect();
+        names.sort();
+        assert_eq!(names, [".gitignore", "main.py"]);
+        let gitignore = std::fs::read_to_string(dir.join(".gitignore")).unwrap();
+        assert!(gitignore.starts_with("*.log\n.env\n"), "{}", gitignore);
+        std::fs::remove_dir_all(outer).unwrap();
+    }
+
+    #[test]
+    fn baseline_leaves_secrets_out_of_existing_repositories() {
+        let dir = project("existing");
+        Repository::init(&dir).unwrap();
+        std::fs::write(dir.join(".env"), "API_KEY=secret\n").unwrap();
+        commit_baseline(&dir, "Baseline").unwrap();
+
+        std::fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+        std::fs::write(dir.join(".venv/bin/python"), "").unwrap();
+        std::fs::write(dir.join("model.py"), "").unwrap();
+        commit_baseline(&dir, "Baseline").unwrap();
+
+        let repo = Repository::open(&dir).unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let mut names: Vec<_> = tree
+            .iter()
+            .filter_map(|e| e.name().map(str::to_string))
+            .collect();
+        names.sort();
+        assert_eq!(names, ["main.py", "model.py"]);
+        assert!(!dir.join(".gitignore").exists());
+        assert_eq!(repo_status(&dir).unwrap(), RepoStatus::Clean);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_uncommitted_changes_until_committed() {
+        let dir = project("dirty");
+        Repository::init(&dir).unwrap();
+        assert_eq!(repo_status(&dir).unwrap(), RepoStatus::NoCommits);
+
+        commit_baseline(&dir, "Baseline").unwrap();
+        std::fs::write(dir.join("main.py"), "print('changed')\n").unwrap();
+        std::fs::write(dir.join("new.py"), "").unwrap();
+        let RepoStatus::Dirty(mut paths) = repo_status(&dir).unwrap() else {
+            panic!("expected uncommitted changes");
+        };
+        paths.sort();
+        assert_eq!(paths, ["main.py", "new.py"]);
+
+        std::fs::remove_file(dir.join("new.py")).unwrap();
+        commit_baseline(&dir, "Second").unwrap();
+        assert_eq!(repo_status(&dir).unwrap(), RepoStatus::Clean);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn diff_stat_counts_a_branch_since_it_forked() {
+        let dir = project("stat");
+        Repository::init(&dir).unwrap();
+        commit_baseline(&dir, "Baseline").unwrap();
+        let repo = Repository::open(&dir).unwrap();
+        let main = head_branch(&dir).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("uniq/variant", &head, false).unwrap();
+        repo.set_head("refs/heads/uniq/variant").unwrap();
+        std::fs::write(dir.join("main.py"), "print('hello')\nprint('there')\n").unwrap();
+        std::fs::write(dir.join("model.py"), "x = 1\n").unwrap();
+        commit_baseline(&dir, "Variant").unwrap();
+        repo.set_head(&format!("refs/heads/{}", main)).unwrap();
+
+        let stat = diff_stat(&dir, "uniq/variant").unwrap();
+        assert_eq!(
+            stat,
+            DiffStat {
+                files: 2,
+                insertions: 3,
+                deletions: 1,
+            }
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn patches_are_taken_against_the_base_and_keep_merges() {
+        let dir = project("patches");
+        Repository::init(&dir).unwrap();
+        commit_baseline(&dir, "Baseline").unwrap();
+        let repo = Repository::open(&dir).unwrap();
+        let main = head_branch(&dir).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        for branch in ["uniq/variant", "uniq/other"] {
+            repo.branch(branch, &head, false).unwrap();
+            repo.set_head(&format!("refs/heads/{}", branch)).unwrap();
+            let file = dir.join(format!("{}.py", slugify(branch)));
+            std::fs::write(&file, "x = 1\n").unwrap();
+            commit_baseline(&dir, branch).unwrap();
+            std::fs::remove_file(file).unwrap();
+        }
+        let tip = |name: &str| {
+            repo.revparse_single(name)
+                .unwrap()
+                .peel_to_commit()
+                .unwrap()
+        };
+        let (variant, other) = (tip("uniq/variant"), tip("uniq/other"));
+        let tree = repo
+            .merge_commits(&variant, &other, None)
+            .unwrap()
+            .write_tree_to(&repo)
+            .unwrap();
+        let signature = git2::Signature::now("uniq", "uniq@localhost").unwrap();
+        repo.commit(
+            Some("refs/heads/uniq/variant"),
+            &signature,
+            &signature,
+            "Merge other",
+            &repo.find_tree(tree).unwrap(),
+            &[&variant, &other],
+        )
+        .unwrap();
+
+        // Checked out on the variant itself, which HEAD would hide entirely.
+        repo.set_head("refs/heads/uniq/variant").unwrap();
+        let out = dir.join("patches");
+        let patches = format_patches(&dir, "uniq/variant", &main, &out).unwrap();
+        let names: Vec<_> = patches
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["0001-uniq-variant.patch", "0002-merge-other.patch"]);
+        let merge = std::fs::read_to_string(&patches[1]).unwrap();
+        assert!(merge.contains("uniq-other.py") && !merge.contains("uniq-variant.py"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn merge_previews_list_shared_files_conflicts_first() {
+        let dir = project("preview");
+        std::fs::write(dir.join("model.py"), "a = 1\nb = 2\nc = 3\n").unwrap();
+        commit_baseline(&dir, "Baseline").unwrap();
+        let repo = Repository::open(&dir).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let files: [(&str, &[(&str, &str)]); 3] = [
+            (
+                "a",
+                &[
+                    ("main.py", "print('a')\n"),
+                    ("model.py", "a = 10\nb = 2\nc = 3\n"),
+                ],
+            ),
+            (
+                "b",
+                &[
+                    ("main.py", "print('b')\n"),
+                    ("model.py", "a = 1\nb = 2\nc = 30\n"),
+                ],
+            ),
+            ("c", &[("notes.md", "c\n")]),
+        ];
+        for (branch, changes) in files {
+            repo.branch(branch, &head, false).unwrap();
+            for (path, contents) in changes {
+                commit_file(&dir, branch, path, contents, "Change").unwrap();
+            }
+        }
+
+        let branches = ["a", "b", "c"].map(String::from);
+        let preview = preview_merge(&dir, &branches).unwrap();
+        assert_eq!(
+            preview.shared,
+            [
+                SharedFile {
+                    path: "main.py".into(),
+                    sources: vec![0, 1],
+                    conflict: true,
+                },
+                SharedFile {
+                    path: "model.py".into(),
+                    sources: vec![0, 1],
+                    conflict: false,
+                },
+            ]
+        );
+        assert_eq!(preview.unshared, 1);
+        assert_eq!(preview.conflicts(), 1);
+        assert!(preview_merge(&dir, &["a".into(), "missing".into()]).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,129 @@
+//! Lifecycle hooks: shell commands the user configures to run when a variant
+//! is generated, a benchmark run completes or a merge completes, e.g. to post
+//! to Slack, trigger CI or collect data.
+//!
+//! Each hook runs in the project directory with a JSON object on stdin that
+//! names the hook and the project and describes what happened, e.g.
+//! `{"hook": "on_merge_complete", "project": "/src/app", "variant": {...}}`.
+//! What a hook prints is ignored; failing only gets reported.
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::benchmark::ExecutionMetrics;
+use crate::config::HooksConfig;
+use crate::error::{Result, UniqError};
+use crate::plugin::exchange;
+use crate::variant::Variant;
+
+/// Something that happened that a hook may want to know about.
+#[derive(Debug, Serialize)]
+#[serde(tag = "hook")]
+pub enum HookEvent<'a> {
+    /// A variant finished generating.
+    #[serde(rename = "on_variant_generated")]
+    VariantGenerated { variant: &'a Variant },
+    /// A benchmark run finished. `baseline` is the unmodified project's
+    /// execution metrics, if it was benchmarked.
+    #[serde(rename = "on_benchmark_complete")]
+    BenchmarkComplete {
+        variants: &'a [Variant],
+        baseline: Option<&'a ExecutionMetrics>,
+    },
+    /// Variants were merged into `variant`.
+    #[serde(rename = "on_merge_complete")]
+    MergeComplete { variant: &'a Variant },
+}
+
+impl HookEvent<'_> {
+    /// The hook's name in config and in its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::VariantGenerated { .. } => "on_variant_generated",
+            Self::BenchmarkComplete { .. } => "on_benchmark_complete",
+            Self::MergeComplete { .. } => "on_merge_complete",
+        }
+    }
+
+    /// The command configured for this hook, if any.
+    pub fn command<'c>(&self, config: &'c HooksConfig) -> Option<&'c str> {
+        let command = match self {
+            Self::VariantGenerated { .. } => &config.on_variant_generated,
+            Self::BenchmarkComplete { .. } => &config.on_benchmark_complete,
+            Self::MergeComplete { .. } => &config.on_merge_complete,
+        };
+        command.as_deref().filter(|c| !c.trim().is_empty())
+    }
+
+    /// What the hook gets on stdin.
+    pub fn payload(&self, project: &Path) -> Result<String> {
+        #[derive(Serialize)]
+        struct Payload<'e, 'a> {
+            #[serde(flatten)]
+            event: &'e HookEvent<'a>,
+            project: &'e Path,
+        }
+        Ok(serde_json::to_string(&Payload {
+            event: self,
+            project,
+        })?)
+    }
+}
+
+/// Run hook `command` in `project` with `payload` on stdin.
+pub fn run(command: &str, project: &Path, payload: String, timeout: Duration) -> Result<()> {
+    exchange(command, project, payload, timeout)
+        .map(|_| ())
+        .map_err(UniqError::Hook)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    const BENCHMARKED: HookEvent<'static> = HookEvent::BenchmarkComplete {
+        variants: &[],
+        baseline: None,
+    };
+
+    #[test]
+    fn picks_the_configured_command() {
+        let mut config = HooksConfig {
+            on_merge_complete: Some("notify".into()),
+            ..Default::default()
+        };
+        assert_eq!(BENCHMARKED.command(&config), None);
+        config.on_benchmark_complete = Some("  ".into());
+        assert_eq!(BENCHMARKED.command(&config), None);
+        config.on_benchmark_complete = Some("notify".into());
+        assert_eq!(BENCHMARKED.command(&config), Some("notify"));
+    }
+
+    #[test]
+    fn runs_with_the_event_on_stdin() {
+        let dir = std::env::temp_dir().join(format!("uniq-hooks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let payload = BENCHMARKED.payload(&dir).unwrap();
+
+        run("cat > payload.json", &dir, payload, Duration::from_secs(5)).unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("payload.json")).unwrap())
+                .unwrap();
+        assert_eq!(written["hook"], "on_benchmark_complete");
+        assert_eq!(written["project"], dir.to_string_lossy().as_ref());
+        assert_eq!(written["variants"], serde_json::json!([]));
+        assert!(written["baseline"].is_null());
+
+        let err = run(
+            "echo nope >&2; exit 1",
+            &dir,
+            "{}".into(),
+            Duration::from_secs(5),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("nope"), "{}", err);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, UniqError};
+use crate::session::{project_files, same_project};
+use crate::variant::{Variant, VariantId};
+
+/// Best known result of one variant ever benchmarked for a project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// `None` for entries recorded before variant ids were kept.
+    #[serde(default)]
+    pub variant_id: Option<VariantId>,
+    pub display_name: String,
+    pub branch_name: String,
+    /// Name of the technique it implemented, for research variants.
+    pub technique: Option<String>,
+    pub composite_score: f64,
+    pub benchmarked_at: DateTime<Utc>,
+}
+
+/// Every variant benchmarked for a project, across sessions, so good
+/// ideas from earlier sessions aren't lost when a new one starts.
+///
+/// Stored next to the session files, one file per project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub project_path: PathBuf,
+    #[serde(default)]
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn new(project_path: impl Into<PathBuf>) -> Self {
+        Self {
+            project_path: project_path.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Directory leaderboard files live in.
+    fn leaderboards_dir() -> Result<PathBuf> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| UniqError::Config("Could not determine data directory".into()))?;
+        Ok(data_dir.join("uniq").join("leaderboards"))
+    }
+
+    /// Leaderboard file for a project.
+    pub fn path_for(project_path: &Path) -> Result<PathBuf> {
+        let [path, ..] = project_files(&Self::leaderboards_dir()?, project_path);
+        Ok(path)
+    }
+
+    /// Load a project's leaderboard, or an empty one if it has none yet.
+    pub fn load(project_path: &Path) -> Result<Self> {
+        Self::load_from(&Self::leaderboards_dir()?, project_path)
+    }
+
+    /// Load a project's leaderboard from `dir`. Leaderboards saved before
+    /// files were named by hash are still found, as long as they were
+    /// saved for this project.
+    fn load_from(dir: &Path, project_path: &Path) -> Result<Self> {
+        for path in project_files(dir, project_path) {
+            if !path.exists() {
+                continue;
+            }
+            let contents = std::fs::read_to_string(&path)?;
+            let leaderboard: Self = serde_json::from_str(&contents)?;
+            if same_project(&leaderboard.project_path, project_path) {
+                return Ok(leaderboard);
+            }
+        }
+        Ok(Self::new(project_path))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path_for(&self.project_path)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record `variant`'s latest composite score, replacing any earlier
+    /// entry for the same variant. Variants without a score are skipped.
+    ///
+    /// Entries are matched by id rather than branch, as a branch can be
+    /// deleted and created again for another variant.
+    pub fn record(&mut self, variant: &Variant) {
+        let Some(score) = variant
+            .benchmark_results
+            .as_ref()
+            .and_then(|br| br.composite_score)
+        else {
+            return;
+        };
+        let entry = LeaderboardEntry {
+            variant_id: Some(variant.id.clone()),
+            display_name: variant.display_name.clone(),
+            branch_name: variant.branch_name.clone(),
+            technique: variant.technique().map(|t| t.name.clone()),
+            composite_score: score,
+            benchmarked_at: Utc::now(),
+        };
+        match self
+            .entries
+            .iter_mut()
+            .find(|e| e.variant_id.as_ref() == Some(&variant.id))
+        {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+        self.entries
+            .sort_by(|a, b| b.composite_score.total_cmp(&a.composite_score));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::BenchmarkResults;
+    use crate::research::{Complexity, TechniqueCard};
+
+    fn scored(index: usize, name: &str, score: f64) -> Variant {
+        let mut variant = Variant::from_technique(
+            index,
+            TechniqueCard {
+                name: name.to_string(),
+                paper_id: "p1".to_string(),
+                paper_title: String::new(),
+                methodology: String::new(),
+                key_components: vec![],
+                required_data_format: String::new(),
+                implementation_complexity: Complexity::Low,
+                hardware_requirements: String::new(),
+                dependencies: vec![],
+                relevance_score: 0.5,
+                integration_approach: String::new(),
+                selected: true,
+                related_papers: vec![],
+            },
+        );
+        variant.benchmark_results = Some(BenchmarkResults {
+            composite_score: Some(score),
+            ..Default::default()
+        });
+        variant
+    }
+
+    #[test]
+    fn entries_follow_the_variant_not_its_branch() {
+        let mut leaderboard = Leaderboard::new("/src/shop");
+        let first = scored(1, "Caching", 60.0);
+        leaderboard.record(&first);
+        leaderboard.record(&scored(1, "Caching", 70.0));
+        assert_eq!(leaderboard.entries.len(), 1);
+        assert_eq!(leaderboard.entries[0].composite_score, 70.0);
+
+        // Another variant on a branch of the same name gets an entry of its own.
+        let mut other = scored(2, "Caching", 50.0);
+        other.branch_name = first.branch_name.clone();
+        leaderboard.record(&other);
+        assert_eq!(leaderboard.entries.len(), 2);
+        assert_eq!(leaderboard.entries[1].variant_id.as_ref(), Some(&other.id));
+    }
+
+    #[test]
+    fn projects_with_alike_paths_keep_their_own_leaderboards() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path().join("leaderboards");
+        let (dashed, nested) = (dir.path().join("a-b"), dir.path().join("a").join("b"));
+        std::fs::create_dir_all(&data).unwrap();
+        std::fs::create_dir_all(&dashed).unwrap();
+        std::fs::create_dir_all(&nested).unwrap();
+
+        // A leaderboard saved under the old slug name for one of them.
+        let mut legacy = Leaderboard::new(&dashed);
+        legacy.record(&scored(1, "Caching", 60.0));
+        let [_, slugged, _] = project_files(&data, &dashed);
+        std::fs::write(&slugged, serde_json::to_string(&legacy).unwrap()).unwrap();
+
+        assert_eq!(
+            Leaderboard::load_from(&data, &dashed)
+                .unwrap()
+                .entries
+                .len(),
+            1
+        );
+        assert!(Leaderboard::load_from(&data, &nested)
+            .unwrap()
+            .entries
+            .is_empty());
+        assert_ne!(
+            Leaderboard::path_for(&dashed).unwrap(),
+            Leaderboard::path_for(&nested).unwrap()
+        );
+    }
+}
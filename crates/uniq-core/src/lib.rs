@@ -1,9 +1,23 @@
 pub mod benchmark;
 pub mod config;
+pub mod conflict;
 pub mod error;
+pub mod estimate;
+pub mod export;
+pub mod fuzzy;
+pub mod git;
+pub mod hooks;
+pub mod leaderboard;
+pub mod llm;
 pub mod merge;
+pub mod metrics;
+pub mod plugin;
 pub mod project;
+pub mod promote;
+pub mod prompts;
+pub mod report;
 pub mod research;
+pub mod session;
 pub mod variant;
 
 pub use config::UniqConfig;
@@ -0,0 +1,129 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// An LLM API the sidecar can call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    #[default]
+    Anthropic,
+    /// OpenAI, or any server with its chat completions API.
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// A local Ollama server.
+    Ollama,
+}
+
+impl LlmProvider {
+    /// Model used when the config doesn't name one.
+    pub fn default_model(self) -> &'static str {
+        match self {
+            LlmProvider::Anthropic => "claude-sonnet-4-20250514",
+            LlmProvider::OpenAi => "gpt-4o",
+            LlmProvider::Ollama => "llama3.1",
+        }
+    }
+
+    /// Whether calls cost money, for estimates.
+    pub fn is_metered(self) -> bool {
+        self != LlmProvider::Ollama
+    }
+}
+
+impl std::fmt::Display for LlmProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LlmProvider::Anthropic => write!(f, "Anthropic"),
+            LlmProvider::OpenAi => write!(f, "OpenAI"),
+            LlmProvider::Ollama => write!(f, "Ollama"),
+        }
+    }
+}
+
+/// The backend a sidecar request runs on, sent with every request that
+/// calls an LLM.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LlmBackend {
+    pub provider: LlmProvider,
+    pub model: String,
+    /// API root, e.g. `http://localhost:11434/v1`. The sidecar uses the
+    /// provider's usual one when unset.
+    pub base_url: Option<String>,
+}
+
+impl Default for LlmBackend {
+    fn default() -> Self {
+        let provider = LlmProvider::default();
+        Self {
+            provider,
+            model: provider.default_model().to_string(),
+            base_url: None,
+        }
+    }
+}
+
+/// A step of the pipeline that calls an LLM, each of which can use its own
+/// model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PipelineStage {
+    /// Project analysis.
+    Intake,
+    /// Technique extraction from papers.
+    Extraction,
+    /// Planning and generating variants.
+    Generation,
+    /// Merging variants and reconciling their conflicts.
+    Merge,
+    /// Judging benchmarked variants.
+    Judge,
+}
+
+/// Models for particular stages, replacing the backend's model there, e.g.
+/// a cheap model for extraction and judging and a strong one for
+/// generation and merges.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageModels {
+    #[serde(default)]
+    pub intake: Option<String>,
+    #[serde(default)]
+    pub extraction: Option<String>,
+    #[serde(default)]
+    pub generation: Option<String>,
+    #[serde(default)]
+    pub merge: Option<String>,
+    #[serde(default)]
+    pub judge: Option<String>,
+}
+
+impl StageModels {
+    /// The model set for `stage`, if any.
+    pub fn get(&self, stage: PipelineStage) -> Option<&str> {
+        let model = match stage {
+            PipelineStage::Intake => &self.intake,
+            PipelineStage::Extraction => &self.extraction,
+            PipelineStage::Generation => &self.generation,
+            PipelineStage::Merge => &self.merge,
+            PipelineStage::Judge => &self.judge,
+        };
+        model.as_deref().map(str::trim).filter(|m| !m.is_empty())
+    }
+}
+
+/// The backend each stage runs on: one backend, with the model swapped
+/// per stage.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LlmRoutes {
+    pub backend: LlmBackend,
+    pub models: StageModels,
+}
+
+impl LlmRoutes {
+    /// The backend requests for `stage` run on.
+    pub fn for_stage(&self, stage: PipelineStage) -> LlmBackend {
+        let mut backend = self.backend.clone();
+        if let Some(model) = self.models.get(stage) {
+            backend.model = model.to_string();
+        }
+        backend
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use crate::variant::VariantId;
+use crate::benchmark::BenchmarkResults;
+use crate::variant::{Variant, VariantId};
 
 /// How much of a particular variant's technique to integrate in a merge.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -18,6 +19,14 @@ pub enum BlendRatio {
 }
 
 impl BlendRatio {
+    /// The ratio for a percentage, if it is one of the supported steps.
+    pub fn from_percent(percent: u8) -> Option<BlendRatio> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|b| b.as_percent() == percent)
+    }
+
     /// Get the numeric percentage.
     pub fn as_percent(&self) -> u8 {
         match self {
@@ -80,47 +89,269 @@ impl std::fmt::Display for BlendRatio {
     }
 }
 
-/// Specification for merging two variants together.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MergeSpec {
-    /// First parent variant.
-    pub parent_a: VariantId,
+/// Most variants a single merge can combine.
+pub const MAX_MERGE_SOURCES: usize = 4;
+
+/// One variant feeding into a merge, and how much of it to integrate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MergeSource {
+    pub variant: VariantId,
+    pub blend: BlendRatio,
+    /// Blends for individual key components of the source's technique,
+    /// overriding `blend` for those parts. Empty: `blend` covers it all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ComponentBlend>,
+}
+
+impl MergeSource {
+    pub fn new(variant: VariantId, blend: BlendRatio) -> Self {
+        Self {
+            variant,
+            blend,
+            components: Vec::new(),
+        }
+    }
+}
+
+/// How much of one key component of a technique to integrate, e.g. all of
+/// A's indexing but none of its training loop.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ComponentBlend {
+    pub name: String,
+    pub blend: BlendRatio,
+}
+
+/// How the sources' techniques are fitted together in a merge, separately
+/// from how much of each is used.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// One technique forms the base layer; the others build on top of it.
+    LayeredIntegration,
+    /// Each technique stays behind a common interface with adapters.
+    AdapterPattern,
+    /// The techniques coexist, switchable with a configuration flag.
+    FeatureFlagged,
+}
+
+impl MergeStrategy {
+    pub const ALL: [MergeStrategy; 3] = [
+        MergeStrategy::LayeredIntegration,
+        MergeStrategy::AdapterPattern,
+        MergeStrategy::FeatureFlagged,
+    ];
 
-    /// Second parent variant.
-    pub parent_b: VariantId,
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergeStrategy::LayeredIntegration => "layered integration",
+            MergeStrategy::AdapterPattern => "adapter pattern",
+            MergeStrategy::FeatureFlagged => "feature-flagged coexistence",
+        }
+    }
 
-    /// How much of variant A's technique to integrate.
-    pub blend_a: BlendRatio,
+    /// Instruction passed to the model alongside the blend ratios.
+    pub fn directive(&self) -> &'static str {
+        match self {
+            MergeStrategy::LayeredIntegration => {
+                "Layer the techniques: the highest-blend technique is the base architecture \
+                 and the others are integrated as stages or enhancements built on top of it."
+            }
+            MergeStrategy::AdapterPattern => {
+                "Put the techniques behind one shared interface and write an adapter for each, \
+                 so they can be composed or swapped without touching the callers."
+            }
+            MergeStrategy::FeatureFlagged => {
+                "Keep each technique's implementation intact and let them coexist, selected \
+                 by a configuration flag that defaults to the highest-blend technique."
+            }
+        }
+    }
+}
 
-    /// How much of variant B's technique to integrate.
-    pub blend_b: BlendRatio,
+/// Specification for merging two to `MAX_MERGE_SOURCES` variants together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeSpec {
+    /// Source variants, in the order the user picked them.
+    pub sources: Vec<MergeSource>,
+    /// Free-form instructions for combining the sources, for intent the
+    /// blend ratios can't express.
+    pub guidance: Option<String>,
+    /// How to fit the techniques together; the model decides when unset.
+    pub strategy: Option<MergeStrategy>,
 }
 
 impl MergeSpec {
+    /// A merge of two variants.
     pub fn new(
         parent_a: VariantId,
         parent_b: VariantId,
         blend_a: BlendRatio,
         blend_b: BlendRatio,
     ) -> Self {
+        Self::from_sources(vec![
+            MergeSource::new(parent_a, blend_a),
+            MergeSource::new(parent_b, blend_b),
+        ])
+    }
+
+    pub fn from_sources(sources: Vec<MergeSource>) -> Self {
         Self {
-            parent_a,
-            parent_b,
-            blend_a,
-            blend_b,
+            sources,
+            guidance: None,
+            strategy: None,
         }
     }
 
+    pub fn with_guidance(mut self, guidance: Option<String>) -> Self {
+        self.guidance = guidance.filter(|g| !g.trim().is_empty());
+        self
+    }
+
+    pub fn with_strategy(mut self, strategy: Option<MergeStrategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
     /// Generate a human-readable summary for display.
     pub fn summary(&self) -> String {
-        format!(
-            "{} ({}%) + {} ({}%)",
-            self.parent_a,
-            self.blend_a.as_percent(),
-            self.parent_b,
-            self.blend_b.as_percent(),
-        )
+        self.sources
+            .iter()
+            .map(|s| format!("{} ({}%)", s.variant, s.blend.as_percent()))
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+}
+
+/// A file more than one merge source changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedFile {
+    pub path: String,
+    /// Indices of the sources that changed it.
+    pub sources: Vec<usize>,
+    /// Whether git can't merge the sources' changes to it cleanly. Files
+    /// that merge cleanly may still need their changes reconciled.
+    pub conflict: bool,
+}
+
+/// What merging a set of branches would run into, worked out before
+/// starting the merge.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergePreview {
+    /// Files changed by more than one source, conflicts first.
+    pub shared: Vec<SharedFile>,
+    /// Number of files only one source changed.
+    pub unshared: usize,
+}
+
+impl MergePreview {
+    pub fn conflicts(&self) -> usize {
+        self.shared.iter().filter(|f| f.conflict).count()
+    }
+}
+
+/// A merge worth trying: two variants that each lead on something the
+/// other is weaker at.
+#[derive(Debug, Clone)]
+pub struct MergeSuggestion {
+    pub sources: Vec<MergeSource>,
+    /// Why the pair complement each other, e.g. "V1 leads on quality, V3
+    /// on runtime".
+    pub reason: String,
+    /// How strongly the pair complement each other; suggestions are sorted
+    /// by this.
+    pub strength: f64,
+}
+
+/// Smallest relative lead that counts as a variant being stronger.
+const LEAD_MARGIN: f64 = 0.05;
+
+/// Composite score gap under which neither side of a suggestion dominates.
+const BALANCED_GAP: f64 = 5.0;
+
+/// Reads one benchmark signal from a variant's results.
+type Signal = fn(&BenchmarkResults) -> Option<f64>;
+
+/// Benchmark signals a merge can combine: name, how to read it, and
+/// whether higher is better (false for costs like runtime).
+const SIGNALS: [(&str, Signal, bool); 4] = [
+    ("quality", |r| r.judge.as_ref().map(|j| j.overall), true),
+    ("tests", |r| r.execution.as_ref()?.test_pass_rate, true),
+    ("runtime", |r| r.execution.as_ref()?.runtime_ms, false),
+    ("memory", |r| r.execution.as_ref()?.memory_mb, false),
+];
+
+/// Relative lead of `a` over `b` on each signal both have, where `a` is
+/// ahead by at least `LEAD_MARGIN`.
+fn leads(a: &BenchmarkResults, b: &BenchmarkResults) -> Vec<(&'static str, f64)> {
+    SIGNALS
+        .iter()
+        .filter_map(|(name, read, higher_is_better)| {
+            let (a, b) = (read(a)?, read(b)?);
+            let gain = if *higher_is_better { a - b } else { b - a };
+            let lead = gain / b.abs().max(f64::EPSILON);
+            (lead >= LEAD_MARGIN).then_some((*name, lead))
+        })
+        .collect()
+}
+
+/// Pairs of benchmarked variants worth merging, strongest first. Each
+/// pair leads on different signals; the variant with the clearly higher
+/// composite score gets the larger blend.
+pub fn suggest_merges(variants: &[Variant]) -> Vec<MergeSuggestion> {
+    let scored: Vec<(&Variant, &BenchmarkResults)> = variants
+        .iter()
+        .filter_map(|v| Some((v, v.benchmark_results.as_ref()?)))
+        .collect();
+
+    let mut suggestions = Vec::new();
+    for (i, (a, results_a)) in scored.iter().enumerate() {
+        for (b, results_b) in &scored[i + 1..] {
+            let a_leads = leads(results_a, results_b);
+            let b_leads = leads(results_b, results_a);
+            if a_leads.is_empty() || b_leads.is_empty() {
+                continue;
+            }
+
+            let gap = results_a
+                .composite_score
+                .zip(results_b.composite_score)
+                .map(|(a, b)| a - b)
+                .unwrap_or(0.0);
+            let (blend_a, blend_b) = if gap > BALANCED_GAP {
+                (BlendRatio::ThreeQuarter, BlendRatio::Quarter)
+            } else if gap < -BALANCED_GAP {
+                (BlendRatio::Quarter, BlendRatio::ThreeQuarter)
+            } else {
+                (BlendRatio::Half, BlendRatio::Half)
+            };
+
+            let names = |leads: &[(&str, f64)]| {
+                leads
+                    .iter()
+                    .map(|(n, _)| *n)
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            };
+            let total = |leads: &[(&str, f64)]| leads.iter().map(|(_, l)| l).sum::<f64>();
+            suggestions.push(MergeSuggestion {
+                sources: vec![
+                    MergeSource::new(a.id.clone(), blend_a),
+                    MergeSource::new(b.id.clone(), blend_b),
+                ],
+                reason: format!(
+                    "{} leads on {}, {} on {}",
+                    a.display_name,
+                    names(&a_leads),
+                    b.display_name,
+                    names(&b_leads)
+                ),
+                // Balanced pairs beat one-sided ones with a single big lead.
+                strength: total(&a_leads).min(total(&b_leads)),
+            });
+        }
     }
+    suggestions.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+    suggestions
 }
 
 /// A node in the merge lineage tree, used for visualizing how merged variants
@@ -133,13 +364,10 @@ pub enum LineageNode {
         technique_name: String,
         paper_id: String,
     },
-    /// A merge node — has two parent lineage nodes.
+    /// A merge node — one parent lineage node per merge source.
     Merged {
         variant_id: VariantId,
-        blend_a: BlendRatio,
-        blend_b: BlendRatio,
-        parent_a: Box<LineageNode>,
-        parent_b: Box<LineageNode>,
+        parents: Vec<(BlendRatio, LineageNode)>,
     },
 }
 
@@ -151,3 +379,53 @@ impl LineageNode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::{ExecutionMetrics, JudgeScores};
+
+    fn benchmarked(index: usize, overall: f64, runtime_ms: f64, composite: f64) -> Variant {
+        let spec = MergeSpec::new(
+            VariantId::new("x"),
+            VariantId::new("y"),
+            BlendRatio::Half,
+            BlendRatio::Half,
+        );
+        let mut variant = Variant::from_merge(index, spec, format!("v{}", index));
+        let judge: JudgeScores = serde_json::from_value(serde_json::json!({
+            "code_quality": overall, "novelty": overall, "feasibility": overall,
+            "goal_alignment": overall, "completeness": overall, "overall": overall,
+            "explanation": "",
+        }))
+        .unwrap();
+        let execution: ExecutionMetrics = serde_json::from_value(serde_json::json!({
+            "build_success": true, "build_error": null, "test_pass_rate": null,
+            "tests_passed": null, "tests_total": null, "runtime_ms": runtime_ms,
+            "memory_mb": null, "custom_metrics": {},
+        }))
+        .unwrap();
+        variant.benchmark_results = Some(BenchmarkResults {
+            execution: Some(execution),
+            judge: Some(judge),
+            composite_score: Some(composite),
+            ..Default::default()
+        });
+        variant
+    }
+
+    #[test]
+    fn test_suggests_complementary_pairs() {
+        let quality = benchmarked(1, 9.0, 400.0, 80.0);
+        let fast = benchmarked(2, 6.0, 100.0, 60.0);
+        // Worse than both on everything, so never suggested.
+        let dominated = benchmarked(3, 5.0, 500.0, 40.0);
+
+        let suggestions = suggest_merges(&[quality, fast, dominated]);
+        assert_eq!(suggestions.len(), 1);
+        let top = &suggestions[0];
+        assert_eq!(top.reason, "M1: v1 leads on quality, M2: v2 on runtime");
+        assert_eq!(top.sources[0].blend, BlendRatio::ThreeQuarter);
+        assert_eq!(top.sources[1].blend, BlendRatio::Quarter);
+    }
+}
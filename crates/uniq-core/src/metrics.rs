@@ -0,0 +1,167 @@
+//! Opt-in usage stats kept on this machine: how long each step of a run
+//! takes, how often it fails and how many papers searches turn up.
+//!
+//! Nothing identifying is recorded (no paths, project names, descriptions
+//! or queries) and nothing is uploaded. The file is plain JSON, for the user
+//! to read or attach to an issue.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::error::{Result, UniqError};
+
+/// Searches whose paper counts are kept; older ones are dropped.
+const MAX_QUERIES: usize = 200;
+
+/// A timed step of the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    Analysis,
+    Search,
+    Extraction,
+    Generation,
+    Merge,
+    Benchmark,
+}
+
+/// Outcomes and durations of every run of one step.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StepStats {
+    pub succeeded: u64,
+    pub failed: u64,
+    /// Time spent in the step over all runs.
+    pub total_secs: f64,
+    pub longest_secs: f64,
+}
+
+impl StepStats {
+    pub fn runs(&self) -> u64 {
+        self.succeeded + self.failed
+    }
+
+    pub fn mean_secs(&self) -> Option<f64> {
+        match self.runs() {
+            0 => None,
+            runs => Some(self.total_secs / runs as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageMetrics {
+    /// When recording started.
+    pub since: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub steps: BTreeMap<Step, StepStats>,
+    /// Papers found by each search query, oldest first.
+    #[serde(default)]
+    pub papers_per_query: Vec<usize>,
+}
+
+impl UsageMetrics {
+    pub fn new() -> Self {
+        let now = Utc::now();
+        Self {
+            since: now,
+            updated_at: now,
+            steps: BTreeMap::new(),
+            papers_per_query: Vec::new(),
+        }
+    }
+
+    /// Metrics file (`~/.local/share/uniq/metrics.json` on Linux).
+    pub fn path() -> Result<PathBuf> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| UniqError::Config("Could not determine data directory".into()))?;
+        Ok(data_dir.join("uniq").join("metrics.json"))
+    }
+
+    /// Load the recorded metrics, or start fresh if there are none yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Save the metrics, stamping `updated_at`.
+    pub fn save(&mut self) -> Result<()> {
+        self.updated_at = Utc::now();
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record one run of `step` that took `duration`.
+    pub fn record(&mut self, step: Step, duration: Duration, succeeded: bool) {
+        let stats = self.steps.entry(step).or_default();
+        if succeeded {
+            stats.succeeded += 1;
+        } else {
+            stats.failed += 1;
+        }
+        let secs = duration.as_secs_f64();
+        stats.total_secs += secs;
+        stats.longest_secs = stats.longest_secs.max(secs);
+    }
+
+    /// Record how many papers a search query found.
+    pub fn record_query(&mut self, papers: usize) {
+        self.papers_per_query.push(papers);
+        let excess = self.papers_per_query.len().saturating_sub(MAX_QUERIES);
+        self.papers_per_query.drain(..excess);
+    }
+}
+
+impl Default for UsageMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_tracks_outcomes_and_durations() {
+        let mut metrics = UsageMetrics::new();
+        metrics.record(Step::Search, Duration::from_secs(2), true);
+        metrics.record(Step::Search, Duration::from_secs(6), false);
+
+        let search = &metrics.steps[&Step::Search];
+        assert_eq!((search.succeeded, search.failed), (1, 1));
+        assert_eq!(search.longest_secs, 6.0);
+        assert_eq!(search.mean_secs(), Some(4.0));
+        assert_eq!(StepStats::default().mean_secs(), None);
+    }
+
+    #[test]
+    fn record_query_keeps_the_latest() {
+        let mut metrics = UsageMetrics::new();
+        for papers in 0..MAX_QUERIES + 5 {
+            metrics.record_query(papers);
+        }
+        assert_eq!(metrics.papers_per_query.len(), MAX_QUERIES);
+        assert_eq!(metrics.papers_per_query[0], 5);
+    }
+
+    #[test]
+    fn steps_serialize_by_name() {
+        let mut metrics = UsageMetrics::new();
+        metrics.record(Step::Analysis, Duration::from_millis(500), true);
+        let json = serde_json::to_value(&metrics).unwrap();
+        assert_eq!(json["steps"]["analysis"]["succeeded"], 1);
+    }
+}
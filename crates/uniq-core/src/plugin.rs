@@ -0,0 +1,419 @@
+//! Extension points for organizations to plug in their own paper indexes and
+//! evaluation harnesses without forking uniq.
+//!
+//! A [`ResearchProvider`] adds papers to the research phase's search, and a
+//! [`MetricCollector`] adds metrics to each variant's execution benchmark.
+//! Both are registered in a [`PluginRegistry`]. Embedders register their own
+//! implementations; everyone else configures [`CommandPlugin`]s, external
+//! commands that talk JSON over stdin and stdout:
+//!
+//! - A research plugin gets `{"queries": [...], "limit": 20}` and answers
+//!   `{"papers": [{"id": "...", "title": "...", ...}]}`, with the fields of
+//!   [`PaperMeta`] other than `source` and `relevance_score`, all but `id`
+//!   and `title` optional.
+//! - A metrics plugin is run in the variant's worktree, gets
+//!   `{"branch": "...", "path": "..."}` and answers
+//!   `{"metrics": [{"name": "...", "value": 1.0, "unit": "ms",
+//!   "higher_is_better": false}]}`, `unit` and `higher_is_better` optional.
+
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::Stdio;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::benchmark::runner::{kill, shell};
+use crate::benchmark::MetricValue;
+use crate::config::{PluginCommand, PluginsConfig};
+use crate::error::{Result, UniqError};
+use crate::research::{PaperMeta, PaperSource};
+
+/// How often a running command is checked for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How much of a failed command's stderr goes in its error.
+const MAX_STDERR: usize = 500;
+
+/// A source of papers besides Semantic Scholar and arXiv.
+pub trait ResearchProvider: Send + Sync {
+    /// Shown in the UI and prefixed to the IDs of the papers found.
+    fn name(&self) -> &str;
+
+    /// Up to `limit` papers relevant to any of `queries`.
+    fn search(&self, queries: &[String], limit: usize) -> Result<Vec<PaperMeta>>;
+}
+
+/// A source of benchmark metrics besides the built-in execution benchmark.
+pub trait MetricCollector: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Metrics for `branch`, checked out at `dir`.
+    fn collect(&self, dir: &Path, branch: &str) -> Result<Vec<MetricValue>>;
+}
+
+/// A plugin that failed, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginFailure {
+    pub plugin: String,
+    pub error: String,
+}
+
+impl PluginFailure {
+    fn new(plugin: &str, error: UniqError) -> Self {
+        let error = match error {
+            UniqError::Plugin(message) => message,
+            other => other.to_string(),
+        };
+        Self {
+            plugin: plugin.to_string(),
+            error,
+        }
+    }
+}
+
+/// The research providers and metric collectors in use.
+#[derive(Default)]
+pub struct PluginRegistry {
+    providers: Vec<Box<dyn ResearchProvider>>,
+    collectors: Vec<Box<dyn MetricCollector>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The command plugins configured in `config`.
+    pub fn from_config(config: &PluginsConfig) -> Self {
+        let mut registry = Self::new();
+        for command in &config.research {
+            registry.register_provider(CommandPlugin::new(command.clone()));
+        }
+        for command in &config.metrics {
+            registry.register_collector(CommandPlugin::new(command.clone()));
+        }
+        registry
+    }
+
+    pub fn register_provider(&mut self, provider: impl ResearchProvider + 'static) {
+        self.providers.push(Box::new(provider));
+    }
+
+    pub fn register_collector(&mut self, collector: impl MetricCollector + 'static) {
+        self.collectors.push(Box::new(collector));
+    }
+
+    pub fn has_providers(&self) -> bool {
+        !self.providers.is_empty()
+    }
+
+    pub fn has_collectors(&self) -> bool {
+        !self.collectors.is_empty()
+    }
+
+    /// Search every provider. Papers are marked as coming from a plugin and
+    /// their IDs prefixed with its name, so they can't collide with the
+    /// built-in sources' or each other's. A failing provider doesn't stop
+    /// the others.
+    pub fn search(&self, queries: &[String], limit: usize) -> (Vec<PaperMeta>, Vec<PluginFailure>) {
+        let mut papers = Vec::new();
+        let mut failures = Vec::new();
+        for provider in &self.providers {
+            match provider.search(queries, limit) {
+                Ok(found) => papers.extend(found.into_iter().take(limit).map(|mut paper| {
+                    paper.id = format!("{}:{}", provider.name(), paper.id);
+                    paper.source = PaperSource::Plugin;
+                    paper
+                })),
+                Err(e) => failures.push(PluginFailure::new(provider.name(), e)),
+            }
+        }
+        (papers, failures)
+    }
+
+    /// Run every collector on `branch`, checked out at `dir`. When two
+    /// report a metric of the same name, the later one wins.
+    pub fn collect_metrics(
+        &self,
+        dir: &Path,
+        branch: &str,
+    ) -> (Vec<MetricValue>, Vec<PluginFailure>) {
+        let mut metrics: Vec<MetricValue> = Vec::new();
+        let mut failures = Vec::new();
+        for collector in &self.collectors {
+            match collector.collect(dir, branch) {
+                Ok(found) => {
+                    for metric in found {
+                        metrics.retain(|m| m.name != metric.name);
+                        metrics.push(metric);
+                    }
+                }
+                Err(e) => failures.push(PluginFailure::new(collector.name(), e)),
+            }
+        }
+        (metrics, failures)
+    }
+}
+
+/// A plugin run as an external command; see the [module docs](self) for
+/// what it reads and writes.
+#[derive(Debug, Clone)]
+pub struct CommandPlugin {
+    config: PluginCommand,
+}
+
+impl CommandPlugin {
+    pub fn new(config: PluginCommand) -> Self {
+        Self { config }
+    }
+
+    /// Run the command in `dir` with `request` on stdin and parse what it
+    /// prints.
+    fn call<T: DeserializeOwned>(&self, dir: &Path, request: serde_json::Value) -> Result<T> {
+        let timeout = Duration::from_secs(self.config.timeout_seconds);
+        let stdout = exchange(&self.config.command, dir, request.to_string(), timeout)
+            .map_err(UniqError::Plugin)?;
+        serde_json::from_str(&stdout)
+            .map_err(|e| UniqError::Plugin(format!("invalid answer: {}", e)))
+    }
+}
+
+/// Run `command` in `dir` with `input` on stdin, and return what it printed
+/// if it succeeded within `timeout`, or why it didn't.
+pub(crate) fn exchange(
+    command: &str,
+    dir: &Path,
+    input: String,
+    timeout: Duration,
+) -> std::result::Result<String, String> {
+    let mut child = shell(command)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not start `{}`: {}", command, e))?;
+
+    // Written and read on their own threads so a command that answers
+    // before reading all of its input, or says a lot, can't deadlock.
+    let mut stdin = child.stdin.take();
+    let writer = thread::spawn(move || {
+        if let Some(stdin) = stdin.as_mut() {
+            let _ = stdin.write_all(input.as_bytes());
+        }
+    });
+    let stdout = read_all(child.stdout.take());
+    let stderr = read_all(child.stderr.take());
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() >= timeout => {
+                kill(&mut child);
+                return Err(format!("no answer within {}s", timeout.as_secs()));
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    let _ = writer.join();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        let stderr = stderr.trim();
+        let mut end = stderr.len().min(MAX_STDERR);
+        while !stderr.is_char_boundary(end) {
+            end -= 1;
+        }
+        return Err(format!("exited with {}: {}", status, &stderr[..end]));
+    }
+    Ok(stdout)
+}
+
+fn read_all(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+#[derive(Deserialize)]
+struct PapersAnswer {
+    papers: Vec<PluginPaper>,
+}
+
+#[derive(Deserialize)]
+struct PluginPaper {
+    id: String,
+    title: String,
+    #[serde(default)]
+    authors: Vec<String>,
+    year: Option<u16>,
+    published_date: Option<NaiveDate>,
+    #[serde(default, alias = "abstract")]
+    abstract_text: String,
+    citation_count: Option<u32>,
+    #[serde(default)]
+    url: String,
+    pdf_url: Option<String>,
+    doi: Option<String>,
+    #[serde(default)]
+    fields: Vec<String>,
+}
+
+impl From<PluginPaper> for PaperMeta {
+    fn from(paper: PluginPaper) -> Self {
+        PaperMeta {
+            id: paper.id,
+            title: paper.title,
+            authors: paper.authors,
+            year: paper.year,
+            published_date: paper.published_date,
+            abstract_text: paper.abstract_text,
+            citation_count: paper.citation_count,
+            url: paper.url,
+            pdf_url: paper.pdf_url,
+            doi: paper.doi,
+            source: PaperSource::Plugin,
+            fields: paper.fields,
+            relevance_score: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MetricsAnswer {
+    metrics: Vec<PluginMetric>,
+}
+
+#[derive(Deserialize)]
+struct PluginMetric {
+    name: String,
+    value: f64,
+    #[serde(default)]
+    unit: String,
+    #[serde(default = "default_higher_is_better")]
+    higher_is_better: bool,
+}
+
+fn default_higher_is_better() -> bool {
+    true
+}
+
+impl ResearchProvider for CommandPlugin {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn search(&self, queries: &[String], limit: usize) -> Result<Vec<PaperMeta>> {
+        let answer: PapersAnswer = self.call(
+            &std::env::temp_dir(),
+            json!({ "queries": queries, "limit": limit }),
+        )?;
+        Ok(answer.papers.into_iter().map(PaperMeta::from).collect())
+    }
+}
+
+impl MetricCollector for CommandPlugin {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn collect(&self, dir: &Path, branch: &str) -> Result<Vec<MetricValue>> {
+        let answer: MetricsAnswer = self.call(
+            dir,
+            json!({ "branch": branch, "path": dir.to_string_lossy() }),
+        )?;
+        Ok(answer
+            .metrics
+            .into_iter()
+            .map(|metric| MetricValue {
+                name: metric.name,
+                value: metric.value,
+                unit: metric.unit,
+                higher_is_better: metric.higher_is_better,
+            })
+            .collect())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn plugin(name: &str, command: &str) -> CommandPlugin {
+        CommandPlugin::new(PluginCommand {
+            name: name.into(),
+            command: command.into(),
+            timeout_seconds: 5,
+        })
+    }
+
+    #[test]
+    fn searches_command_plugins_and_reports_failures() {
+        let mut registry = PluginRegistry::new();
+        registry.register_provider(plugin(
+            "wiki",
+            r#"grep -q '"limit":5' && echo '{"papers": [{"id": "42", "title": "Internal", "abstract": "Text", "year": 2024}]}'"#,
+        ));
+        registry.register_provider(plugin("broken", "echo oops >&2; exit 3"));
+        registry.register_provider(plugin("garbled", "echo not json"));
+
+        let (papers, failures) = registry.search(&["caching".into()], 5);
+        assert_eq!(papers.len(), 1);
+        assert_eq!(papers[0].id, "wiki:42");
+        assert_eq!(papers[0].abstract_text, "Text");
+        assert_eq!(papers[0].year, Some(2024));
+        assert!(matches!(papers[0].source, PaperSource::Plugin));
+
+        let failed: Vec<_> = failures.iter().map(|f| f.plugin.as_str()).collect();
+        assert_eq!(failed, ["broken", "garbled"]);
+        assert!(failures[0].error.contains("oops"), "{}", failures[0].error);
+    }
+
+    #[test]
+    fn collects_metrics_in_the_worktree() {
+        let dir = std::env::temp_dir().join(format!("uniq-plugin-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("score"), "0.9").unwrap();
+
+        let mut registry = PluginRegistry::new();
+        registry.register_collector(plugin(
+            "eval",
+            r#"echo "{\"metrics\": [{\"name\": \"score\", \"value\": $(cat score)}, {\"name\": \"latency\", \"value\": 12, \"unit\": \"ms\", \"higher_is_better\": false}]}""#,
+        ));
+        registry.register_collector(plugin(
+            "override",
+            r#"echo '{"metrics": [{"name": "score", "value": 1}]}'"#,
+        ));
+        registry.register_collector(CommandPlugin::new(PluginCommand {
+            name: "slow".into(),
+            command: "sleep 5".into(),
+            timeout_seconds: 0,
+        }));
+
+        let (metrics, failures) = registry.collect_metrics(&dir, "uniq/v1");
+        let found: Vec<_> = metrics
+            .iter()
+            .map(|m| (m.name.as_str(), m.value, m.higher_is_better))
+            .collect();
+        assert_eq!(found, [("latency", 12.0, false), ("score", 1.0, true)]);
+        assert_eq!(failures.len(), 1);
+        assert!(
+            failures[0].error.contains("no answer"),
+            "{}",
+            failures[0].error
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
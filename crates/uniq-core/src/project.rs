@@ -1,3 +1,6 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::path::PathBuf;
 
@@ -46,8 +49,18 @@ impl<'de> Deserialize<'de> for Language {
     }
 }
 
+impl JsonSchema for Language {
+    fn schema_name() -> String {
+        "Language".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 /// A detected framework or major dependency.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DetectedFramework {
     pub name: String,
     pub version: Option<String>,
@@ -90,8 +103,18 @@ impl<'de> Deserialize<'de> for FrameworkCategory {
     }
 }
 
+impl JsonSchema for FrameworkCategory {
+    fn schema_name() -> String {
+        "FrameworkCategory".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 /// Represents a point in the project where AI techniques could be integrated.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct IntegrationPoint {
     pub file_path: PathBuf,
     pub description: String,
@@ -124,8 +147,18 @@ impl<'de> Deserialize<'de> for IntegrationComplexity {
     }
 }
 
+impl JsonSchema for IntegrationComplexity {
+    fn schema_name() -> String {
+        "IntegrationComplexity".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 /// The complete profile of a user's project, generated by project analysis.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectProfile {
     /// Root path to the project.
     pub path: PathBuf,
@@ -154,3 +187,27 @@ pub struct ProjectProfile {
     /// Raw file tree (truncated to reasonable depth).
     pub file_tree: String,
 }
+
+impl ProjectProfile {
+    /// Whether `dependency` (e.g. `"scikit-learn>=1.3"`) matches a detected
+    /// framework. Names are compared case-insensitively, ignoring version
+    /// specifiers and `-`/`_` differences.
+    pub fn has_dependency(&self, dependency: &str) -> bool {
+        let wanted = normalize_dependency(dependency);
+        !wanted.is_empty()
+            && self
+                .frameworks
+                .iter()
+                .any(|f| normalize_dependency(&f.name) == wanted)
+    }
+}
+
+/// Reduce a dependency spec to a comparable package name.
+fn normalize_dependency(spec: &str) -> String {
+    spec.split(|c: char| "<>=~!@[( ".contains(c))
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase()
+        .replace('_', "-")
+}
@@ -0,0 +1,159 @@
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{Result, UniqError};
+use crate::project::ProjectProfile;
+use crate::variant::Variant;
+
+/// Pull request title for promoting `variant`.
+pub fn pull_request_title(variant: &Variant) -> String {
+    match variant.technique() {
+        Some(technique) => format!("Apply {} ({})", technique.name, technique.paper_id),
+        None => variant.display_name.clone(),
+    }
+}
+
+/// Pull request description: technique summary, paper citation and the
+/// variant's benchmark results.
+pub fn pull_request_body(variant: &Variant, project: Option<&ProjectProfile>) -> String {
+    let mut out = String::new();
+
+    let summary = variant
+        .transcript
+        .as_ref()
+        .map(|t| t.explanation.as_str())
+        .filter(|s| !s.is_empty());
+    if let Some(technique) = variant.technique() {
+        let _ = writeln!(out, "## Summary\n");
+        let _ = writeln!(out, "{}\n", summary.unwrap_or(&technique.methodology));
+        let _ = writeln!(out, "## Source\n");
+        let _ = writeln!(
+            out,
+            "{} (`{}`)\n",
+            technique.paper_title, technique.paper_id
+        );
+    } else if let Some(summary) = summary {
+        let _ = writeln!(out, "## Summary\n\n{}\n", summary);
+    }
+    if let Some(goal) = project.map(|p| &p.user_request).filter(|g| !g.is_empty()) {
+        let _ = writeln!(out, "**Goal:** {}\n", goal);
+    }
+
+    if let Some(ref results) = variant.benchmark_results {
+        let _ = writeln!(out, "## Benchmark results\n");
+        let _ = writeln!(out, "| Metric | Value |\n|---|---|");
+        if let Some(ref exec) = results.execution {
+            let build = if exec.build_success { "pass" } else { "fail" };
+            let _ = writeln!(out, "| Build | {} |", build);
+            if let Some(rate) = exec.test_pass_rate {
+                let _ = writeln!(out, "| Tests | {:.0}% |", rate * 100.0);
+            }
+            if let Some(runtime) = exec.runtime_ms {
+                let _ = writeln!(out, "| Runtime | {:.0} ms |", runtime);
+            }
+            if let Some(memory) = exec.memory_mb {
+                let _ = writeln!(out, "| Memory | {:.1} MB |", memory);
+            }
+        }
+        if let Some(ref judge) = results.judge {
+            let _ = writeln!(out, "| Code quality | {:.1}/10 |", judge.code_quality);
+            let _ = writeln!(out, "| Novelty | {:.1}/10 |", judge.novelty);
+            let _ = writeln!(out, "| Judge overall | {:.1}/10 |", judge.overall);
+        }
+        if let Some(ref rating) = results.user_rating {
+            let _ = writeln!(out, "| User rating | {}/5 |", rating.stars);
+        }
+        if let Some(score) = results.composite_score {
+            let _ = writeln!(out, "| **Composite** | **{:.1}** |", score);
+        }
+        let _ = writeln!(out);
+    }
+
+    if !variant.modified_files.is_empty() {
+        let _ = writeln!(out, "## Files\n");
+        for file in &variant.modified_files {
+            let _ = writeln!(out, "- `{}`", file);
+        }
+        let _ = writeln!(out);
+    }
+
+    let _ = writeln!(
+        out,
+        "_Generated with uniq from branch `{}`._",
+        variant.branch_name
+    );
+    out
+}
+
+/// Push `variant`'s branch to `origin` and open a pull request for it with
+/// the GitHub CLI. Returns the pull request URL.
+pub fn open_pull_request(
+    repo_path: &Path,
+    variant: &Variant,
+    project: Option<&ProjectProfile>,
+) -> Result<String> {
+    run(
+        repo_path,
+        "git",
+        &["push", "--set-upstream", "origin", &variant.branch_name],
+    )?;
+    let url = run(
+        repo_path,
+        "gh",
+        &[
+            "pr",
+            "create",
+            "--head",
+            &variant.branch_name,
+            "--title",
+            &pull_request_title(variant),
+            "--body",
+            &pull_request_body(variant, project),
+        ],
+    )?;
+    Ok(url.trim().to_string())
+}
+
+/// Run a command in `dir`, returning stdout or an error with its stderr.
+fn run(dir: &Path, program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| UniqError::PullRequest(format!("Could not run {}: {}", program, e)))?;
+    if !output.status.success() {
+        return Err(UniqError::PullRequest(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::BenchmarkResults;
+    use crate::merge::{BlendRatio, MergeSpec};
+    use crate::variant::VariantId;
+
+    #[test]
+    fn test_pull_request_body_includes_results() {
+        let spec = MergeSpec::new(
+            VariantId::new("a"),
+            VariantId::new("b"),
+            BlendRatio::Half,
+            BlendRatio::Half,
+        );
+        let mut variant = Variant::from_merge(1, spec, "ab".into());
+        variant.benchmark_results = Some(BenchmarkResults {
+            composite_score: Some(72.5),
+            ..Default::default()
+        });
+        let body = pull_request_body(&variant, None);
+        assert!(body.contains("| **Composite** | **72.5** |"));
+        assert!(body.contains("`uniq/merge-1-ab`"));
+    }
+}
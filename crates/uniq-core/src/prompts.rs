@@ -0,0 +1,252 @@
+//! User-supplied prompt templates, for domain-specific prompting without
+//! touching the sidecar.
+//!
+//! A template is a text file that replaces the sidecar's own instructions
+//! for one kind of request. It names the material the sidecar fills in as
+//! `{placeholder}`s, e.g. `{papers}` for the papers to extract techniques
+//! from; any other braces are left alone. The sidecar still appends the
+//! format it needs the answer in, so a template never has to describe it.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::{PromptsConfig, UniqConfig};
+use crate::error::{Result, UniqError};
+
+/// A request whose prompt can be replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PromptKind {
+    /// Extracting techniques from papers.
+    Extraction,
+    /// Planning and generating variants.
+    Generation,
+    /// Merging variants.
+    Merge,
+    /// Scoring a variant as the LLM judge.
+    Judge,
+}
+
+impl PromptKind {
+    pub const ALL: [PromptKind; 4] = [
+        PromptKind::Extraction,
+        PromptKind::Generation,
+        PromptKind::Merge,
+        PromptKind::Judge,
+    ];
+
+    /// Its key under `[prompts]`.
+    pub fn key(self) -> &'static str {
+        match self {
+            PromptKind::Extraction => "extraction",
+            PromptKind::Generation => "generation",
+            PromptKind::Merge => "merge",
+            PromptKind::Judge => "judge",
+        }
+    }
+
+    /// Placeholders a template must use, or the request would lose what
+    /// it's about.
+    pub fn required(self) -> &'static [&'static str] {
+        match self {
+            PromptKind::Extraction => &["papers"],
+            PromptKind::Generation => &["technique"],
+            PromptKind::Merge => &["techniques", "instructions"],
+            PromptKind::Judge => &["diff"],
+        }
+    }
+
+    /// Placeholders a template may use.
+    pub fn optional(self) -> &'static [&'static str] {
+        match self {
+            PromptKind::Extraction => &["project_summary", "user_request", "guidance"],
+            PromptKind::Generation => &["project", "user_request"],
+            PromptKind::Merge => &["project", "user_request", "guidance"],
+            PromptKind::Judge => &["user_request", "branch"],
+        }
+    }
+}
+
+/// The templates in use, one per kind at most. Kinds without one use the
+/// sidecar's built-in prompt.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptTemplates {
+    pub extraction: Option<String>,
+    pub generation: Option<String>,
+    pub merge: Option<String>,
+    pub judge: Option<String>,
+}
+
+impl PromptTemplates {
+    /// Read and check the templates `config` names. Relative paths are
+    /// taken from the directory `config.toml` is in.
+    pub fn load(config: &PromptsConfig) -> Result<Self> {
+        let config_path = UniqConfig::config_path()?;
+        let dir = config_path.parent().unwrap_or(Path::new("."));
+        Self::load_from(config, dir)
+    }
+
+    /// [`load`](Self::load), with relative paths taken from `dir`.
+    pub fn load_from(config: &PromptsConfig, dir: &Path) -> Result<Self> {
+        let mut templates = Self::default();
+        for kind in PromptKind::ALL {
+            let Some(path) = config.path(kind) else {
+                continue;
+            };
+            let path = dir.join(path);
+            let text = std::fs::read_to_string(&path).map_err(|e| {
+                UniqError::Config(format!(
+                    "Failed to read the {} prompt template {}: {e}",
+                    kind.key(),
+                    path.display()
+                ))
+            })?;
+            validate(kind, &text).map_err(|e| {
+                UniqError::Config(format!(
+                    "{} prompt template {}: {e}",
+                    kind.key(),
+                    path.display()
+                ))
+            })?;
+            *templates.slot(kind) = Some(text);
+        }
+        Ok(templates)
+    }
+
+    /// The template for `kind`, if there is one.
+    pub fn get(&self, kind: PromptKind) -> Option<&str> {
+        match kind {
+            PromptKind::Extraction => self.extraction.as_deref(),
+            PromptKind::Generation => self.generation.as_deref(),
+            PromptKind::Merge => self.merge.as_deref(),
+            PromptKind::Judge => self.judge.as_deref(),
+        }
+    }
+
+    fn slot(&mut self, kind: PromptKind) -> &mut Option<String> {
+        match kind {
+            PromptKind::Extraction => &mut self.extraction,
+            PromptKind::Generation => &mut self.generation,
+            PromptKind::Merge => &mut self.merge,
+            PromptKind::Judge => &mut self.judge,
+        }
+    }
+}
+
+impl PromptsConfig {
+    /// The file configured for `kind`, if any.
+    pub fn path(&self, kind: PromptKind) -> Option<&PathBuf> {
+        let path = match kind {
+            PromptKind::Extraction => &self.extraction,
+            PromptKind::Generation => &self.generation,
+            PromptKind::Merge => &self.merge,
+            PromptKind::Judge => &self.judge,
+        };
+        path.as_ref().filter(|p| !p.as_os_str().is_empty())
+    }
+}
+
+/// Check that `text` uses every placeholder `kind` requires and none it
+/// doesn't know, which would reach the model unfilled.
+pub fn validate(kind: PromptKind, text: &str) -> std::result::Result<(), String> {
+    let used = placeholders(text);
+    let missing: Vec<_> = kind
+        .required()
+        .iter()
+        .filter(|name| !used.contains(name))
+        .map(|name| format!("{{{name}}}"))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("missing {}", missing.join(", ")));
+    }
+    let unknown: Vec<_> = used
+        .iter()
+        .filter(|name| !kind.required().contains(name) && !kind.optional().contains(name))
+        .map(|name| format!("{{{name}}}"))
+        .collect();
+    if !unknown.is_empty() {
+        let known: Vec<_> = kind
+            .required()
+            .iter()
+            .chain(kind.optional())
+            .map(|name| format!("{{{name}}}"))
+            .collect();
+        return Err(format!(
+            "unknown {} (use {})",
+            unknown.join(", "),
+            known.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// The `{name}`s in `text`, in order and without repeats, where a name is
+/// lowercase letters, digits and underscores.
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    for (start, _) in text.match_indices('{') {
+        let rest = &text[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        let is_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if is_name && !found.contains(&name) {
+            found.push(name);
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_placeholders() {
+        let template = "Extract techniques for {user_request} from:\n{papers}\n\
+                        Answer like {\"name\": \"...\"}.";
+        assert_eq!(validate(PromptKind::Extraction, template), Ok(()));
+        assert_eq!(
+            validate(PromptKind::Extraction, "Read {project_summary}."),
+            Err("missing {papers}".to_string())
+        );
+        assert_eq!(
+            validate(PromptKind::Judge, "Rate {diff} against {goal}."),
+            Err("unknown {goal} (use {diff}, {user_request}, {branch})".to_string())
+        );
+        assert_eq!(
+            validate(PromptKind::Merge, "{techniques}"),
+            Err("missing {instructions}".to_string())
+        );
+    }
+
+    #[test]
+    fn loads_only_configured_templates() {
+        let dir = std::env::temp_dir().join(format!("uniq-prompts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("judge.txt"), "Be strict. {diff}").unwrap();
+        std::fs::write(dir.join("bad.txt"), "Implement it.").unwrap();
+
+        let config = PromptsConfig {
+            judge: Some("judge.txt".into()),
+            ..Default::default()
+        };
+        let templates = PromptTemplates::load_from(&config, &dir).unwrap();
+        assert_eq!(templates.get(PromptKind::Judge), Some("Be strict. {diff}"));
+        assert_eq!(templates.get(PromptKind::Generation), None);
+
+        let config = PromptsConfig {
+            generation: Some("bad.txt".into()),
+            ..Default::default()
+        };
+        let error = PromptTemplates::load_from(&config, &dir).unwrap_err();
+        assert!(
+            error.to_string().contains("missing {technique}"),
+            "{}",
+            error
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,735 @@
+//! Full-session report: what a uniq run looked at and produced, from the
+//! project and the literature reviewed to benchmark tables, judge commentary
+//! and where merged variants came from. Written as Markdown, or as a
+//! standalone HTML page to hand around.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use pulldown_cmark::{html, Event, Options, Parser};
+
+use crate::benchmark::{ExecutionMetrics, MetricDeltas};
+use crate::error::Result;
+use crate::export::{exports_dir, write_technique_brief};
+use crate::git::{self, DiffStat};
+use crate::project::Language;
+use crate::session::Session;
+use crate::variant::{Variant, VariantId, VariantOrigin};
+
+/// File format for session reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// File extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// How much each of the session's variants changed, by branch. Variants
+/// whose branch is gone are left out.
+pub fn diff_stats(session: &Session) -> HashMap<String, DiffStat> {
+    session
+        .variants
+        .iter()
+        .filter_map(|v| {
+            let stat = git::diff_stat(&session.project_path, &v.branch_name).ok()?;
+            Some((v.branch_name.clone(), stat))
+        })
+        .collect()
+}
+
+/// The report on `session` in `format`, with `stats` from [`diff_stats`].
+pub fn render(
+    session: &Session,
+    stats: &HashMap<String, DiffStat>,
+    format: ReportFormat,
+) -> String {
+    let markdown = session_markdown(session, stats);
+    match format {
+        ReportFormat::Markdown => markdown,
+        ReportFormat::Html => markdown_to_html(&title(session), &markdown),
+    }
+}
+
+/// Write the report on `session` to a timestamped file in [`exports_dir`]
+/// and return its path.
+pub fn export_report(session: &Session, format: ReportFormat) -> Result<PathBuf> {
+    let contents = render(session, &diff_stats(session), format);
+    let dir = exports_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "report-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    ));
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+fn title(session: &Session) -> String {
+    let name = session
+        .project_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| session.project_path.display().to_string());
+    format!("uniq report: {}", name)
+}
+
+/// The report as Markdown.
+pub fn session_markdown(session: &Session, stats: &HashMap<String, DiffStat>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", title(session));
+    let _ = writeln!(out, "**Project:** `{}`  ", session.project_path.display());
+    if let Some(profile) = &session.profile {
+        if !profile.user_request.is_empty() {
+            let _ = writeln!(out, "**Goal:** {}  ", profile.user_request);
+        }
+    }
+    let _ = writeln!(
+        out,
+        "_Generated {} · {} paper(s) · {} technique(s) · {} variant(s)_\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        session.papers.len(),
+        session.techniques.len(),
+        session.variants.len()
+    );
+
+    write_project(&mut out, session);
+    write_literature(&mut out, session);
+    write_techniques(&mut out, session);
+    write_variants(&mut out, session, stats);
+    write_benchmarks(&mut out, session);
+    write_commentary(&mut out, session);
+    write_lineage(&mut out, session);
+    write_discarded(&mut out, session);
+    out
+}
+
+fn write_project(out: &mut String, session: &Session) {
+    let Some(profile) = &session.profile else {
+        return;
+    };
+    let _ = writeln!(out, "## Project\n");
+    if !profile.summary.is_empty() {
+        let _ = writeln!(out, "{}\n", profile.summary);
+    }
+    if !profile.languages.is_empty() {
+        let languages: Vec<String> = profile
+            .languages
+            .iter()
+            .map(|language| match language {
+                Language::Other(name) => name.clone(),
+                known => format!("{:?}", known),
+            })
+            .collect();
+        let _ = writeln!(out, "- **Languages:** {}", languages.join(", "));
+    }
+    if !profile.frameworks.is_empty() {
+        let frameworks: Vec<&str> = profile.frameworks.iter().map(|f| f.name.as_str()).collect();
+        let _ = writeln!(out, "- **Frameworks:** {}", frameworks.join(", "));
+    }
+    let _ = writeln!(out, "- **Files:** {}\n", profile.file_count);
+}
+
+fn write_literature(out: &mut String, session: &Session) {
+    let _ = writeln!(out, "## Literature reviewed\n");
+    // Techniques extracted from each paper.
+    let mut extracted: HashMap<&str, Vec<&str>> = HashMap::new();
+    for card in &session.techniques {
+        let papers = std::iter::once(card.paper_id.as_str())
+            .chain(card.related_papers.iter().map(|p| p.id.as_str()));
+        for paper in papers {
+            extracted.entry(paper).or_default().push(&card.name);
+        }
+    }
+
+    if session.papers.is_empty() {
+        // Sessions saved before papers were kept still know what the
+        // techniques cite.
+        let mut cited = BTreeSet::new();
+        for card in &session.techniques {
+            cited.insert((card.paper_title.as_str(), card.paper_id.as_str()));
+            for paper in &card.related_papers {
+                cited.insert((paper.title.as_str(), paper.id.as_str()));
+            }
+        }
+        if cited.is_empty() {
+            let _ = writeln!(out, "_No papers recorded._\n");
+            return;
+        }
+        for (i, (title, id)) in cited.into_iter().enumerate() {
+            let _ = write!(out, "{}. {} (`{}`)", i + 1, title, id);
+            write_extracted(out, extracted.get(id));
+        }
+    } else {
+        for (i, paper) in session.papers.iter().enumerate() {
+            let _ = write!(out, "{}. {}", i + 1, paper.citation());
+            write_extracted(out, extracted.get(paper.id.as_str()));
+        }
+    }
+    let _ = writeln!(out);
+}
+
+fn write_extracted(out: &mut String, techniques: Option<&Vec<&str>>) {
+    match techniques {
+        Some(names) => {
+            let _ = writeln!(out, " — techniques: {}", names.join(", "));
+        }
+        None => {
+            let _ = writeln!(out);
+        }
+    }
+}
+
+fn write_techniques(out: &mut String, session: &Session) {
+    if session.techniques.is_empty() {
+        return;
+    }
+    let (selected, others): (Vec<_>, Vec<_>) =
+        session.techniques.iter().partition(|card| card.selected);
+    let _ = writeln!(out, "## Techniques\n");
+    if selected.is_empty() {
+        let _ = writeln!(out, "_None selected for generation._\n");
+    }
+    for (i, card) in selected.iter().enumerate() {
+        write_technique_brief(out, i + 1, card, 3);
+    }
+    if !others.is_empty() {
+        let names: Vec<&str> = others.iter().map(|card| card.name.as_str()).collect();
+        let _ = writeln!(
+            out,
+            "_Also extracted, not selected:_ {}\n",
+            names.join(", ")
+        );
+    }
+}
+
+fn write_variants(out: &mut String, session: &Session, stats: &HashMap<String, DiffStat>) {
+    let _ = writeln!(out, "## Variants\n");
+    if session.variants.is_empty() {
+        let _ = writeln!(out, "_No variants generated._\n");
+        return;
+    }
+    let _ = writeln!(
+        out,
+        "| Variant | Origin | Branch | Files | Lines | New dependencies |"
+    );
+    let _ = writeln!(out, "|---|---|---|--:|--:|---|");
+    for v in &session.variants {
+        let (files, lines) = match stats.get(&v.branch_name) {
+            Some(stat) => (
+                stat.files.to_string(),
+                format!("+{} −{}", stat.insertions, stat.deletions),
+            ),
+            None => (v.modified_files.len().to_string(), "—".to_string()),
+        };
+        let dependencies = if v.new_dependencies.is_empty() {
+            "none".to_string()
+        } else {
+            v.new_dependencies.join(", ")
+        };
+        let _ = writeln!(
+            out,
+            "| {} | {} | `{}` | {} | {} | {} |",
+            cell(&v.display_name),
+            cell(&origin(session, v)),
+            v.branch_name,
+            files,
+            lines,
+            cell(&dependencies)
+        );
+    }
+    let _ = writeln!(out);
+}
+
+/// Where `v` came from, in a few words.
+fn origin(session: &Session, v: &Variant) -> String {
+    let mut origin = match &v.origin {
+        VariantOrigin::Research { technique } => match v.style {
+            Some(style) => format!("{} ({})", technique.name, style.label()),
+            None => technique.name.clone(),
+        },
+        VariantOrigin::Merge { spec } => {
+            let sources: Vec<String> = spec
+                .sources
+                .iter()
+                .map(|s| name_of(session, &s.variant))
+                .collect();
+            format!("merge of {}", sources.join(" + "))
+        }
+    };
+    if let Some(revision) = &v.revision {
+        let _ = write!(origin, ", revision {}", revision.number);
+    }
+    origin
+}
+
+/// Display name of the variant with `id`, or the ID if it's gone.
+fn name_of(session: &Session, id: &VariantId) -> String {
+    session
+        .variants
+        .iter()
+        .find(|v| v.id == *id)
+        .map(|v| v.display_name.clone())
+        .or_else(|| {
+            session
+                .discarded
+                .iter()
+                .find(|d| d.id == *id)
+                .map(|d| d.display_name.clone())
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+fn write_benchmarks(out: &mut String, session: &Session) {
+    let benchmarked: Vec<&Variant> = {
+        let mut ranked: Vec<&Variant> = session
+            .variants
+            .iter()
+            .filter(|v| v.benchmark_results.is_some())
+            .collect();
+        // Best composite first; unscored variants last.
+        ranked.sort_by(|a, b| {
+            let score = |v: &Variant| {
+                v.benchmark_results
+                    .as_ref()
+                    .and_then(|r| r.composite_score)
+                    .unwrap_or(f64::NEG_INFINITY)
+            };
+            score(b).total_cmp(&score(a))
+        });
+        ranked
+    };
+    if benchmarked.is_empty() && session.baseline.is_none() {
+        return;
+    }
+
+    let _ = writeln!(out, "## Benchmarks\n");
+    let _ = writeln!(
+        out,
+        "| Variant | Build | Tests | Runtime | Memory | Judge | Composite | Elo | Rating |"
+    );
+    let _ = writeln!(out, "|---|---|--:|--:|--:|--:|--:|--:|--:|");
+    if let Some(baseline) = &session.baseline {
+        let _ = writeln!(
+            out,
+            "| _Baseline_ | {} | — | — | — | — |",
+            execution_cells(baseline, &MetricDeltas::default())
+        );
+    }
+    for v in &benchmarked {
+        let Some(results) = &v.benchmark_results else {
+            continue;
+        };
+        let execution = match &results.execution {
+            Some(execution) => {
+                let deltas = session
+                    .baseline
+                    .as_ref()
+                    .map(|baseline| execution.deltas_from(baseline))
+                    .unwrap_or_default();
+                execution_cells(execution, &deltas)
+            }
+            None => "— | — | — | —".to_string(),
+        };
+        let judge = results
+            .judge
+            .as_ref()
+            .map_or("—".to_string(), |j| format!("{:.1}/10", j.overall));
+        let composite = results
+            .composite_score
+            .map_or("—".to_string(), |s| format!("{:.0}", s));
+        let elo = results.elo.map_or("—".to_string(), |e| format!("{:.0}", e));
+        let rating = results
+            .user_rating
+            .as_ref()
+            .map_or("—".to_string(), |r| format!("{}/5", r.stars));
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |",
+            cell(&v.display_name),
+            execution,
+            judge,
+            composite,
+            elo,
+            rating
+        );
+    }
+    let _ = writeln!(out);
+    write_custom_metrics(out, session, &benchmarked);
+}
+
+/// Build, tests, runtime and memory cells, with changes from the baseline.
+fn execution_cells(execution: &ExecutionMetrics, deltas: &MetricDeltas) -> String {
+    let build = if execution.build_success {
+        "pass"
+    } else {
+        "fail"
+    };
+    let tests = match (execution.tests_passed, execution.tests_total) {
+        (Some(passed), Some(total)) => format!("{}/{}", passed, total),
+        _ => execution
+            .test_pass_rate
+            .map_or("—".to_string(), |rate| format!("{:.0}%", rate * 100.0)),
+    };
+    let with_delta = |value: Option<f64>, unit: &str, delta: Option<f64>| match value {
+        Some(value) => match delta {
+            Some(delta) => format!("{:.1} {} ({:+.1}%)", value, unit, delta),
+            None => format!("{:.1} {}", value, unit),
+        },
+        None => "—".to_string(),
+    };
+    format!(
+        "{} | {} | {} | {}",
+        build,
+        tests,
+        with_delta(execution.runtime_ms, "ms", deltas.runtime_pct),
+        with_delta(execution.memory_mb, "MB", deltas.memory_pct)
+    )
+}
+
+/// A table of the metrics plugins and GPU sampling added, if any were.
+fn write_custom_metrics(out: &mut String, session: &Session, benchmarked: &[&Variant]) {
+    let executions: Vec<(String, &ExecutionMetrics)> = session
+        .baseline
+        .iter()
+        .map(|baseline| ("_Baseline_".to_string(), baseline))
+        .chain(benchmarked.iter().filter_map(|v| {
+            let execution = v.benchmark_results.as_ref()?.execution.as_ref()?;
+            Some((cell(&v.display_name), execution))
+        }))
+        .collect();
+    let names: BTreeSet<&str> = executions
+        .iter()
+        .flat_map(|(_, execution)| execution.custom_metrics.keys().map(String::as_str))
+        .collect();
+    if names.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(out, "### Other metrics\n");
+    let mut header = "| Variant |".to_string();
+    let mut rule = "|---|".to_string();
+    for name in &names {
+        let _ = write!(header, " {} |", cell(name));
+        rule.push_str("--:|");
+    }
+    let _ = writeln!(out, "{}\n{}", header, rule);
+    for (variant, execution) in &executions {
+        let _ = write!(out, "| {} |", variant);
+        for name in &names {
+            match execution.custom_metrics.get(*name) {
+                Some(metric) if metric.unit.is_empty() => {
+                    let _ = write!(out, " {:.2} |", metric.value);
+                }
+                Some(metric) => {
+                    let _ = write!(out, " {:.2} {} |", metric.value, cell(&metric.unit));
+                }
+                None => out.push_str(" — |"),
+            }
+        }
+        out.push('\n');
+    }
+    let _ = writeln!(out);
+}
+
+fn write_commentary(out: &mut String, session: &Session) {
+    let commented: Vec<&Variant> = session
+        .variants
+        .iter()
+        .filter(|v| {
+            v.benchmark_results.as_ref().is_some_and(|r| {
+                r.judge.as_ref().is_some_and(|j| !j.explanation.is_empty())
+                    || r.user_rating.as_ref().is_some_and(|u| !u.notes.is_empty())
+            })
+        })
+        .collect();
+    if commented.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(out, "## Judge commentary\n");
+    for v in commented {
+        let Some(results) = &v.benchmark_results else {
+            continue;
+        };
+        let _ = writeln!(out, "### {}\n", v.display_name);
+        if let Some(judge) = results.judge.as_ref().filter(|j| !j.explanation.is_empty()) {
+            let _ = writeln!(
+                out,
+                "**Overall {:.1}/10** · code quality {:.1} · novelty {:.1} · feasibility {:.1} \
+                 · goal alignment {:.1} · completeness {:.1}\n",
+                judge.overall,
+                judge.code_quality,
+                judge.novelty,
+                judge.feasibility,
+                judge.goal_alignment,
+                judge.completeness
+            );
+            let _ = writeln!(out, "{}\n", judge.explanation);
+        }
+        if let Some(rating) = results.user_rating.as_ref().filter(|r| !r.notes.is_empty()) {
+            let _ = writeln!(
+                out,
+                "> **Reviewer ({}/5):** {}\n",
+                rating.stars, rating.notes
+            );
+        }
+    }
+}
+
+fn write_lineage(out: &mut String, session: &Session) {
+    let derived: Vec<&Variant> = session
+        .variants
+        .iter()
+        .filter(|v| matches!(v.origin, VariantOrigin::Merge { .. }) || v.revision.is_some())
+        .collect();
+    if derived.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(out, "## Merge lineage\n");
+    for v in derived {
+        if let VariantOrigin::Merge { spec } = &v.origin {
+            let sources: Vec<String> = spec
+                .sources
+                .iter()
+                .map(|s| {
+                    format!(
+                        "{} ({}%)",
+                        name_of(session, &s.variant),
+                        s.blend.as_percent()
+                    )
+                })
+                .collect();
+            let _ = write!(out, "- **{}** ← {}", v.display_name, sources.join(" + "));
+            if let Some(strategy) = spec.strategy {
+                let _ = write!(out, " · {}", strategy.label());
+            }
+            if let Some(guidance) = spec.guidance.as_deref().filter(|g| !g.is_empty()) {
+                let _ = write!(out, " · “{}”", guidance);
+            }
+            out.push('\n');
+        }
+        if let Some(revision) = &v.revision {
+            let _ = write!(
+                out,
+                "- **{}** revises {} (revision {})",
+                v.display_name, revision.original_name, revision.number
+            );
+            if !revision.feedback.is_empty() {
+                let _ = write!(out, " · “{}”", revision.feedback);
+            }
+            out.push('\n');
+        }
+    }
+    let _ = writeln!(out);
+}
+
+fn write_discarded(out: &mut String, session: &Session) {
+    if session.discarded.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "## Discarded\n");
+    for d in &session.discarded {
+        let _ = write!(out, "- {}", d.display_name);
+        if let Some(technique) = &d.technique {
+            let _ = write!(out, " ({})", technique);
+        }
+        let _ = writeln!(out, " — {}", d.discarded_at.format("%Y-%m-%d"));
+    }
+    let _ = writeln!(out);
+}
+
+/// Escape what would break a Markdown table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// `markdown` as a standalone HTML page. HTML in the Markdown, which could
+/// come from a paper or a model, is shown as text rather than interpreted.
+fn markdown_to_html(title: &str, markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        other => other,
+    });
+    let mut body = String::new();
+    html::push_html(&mut body, events);
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n{}</main>\n</body>\n</html>\n",
+        escape_html(title),
+        STYLE,
+        body
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+const STYLE: &str = "
+body { font: 15px/1.55 -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; color: #1f2328; margin: 0; }
+main { max-width: 960px; margin: 0 auto; padding: 2rem 1.5rem 4rem; }
+h1 { border-bottom: 1px solid #d0d7de; padding-bottom: .3em; }
+h2 { margin-top: 2.2em; border-bottom: 1px solid #d0d7de; padding-bottom: .2em; }
+table { border-collapse: collapse; margin: 1em 0; font-size: 14px; display: block; overflow-x: auto; }
+th, td { border: 1px solid #d0d7de; padding: 6px 10px; }
+th { background: #f6f8fa; }
+tr:nth-child(even) td { background: #fafbfc; }
+code { background: #f6f8fa; padding: .1em .3em; border-radius: 4px; font-size: 90%; }
+blockquote { margin: 0; padding: 0 1em; color: #59636e; border-left: 4px solid #d0d7de; }
+@media (prefers-color-scheme: dark) {
+  body { background: #0d1117; color: #e6edf3; }
+  th, code { background: #161b22; }
+  tr:nth-child(even) td { background: #111620; }
+  th, td, h1, h2 { border-color: #30363d; }
+  blockquote { color: #9198a1; border-color: #30363d; }
+}
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::{BenchmarkResults, JudgeScores, MetricValue};
+    use crate::merge::{BlendRatio, MergeSource, MergeSpec};
+    use crate::research::{Complexity, TechniqueCard};
+
+    fn card(name: &str, paper_id: &str) -> TechniqueCard {
+        TechniqueCard {
+            name: name.to_string(),
+            paper_id: paper_id.to_string(),
+            paper_title: format!("Paper {}", paper_id),
+            methodology: "Do <b>this</b>.".to_string(),
+            key_components: vec![],
+            required_data_format: String::new(),
+            implementation_complexity: Complexity::Low,
+            hardware_requirements: String::new(),
+            dependencies: vec![],
+            relevance_score: 0.8,
+            integration_approach: String::new(),
+            selected: true,
+            related_papers: vec![],
+        }
+    }
+
+    fn execution(runtime_ms: f64) -> ExecutionMetrics {
+        serde_json::from_value(serde_json::json!({
+            "build_success": true, "build_error": null, "test_pass_rate": 1.0,
+            "tests_passed": 4, "tests_total": 4, "runtime_ms": runtime_ms,
+            "memory_mb": null, "custom_metrics": {},
+        }))
+        .unwrap()
+    }
+
+    fn session() -> Session {
+        let mut session = Session::new("/src/shop");
+        session.techniques = vec![card("Caching", "p1"), card("Batching", "p2")];
+        session.techniques[1].selected = false;
+        session.baseline = Some(execution(200.0));
+
+        let mut cache = Variant::from_technique(1, session.techniques[0].clone());
+        let mut metrics = execution(150.0);
+        metrics.custom_metrics.insert(
+            "hit_rate".into(),
+            MetricValue {
+                name: "hit_rate".into(),
+                value: 0.9,
+                unit: String::new(),
+                higher_is_better: true,
+            },
+        );
+        cache.benchmark_results = Some(BenchmarkResults {
+            execution: Some(metrics),
+            judge: Some(JudgeScores {
+                code_quality: 8.0,
+                novelty: 6.0,
+                feasibility: 9.0,
+                goal_alignment: 8.0,
+                completeness: 7.0,
+                overall: 7.5,
+                explanation: "Clean | simple.".into(),
+                samples: 1,
+                spread: 0.0,
+                disagreement: false,
+            }),
+            composite_score: Some(81.0),
+            ..Default::default()
+        });
+        let mut batch = Variant::from_technique(2, session.techniques[1].clone());
+        batch.benchmark_results = Some(BenchmarkResults {
+            composite_score: Some(40.0),
+            ..Default::default()
+        });
+        let merged = Variant::from_merge(
+            1,
+            MergeSpec::from_sources(vec![
+                MergeSource::new(cache.id.clone(), BlendRatio::ThreeQuarter),
+                MergeSource::new(batch.id.clone(), BlendRatio::Quarter),
+            ]),
+            "cache + batch".into(),
+        );
+        session.variants = vec![cache, batch, merged];
+        session
+    }
+
+    #[test]
+    fn markdown_covers_the_session() {
+        let session = session();
+        let mut stats = HashMap::new();
+        stats.insert(
+            session.variants[0].branch_name.clone(),
+            DiffStat {
+                files: 2,
+                insertions: 30,
+                deletions: 4,
+            },
+        );
+        let report = session_markdown(&session, &stats);
+
+        assert!(report.starts_with("# uniq report: shop\n"), "{report}");
+        assert!(
+            report.contains("1. Paper p1 (`p1`) — techniques: Caching"),
+            "{report}"
+        );
+        assert!(report.contains("### 1. Caching"), "{report}");
+        assert!(
+            report.contains("_Also extracted, not selected:_ Batching"),
+            "{report}"
+        );
+        assert!(report.contains("| 2 | +30 −4 |"), "{report}");
+        assert!(report.contains("150.0 ms (-25.0%)"), "{report}");
+        assert!(report.contains("| hit_rate |"), "{report}");
+        assert!(report.contains("**Overall 7.5/10**"), "{report}");
+        assert!(report.contains("\nClean | simple.\n"), "{report}");
+        assert!(
+            report.contains("- **M1: cache + batch** ← V1: Caching (75%) + V2: Batching (25%)"),
+            "{report}"
+        );
+        // The best-scoring variant leads the benchmark table.
+        let table = &report[report.find("## Benchmarks").unwrap()..];
+        assert!(table.find("| V1: Caching").unwrap() < table.find("| V2: Batching").unwrap());
+    }
+
+    #[test]
+    fn html_is_a_page_with_tables_and_no_raw_html() {
+        let html = render(&session(), &HashMap::new(), ReportFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>uniq report: shop</title>"));
+        assert!(html.contains("<table>"));
+        assert!(html.contains("Do &lt;b&gt;this&lt;/b&gt;."), "{html}");
+    }
+}
@@ -1,8 +1,13 @@
+use std::collections::HashMap;
+
 use chrono::NaiveDate;
-use serde::{Deserialize, Serialize};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Metadata about an academic paper from Semantic Scholar or arXiv.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PaperMeta {
     /// Unique identifier (e.g., "arxiv:2106.15928" or Semantic Scholar ID).
     pub id: String,
@@ -44,14 +49,48 @@ pub struct PaperMeta {
     pub relevance_score: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+impl PaperMeta {
+    /// A one-line reference: authors (year). Title. DOI link, or the URL.
+    pub fn citation(&self) -> String {
+        let mut authors = self
+            .authors
+            .iter()
+            .take(3)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.authors.len() > 3 {
+            authors.push_str(", et al.");
+        }
+        let mut out = String::new();
+        if !authors.is_empty() {
+            out.push_str(&authors);
+            out.push(' ');
+        }
+        if let Some(year) = self.year {
+            out.push_str(&format!("({}). ", year));
+        }
+        out.push_str(self.title.trim_end_matches('.'));
+        out.push('.');
+        match &self.doi {
+            Some(doi) => out.push_str(&format!(" https://doi.org/{}", doi)),
+            None if !self.url.is_empty() => out.push_str(&format!(" {}", self.url)),
+            None => {}
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub enum PaperSource {
     SemanticScholar,
     ArXiv,
+    /// A research plugin, named by the prefix of the paper's ID.
+    Plugin,
 }
 
 /// A structured technique extracted from a research paper.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TechniqueCard {
     /// Human-readable name for this technique.
     pub name: String,
@@ -88,15 +127,75 @@ pub struct TechniqueCard {
 
     /// Whether the user has selected this technique for variant generation.
     pub selected: bool,
+
+    /// Other papers describing the same technique, after consolidation.
+    #[serde(default)]
+    pub related_papers: Vec<CitedPaper>,
+}
+
+impl TechniqueCard {
+    /// Identity of the technique across runs: its source paper and name.
+    pub fn key(&self) -> String {
+        format!("{}::{}", self.paper_id, self.name)
+    }
+
+    /// A short plain-text summary: name, source, methodology and how it
+    /// would be integrated.
+    pub fn summary(&self) -> String {
+        format!(
+            "{}\nFrom: {}\nComplexity: {} · Relevance: {:.0}%\n\n{}\n\nIntegration: {}",
+            self.name,
+            self.paper_title,
+            self.implementation_complexity,
+            self.relevance_score * 100.0,
+            self.methodology,
+            self.integration_approach
+        )
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// A paper cited by a consolidated technique card.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct CitedPaper {
+    pub id: String,
+    pub title: String,
+}
+
+/// Implementation complexity of a technique, as judged by the model.
+///
+/// Deserializes case-insensitively from a plain string; unknown values map
+/// to `Medium`, so one odd answer doesn't fail a whole extraction.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub enum Complexity {
     Low,
     Medium,
     High,
 }
 
+impl<'de> Deserialize<'de> for Complexity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(match s.trim().to_lowercase().as_str() {
+            "low" => Complexity::Low,
+            "high" => Complexity::High,
+            _ => Complexity::Medium,
+        })
+    }
+}
+
+impl JsonSchema for Complexity {
+    fn schema_name() -> String {
+        "Complexity".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 impl std::fmt::Display for Complexity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -106,3 +205,191 @@ impl std::fmt::Display for Complexity {
         }
     }
 }
+
+/// Words that carry no meaning when comparing technique names.
+const NAME_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "based", "for", "in", "of", "on", "the", "to", "using", "via", "with",
+];
+
+/// What duplicate technique names have in common: their significant
+/// lowercase words, singular and sorted, so case, punctuation, word order
+/// and filler words don't tell them apart.
+pub fn duplicate_key(name: &str) -> String {
+    let mut words: Vec<&str> = Vec::new();
+    let lower = name.to_lowercase();
+    for word in lower.split(|c: char| !c.is_alphanumeric()) {
+        if word.is_empty() || NAME_STOPWORDS.contains(&word) {
+            continue;
+        }
+        let singular = match word.strip_suffix('s') {
+            Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem,
+            _ => word,
+        };
+        words.push(singular);
+    }
+    words.sort_unstable();
+    words.dedup();
+    words.join(" ")
+}
+
+/// Group cards from different papers whose names have the same
+/// [`duplicate_key`].
+///
+/// Returns groups of indices into `cards` in the order their first card
+/// appears, each with at least two members and at most one card per paper;
+/// cards without a duplicate are not listed.
+pub fn find_duplicate_techniques(cards: &[TechniqueCard]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut by_key: HashMap<String, usize> = HashMap::new();
+    for (i, card) in cards.iter().enumerate() {
+        let key = duplicate_key(&card.name);
+        if key.is_empty() {
+            continue;
+        }
+        let next = groups.len();
+        let index = *by_key.entry(key).or_insert(next);
+        if index == next {
+            groups.push(Vec::new());
+        }
+        let group = &mut groups[index];
+        // Several components of one paper are never duplicates of each other.
+        if group.iter().all(|&j| cards[j].paper_id != card.paper_id) {
+            group.push(i);
+        }
+    }
+    groups.retain(|g| g.len() > 1);
+    groups
+}
+
+/// Merge duplicate cards into one, keeping the most relevant card's text
+/// and citing the other papers.
+///
+/// Panics if `cards` is empty.
+pub fn consolidate_techniques(mut cards: Vec<TechniqueCard>) -> TechniqueCard {
+    cards.sort_by(|a, b| b.relevance_score.total_cmp(&a.relevance_score));
+    let mut rest = cards.split_off(1);
+    let mut merged = cards.remove(0);
+
+    for other in rest.drain(..) {
+        let cited = std::iter::once(CitedPaper {
+            id: other.paper_id,
+            title: other.paper_title,
+        })
+        .chain(other.related_papers);
+        for paper in cited {
+            if paper.id != merged.paper_id && !merged.related_papers.contains(&paper) {
+                merged.related_papers.push(paper);
+            }
+        }
+        for dep in other.dependencies {
+            if !merged
+                .dependencies
+                .iter()
+                .any(|d| d.eq_ignore_ascii_case(&dep))
+            {
+                merged.dependencies.push(dep);
+            }
+        }
+        for component in other.key_components {
+            if !merged
+                .key_components
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(&component))
+            {
+                merged.key_components.push(component);
+            }
+        }
+        merged.selected |= other.selected;
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(name: &str, paper_id: &str, relevance: f64) -> TechniqueCard {
+        TechniqueCard {
+            name: name.to_string(),
+            paper_id: paper_id.to_string(),
+            paper_title: format!("Paper {}", paper_id),
+            methodology: String::new(),
+            key_components: vec![],
+            required_data_format: String::new(),
+            implementation_complexity: Complexity::Low,
+            hardware_requirements: String::new(),
+            dependencies: vec!["peft".to_string()],
+            relevance_score: relevance,
+            integration_approach: String::new(),
+            selected: false,
+            related_papers: vec![],
+        }
+    }
+
+    #[test]
+    fn test_citation() {
+        let mut paper = PaperMeta {
+            id: "arxiv:2106.09685".to_string(),
+            title: "LoRA: Low-Rank Adaptation of Large Language Models".to_string(),
+            authors: ["Hu", "Shen", "Wallis", "Allen-Zhu"]
+                .map(String::from)
+                .to_vec(),
+            year: Some(2021),
+            published_date: None,
+            abstract_text: String::new(),
+            citation_count: None,
+            url: "https://arxiv.org/abs/2106.09685".to_string(),
+            pdf_url: None,
+            doi: None,
+            source: PaperSource::ArXiv,
+            fields: vec![],
+            relevance_score: None,
+        };
+        assert_eq!(
+            paper.citation(),
+            "Hu, Shen, Wallis, et al. (2021). LoRA: Low-Rank Adaptation of Large Language \
+             Models. https://arxiv.org/abs/2106.09685"
+        );
+        paper.authors.truncate(1);
+        paper.doi = Some("10.48550/arXiv.2106.09685".to_string());
+        assert_eq!(
+            paper.citation(),
+            "Hu (2021). LoRA: Low-Rank Adaptation of Large Language Models. \
+             https://doi.org/10.48550/arXiv.2106.09685"
+        );
+    }
+
+    #[test]
+    fn test_find_duplicate_techniques() {
+        let cards = vec![
+            card("LoRA Fine-Tuning", "p1", 0.9),
+            card("Graph Attention Network", "p2", 0.8),
+            card("Fine-tuning with LoRA", "p3", 0.7),
+            // Same paper as the first card: a separate component.
+            card("LoRA fine tuning", "p1", 0.6),
+            card("Graph attention networks", "p4", 0.5),
+            card("LoRA fine-tuning for classification", "p5", 0.4),
+        ];
+        assert_eq!(
+            find_duplicate_techniques(&cards),
+            vec![vec![0, 2], vec![1, 4]]
+        );
+    }
+
+    #[test]
+    fn test_consolidate_techniques() {
+        let merged = consolidate_techniques(vec![
+            card("LoRA fine-tuning", "p3", 0.7),
+            card("LoRA Fine-Tuning", "p1", 0.9),
+        ]);
+        assert_eq!(merged.paper_id, "p1");
+        assert_eq!(merged.dependencies, vec!["peft"]);
+        assert_eq!(
+            merged.related_papers,
+            vec![CitedPaper {
+                id: "p3".to_string(),
+                title: "Paper p3".to_string()
+            }]
+        );
+    }
+}
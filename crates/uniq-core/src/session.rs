@@ -0,0 +1,220 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::benchmark::{ExecutionMetrics, PairwiseComparison};
+use crate::error::{Result, UniqError};
+use crate::project::ProjectProfile;
+use crate::research::{PaperMeta, TechniqueCard};
+use crate::variant::{slugify, Variant, VariantId};
+
+/// A variant that was discarded, and its branch deleted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscardedVariant {
+    pub id: VariantId,
+    pub display_name: String,
+    pub branch_name: String,
+    /// Name of the technique it implemented, for research variants.
+    pub technique: Option<String>,
+    pub discarded_at: DateTime<Utc>,
+}
+
+/// Per-project state saved between runs, so a session can be resumed
+/// without re-extracting techniques or regenerating variants.
+///
+/// Stored under the user's data directory rather than in the project,
+/// since variant generation commits everything in the project tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// Project this session belongs to.
+    pub project_path: PathBuf,
+
+    /// The project's analysis, including what the user asked for.
+    #[serde(default)]
+    pub profile: Option<ProjectProfile>,
+
+    /// Papers the research phase found.
+    #[serde(default)]
+    pub papers: Vec<PaperMeta>,
+
+    /// Extracted technique cards, including the user's selection.
+    #[serde(default)]
+    pub techniques: Vec<TechniqueCard>,
+
+    /// Variants that were generated.
+    #[serde(default)]
+    pub variants: Vec<Variant>,
+
+    /// Variants the user discarded, kept for reporting.
+    #[serde(default)]
+    pub discarded: Vec<DiscardedVariant>,
+
+    /// Execution metrics of the unmodified project, from the last run.
+    #[serde(default)]
+    pub baseline: Option<ExecutionMetrics>,
+
+    /// Branch the project was on when the session started, which variants
+    /// branch from and patches are taken against.
+    #[serde(default)]
+    pub base_branch: Option<String>,
+
+    /// Verdicts of the last pairwise judge tournament.
+    #[serde(default)]
+    pub comparisons: Vec<PairwiseComparison>,
+
+    /// When the session was last saved.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// File name for a project's data, without extension: the project's
+/// directory name, for whoever browses the data directory, then a hash of
+/// its canonical path, so projects with alike paths never share a file.
+pub(crate) fn project_file_stem(project_path: &Path) -> String {
+    let canonical = canonical(project_path);
+    let name = canonical
+        .file_name()
+        .map(|name| slugify(&name.to_string_lossy()))
+        .unwrap_or_default();
+    format!(
+        "{}-{:016x}",
+        name,
+        fnv1a(canonical.as_os_str().as_encoded_bytes())
+    )
+}
+
+/// Files in `dir` that may hold a project's data, most likely first: the
+/// one named by [`project_file_stem`], then the ones named by slugs of its
+/// path from before files were named by hash. Legacy files may belong to
+/// another project whose path slugs the same, so check what they hold.
+pub(crate) fn project_files(dir: &Path, project_path: &Path) -> [PathBuf; 3] {
+    let file = |stem: String| dir.join(format!("{}.json", stem));
+    [
+        file(project_file_stem(project_path)),
+        file(slugify(&project_path.to_string_lossy())),
+        file(slugify(&canonical(project_path).to_string_lossy())),
+    ]
+}
+
+/// Whether `a` and `b` are the same project directory.
+pub(crate) fn same_project(a: &Path, b: &Path) -> bool {
+    canonical(a) == canonical(b)
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` is the same in every build,
+/// so file names stay put across upgrades.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl Session {
+    pub fn new(project_path: impl Into<PathBuf>) -> Self {
+        let project_path = project_path.into();
+        Self {
+            base_branch: crate::git::head_branch(&project_path).ok(),
+            project_path,
+            profile: None,
+            papers: Vec::new(),
+            techniques: Vec::new(),
+            variants: Vec::new(),
+            discarded: Vec::new(),
+            baseline: None,
+            comparisons: Vec::new(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Directory session files live in (`~/.local/share/uniq/sessions` on Linux).
+    pub fn sessions_dir() -> Result<PathBuf> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| UniqError::Config("Could not determine data directory".into()))?;
+        Ok(data_dir.join("uniq").join("sessions"))
+    }
+
+    /// Session file for a project, named by its canonical path (see
+    /// [`project_file_stem`]).
+    pub fn path_for(project_path: &Path) -> Result<PathBuf> {
+        let [path, ..] = project_files(&Self::sessions_dir()?, project_path);
+        Ok(path)
+    }
+
+    /// Load the saved session for a project, if there is one. Sessions
+    /// saved before files were named by hash are still found, as long as
+    /// they were saved for this project.
+    pub fn load(project_path: &Path) -> Result<Option<Self>> {
+        for path in project_files(&Self::sessions_dir()?, project_path) {
+            if !path.exists() {
+                continue;
+            }
+            let contents = std::fs::read_to_string(&path)?;
+            let session: Self = serde_json::from_str(&contents)?;
+            if same_project(&session.project_path, project_path) {
+                return Ok(Some(session));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Save the session, stamping `updated_at`.
+    pub fn save(&mut self) -> Result<()> {
+        self.updated_at = Utc::now();
+        let path = Self::path_for(&self.project_path)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record that `variant` was discarded.
+    pub fn record_discarded(&mut self, variant: &Variant) {
+        self.discarded.push(DiscardedVariant {
+            id: variant.id.clone(),
+            display_name: variant.display_name.clone(),
+            branch_name: variant.branch_name.clone(),
+            technique: variant.technique().map(|t| t.name.clone()),
+            discarded_at: Utc::now(),
+        });
+    }
+
+    /// Whether a variant was already generated for `technique`.
+    pub fn has_variant_for(&self, technique: &TechniqueCard) -> bool {
+        self.variants
+            .iter()
+            .filter_map(Variant::technique)
+            .any(|t| t.key() == technique.key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projects_with_alike_paths_get_their_own_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let (dashed, nested) = (dir.path().join("a-b"), dir.path().join("a").join("b"));
+        std::fs::create_dir_all(&dashed).unwrap();
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(
+            slugify(&dashed.to_string_lossy()),
+            slugify(&nested.to_string_lossy())
+        );
+
+        assert_ne!(
+            Session::path_for(&dashed).unwrap(),
+            Session::path_for(&nested).unwrap()
+        );
+        assert_eq!(
+            Session::path_for(&nested.join("..").join("..").join("a-b")).unwrap(),
+            Session::path_for(&dashed).unwrap()
+        );
+        assert!(project_file_stem(&dashed).starts_with("a-b-"));
+        assert!(!same_project(&dashed, &nested));
+    }
+}
@@ -1,6 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::benchmark::BenchmarkResults;
+use chrono::Utc;
+
+use crate::benchmark::{BenchmarkResults, BenchmarkRun};
 use crate::merge::MergeSpec;
 use crate::research::TechniqueCard;
 
@@ -22,10 +25,11 @@ impl std::fmt::Display for VariantId {
 
 /// How a variant was created — either from a research technique or by merging.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)] // Research origins are by far the common case.
 pub enum VariantOrigin {
     /// Created from a single research technique.
     Research { technique: TechniqueCard },
-    /// Created by merging two existing variants.
+    /// Created by merging existing variants.
     Merge { spec: MergeSpec },
 }
 
@@ -36,12 +40,144 @@ pub enum VariantStatus {
     Pending,
     /// Currently being generated by Claude.
     Generating,
+    /// Plan drafted (plan-first mode), waiting for the user to approve it.
+    AwaitingApproval,
     /// Successfully generated.
     Ready,
     /// Generation failed.
     Failed(String),
 }
 
+/// A style directive for implementing a technique, so one technique can
+/// be explored as several differently-shaped variants.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ImplementationStyle {
+    /// Smallest change that integrates the technique.
+    MinimalDiff,
+    /// Restructure the surrounding code so the technique fits idiomatically.
+    IdiomaticRefactor,
+    /// Favour runtime and memory efficiency.
+    Performance,
+}
+
+impl ImplementationStyle {
+    pub const ALL: [ImplementationStyle; 3] = [
+        ImplementationStyle::MinimalDiff,
+        ImplementationStyle::IdiomaticRefactor,
+        ImplementationStyle::Performance,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImplementationStyle::MinimalDiff => "minimal diff",
+            ImplementationStyle::IdiomaticRefactor => "idiomatic refactor",
+            ImplementationStyle::Performance => "performance",
+        }
+    }
+
+    /// Instruction passed to the model alongside the technique.
+    pub fn directive(&self) -> &'static str {
+        match self {
+            ImplementationStyle::MinimalDiff => {
+                "Make the smallest possible change: touch as few files and lines as you can \
+                 and keep existing structure and names intact."
+            }
+            ImplementationStyle::IdiomaticRefactor => {
+                "Refactor the surrounding code where needed so the technique fits the \
+                 project's idioms cleanly, even if the diff gets larger."
+            }
+            ImplementationStyle::Performance => {
+                "Prioritise runtime and memory efficiency: vectorise, avoid redundant work \
+                 and prefer efficient library primitives."
+            }
+        }
+    }
+}
+
+/// Link from a regenerated variant back to the variant it revises.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Revision {
+    /// The first-generation variant this is a revision of.
+    pub original: VariantId,
+
+    /// Branch of the original variant.
+    pub original_branch: String,
+
+    /// Display name of the original variant.
+    pub original_name: String,
+
+    /// Revision number; the original counts as 1, so the first rerun is 2.
+    pub number: u32,
+
+    /// Extra instruction the user gave for this regeneration.
+    pub feedback: String,
+}
+
+/// A file the implementation plan intends to touch.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlannedFile {
+    pub path: String,
+    /// `create` or `modify`.
+    #[serde(default)]
+    pub action: String,
+    /// Why the file is touched.
+    #[serde(default)]
+    pub purpose: String,
+}
+
+/// An implementation plan drafted before any code is written, for the
+/// user to approve in plan-first mode.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ImplementationPlan {
+    #[serde(default)]
+    pub steps: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<PlannedFile>,
+    #[serde(default)]
+    pub new_modules: Vec<String>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+/// A dependency the package manager could not install.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FailedDependency {
+    pub name: String,
+    pub error: String,
+}
+
+/// Outcome of installing a variant's new dependencies after generation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct DependencyCheck {
+    /// Package manager used (`cargo`, `npm`, `uv`, `pip`, `go`).
+    pub installer: String,
+    #[serde(default)]
+    pub installed: Vec<String>,
+    #[serde(default)]
+    pub failed: Vec<FailedDependency>,
+}
+
+/// What happened while a variant was generated, as reported by the sidecar.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct GenerationTranscript {
+    /// The model's implementation plan, one step per entry.
+    #[serde(default)]
+    pub plan: Vec<String>,
+
+    /// Files written, prefixed with the action taken (`create`/`modify`).
+    #[serde(default)]
+    pub files_written: Vec<String>,
+
+    /// The model's explanation of what it implemented.
+    #[serde(default)]
+    pub explanation: String,
+
+    /// Step-by-step log of the generation run.
+    #[serde(default)]
+    pub log: Vec<String>,
+}
+
 /// A project variant — a modified copy of the user's project implementing
 /// a specific research technique (or a merge of techniques).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +205,40 @@ pub struct Variant {
 
     /// Benchmark results, once available.
     pub benchmark_results: Option<BenchmarkResults>,
+
+    /// Generation transcript, once generation has run.
+    #[serde(default)]
+    pub transcript: Option<GenerationTranscript>,
+
+    /// Set when this variant is a regeneration of another one.
+    #[serde(default)]
+    pub revision: Option<Revision>,
+
+    /// Plan the user reviewed before generation, in plan-first mode.
+    #[serde(default)]
+    pub plan: Option<ImplementationPlan>,
+
+    /// Style directive this variant was generated with, if any.
+    #[serde(default)]
+    pub style: Option<ImplementationStyle>,
+
+    /// Result of installing `new_dependencies`, if that was attempted.
+    #[serde(default)]
+    pub dependency_check: Option<DependencyCheck>,
+
+    /// Benchmark timeout for this variant, overriding the configured one.
+    #[serde(default)]
+    pub benchmark_timeout: Option<u64>,
+
+    /// Earlier benchmark runs, oldest first. The latest run is also in
+    /// `benchmark_results`.
+    #[serde(default)]
+    pub benchmark_history: Vec<BenchmarkRun>,
+
+    /// For merges, files the sources conflicted on at the git level and
+    /// that haven't been resolved yet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merge_conflicts: Vec<String>,
 }
 
 impl Variant {
@@ -87,6 +257,14 @@ impl Variant {
             modified_files: Vec::new(),
             new_dependencies: Vec::new(),
             benchmark_results: None,
+            transcript: None,
+            revision: None,
+            plan: None,
+            style: None,
+            dependency_check: None,
+            benchmark_timeout: None,
+            benchmark_history: Vec::new(),
+            merge_conflicts: Vec::new(),
         }
     }
 
@@ -105,17 +283,200 @@ impl Variant {
             modified_files: Vec::new(),
             new_dependencies: Vec::new(),
             benchmark_results: None,
+            transcript: None,
+            revision: None,
+            plan: None,
+            style: None,
+            dependency_check: None,
+            benchmark_timeout: None,
+            benchmark_history: Vec::new(),
+            merge_conflicts: Vec::new(),
+        }
+    }
+
+    /// Generate this variant with `style`, which is appended to its
+    /// branch and display name.
+    pub fn with_style(mut self, style: ImplementationStyle) -> Self {
+        self.branch_name = format!("{}-{}", self.branch_name, slugify(style.label()));
+        self.display_name = format!("{} ({})", self.display_name, style.label());
+        self.style = Some(style);
+        self
+    }
+
+    /// Create revision `number` of `original`, regenerated with `feedback`.
+    ///
+    /// Revisions always link back to the first-generation variant, and
+    /// their branch is the original's with a `-v{number}` suffix. Merged
+    /// variants can't be regenerated, so this returns `None` for them.
+    pub fn revise(original: &Variant, number: u32, feedback: String) -> Option<Self> {
+        let technique = original.technique()?.clone();
+        let (id, branch, name) = match original.revision {
+            Some(ref r) => (
+                r.original.clone(),
+                r.original_branch.clone(),
+                r.original_name.clone(),
+            ),
+            None => (
+                original.id.clone(),
+                original.branch_name.clone(),
+                original.display_name.clone(),
+            ),
+        };
+
+        Some(Self {
+            id: VariantId::new(format!("{}-v{}", id, number)),
+            branch_name: format!("{}-v{}", branch, number),
+            display_name: format!("{} (v{})", name, number),
+            origin: VariantOrigin::Research { technique },
+            status: VariantStatus::Pending,
+            modified_files: Vec::new(),
+            new_dependencies: Vec::new(),
+            benchmark_results: None,
+            transcript: None,
+            revision: Some(Revision {
+                original: id,
+                original_branch: branch,
+                original_name: name,
+                number,
+                feedback,
+            }),
+            plan: None,
+            style: original.style,
+            dependency_check: None,
+            benchmark_timeout: original.benchmark_timeout,
+            benchmark_history: Vec::new(),
+            merge_conflicts: Vec::new(),
+        })
+    }
+
+    /// This variant's branch name with the descriptive suffix replaced,
+    /// e.g. `uniq/variant-3-temporal-fusion` → `uniq/variant-3-tft`.
+    pub fn branch_with_suffix(&self, suffix: &str) -> String {
+        let (dir, leaf) = match self.branch_name.rsplit_once('/') {
+            Some((dir, leaf)) => (Some(dir), leaf),
+            None => (None, self.branch_name.as_str()),
+        };
+        // Keep the `variant-3` / `merge-1` stem so branches stay unique.
+        let stem = leaf.splitn(3, '-').take(2).collect::<Vec<_>>().join("-");
+        let leaf = format!("{}-{}", stem, slugify(suffix));
+        match dir {
+            Some(dir) => format!("{}/{}", dir, leaf),
+            None => leaf,
         }
     }
 
+    /// The first-generation variant this one descends from (itself, unless
+    /// it is a revision).
+    pub fn original_id(&self) -> &VariantId {
+        self.revision
+            .as_ref()
+            .map(|r| &r.original)
+            .unwrap_or(&self.id)
+    }
+
     /// Whether this variant is a merge of other variants.
     pub fn is_merge(&self) -> bool {
         matches!(self.origin, VariantOrigin::Merge { .. })
     }
+
+    /// Snapshot the current benchmark results into the history.
+    pub fn record_benchmark_run(&mut self) {
+        if let Some(ref results) = self.benchmark_results {
+            self.benchmark_history.push(BenchmarkRun {
+                at: Utc::now(),
+                results: results.clone(),
+            });
+        }
+    }
+
+    /// Composite score change since the previous recorded run.
+    pub fn score_trend(&self) -> Option<f64> {
+        let [.., previous, latest] = self.benchmark_history.as_slice() else {
+            return None;
+        };
+        Some(latest.results.composite_score? - previous.results.composite_score?)
+    }
+
+    /// Whether all new dependencies installed (or none were checked).
+    pub fn dependencies_ok(&self) -> bool {
+        self.dependency_check
+            .as_ref()
+            .is_none_or(|check| check.failed.is_empty())
+    }
+
+    /// The technique this variant implements, unless it is a merge.
+    pub fn technique(&self) -> Option<&TechniqueCard> {
+        match self.origin {
+            VariantOrigin::Research { ref technique } => Some(technique),
+            VariantOrigin::Merge { .. } => None,
+        }
+    }
+}
+
+/// Which files two variants touched, split by whether they overlap.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileOverlap {
+    /// Files only the first variant modified.
+    pub only_a: Vec<String>,
+    /// Files only the second variant modified.
+    pub only_b: Vec<String>,
+    /// Files both variants modified.
+    pub both: Vec<String>,
+}
+
+impl FileOverlap {
+    pub fn between(a: &Variant, b: &Variant) -> Self {
+        let mut overlap = FileOverlap::default();
+        for file in &a.modified_files {
+            if b.modified_files.contains(file) {
+                overlap.both.push(file.clone());
+            } else {
+                overlap.only_a.push(file.clone());
+            }
+        }
+        overlap.only_b = b
+            .modified_files
+            .iter()
+            .filter(|f| !a.modified_files.contains(f))
+            .cloned()
+            .collect();
+        overlap
+    }
+}
+
+/// The index for a new variant whose id starts with `stem` (`variant` or
+/// `merge`): one past the highest index among `taken`, so ids and branch
+/// names stay unique after variants are discarded or left out of a resumed
+/// session.
+pub fn next_index<'a>(taken: impl IntoIterator<Item = &'a VariantId>, stem: &str) -> usize {
+    taken
+        .into_iter()
+        .filter_map(|id| {
+            let rest = id.0.strip_prefix(stem)?.strip_prefix('-')?;
+            rest.split('-').next()?.parse::<usize>().ok()
+        })
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+/// The number for a new revision of `original`: one past the highest
+/// revision among `taken`, the original itself counting as the first.
+pub fn next_revision<'a>(
+    taken: impl IntoIterator<Item = &'a VariantId>,
+    original: &VariantId,
+) -> u32 {
+    let prefix = format!("{}-v", original.0);
+    taken
+        .into_iter()
+        .filter_map(|id| id.0.strip_prefix(&prefix)?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(1)
+        + 1
 }
 
 /// Convert a technique name to a URL/branch-safe slug.
-fn slugify(name: &str) -> String {
+pub(crate) fn slugify(name: &str) -> String {
     name.to_lowercase()
         .chars()
         .map(|c| if c.is_alphanumeric() { c } else { '-' })
@@ -130,6 +491,68 @@ fn slugify(name: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_numbering_skips_past_gaps() {
+        let ids: Vec<VariantId> = ["variant-1", "variant-3", "variant-3-v2", "merge-2"]
+            .into_iter()
+            .map(VariantId::new)
+            .collect();
+        assert_eq!(next_index(&ids, "variant"), 4);
+        assert_eq!(next_index(&ids, "merge"), 3);
+        assert_eq!(next_index(&[], "variant"), 1);
+
+        let original = VariantId::new("variant-3");
+        assert_eq!(next_revision(&ids, &original), 3);
+        assert_eq!(next_revision(&ids, &VariantId::new("variant-1")), 2);
+    }
+
+    #[test]
+    fn test_branch_with_suffix() {
+        let mut variant = Variant::from_technique(
+            3,
+            crate::research::TechniqueCard {
+                name: "Temporal Fusion Transformer".to_string(),
+                paper_id: "p1".to_string(),
+                paper_title: String::new(),
+                methodology: String::new(),
+                key_components: vec![],
+                required_data_format: String::new(),
+                implementation_complexity: crate::research::Complexity::Medium,
+                hardware_requirements: String::new(),
+                dependencies: vec![],
+                relevance_score: 0.5,
+                integration_approach: String::new(),
+                selected: true,
+                related_papers: vec![],
+            },
+        );
+        assert_eq!(
+            variant.branch_with_suffix("TFT v2"),
+            "uniq/variant-3-tft-v2"
+        );
+        variant.branch_name = "variant-3-x".to_string();
+        assert_eq!(variant.branch_with_suffix("short"), "variant-3-short");
+    }
+
+    #[test]
+    fn test_file_overlap() {
+        use crate::merge::{BlendRatio, MergeSpec};
+        let spec = MergeSpec::new(
+            VariantId::new("a"),
+            VariantId::new("b"),
+            BlendRatio::Half,
+            BlendRatio::Half,
+        );
+        let mut a = Variant::from_merge(1, spec, "ab".into());
+        let mut b = a.clone();
+        a.modified_files = vec!["model.py".into(), "train.py".into()];
+        b.modified_files = vec!["model.py".into(), "data.py".into()];
+        let overlap = FileOverlap::between(&a, &b);
+        assert_eq!(overlap.only_a, vec!["train.py"]);
+        assert_eq!(overlap.only_b, vec!["data.py"]);
+        assert_eq!(overlap.both, vec!["model.py"]);
+    }
+
     #[test]
     fn test_slugify() {
         assert_eq!(
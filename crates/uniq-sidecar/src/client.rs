@@ -1,19 +1,46 @@
 //! HTTP client for communicating with the Python sidecar.
 
-use reqwest::Client;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
-use tracing::{debug, instrument};
+use std::sync::{Arc, RwLock};
+use tracing::{debug, instrument, warn};
 
-use uniq_core::benchmark::{ExecutionMetrics, JudgeScores};
+use uniq_core::benchmark::{
+    BenchmarkStage, ExecutionMetrics, JudgeEnsemble, JudgeScores, PairwiseComparison,
+};
+use uniq_core::llm::{LlmBackend, LlmRoutes, PipelineStage};
 use uniq_core::project::ProjectProfile;
+use uniq_core::prompts::{PromptKind, PromptTemplates};
 use uniq_core::research::{PaperMeta, TechniqueCard};
+use uniq_core::variant::ImplementationPlan;
 
+use crate::correlation;
+use crate::decode::decode;
 use crate::protocol::*;
+use crate::rate_limit::{self, EndpointClass, RateLimiter};
+
+/// The body of `resp` as a `T`, if the request succeeded.
+async fn read<T: DeserializeOwned>(resp: Response) -> anyhow::Result<T> {
+    let resp = resp.error_for_status()?;
+    let endpoint = resp.url().path().to_string();
+    let body = resp.text().await?;
+    decode(&endpoint, &body).map_err(|e| {
+        warn!("{}", e);
+        e.into()
+    })
+}
 
 /// Client for the Python sidecar API.
 pub struct SidecarClient {
     client: Client,
     base_url: String,
+    limiter: Arc<RateLimiter>,
+    /// Backends sent with requests that call an LLM, by stage.
+    llm: RwLock<LlmRoutes>,
+    /// User templates sent in place of the sidecar's prompts.
+    prompts: RwLock<PromptTemplates>,
 }
 
 impl SidecarClient {
@@ -22,18 +49,126 @@ impl SidecarClient {
             .timeout(std::time::Duration::from_secs(120))
             .build()
             .unwrap_or_else(|_| Client::new());
-        Self { client, base_url }
+        Self {
+            client,
+            base_url,
+            limiter: Arc::default(),
+            llm: RwLock::default(),
+            prompts: RwLock::default(),
+        }
+    }
+
+    /// The LLM backends requests run on from now on.
+    pub fn set_llm(&self, routes: LlmRoutes) {
+        *self.llm.write().unwrap_or_else(|e| e.into_inner()) = routes;
+    }
+
+    /// The LLM backend requests for `stage` currently run on.
+    pub fn llm(&self, stage: PipelineStage) -> LlmBackend {
+        self.llm
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .for_stage(stage)
+    }
+
+    /// The prompt templates requests carry from now on.
+    pub fn set_prompts(&self, templates: PromptTemplates) {
+        *self.prompts.write().unwrap_or_else(|e| e.into_inner()) = templates;
+    }
+
+    /// The template currently sent for `kind`, if any.
+    pub fn prompt(&self, kind: PromptKind) -> Option<String> {
+        self.prompts
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(kind)
+            .map(str::to_string)
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        Self::correlate(self.client.get(url))
+    }
+
+    fn post(&self, url: &str) -> RequestBuilder {
+        Self::correlate(self.client.post(url))
+    }
+
+    /// Send the running operation's correlation ID, if there is one.
+    fn correlate(request: RequestBuilder) -> RequestBuilder {
+        match correlation::current() {
+            Some(id) => request.header(correlation::HEADER, id),
+            None => request,
+        }
+    }
+
+    /// Send `request` once its endpoint's rate limit allows, retrying while
+    /// the sidecar answers 429.
+    async fn send(&self, request: RequestBuilder) -> anyhow::Result<Response> {
+        let request = request.build()?;
+        let class = EndpointClass::for_path(request.url().path());
+        let mut attempt = 0;
+        loop {
+            self.limiter.acquire(class).await;
+            // Bodies here are always in memory, so this only fails for
+            // streams, which can't be resent anyway.
+            let Some(this_try) = request.try_clone() else {
+                return Ok(self.client.execute(request).await?);
+            };
+            let resp = self.client.execute(this_try).await?;
+            if resp.status() != StatusCode::TOO_MANY_REQUESTS || attempt >= rate_limit::MAX_RETRIES
+            {
+                return Ok(resp);
+            }
+            let retry_after = resp
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok());
+            let delay = rate_limit::retry_delay(retry_after, attempt);
+            warn!(
+                "Rate limited on {}, retrying in {:.1}s",
+                request.url().path(),
+                delay.as_secs_f64()
+            );
+            self.limiter.back_off(class, delay);
+            attempt += 1;
+        }
     }
 
     /// Check sidecar health.
     #[instrument(skip(self))]
     pub async fn health(&self) -> anyhow::Result<HealthResponse> {
         let url = format!("{}/api/health", self.base_url);
-        let resp = self.client.get(&url).send().await?;
-        let health: HealthResponse = resp.json().await?;
+        let resp = self.send(self.get(&url)).await?;
+        let health: HealthResponse = read(resp).await?;
         Ok(health)
     }
 
+    /// Make a call to `llm` that costs nothing. `Err` with the reason if the
+    /// backend can't be reached with the key the sidecar has.
+    #[instrument(skip(self))]
+    pub async fn ping(&self, llm: LlmBackend) -> anyhow::Result<()> {
+        let url = format!("{}/api/ping", self.base_url);
+        let resp = self
+            .send(self.post(&url).json(&PingRequest { llm }))
+            .await?;
+        let ping: PingResponse = read(resp).await?;
+        if ping.ok {
+            return Ok(());
+        }
+        anyhow::bail!(ping
+            .error
+            .unwrap_or_else(|| "backend unreachable".to_string()))
+    }
+
+    /// Claude tokens used so far, by phase and model.
+    #[instrument(skip(self))]
+    pub async fn usage(&self) -> anyhow::Result<Vec<UsageEntry>> {
+        let url = format!("{}/api/usage", self.base_url);
+        let resp = self.send(self.get(&url)).await?;
+        let result: UsageResponse = read(resp).await?;
+        Ok(result.entries)
+    }
+
     /// Analyze a project directory.
     #[instrument(skip(self))]
     pub async fn analyze_project(
@@ -42,9 +177,13 @@ impl SidecarClient {
         description: String,
     ) -> anyhow::Result<ProjectProfile> {
         let url = format!("{}/api/analyze-project", self.base_url);
-        let req = AnalyzeProjectRequest { path, description };
-        let resp = self.client.post(&url).json(&req).send().await?;
-        let profile: ProjectProfile = resp.error_for_status()?.json().await?;
+        let req = AnalyzeProjectRequest {
+            path,
+            description,
+            llm: self.llm(PipelineStage::Intake),
+        };
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let profile: ProjectProfile = read(resp).await?;
         debug!(
             "Project analyzed: {} files, {} languages",
             profile.file_count,
@@ -71,8 +210,8 @@ impl SidecarClient {
             year_max,
             prefer_open_access,
         };
-        let resp = self.client.post(&url).json(&req).send().await?;
-        let papers: Vec<PaperMeta> = resp.error_for_status()?.json().await?;
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let papers: Vec<PaperMeta> = read(resp).await?;
         debug!("Found {} papers", papers.len());
         Ok(papers)
     }
@@ -96,9 +235,11 @@ impl SidecarClient {
             project_summary,
             user_request,
             doi,
+            llm: self.llm(PipelineStage::Extraction),
+            prompt_template: self.prompt(PromptKind::Extraction),
         };
-        let resp = self.client.post(&url).json(&req).send().await?;
-        let technique: TechniqueCard = resp.error_for_status()?.json().await?;
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let technique: TechniqueCard = read(resp).await?;
         debug!("Extracted technique: {}", technique.name);
         Ok(technique)
     }
@@ -111,6 +252,7 @@ impl SidecarClient {
         project_summary: String,
         user_request: String,
         max_techniques: usize,
+        guidance: Option<String>,
     ) -> anyhow::Result<Vec<TechniqueCard>> {
         let url = format!("{}/api/batch-extract-techniques", self.base_url);
         let req = BatchExtractRequest {
@@ -118,61 +260,127 @@ impl SidecarClient {
             project_summary,
             user_request,
             max_techniques,
+            guidance,
+            llm: self.llm(PipelineStage::Extraction),
+            prompt_template: self.prompt(PromptKind::Extraction),
         };
-        let resp = self.client.post(&url).json(&req).send().await?;
-        let techniques: Vec<TechniqueCard> = resp.error_for_status()?.json().await?;
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let techniques: Vec<TechniqueCard> = read(resp).await?;
         debug!("Batch extracted {} techniques", techniques.len());
         Ok(techniques)
     }
 
-    /// Generate a variant by applying a technique to the project.
+    /// Draft an implementation plan for a technique without writing code.
     #[instrument(skip(self, technique, project))]
+    pub async fn plan_variant(
+        &self,
+        technique: TechniqueCard,
+        project: ProjectProfile,
+        feedback: Option<String>,
+        style: Option<String>,
+    ) -> anyhow::Result<PlanVariantResponse> {
+        let url = format!("{}/api/plan-variant", self.base_url);
+        let req = PlanVariantRequest {
+            technique,
+            project,
+            feedback,
+            style,
+            llm: self.llm(PipelineStage::Generation),
+            prompt_template: self.prompt(PromptKind::Generation),
+        };
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let result: PlanVariantResponse = read(resp).await?;
+        Ok(result)
+    }
+
+    /// Generate a variant by applying a technique to the project.
+    /// `feedback` carries extra user instructions when regenerating, `plan`
+    /// an approved plan to follow and `style` a style directive.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(self, technique, project, plan))]
     pub async fn generate_variant(
         &self,
         technique: TechniqueCard,
         project: ProjectProfile,
         branch_name: String,
+        feedback: Option<String>,
+        plan: Option<ImplementationPlan>,
+        style: Option<String>,
+        install_dependencies: bool,
     ) -> anyhow::Result<GenerateVariantResponse> {
         let url = format!("{}/api/generate-variant", self.base_url);
         let req = GenerateVariantRequest {
             technique,
             project,
             branch_name,
+            feedback,
+            plan,
+            style,
+            install_dependencies,
+            llm: self.llm(PipelineStage::Generation),
+            prompt_template: self.prompt(PromptKind::Generation),
         };
-        let resp = self.client.post(&url).json(&req).send().await?;
-        let result: GenerateVariantResponse = resp.error_for_status()?.json().await?;
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let result: GenerateVariantResponse = read(resp).await?;
         Ok(result)
     }
 
-    /// Merge two variants with specified blend ratios.
-    #[instrument(skip(self, project))]
+    /// Cancel a running variant generation. Returns whether one was running.
+    #[instrument(skip(self))]
+    pub async fn cancel_generation(&self, branch_name: String) -> anyhow::Result<bool> {
+        let url = format!("{}/api/cancel-generation", self.base_url);
+        let req = CancelGenerationRequest { branch_name };
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let result: CancelGenerationResponse = read(resp).await?;
+        Ok(result.cancelled)
+    }
+
+    /// Current step of each running generation, keyed by branch name.
+    #[instrument(skip(self))]
+    pub async fn generation_progress(&self) -> anyhow::Result<HashMap<String, String>> {
+        let url = format!("{}/api/generation-progress", self.base_url);
+        let resp = self.send(self.get(&url)).await?;
+        let result: GenerationProgressResponse = read(resp).await?;
+        Ok(result.progress)
+    }
+
+    /// Merge source variants, each with its own blend ratio.
+    #[instrument(skip(self, sources, project))]
     pub async fn merge_variants(
         &self,
-        variant_a_branch: String,
-        variant_a_technique: serde_json::Value,
-        variant_b_branch: String,
-        variant_b_technique: serde_json::Value,
-        blend_a: u8,
-        blend_b: u8,
+        sources: Vec<MergeSourceRequest>,
+        guidance: Option<String>,
+        strategy: Option<String>,
         project: ProjectProfile,
         target_branch: String,
     ) -> anyhow::Result<GenerateVariantResponse> {
         let url = format!("{}/api/merge-variants", self.base_url);
         let req = MergeVariantsRequest {
-            variant_a_branch,
-            variant_a_technique,
-            variant_b_branch,
-            variant_b_technique,
-            blend_a,
-            blend_b,
+            sources,
+            guidance,
+            strategy,
             project,
             target_branch,
+            llm: self.llm(PipelineStage::Merge),
+            prompt_template: self.prompt(PromptKind::Merge),
         };
-        let resp = self.client.post(&url).json(&req).send().await?;
-        let result: GenerateVariantResponse = resp.error_for_status()?.json().await?;
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let result: GenerateVariantResponse = read(resp).await?;
         Ok(result)
     }
 
+    /// Have the LLM write one version of a hunk two merge sources conflict on.
+    #[instrument(skip(self, request))]
+    pub async fn reconcile_hunk(
+        &self,
+        request: ReconcileHunkRequest,
+    ) -> anyhow::Result<Vec<String>> {
+        let url = format!("{}/api/reconcile-hunk", self.base_url);
+        let resp = self.send(self.post(&url).json(&request)).await?;
+        let result: ReconcileHunkResponse = read(resp).await?;
+        Ok(result.lines)
+    }
+
     /// Run benchmarks on variant branches.
     #[instrument(skip(self))]
     pub async fn run_benchmark(
@@ -181,6 +389,8 @@ impl SidecarClient {
         project_path: std::path::PathBuf,
         metrics: Vec<String>,
         timeout_seconds: u64,
+        timeouts: HashMap<String, u64>,
+        repetitions: u32,
     ) -> anyhow::Result<HashMap<String, ExecutionMetrics>> {
         let url = format!("{}/api/run-benchmark", self.base_url);
         let req = RunBenchmarkRequest {
@@ -188,12 +398,23 @@ impl SidecarClient {
             project_path,
             metrics,
             timeout_seconds,
+            timeouts,
+            repetitions,
         };
-        let resp = self.client.post(&url).json(&req).send().await?;
-        let result: RunBenchmarkResponse = resp.error_for_status()?.json().await?;
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let result: RunBenchmarkResponse = read(resp).await?;
         Ok(result.results)
     }
 
+    /// Current stage of each branch in a running benchmark.
+    #[instrument(skip(self))]
+    pub async fn benchmark_progress(&self) -> anyhow::Result<HashMap<String, BenchmarkStage>> {
+        let url = format!("{}/api/benchmark-progress", self.base_url);
+        let resp = self.send(self.get(&url)).await?;
+        let result: BenchmarkProgressResponse = read(resp).await?;
+        Ok(result.progress)
+    }
+
     /// Run LLM-as-judge evaluation on variants.
     #[instrument(skip(self))]
     pub async fn llm_judge(
@@ -201,22 +422,50 @@ impl SidecarClient {
         variant_branches: Vec<String>,
         project_path: std::path::PathBuf,
         user_request: String,
+        ensemble: JudgeEnsemble,
     ) -> anyhow::Result<HashMap<String, JudgeScores>> {
         let url = format!("{}/api/llm-judge", self.base_url);
         let req = LlmJudgeRequest {
             variant_branches,
             project_path,
             user_request,
+            ensemble,
+            llm: self.llm(PipelineStage::Judge),
+            prompt_template: self.prompt(PromptKind::Judge),
         };
-        let resp = self.client.post(&url).json(&req).send().await?;
-        let result: LlmJudgeResponse = resp.error_for_status()?.json().await?;
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let result: LlmJudgeResponse = read(resp).await?;
         Ok(result.scores)
     }
 
+    /// Have the LLM judge compare every pair of variant branches.
+    #[instrument(skip(self))]
+    pub async fn llm_judge_pairwise(
+        &self,
+        variant_branches: Vec<String>,
+        project_path: std::path::PathBuf,
+        user_request: String,
+        ensemble: JudgeEnsemble,
+    ) -> anyhow::Result<Vec<PairwiseComparison>> {
+        let url = format!("{}/api/llm-judge-pairwise", self.base_url);
+        let req = LlmJudgeRequest {
+            variant_branches,
+            project_path,
+            user_request,
+            ensemble,
+            llm: self.llm(PipelineStage::Judge),
+            // The judge template scores one variant, not a pair.
+            prompt_template: None,
+        };
+        let resp = self.send(self.post(&url).json(&req)).await?;
+        let result: PairwiseJudgeResponse = read(resp).await?;
+        Ok(result.comparisons)
+    }
+
     /// Request graceful shutdown.
     pub async fn shutdown(&self) -> anyhow::Result<()> {
         let url = format!("{}/api/shutdown", self.base_url);
-        let _ = self.client.post(&url).send().await;
+        let _ = self.send(self.post(&url)).await;
         Ok(())
     }
 }
@@ -0,0 +1,49 @@
+//! Correlation IDs that tie together the log lines of one pipeline
+//! operation, such as an extraction, on both sides of the sidecar
+//! connection.
+
+use std::future::Future;
+
+use tracing::Instrument;
+
+/// Header carrying the ID to the sidecar, which tags its own log lines
+/// with it.
+pub const HEADER: &str = "X-Correlation-ID";
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+/// A new ID: the start of a random UUID, short enough to grep for.
+pub fn new_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..12].to_string()
+}
+
+/// Run `operation` under a new correlation ID: everything it logs is in an
+/// `operation` span carrying the ID, and the sidecar requests it makes send
+/// it in [`HEADER`]. Tasks it spawns don't inherit the ID.
+pub fn correlated<F: Future>(name: &'static str, operation: F) -> impl Future<Output = F::Output> {
+    let id = new_id();
+    let span = tracing::info_span!("operation", name, correlation_id = %id);
+    CORRELATION_ID.scope(id, operation.instrument(span))
+}
+
+/// The ID of the operation running on this task, if any.
+pub fn current() -> Option<String> {
+    CORRELATION_ID.try_with(String::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn operations_get_their_own_id() {
+        assert_eq!(current(), None);
+        let first = correlated("first", async { current() }).await.unwrap();
+        let second = correlated("second", async { current() }).await.unwrap();
+        assert_eq!(first.len(), 12);
+        assert_ne!(first, second);
+        assert_eq!(current(), None);
+    }
+}
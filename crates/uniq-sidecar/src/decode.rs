@@ -0,0 +1,128 @@
+//! Decoding of sidecar responses. Fields the client doesn't know are
+//! ignored, so the sidecar can grow ahead of it; a response that still
+//! doesn't fit fails with the field at fault and the text around it rather
+//! than a bare serde message for the whole phase.
+
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+/// Characters of the body shown before the point decoding failed.
+const SNIPPET_BEFORE: usize = 40;
+
+/// Characters of the body shown after it.
+const SNIPPET_AFTER: usize = 20;
+
+/// A sidecar response that doesn't match the protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Could not read the sidecar's {endpoint} response: {}{message} (near `{snippet}`)", field_prefix(.field))]
+pub struct DecodeError {
+    /// The endpoint that answered, e.g. `run-benchmark`.
+    pub endpoint: String,
+    /// Path to the offending field, e.g. `results.main.custom_metrics`, or
+    /// empty if the body as a whole is wrong.
+    pub field: String,
+    /// What was wrong with it, e.g. what type was expected.
+    pub message: String,
+    /// The body around where decoding stopped, on one line.
+    pub snippet: String,
+}
+
+fn field_prefix(field: &str) -> String {
+    if field.is_empty() {
+        String::new()
+    } else {
+        format!("`{}`: ", field)
+    }
+}
+
+/// Decode `body`, the response from `endpoint`, as a `T`.
+pub fn decode<T: DeserializeOwned>(endpoint: &str, body: &str) -> Result<T, DecodeError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        let field = match e.path().to_string() {
+            root if root == "." => String::new(),
+            path => path,
+        };
+        let inner = e.into_inner();
+        // The position is shown as a snippet instead.
+        let message = inner.to_string();
+        let message = match message.rsplit_once(" at line ") {
+            Some((message, _)) => message.to_string(),
+            None => message,
+        };
+        DecodeError {
+            endpoint: endpoint.trim_start_matches("/api/").to_string(),
+            field,
+            message,
+            snippet: snippet(body, inner.line(), inner.column()),
+        }
+    })
+}
+
+/// The text of `body` around `line` and `column` (both from 1), with
+/// whitespace runs collapsed so it fits on a status line.
+fn snippet(body: &str, line: usize, column: usize) -> String {
+    let Some(text) = body.lines().nth(line.saturating_sub(1)) else {
+        return String::new();
+    };
+    // serde_json counts columns in bytes.
+    let mut at = column.min(text.len());
+    while !text.is_char_boundary(at) {
+        at -= 1;
+    }
+    let (before, after) = text.split_at(at);
+    let skipped = before.chars().count().saturating_sub(SNIPPET_BEFORE);
+    let before: String = before.chars().skip(skipped).collect();
+    let after: String = after.chars().take(SNIPPET_AFTER).collect();
+    let mut out = String::new();
+    if skipped > 0 {
+        out.push('…');
+    }
+    out.push_str(&before);
+    out.push_str(&after);
+    if after.chars().count() < text[at..].chars().count() {
+        out.push('…');
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{GenerateVariantResponse, RunBenchmarkResponse};
+
+    #[test]
+    fn ignores_fields_the_client_does_not_know() {
+        let body = r#"{"success": true, "modified_files": ["a.py"], "new_dependencies": [],
+            "error": null, "cost_usd": 0.42, "transcript": {"plan": [], "model": "x"}}"#;
+        let response: GenerateVariantResponse = decode("/api/generate-variant", body).unwrap();
+        assert!(response.success);
+        assert_eq!(response.modified_files, ["a.py"]);
+    }
+
+    #[test]
+    fn names_the_field_at_fault() {
+        let body = r#"{"results": {"main": {"build_success": true, "build_error": null,
+            "test_pass_rate": 1.0, "tests_passed": 3, "tests_total": 3, "runtime_ms": 12.5,
+            "memory_mb": null, "custom_metrics": {"accuracy": 0.93}}}}"#;
+        let error = decode::<RunBenchmarkResponse>("/api/run-benchmark", body).unwrap_err();
+        assert_eq!(error.endpoint, "run-benchmark");
+        assert_eq!(error.field, "results.main.custom_metrics.accuracy");
+        assert_eq!(
+            error.message,
+            "invalid type: floating point `0.93`, expected struct MetricValue"
+        );
+        assert_eq!(
+            error.snippet,
+            r#"…ull, "custom_metrics": {"accuracy": 0.93}}}}"#
+        );
+
+        let error = decode::<RunBenchmarkResponse>("/api/run-benchmark", "Internal Server Error")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not read the sidecar's run-benchmark response: expected value \
+             (near `Internal Server Error`)"
+        );
+    }
+}
@@ -1,6 +1,10 @@
 pub mod client;
+pub mod correlation;
+pub mod decode;
 pub mod manager;
 pub mod protocol;
+pub mod rate_limit;
+pub mod schema;
 
 pub use client::SidecarClient;
 pub use manager::SidecarManager;
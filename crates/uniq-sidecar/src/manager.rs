@@ -5,11 +5,12 @@
 //! - Waiting for it to become healthy
 //! - Graceful and forced shutdown
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tokio::process::{Child, Command};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Level};
+use uniq_core::config::ApiKeysConfig;
 
 /// How long to wait for the sidecar to report healthy (in seconds).
 const STARTUP_TIMEOUT_SECS: u64 = 60;
@@ -22,6 +23,7 @@ pub struct SidecarManager {
     child: Option<Child>,
     port: u16,
     sidecar_dir: PathBuf,
+    api_keys: ApiKeysConfig,
 }
 
 impl SidecarManager {
@@ -31,9 +33,18 @@ impl SidecarManager {
             child: None,
             port: 0,
             sidecar_dir,
+            api_keys: ApiKeysConfig::default(),
         }
     }
 
+    /// Hand the sidecar the API keys from the resolved config (profile,
+    /// `${VAR}` references and `UNIQ_API_KEYS__*` overrides applied). Keys
+    /// left empty fall back to whatever the environment already has.
+    pub fn with_api_keys(mut self, api_keys: &ApiKeysConfig) -> Self {
+        self.api_keys = api_keys.clone();
+        self
+    }
+
     /// Get the port the sidecar is running on.
     pub fn port(&self) -> u16 {
         self.port
@@ -76,31 +87,18 @@ impl SidecarManager {
                 &port.to_string(),
             ])
             .current_dir(&self.sidecar_dir)
+            .envs(key_env(&self.api_keys))
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .kill_on_drop(true)
             .spawn()?;
 
-        // Spawn a task to log stderr output.
+        // Spawn tasks to copy the sidecar's output into our log.
         if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let reader = BufReader::new(stderr);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    debug!(target: "sidecar::stderr", "{}", line);
-                }
-            });
+            tokio::spawn(forward_output(stderr));
         }
-
-        // Spawn a task to log stdout output.
         if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let reader = BufReader::new(stdout);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    debug!(target: "sidecar::stdout", "{}", line);
-                }
-            });
+            tokio::spawn(forward_output(stdout));
         }
 
         self.child = Some(child);
@@ -198,9 +196,104 @@ impl Drop for SidecarManager {
     }
 }
 
+/// Whether `dir` looks like the sidecar project: a `pyproject.toml` and the
+/// `src/server.py` entry point.
+pub fn is_sidecar_dir(dir: &Path) -> bool {
+    dir.join("pyproject.toml").is_file() && dir.join("src").join("server.py").is_file()
+}
+
+/// Look for the sidecar in `./sidecar`, then beside the executable and in
+/// its parent directories (which covers `target/debug/uniq` in a checkout).
+pub fn detect_sidecar_dir() -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Ok(cwd) = std::env::current_dir() {
+        candidates.push(cwd.join("sidecar"));
+    }
+    if let Ok(exe) = std::env::current_exe() {
+        candidates.extend(
+            exe.ancestors()
+                .skip(1)
+                .take(4)
+                .map(|dir| dir.join("sidecar")),
+        );
+    }
+    candidates.into_iter().find(|dir| is_sidecar_dir(dir))
+}
+
 /// Find an available TCP port on localhost.
+/// The environment variables the sidecar reads `api_keys` from.
+fn key_env(api_keys: &ApiKeysConfig) -> Vec<(&'static str, &str)> {
+    [
+        ("ANTHROPIC_API_KEY", api_keys.anthropic.as_str()),
+        ("OPENAI_API_KEY", api_keys.openai.as_str()),
+    ]
+    .into_iter()
+    .filter(|(_, key)| !key.is_empty())
+    .collect()
+}
+
+/// Log each line of the sidecar's `output` at the level Python logged it
+/// at, so its warnings and errors pass the default filter. Lines without a
+/// level, such as a traceback's, go with the line before them.
+async fn forward_output(output: impl AsyncRead + Unpin) {
+    let mut lines = BufReader::new(output).lines();
+    let mut level = Level::DEBUG;
+    while let Ok(Some(line)) = lines.next_line().await {
+        level = python_level(&line).unwrap_or(level);
+        match level {
+            Level::ERROR => error!(target: "sidecar", "{}", line),
+            Level::WARN => warn!(target: "sidecar", "{}", line),
+            Level::INFO => info!(target: "sidecar", "{}", line),
+            _ => debug!(target: "sidecar", "{}", line),
+        }
+    }
+}
+
+/// The level of a Python log line: the sidecar's own format puts it after
+/// the timestamp, uvicorn's (`WARNING:  ...`) first.
+fn python_level(line: &str) -> Option<Level> {
+    line.split_whitespace()
+        .take(3)
+        .find_map(|word| match word.trim_end_matches(':') {
+            "CRITICAL" | "ERROR" => Some(Level::ERROR),
+            "WARNING" => Some(Level::WARN),
+            "INFO" => Some(Level::INFO),
+            "DEBUG" => Some(Level::DEBUG),
+            _ => None,
+        })
+}
+
 fn find_free_port() -> anyhow::Result<u16> {
     let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
     let port = listener.local_addr()?.port();
     Ok(port)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_configured_keys_are_passed_on() {
+        let api_keys = ApiKeysConfig {
+            anthropic: "sk-ant".into(),
+            semantic_scholar: "s2".into(),
+            openai: String::new(),
+        };
+        assert_eq!(key_env(&api_keys), [("ANTHROPIC_API_KEY", "sk-ant")]);
+    }
+
+    #[test]
+    fn python_levels_are_read_from_either_log_format() {
+        let own = "2026-10-15 09:12:01,532 WARNING src.services.merger [op-7] Merge failed";
+        assert_eq!(python_level(own), Some(Level::WARN));
+        let uvicorn = "ERROR:    Exception in ASGI application";
+        assert_eq!(python_level(uvicorn), Some(Level::ERROR));
+        assert_eq!(
+            python_level("INFO:     Started server process"),
+            Some(Level::INFO)
+        );
+        assert_eq!(python_level("Traceback (most recent call last):"), None);
+        assert_eq!(python_level("    raise ValueError('WARNING')"), None);
+    }
+}
@@ -1,31 +1,88 @@
 //! Request and response types for communicating with the Python sidecar.
 //! These types mirror the Python Pydantic models exactly.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use uniq_core::llm::{LlmBackend, LlmProvider};
 
 // ── Health ──────────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct HealthResponse {
     pub status: String,
     pub version: String,
+    /// Whether an Anthropic API key was found, so Claude calls can work.
+    #[serde(default)]
+    pub claude: bool,
+    /// Whether an OpenAI API key was found.
+    #[serde(default)]
+    pub openai: bool,
+}
+
+impl HealthResponse {
+    /// Whether the sidecar has the API key `backend` needs, or `None` if it
+    /// doesn't need one (Ollama, or a self-hosted OpenAI-style server).
+    pub fn has_key_for(&self, backend: &LlmBackend) -> Option<bool> {
+        match backend.provider {
+            LlmProvider::Anthropic => Some(self.claude),
+            LlmProvider::OpenAi if self.openai || backend.base_url.is_none() => Some(self.openai),
+            LlmProvider::OpenAi | LlmProvider::Ollama => None,
+        }
+    }
+}
+
+/// A call to a backend that costs nothing, to check its key and connection.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PingRequest {
+    pub llm: LlmBackend,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PingResponse {
+    pub ok: bool,
+    /// Why the backend couldn't be reached, when it couldn't.
+    pub error: Option<String>,
+}
+
+// ── Token Usage ─────────────────────────────────────────────────
+
+/// Tokens one phase has used with one model since the sidecar started.
+/// Phases are "intake", "techniques", "generation", "benchmark" and
+/// "other".
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+pub struct UsageEntry {
+    pub phase: String,
+    #[serde(default)]
+    pub provider: LlmProvider,
+    pub model: String,
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UsageResponse {
+    #[serde(default)]
+    pub entries: Vec<UsageEntry>,
 }
 
 // ── Project Analysis ────────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AnalyzeProjectRequest {
     pub path: PathBuf,
     pub description: String,
+    /// LLM backend to run on.
+    pub llm: LlmBackend,
 }
 
 // Response is uniq_core::project::ProjectProfile (deserialized directly)
 
 // ── Paper Search ────────────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SearchPapersRequest {
     pub queries: Vec<String>,
     pub max_results: usize,
@@ -38,7 +95,7 @@ pub struct SearchPapersRequest {
 
 // ── Technique Extraction ────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ExtractTechniqueRequest {
     pub pdf_url: Option<String>,
     pub paper_id: String,
@@ -46,80 +103,208 @@ pub struct ExtractTechniqueRequest {
     pub project_summary: String,
     pub user_request: String,
     pub doi: Option<String>,
+    /// LLM backend to run on.
+    pub llm: LlmBackend,
+    /// Replaces the sidecar's built-in prompt (see [`uniq_core::prompts`]).
+    pub prompt_template: Option<String>,
 }
 
 // Response is uniq_core::research::TechniqueCard
 
 // ── Batch Technique Extraction (abstract-based) ─────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct BatchExtractRequest {
     pub papers: Vec<uniq_core::research::PaperMeta>,
     pub project_summary: String,
     pub user_request: String,
     pub max_techniques: usize,
+    /// Optional user hint steering what to extract (used for re-extraction).
+    pub guidance: Option<String>,
+    /// LLM backend to run on.
+    pub llm: LlmBackend,
+    /// Replaces the sidecar's built-in prompt (see [`uniq_core::prompts`]).
+    pub prompt_template: Option<String>,
 }
 
 // Response is Vec<uniq_core::research::TechniqueCard>
 
 // ── Variant Generation ──────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct GenerateVariantRequest {
     pub technique: uniq_core::research::TechniqueCard,
     pub project: uniq_core::project::ProjectProfile,
     pub branch_name: String,
+    pub feedback: Option<String>,
+    /// An approved plan the generated code must follow.
+    pub plan: Option<uniq_core::variant::ImplementationPlan>,
+    /// Style directive to implement the technique in.
+    pub style: Option<String>,
+    /// Install new dependencies on the branch before committing.
+    pub install_dependencies: bool,
+    /// LLM backend to run on.
+    pub llm: LlmBackend,
+    /// Replaces the sidecar's built-in prompt (see [`uniq_core::prompts`]).
+    pub prompt_template: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PlanVariantRequest {
+    pub technique: uniq_core::research::TechniqueCard,
+    pub project: uniq_core::project::ProjectProfile,
+    pub feedback: Option<String>,
+    pub style: Option<String>,
+    /// LLM backend to run on.
+    pub llm: LlmBackend,
+    /// Replaces the sidecar's built-in prompt (see [`uniq_core::prompts`]).
+    pub prompt_template: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PlanVariantResponse {
+    pub success: bool,
+    pub plan: Option<uniq_core::variant::ImplementationPlan>,
+    pub error: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct GenerateVariantResponse {
     pub success: bool,
     pub modified_files: Vec<String>,
     pub new_dependencies: Vec<String>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub transcript: Option<uniq_core::variant::GenerationTranscript>,
+    #[serde(default)]
+    pub dependency_check: Option<uniq_core::variant::DependencyCheck>,
+    /// For merges, files the sources conflict on in git.
+    #[serde(default)]
+    pub conflicts: Vec<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CancelGenerationRequest {
+    pub branch_name: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CancelGenerationResponse {
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GenerationProgressResponse {
+    /// Current step (e.g. "writing src/model.py") per running branch.
+    #[serde(default)]
+    pub progress: HashMap<String, String>,
 }
 
 // ── Variant Merge ───────────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MergeSourceRequest {
+    pub branch: String,
+    pub technique: serde_json::Value,
+    /// Blend percentage: 0, 25, 50, 75 or 100.
+    pub blend: u8,
+    /// Per-component blends overriding `blend` for those components.
+    pub components: Vec<ComponentBlendRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ComponentBlendRequest {
+    pub name: String,
+    pub blend: u8,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct MergeVariantsRequest {
-    pub variant_a_branch: String,
-    pub variant_a_technique: serde_json::Value,
-    pub variant_b_branch: String,
-    pub variant_b_technique: serde_json::Value,
-    pub blend_a: u8,
-    pub blend_b: u8,
+    /// Two to four source variants.
+    pub sources: Vec<MergeSourceRequest>,
+    /// Free-form instructions for combining the sources.
+    pub guidance: Option<String>,
+    /// Strategy directive for fitting the techniques together.
+    pub strategy: Option<String>,
     pub project: uniq_core::project::ProjectProfile,
     pub target_branch: String,
+    /// LLM backend to run on.
+    pub llm: LlmBackend,
+    /// Replaces the sidecar's built-in prompt (see [`uniq_core::prompts`]).
+    pub prompt_template: Option<String>,
 }
 
 // Response is GenerateVariantResponse
 
+/// One conflicting hunk between two merge sources, for the LLM to reconcile.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReconcileHunkRequest {
+    pub path: String,
+    pub a: Vec<String>,
+    pub b: Vec<String>,
+    pub base: Vec<String>,
+    /// Clean lines around the hunk.
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+    /// Technique names of the two sources.
+    pub sources: Vec<String>,
+    pub guidance: Option<String>,
+    pub project: uniq_core::project::ProjectProfile,
+    /// LLM backend to run on.
+    pub llm: LlmBackend,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReconcileHunkResponse {
+    pub lines: Vec<String>,
+}
+
 // ── Benchmark ───────────────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct RunBenchmarkRequest {
     pub variant_branches: Vec<String>,
     pub project_path: PathBuf,
     pub metrics: Vec<String>,
     pub timeout_seconds: u64,
+    /// Per-branch timeouts overriding `timeout_seconds`.
+    pub timeouts: HashMap<String, u64>,
+    /// Times to repeat the measured command per branch.
+    pub repetitions: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct RunBenchmarkResponse {
     pub results: HashMap<String, uniq_core::benchmark::ExecutionMetrics>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BenchmarkProgressResponse {
+    /// Current stage per branch being benchmarked.
+    #[serde(default)]
+    pub progress: HashMap<String, uniq_core::benchmark::BenchmarkStage>,
+}
+
 // ── LLM Judge ───────────────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct LlmJudgeRequest {
     pub variant_branches: Vec<String>,
     pub project_path: PathBuf,
     pub user_request: String,
+    pub ensemble: uniq_core::benchmark::JudgeEnsemble,
+    /// LLM backend to run on.
+    pub llm: LlmBackend,
+    /// Replaces the sidecar's built-in prompt (see [`uniq_core::prompts`]).
+    pub prompt_template: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct LlmJudgeResponse {
     pub scores: HashMap<String, uniq_core::benchmark::JudgeScores>,
 }
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PairwiseJudgeResponse {
+    pub comparisons: Vec<uniq_core::benchmark::PairwiseComparison>,
+}
@@ -0,0 +1,166 @@
+//! Client-side pacing of sidecar requests, so fanning out (one extraction
+//! per paper, say) doesn't trip the limits of the APIs behind the sidecar,
+//! and a 429 it passes on is waited out rather than failing the operation.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How many times a request answered with 429 is tried again.
+pub const MAX_RETRIES: u32 = 5;
+
+/// Longest wait before a retry, whatever the server asks for.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Endpoints grouped by the upstream limit they count against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointClass {
+    /// Calls Claude.
+    Claude,
+    /// Queries the paper search APIs.
+    Search,
+    /// Handled by the sidecar itself: health, progress, cancellation.
+    Local,
+}
+
+impl EndpointClass {
+    /// The class of the endpoint at URL `path`.
+    pub fn for_path(path: &str) -> Self {
+        let endpoint = path.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+        match endpoint {
+            "search-papers" => Self::Search,
+            "health"
+            | "usage"
+            | "cancel-generation"
+            | "generation-progress"
+            | "run-benchmark"
+            | "benchmark-progress"
+            | "shutdown" => Self::Local,
+            _ => Self::Claude,
+        }
+    }
+
+    /// Least time between the starts of two requests, or `None` if they
+    /// aren't limited.
+    fn interval(self) -> Option<Duration> {
+        match self {
+            Self::Claude => Some(Duration::from_millis(500)),
+            Self::Search => Some(Duration::from_secs(1)),
+            Self::Local => None,
+        }
+    }
+}
+
+/// Spaces out the starts of requests in each [`EndpointClass`].
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    /// Earliest start of the next request, per class.
+    next: Mutex<HashMap<EndpointClass, Instant>>,
+}
+
+impl RateLimiter {
+    /// Wait for a slot to send a request of `class`.
+    pub async fn acquire(&self, class: EndpointClass) {
+        let wait = self.reserve(class, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take the next slot for `class` and return how long after `now` it is.
+    fn reserve(&self, class: EndpointClass, now: Instant) -> Duration {
+        let Some(interval) = class.interval() else {
+            return Duration::ZERO;
+        };
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = next.get(&class).map_or(now, |&at| at.max(now));
+        next.insert(class, slot + interval);
+        slot - now
+    }
+
+    /// Hold back every request of `class` for `delay`, after one was
+    /// rejected for going too fast.
+    pub fn back_off(&self, class: EndpointClass, delay: Duration) {
+        let until = Instant::now() + delay;
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        let at = next.entry(class).or_insert(until);
+        *at = (*at).max(until);
+    }
+}
+
+/// How long to wait before retry number `attempt` (from 0) of a request
+/// answered with 429: what its `Retry-After` header asks, or a delay
+/// doubling from one second if there's no usable header.
+pub fn retry_delay(retry_after: Option<&str>, attempt: u32) -> Duration {
+    let delay = retry_after
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(|secs| Duration::from_secs_f64(secs.min(MAX_RETRY_DELAY.as_secs_f64())))
+        .unwrap_or_else(|| Duration::from_secs(1 << attempt.min(6)));
+    delay.min(MAX_RETRY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_endpoints() {
+        let class = |path| EndpointClass::for_path(path);
+        assert_eq!(class("/api/extract-technique"), EndpointClass::Claude);
+        assert_eq!(class("/api/llm-judge-pairwise"), EndpointClass::Claude);
+        assert_eq!(class("/api/search-papers"), EndpointClass::Search);
+        assert_eq!(class("/api/generation-progress"), EndpointClass::Local);
+        assert_eq!(class("/api/health/"), EndpointClass::Local);
+    }
+
+    #[test]
+    fn spaces_out_requests_per_class() {
+        let limiter = RateLimiter::default();
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(EndpointClass::Claude, now), Duration::ZERO);
+        assert_eq!(
+            limiter.reserve(EndpointClass::Claude, now),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            limiter.reserve(EndpointClass::Claude, now),
+            Duration::from_secs(1)
+        );
+        assert_eq!(limiter.reserve(EndpointClass::Search, now), Duration::ZERO);
+        assert_eq!(limiter.reserve(EndpointClass::Local, now), Duration::ZERO);
+        assert_eq!(limiter.reserve(EndpointClass::Local, now), Duration::ZERO);
+        // A slot in the past isn't owed.
+        let later = now + Duration::from_secs(5);
+        assert_eq!(
+            limiter.reserve(EndpointClass::Claude, later),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn backs_off_everything_in_the_class() {
+        let limiter = RateLimiter::default();
+        limiter.back_off(EndpointClass::Search, Duration::from_secs(10));
+        let wait = limiter.reserve(EndpointClass::Search, Instant::now());
+        assert!(wait > Duration::from_secs(9), "{:?}", wait);
+        assert_eq!(
+            limiter.reserve(EndpointClass::Claude, Instant::now()),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn retry_delay_follows_retry_after() {
+        assert_eq!(retry_delay(Some("3"), 0), Duration::from_secs(3));
+        assert_eq!(retry_delay(Some(" 0.5 "), 4), Duration::from_millis(500));
+        assert_eq!(retry_delay(Some("86400"), 0), MAX_RETRY_DELAY);
+        // HTTP dates and junk fall back to doubling.
+        assert_eq!(
+            retry_delay(Some("Wed, 21 Oct 2026 07:28:00 GMT"), 0),
+            Duration::from_secs(1)
+        );
+        assert_eq!(retry_delay(None, 2), Duration::from_secs(4));
+        assert_eq!(retry_delay(None, 30), MAX_RETRY_DELAY);
+    }
+}
@@ -0,0 +1,94 @@
+//! JSON Schemas for the types in [`crate::protocol`], so the sidecar's
+//! Pydantic models can be checked against what this client sends and
+//! expects. `uniq schema` prints them; `sidecar/protocol.schema.json` is the
+//! copy the sidecar's tests read, and the test below fails when it's stale.
+
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Value};
+use uniq_core::project::ProjectProfile;
+use uniq_core::research::{PaperMeta, TechniqueCard};
+
+use crate::protocol::*;
+
+/// Every protocol type, and the core types sent as whole bodies, under
+/// `definitions`.
+pub fn protocol_schema() -> Value {
+    let mut gen = SchemaSettings::draft07().into_generator();
+    define::<HealthResponse>(&mut gen);
+    define::<PingRequest>(&mut gen);
+    define::<PingResponse>(&mut gen);
+    define::<UsageResponse>(&mut gen);
+    define::<AnalyzeProjectRequest>(&mut gen);
+    define::<ProjectProfile>(&mut gen);
+    define::<SearchPapersRequest>(&mut gen);
+    define::<PaperMeta>(&mut gen);
+    define::<ExtractTechniqueRequest>(&mut gen);
+    define::<TechniqueCard>(&mut gen);
+    define::<BatchExtractRequest>(&mut gen);
+    define::<GenerateVariantRequest>(&mut gen);
+    define::<PlanVariantRequest>(&mut gen);
+    define::<PlanVariantResponse>(&mut gen);
+    define::<GenerateVariantResponse>(&mut gen);
+    define::<CancelGenerationRequest>(&mut gen);
+    define::<CancelGenerationResponse>(&mut gen);
+    define::<GenerationProgressResponse>(&mut gen);
+    define::<MergeVariantsRequest>(&mut gen);
+    define::<ReconcileHunkRequest>(&mut gen);
+    define::<ReconcileHunkResponse>(&mut gen);
+    define::<RunBenchmarkRequest>(&mut gen);
+    define::<RunBenchmarkResponse>(&mut gen);
+    define::<BenchmarkProgressResponse>(&mut gen);
+    define::<LlmJudgeRequest>(&mut gen);
+    define::<LlmJudgeResponse>(&mut gen);
+    define::<PairwiseJudgeResponse>(&mut gen);
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "uniq sidecar protocol",
+        "definitions": gen.take_definitions(),
+    })
+}
+
+/// [`protocol_schema`] as it's written to `protocol.schema.json`.
+pub fn protocol_schema_json() -> String {
+    let mut out =
+        serde_json::to_string_pretty(&protocol_schema()).expect("schemas serialize to JSON");
+    out.push('\n');
+    out
+}
+
+/// Add `T`, and every named type it uses, to the definitions.
+fn define<T: JsonSchema>(gen: &mut SchemaGenerator) {
+    gen.subschema_for::<T>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn defines_protocol_and_nested_types() {
+        let schema = protocol_schema();
+        let definitions = schema["definitions"].as_object().unwrap();
+        for name in ["MergeSourceRequest", "JudgeEnsemble", "MetricValue"] {
+            assert!(definitions.contains_key(name), "missing {}", name);
+        }
+        // Strings on the wire, whatever the Rust enum looks like.
+        assert_eq!(definitions["Language"]["type"], "string");
+        let required = &definitions["GenerateVariantResponse"]["required"];
+        assert!(!required.as_array().unwrap().contains(&json!("conflicts")));
+    }
+
+    #[test]
+    fn checked_in_schema_is_current() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../sidecar/protocol.schema.json");
+        let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            checked_in == protocol_schema_json(),
+            "{} is out of date; regenerate it with `uniq schema -o sidecar/protocol.schema.json`",
+            path.display()
+        );
+    }
+}
@@ -1,9 +1,22 @@
 //! Action enum — the central message bus for the TUI.
 //! All user interactions and async results flow through here.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use uniq_core::benchmark::{
+    BenchmarkStage, ExecutionMetrics, JudgeScores, MetricValue, PairwiseComparison,
+};
+use uniq_core::conflict::FileConflict;
+use uniq_core::export::ExportFormat;
+use uniq_core::git::RepoStatus;
+use uniq_core::llm::LlmProvider;
+use uniq_core::merge::{MergePreview, MergeSource, MergeStrategy};
 use uniq_core::project::ProjectProfile;
+use uniq_core::report::ReportFormat;
 use uniq_core::research::{PaperMeta, TechniqueCard};
-use uniq_core::variant::Variant;
+use uniq_core::variant::{FileOverlap, ImplementationPlan, Variant};
+use uniq_sidecar::protocol::UsageEntry;
 
 /// Every possible action that can occur in the application.
 #[derive(Debug, Clone)]
@@ -15,12 +28,45 @@ pub enum Action {
     NextPhase,
     /// Move to the previous phase.
     PrevPhase,
+    /// Focus the next panel of the current phase, or move to the next phase
+    /// if it has only one.
+    FocusNext,
+    /// Focus the previous panel, or move to the previous phase.
+    FocusPrev,
 
     // ── Global ──────────────────────────────────────────────
     /// Quit the application.
     Quit,
+    /// Draw the screen again, e.g. after the terminal was resized.
+    Redraw,
     /// Toggle help overlay.
     ToggleHelp,
+    /// Esc: close the topmost dialog or view. Components with nothing left
+    /// to close answer with `CancelPhaseTask`.
+    Escape,
+    /// Cancel the current phase's background work, after a second Esc.
+    CancelPhaseTask,
+    /// The phase's analysis, search or extraction task was aborted.
+    PhaseTaskCancelled(Phase),
+    /// Show or hide the log of earlier status messages.
+    ToggleStatusLog,
+    /// Show or hide the timeline of what happened this session.
+    ToggleTimeline,
+    /// Show or hide token usage and cost by phase.
+    ToggleUsage,
+    /// Show or hide the pipeline summary sidebar.
+    ToggleSidebar,
+    /// Open the fuzzy-searchable list of all actions.
+    OpenCommandPalette,
+    /// Switch to the next theme preset.
+    CycleTheme,
+    /// Copy the highlighted item's text (citation, summary or branch).
+    Copy,
+    /// Put `text` on the clipboard; `what` names it in the status message.
+    CopyText {
+        text: String,
+        what: String,
+    },
     /// Display a status message in the status bar.
     SetStatus(String),
     /// Clear the status message.
@@ -28,6 +74,27 @@ pub enum Action {
     /// A tick event for animations and polling.
     Tick,
 
+    // ── First-run setup ─────────────────────────────────────
+    /// Save the key and sidecar location from setup, then start the
+    /// sidecar to check they work.
+    TestConnection {
+        api_key: String,
+        sidecar_dir: PathBuf,
+    },
+    /// The sidecar started; `api_key` is whether it has the key the
+    /// configured `provider` needs (`None` if it needs none).
+    /// `ping_error` is why a call to the backend failed, when setup made
+    /// one.
+    SidecarReady {
+        provider: LlmProvider,
+        api_key: Option<bool>,
+        ping_error: Option<String>,
+    },
+    /// The sidecar failed to start.
+    SidecarFailed(String),
+    /// Setup is done (or skipped); carry on to Phase 1.
+    FinishOnboarding,
+
     // ── Phase 1: Project Intake ─────────────────────────────
     /// User submitted project path and description.
     SubmitProject {
@@ -38,6 +105,14 @@ pub enum Action {
     ProjectAnalyzed(Box<ProjectProfile>),
     /// Project analysis failed.
     ProjectAnalysisFailed(String),
+    /// Edit the description in `$EDITOR`.
+    EditDescriptionExternally,
+    /// Show or hide the directory tree for picking the project path.
+    ToggleDirTree,
+    /// Suspend the TUI and open this text in `$EDITOR`.
+    OpenInEditor(String),
+    /// The description as saved in `$EDITOR`.
+    DescriptionEdited(String),
 
     // ── Phase 2: Research Discovery ─────────────────────────
     /// Start searching for papers.
@@ -75,10 +150,81 @@ pub enum Action {
     ToggleTechnique(usize),
     /// Confirm technique selection and proceed to generation.
     ConfirmTechniques,
+    /// Select every extracted technique.
+    SelectAllTechniques,
+    /// Clear the technique selection.
+    SelectNoTechniques,
+    /// Flip the selection of every technique.
+    InvertTechniqueSelection,
+    /// Ask how many of the most relevant techniques to select.
+    OpenSelectTopPrompt,
+    /// Select exactly the N most relevant techniques.
+    SelectTopTechniques(usize),
+    /// Cycle the complexity filter: all → low → medium → high → all.
+    CycleComplexityFilter,
+    /// Toggle hiding techniques that need dependencies the project lacks.
+    ToggleKnownDepsFilter,
+    /// Toggle nesting techniques under their source paper.
+    ToggleGroupByPaper,
+    /// Merge the technique under the cursor and its duplicates from other
+    /// papers into one card.
+    ConsolidateDuplicates,
+    /// Keep the technique under the cursor and drop its duplicates from
+    /// other papers.
+    DropDuplicates,
+    /// Write the selected (or all) technique cards to a file.
+    ExportTechniques(ExportFormat),
+    /// Toggle the side-by-side comparison of the selected techniques, or
+    /// mark the highlighted variant for comparison.
+    ToggleComparison,
+    /// Ask for guidance to re-extract the highlighted technique.
+    OpenReExtractPrompt,
+    /// Re-run extraction for the technique at `index` with a user hint.
+    ReExtractTechnique {
+        index: usize,
+        guidance: String,
+    },
+    /// A guided re-extraction finished; replaces the card for technique
+    /// `name` of paper `paper_id`, if it's still there.
+    TechniqueReExtracted {
+        paper_id: String,
+        name: String,
+        card: Box<TechniqueCard>,
+    },
 
     // ── Phase 4: Variant Generation ─────────────────────────
     /// Start generating variants.
     StartGeneration,
+    /// The project can't be branched from as it is; ask what to do.
+    OpenGitSetup {
+        status: RepoStatus,
+        path: String,
+    },
+    /// Commit the project as it is, making it a repository first if need
+    /// be, then start generating.
+    CommitBaseline,
+    /// Start generating from the last commit, leaving uncommitted changes
+    /// out.
+    GenerateFromLastCommit,
+    /// A queued variant got a generation slot and started generating.
+    VariantGenerationStarted {
+        variant_id: String,
+    },
+    /// A plan was drafted for a variant (plan-first mode) and awaits approval.
+    VariantPlanned {
+        variant_id: String,
+        plan: Box<ImplementationPlan>,
+    },
+    /// Approve the highlighted variant's plan.
+    ApprovePlan,
+    /// Generate a variant following its approved plan.
+    ApproveVariantPlan {
+        variant_id: String,
+    },
+    /// Current step of each running generation, keyed by branch name.
+    GenerationProgress(HashMap<String, String>),
+    /// Claude tokens used so far, from the sidecar.
+    UsageUpdated(Vec<UsageEntry>),
     /// A variant was generated successfully.
     VariantGenerated(Box<Variant>),
     /// A variant generation failed.
@@ -88,39 +234,195 @@ pub enum Action {
     },
     /// All variants generated.
     GenerationComplete,
+    /// Ask for feedback to regenerate the highlighted variant.
+    OpenRegeneratePrompt,
+    /// Regenerate a variant as a new revision with extra instructions.
+    RegenerateVariant {
+        variant_id: String,
+        feedback: String,
+    },
+    /// Ask for a new name for the highlighted variant.
+    OpenRenamePrompt,
+    /// Rename a variant, optionally replacing its branch suffix too.
+    RenameVariant {
+        variant_id: String,
+        name: String,
+        branch_suffix: Option<String>,
+    },
+    /// Compare the files two variants touched.
+    CompareVariants {
+        a: String,
+        b: String,
+    },
+    /// The file-level comparison of two variants is ready.
+    VariantsCompared {
+        a: String,
+        b: String,
+        overlap: FileOverlap,
+        diff: String,
+    },
+    /// Write every ready variant's changes as a patch series.
+    ExportVariantPatches,
+    /// Write a report on the whole session.
+    ExportReport(ReportFormat),
+    /// Delete the highlighted variant (asks to press again to confirm).
+    DeleteVariant,
+    /// Drop a variant and delete its branch.
+    DiscardVariant {
+        variant_id: String,
+    },
+    /// Cancel generation of the highlighted variant.
+    CancelVariantGeneration,
+    /// Ask the sidecar to stop generating a variant.
+    CancelGeneration {
+        variant_id: String,
+        branch_name: String,
+    },
 
     // ── Phase 5: Benchmarking ───────────────────────────────
     /// Start benchmarking all variants.
     StartBenchmark,
-    /// Benchmark results updated for a variant.
+    /// Benchmark results for a variant's branch, or the baseline's.
+    /// Execution metrics and judge scores arrive separately.
     BenchmarkUpdated {
-        variant_id: String,
+        branch: String,
+        execution: Option<Box<ExecutionMetrics>>,
+        judge: Option<JudgeScores>,
+    },
+    /// Metrics from metric plugins for a benchmarked branch, added to its
+    /// execution metrics.
+    PluginMetrics {
+        branch: String,
+        metrics: Vec<MetricValue>,
+    },
+    /// Verdicts of a pairwise judge tournament between variants.
+    PairwiseJudged {
+        comparisons: Vec<PairwiseComparison>,
+    },
+    /// A variant moved to a new stage of the benchmark run.
+    BenchmarkProgress {
+        branch: String,
+        stage: BenchmarkStage,
     },
     /// All benchmarks complete.
     BenchmarkComplete,
+    /// A cancelled benchmark run has stopped and cleaned up.
+    BenchmarkCancelled,
+    /// Open the rating dialog for a variant, prefilled with its rating.
+    OpenRatingDialog {
+        variant_id: String,
+        variant_name: String,
+        stars: u8,
+        notes: String,
+    },
     /// User rated a variant.
     UserRated {
         variant_id: String,
         stars: u8,
         notes: String,
     },
+    /// Promote the highlighted variant to a pull request.
+    PromoteVariant,
+    /// Push a variant's branch and open a pull request for it.
+    OpenPullRequest {
+        variant_id: String,
+    },
+    /// Ask for a benchmark timeout for the highlighted variant.
+    OpenTimeoutPrompt,
+    /// Toggle the highlighted variant's benchmark run history.
+    ToggleBenchmarkHistory,
+    /// Toggle the full judge explanation for the highlighted variant.
+    ToggleJudgeExplanation,
+    /// Toggle the project's leaderboard of variants from every session.
+    ToggleLeaderboard,
+    /// Open the merge dialog on the highlighted variant's suggested merge.
+    TrySuggestedMerge,
+    /// Sort the benchmark table by the next column.
+    CycleSort,
+    /// Reverse the benchmark table's sort order.
+    ReverseSort,
+    /// Re-read composite score weights from the config file.
+    ReloadScoreWeights,
+    /// Override a variant's benchmark timeout; `None` restores the default.
+    SetBenchmarkTimeout {
+        variant_id: String,
+        seconds: Option<u64>,
+    },
 
     // ── Merging ─────────────────────────────────────────────
     /// Open the merge dialog.
     OpenMergeDialog,
-    /// Close the merge dialog.
-    CloseMergeDialog,
-    /// Start merging two variants.
+    /// Add another source variant to the merge dialog.
+    AddMergeSource,
+    /// Drop the focused source variant from the merge dialog.
+    RemoveMergeSource,
+    /// Toggle blending each source's key components separately.
+    ToggleMergeComponents,
+    /// Open the merge dialog with these sources (and guidance) already picked.
+    PrefillMerge {
+        sources: Vec<MergeSource>,
+        guidance: Option<String>,
+        strategy: Option<MergeStrategy>,
+    },
+    /// Work out which files the picked variants would conflict on.
+    PreviewMerge {
+        variant_ids: Vec<String>,
+    },
+    /// Merge preview computed for the merge dialog, for the variants it
+    /// was asked for.
+    MergePreviewed {
+        variant_ids: Vec<String>,
+        preview: MergePreview,
+    },
+    /// Start merging variants, with any free-form guidance and strategy.
     StartMerge {
-        variant_a_id: String,
-        variant_b_id: String,
-        blend_a: u8,
-        blend_b: u8,
+        sources: Vec<MergeSource>,
+        guidance: Option<String>,
+        strategy: Option<MergeStrategy>,
     },
     /// Merge completed.
     MergeComplete(Box<Variant>),
     /// Merge failed.
-    MergeFailed(String),
+    MergeFailed {
+        variant_id: String,
+        error: String,
+    },
+    /// Open the conflict viewer for the highlighted merge.
+    OpenConflictViewer,
+    /// Load a merge's git conflicts into the conflict viewer.
+    ShowConflicts {
+        variant_id: String,
+    },
+    /// A merge's conflicting files, split into hunks.
+    ConflictsLoaded {
+        variant_id: String,
+        /// The merge's branch.
+        branch: String,
+        /// Technique names of the merge sources.
+        sources: Vec<String>,
+        files: Vec<FileConflict>,
+    },
+    /// Ask the LLM to reconcile the conflict viewer's focused hunk.
+    ReconcileConflictHunk,
+    /// The LLM's version of a conflicting hunk of the merge on `branch`.
+    HunkReconciled {
+        branch: String,
+        path: String,
+        /// The pair of sources the hunk is between.
+        sources: [usize; 2],
+        hunk: usize,
+        lines: Vec<String>,
+    },
+    /// Commit the resolved files among `files` to a merge's branch.
+    ApplyConflictResolutions {
+        variant_id: String,
+        files: Vec<FileConflict>,
+    },
+    /// Resolutions of `paths` were committed to a merge's branch.
+    ConflictsResolved {
+        variant_id: String,
+        paths: Vec<String>,
+    },
 
     // ── Text Input ───────────────────────────────────────────
     /// A character was typed (only sent when in input mode).
@@ -129,6 +431,14 @@ pub enum Action {
     BackspaceInput,
     /// Delete word (Ctrl+Backspace or Ctrl+W).
     DeleteWord,
+    /// Move the text cursor (only sent in input mode).
+    MoveCursor(CursorMove),
+    /// Move the text cursor, selecting the text it passes (Shift+arrow).
+    ExtendSelection(CursorMove),
+    /// Copy the selected text to the clipboard and delete it (Ctrl+X).
+    CutSelection,
+    /// Copy the selected text to the clipboard (Ctrl+Y).
+    CopySelection,
     /// Insert a newline in the current text field (Enter in multi-line fields).
     NewlineInput,
     /// Switch focus between input fields (Tab in input mode).
@@ -140,14 +450,215 @@ pub enum Action {
     /// Bulk paste from bracketed paste mode (terminal sends entire text at once).
     PasteBulk(String),
 
+    // ── Prompt ──────────────────────────────────────────────
+    /// Open the one-line text prompt overlay.
+    OpenPrompt(PromptKind),
+
+    // ── Command line / search ────────────────────────────────
+    /// Run a `:` command line entry.
+    RunCommand(String),
+    /// Jump to the next list item matching the query.
+    Search(String),
+
     // ── Scrolling / Selection ───────────────────────────────
     ScrollUp,
     ScrollDown,
+    /// Jump to the first / last item.
+    ScrollTop,
+    ScrollBottom,
+    /// Move a page up / down.
+    PageUp,
+    PageDown,
+    /// Make the detail panel under a list taller (or shorter, if negative).
+    ResizeDetail(i16),
     SelectNext,
     SelectPrev,
     Confirm,
 }
 
+impl Action {
+    /// Whether this action comes from a key press in editing mode. Modal
+    /// text overlays capture these so the phase underneath doesn't react.
+    pub fn is_editing_input(&self) -> bool {
+        matches!(
+            self,
+            Action::CharInput(_)
+                | Action::BackspaceInput
+                | Action::DeleteWord
+                | Action::NewlineInput
+                | Action::SwitchInputField
+                | Action::SubmitForm
+                | Action::PasteInput
+                | Action::PasteBulk(_)
+                | Action::MoveCursor(_)
+                | Action::ExtendSelection(_)
+                | Action::CutSelection
+                | Action::CopySelection
+                | Action::ScrollUp
+                | Action::ScrollDown
+                | Action::Escape
+        )
+    }
+
+    /// Whether this action is list/dialog navigation from a key press.
+    /// Modal dialogs in normal mode capture these.
+    pub fn is_navigation_input(&self) -> bool {
+        matches!(
+            self,
+            Action::ScrollUp
+                | Action::ScrollDown
+                | Action::ScrollTop
+                | Action::ScrollBottom
+                | Action::PageUp
+                | Action::PageDown
+                | Action::SelectNext
+                | Action::SelectPrev
+                | Action::NextPhase
+                | Action::PrevPhase
+                | Action::Confirm
+                | Action::Escape
+                | Action::AddMergeSource
+                | Action::RemoveMergeSource
+                | Action::ToggleMergeComponents
+                | Action::ReconcileConflictHunk
+        )
+    }
+}
+
+/// A text cursor movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorMove {
+    Left,
+    Right,
+    Up,
+    Down,
+    /// Start / end of the line (Home / End).
+    LineStart,
+    LineEnd,
+}
+
+/// What the text prompt overlay is collecting input for. The prompt turns
+/// the submitted text into the matching follow-up action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptKind {
+    /// Guidance for re-extracting the technique at `index`.
+    ReExtractGuidance {
+        index: usize,
+        technique_name: String,
+    },
+    /// How many of the top `available` techniques to select.
+    SelectTopTechniques { available: usize },
+    /// Extra instructions for regenerating a variant.
+    RegenerateFeedback {
+        variant_id: String,
+        variant_name: String,
+    },
+    /// A new display name (and optional branch suffix) for a variant.
+    RenameVariant {
+        variant_id: String,
+        variant_name: String,
+    },
+    /// A benchmark timeout for a variant.
+    BenchmarkTimeout {
+        variant_id: String,
+        variant_name: String,
+    },
+    /// Text to find in the current list.
+    Search,
+    /// A `:` command: a key binding name, `q` or a phase number.
+    Command,
+}
+
+impl PromptKind {
+    /// Title shown on the prompt border.
+    pub fn title(&self) -> String {
+        match self {
+            PromptKind::ReExtractGuidance { technique_name, .. } => {
+                format!(" {} ", t!("prompt.re_extract", name = technique_name))
+            }
+            PromptKind::SelectTopTechniques { available } => {
+                format!(" {} ", t!("prompt.select_top", available = available))
+            }
+            PromptKind::RegenerateFeedback { variant_name, .. } => {
+                format!(" {} ", t!("prompt.regenerate", name = variant_name))
+            }
+            PromptKind::RenameVariant { variant_name, .. } => {
+                format!(" {} ", t!("prompt.rename", name = variant_name))
+            }
+            PromptKind::BenchmarkTimeout { variant_name, .. } => {
+                format!(" {} ", t!("prompt.benchmark_timeout", name = variant_name))
+            }
+            PromptKind::Search => " / ".to_string(),
+            PromptKind::Command => " : ".to_string(),
+        }
+    }
+
+    /// Placeholder shown while the input is empty.
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            PromptKind::ReExtractGuidance { .. } => t!("prompt.re_extract_placeholder"),
+            PromptKind::SelectTopTechniques { .. } => t!("prompt.select_top_placeholder"),
+            PromptKind::RegenerateFeedback { .. } => t!("prompt.regenerate_placeholder"),
+            PromptKind::RenameVariant { .. } => t!("prompt.rename_placeholder"),
+            PromptKind::BenchmarkTimeout { .. } => t!("prompt.benchmark_timeout_placeholder"),
+            PromptKind::Search => t!("prompt.search_placeholder"),
+            PromptKind::Command => t!("prompt.command_placeholder"),
+        }
+    }
+
+    /// Build the action to dispatch for the submitted text.
+    pub fn submit(&self, text: String) -> Action {
+        match self {
+            PromptKind::ReExtractGuidance { index, .. } => Action::ReExtractTechnique {
+                index: *index,
+                guidance: text,
+            },
+            PromptKind::SelectTopTechniques { .. } => match text.parse::<usize>() {
+                Ok(n) => Action::SelectTopTechniques(n),
+                Err(_) => Action::SetStatus(t!("prompt.not_a_number", text = text)),
+            },
+            PromptKind::RegenerateFeedback { variant_id, .. } => Action::RegenerateVariant {
+                variant_id: variant_id.clone(),
+                feedback: text,
+            },
+            PromptKind::RenameVariant { variant_id, .. } => {
+                let (name, suffix) = match text.split_once('|') {
+                    Some((name, suffix)) => (name.trim(), Some(suffix.trim())),
+                    None => (text.as_str(), None),
+                };
+                Action::RenameVariant {
+                    variant_id: variant_id.clone(),
+                    name: name.to_string(),
+                    branch_suffix: suffix.filter(|s| !s.is_empty()).map(str::to_string),
+                }
+            }
+            PromptKind::BenchmarkTimeout { variant_id, .. } => match parse_timeout(&text) {
+                Some(seconds) => Action::SetBenchmarkTimeout {
+                    variant_id: variant_id.clone(),
+                    seconds: (seconds > 0).then_some(seconds),
+                },
+                None => Action::SetStatus(t!("prompt.not_a_duration", text = text)),
+            },
+            PromptKind::Search => Action::Search(text),
+            PromptKind::Command => Action::RunCommand(text),
+        }
+    }
+}
+
+/// Seconds in a timeout typed as seconds or minutes: `900`, `900s` or `15m`.
+fn parse_timeout(text: &str) -> Option<u64> {
+    let text = text.trim();
+    match text.strip_suffix('m') {
+        Some(minutes) => minutes.trim_end().parse::<u64>().ok()?.checked_mul(60),
+        None => text
+            .strip_suffix('s')
+            .unwrap_or(text)
+            .trim_end()
+            .parse()
+            .ok(),
+    }
+}
+
 /// Whether the app is in a text-input mode where raw keys should
 /// be forwarded to the active component instead of interpreted as
 /// global shortcuts.
@@ -181,17 +692,22 @@ impl Phase {
         ]
     }
 
-    /// Get the display label for the phase tab.
-    pub fn label(&self) -> &'static str {
+    /// Short phase name, without the number.
+    pub fn name(&self) -> &'static str {
         match self {
-            Phase::ProjectIntake => "1.Intake",
-            Phase::ResearchDiscovery => "2.Research",
-            Phase::TechniqueSelection => "3.Techniques",
-            Phase::VariantGeneration => "4.Build",
-            Phase::Benchmarking => "5.Benchmark",
+            Phase::ProjectIntake => t!("phase.intake"),
+            Phase::ResearchDiscovery => t!("phase.research"),
+            Phase::TechniqueSelection => t!("phase.techniques"),
+            Phase::VariantGeneration => t!("phase.build"),
+            Phase::Benchmarking => t!("phase.benchmark"),
         }
     }
 
+    /// Get the display label for the phase tab.
+    pub fn label(&self) -> String {
+        format!("{}.{}", self.index() + 1, self.name())
+    }
+
     /// Get the next phase, if any.
     pub fn next(&self) -> Option<Phase> {
         match self {
@@ -225,3 +741,20 @@ impl Phase {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeouts_parse_as_seconds_or_minutes() {
+        assert_eq!(parse_timeout("900"), Some(900));
+        assert_eq!(parse_timeout("90s"), Some(90));
+        assert_eq!(parse_timeout("15m"), Some(900));
+        assert_eq!(parse_timeout(" 5 m "), Some(300));
+        assert_eq!(parse_timeout("0"), Some(0));
+        assert_eq!(parse_timeout("abc"), None);
+        assert_eq!(parse_timeout("-5"), None);
+        assert_eq!(parse_timeout(""), None);
+    }
+}
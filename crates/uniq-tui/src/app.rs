@@ -5,32 +5,108 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use futures_util::stream::{self, StreamExt};
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Terminal;
+use std::collections::{HashMap, HashSet};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::AbortHandle;
+use tokio::time::MissedTickBehavior;
 use tracing::{error, info, warn};
 
+use uniq_core::benchmark::{
+    elo_ratings, runner, BenchmarkStage, ExecutionMetrics, JudgeEnsemble, JudgeMode, JudgeScores,
+    MetricValue, PairwiseComparison, ScoreWeights, UserRating,
+};
+use uniq_core::config::{
+    ApiKeysConfig, BenchmarkConfig, HooksConfig, PaneSizes, PROJECT_CONFIG_FILE,
+};
+use uniq_core::conflict::{self, FileConflict};
+use uniq_core::export::{export_techniques, export_variant_patches, ExportFormat};
+use uniq_core::git::{self, RepoStatus};
+use uniq_core::hooks::{self, HookEvent};
+use uniq_core::leaderboard::Leaderboard;
+use uniq_core::llm::PipelineStage;
+use uniq_core::merge::{suggest_merges, MergeSource, MergeSpec, MergeStrategy};
+use uniq_core::plugin::PluginRegistry;
+use uniq_core::promote::open_pull_request;
+use uniq_core::prompts::{PromptKind, PromptTemplates};
+use uniq_core::report::{self, ReportFormat};
+use uniq_core::session::Session;
+use uniq_core::variant::{
+    FileOverlap, ImplementationStyle, Variant, VariantId, VariantOrigin, VariantStatus,
+};
+use uniq_core::UniqConfig;
+use uniq_sidecar::correlation::correlated;
+use uniq_sidecar::decode::DecodeError;
+use uniq_sidecar::protocol::{ComponentBlendRequest, MergeSourceRequest, ReconcileHunkRequest};
 use uniq_sidecar::{SidecarClient, SidecarManager};
 
 use crate::action::{Action, InputMode, Phase};
+use crate::channel::{action_channel, ActionSender};
+use crate::clipboard;
 use crate::components::benchmark_dashboard::BenchmarkDashboardComponent;
-use crate::components::help::HelpComponent;
+use crate::components::command_palette::CommandPaletteComponent;
+use crate::components::conflict_viewer::ConflictViewerComponent;
+use crate::components::git_setup::GitSetupComponent;
+use crate::components::help::{HelpComponent, HelpContext};
 use crate::components::merge_dialog::MergeDialogComponent;
+use crate::components::onboarding::OnboardingComponent;
+use crate::components::progress::Spinner;
 use crate::components::project_intake::ProjectIntakeComponent;
+use crate::components::prompt::PromptComponent;
+use crate::components::rating::RatingComponent;
 use crate::components::research_explorer::ResearchExplorerComponent;
+use crate::components::sidebar::{PipelineSummary, SidebarComponent, SIDEBAR_WIDTH};
 use crate::components::status_bar::StatusBarComponent;
+use crate::components::status_log::StatusLogComponent;
 use crate::components::technique_cards::TechniqueCardsComponent;
+use crate::components::timeline::TimelineComponent;
+use crate::components::usage::UsageComponent;
 use crate::components::variant_builder::VariantBuilderComponent;
 use crate::components::Component;
-use crate::event::{self, EventHandler, InputModeFlag};
-use crate::theme::Theme;
+use crate::event::{self, EventHandler, InputModeFlag, PauseFlag};
+use crate::i18n::{self, Locale};
+use crate::keymap::Keymap;
+use crate::metrics::MetricsRecorder;
+use crate::terminal::{Progress, TerminalStatus};
+use crate::theme::{ColorDepth, Palette, Theme};
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+/// Result of the background sidecar startup task.
+type SidecarStartupResult = Result<(SidecarManager, Arc<SidecarClient>), String>;
+
+/// How long a first Esc waits for the second one that confirms a cancel.
+const CANCEL_CONFIRM_WINDOW: Duration = Duration::from_secs(3);
+
+/// How often a cancelled benchmark run notices it was cancelled.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Most papers taken from each research plugin per search.
+const PLUGIN_PAPER_LIMIT: usize = 20;
+
+/// How often the screen is redrawn while something on it is changing.
+/// Spinners advance one frame per tick, so this sets their speed too.
+const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often the token usage view asks the sidecar for new totals.
+const USAGE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Longest wait between usage polls while the sidecar keeps failing them.
+const USAGE_POLL_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Variants the LLM judge scores at once.
+const CONCURRENT_JUDGEMENTS: usize = 4;
 
 /// Main application state.
 pub struct App {
@@ -40,22 +116,77 @@ pub struct App {
     should_quit: bool,
     /// Shared flag to tell the EventHandler which key-mapping to use.
     input_mode_flag: InputModeFlag,
+    /// Set while `$EDITOR` owns the terminal, so key events go to it.
+    pause_flag: PauseFlag,
+    /// Window title and tab progress last sent to the terminal.
+    terminal_status: TerminalStatus,
+    /// Text to open in `$EDITOR` once the current action is handled.
+    pending_editor: Option<String>,
+    /// Key bindings, with the user's `[keys]` overrides applied.
+    keymap: Keymap,
 
     // ── Sidecar ──────────────────────────────────────────────
     /// Path to the Python sidecar directory.
     sidecar_dir: PathBuf,
+    /// API keys the sidecar is started with.
+    api_keys: ApiKeysConfig,
     /// Sidecar process manager (owns the child process).
     sidecar_manager: Option<SidecarManager>,
     /// HTTP client for sidecar API calls (shared across async tasks).
     sidecar_client: Option<Arc<SidecarClient>>,
     /// Receiver for the background sidecar startup result.
-    sidecar_startup_rx: Option<
-        tokio::sync::oneshot::Receiver<Result<(SidecarManager, Arc<SidecarClient>), String>>,
-    >,
+    sidecar_startup_rx: Option<tokio::sync::oneshot::Receiver<SidecarStartupResult>>,
 
     // ── Shared state for async operations ────────────────────
     /// The user's description (saved after project submission).
     user_description: String,
+    /// Saved state for the current project, once one is analyzed.
+    session: Option<Session>,
+    /// Limits how many variants generate at once.
+    generation_slots: Arc<Semaphore>,
+    /// Merges run one at a time, since the sidecar merges in the project's
+    /// working tree.
+    merge_queue: Arc<Semaphore>,
+    /// Running or queued generation tasks, by variant id.
+    generation_tasks: HashMap<String, AbortHandle>,
+    /// Background work Esc can cancel, by phase: project analysis, paper
+    /// search, technique extraction and benchmark runs.
+    phase_tasks: HashMap<Phase, AbortHandle>,
+    /// Phase whose task the first Esc offered to cancel, and when.
+    pending_cancel: Option<(Phase, Instant)>,
+    /// Polls the sidecar for per-variant progress while generations run.
+    progress_poller: Option<AbortHandle>,
+    /// Whether variants get a plan approved before code is written.
+    plan_first: bool,
+    /// Styles each technique is generated in (empty: one plain variant).
+    generation_styles: Vec<ImplementationStyle>,
+    /// The user chose to generate from the last commit despite uncommitted
+    /// changes, so don't ask again until the run ends.
+    allow_uncommitted: bool,
+    /// Whether the sidecar installs new dependencies after generating.
+    install_dependencies: bool,
+    /// Benchmark commands, timeout and runner choice.
+    benchmark_config: BenchmarkConfig,
+    /// Set to stop the benchmark run in progress.
+    benchmark_cancel: Arc<AtomicBool>,
+    /// Extra paper sources and benchmark metrics.
+    plugins: Arc<PluginRegistry>,
+    /// Commands run when variants are generated, benchmarked or merged.
+    hooks: HooksConfig,
+    /// Prompt templates sent in place of the sidecar's own.
+    prompt_templates: PromptTemplates,
+    /// Why the configured templates were ignored at startup, shown once
+    /// the sidecar's startup messages are done.
+    prompts_error: Option<String>,
+    /// Detail panel heights at startup, to tell whether they need saving.
+    pane_sizes: PaneSizes,
+    /// Config profile chosen at startup, kept whenever config is reloaded.
+    config_profile: Option<String>,
+    /// Whether sessions, the leaderboard and the config file are read from
+    /// and written to disk. Off under the test harness.
+    persist: bool,
+    /// Local usage stats, if the user opted in.
+    metrics: Option<MetricsRecorder>,
 
     // Components
     project_intake: ProjectIntakeComponent,
@@ -64,29 +195,111 @@ pub struct App {
     variant_builder: VariantBuilderComponent,
     benchmark_dashboard: BenchmarkDashboardComponent,
     merge_dialog: MergeDialogComponent,
+    conflict_viewer: ConflictViewerComponent,
     status_bar: StatusBarComponent,
+    sidebar: SidebarComponent,
     help: HelpComponent,
+    command_palette: CommandPaletteComponent,
+    status_log: StatusLogComponent,
+    timeline: TimelineComponent,
+    usage: UsageComponent,
+    prompt: PromptComponent,
+    rating: RatingComponent,
+    git_setup: GitSetupComponent,
+    onboarding: OnboardingComponent,
 }
 
 impl App {
-    pub fn new(sidecar_dir: PathBuf) -> Self {
+    pub fn new(sidecar_dir: PathBuf, config: UniqConfig) -> Self {
+        i18n::set_locale(Locale::detect(config.ui.locale.as_deref()));
+        Theme::set_color_depth(ColorDepth::detect());
+        match Palette::from_config(&config.ui) {
+            Ok(palette) => Theme::set_palette(palette),
+            Err(e) => {
+                warn!("Ignoring theme config: {}", e);
+                Theme::set_palette(Palette::DARK);
+            }
+        }
+        let generation_slots = Arc::new(Semaphore::new(config.generation.max_concurrent.max(1)));
+        let plan_first = config.generation.plan_first;
+        let generation_styles = config.generation.styles.clone();
+        let install_dependencies = config.generation.install_dependencies;
+        let pane_sizes = config.ui.panes;
+        let mut research_explorer = ResearchExplorerComponent::new();
+        research_explorer.detail_height = pane_sizes.research_detail;
+        research_explorer.spinner = Spinner::new(config.ui.reduced_motion);
+        let mut technique_cards = TechniqueCardsComponent::new();
+        technique_cards.generation_config = config.generation;
+        technique_cards.detail_height = pane_sizes.technique_detail;
+        technique_cards.spinner = Spinner::new(config.ui.reduced_motion);
+        let mut benchmark_dashboard = BenchmarkDashboardComponent::new();
+        benchmark_dashboard.weights = config.benchmark.weights;
+        benchmark_dashboard.detail_height = pane_sizes.benchmark_detail;
+        let keymap = Keymap::new(config.ui.keymap, &config.keys);
+        let mut project_intake = ProjectIntakeComponent::new();
+        project_intake.keymap = keymap.clone();
+        let (prompt_templates, prompts_error) = match PromptTemplates::load(&config.prompts) {
+            Ok(templates) => (templates, None),
+            Err(e) => {
+                warn!("Ignoring prompt templates: {}", e);
+                let error = t!("app.prompts_ignored", error = e);
+                (PromptTemplates::default(), Some(error))
+            }
+        };
+
         Self {
             current_phase: Phase::ProjectIntake,
             should_quit: false,
             input_mode_flag: event::new_input_mode_flag(),
+            pause_flag: event::new_pause_flag(),
+            terminal_status: TerminalStatus::new(config.ui.terminal_progress),
+            pending_editor: None,
+            keymap: keymap.clone(),
             sidecar_dir,
+            api_keys: config.api_keys,
             sidecar_manager: None,
             sidecar_client: None,
             sidecar_startup_rx: None,
             user_description: String::new(),
-            project_intake: ProjectIntakeComponent::new(),
-            research_explorer: ResearchExplorerComponent::new(),
-            technique_cards: TechniqueCardsComponent::new(),
+            session: None,
+            generation_slots,
+            merge_queue: Arc::new(Semaphore::new(1)),
+            generation_tasks: HashMap::new(),
+            phase_tasks: HashMap::new(),
+            pending_cancel: None,
+            progress_poller: None,
+            plan_first,
+            generation_styles,
+            allow_uncommitted: false,
+            install_dependencies,
+            benchmark_config: config.benchmark,
+            benchmark_cancel: Arc::default(),
+            plugins: Arc::new(PluginRegistry::from_config(&config.plugins)),
+            hooks: config.hooks,
+            prompt_templates,
+            prompts_error,
+            pane_sizes,
+            config_profile: config.active_profile,
+            persist: true,
+            metrics: config.metrics.enabled.then(MetricsRecorder::load),
+            project_intake,
+            research_explorer,
+            technique_cards,
             variant_builder: VariantBuilderComponent::new(),
-            benchmark_dashboard: BenchmarkDashboardComponent::new(),
+            benchmark_dashboard,
             merge_dialog: MergeDialogComponent::new(),
+            conflict_viewer: ConflictViewerComponent::new(),
             status_bar: StatusBarComponent::new(),
-            help: HelpComponent::new(),
+            sidebar: SidebarComponent::new(config.ui.sidebar),
+            command_palette: CommandPaletteComponent::new(keymap.clone()),
+            help: HelpComponent::new(keymap),
+            status_log: StatusLogComponent::new(),
+            timeline: TimelineComponent::new(),
+            usage: UsageComponent::new(),
+            prompt: PromptComponent::new(),
+            rating: RatingComponent::new(),
+            git_setup: GitSetupComponent::new(),
+            onboarding: OnboardingComponent::new(),
         }
     }
 
@@ -101,6 +314,12 @@ impl App {
         self.project_intake.description_input = description;
     }
 
+    /// Walk through first-run setup before Phase 1. The sidecar starts once
+    /// setup knows where it is.
+    pub fn start_onboarding(&mut self) {
+        self.onboarding.open(&self.sidecar_dir);
+    }
+
     /// Run the TUI application.
     pub async fn run(&mut self) -> anyhow::Result<()> {
         // Set up terminal.
@@ -116,38 +335,60 @@ impl App {
         let mut terminal = Terminal::new(backend)?;
 
         // Create the action channel.
-        let (tx, mut rx) = mpsc::unbounded_channel::<Action>();
+        let (tx, mut rx) = action_channel();
 
         // Start the event handler with the shared input mode flag.
         let event_tx = tx.clone();
         let mode_flag = self.input_mode_flag.clone();
-        let event_handler = EventHandler::new(event_tx, Duration::from_millis(100), mode_flag);
+        let mut event_handler = EventHandler::new(
+            event_tx,
+            mode_flag,
+            self.pause_flag.subscribe(),
+            self.keymap.clone(),
+        );
         tokio::spawn(async move {
             event_handler.run().await;
         });
 
         // Start the Python sidecar in the background so the TUI renders immediately.
-        self.start_sidecar_async(tx.clone());
+        if !self.onboarding.visible {
+            self.start_sidecar_async(tx.clone(), false);
+        }
 
         // Set initial input mode (Phase 1 starts in editing mode).
         self.sync_input_mode();
 
-        // Main loop.
+        // Main loop. Actions are handled as they come, but the screen is
+        // only redrawn on the frame clock, so spinners keep turning while
+        // nothing arrives and a burst of actions costs one redraw.
+        let mut frames = tokio::time::interval(FRAME_INTERVAL);
+        frames.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut dirty = true;
         loop {
-            // Render.
-            terminal.draw(|frame| {
-                self.render(frame);
-            })?;
-
-            // Check if the background sidecar startup has completed.
-            self.poll_sidecar_startup();
-
-            // Process actions.
-            if let Some(action) = rx.recv().await {
-                self.handle_action(&action, &tx);
+            tokio::select! {
+                _ = frames.tick() => {
+                    // Check if the background sidecar startup has completed.
+                    self.poll_sidecar_startup();
+                    if self.animating() {
+                        self.handle_action(&Action::Tick, &tx);
+                        dirty = true;
+                    }
+                    if dirty {
+                        self.draw(&mut terminal)?;
+                        dirty = false;
+                    }
+                }
+                action = rx.recv() => {
+                    let Some(action) = action else { break };
+                    self.handle_action(&action, &tx);
+                    dirty = true;
 
-                if self.should_quit {
-                    break;
+                    if self.should_quit {
+                        break;
+                    }
+                    if let Some(text) = self.pending_editor.take() {
+                        self.edit_in_editor(&mut terminal, &text, &tx).await?;
+                    }
                 }
             }
         }
@@ -156,6 +397,8 @@ impl App {
         self.shutdown_sidecar().await;
 
         // Restore terminal.
+        self.terminal_status
+            .clear_progress(terminal.backend_mut())?;
         disable_raw_mode()?;
         execute!(
             terminal.backend_mut(),
@@ -168,31 +411,144 @@ impl App {
         Ok(())
     }
 
+    /// Redraw the screen and update the terminal's title and progress.
+    fn draw(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<()> {
+        if self.sidebar.visible {
+            self.sidebar.summary = self.pipeline_summary();
+        }
+        terminal.draw(|frame| {
+            self.render(frame);
+        })?;
+        let title = self.terminal_title();
+        let progress = self.terminal_progress();
+        self.terminal_status
+            .update(terminal.backend_mut(), &title, progress)
+    }
+
+    /// Suspend the TUI, open `text` in `$VISUAL` or `$EDITOR` (falling back
+    /// to `vi`) and send back what was saved.
+    async fn edit_in_editor(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        text: &str,
+        tx: &ActionSender,
+    ) -> anyhow::Result<()> {
+        // Created exclusively with a random name, so nothing else can have
+        // put a file or symlink there first. Removed when dropped.
+        let file = tempfile::Builder::new()
+            .prefix("uniq-description-")
+            .suffix(".md")
+            .tempfile()
+            .and_then(|file| std::fs::write(file.path(), text).map(|()| file));
+        let file = match file {
+            Ok(file) => file,
+            Err(e) => {
+                let _ = tx.send_now(Action::SetStatus(t!("app.editor_failed", error = e)));
+                return Ok(());
+            }
+        };
+        let path = file.path();
+        let editor = std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .unwrap_or_else(|_| "vi".to_string());
+        // Editors are often configured with flags, e.g. "code --wait".
+        let mut words = editor.split_whitespace();
+        let program = words.next().unwrap_or("vi");
+
+        // Give the event handler a moment to stop reading before handing
+        // over the terminal.
+        self.pause_flag.send_replace(true);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        disable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        )?;
+
+        let status = std::process::Command::new(program)
+            .args(words)
+            .arg(path)
+            .status();
+
+        enable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
+        terminal.clear()?;
+        self.pause_flag.send_replace(false);
+
+        // Read by path, as editors often save by replacing the file.
+        let edited = std::fs::read_to_string(path);
+        drop(file);
+        let status = match (status, edited) {
+            (Err(e), _) => t!("app.editor_run_failed", program = program, error = e),
+            (Ok(status), _) if !status.success() => {
+                t!("app.editor_exited", program = program, status = status)
+            }
+            (Ok(_), Err(e)) => t!("app.editor_read_failed", error = e),
+            (Ok(_), Ok(edited)) => {
+                let edited = edited.trim_end_matches('\n').to_string();
+                let _ = tx.send_now(Action::DescriptionEdited(edited));
+                t!("app.editor_updated").to_string()
+            }
+        };
+        let _ = tx.send_now(Action::SetStatus(status));
+        Ok(())
+    }
+
     /// Spawn sidecar startup in the background. The TUI renders immediately
     /// while the sidecar boots. A SidecarReady/SidecarFailed action is sent
-    /// when it completes.
-    fn start_sidecar_async(&mut self, tx: mpsc::UnboundedSender<Action>) {
+    /// when it completes. With `ping`, the backend is called too, to check
+    /// its key works.
+    fn start_sidecar_async(&mut self, tx: ActionSender, ping: bool) {
         let sidecar_dir = self.sidecar_dir.clone();
+        let api_keys = self.api_keys.clone();
+        let backend = self.technique_cards.generation_config.llm();
         info!(dir = %sidecar_dir.display(), "Starting sidecar (background)");
-        let _ = tx.send(Action::SetStatus("Starting Python sidecar...".to_string()));
+        let _ = tx.send_now(Action::SetStatus(t!("app.sidecar_starting").to_string()));
 
         // Use a oneshot to send the manager + client back to the main task.
         let (result_tx, result_rx) = tokio::sync::oneshot::channel();
 
         tokio::spawn(async move {
-            let mut manager = SidecarManager::new(sidecar_dir);
+            let mut manager = SidecarManager::new(sidecar_dir).with_api_keys(&api_keys);
             match manager.start().await {
                 Ok(()) => {
                     let base_url = manager.base_url();
                     info!(url = %base_url, "Sidecar started successfully");
                     let client = Arc::new(SidecarClient::new(base_url));
-                    let _ = result_tx.send(Ok((manager, client)));
-                    let _ = tx.send(Action::SetStatus("Sidecar ready".to_string()));
+                    let api_key = match client.health().await {
+                        Ok(health) => health.has_key_for(&backend),
+                        Err(_) => Some(false),
+                    };
+                    let provider = backend.provider;
+                    let ping_error = if ping && api_key != Some(false) {
+                        client.ping(backend).await.err().map(|e| e.to_string())
+                    } else {
+                        None
+                    };
+                    let _ = result_tx.send(Ok((manager, client.clone())));
+                    let _ = tx
+                        .send(Action::SidecarReady {
+                            provider,
+                            api_key,
+                            ping_error,
+                        })
+                        .await;
+                    poll_usage(&client, &tx).await;
                 }
                 Err(e) => {
                     error!("Failed to start sidecar: {}", e);
                     let _ = result_tx.send(Err(format!("{}", e)));
-                    let _ = tx.send(Action::SetStatus(format!("Sidecar failed: {}", e)));
+                    let _ = tx.send(Action::SidecarFailed(e.to_string())).await;
+                    let _ = tx
+                        .send(Action::SetStatus(t!("app.sidecar_failed", error = e)))
+                        .await;
                 }
             }
         });
@@ -206,6 +562,8 @@ impl App {
         if let Some(ref mut rx) = self.sidecar_startup_rx {
             match rx.try_recv() {
                 Ok(Ok((manager, client))) => {
+                    client.set_llm(self.technique_cards.generation_config.llm_routes());
+                    client.set_prompts(self.prompt_templates.clone());
                     self.sidecar_manager = Some(manager);
                     self.sidecar_client = Some(client);
                     self.sidecar_startup_rx = None;
@@ -237,6 +595,34 @@ impl App {
         }
     }
 
+    /// Whether a spinner or a large paste is running, so ticks must redraw.
+    fn animating(&self) -> bool {
+        self.research_explorer.searching
+            || self.technique_cards.extracting
+            || self.project_intake.is_pasting()
+    }
+
+    /// The component for the phase on screen.
+    fn phase_component(&self) -> &dyn Component {
+        match self.current_phase {
+            Phase::ProjectIntake => &self.project_intake,
+            Phase::ResearchDiscovery => &self.research_explorer,
+            Phase::TechniqueSelection => &self.technique_cards,
+            Phase::VariantGeneration => &self.variant_builder,
+            Phase::Benchmarking => &self.benchmark_dashboard,
+        }
+    }
+
+    fn phase_component_mut(&mut self) -> &mut dyn Component {
+        match self.current_phase {
+            Phase::ProjectIntake => &mut self.project_intake,
+            Phase::ResearchDiscovery => &mut self.research_explorer,
+            Phase::TechniqueSelection => &mut self.technique_cards,
+            Phase::VariantGeneration => &mut self.variant_builder,
+            Phase::Benchmarking => &mut self.benchmark_dashboard,
+        }
+    }
+
     /// Determine and set the correct input mode based on the current phase
     /// and component state. Called after every action.
     fn sync_input_mode(&self) {
@@ -244,11 +630,131 @@ impl App {
         event::set_input_mode(&self.input_mode_flag, mode);
     }
 
+    /// Esc with nothing left to close: the first press offers to cancel
+    /// the current phase's background work, a second one cancels it.
+    fn cancel_phase_task(&mut self, tx: &ActionSender) {
+        let phase = self.current_phase;
+        let in_progress: Vec<&Variant> = self
+            .variant_builder
+            .variants
+            .iter()
+            .filter(|v| {
+                matches!(
+                    v.status,
+                    VariantStatus::Pending
+                        | VariantStatus::Generating
+                        | VariantStatus::AwaitingApproval
+                )
+            })
+            .collect();
+        let running = match phase {
+            Phase::VariantGeneration => !in_progress.is_empty(),
+            _ => self
+                .phase_tasks
+                .get(&phase)
+                .is_some_and(|task| !task.is_finished()),
+        };
+        if !running {
+            return;
+        }
+        let what = match phase {
+            Phase::ProjectIntake => t!("app.task_analysis"),
+            Phase::ResearchDiscovery => t!("app.task_search"),
+            Phase::TechniqueSelection => t!("app.task_extraction"),
+            Phase::VariantGeneration => t!("app.task_generation"),
+            Phase::Benchmarking => t!("app.task_benchmark"),
+        };
+
+        let confirmed = self
+            .pending_cancel
+            .is_some_and(|(p, at)| p == phase && at.elapsed() < CANCEL_CONFIRM_WINDOW);
+        if !confirmed {
+            self.pending_cancel = Some((phase, Instant::now()));
+            let _ = tx.send_now(Action::SetStatus(t!("app.confirm_cancel", task = what)));
+            return;
+        }
+        self.pending_cancel = None;
+
+        if phase == Phase::VariantGeneration {
+            for variant in in_progress {
+                let _ = tx.send_now(Action::CancelGeneration {
+                    variant_id: variant.id.0.clone(),
+                    branch_name: variant.branch_name.clone(),
+                });
+            }
+            return;
+        }
+        if phase == Phase::Benchmarking {
+            // Aborting can't stop commands running on a blocking thread, so
+            // the run is told to stop, and reports `BenchmarkCancelled` once
+            // it has cleaned up. Until then another run can't start.
+            self.benchmark_cancel.store(true, Ordering::Relaxed);
+            let _ = tx.send_now(Action::SetStatus(t!("app.cancelling_task", task = what)));
+            return;
+        }
+        if let Some(task) = self.phase_tasks.remove(&phase) {
+            task.abort();
+        }
+        match phase {
+            Phase::ProjectIntake => self.project_intake.analyzing = false,
+            Phase::ResearchDiscovery => self.research_explorer.searching = false,
+            Phase::TechniqueSelection => self.technique_cards.extracting = false,
+            Phase::VariantGeneration | Phase::Benchmarking => {}
+        }
+        let _ = tx.send_now(Action::PhaseTaskCancelled(phase));
+        let _ = tx.send_now(Action::SetStatus(t!("app.cancelled_task", task = what)));
+    }
+
+    /// The action a `:` command names: `q`, a phase number (`3` or
+    /// `phase 3`), or any key binding name such as `export_json`.
+    fn command_action(&self, command: &str) -> Action {
+        let command = command.trim().trim_start_matches(':').trim();
+        if matches!(command, "q" | "q!" | "qa" | "quit") {
+            return Action::Quit;
+        }
+        let phase = command.strip_prefix("phase").unwrap_or(command).trim();
+        if let Ok(n) = phase.parse::<usize>() {
+            return match n.checked_sub(1).and_then(|i| Phase::all().get(i)) {
+                Some(phase) => Action::GoToPhase(*phase),
+                None => Action::SetStatus(t!("app.no_phase", phase = n)),
+            };
+        }
+        match self.keymap.get(&command.replace(['-', ' '], "_")) {
+            Some(binding) => binding.action(),
+            None => Action::SetStatus(t!("app.unknown_command", command = command)),
+        }
+    }
+
     /// What input mode should be active right now?
     fn current_input_mode(&self) -> InputMode {
-        // If help or merge dialog is visible, stay in normal mode
+        // First-run setup takes every key until it's done.
+        if self.onboarding.visible {
+            return InputMode::Editing;
+        }
+
+        // The text prompt, rating and git setup dialogs and the command
+        // palette always take typed input while open.
+        if self.prompt.visible
+            || self.rating.visible
+            || self.git_setup.visible
+            || self.command_palette.visible
+        {
+            return InputMode::Editing;
+        }
+
+        if self.merge_dialog.wants_input() {
+            return InputMode::Editing;
+        }
+
+        // If help or a merge overlay is visible, stay in normal mode
         // so Esc and other keys work as expected.
-        if self.help.visible || self.merge_dialog.visible {
+        if self.help.visible
+            || self.status_log.visible
+            || self.timeline.visible
+            || self.usage.visible
+            || self.merge_dialog.visible
+            || self.conflict_viewer.visible
+        {
             return InputMode::Normal;
         }
 
@@ -265,13 +771,89 @@ impl App {
     }
 
     /// Dispatch an action to all relevant components.
-    fn handle_action(&mut self, action: &Action, tx: &mpsc::UnboundedSender<Action>) {
+    // Arms guard their side effects with inner `if`s so the comment
+    // explaining each condition stays next to it.
+    #[allow(clippy::collapsible_match)]
+    fn handle_action(&mut self, action: &Action, tx: &ActionSender) {
+        let variants = &self.variant_builder.variants;
+        self.timeline.observe(action, |id| {
+            variants
+                .iter()
+                .find(|v| v.id.0 == id || v.branch_name == id)
+                .map(|v| v.display_name.clone())
+        });
+        if let Some(ref mut metrics) = self.metrics {
+            metrics.observe(action);
+        }
+
         // Global actions first.
         match action {
             Action::Quit => {
+                self.save_session();
+                self.save_pane_sizes();
                 self.should_quit = true;
                 return;
             }
+            Action::CycleTheme => {
+                let name = Theme::cycle();
+                let _ = tx.send_now(Action::SetStatus(t!("app.theme", name = name)));
+            }
+            Action::OpenInEditor(text) => {
+                self.pending_editor = Some(text.clone());
+                return;
+            }
+            Action::CopyText { text, what } => {
+                let (text, what, tx) = (text.clone(), what.clone(), tx.clone());
+                tokio::task::spawn_blocking(move || {
+                    let status = match clipboard::copy(&text) {
+                        Ok(()) => t!("app.copied", what = what),
+                        Err(e) => t!("app.copy_failed", what = what, error = e),
+                    };
+                    let _ = tx.blocking_send(Action::SetStatus(status));
+                });
+                return;
+            }
+            Action::ToggleHelp => {
+                self.help.context = if self.conflict_viewer.visible {
+                    HelpContext::ConflictViewer
+                } else if self.merge_dialog.visible {
+                    HelpContext::MergeDialog
+                } else {
+                    HelpContext::Phase(self.current_phase)
+                };
+            }
+            Action::CancelPhaseTask => {
+                self.cancel_phase_task(tx);
+            }
+            Action::TestConnection {
+                api_key,
+                sidecar_dir,
+            } => {
+                self.test_connection(api_key, sidecar_dir, tx);
+            }
+            Action::SidecarReady { .. } => {
+                let status = self.prompts_error.take();
+                let status = status.unwrap_or_else(|| t!("app.sidecar_ready").to_string());
+                let _ = tx.send_now(Action::SetStatus(status));
+            }
+            Action::FinishOnboarding => {
+                // Setup is offered until there's a config file, so a
+                // skipped one writes the defaults.
+                if self.persist && !UniqConfig::exists() {
+                    if let Err(e) = UniqConfig::load().and_then(|config| config.save()) {
+                        warn!("Could not save config after setup: {}", e);
+                        let _ =
+                            tx.send_now(Action::SetStatus(t!("app.save_config_failed", error = e)));
+                    }
+                }
+                // Setup was skipped before the sidecar was started.
+                if self.sidecar_client.is_none() && self.sidecar_startup_rx.is_none() {
+                    self.start_sidecar_async(tx.clone(), false);
+                }
+            }
+            Action::RunCommand(command) => {
+                let _ = tx.send_now(self.command_action(command));
+            }
             Action::GoToPhase(phase) => {
                 self.current_phase = *phase;
                 self.status_bar.current_phase = *phase;
@@ -282,7 +864,7 @@ impl App {
                 // but if it arrives anyway (e.g. Right arrow in editing mode), ignore it.
                 if self.current_input_mode() != InputMode::Editing {
                     if let Some(next) = self.current_phase.next() {
-                        if !self.merge_dialog.visible {
+                        if !self.merge_dialog.visible && !self.conflict_viewer.visible {
                             self.current_phase = next;
                             self.status_bar.current_phase = next;
                             self.auto_trigger_phase(next, tx);
@@ -290,10 +872,30 @@ impl App {
                     }
                 }
             }
+            Action::FocusNext | Action::FocusPrev => {
+                let forward = matches!(action, Action::FocusNext);
+                // Dialogs on top, and phases with a single panel, treat Tab
+                // like the arrow keys.
+                let overlay = self.help.visible
+                    || self.status_log.visible
+                    || self.timeline.visible
+                    || self.usage.visible
+                    || self.merge_dialog.visible
+                    || self.conflict_viewer.visible;
+                if overlay || !self.phase_component_mut().cycle_focus(forward) {
+                    let fallback = if forward {
+                        Action::NextPhase
+                    } else {
+                        Action::PrevPhase
+                    };
+                    self.handle_action(&fallback, tx);
+                    return;
+                }
+            }
             Action::PrevPhase => {
                 if self.current_input_mode() != InputMode::Editing {
                     if let Some(prev) = self.current_phase.prev() {
-                        if !self.merge_dialog.visible {
+                        if !self.merge_dialog.visible && !self.conflict_viewer.visible {
                             self.current_phase = prev;
                             self.status_bar.current_phase = prev;
                         }
@@ -324,27 +926,215 @@ impl App {
                     self.spawn_generate_variants(tx.clone());
                 }
             }
+            Action::CommitBaseline => self.commit_baseline(tx),
+            Action::GenerateFromLastCommit => {
+                self.allow_uncommitted = true;
+                let _ = tx.send_now(Action::StartGeneration);
+            }
+            Action::OpenMergeDialog | Action::PrefillMerge { .. } => {
+                let ready: Vec<Variant> = self
+                    .variant_builder
+                    .variants
+                    .iter()
+                    .filter(|v| v.status == VariantStatus::Ready)
+                    .cloned()
+                    .collect();
+                self.merge_dialog.set_variants(&ready);
+            }
+            Action::PreviewMerge { variant_ids } => {
+                self.preview_merge(variant_ids, tx);
+            }
+            Action::StartMerge {
+                sources,
+                guidance,
+                strategy,
+            } => {
+                self.spawn_merge(sources, guidance.clone(), *strategy, tx.clone());
+            }
+            Action::MergeComplete(variant) => {
+                // Updated here rather than by the builder so merges finishing
+                // while another tab is open aren't lost.
+                let variants = &mut self.variant_builder.variants;
+                match variants.iter_mut().find(|v| v.id == variant.id) {
+                    Some(queued) => *queued = *variant.clone(),
+                    None => variants.push(*variant.clone()),
+                }
+                let _ = tx.send_now(Action::SetStatus(if variant.merge_conflicts.is_empty() {
+                    t!("app.merged", name = variant.display_name)
+                } else {
+                    t!(
+                        "app.merged_conflicts",
+                        name = variant.display_name,
+                        count = variant.merge_conflicts.len()
+                    )
+                }));
+            }
+            Action::ShowConflicts { variant_id } => {
+                self.show_conflicts(variant_id, tx);
+            }
+            Action::ReconcileConflictHunk if self.conflict_viewer.visible => {
+                self.spawn_reconcile_hunk(tx.clone());
+            }
+            Action::ApplyConflictResolutions { variant_id, files } => {
+                self.apply_conflict_resolutions(variant_id, files, tx);
+            }
+            Action::ConflictsResolved { variant_id, paths } => {
+                self.conflicts_resolved(variant_id, paths, tx);
+            }
+            Action::MergeFailed { variant_id, error } => {
+                let variants = &mut self.variant_builder.variants;
+                if let Some(queued) = variants.iter_mut().find(|v| v.id.0 == *variant_id) {
+                    queued.status = VariantStatus::Failed(error.clone());
+                }
+                let _ = tx.send_now(Action::SetStatus(t!("app.merge_failed", error = error)));
+            }
             Action::StartBenchmark => {
                 if !self.benchmark_dashboard.benchmarking {
                     self.spawn_run_benchmarks(tx.clone());
                 }
             }
+            Action::BenchmarkUpdated {
+                branch,
+                execution,
+                judge,
+            } => {
+                self.apply_benchmark_results(branch, execution.as_deref(), judge.as_ref());
+            }
+            Action::PluginMetrics { branch, metrics } => {
+                self.apply_plugin_metrics(branch, metrics);
+            }
+            Action::PairwiseJudged { comparisons } => {
+                self.apply_pairwise_results(comparisons);
+            }
+            Action::BenchmarkComplete => {
+                self.record_benchmark_runs();
+                self.suggest_merges(tx);
+            }
+            Action::BenchmarkCancelled => {
+                self.phase_tasks.remove(&Phase::Benchmarking);
+                self.benchmark_dashboard.benchmarking = false;
+                self.benchmark_dashboard.stages.clear();
+                let what = t!("app.task_benchmark");
+                let _ = tx.send_now(Action::SetStatus(t!("app.cancelled_task", task = what)));
+            }
+            Action::ReloadScoreWeights => {
+                self.reload_score_weights(tx);
+            }
+            Action::BenchmarkProgress { branch, stage } => {
+                // Recorded here so progress isn't lost while another tab is open.
+                self.benchmark_dashboard
+                    .stages
+                    .insert(branch.clone(), *stage);
+            }
+            Action::ReExtractTechnique { index, guidance } => {
+                self.spawn_reextract_technique(*index, guidance.clone(), tx.clone());
+            }
+            Action::ExportTechniques(format) => {
+                self.export_techniques(*format, tx);
+            }
+            Action::ExportVariantPatches => {
+                self.export_variant_patches(tx);
+            }
+            Action::ExportReport(format) => {
+                self.export_report(*format, tx);
+            }
+            Action::RegenerateVariant {
+                variant_id,
+                feedback,
+            } => {
+                self.spawn_regenerate_variant(variant_id, feedback.clone(), tx.clone());
+            }
+            Action::RenameVariant {
+                variant_id,
+                name,
+                branch_suffix,
+            } => {
+                self.rename_variant(variant_id, name, branch_suffix.as_deref(), tx);
+            }
+            Action::SetBenchmarkTimeout {
+                variant_id,
+                seconds,
+            } => {
+                self.set_benchmark_timeout(variant_id, *seconds, tx);
+            }
+            Action::ApproveVariantPlan { variant_id } => {
+                self.spawn_approved_variant(variant_id, tx.clone());
+            }
+            Action::UserRated {
+                variant_id,
+                stars,
+                notes,
+            } => {
+                self.rate_variant(variant_id, *stars, notes, tx);
+            }
+            Action::OpenPullRequest { variant_id } => {
+                self.spawn_open_pull_request(variant_id, tx.clone());
+            }
+            Action::CompareVariants { a, b } => {
+                self.compare_variants(a, b, tx);
+            }
+            Action::DiscardVariant { variant_id } => {
+                self.discard_variant(variant_id, tx);
+            }
+            Action::CancelGeneration {
+                variant_id,
+                branch_name,
+            } => {
+                self.spawn_cancel_generation(variant_id.clone(), branch_name.clone(), tx.clone());
+            }
             _ => {}
         }
 
-        // Forward to the active phase component.
-        let result = match self.current_phase {
-            Phase::ProjectIntake => self.project_intake.handle_action(action),
-            Phase::ResearchDiscovery => self.research_explorer.handle_action(action),
-            Phase::TechniqueSelection => self.technique_cards.handle_action(action),
-            Phase::VariantGeneration => self.variant_builder.handle_action(action),
-            Phase::Benchmarking => self.benchmark_dashboard.handle_action(action),
+        // While the prompt is open it owns all typed input; everything else
+        // (ticks, async results) still reaches the components below.
+        let result = if self.onboarding.visible && action.is_editing_input() {
+            self.onboarding.handle_action(action)
+        } else if self.prompt.visible && action.is_editing_input() {
+            self.prompt.handle_action(action)
+        } else if self.command_palette.visible && action.is_editing_input() {
+            self.command_palette.handle_action(action)
+        } else if self.rating.visible && action.is_editing_input() {
+            self.rating.handle_action(action)
+        } else if self.git_setup.visible && action.is_editing_input() {
+            self.git_setup.handle_action(action)
+        } else if self.help.visible && action.is_navigation_input() {
+            // Help sits on top: it scrolls instead of the view underneath.
+            self.help.handle_action(action)
+        } else if self.status_log.visible && action.is_navigation_input() {
+            self.status_log.handle_action(action)
+        } else if self.timeline.visible && action.is_navigation_input() {
+            self.timeline.handle_action(action)
+        } else if self.usage.visible && action.is_navigation_input() {
+            self.usage.handle_action(action)
+        } else if self.merge_dialog.wants_input() && action.is_editing_input() {
+            self.merge_dialog.handle_action(action)
+        } else if self.merge_dialog.visible && action.is_navigation_input() {
+            // Likewise the merge dialog owns navigation keys while open.
+            self.merge_dialog.handle_action(action)
+        } else if self.conflict_viewer.visible && action.is_navigation_input() {
+            self.conflict_viewer.handle_action(action)
+        } else {
+            // Forward to the active phase component.
+            let result = self.phase_component_mut().handle_action(action);
+
+            // Always forward to overlays.
+            self.merge_dialog.handle_action(action);
+            self.conflict_viewer.handle_action(action);
+            self.help.handle_action(action);
+            self.status_log.handle_action(action);
+            self.timeline.handle_action(action);
+            self.usage.handle_action(action);
+            self.sidebar.handle_action(action);
+            self.command_palette.handle_action(action);
+            self.prompt.handle_action(action);
+            self.rating.handle_action(action);
+            self.git_setup.handle_action(action);
+            self.onboarding.handle_action(action);
+            result
         };
-
-        // Always forward to overlays and status bar.
-        self.merge_dialog.handle_action(action);
-        self.help.handle_action(action);
         self.status_bar.handle_action(action);
+        self.status_bar.focus = self.phase_component().focused_panel();
+        self.status_bar.spend = self.usage.total();
 
         // Sync input mode after every action (phase may have changed,
         // or the component state may have changed).
@@ -352,6 +1142,9 @@ impl App {
 
         // Auto-advance to Phase 2 after project analysis completes.
         if matches!(action, Action::ProjectAnalyzed(_)) && self.project_intake.profile.is_some() {
+            self.technique_cards.profile = self.project_intake.profile.clone();
+            self.load_project_config(tx);
+            self.resume_session(tx);
             self.current_phase = Phase::ResearchDiscovery;
             self.status_bar.current_phase = Phase::ResearchDiscovery;
             self.sync_input_mode();
@@ -359,22 +1152,87 @@ impl App {
         }
 
         // Check if all variant generation is complete.
-        if self.variant_builder.generating {
-            if matches!(
+        if self.variant_builder.generating
+            && matches!(
                 action,
                 Action::VariantGenerated(_) | Action::VariantGenerationFailed { .. }
-            ) {
-                let all_done = self.variant_builder.variants.iter().all(|v| {
-                    !matches!(
-                        v.status,
-                        uniq_core::variant::VariantStatus::Pending
-                            | uniq_core::variant::VariantStatus::Generating
-                    )
-                });
-                if all_done {
-                    self.handle_action(&Action::GenerationComplete, tx);
-                }
+            )
+        {
+            // Queued merges don't hold up the end of generation.
+            let mut generated = self
+                .variant_builder
+                .variants
+                .iter()
+                .filter(|v| !v.is_merge());
+            let all_done = generated.all(|v| {
+                !matches!(
+                    v.status,
+                    uniq_core::variant::VariantStatus::Pending
+                        | uniq_core::variant::VariantStatus::Generating
+                        | uniq_core::variant::VariantStatus::AwaitingApproval
+                )
+            });
+            if all_done {
+                // The choice covered this run only; ask again next time.
+                self.allow_uncommitted = false;
+                self.handle_action(&Action::GenerationComplete, tx);
+            }
+        }
+
+        match action {
+            Action::VariantGenerated(variant) | Action::MergeComplete(variant) => {
+                self.generation_tasks.remove(&variant.id.0);
+            }
+            Action::VariantGenerationFailed { variant_id, .. }
+            | Action::VariantPlanned { variant_id, .. }
+            | Action::MergeFailed { variant_id, .. } => {
+                self.generation_tasks.remove(variant_id);
+            }
+            _ => {}
+        }
+        if self.generation_tasks.is_empty() {
+            if let Some(poller) = self.progress_poller.take() {
+                poller.abort();
+            }
+        }
+
+        if let Action::VariantGenerated(variant) = action {
+            if let Some(technique) = variant.technique() {
+                self.technique_cards.generated.insert(technique.key());
+            }
+        }
+
+        match action {
+            Action::VariantGenerated(variant) => {
+                self.run_hook(HookEvent::VariantGenerated { variant }, tx);
+            }
+            Action::MergeComplete(variant) => {
+                self.run_hook(HookEvent::MergeComplete { variant }, tx);
+            }
+            Action::BenchmarkComplete => {
+                let dashboard = &self.benchmark_dashboard;
+                self.run_hook(
+                    HookEvent::BenchmarkComplete {
+                        variants: &dashboard.variants,
+                        baseline: dashboard.baseline.as_ref(),
+                    },
+                    tx,
+                );
             }
+            _ => {}
+        }
+
+        // Persist the session after anything worth resuming from.
+        if matches!(
+            action,
+            Action::ExtractionComplete
+                | Action::TechniqueReExtracted { .. }
+                | Action::ConsolidateDuplicates
+                | Action::DropDuplicates
+                | Action::GenerationComplete
+                | Action::MergeComplete(_)
+        ) {
+            self.save_session();
         }
 
         // Handle chained actions from components.
@@ -383,51 +1241,94 @@ impl App {
         }
     }
 
+    /// Run the hook configured for `event`, if any, in the background.
+    fn run_hook(&self, event: HookEvent, tx: &ActionSender) {
+        let Some(command) = event.command(&self.hooks) else {
+            return;
+        };
+        let Some(project) = self.project_intake.profile.as_ref().map(|p| p.path.clone()) else {
+            return;
+        };
+        let name = event.name();
+        let payload = match event.payload(&project) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!("Could not describe {} for its hook: {}", name, e);
+                return;
+            }
+        };
+        let command = command.to_string();
+        let timeout = Duration::from_secs(self.hooks.timeout_seconds);
+        let tx = tx.clone();
+        tokio::spawn(correlated("hook", async move {
+            let run = tokio::task::spawn_blocking(move || {
+                hooks::run(&command, &project, payload, timeout)
+            })
+            .await;
+            let error = match run {
+                Ok(Ok(())) => {
+                    info!("Hook {} ran", name);
+                    return;
+                }
+                Ok(Err(uniq_core::UniqError::Hook(message))) => message,
+                Ok(Err(e)) => e.to_string(),
+                Err(e) => e.to_string(),
+            };
+            warn!("Hook {} failed: {}", name, error);
+            let _ = tx
+                .send(Action::SetStatus(t!(
+                    "app.hook_failed",
+                    hook = name,
+                    error = error
+                )))
+                .await;
+        }));
+    }
+
     // ── Async task spawners ─────────────────────────────────────
 
     /// Spawn a task to analyze the project via the sidecar.
-    fn spawn_analyze_project(
-        &self,
-        path: String,
-        description: String,
-        tx: mpsc::UnboundedSender<Action>,
-    ) {
+    fn spawn_analyze_project(&mut self, path: String, description: String, tx: ActionSender) {
         let Some(client) = self.sidecar_client.clone() else {
-            let _ = tx.send(Action::ProjectAnalysisFailed(
-                "Sidecar is not running. Cannot analyze project.".to_string(),
+            let _ = tx.send_now(Action::ProjectAnalysisFailed(
+                t!("app.analysis_no_sidecar").to_string(),
             ));
             return;
         };
 
-        let _ = tx.send(Action::SetStatus("Analyzing project...".to_string()));
+        let _ = tx.send_now(Action::SetStatus(t!("app.analyzing").to_string()));
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(correlated("analysis", async move {
             match client
                 .analyze_project(PathBuf::from(&path), description)
                 .await
             {
                 Ok(profile) => {
                     info!("Project analyzed: {} files", profile.file_count);
-                    let _ = tx.send(Action::ProjectAnalyzed(Box::new(profile)));
-                    let _ = tx.send(Action::SetStatus(
-                        "Project analyzed successfully".to_string(),
-                    ));
+                    let _ = tx.send(Action::ProjectAnalyzed(Box::new(profile))).await;
+                    let _ = tx
+                        .send(Action::SetStatus(t!("app.analyzed").to_string()))
+                        .await;
                 }
                 Err(e) => {
                     error!("Project analysis failed: {}", e);
-                    let _ = tx.send(Action::ProjectAnalysisFailed(format!("{}", e)));
-                    let _ = tx.send(Action::SetStatus(format!("Analysis failed: {}", e)));
+                    let _ = tx
+                        .send(Action::ProjectAnalysisFailed(format!("{}", e)))
+                        .await;
+                    let _ = tx
+                        .send(Action::SetStatus(t!("app.analysis_failed", error = e)))
+                        .await;
                 }
             }
-        });
+        }));
+        self.phase_tasks
+            .insert(Phase::ProjectIntake, task.abort_handle());
     }
 
     /// Spawn a task to search for academic papers via the sidecar.
-    fn spawn_search_papers(&self, tx: mpsc::UnboundedSender<Action>) {
+    fn spawn_search_papers(&mut self, tx: ActionSender) {
         let Some(client) = self.sidecar_client.clone() else {
-            let _ = tx.send(Action::ResearchFailed(
-                "Sidecar is not running.".to_string(),
-            ));
+            let _ = tx.send_now(Action::ResearchFailed(t!("app.sidecar_down").to_string()));
             return;
         };
 
@@ -440,9 +1341,10 @@ impl App {
             .map(|p| p.summary.clone())
             .unwrap_or_default();
 
-        let _ = tx.send(Action::SetStatus("Searching for papers...".to_string()));
+        let _ = tx.send_now(Action::SetStatus(t!("app.searching").to_string()));
+        let plugins = self.plugins.clone();
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(correlated("search", async move {
             // Generate diverse search queries from the user's description
             // and the project summary. API search endpoints have query length
             // limits, so we extract short, focused phrases rather than
@@ -474,18 +1376,36 @@ impl App {
             ];
 
             let total_queries = queries.len();
-            let _ = tx.send(Action::SearchQueryStarted {
-                query: queries.join(" | "),
-                query_idx: 0,
-                total_queries: 1,
+            let _ = tx
+                .send(Action::SearchQueryStarted {
+                    query: queries.join(" | "),
+                    query_idx: 0,
+                    total_queries: 1,
+                })
+                .await;
+
+            // Research plugins run alongside the sidecar, which searches S2 +
+            // arXiv concurrently across all queries in a single call.
+            let plugin_queries = queries.clone();
+            let plugin_search = tokio::task::spawn_blocking(move || {
+                plugins.search(&plugin_queries, PLUGIN_PAPER_LIMIT)
             });
+            let sidecar_search = client.search_papers(queries, 60, Some(2020), None, true);
+            let (searched, plugin_search) = tokio::join!(sidecar_search, plugin_search);
 
-            // Send all queries in a single sidecar call so the Python side
-            // can search S2 + arXiv concurrently across all queries.
-            match client
-                .search_papers(queries, 60, Some(2020), None, true)
-                .await
-            {
+            let mut plugin_failures = Vec::new();
+            match plugin_search {
+                Ok((papers, failures)) => {
+                    if !papers.is_empty() {
+                        info!("Research plugins found {} papers", papers.len());
+                        let _ = tx.send(Action::PapersFound(papers)).await;
+                    }
+                    plugin_failures = failures;
+                }
+                Err(e) => error!("Research plugin task panicked: {}", e),
+            }
+
+            match searched {
                 Ok(papers) => {
                     info!(
                         "Search complete: {} queries, {} papers found",
@@ -493,17 +1413,33 @@ impl App {
                         papers.len()
                     );
                     if !papers.is_empty() {
-                        let _ = tx.send(Action::PapersFound(papers));
+                        let _ = tx.send(Action::PapersFound(papers)).await;
                     }
                 }
                 Err(e) => {
                     warn!("Search failed: {}", e);
-                    let _ = tx.send(Action::ResearchFailed(format!("{}", e)));
+                    let _ = tx.send(Action::ResearchFailed(format!("{}", e))).await;
                 }
             }
 
-            let _ = tx.send(Action::ResearchComplete);
-        });
+            let _ = tx.send(Action::ResearchComplete).await;
+            // After the completion status, which would hide them.
+            for failure in plugin_failures {
+                warn!(
+                    "Research plugin {} failed: {}",
+                    failure.plugin, failure.error
+                );
+                let _ = tx
+                    .send(Action::SetStatus(t!(
+                        "app.plugin_failed",
+                        plugin = failure.plugin,
+                        error = failure.error
+                    )))
+                    .await;
+            }
+        }));
+        self.phase_tasks
+            .insert(Phase::ResearchDiscovery, task.abort_handle());
     }
 
     /// Batch-extract techniques from paper abstracts in a single Claude API call.
@@ -513,14 +1449,14 @@ impl App {
     /// - Single API call (~6k tokens) instead of N calls (~15k tokens each)
     /// - Typically completes in 10-15 seconds instead of minutes
     fn spawn_extract_techniques(
-        &self,
+        &mut self,
         papers: Vec<uniq_core::research::PaperMeta>,
-        tx: mpsc::UnboundedSender<Action>,
+        tx: ActionSender,
     ) {
         let Some(client) = self.sidecar_client.clone() else {
-            let _ = tx.send(Action::TechniqueExtractionFailed {
+            let _ = tx.send_now(Action::TechniqueExtractionFailed {
                 paper_id: "all".to_string(),
-                error: "Sidecar is not running.".to_string(),
+                error: t!("app.sidecar_down").to_string(),
             });
             return;
         };
@@ -534,15 +1470,12 @@ impl App {
             .unwrap_or_default();
 
         let paper_count = papers.len();
-        let _ = tx.send(Action::SetStatus(format!(
-            "Analyzing {} paper abstracts with Claude...",
-            paper_count
-        )));
-        let _ = tx.send(Action::ExtractionStarted {
-            paper_title: format!("Batch analysis of {} papers", paper_count),
+        let _ = tx.send_now(Action::SetStatus(t!("app.extracting", count = paper_count)));
+        let _ = tx.send_now(Action::ExtractionStarted {
+            paper_title: t!("app.batch_title", count = paper_count),
         });
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(correlated("extraction", async move {
             // Single batch call with 120s timeout.
             let result = tokio::time::timeout(
                 std::time::Duration::from_secs(120),
@@ -551,6 +1484,7 @@ impl App {
                     project_summary,
                     user_request,
                     8, // max techniques
+                    None,
                 ),
             )
             .await;
@@ -559,63 +1493,1291 @@ impl App {
                 Ok(Ok(techniques)) => {
                     info!("Batch extracted {} techniques", techniques.len());
                     for technique in techniques {
-                        let _ = tx.send(Action::TechniqueExtracted(Box::new(technique)));
+                        let _ = tx
+                            .send(Action::TechniqueExtracted(Box::new(technique)))
+                            .await;
                     }
-                    let _ = tx.send(Action::ExtractionComplete);
+                    let _ = tx.send(Action::ExtractionComplete).await;
                 }
                 Ok(Err(e)) => {
                     warn!("Batch extraction failed: {}", e);
-                    let _ = tx.send(Action::TechniqueExtractionFailed {
-                        paper_id: "batch".to_string(),
-                        error: format!("{}", e),
-                    });
-                    let _ = tx.send(Action::ExtractionComplete);
+                    let _ = tx
+                        .send(Action::TechniqueExtractionFailed {
+                            paper_id: "batch".to_string(),
+                            error: format!("{}", e),
+                        })
+                        .await;
+                    let _ = tx.send(Action::ExtractionComplete).await;
                 }
                 Err(_) => {
                     warn!("Batch extraction timed out");
-                    let _ = tx.send(Action::TechniqueExtractionFailed {
-                        paper_id: "batch".to_string(),
-                        error: "Timed out (120s)".to_string(),
-                    });
-                    let _ = tx.send(Action::ExtractionComplete);
+                    let _ = tx
+                        .send(Action::TechniqueExtractionFailed {
+                            paper_id: "batch".to_string(),
+                            error: t!("app.timed_out", seconds = 120),
+                        })
+                        .await;
+                    let _ = tx.send(Action::ExtractionComplete).await;
                 }
             }
-        });
+        }));
+        self.phase_tasks
+            .insert(Phase::TechniqueSelection, task.abort_handle());
     }
 
-    /// Automatically trigger async operations when entering a new phase.
-    /// This prevents the user from having to manually start each phase.
-    fn auto_trigger_phase(&self, phase: Phase, tx: &mpsc::UnboundedSender<Action>) {
-        match phase {
-            Phase::ResearchDiscovery => {
-                // Auto-start research if project is analyzed and no papers yet.
-                if self.project_intake.profile.is_some()
-                    && self.research_explorer.papers.is_empty()
-                    && !self.research_explorer.searching
-                {
-                    let _ = tx.send(Action::StartResearch);
-                }
+    /// Selected techniques that don't have a pending or ready variant yet.
+    fn techniques_needing_variants(&self) -> Vec<uniq_core::research::TechniqueCard> {
+        let built: HashSet<String> = self
+            .variant_builder
+            .variants
+            .iter()
+            .filter(|v| !matches!(v.status, VariantStatus::Failed(_)))
+            .filter_map(|v| v.technique())
+            .map(|t| t.key())
+            .collect();
+        self.technique_cards
+            .techniques
+            .iter()
+            .filter(|t| t.selected && !built.contains(&t.key()))
+            .cloned()
+            .collect()
+    }
+
+    /// Rename a variant everywhere it is shown, renaming its git branch
+    /// too when a new branch suffix is given.
+    fn rename_variant(
+        &mut self,
+        variant_id: &str,
+        name: &str,
+        branch_suffix: Option<&str>,
+        tx: &ActionSender,
+    ) {
+        let Some(variant) = self
+            .variant_builder
+            .variants
+            .iter()
+            .find(|v| v.id.0 == variant_id)
+        else {
+            return;
+        };
+
+        let mut branch_name = variant.branch_name.clone();
+        if let Some(suffix) = branch_suffix {
+            if variant.status != VariantStatus::Ready {
+                let _ = tx.send_now(Action::SetStatus(t!("app.rename_ready_only").to_string()));
+                return;
             }
-            Phase::TechniqueSelection => {
-                // Auto-start batch extraction if papers exist but no techniques yet.
-                // The batch endpoint uses abstracts (no PDF downloads) and ranks
-                // papers by relevance in a single Claude API call.
-                if !self.research_explorer.papers.is_empty()
-                    && self.technique_cards.techniques.is_empty()
-                    && !self.technique_cards.extracting
-                    && !self.technique_cards.extraction_attempted
-                {
-                    let papers = self.research_explorer.papers.clone();
-                    let _ = tx.send(Action::StartExtraction(papers));
-                }
+            let Some(ref profile) = self.project_intake.profile else {
+                return;
+            };
+            let renamed = variant.branch_with_suffix(suffix);
+            if let Err(e) = uniq_core::git::rename_branch(&profile.path, &branch_name, &renamed) {
+                let _ = tx.send_now(Action::SetStatus(t!("app.rename_failed", error = e)));
+                return;
+            }
+            branch_name = renamed;
+        }
+
+        // The benchmark dashboard keeps its own copy of the variants.
+        let copies = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .chain(self.benchmark_dashboard.variants.iter_mut())
+            .filter(|v| v.id.0 == variant_id);
+        for v in copies {
+            if !name.is_empty() {
+                v.display_name = name.to_string();
+            }
+            v.branch_name = branch_name.clone();
+        }
+        self.save_session();
+        let _ = tx.send_now(Action::SetStatus(t!("app.renamed", branch = branch_name)));
+    }
+
+    /// Work out which files two variants touched and diff the ones both
+    /// modified.
+    fn compare_variants(&self, a: &str, b: &str, tx: &ActionSender) {
+        let find = |id: &str| self.variant_builder.variants.iter().find(|v| v.id.0 == id);
+        let (Some(variant_a), Some(variant_b)) = (find(a), find(b)) else {
+            return;
+        };
+        let Some(ref profile) = self.project_intake.profile else {
+            return;
+        };
+
+        let overlap = FileOverlap::between(variant_a, variant_b);
+        let diff = match uniq_core::git::diff_branches(
+            &profile.path,
+            &variant_a.branch_name,
+            &variant_b.branch_name,
+            &overlap.both,
+        ) {
+            Ok(diff) => diff,
+            Err(e) => {
+                let _ = tx.send_now(Action::SetStatus(t!("app.diff_failed", error = e)));
+                return;
+            }
+        };
+        let _ = tx.send_now(Action::VariantsCompared {
+            a: a.to_string(),
+            b: b.to_string(),
+            overlap,
+            diff,
+        });
+    }
+
+    /// Drop a finished variant, deleting its branch and recording the
+    /// discard in the session.
+    fn discard_variant(&mut self, variant_id: &str, tx: &ActionSender) {
+        let Some(variant) = self
+            .variant_builder
+            .variants
+            .iter()
+            .find(|v| v.id.0 == variant_id)
+            .cloned()
+        else {
+            return;
+        };
+
+        if variant.status == VariantStatus::Ready {
+            let Some(ref profile) = self.project_intake.profile else {
+                return;
+            };
+            if let Err(e) = uniq_core::git::delete_branch(&profile.path, &variant.branch_name) {
+                let _ = tx.send_now(Action::SetStatus(t!("app.delete_failed", error = e)));
+                return;
+            }
+        }
+
+        // The benchmark dashboard keeps its own copy of the variants.
+        let dashboard_row = self.benchmark_dashboard.selected_row();
+        self.variant_builder.variants.retain(|v| v.id != variant.id);
+        self.benchmark_dashboard
+            .variants
+            .retain(|v| v.id != variant.id);
+        self.variant_builder.selected = self
+            .variant_builder
+            .selected
+            .min(self.variant_builder.variants.len().saturating_sub(1));
+        self.benchmark_dashboard.select_row(dashboard_row);
+
+        // The technique is no longer built unless another variant covers it.
+        if let Some(technique) = variant.technique() {
+            let key = technique.key();
+            let still_built = self.variant_builder.variants.iter().any(|v| {
+                v.status == VariantStatus::Ready
+                    && v.technique().map(|t| t.key()) == Some(key.clone())
+            });
+            if !still_built {
+                self.technique_cards.generated.remove(&key);
+            }
+        }
+
+        if let Some(ref mut session) = self.session {
+            session.record_discarded(&variant);
+        }
+        self.save_session();
+        let _ = tx.send_now(Action::SetStatus(t!(
+            "app.discarded",
+            name = variant.display_name,
+            branch = variant.branch_name
+        )));
+    }
+
+    /// Cancel a queued or running generation. The variant is marked as
+    /// failed right away; a queued one is simply dropped, while a running
+    /// one is also stopped on the sidecar.
+    fn spawn_cancel_generation(
+        &mut self,
+        variant_id: String,
+        branch_name: String,
+        tx: ActionSender,
+    ) {
+        if let Some(task) = self.generation_tasks.remove(&variant_id) {
+            task.abort();
+        }
+        let started = self
+            .variant_builder
+            .variants
+            .iter()
+            .any(|v| v.id.0 == variant_id && v.status == VariantStatus::Generating);
+        let _ = tx.send_now(Action::VariantGenerationFailed {
+            variant_id,
+            error: "cancelled".to_string(),
+        });
+        if !started {
+            return;
+        }
+        let Some(client) = self.sidecar_client.clone() else {
+            return;
+        };
+        tokio::spawn(correlated("cancel", async move {
+            match client.cancel_generation(branch_name.clone()).await {
+                Ok(true) => {
+                    let _ = tx
+                        .send(Action::SetStatus(t!(
+                            "app.cancelled_branch",
+                            branch = branch_name
+                        )))
+                        .await;
+                }
+                Ok(false) => {
+                    let _ = tx
+                        .send(Action::SetStatus(t!(
+                            "app.not_running",
+                            branch = branch_name
+                        )))
+                        .await;
+                }
+                Err(e) => {
+                    warn!("Cancel request for {} failed: {}", branch_name, e);
+                }
+            }
+        }));
+    }
+
+    // ── Session persistence ─────────────────────────────────────
+
+    /// Restore techniques and variants saved for the analyzed project.
+    fn resume_session(&mut self, tx: &ActionSender) {
+        let Some(ref profile) = self.project_intake.profile else {
+            return;
+        };
+        if !self.persist {
+            self.session = Some(Session::new(&profile.path));
+            return;
+        }
+        self.benchmark_dashboard.leaderboard = match Leaderboard::load(&profile.path) {
+            Ok(leaderboard) => Some(leaderboard),
+            Err(e) => {
+                warn!("Could not load leaderboard: {}", e);
+                None
+            }
+        };
+        let session = match Session::load(&profile.path) {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                self.session = Some(Session::new(&profile.path));
+                return;
+            }
+            Err(e) => {
+                warn!(
+                    "Could not load session for {}: {}",
+                    profile.path.display(),
+                    e
+                );
+                self.session = Some(Session::new(&profile.path));
+                return;
+            }
+        };
+        self.restore_session(session, tx);
+    }
+
+    /// Pick up where `session` left off.
+    fn restore_session(&mut self, mut session: Session, tx: &ActionSender) {
+        // Sessions from before the base branch was recorded.
+        if session.base_branch.is_none() {
+            session.base_branch = git::head_branch(&session.project_path).ok();
+        }
+        if !session.techniques.is_empty() {
+            self.technique_cards.techniques = session.techniques.clone();
+            self.technique_cards.extraction_attempted = true;
+            self.technique_cards.generated = session
+                .techniques
+                .iter()
+                .filter(|t| session.has_variant_for(t))
+                .map(|t| t.key())
+                .collect();
+            self.variant_builder.variants = session.variants.clone();
+            self.benchmark_dashboard.baseline = session.baseline.clone();
+            self.benchmark_dashboard.comparisons = session.comparisons.clone();
+
+            let _ = tx.send_now(Action::SetStatus(t!(
+                "app.resumed",
+                techniques = session.techniques.len(),
+                selected = self.technique_cards.selected_count(),
+                variants = session.variants.len()
+            )));
+        }
+        self.session = Some(session);
+    }
+
+    /// Ids of the variants in the list, of those discarded earlier and of
+    /// those on the leaderboard from earlier sessions, which new variants
+    /// mustn't reuse.
+    fn taken_ids(&self) -> impl Iterator<Item = &VariantId> {
+        let discarded = self.session.iter().flat_map(|s| &s.discarded);
+        let ranked = self
+            .benchmark_dashboard
+            .leaderboard
+            .iter()
+            .flat_map(|l| &l.entries)
+            .filter_map(|e| e.variant_id.as_ref());
+        self.variant_builder
+            .variants
+            .iter()
+            .map(|v| &v.id)
+            .chain(discarded.map(|d| &d.id))
+            .chain(ranked)
+    }
+
+    /// Save the current techniques and finished variants for this project.
+    fn save_session(&mut self) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        session.profile = self.project_intake.profile.clone();
+        // Papers aren't restored on resume, so the saved ones stay until
+        // a new search finds some.
+        if !self.research_explorer.papers.is_empty() {
+            session.papers = self.research_explorer.papers.clone();
+        }
+        session.techniques = self.technique_cards.techniques.clone();
+        session.baseline = self.benchmark_dashboard.baseline.clone();
+        session.comparisons = self.benchmark_dashboard.comparisons.clone();
+        session.variants = self
+            .variant_builder
+            .variants
+            .iter()
+            .filter(|v| v.status == VariantStatus::Ready)
+            .cloned()
+            .collect();
+        if !self.persist {
+            return;
+        }
+        if let Err(e) = session.save() {
+            warn!("Could not save session: {}", e);
+        }
+    }
+
+    /// Write resized detail panels back to the config file, if any changed.
+    fn save_pane_sizes(&self) {
+        let panes = PaneSizes {
+            research_detail: self.research_explorer.detail_height,
+            technique_detail: self.technique_cards.detail_height,
+            benchmark_detail: self.benchmark_dashboard.detail_height,
+        };
+        if !self.persist || panes == self.pane_sizes {
+            return;
+        }
+        if let Err(e) = UniqConfig::save_pane_sizes(&panes) {
+            warn!("Could not save panel sizes: {}", e);
+        }
+    }
+
+    /// Save what first-run setup collected, then start the sidecar from
+    /// the chosen directory; it reports back with `SidecarReady` or
+    /// `SidecarFailed`.
+    fn test_connection(&mut self, api_key: &str, sidecar_dir: &Path, tx: &ActionSender) {
+        let result = if self.persist {
+            UniqConfig::load().and_then(|mut config| {
+                if !api_key.is_empty() {
+                    config.api_keys.anthropic = api_key.to_string();
+                }
+                config.sidecar.dir = Some(sidecar_dir.to_path_buf());
+                config.save()
+            })
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            let _ = tx.send_now(Action::SidecarFailed(t!(
+                "app.save_config_failed",
+                error = e
+            )));
+            return;
+        }
+        if !api_key.is_empty() {
+            self.api_keys.anthropic = api_key.to_string();
+        }
+        self.sidecar_dir = sidecar_dir.to_path_buf();
+        self.start_sidecar_async(tx.clone(), true);
+    }
+
+    /// Export the selected technique cards (or all, if none are selected).
+    fn export_techniques(&self, format: ExportFormat, tx: &ActionSender) {
+        let techniques = &self.technique_cards.techniques;
+        let selected: Vec<_> = techniques.iter().filter(|t| t.selected).cloned().collect();
+        let (cards, message) = if selected.is_empty() {
+            (techniques.clone(), "app.exported_all")
+        } else {
+            (selected, "app.exported_selected")
+        };
+        if cards.is_empty() {
+            let _ = tx.send_now(Action::SetStatus(t!("app.nothing_to_export").to_string()));
+            return;
+        }
+
+        let status = match export_techniques(&cards, self.project_intake.profile.as_ref(), format) {
+            Ok(path) => {
+                info!("Exported {} techniques to {}", cards.len(), path.display());
+                t!(message, count = cards.len(), path = path.display())
+            }
+            Err(e) => {
+                error!("Technique export failed: {}", e);
+                t!("app.export_failed", error = e)
+            }
+        };
+        let _ = tx.send_now(Action::SetStatus(status));
+    }
+
+    /// Write a report on the session so far.
+    fn export_report(&mut self, format: ReportFormat, tx: &ActionSender) {
+        self.save_session();
+        let Some(ref session) = self.session else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.nothing_to_export").to_string()));
+            return;
+        };
+        let status = match report::export_report(session, format) {
+            Ok(path) => {
+                info!("Wrote session report to {}", path.display());
+                t!("app.report_exported", path = path.display())
+            }
+            Err(e) => {
+                error!("Report export failed: {}", e);
+                t!("app.export_failed", error = e)
+            }
+        };
+        let _ = tx.send_now(Action::SetStatus(status));
+    }
+
+    /// Store a user rating on a variant and recompute its composite score.
+    fn rate_variant(&mut self, variant_id: &str, stars: u8, notes: &str, tx: &ActionSender) {
+        // The benchmark dashboard keeps its own copy of the variants.
+        let weights = self.benchmark_config.weights;
+        let copies = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .chain(self.benchmark_dashboard.variants.iter_mut())
+            .filter(|v| v.id.0 == variant_id);
+        let mut score = None;
+        for v in copies {
+            let results = v.benchmark_results.get_or_insert_with(Default::default);
+            results.user_rating = Some(UserRating {
+                stars,
+                notes: notes.to_string(),
+            });
+            results.compute_composite(&weights);
+            score = results.composite_score;
+        }
+        self.save_session();
+        if let Some(score) = score {
+            let _ = tx.send_now(Action::SetStatus(t!(
+                "app.rated",
+                stars = stars,
+                score = format!("{:.1}", score)
+            )));
+        }
+    }
+
+    /// Config for the analyzed project, or the global config before one
+    /// has been analyzed, with the startup profile.
+    fn load_config(&self) -> uniq_core::Result<UniqConfig> {
+        let config_profile = self.config_profile.as_deref();
+        match self.project_intake.profile {
+            Some(ref profile) => UniqConfig::load_for_project(&profile.path, config_profile),
+            None => UniqConfig::load_profile(config_profile),
+        }
+    }
+
+    /// Apply the analyzed project's generation and benchmark settings,
+    /// from its `.uniq.toml` over the global config.
+    fn load_project_config(&mut self, tx: &ActionSender) {
+        let Some(ref profile) = self.project_intake.profile else {
+            return;
+        };
+        if !self.persist {
+            return;
+        }
+        let has_project_file = profile.path.join(PROJECT_CONFIG_FILE).exists();
+        let config = match self.load_config() {
+            Ok(config) => config,
+            Err(e) => {
+                let _ = tx.send_now(Action::SetStatus(t!(
+                    "app.project_config_ignored",
+                    file = PROJECT_CONFIG_FILE,
+                    error = e
+                )));
+                return;
+            }
+        };
+
+        // Generations already running keep the slots they were started with.
+        self.generation_slots = Arc::new(Semaphore::new(config.generation.max_concurrent.max(1)));
+        self.plan_first = config.generation.plan_first;
+        self.generation_styles = config.generation.styles.clone();
+        self.install_dependencies = config.generation.install_dependencies;
+        self.prompts_error = None;
+        match PromptTemplates::load(&config.prompts) {
+            Ok(templates) => self.prompt_templates = templates,
+            Err(e) => {
+                warn!("Ignoring prompt templates: {}", e);
+                self.prompt_templates = PromptTemplates::default();
+                let _ = tx.send_now(Action::SetStatus(t!("app.prompts_ignored", error = e)));
+            }
+        }
+        if let Some(client) = &self.sidecar_client {
+            client.set_llm(config.generation.llm_routes());
+            client.set_prompts(self.prompt_templates.clone());
+        }
+        self.technique_cards.generation_config = config.generation;
+        self.benchmark_dashboard.weights = config.benchmark.weights;
+        self.benchmark_config = config.benchmark;
+        if has_project_file {
+            info!(path = %profile.path.display(), "Loaded project config");
+            let _ = tx.send_now(Action::SetStatus(t!(
+                "app.project_config",
+                file = PROJECT_CONFIG_FILE
+            )));
+        }
+    }
+
+    /// Re-read score weights from the config file and rescore every
+    /// benchmarked variant with them.
+    fn reload_score_weights(&mut self, tx: &ActionSender) {
+        let config = match self.load_config() {
+            Ok(config) => config,
+            Err(e) => {
+                let _ = tx.send_now(Action::SetStatus(t!("app.reload_failed", error = e)));
+                return;
+            }
+        };
+        let weights = config.benchmark.weights;
+        self.apply_score_weights(weights);
+        let _ = tx.send_now(Action::SetStatus(t!(
+            "app.score_weights",
+            execution = format!("{:.2}", weights.execution),
+            judge = format!("{:.2}", weights.judge),
+            user = format!("{:.2}", weights.user)
+        )));
+    }
+
+    /// Rescore every benchmarked variant, and its earlier runs, with
+    /// `weights`, and rework the merge suggestions that follow from the scores.
+    fn apply_score_weights(&mut self, weights: ScoreWeights) {
+        self.benchmark_config.weights = weights;
+        self.benchmark_dashboard.weights = weights;
+        let copies = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .chain(self.benchmark_dashboard.variants.iter_mut());
+        for v in copies {
+            let history = v.benchmark_history.iter_mut().map(|run| &mut run.results);
+            for results in v.benchmark_results.iter_mut().chain(history) {
+                results.compute_composite(&weights);
+            }
+        }
+        // Mid-run, suggestions wait for the run to finish as usual.
+        if !self.benchmark_dashboard.benchmarking {
+            self.benchmark_dashboard.suggestions =
+                suggest_merges(&self.benchmark_dashboard.variants);
+        }
+        self.save_session();
+    }
+
+    /// Add this run's results to the history of every variant that
+    /// finished benchmarking.
+    fn record_benchmark_runs(&mut self) {
+        let stages = &self.benchmark_dashboard.stages;
+        let copies = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .chain(self.benchmark_dashboard.variants.iter_mut())
+            .filter(|v| stages.get(&v.branch_name) == Some(&BenchmarkStage::Done));
+        for v in copies {
+            v.record_benchmark_run();
+        }
+        if let Some(ref mut leaderboard) = self.benchmark_dashboard.leaderboard {
+            for v in &self.benchmark_dashboard.variants {
+                if stages.get(&v.branch_name) == Some(&BenchmarkStage::Done) {
+                    leaderboard.record(v);
+                }
+            }
+            if let Err(e) = leaderboard.save() {
+                warn!("Could not save leaderboard: {}", e);
+            }
+        }
+        self.save_session();
+    }
+
+    /// Work out which benchmarked variants are worth merging.
+    fn suggest_merges(&mut self, tx: &ActionSender) {
+        let suggestions = suggest_merges(&self.benchmark_dashboard.variants);
+        if !suggestions.is_empty() {
+            let _ = tx.send_now(Action::SetStatus(if suggestions.len() == 1 {
+                t!("app.suggested_merge").to_string()
+            } else {
+                t!("app.suggested_merges", count = suggestions.len())
+            }));
+        }
+        self.benchmark_dashboard.suggestions = suggestions;
+    }
+
+    fn set_benchmark_timeout(&mut self, variant_id: &str, seconds: Option<u64>, tx: &ActionSender) {
+        let copies = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .chain(self.benchmark_dashboard.variants.iter_mut())
+            .filter(|v| v.id.0 == variant_id);
+        for v in copies {
+            v.benchmark_timeout = seconds;
+        }
+        self.save_session();
+        let status = match seconds {
+            Some(seconds) => t!("app.timeout_set", seconds = seconds),
+            None => t!(
+                "app.timeout_reset",
+                seconds = self.benchmark_config.timeout_seconds
+            ),
+        };
+        let _ = tx.send_now(Action::SetStatus(status));
+    }
+
+    /// Store benchmark results for the variant on `branch` and rescore it.
+    fn apply_benchmark_results(
+        &mut self,
+        branch: &str,
+        execution: Option<&ExecutionMetrics>,
+        judge: Option<&JudgeScores>,
+    ) {
+        if self.benchmark_dashboard.baseline_branch.as_deref() == Some(branch) {
+            self.benchmark_dashboard.baseline = execution.cloned();
+            self.save_session();
+            return;
+        }
+        let weights = self.benchmark_config.weights;
+        let copies = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .chain(self.benchmark_dashboard.variants.iter_mut())
+            .filter(|v| v.branch_name == branch);
+        for v in copies {
+            let results = v.benchmark_results.get_or_insert_with(Default::default);
+            if let Some(execution) = execution {
+                results.execution = Some(execution.clone());
+            }
+            if let Some(judge) = judge {
+                results.judge = Some(judge.clone());
+            }
+            results.compute_composite(&weights);
+        }
+        self.save_session();
+    }
+
+    /// Add metric plugins' results to `branch`'s execution metrics.
+    fn apply_plugin_metrics(&mut self, branch: &str, metrics: &[MetricValue]) {
+        let add = |execution: &mut ExecutionMetrics| {
+            for metric in metrics {
+                execution
+                    .custom_metrics
+                    .insert(metric.name.clone(), metric.clone());
+            }
+        };
+        if self.benchmark_dashboard.baseline_branch.as_deref() == Some(branch) {
+            if let Some(baseline) = self.benchmark_dashboard.baseline.as_mut() {
+                add(baseline);
+            }
+            self.save_session();
+            return;
+        }
+        let weights = self.benchmark_config.weights;
+        let copies = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .chain(self.benchmark_dashboard.variants.iter_mut())
+            .filter(|v| v.branch_name == branch);
+        for v in copies {
+            if let Some(results) = v.benchmark_results.as_mut() {
+                if let Some(execution) = results.execution.as_mut() {
+                    add(execution);
+                }
+                results.compute_composite(&weights);
+            }
+        }
+        self.save_session();
+    }
+
+    /// Rank variants by Elo rating from a pairwise judge tournament.
+    fn apply_pairwise_results(&mut self, comparisons: &[PairwiseComparison]) {
+        let branches: Vec<String> = self
+            .benchmark_dashboard
+            .variants
+            .iter()
+            .map(|v| v.branch_name.clone())
+            .collect();
+        let ratings = elo_ratings(&branches, comparisons);
+        let weights = self.benchmark_config.weights;
+        let copies = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .chain(self.benchmark_dashboard.variants.iter_mut());
+        for v in copies {
+            if let Some(&elo) = ratings.get(&v.branch_name) {
+                let results = v.benchmark_results.get_or_insert_with(Default::default);
+                results.elo = Some(elo);
+                results.compute_composite(&weights);
+            }
+        }
+        self.benchmark_dashboard.comparisons = comparisons.to_vec();
+        self.save_session();
+    }
+
+    /// Predict conflicts between the merge dialog's picked variants, off
+    /// the UI thread.
+    fn preview_merge(&self, variant_ids: &[String], tx: &ActionSender) {
+        let Some(path) = self.project_intake.profile.as_ref().map(|p| p.path.clone()) else {
+            return;
+        };
+        let branches: Vec<String> = variant_ids
+            .iter()
+            .filter_map(|id| self.variant_builder.variants.iter().find(|v| v.id.0 == *id))
+            .map(|v| v.branch_name.clone())
+            .collect();
+        let variant_ids = variant_ids.to_vec();
+        let tx = tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let action = match git::preview_merge(&path, &branches) {
+                Ok(preview) => Action::MergePreviewed {
+                    variant_ids,
+                    preview,
+                },
+                Err(e) => Action::SetStatus(t!("app.preview_failed", error = e)),
+            };
+            let _ = tx.blocking_send(action);
+        });
+    }
+
+    /// Branches and technique names of a merge's sources, in spec order.
+    fn merge_sources(&self, merge: &Variant) -> Option<(Vec<String>, Vec<String>)> {
+        let VariantOrigin::Merge { ref spec } = merge.origin else {
+            return None;
+        };
+        let variants = &self.variant_builder.variants;
+        spec.sources
+            .iter()
+            .map(|source| {
+                let v = variants.iter().find(|v| v.id == source.variant)?;
+                let name = v
+                    .technique()
+                    .map(|t| t.name.clone())
+                    .unwrap_or_else(|| v.display_name.clone());
+                Some((v.branch_name.clone(), name))
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(|pairs| pairs.into_iter().unzip())
+    }
+
+    /// Split the files a merge's sources conflict on into hunks for the
+    /// conflict viewer, off the UI thread.
+    fn show_conflicts(&self, variant_id: &str, tx: &ActionSender) {
+        let Some(path) = self.project_intake.profile.as_ref().map(|p| p.path.clone()) else {
+            return;
+        };
+        let Some(merge) = self
+            .variant_builder
+            .variants
+            .iter()
+            .find(|v| v.id.0 == variant_id)
+        else {
+            return;
+        };
+        let Some((branches, sources)) = self.merge_sources(merge) else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.source_deleted").to_string()));
+            return;
+        };
+        let variant_id = variant_id.to_string();
+        let branch = merge.branch_name.clone();
+        let conflicts = merge.merge_conflicts.clone();
+        let tx = tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let action = match conflict::load_conflicts(&path, &branch, &branches, &conflicts) {
+                Ok(files) if files.is_empty() => {
+                    Action::SetStatus(t!("app.no_longer_conflict").to_string())
+                }
+                Ok(files) => Action::ConflictsLoaded {
+                    variant_id,
+                    branch,
+                    sources,
+                    files,
+                },
+                Err(e) => Action::SetStatus(t!("app.conflicts_failed", error = e)),
+            };
+            let _ = tx.blocking_send(action);
+        });
+    }
+
+    /// Ask the sidecar to reconcile the conflict viewer's focused hunk,
+    /// reporting `HunkReconciled`.
+    fn spawn_reconcile_hunk(&self, tx: ActionSender) {
+        let Some(client) = self.sidecar_client.clone() else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.sidecar_down").to_string()));
+            return;
+        };
+        let Some(profile) = self.project_intake.profile.clone() else {
+            return;
+        };
+        let Some((file, index)) = self.conflict_viewer.current() else {
+            return;
+        };
+        let Some(hunk) = file.hunks().nth(index) else {
+            return;
+        };
+        let guidance = self
+            .variant_builder
+            .variants
+            .iter()
+            .find(|v| v.id.0 == self.conflict_viewer.variant_id)
+            .and_then(|v| match v.origin {
+                VariantOrigin::Merge { ref spec } => spec.guidance.clone(),
+                VariantOrigin::Research { .. } => None,
+            });
+        let names = &self.conflict_viewer.source_names;
+        let name = |i: usize| names.get(i).cloned().unwrap_or_default();
+        let (before, after) = file.context(index, 20);
+        let path = file.path.clone();
+        let pair = file.sources;
+        let branch = self.conflict_viewer.branch.clone();
+        let request = ReconcileHunkRequest {
+            path: path.clone(),
+            a: hunk.a.clone(),
+            b: hunk.b.clone(),
+            base: hunk.base.clone(),
+            before,
+            after,
+            sources: vec![name(file.sources[0]), name(file.sources[1])],
+            guidance,
+            project: profile,
+            llm: client.llm(PipelineStage::Merge),
+        };
+        let _ = tx.send_now(Action::SetStatus(t!(
+            "app.reconciling",
+            hunk = index + 1,
+            path = path
+        )));
+
+        tokio::spawn(correlated("reconcile", async move {
+            match client.reconcile_hunk(request).await {
+                Ok(lines) => {
+                    let _ = tx
+                        .send(Action::HunkReconciled {
+                            branch,
+                            path,
+                            sources: pair,
+                            hunk: index,
+                            lines,
+                        })
+                        .await;
+                }
+                Err(e) => {
+                    let _ = tx
+                        .send(Action::SetStatus(t!("app.reconcile_failed", error = e)))
+                        .await;
+                }
+            }
+        }));
+    }
+
+    /// Commit the resolved files among `files` to a merge's branch, off the
+    /// UI thread, reporting `ConflictsResolved` for those committed.
+    fn apply_conflict_resolutions(
+        &mut self,
+        variant_id: &str,
+        files: &[FileConflict],
+        tx: &ActionSender,
+    ) {
+        let Some(path) = self.project_intake.profile.as_ref().map(|p| p.path.clone()) else {
+            return;
+        };
+        let Some(merge) = self
+            .variant_builder
+            .variants
+            .iter()
+            .find(|v| v.id.0 == variant_id)
+        else {
+            return;
+        };
+        let Some((branches, _)) = self.merge_sources(merge) else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.source_deleted").to_string()));
+            return;
+        };
+        let variant_id = variant_id.to_string();
+        let branch = merge.branch_name.clone();
+        let files = files.to_vec();
+        let tx = tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let resolved = match conflict::resolve(&path, &branch, &branches, &files) {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    let status = t!("app.conflicts_failed", error = e);
+                    let _ = tx.blocking_send(Action::SetStatus(status));
+                    return;
+                }
+            };
+            let mut paths = Vec::new();
+            let mut failure = None;
+            for (file, contents) in resolved {
+                let message = format!("uniq: Resolve merge conflicts in {}", file);
+                match uniq_core::git::commit_file(&path, &branch, &file, &contents, &message) {
+                    Ok(()) => paths.push(file),
+                    Err(e) => {
+                        failure = Some(t!("app.commit_failed", path = file, error = e));
+                        break;
+                    }
+                }
+            }
+            if !paths.is_empty() {
+                let _ = tx.blocking_send(Action::ConflictsResolved { variant_id, paths });
+            }
+            if let Some(status) = failure {
+                let _ = tx.blocking_send(Action::SetStatus(status));
+            }
+        });
+    }
+
+    /// Clear committed resolutions from a merge's outstanding conflicts.
+    fn conflicts_resolved(&mut self, variant_id: &str, paths: &[String], tx: &ActionSender) {
+        let Some(merge) = self
+            .variant_builder
+            .variants
+            .iter_mut()
+            .find(|v| v.id.0 == variant_id)
+        else {
+            return;
+        };
+        merge.merge_conflicts.retain(|p| !paths.contains(p));
+        let remaining = merge.merge_conflicts.len();
+        self.save_session();
+        let _ = tx.send_now(Action::SetStatus(if remaining == 0 {
+            t!("app.resolved", count = paths.len())
+        } else {
+            t!(
+                "app.resolved_remaining",
+                count = paths.len(),
+                remaining = remaining
+            )
+        }));
+    }
+
+    /// Queue a merge of `sources` into a
+    /// new variant, which shows in the builder with live progress and can be
+    /// cancelled like a generation. Merges run on the sidecar one at a time,
+    /// reporting `MergeComplete` or `MergeFailed`.
+    fn spawn_merge(
+        &mut self,
+        sources: &[MergeSource],
+        guidance: Option<String>,
+        strategy: Option<MergeStrategy>,
+        tx: ActionSender,
+    ) {
+        let Some(client) = self.sidecar_client.clone() else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.merge_no_sidecar").to_string()));
+            return;
+        };
+        let Some(profile) = self.project_intake.profile.clone() else {
+            return;
+        };
+        let variants = &self.variant_builder.variants;
+        let mut request = Vec::new();
+        let mut names = Vec::new();
+        for source in sources {
+            let Some(variant) = variants.iter().find(|v| v.id == source.variant) else {
+                let _ = tx.send_now(Action::SetStatus(t!(
+                    "app.unknown_variant",
+                    id = source.variant
+                )));
+                return;
+            };
+            let technique = match variant.origin {
+                VariantOrigin::Research { ref technique } => {
+                    serde_json::to_value(technique).unwrap_or_default()
+                }
+                VariantOrigin::Merge { ref spec } => serde_json::Value::String(spec.summary()),
+            };
+            request.push(MergeSourceRequest {
+                branch: variant.branch_name.clone(),
+                technique,
+                blend: source.blend.as_percent(),
+                components: source
+                    .components
+                    .iter()
+                    .map(|c| ComponentBlendRequest {
+                        name: c.name.clone(),
+                        blend: c.blend.as_percent(),
+                    })
+                    .collect(),
+            });
+            names.push(
+                variant
+                    .technique()
+                    .map(|t| t.name.clone())
+                    .unwrap_or_else(|| variant.display_name.clone()),
+            );
+        }
+
+        let index = uniq_core::variant::next_index(self.taken_ids(), "merge");
+        let spec = MergeSpec::from_sources(sources.to_vec())
+            .with_guidance(guidance)
+            .with_strategy(strategy);
+        let guidance = spec.guidance.clone();
+        let strategy = strategy.map(|s| s.directive().to_string());
+        let mut variant = Variant::from_merge(index, spec, names.join(" + "));
+        let queued = self
+            .variant_builder
+            .variants
+            .iter()
+            .filter(|v| v.is_merge() && v.status == VariantStatus::Pending)
+            .count();
+        let _ = tx.send_now(Action::SetStatus(if queued == 0 {
+            t!("app.merging", name = variant.display_name)
+        } else {
+            t!("app.queued", name = variant.display_name, count = queued)
+        }));
+        self.variant_builder.variants.push(variant.clone());
+
+        let merge_queue = self.merge_queue.clone();
+        let slots = self.generation_slots.clone();
+        let task_id = variant.id.0.clone();
+        let client_for_poller = client.clone();
+        let tx_for_poller = tx.clone();
+        let task = tokio::spawn(correlated("merge", async move {
+            // Wait for earlier merges, then for a generation slot.
+            let Ok(_turn) = merge_queue.acquire_owned().await else {
+                return;
+            };
+            let Ok(_permit) = slots.acquire_owned().await else {
+                return;
+            };
+            let variant_id = variant.id.0.clone();
+            let _ = tx
+                .send(Action::VariantGenerationStarted {
+                    variant_id: variant_id.clone(),
+                })
+                .await;
+
+            let branch_name = variant.branch_name.clone();
+            match client
+                .merge_variants(request, guidance, strategy, profile, branch_name)
+                .await
+            {
+                Ok(result) if result.success => {
+                    info!("Merged {}", variant.branch_name);
+                    variant.status = VariantStatus::Ready;
+                    variant.modified_files = result.modified_files;
+                    variant.new_dependencies = result.new_dependencies;
+                    variant.transcript = result.transcript;
+                    variant.merge_conflicts = result.conflicts;
+                    let _ = tx.send(Action::MergeComplete(Box::new(variant))).await;
+                }
+                Ok(result) => {
+                    let error = result
+                        .error
+                        .unwrap_or_else(|| t!("app.unknown_error").to_string());
+                    let _ = tx.send(Action::MergeFailed { variant_id, error }).await;
+                }
+                Err(e) => {
+                    error!("Merge into {} failed: {}", variant.branch_name, e);
+                    let _ = tx
+                        .send(Action::MergeFailed {
+                            variant_id,
+                            error: format!("{}", e),
+                        })
+                        .await;
+                }
+            }
+        }));
+        self.generation_tasks.insert(task_id, task.abort_handle());
+        self.ensure_progress_poller(client_for_poller, tx_for_poller);
+    }
+
+    /// Push a variant's branch and open a pull request for it with `gh`.
+    fn spawn_open_pull_request(&self, variant_id: &str, tx: ActionSender) {
+        let Some(profile) = self.project_intake.profile.clone() else {
+            return;
+        };
+        let Some(variant) = self
+            .benchmark_dashboard
+            .variants
+            .iter()
+            .find(|v| v.id.0 == variant_id)
+            .cloned()
+        else {
+            return;
+        };
+
+        let _ = tx.send_now(Action::SetStatus(t!(
+            "app.pushing",
+            branch = variant.branch_name
+        )));
+        tokio::task::spawn_blocking(move || {
+            let status = match open_pull_request(&profile.path, &variant, Some(&profile)) {
+                Ok(url) => {
+                    info!("Opened pull request for {}: {}", variant.branch_name, url);
+                    t!("app.pr_opened", url = url)
+                }
+                Err(e) => {
+                    error!("Pull request for {} failed: {}", variant.branch_name, e);
+                    t!("app.pr_failed", error = e)
+                }
+            };
+            let _ = tx.blocking_send(Action::SetStatus(status));
+        });
+    }
+
+    /// Export every ready variant's commits as a patch series.
+    fn export_variant_patches(&self, tx: &ActionSender) {
+        let Some(ref profile) = self.project_intake.profile else {
+            return;
+        };
+        let ready: Vec<_> = self
+            .variant_builder
+            .variants
+            .iter()
+            .filter(|v| v.status == VariantStatus::Ready)
+            .cloned()
+            .collect();
+        if ready.is_empty() {
+            let _ = tx.send_now(Action::SetStatus(t!("app.no_patches").to_string()));
+            return;
+        }
+
+        let base = self
+            .session
+            .as_ref()
+            .and_then(|s| s.base_branch.clone())
+            .unwrap_or_else(|| "HEAD".to_string());
+        let status = match export_variant_patches(&profile.path, &base, &ready) {
+            Ok(dir) => {
+                info!(
+                    "Exported {} variant patches to {}",
+                    ready.len(),
+                    dir.display()
+                );
+                t!(
+                    "app.patches_exported",
+                    count = ready.len(),
+                    path = dir.display()
+                )
+            }
+            Err(e) => {
+                error!("Patch export failed: {}", e);
+                t!("app.export_failed", error = e)
+            }
+        };
+        let _ = tx.send_now(Action::SetStatus(status));
+    }
+
+    /// Re-extract a single technique from its source paper's abstract,
+    /// steering the model with a user-provided hint.
+    fn spawn_reextract_technique(&self, index: usize, guidance: String, tx: ActionSender) {
+        let Some(client) = self.sidecar_client.clone() else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.sidecar_down").to_string()));
+            return;
+        };
+
+        let Some(card) = self.technique_cards.techniques.get(index) else {
+            return;
+        };
+        let Some(paper) = self
+            .research_explorer
+            .papers
+            .iter()
+            .find(|p| p.id == card.paper_id)
+            .cloned()
+        else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.paper_missing", name = card.name)));
+            return;
+        };
+
+        let user_request = self.user_description.clone();
+        let project_summary = self
+            .project_intake
+            .profile
+            .as_ref()
+            .map(|p| p.summary.clone())
+            .unwrap_or_default();
+
+        let _ = tx.send_now(Action::SetStatus(t!("app.reextracting", name = card.name)));
+
+        let name = card.name.clone();
+        tokio::spawn(correlated("reextraction", async move {
+            let paper_id = paper.id.clone();
+            match client
+                .batch_extract_techniques(
+                    vec![paper],
+                    project_summary,
+                    user_request,
+                    1,
+                    Some(guidance),
+                )
+                .await
+            {
+                Ok(techniques) => match techniques.into_iter().next() {
+                    Some(card) => {
+                        let _ = tx
+                            .send(Action::TechniqueReExtracted {
+                                paper_id,
+                                name,
+                                card: Box::new(card),
+                            })
+                            .await;
+                    }
+                    None => {
+                        let _ = tx
+                            .send(Action::SetStatus(t!("app.reextract_empty").to_string()))
+                            .await;
+                    }
+                },
+                Err(e) => {
+                    warn!("Re-extraction failed for {}: {}", paper_id, e);
+                    let _ = tx
+                        .send(Action::SetStatus(t!("app.reextract_failed", error = e)))
+                        .await;
+                }
+            }
+        }));
+    }
+
+    /// Automatically trigger async operations when entering a new phase.
+    /// This prevents the user from having to manually start each phase.
+    #[allow(clippy::collapsible_match)]
+    fn auto_trigger_phase(&self, phase: Phase, tx: &ActionSender) {
+        match phase {
+            Phase::ResearchDiscovery => {
+                // Auto-start research if project is analyzed and no papers yet.
+                if self.project_intake.profile.is_some()
+                    && self.research_explorer.papers.is_empty()
+                    && !self.research_explorer.searching
+                {
+                    let _ = tx.send_now(Action::StartResearch);
+                }
+            }
+            Phase::TechniqueSelection => {
+                // Auto-start batch extraction if papers exist but no techniques yet.
+                // The batch endpoint uses abstracts (no PDF downloads) and ranks
+                // papers by relevance in a single Claude API call.
+                if !self.research_explorer.papers.is_empty()
+                    && self.technique_cards.techniques.is_empty()
+                    && !self.technique_cards.extracting
+                    && !self.technique_cards.extraction_attempted
+                {
+                    let papers = self.research_explorer.papers.clone();
+                    let _ = tx.send_now(Action::StartExtraction(papers));
+                }
             }
             Phase::VariantGeneration => {
-                // Auto-start generation if techniques are selected but no variants yet.
-                if self.technique_cards.selected_count() > 0
-                    && self.variant_builder.variants.is_empty()
+                // Auto-start generation if selected techniques lack variants.
+                if !self.techniques_needing_variants().is_empty()
                     && !self.variant_builder.generating
                 {
-                    let _ = tx.send(Action::StartGeneration);
+                    let _ = tx.send_now(Action::StartGeneration);
                 }
             }
             Phase::Benchmarking => {
@@ -633,19 +2795,72 @@ impl App {
                         .all(|v| v.benchmark_results.is_none());
 
                 if has_ready && none_benchmarked && !self.benchmark_dashboard.benchmarking {
-                    let _ = tx.send(Action::StartBenchmark);
+                    let _ = tx.send_now(Action::StartBenchmark);
                 }
             }
             _ => {}
         }
     }
 
+    /// Whether variants can be branched from the project at `path`. If not,
+    /// asks the user whether to commit a baseline, since otherwise branch
+    /// creation would fail partway through generation.
+    fn repository_ready(&self, path: &Path, tx: &ActionSender) -> bool {
+        let status = match git::repo_status(path) {
+            Ok(status) => status,
+            Err(e) => {
+                // Let generation report whatever is wrong.
+                warn!(
+                    "Could not check the repository at {}: {}",
+                    path.display(),
+                    e
+                );
+                return true;
+            }
+        };
+        match status {
+            RepoStatus::Clean => true,
+            RepoStatus::Dirty(_) if self.allow_uncommitted => true,
+            status => {
+                let _ = tx.send_now(Action::OpenGitSetup {
+                    status,
+                    path: path.display().to_string(),
+                });
+                false
+            }
+        }
+    }
+
+    /// Commit the project as it is, off the UI thread, and start generating
+    /// from there.
+    fn commit_baseline(&mut self, tx: &ActionSender) {
+        let Some(path) = self.project_intake.profile.as_ref().map(|p| p.path.clone()) else {
+            return;
+        };
+        let tx = tx.clone();
+        tokio::task::spawn_blocking(move || {
+            match git::commit_baseline(&path, "Baseline before generating variants") {
+                Ok(()) => {
+                    info!("Committed a baseline in {}", path.display());
+                    let status = t!("app.baseline_committed").to_string();
+                    let _ = tx.blocking_send(Action::SetStatus(status));
+                    let _ = tx.blocking_send(Action::StartGeneration);
+                }
+                Err(e) => {
+                    error!("Baseline commit failed: {}", e);
+                    let status = t!("app.baseline_failed", error = e);
+                    let _ = tx.blocking_send(Action::SetStatus(status));
+                }
+            }
+        });
+    }
+
     /// Spawn tasks to generate variants for all selected techniques.
-    fn spawn_generate_variants(&mut self, tx: mpsc::UnboundedSender<Action>) {
+    fn spawn_generate_variants(&mut self, tx: ActionSender) {
         let Some(client) = self.sidecar_client.clone() else {
-            let _ = tx.send(Action::VariantGenerationFailed {
+            let _ = tx.send_now(Action::VariantGenerationFailed {
                 variant_id: "all".to_string(),
-                error: "Sidecar is not running.".to_string(),
+                error: t!("app.sidecar_down").to_string(),
             });
             return;
         };
@@ -653,107 +2868,261 @@ impl App {
         let profile = match self.project_intake.profile.clone() {
             Some(p) => p,
             None => {
-                let _ = tx.send(Action::SetStatus(
-                    "No project profile — analyze a project first.".to_string(),
-                ));
+                let _ = tx.send_now(Action::SetStatus(t!("app.no_profile").to_string()));
                 return;
             }
         };
 
-        // Collect selected techniques and create variant stubs.
-        let selected_techniques: Vec<_> = self
-            .technique_cards
-            .techniques
-            .iter()
-            .filter(|t| t.selected)
-            .cloned()
-            .collect();
+        if self.technique_cards.selected_count() == 0 {
+            let _ = tx.send_now(Action::SetStatus(t!("app.none_selected").to_string()));
+            return;
+        }
 
+        // Collect selected techniques without a variant and create stubs.
+        let selected_techniques = self.techniques_needing_variants();
         if selected_techniques.is_empty() {
-            let _ = tx.send(Action::SetStatus(
-                "No techniques selected. Go back to Phase 3 and select some.".to_string(),
-            ));
+            let _ = tx.send_now(Action::SetStatus(t!("app.all_generated").to_string()));
             return;
         }
+        if !self.repository_ready(&profile.path, &tx) {
+            return;
+        }
+        // Number new variants after the ones from earlier runs.
+        let first_index = uniq_core::variant::next_index(self.taken_ids(), "variant");
 
         self.variant_builder.generating = true;
 
-        let total = selected_techniques.len();
-        let _ = tx.send(Action::SetStatus(format!(
-            "Generating {} variants...",
-            total
-        )));
+        let total = selected_techniques.len() * self.generation_styles.len().max(1);
+        let _ = tx.send_now(Action::SetStatus(t!("app.generating", count = total)));
 
-        // Create Variant stubs and spawn generation tasks.
-        for (i, technique) in selected_techniques.into_iter().enumerate() {
-            let index = i + 1;
-            let variant = uniq_core::variant::Variant::from_technique(index, technique.clone());
-            let branch_name = variant.branch_name.clone();
-            let variant_id = variant.id.0.clone();
+        // Create Variant stubs (one per style, if styles are configured)
+        // and spawn generation tasks.
+        let styles: Vec<Option<ImplementationStyle>> = if self.generation_styles.is_empty() {
+            vec![None]
+        } else {
+            self.generation_styles.iter().copied().map(Some).collect()
+        };
+        let mut index = first_index;
+        for technique in selected_techniques {
+            for style in &styles {
+                let mut variant = Variant::from_technique(index, technique.clone());
+                if let Some(style) = style {
+                    variant = variant.with_style(*style);
+                }
+                index += 1;
+
+                // Add the pending variant to the builder so the UI shows it immediately.
+                self.variant_builder.variants.push(variant.clone());
+                self.spawn_variant_task(client.clone(), variant, profile.clone(), None, tx.clone());
+            }
+        }
+
+        // Generation completion is detected in handle_action by checking
+        // whether all variants have left the Pending/Generating state.
+    }
+
+    /// Regenerate a Ready or Failed variant as a new revision, passing the
+    /// user's feedback along to the model.
+    fn spawn_regenerate_variant(&mut self, variant_id: &str, feedback: String, tx: ActionSender) {
+        let (Some(client), Some(profile)) = (
+            self.sidecar_client.clone(),
+            self.project_intake.profile.clone(),
+        ) else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.regenerate_missing").to_string()));
+            return;
+        };
+        let variants = &self.variant_builder.variants;
+        let Some(original) = variants.iter().find(|v| v.id.0 == variant_id) else {
+            return;
+        };
+
+        let number = uniq_core::variant::next_revision(self.taken_ids(), original.original_id());
+        let Some(revision) =
+            uniq_core::variant::Variant::revise(original, number, feedback.clone())
+        else {
+            let _ = tx.send_now(Action::SetStatus(t!("app.merge_regenerate").to_string()));
+            return;
+        };
 
-            // Add the pending variant to the builder so the UI shows it immediately.
-            self.variant_builder.variants.push(variant);
+        let _ = tx.send_now(Action::SetStatus(t!(
+            "app.regenerating",
+            branch = revision.branch_name
+        )));
+        self.variant_builder.variants.push(revision.clone());
+        self.variant_builder.generating = true;
+        self.spawn_variant_task(client, revision, profile, Some(feedback), tx);
+    }
 
-            let client = client.clone();
-            let tx = tx.clone();
-            let profile = profile.clone();
-            let technique_for_result = technique.clone();
+    /// Generate a variant whose plan the user approved.
+    fn spawn_approved_variant(&mut self, variant_id: &str, tx: ActionSender) {
+        let (Some(client), Some(profile)) = (
+            self.sidecar_client.clone(),
+            self.project_intake.profile.clone(),
+        ) else {
+            return;
+        };
+        let Some(variant) = self
+            .variant_builder
+            .variants
+            .iter()
+            .find(|v| v.id.0 == variant_id)
+            .cloned()
+        else {
+            return;
+        };
+        let feedback = variant.revision.as_ref().map(|r| r.feedback.clone());
+        self.spawn_variant_task(client, variant, profile, feedback, tx);
+    }
 
-            tokio::spawn(async move {
-                match client
-                    .generate_variant(technique, profile, branch_name.clone())
+    /// Generate one variant on the sidecar once a generation slot is free,
+    /// reporting the outcome as `VariantGenerated` or `VariantGenerationFailed`.
+    fn spawn_variant_task(
+        &mut self,
+        client: Arc<SidecarClient>,
+        variant: uniq_core::variant::Variant,
+        profile: uniq_core::project::ProjectProfile,
+        feedback: Option<String>,
+        tx: ActionSender,
+    ) {
+        let Some(technique) = variant.technique().cloned() else {
+            return;
+        };
+        let branch_name = variant.branch_name.clone();
+        let variant_id = variant.id.0.clone();
+        let slots = self.generation_slots.clone();
+        let plan_first = self.plan_first;
+        let install_dependencies = self.install_dependencies;
+        let style = variant.style.map(|s| s.directive().to_string());
+        let task_id = variant_id.clone();
+        let client_for_poller = client.clone();
+        let tx_for_poller = tx.clone();
+
+        let task = tokio::spawn(correlated("generation", async move {
+            // Queued until a slot frees up; the permit is held until done.
+            let Ok(_permit) = slots.acquire_owned().await else {
+                return;
+            };
+            let _ = tx
+                .send(Action::VariantGenerationStarted {
+                    variant_id: variant_id.clone(),
+                })
+                .await;
+
+            // In plan-first mode, stop after drafting a plan; approving it
+            // spawns this task again with the plan attached.
+            if plan_first && variant.plan.is_none() {
+                let outcome = match client
+                    .plan_variant(technique, profile, feedback, style)
                     .await
                 {
-                    Ok(result) => {
-                        if result.success {
-                            info!(
-                                "Variant {} generated: {} files modified",
-                                variant_id,
-                                result.modified_files.len()
-                            );
-                            let mut v = uniq_core::variant::Variant::from_technique(
-                                index,
-                                technique_for_result,
-                            );
-                            v.status = uniq_core::variant::VariantStatus::Ready;
-                            v.modified_files = result.modified_files;
-                            v.new_dependencies = result.new_dependencies;
-                            let _ = tx.send(Action::VariantGenerated(Box::new(v)));
-                        } else {
-                            let err = result.error.unwrap_or_else(|| "Unknown error".to_string());
-                            let _ = tx.send(Action::VariantGenerationFailed {
+                    Ok(result) => result.plan.ok_or_else(|| {
+                        result
+                            .error
+                            .unwrap_or_else(|| t!("app.no_plan").to_string())
+                    }),
+                    Err(e) => Err(format!("{}", e)),
+                };
+                let _ = tx
+                    .send(match outcome {
+                        Ok(plan) => Action::VariantPlanned {
+                            variant_id,
+                            plan: Box::new(plan),
+                        },
+                        Err(error) => Action::VariantGenerationFailed { variant_id, error },
+                    })
+                    .await;
+                return;
+            }
+
+            let plan = variant.plan.clone();
+            match client
+                .generate_variant(
+                    technique,
+                    profile,
+                    branch_name,
+                    feedback,
+                    plan,
+                    style,
+                    install_dependencies,
+                )
+                .await
+            {
+                Ok(result) => {
+                    if result.success {
+                        info!(
+                            "Variant {} generated: {} files modified",
+                            variant_id,
+                            result.modified_files.len()
+                        );
+                        let mut v = variant;
+                        v.status = uniq_core::variant::VariantStatus::Ready;
+                        v.modified_files = result.modified_files;
+                        v.new_dependencies = result.new_dependencies;
+                        v.transcript = result.transcript;
+                        v.dependency_check = result.dependency_check;
+                        let _ = tx.send(Action::VariantGenerated(Box::new(v))).await;
+                    } else {
+                        let err = result
+                            .error
+                            .unwrap_or_else(|| t!("app.unknown_error").to_string());
+                        let _ = tx
+                            .send(Action::VariantGenerationFailed {
                                 variant_id,
                                 error: err,
-                            });
-                        }
+                            })
+                            .await;
                     }
-                    Err(e) => {
-                        error!("Variant generation failed for {}: {}", variant_id, e);
-                        let _ = tx.send(Action::VariantGenerationFailed {
+                }
+                Err(e) => {
+                    error!("Variant generation failed for {}: {}", variant_id, e);
+                    let _ = tx
+                        .send(Action::VariantGenerationFailed {
                             variant_id,
                             error: format!("{}", e),
-                        });
-                    }
+                        })
+                        .await;
                 }
-            });
-        }
-
-        // Generation completion is detected in handle_action by checking
-        // whether all variants have left the Pending/Generating state.
+            }
+        }));
+        self.generation_tasks.insert(task_id, task.abort_handle());
+        self.ensure_progress_poller(client_for_poller, tx_for_poller);
     }
 
-    /// Spawn tasks to run benchmarks on all ready variants.
-    fn spawn_run_benchmarks(&mut self, tx: mpsc::UnboundedSender<Action>) {
-        let Some(client) = self.sidecar_client.clone() else {
-            let _ = tx.send(Action::SetStatus("Sidecar is not running.".to_string()));
+    /// Start polling generation progress, unless already polling. The
+    /// poller is stopped once no generation tasks remain.
+    fn ensure_progress_poller(&mut self, client: Arc<SidecarClient>, tx: ActionSender) {
+        if self.progress_poller.is_some() {
             return;
-        };
+        }
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            let mut reported = false;
+            loop {
+                interval.tick().await;
+                match client.generation_progress().await {
+                    Ok(progress) => {
+                        if tx.send(Action::GenerationProgress(progress)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Generation progress poll failed: {}", e);
+                        report_unreadable(&e, &mut reported, &tx).await;
+                    }
+                }
+            }
+        });
+        self.progress_poller = Some(task.abort_handle());
+    }
 
+    /// Spawn tasks to run benchmarks on all ready variants.
+    fn spawn_run_benchmarks(&mut self, tx: ActionSender) {
+        let client = self.sidecar_client.clone();
         let project_path = self.project_intake.profile.as_ref().map(|p| p.path.clone());
         let project_path = match project_path {
-            Some(p) => PathBuf::from(p),
+            Some(p) => p,
             None => {
-                let _ = tx.send(Action::SetStatus("No project path available.".to_string()));
+                let _ = tx.send_now(Action::SetStatus(t!("app.no_path").to_string()));
                 return;
             }
         };
@@ -771,67 +3140,108 @@ impl App {
             .collect();
 
         if ready_branches.is_empty() {
-            let _ = tx.send(Action::SetStatus(
-                "No ready variants to benchmark.".to_string(),
+            let _ = tx.send_now(Action::SetStatus(
+                t!("app.nothing_to_benchmark").to_string(),
             ));
             self.benchmark_dashboard.benchmarking = false;
             return;
         }
 
+        let timeouts: HashMap<String, u64> = self
+            .variant_builder
+            .variants
+            .iter()
+            .filter(|v| ready_branches.contains(&v.branch_name))
+            .map(|v| (v.branch_name.clone(), self.benchmark_config.timeout_for(v)))
+            .collect();
+
         let user_request = self.user_description.clone();
-        let _ = tx.send(Action::SetStatus(format!(
-            "Running benchmarks on {} variants...",
-            ready_branches.len()
+        let _ = tx.send_now(Action::SetStatus(t!(
+            "app.benchmarking",
+            count = ready_branches.len()
         )));
 
-        // Run execution benchmarks and LLM judge in parallel.
-        let client_exec = client.clone();
-        let client_judge = client;
-        let branches_exec = ready_branches.clone();
-        let branches_judge = ready_branches;
-        let path_exec = project_path.clone();
-        let path_judge = project_path;
-        let tx_exec = tx.clone();
-        let tx_judge = tx;
-
-        // Execution benchmarks
-        tokio::spawn(async move {
-            match client_exec
-                .run_benchmark(branches_exec, path_exec, vec![], 300)
-                .await
-            {
-                Ok(results) => {
-                    for (branch, metrics) in results {
-                        info!("Benchmark for {}: build={}", branch, metrics.build_success);
-                        let _ = tx_exec.send(Action::BenchmarkUpdated { variant_id: branch });
-                    }
-                }
-                Err(e) => {
-                    error!("Execution benchmark failed: {}", e);
-                    let _ = tx_exec.send(Action::SetStatus(format!("Benchmark failed: {}", e)));
-                }
+        // The unmodified project is benchmarked first, for deltas.
+        let baseline = match uniq_core::git::head_branch(&project_path) {
+            Ok(branch) => Some(branch),
+            Err(e) => {
+                warn!("No baseline branch to benchmark: {}", e);
+                None
             }
-        });
+        };
+        self.benchmark_dashboard.baseline_branch = baseline.clone();
+        self.benchmark_dashboard.baseline = None;
 
-        // LLM judge
-        tokio::spawn(async move {
-            match client_judge
-                .llm_judge(branches_judge, path_judge, user_request)
-                .await
-            {
-                Ok(scores) => {
-                    for (branch, _judge_scores) in scores {
-                        let _ = tx_judge.send(Action::BenchmarkUpdated { variant_id: branch });
-                    }
-                    let _ = tx_judge.send(Action::BenchmarkComplete);
-                }
-                Err(e) => {
-                    error!("LLM judge failed: {}", e);
-                    let _ = tx_judge.send(Action::SetStatus(format!("LLM judge failed: {}", e)));
-                    let _ = tx_judge.send(Action::BenchmarkComplete);
+        self.benchmark_dashboard.stages = baseline
+            .iter()
+            .chain(&ready_branches)
+            .map(|b| (b.clone(), BenchmarkStage::Queued))
+            .collect();
+
+        // Execution benchmarks run in-process when configured, or when
+        // there is no sidecar to run them. The LLM judge needs the sidecar.
+        let native = self.benchmark_config.native_runner || client.is_none();
+        let config = self.benchmark_config.clone();
+        let after_execution = if client.is_some() {
+            BenchmarkStage::Queued
+        } else {
+            BenchmarkStage::Done
+        };
+
+        let plugins = self.plugins.clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.benchmark_cancel = cancel.clone();
+
+        let task = tokio::spawn(correlated("benchmark", async move {
+            let runner = client.as_deref().filter(|_| !native);
+            if let Some(baseline) = baseline {
+                run_execution(
+                    runner,
+                    std::slice::from_ref(&baseline),
+                    &project_path,
+                    &config,
+                    &timeouts,
+                    BenchmarkStage::Done,
+                    &cancel,
+                    &tx,
+                )
+                .await;
+                collect_plugin_metrics(&plugins, &[baseline], &project_path, &cancel, &tx).await;
+            }
+            run_execution(
+                runner,
+                &ready_branches,
+                &project_path,
+                &config,
+                &timeouts,
+                after_execution,
+                &cancel,
+                &tx,
+            )
+            .await;
+            collect_plugin_metrics(&plugins, &ready_branches, &project_path, &cancel, &tx).await;
+            if let Some(client) = client {
+                tokio::select! {
+                    _ = judge_variants(
+                        &client,
+                        &ready_branches,
+                        &project_path,
+                        &user_request,
+                        &config.judge,
+                        &tx,
+                    ) => {}
+                    _ = cancelled(&cancel) => {}
                 }
             }
-        });
+            let done = if cancel.load(Ordering::Relaxed) {
+                Action::BenchmarkCancelled
+            } else {
+                Action::BenchmarkComplete
+            };
+            let _ = tx.send(done).await;
+        }));
+        self.phase_tasks
+            .insert(Phase::Benchmarking, task.abort_handle());
     }
 
     /// Render the full UI.
@@ -857,14 +3267,16 @@ impl App {
             chunks[1],
         );
 
-        // Main content
-        match self.current_phase {
-            Phase::ProjectIntake => self.project_intake.render(frame, chunks[2]),
-            Phase::ResearchDiscovery => self.research_explorer.render(frame, chunks[2]),
-            Phase::TechniqueSelection => self.technique_cards.render(frame, chunks[2]),
-            Phase::VariantGeneration => self.variant_builder.render(frame, chunks[2]),
-            Phase::Benchmarking => self.benchmark_dashboard.render(frame, chunks[2]),
+        // Main content, with the sidebar on the right if it's open and fits.
+        let mut main = chunks[2];
+        if self.sidebar.visible && main.width >= SIDEBAR_WIDTH * 3 {
+            let [content, sidebar] =
+                Layout::horizontal([Constraint::Min(0), Constraint::Length(SIDEBAR_WIDTH)])
+                    .areas(main);
+            self.sidebar.render(frame, sidebar);
+            main = content;
         }
+        self.phase_component().render(frame, main);
 
         // Thin separator above status bar
         frame.render_widget(
@@ -877,7 +3289,107 @@ impl App {
 
         // Overlays (rendered on top)
         self.merge_dialog.render(frame, area);
+        self.conflict_viewer.render(frame, area);
+        self.status_log.render(frame, area);
+        self.timeline.render(frame, area);
+        self.usage.render(frame, area);
         self.help.render(frame, area);
+        self.prompt.render(frame, area);
+        self.rating.render(frame, area);
+        self.git_setup.render(frame, area);
+        self.command_palette.render(frame, area);
+        self.onboarding.render(frame, area);
+    }
+
+    /// Name of the analyzed project's directory.
+    fn project_name(&self) -> Option<String> {
+        self.project_intake.profile.as_ref().map(|p| {
+            p.path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| p.path.display().to_string())
+        })
+    }
+
+    /// Terminal window title: the project and the phase on screen.
+    fn terminal_title(&self) -> String {
+        match self.project_name() {
+            Some(project) => format!("uniq — {} — {}", project, self.current_phase.name()),
+            None => format!("uniq — {}", self.current_phase.name()),
+        }
+    }
+
+    /// Progress of whatever long operation is running, for the terminal tab.
+    fn terminal_progress(&self) -> Progress {
+        if self.variant_builder.generating {
+            let generated: Vec<&Variant> = self
+                .variant_builder
+                .variants
+                .iter()
+                .filter(|v| !v.is_merge())
+                .collect();
+            let done = generated
+                .iter()
+                .filter(|v| {
+                    !matches!(
+                        v.status,
+                        VariantStatus::Pending
+                            | VariantStatus::Generating
+                            | VariantStatus::AwaitingApproval
+                    )
+                })
+                .count();
+            return Progress::fraction(done, generated.len());
+        }
+        if self.benchmark_dashboard.benchmarking {
+            let stages = &self.benchmark_dashboard.stages;
+            let done = stages
+                .values()
+                .filter(|s| matches!(s, BenchmarkStage::Done | BenchmarkStage::Failed))
+                .count();
+            return Progress::fraction(done, stages.len());
+        }
+        if self.project_intake.analyzing
+            || self.research_explorer.searching
+            || self.technique_cards.extracting
+        {
+            return Progress::Indeterminate;
+        }
+        Progress::Hidden
+    }
+
+    /// Counts for the sidebar, gathered from every phase.
+    fn pipeline_summary(&self) -> PipelineSummary {
+        let techniques = &self.technique_cards.techniques;
+        let variants = &self.variant_builder.variants;
+        let best = variants
+            .iter()
+            .filter_map(|v| {
+                let score = v.benchmark_results.as_ref()?.composite_score?;
+                Some((v.display_name.clone(), score))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        PipelineSummary {
+            project: self.project_name(),
+            papers: self.research_explorer.papers.len(),
+            searching: self.research_explorer.searching,
+            techniques: (
+                techniques.iter().filter(|t| t.selected).count(),
+                techniques.len(),
+            ),
+            variants: (
+                variants
+                    .iter()
+                    .filter(|v| v.status == VariantStatus::Ready)
+                    .count(),
+                variants.len(),
+            ),
+            generating: variants
+                .iter()
+                .filter(|v| v.status == VariantStatus::Generating)
+                .count(),
+            best,
+        }
     }
 
     /// Render the phase tab bar.
@@ -910,3 +3422,383 @@ impl App {
         frame.render_widget(Paragraph::new(line), area);
     }
 }
+
+/// Keep the token usage view current until the app exits. Polls the
+/// sidecar fails are retried, less often the longer it keeps failing.
+async fn poll_usage(client: &SidecarClient, tx: &ActionSender) {
+    let mut last = Vec::new();
+    let mut delay = USAGE_POLL_INTERVAL;
+    let mut reported = false;
+    loop {
+        match client.usage().await {
+            Ok(entries) => {
+                delay = USAGE_POLL_INTERVAL;
+                if entries != last {
+                    last = entries.clone();
+                    if tx.send(Action::UsageUpdated(entries)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                // Back off while the sidecar is failing, then pick up
+                // again where it left off.
+                if delay == USAGE_POLL_INTERVAL {
+                    warn!("Polling token usage failed, retrying: {}", e);
+                }
+                report_unreadable(&e, &mut reported, tx).await;
+                delay = (delay * 2).min(USAGE_POLL_MAX_BACKOFF);
+            }
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Show a poll's failure in the status bar if the sidecar's answer couldn't
+/// be decoded, which polling again won't fix. Other failures only go to the
+/// log. `reported` keeps a poller from repeating it every second.
+async fn report_unreadable(error: &anyhow::Error, reported: &mut bool, tx: &ActionSender) {
+    if *reported {
+        return;
+    }
+    if let Some(error) = error.downcast_ref::<DecodeError>() {
+        *reported = true;
+        let _ = tx
+            .send(Action::SetStatus(t!(
+                "app.updates_unreadable",
+                error = error
+            )))
+            .await;
+    }
+}
+
+fn stage_action(branch: &str, stage: BenchmarkStage) -> Action {
+    Action::BenchmarkProgress {
+        branch: branch.to_string(),
+        stage,
+    }
+}
+
+async fn send_stage(tx: &ActionSender, branch: &str, stage: BenchmarkStage) {
+    let _ = tx.send(stage_action(branch, stage)).await;
+}
+
+/// Run execution benchmarks for `branches` on the sidecar, or natively
+/// when `sidecar` is `None`.
+#[allow(clippy::too_many_arguments)]
+async fn run_execution(
+    sidecar: Option<&SidecarClient>,
+    branches: &[String],
+    project_path: &Path,
+    config: &BenchmarkConfig,
+    timeouts: &HashMap<String, u64>,
+    after: BenchmarkStage,
+    cancel: &Arc<AtomicBool>,
+    tx: &ActionSender,
+) {
+    match sidecar {
+        // Nothing runs locally, so the request can simply be dropped.
+        Some(client) => tokio::select! {
+            _ = run_sidecar_benchmarks(client, branches, project_path, config, timeouts, after, tx) => {}
+            _ = cancelled(cancel) => {}
+        },
+        None => {
+            run_native_benchmarks(branches, project_path, config, timeouts, after, cancel, tx).await
+        }
+    }
+}
+
+/// Resolves once `cancel` is set.
+async fn cancelled(cancel: &AtomicBool) {
+    while !cancel.load(Ordering::Relaxed) {
+        tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+    }
+}
+
+/// Run the metric plugins on each of `branches`, in its own worktree.
+async fn collect_plugin_metrics(
+    plugins: &Arc<PluginRegistry>,
+    branches: &[String],
+    project_path: &Path,
+    cancel: &AtomicBool,
+    tx: &ActionSender,
+) {
+    if !plugins.has_collectors() {
+        return;
+    }
+    for branch in branches {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        let plugins = plugins.clone();
+        let path = project_path.to_path_buf();
+        let name = branch.clone();
+        let collected = tokio::task::spawn_blocking(move || {
+            let worktree = git::TempWorktree::create(&path, &name)?;
+            Ok::<_, uniq_core::UniqError>(plugins.collect_metrics(&worktree.path, &name))
+        })
+        .await;
+        let (metrics, failures) = match collected {
+            Ok(Ok(collected)) => collected,
+            Ok(Err(e)) => {
+                error!(
+                    "No worktree for {} to collect plugin metrics in: {}",
+                    branch, e
+                );
+                continue;
+            }
+            Err(e) => {
+                error!("Plugin metrics task for {} panicked: {}", branch, e);
+                continue;
+            }
+        };
+        for failure in failures {
+            warn!(
+                "Metric plugin {} failed on {}: {}",
+                failure.plugin, branch, failure.error
+            );
+            let _ = tx
+                .send(Action::SetStatus(t!(
+                    "app.plugin_failed",
+                    plugin = failure.plugin,
+                    error = failure.error
+                )))
+                .await;
+        }
+        if !metrics.is_empty() {
+            let _ = tx
+                .send(Action::PluginMetrics {
+                    branch: branch.clone(),
+                    metrics,
+                })
+                .await;
+        }
+    }
+}
+
+/// Benchmark `branches` one at a time with the native runner, each in its
+/// own worktree. Sequential so builds don't compete for CPU and skew timings.
+/// Each branch moves to `after` once its metrics are in.
+async fn run_native_benchmarks(
+    branches: &[String],
+    project_path: &Path,
+    config: &BenchmarkConfig,
+    timeouts: &HashMap<String, u64>,
+    after: BenchmarkStage,
+    cancel: &Arc<AtomicBool>,
+    tx: &ActionSender,
+) {
+    for branch in branches {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        let cancel = cancel.clone();
+        let path = project_path.to_path_buf();
+        let config = config.clone();
+        let name = branch.clone();
+        let stage_tx = tx.clone();
+        let timeout = Duration::from_secs(
+            timeouts
+                .get(branch)
+                .copied()
+                .unwrap_or(config.timeout_seconds),
+        );
+        let report = tokio::task::spawn_blocking(move || {
+            runner::run_variant(&path, &name, &config, timeout, &cancel, |stage| {
+                let _ = stage_tx.blocking_send(stage_action(&name, stage));
+            })
+        })
+        .await;
+        match report {
+            // Awaited rather than dropped, so the worktree is gone by now.
+            Ok(Err(uniq_core::UniqError::Cancelled)) => return,
+            Ok(Ok(report)) => {
+                let metrics = report.to_metrics();
+                info!("Benchmark for {}: build={}", branch, metrics.build_success);
+                let _ = tx
+                    .send(Action::BenchmarkUpdated {
+                        branch: branch.clone(),
+                        execution: Some(Box::new(metrics)),
+                        judge: None,
+                    })
+                    .await;
+                send_stage(tx, branch, after).await;
+            }
+            Ok(Err(e)) => {
+                error!("Benchmark for {} failed: {}", branch, e);
+                let _ = tx
+                    .send(Action::SetStatus(t!(
+                        "app.benchmark_failed_for",
+                        branch = branch,
+                        error = e
+                    )))
+                    .await;
+                send_stage(tx, branch, BenchmarkStage::Failed).await;
+            }
+            Err(e) => {
+                error!("Benchmark task for {} panicked: {}", branch, e);
+                send_stage(tx, branch, BenchmarkStage::Failed).await;
+            }
+        }
+    }
+}
+
+/// Benchmark `branches` on the sidecar, polling its per-branch progress
+/// while the request runs.
+async fn run_sidecar_benchmarks(
+    client: &SidecarClient,
+    branches: &[String],
+    project_path: &Path,
+    config: &BenchmarkConfig,
+    timeouts: &HashMap<String, u64>,
+    after: BenchmarkStage,
+    tx: &ActionSender,
+) {
+    let request = client.run_benchmark(
+        branches.to_vec(),
+        project_path.to_path_buf(),
+        config.metrics.clone(),
+        config.timeout_seconds,
+        timeouts.clone(),
+        config.repetitions,
+    );
+    tokio::pin!(request);
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    let mut reported = false;
+    let result = loop {
+        tokio::select! {
+            result = &mut request => break result,
+            _ = interval.tick() => match client.benchmark_progress().await {
+                Ok(progress) => {
+                    for (branch, stage) in progress {
+                        send_stage(tx, &branch, stage).await;
+                    }
+                }
+                Err(e) => {
+                    warn!("Benchmark progress poll failed: {}", e);
+                    report_unreadable(&e, &mut reported, tx).await;
+                }
+            },
+        }
+    };
+
+    match result {
+        Ok(results) => {
+            for (branch, metrics) in results {
+                info!("Benchmark for {}: build={}", branch, metrics.build_success);
+                let _ = tx
+                    .send(Action::BenchmarkUpdated {
+                        branch: branch.clone(),
+                        execution: Some(Box::new(metrics)),
+                        judge: None,
+                    })
+                    .await;
+                send_stage(tx, &branch, after).await;
+            }
+        }
+        Err(e) => {
+            error!("Execution benchmark failed: {}", e);
+            let _ = tx
+                .send(Action::SetStatus(t!("app.benchmark_failed", error = e)))
+                .await;
+            for branch in branches {
+                send_stage(tx, branch, BenchmarkStage::Failed).await;
+            }
+        }
+    }
+}
+
+/// Score `branches` with the LLM judge, a few at a time. Each variant is a
+/// request of its own, so its stage is accurate.
+async fn judge_variants(
+    client: &SidecarClient,
+    branches: &[String],
+    project_path: &Path,
+    user_request: &str,
+    ensemble: &JudgeEnsemble,
+    tx: &ActionSender,
+) {
+    if ensemble.mode == JudgeMode::Pairwise && branches.len() > 1 {
+        judge_pairwise(client, branches, project_path, user_request, ensemble, tx).await;
+        return;
+    }
+    stream::iter(branches)
+        .for_each_concurrent(CONCURRENT_JUDGEMENTS, |branch| async move {
+            send_stage(tx, branch, BenchmarkStage::Judging).await;
+            let scores = client
+                .llm_judge(
+                    vec![branch.clone()],
+                    project_path.to_path_buf(),
+                    user_request.to_string(),
+                    ensemble.clone(),
+                )
+                .await;
+            match scores {
+                Ok(scores) => {
+                    for (branch, judge_scores) in scores {
+                        let _ = tx
+                            .send(Action::BenchmarkUpdated {
+                                branch,
+                                execution: None,
+                                judge: Some(judge_scores),
+                            })
+                            .await;
+                    }
+                    send_stage(tx, branch, BenchmarkStage::Done).await;
+                }
+                Err(e) => {
+                    error!("LLM judge failed for {}: {}", branch, e);
+                    let _ = tx
+                        .send(Action::SetStatus(t!("app.judge_failed", error = e)))
+                        .await;
+                    send_stage(tx, branch, BenchmarkStage::Failed).await;
+                }
+            }
+        })
+        .await;
+}
+
+/// Have the judge compare every pair of `branches`, for Elo ranking.
+async fn judge_pairwise(
+    client: &SidecarClient,
+    branches: &[String],
+    project_path: &Path,
+    user_request: &str,
+    ensemble: &JudgeEnsemble,
+    tx: &ActionSender,
+) {
+    for branch in branches {
+        send_stage(tx, branch, BenchmarkStage::Judging).await;
+    }
+    // Pairwise comparisons have no template kind of their own.
+    if client.prompt(PromptKind::Judge).is_some() {
+        warn!("Pairwise judging ignores the judge prompt template");
+        let status = t!("app.pairwise_template_ignored").to_string();
+        let _ = tx.send(Action::SetStatus(status)).await;
+    }
+    let comparisons = client
+        .llm_judge_pairwise(
+            branches.to_vec(),
+            project_path.to_path_buf(),
+            user_request.to_string(),
+            ensemble.clone(),
+        )
+        .await;
+    let stage = match comparisons {
+        Ok(comparisons) => {
+            info!("Pairwise judge made {} comparisons", comparisons.len());
+            let _ = tx.send(Action::PairwiseJudged { comparisons }).await;
+            BenchmarkStage::Done
+        }
+        Err(e) => {
+            error!("Pairwise judge failed: {}", e);
+            let _ = tx
+                .send(Action::SetStatus(t!("app.pairwise_failed", error = e)))
+                .await;
+            BenchmarkStage::Failed
+        }
+    };
+    for branch in branches {
+        send_stage(tx, branch, stage).await;
+    }
+}
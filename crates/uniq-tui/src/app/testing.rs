@@ -0,0 +1,974 @@
+//! Test harness for driving the [`App`] without a terminal: feed it a script
+//! of actions and check what the components did with them, or what ends up
+//! on screen.
+//!
+//! Nothing is read from or written to disk, and no sidecar is started
+//! unless one is injected. Actions that spawn background work still need a
+//! Tokio runtime, so tests that send them should use `#[tokio::test]`.
+//!
+//! The locale and theme are global to the app, but in this crate's tests
+//! each test thread has its own, so a test can switch them freely.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+use unicode_width::UnicodeWidthStr;
+use uniq_core::UniqConfig;
+use uniq_sidecar::SidecarClient;
+
+use crate::action::{Action, InputMode, Phase};
+use crate::app::App;
+use crate::channel::{action_channel, ActionReceiver, ActionSender};
+use crate::components::benchmark_dashboard::BenchmarkDashboardComponent;
+use crate::components::conflict_viewer::ConflictViewerComponent;
+use crate::components::merge_dialog::MergeDialogComponent;
+use crate::components::project_intake::ProjectIntakeComponent;
+use crate::components::research_explorer::ResearchExplorerComponent;
+use crate::components::status_bar::StatusBarComponent;
+use crate::components::technique_cards::TechniqueCardsComponent;
+use crate::components::variant_builder::VariantBuilderComponent;
+use crate::terminal::Progress;
+
+#[cfg(test)]
+mod fixtures;
+#[cfg(test)]
+mod snapshots;
+
+/// Follow-up actions one scripted action may cause before the harness
+/// assumes they're feeding each other forever.
+const MAX_FOLLOW_UPS: usize = 1000;
+
+pub struct Harness {
+    app: App,
+    tx: ActionSender,
+    rx: ActionReceiver,
+    /// Every action handled so far, scripted and follow-up, in order.
+    handled: Vec<Action>,
+}
+
+impl Harness {
+    /// An app with the default config, on Phase 1.
+    pub fn new() -> Self {
+        Self::with_config(UniqConfig::default())
+    }
+
+    /// An app with `config`, in English unless it sets a locale.
+    pub fn with_config(mut config: UniqConfig) -> Self {
+        // Not `LANG`, so results don't depend on the machine.
+        config.ui.locale.get_or_insert_with(|| "en".to_string());
+        let mut app = App::new(PathBuf::new(), config);
+        app.persist = false;
+        app.metrics = None;
+        let (tx, rx) = action_channel();
+        Self {
+            app,
+            tx,
+            rx,
+            handled: Vec::new(),
+        }
+    }
+
+    /// Use `client` for sidecar calls, as if the sidecar had started.
+    pub fn with_sidecar(mut self, client: SidecarClient) -> Self {
+        self.app.sidecar_client = Some(Arc::new(client));
+        self
+    }
+
+    /// Handle `action`, then every action it queues in turn, the way the
+    /// main loop would. Actions sent later by background tasks wait for
+    /// [`Harness::pump`].
+    pub fn dispatch(&mut self, action: Action) {
+        self.handle(action);
+        self.pump();
+    }
+
+    /// Dispatch each action in order, stopping early if the app quits.
+    pub fn run(&mut self, actions: impl IntoIterator<Item = Action>) {
+        for action in actions {
+            if self.app.should_quit {
+                break;
+            }
+            self.dispatch(action);
+        }
+    }
+
+    /// Handle whatever is waiting on the action channel, including actions
+    /// queued since by background tasks.
+    pub fn pump(&mut self) {
+        let mut follow_ups = 0;
+        while let Some(action) = self.rx.try_recv() {
+            if self.app.should_quit {
+                break;
+            }
+            follow_ups += 1;
+            assert!(
+                follow_ups <= MAX_FOLLOW_UPS,
+                "actions kept queueing more actions; last was {:?}",
+                action
+            );
+            self.handle(action);
+        }
+    }
+
+    /// Handle actions as they arrive, background tasks' included, until one
+    /// matching `done` has been handled along with whatever it queued.
+    /// Panics if none comes within ten seconds.
+    pub async fn wait_for(&mut self, done: impl Fn(&Action) -> bool) {
+        loop {
+            let action = tokio::time::timeout(Duration::from_secs(10), self.rx.recv())
+                .await
+                .expect("timed out waiting for an action")
+                .expect("the action channel closed");
+            let finished = done(&action);
+            self.handle(action);
+            if finished {
+                self.pump();
+                return;
+            }
+        }
+    }
+
+    fn handle(&mut self, action: Action) {
+        self.app.handle_action(&action, &self.tx);
+        self.app.sync_input_mode();
+        self.handled.push(action);
+    }
+
+    /// Every action handled so far, in order.
+    pub fn handled(&self) -> &[Action] {
+        &self.handled
+    }
+
+    /// Forget the actions handled so far.
+    pub fn clear_handled(&mut self) {
+        self.handled.clear();
+    }
+
+    /// Draw the app on a `width` by `height` screen and return the text on
+    /// it, one line per row with trailing spaces trimmed. Colors and styles
+    /// are left out.
+    pub fn render(&mut self, width: u16, height: u16) -> String {
+        if self.app.sidebar.visible {
+            self.app.sidebar.summary = self.app.pipeline_summary();
+        }
+        let mut terminal =
+            Terminal::new(TestBackend::new(width, height)).expect("test backend never fails");
+        terminal
+            .draw(|frame| self.app.render(frame))
+            .expect("test backend never fails");
+        text_grid(terminal.backend().buffer())
+    }
+
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.app.current_phase
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.app.should_quit
+    }
+
+    /// Whether keys currently go to a text field.
+    pub fn input_mode(&self) -> InputMode {
+        self.app.current_input_mode()
+    }
+
+    /// The panel that has focus within the current phase, if it has more
+    /// than one.
+    pub fn focused_panel(&self) -> Option<&'static str> {
+        self.app.phase_component().focused_panel()
+    }
+
+    /// The latest status bar message.
+    pub fn status(&self) -> &str {
+        &self.app.status_bar.message
+    }
+
+    /// Text waiting to be opened in `$EDITOR`.
+    pub fn pending_editor(&self) -> Option<&str> {
+        self.app.pending_editor.as_deref()
+    }
+
+    pub fn terminal_title(&self) -> String {
+        self.app.terminal_title()
+    }
+
+    pub fn terminal_progress(&self) -> Progress {
+        self.app.terminal_progress()
+    }
+
+    pub fn project_intake(&self) -> &ProjectIntakeComponent {
+        &self.app.project_intake
+    }
+
+    pub fn research_explorer(&self) -> &ResearchExplorerComponent {
+        &self.app.research_explorer
+    }
+
+    pub fn technique_cards(&self) -> &TechniqueCardsComponent {
+        &self.app.technique_cards
+    }
+
+    pub fn variant_builder(&self) -> &VariantBuilderComponent {
+        &self.app.variant_builder
+    }
+
+    pub fn benchmark_dashboard(&self) -> &BenchmarkDashboardComponent {
+        &self.app.benchmark_dashboard
+    }
+
+    pub fn merge_dialog(&self) -> &MergeDialogComponent {
+        &self.app.merge_dialog
+    }
+
+    pub fn conflict_viewer(&self) -> &ConflictViewerComponent {
+        &self.app.conflict_viewer
+    }
+
+    pub fn status_bar(&self) -> &StatusBarComponent {
+        &self.app.status_bar
+    }
+
+    // Direct access to the phase components, to set up states that would
+    // otherwise need a sidecar.
+
+    pub fn project_intake_mut(&mut self) -> &mut ProjectIntakeComponent {
+        &mut self.app.project_intake
+    }
+
+    pub fn research_explorer_mut(&mut self) -> &mut ResearchExplorerComponent {
+        &mut self.app.research_explorer
+    }
+
+    pub fn technique_cards_mut(&mut self) -> &mut TechniqueCardsComponent {
+        &mut self.app.technique_cards
+    }
+
+    pub fn variant_builder_mut(&mut self) -> &mut VariantBuilderComponent {
+        &mut self.app.variant_builder
+    }
+
+    pub fn benchmark_dashboard_mut(&mut self) -> &mut BenchmarkDashboardComponent {
+        &mut self.app.benchmark_dashboard
+    }
+
+    /// Whether the help, status log, timeline or usage overlay is open.
+    pub fn overlay_open(&self) -> bool {
+        self.app.help.visible
+            || self.app.status_log.visible
+            || self.app.timeline.visible
+            || self.app.usage.visible
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The symbols in `buffer`, row by row. Cells covered by a wide character
+/// to their left are skipped.
+fn text_grid(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    let mut rows = Vec::with_capacity(buffer.area.height as usize);
+    for cells in buffer.content.chunks(width.max(1)) {
+        let mut row = String::with_capacity(width);
+        let mut hidden = 0;
+        for cell in cells {
+            if hidden == 0 {
+                row.push_str(cell.symbol());
+            }
+            hidden = hidden.max(cell.symbol().width()).saturating_sub(1);
+        }
+        rows.push(row.trim_end().to_string());
+    }
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{execution, judge, paper, profile, technique, usage, variant};
+    use super::*;
+    use crate::i18n::{self, Locale};
+    use crate::theme::{Palette, Theme};
+    use uniq_core::benchmark::{BenchmarkResults, ScoreWeights};
+    use uniq_core::git::{self, RepoStatus};
+    use uniq_core::llm::LlmProvider;
+    use uniq_core::merge::{BlendRatio, MergeSpec};
+    use uniq_core::project::ProjectProfile;
+    use uniq_core::research::TechniqueCard;
+    use uniq_core::session::Session;
+    use uniq_core::variant::{GenerationTranscript, Variant, VariantStatus};
+    use uniq_sidecar::protocol::UsageEntry;
+
+    /// On Phase 2 with `count` papers found.
+    fn with_papers(count: usize) -> Harness {
+        let mut harness = Harness::new();
+        harness.run([
+            Action::GoToPhase(Phase::ResearchDiscovery),
+            Action::PapersFound((0..count).map(paper).collect()),
+            Action::ResearchComplete,
+        ]);
+        harness
+    }
+
+    /// With a project in a fresh repository named `name`, and a sidecar
+    /// nothing answers at, so generation starts but never finishes.
+    fn ready_to_generate(name: &str) -> Harness {
+        let path = std::env::temp_dir().join(format!("uniq-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        git::commit_baseline(&path, "Baseline").unwrap();
+
+        let client = SidecarClient::new("http://127.0.0.1:9".to_string());
+        let mut harness = Harness::new().with_sidecar(client);
+        harness.project_intake_mut().profile = Some(ProjectProfile { path, ..profile() });
+        harness
+    }
+
+    fn remove_project(harness: &Harness) {
+        let profile = harness.project_intake().profile.as_ref().unwrap();
+        std::fs::remove_dir_all(&profile.path).unwrap();
+    }
+
+    fn variant_ids(harness: &Harness) -> Vec<&str> {
+        let variants = &harness.variant_builder().variants;
+        variants.iter().map(|v| v.id.0.as_str()).collect()
+    }
+
+    #[test]
+    fn phase_keys_move_between_phases() {
+        let mut harness = Harness::new();
+        harness.dispatch(Action::GoToPhase(Phase::ResearchDiscovery));
+        assert_eq!(harness.phase(), Phase::ResearchDiscovery);
+        assert_eq!(harness.status_bar().current_phase, Phase::ResearchDiscovery);
+
+        harness.run([Action::NextPhase, Action::NextPhase]);
+        assert_eq!(harness.phase(), Phase::VariantGeneration);
+        harness.dispatch(Action::PrevPhase);
+        assert_eq!(harness.phase(), Phase::TechniqueSelection);
+    }
+
+    #[test]
+    fn tab_cycles_panels_within_a_phase() {
+        let mut harness = with_papers(2);
+        assert_eq!(harness.focused_panel(), Some("papers"));
+
+        harness.dispatch(Action::FocusNext);
+        assert_eq!(harness.phase(), Phase::ResearchDiscovery);
+        assert_eq!(harness.focused_panel(), Some("details"));
+
+        harness.dispatch(Action::FocusNext);
+        assert_eq!(harness.focused_panel(), Some("papers"));
+    }
+
+    #[test]
+    fn tab_changes_phase_with_one_panel() {
+        let mut harness = Harness::new();
+        harness.dispatch(Action::GoToPhase(Phase::ResearchDiscovery));
+        assert_eq!(harness.focused_panel(), None);
+
+        harness.dispatch(Action::FocusNext);
+        assert_eq!(harness.phase(), Phase::TechniqueSelection);
+        harness.dispatch(Action::FocusPrev);
+        assert_eq!(harness.phase(), Phase::ResearchDiscovery);
+    }
+
+    #[test]
+    fn overlays_take_navigation_input() {
+        let mut harness = with_papers(3);
+        harness.dispatch(Action::ToggleTimeline);
+        assert!(harness.overlay_open());
+
+        harness.dispatch(Action::SelectNext);
+        assert_eq!(harness.research_explorer().selected, 0);
+        harness.dispatch(Action::Escape);
+        assert!(!harness.overlay_open());
+
+        harness.dispatch(Action::SelectNext);
+        assert_eq!(harness.research_explorer().selected, 1);
+    }
+
+    #[test]
+    fn overlays_stop_scrolling_at_their_last_page() {
+        let mut harness = Harness::new();
+        for i in 0..40 {
+            harness.dispatch(Action::SetStatus(format!("entry {i:02}")));
+        }
+        harness.dispatch(Action::ToggleStatusLog);
+        let screen = harness.render(80, 30);
+        assert!(screen.contains("entry 39"), "{screen}");
+
+        // Scrolling past the end stays on the last page, so one step back
+        // moves straight away.
+        harness.run(std::iter::repeat_n(Action::ScrollDown, 100));
+        let screen = harness.render(80, 30);
+        assert!(screen.contains("entry 00"), "{screen}");
+        harness.dispatch(Action::ScrollUp);
+        let screen = harness.render(80, 30);
+        assert!(!screen.contains("entry 00"), "{screen}");
+        assert!(screen.contains("entry 01"), "{screen}");
+    }
+
+    #[test]
+    fn locale_and_theme_stay_with_their_test() {
+        let mut config = UniqConfig::default();
+        config.ui.locale = Some("de".to_string());
+        let mut harness = Harness::with_config(config);
+        harness.dispatch(Action::CycleTheme);
+        let palette = Theme::palette().name;
+        assert_ne!(palette, Palette::DARK.name);
+
+        // Another test, running alongside, still sees the defaults.
+        std::thread::spawn(|| {
+            assert_eq!(i18n::locale(), Locale::En);
+            assert_eq!(Theme::palette().name, Palette::DARK.name);
+        })
+        .join()
+        .unwrap();
+        assert_eq!(i18n::locale(), Locale::De);
+        assert_eq!(Theme::palette().name, palette);
+    }
+
+    #[test]
+    fn typing_fills_the_project_path() {
+        let mut harness = Harness::new();
+        assert_eq!(harness.input_mode(), InputMode::Editing);
+        assert_eq!(harness.project_intake().path_input, "~/");
+        harness.run("src".chars().map(Action::CharInput));
+        harness.dispatch(Action::BackspaceInput);
+        assert_eq!(harness.project_intake().path_input, "~/sr");
+    }
+
+    #[test]
+    fn commands_run_as_their_actions() {
+        let mut harness = Harness::new();
+        harness.dispatch(Action::RunCommand(":phase 3".to_string()));
+        assert_eq!(harness.phase(), Phase::TechniqueSelection);
+        assert!(matches!(
+            harness.handled(),
+            [
+                Action::RunCommand(_),
+                Action::GoToPhase(Phase::TechniqueSelection)
+            ]
+        ));
+
+        harness.dispatch(Action::RunCommand("frobnicate".to_string()));
+        assert_eq!(harness.status(), "Unknown command: frobnicate");
+    }
+
+    #[test]
+    fn git_setup_asks_about_uncommitted_changes() {
+        let mut harness = Harness::new();
+        let open = Action::OpenGitSetup {
+            status: RepoStatus::Dirty(vec!["model.py".to_string()]),
+            path: "/work/forecaster".to_string(),
+        };
+        harness.dispatch(open.clone());
+        let screen = harness.render(100, 30);
+        assert!(
+            screen.contains("There are uncommitted changes."),
+            "{}",
+            screen
+        );
+        assert!(screen.contains("model.py"), "{}", screen);
+
+        harness.dispatch(Action::Escape);
+        assert_eq!(harness.status(), "Generation cancelled.");
+        assert!(!harness.app().git_setup.visible);
+
+        harness.dispatch(open);
+        harness.clear_handled();
+        harness.dispatch(Action::CharInput('c'));
+        assert!(matches!(
+            harness.handled(),
+            [Action::CharInput('c'), Action::StartGeneration, ..]
+        ));
+        assert!(!harness.app().git_setup.visible);
+    }
+
+    #[tokio::test]
+    async fn generating_despite_uncommitted_changes_covers_one_run() {
+        let mut harness = ready_to_generate("uncommitted");
+        let path = harness
+            .project_intake()
+            .profile
+            .as_ref()
+            .unwrap()
+            .path
+            .clone();
+        std::fs::write(path.join("model.py"), "wip").unwrap();
+        let mut technique = technique(0);
+        technique.selected = true;
+        harness.technique_cards_mut().techniques = vec![technique];
+        harness.dispatch(Action::GoToPhase(Phase::VariantGeneration));
+
+        harness.dispatch(Action::StartGeneration);
+        assert!(harness.app().git_setup.visible);
+        harness.dispatch(Action::Escape);
+
+        harness.dispatch(Action::GenerateFromLastCommit);
+        assert!(harness.variant_builder().generating);
+        harness.dispatch(Action::VariantGenerationFailed {
+            variant_id: variant_ids(&harness)[0].to_string(),
+            error: "cancelled".to_string(),
+        });
+        assert!(!harness.app().allow_uncommitted);
+        remove_project(&harness);
+    }
+
+    #[tokio::test]
+    async fn merge_previews_arrive_for_the_variants_still_picked() {
+        let mut harness = ready_to_generate("preview");
+        let path = harness
+            .project_intake()
+            .profile
+            .as_ref()
+            .unwrap()
+            .path
+            .clone();
+        let variants: Vec<Variant> = (0..3).map(|i| variant(i, VariantStatus::Ready)).collect();
+        for (v, text) in variants.iter().zip(["fast()\n", "cached()\n", "plain()\n"]) {
+            let created = std::process::Command::new("git")
+                .args(["branch", &v.branch_name])
+                .current_dir(&path)
+                .status()
+                .unwrap();
+            assert!(created.success());
+            git::commit_file(&path, &v.branch_name, "forecast.py", text, "Variant").unwrap();
+        }
+        harness.variant_builder_mut().variants = variants;
+        harness.run([
+            Action::GoToPhase(Phase::VariantGeneration),
+            Action::OpenMergeDialog,
+        ]);
+
+        harness.dispatch(Action::Confirm);
+        harness
+            .wait_for(|a| matches!(a, Action::MergePreviewed { .. }))
+            .await;
+        let preview = harness.merge_dialog().preview.clone().unwrap();
+        assert_eq!(preview.conflicts(), 1);
+        assert_eq!(preview.shared[0].path, "forecast.py");
+
+        // A preview of sources no longer picked is dropped.
+        harness.dispatch(Action::NextPhase);
+        assert!(harness.merge_dialog().preview.is_none());
+        harness.dispatch(Action::MergePreviewed {
+            variant_ids: vec!["variant-1".into(), "variant-2".into()],
+            preview,
+        });
+        assert!(harness.merge_dialog().preview.is_none());
+        remove_project(&harness);
+    }
+
+    #[tokio::test]
+    async fn resolved_conflicts_are_committed_to_the_merge() {
+        let mut harness = ready_to_generate("conflicts");
+        let path = harness
+            .project_intake()
+            .profile
+            .as_ref()
+            .unwrap()
+            .path
+            .clone();
+        let (a, b) = (
+            variant(0, VariantStatus::Ready),
+            variant(1, VariantStatus::Ready),
+        );
+        let spec = MergeSpec::new(
+            a.id.clone(),
+            b.id.clone(),
+            BlendRatio::Half,
+            BlendRatio::Half,
+        );
+        let mut merge = Variant::from_merge(1, spec, "Hybrid".to_string());
+        merge.status = VariantStatus::Ready;
+        merge.merge_conflicts = vec!["forecast.py".to_string()];
+        for (branch, text) in [
+            (&a.branch_name, Some("fast()\n")),
+            (&b.branch_name, Some("cached()\n")),
+            (&merge.branch_name, None),
+        ] {
+            let created = std::process::Command::new("git")
+                .args(["branch", branch])
+                .current_dir(&path)
+                .status()
+                .unwrap();
+            assert!(created.success());
+            if let Some(text) = text {
+                git::commit_file(&path, branch, "forecast.py", text, "Variant").unwrap();
+            }
+        }
+        let branch = merge.branch_name.clone();
+        harness.variant_builder_mut().variants = vec![a, b, merge];
+
+        harness.dispatch(Action::ShowConflicts {
+            variant_id: "merge-1".to_string(),
+        });
+        harness
+            .wait_for(|a| matches!(a, Action::ConflictsLoaded { .. }))
+            .await;
+        assert_eq!(harness.conflict_viewer().files.len(), 1);
+
+        let reconciled = |branch: &str| Action::HunkReconciled {
+            branch: branch.to_string(),
+            path: "forecast.py".to_string(),
+            sources: [0, 1],
+            hunk: 0,
+            lines: vec!["cached_fast()".to_string()],
+        };
+        harness.dispatch(reconciled("uniq/merge-2-other"));
+        assert!(!harness.conflict_viewer().files[0].is_resolved());
+        harness.dispatch(reconciled(&branch));
+        harness.dispatch(Action::Confirm);
+        harness
+            .wait_for(|a| matches!(a, Action::ConflictsResolved { .. }))
+            .await;
+
+        assert!(harness.variant_builder().variants[2]
+            .merge_conflicts
+            .is_empty());
+        let committed = std::process::Command::new("git")
+            .args(["show", &format!("{}:forecast.py", branch)])
+            .current_dir(&path)
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&committed.stdout),
+            "cached_fast()\n"
+        );
+        remove_project(&harness);
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_benchmark_stops_its_commands_first() {
+        let path = std::env::temp_dir().join(format!("uniq-cancel-bench-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        git::commit_baseline(&path, "Baseline").unwrap();
+        let mut config = UniqConfig::default();
+        config.benchmark.native_runner = true;
+        config.benchmark.build_command = Some("sleep 30".to_string());
+        let mut harness = Harness::with_config(config);
+        harness.project_intake_mut().profile = Some(ProjectProfile {
+            path: path.clone(),
+            ..profile()
+        });
+        harness.variant_builder_mut().variants = vec![variant(0, VariantStatus::Ready)];
+        harness.dispatch(Action::GoToPhase(Phase::Benchmarking));
+
+        harness.dispatch(Action::StartBenchmark);
+        assert!(harness.benchmark_dashboard().benchmarking);
+        harness.run([Action::CancelPhaseTask, Action::CancelPhaseTask]);
+        assert_eq!(harness.status(), "Cancelling the benchmark run…");
+        harness.dispatch(Action::StartBenchmark);
+        assert!(harness.benchmark_dashboard().benchmarking);
+
+        let started = Instant::now();
+        harness
+            .wait_for(|a| matches!(a, Action::BenchmarkCancelled))
+            .await;
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(!harness.benchmark_dashboard().benchmarking);
+        assert_eq!(harness.status(), "Cancelled the benchmark run.");
+        let worktrees = std::process::Command::new("git")
+            .args(["worktree", "list"])
+            .current_dir(&path)
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&worktrees.stdout).lines().count(),
+            1
+        );
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn reextractions_replace_their_own_card() {
+        let mut harness = Harness::new();
+        harness.technique_cards_mut().techniques = (0..3).map(technique).collect();
+        harness.dispatch(Action::GoToPhase(Phase::TechniqueSelection));
+        let reextracted = |i: usize| {
+            let mut card = technique(i);
+            card.methodology = "Guided".to_string();
+            Action::TechniqueReExtracted {
+                paper_id: card.paper_id.clone(),
+                name: card.name.clone(),
+                card: Box::new(card),
+            }
+        };
+
+        // The first card went away while the model was working.
+        harness.technique_cards_mut().techniques.remove(0);
+        harness.dispatch(reextracted(2));
+        let methodologies: Vec<_> = harness
+            .technique_cards()
+            .techniques
+            .iter()
+            .map(|t| t.methodology.as_str())
+            .collect();
+        assert_eq!(methodologies[0], technique(1).methodology);
+        assert_eq!(methodologies[1], "Guided");
+
+        harness.dispatch(reextracted(0));
+        assert_eq!(harness.technique_cards().techniques.len(), 2);
+        assert!(harness.status().contains("the card is gone"));
+    }
+
+    #[test]
+    fn duplicates_are_merged_or_dropped_a_group_at_a_time() {
+        let mut harness = Harness::new();
+        // Cards 3 and 4 repeat cards 0 and 1 from other papers.
+        let again = |i: usize, paper: usize| TechniqueCard {
+            name: technique(i).name.to_uppercase(),
+            ..technique(paper)
+        };
+        harness.technique_cards_mut().techniques = vec![
+            technique(0),
+            technique(1),
+            technique(2),
+            again(0, 3),
+            again(1, 4),
+        ];
+        harness.dispatch(Action::GoToPhase(Phase::TechniqueSelection));
+        let names = |harness: &Harness| -> Vec<String> {
+            let techniques = &harness.technique_cards().techniques;
+            techniques.iter().map(|t| t.name.clone()).collect()
+        };
+        let screen = harness.render(100, 30);
+        assert_eq!(screen.matches('≈').count(), 4);
+
+        // Keep the cursor's card of the second group, leaving the first.
+        harness.run([Action::ScrollBottom, Action::DropDuplicates]);
+        let kept = again(1, 4).name;
+        assert_eq!(
+            names(&harness),
+            [technique(0).name, technique(2).name, again(0, 3).name, kept]
+        );
+        assert_eq!(harness.technique_cards().selected, 3);
+
+        harness.run([
+            Action::SelectPrev,
+            Action::SelectPrev,
+            Action::ConsolidateDuplicates,
+        ]);
+        assert_eq!(harness.technique_cards().techniques.len(), 4);
+        assert!(harness.status().contains("no duplicates"));
+
+        harness.run([Action::ScrollTop, Action::ConsolidateDuplicates]);
+        let merged = &harness.technique_cards().techniques[0];
+        assert_eq!(merged.paper_id, technique(0).paper_id);
+        assert_eq!(merged.related_papers[0].id, technique(3).paper_id);
+        assert_eq!(harness.technique_cards().techniques.len(), 3);
+        assert!(!harness.render(100, 30).contains('≈'));
+    }
+
+    #[tokio::test]
+    async fn resumed_sessions_number_past_gaps() {
+        let mut harness = ready_to_generate("resumed");
+        // variant-2 failed last time, so only 1 and 3 were saved.
+        let mut session = Session::new("/work/forecaster");
+        session.techniques = (0..3).map(technique).collect();
+        session.techniques[1].selected = true;
+        session.variants = vec![
+            variant(0, VariantStatus::Ready),
+            variant(2, VariantStatus::Ready),
+        ];
+        harness.app.restore_session(session, &harness.tx);
+
+        harness.dispatch(Action::StartGeneration);
+        assert_eq!(
+            variant_ids(&harness),
+            ["variant-1", "variant-3", "variant-4"]
+        );
+        remove_project(&harness);
+    }
+
+    #[tokio::test]
+    async fn discarded_ids_are_not_reused() {
+        let mut harness = ready_to_generate("discarded");
+        let failed = || VariantStatus::Failed("timed out".to_string());
+        let original = variant(1, VariantStatus::Ready);
+        let revisions = [2, 3].map(|n| {
+            let mut revision = Variant::revise(&original, n, "simpler".to_string()).unwrap();
+            revision.status = failed();
+            revision
+        });
+        let mut technique = technique(0);
+        technique.selected = true;
+        harness.technique_cards_mut().techniques =
+            vec![technique, original.technique().unwrap().clone()];
+        harness.variant_builder_mut().variants = [variant(0, failed()), original]
+            .into_iter()
+            .chain(revisions)
+            .collect();
+
+        harness.run([
+            Action::DiscardVariant {
+                variant_id: "variant-1".to_string(),
+            },
+            Action::DiscardVariant {
+                variant_id: "variant-2-v2".to_string(),
+            },
+            Action::StartGeneration,
+        ]);
+        harness.variant_builder_mut().generating = false;
+        harness.dispatch(Action::RegenerateVariant {
+            variant_id: "variant-2".to_string(),
+            feedback: "smaller".to_string(),
+        });
+        assert_eq!(
+            variant_ids(&harness),
+            ["variant-2", "variant-2-v3", "variant-3", "variant-2-v4"]
+        );
+        remove_project(&harness);
+    }
+
+    #[test]
+    fn the_highlight_follows_its_variant_as_scores_arrive() {
+        let mut harness = Harness::new();
+        let variants: Vec<Variant> = (0..3).map(|i| variant(i, VariantStatus::Ready)).collect();
+        harness.variant_builder_mut().variants = variants.clone();
+        harness.benchmark_dashboard_mut().variants = variants.clone();
+        harness.run([Action::GoToPhase(Phase::Benchmarking), Action::SelectNext]);
+        let selected = |harness: &Harness| {
+            harness
+                .benchmark_dashboard()
+                .selected_variant()
+                .map(|v| v.id.0.clone())
+        };
+        assert_eq!(selected(&harness).as_deref(), Some("variant-2"));
+
+        // Scoring the last variant moves it to the top and the highlighted
+        // one down a row.
+        harness.dispatch(Action::BenchmarkUpdated {
+            branch: variants[2].branch_name.clone(),
+            execution: None,
+            judge: Some(judge(0)),
+        });
+        assert_eq!(selected(&harness).as_deref(), Some("variant-2"));
+        assert_eq!(harness.benchmark_dashboard().selected_row(), 2);
+
+        harness.dispatch(Action::ScrollTop);
+        assert_eq!(selected(&harness).as_deref(), Some("variant-3"));
+    }
+
+    #[test]
+    fn transcripts_scroll_to_their_last_line_and_no_further() {
+        let mut harness = Harness::new();
+        let mut generated = variant(0, VariantStatus::Ready);
+        generated.transcript = Some(GenerationTranscript {
+            log: (0..60).map(|i| format!("step {}", i)).collect(),
+            ..Default::default()
+        });
+        harness.variant_builder_mut().variants = vec![generated];
+        harness.run([Action::GoToPhase(Phase::VariantGeneration), Action::Confirm]);
+        let footer = t!("variants.scroll_footer").to_string();
+        assert!(!harness.render(100, 30).contains(&footer));
+
+        harness.dispatch(Action::ScrollBottom);
+        let screen = harness.render(100, 30);
+        assert!(screen.contains("step 59") && screen.contains(&footer));
+
+        // Scrolling down at the bottom stays there, so one line up leaves it.
+        harness.run([Action::ScrollDown, Action::ScrollDown, Action::ScrollUp]);
+        let screen = harness.render(100, 30);
+        assert!(screen.contains("step 59") && !screen.contains(&footer));
+    }
+
+    #[test]
+    fn reloaded_weights_rescore_variants_and_their_history() {
+        let mut harness = Harness::new();
+        let mut scored = variant(0, VariantStatus::Ready);
+        scored.benchmark_results = Some(BenchmarkResults {
+            execution: Some(execution(0)),
+            judge: Some(judge(5)),
+            ..Default::default()
+        });
+        scored.record_benchmark_run();
+        harness.variant_builder_mut().variants = vec![scored.clone()];
+        harness.benchmark_dashboard_mut().variants = vec![scored];
+
+        harness.app.apply_score_weights(ScoreWeights {
+            execution: 1.0,
+            judge: 0.0,
+            user: 0.0,
+        });
+        let variants = &harness.benchmark_dashboard().variants;
+        let execution_only = Some(30.0 + 1.0 * 70.0);
+        assert_eq!(
+            variants[0]
+                .benchmark_results
+                .as_ref()
+                .unwrap()
+                .composite_score,
+            execution_only
+        );
+        assert_eq!(
+            variants[0].benchmark_history[0].results.composite_score,
+            execution_only
+        );
+        let builder = &harness.variant_builder().variants[0];
+        assert_eq!(
+            builder.benchmark_results.as_ref().unwrap().composite_score,
+            execution_only
+        );
+        assert_eq!(harness.benchmark_dashboard().weights.judge, 0.0);
+    }
+
+    #[test]
+    fn local_models_cost_nothing() {
+        let mut harness = Harness::new();
+        let paid = usage("generation", 2);
+        let local = UsageEntry {
+            provider: LlmProvider::Ollama,
+            ..usage("benchmark", 3)
+        };
+        harness.dispatch(Action::UsageUpdated(vec![paid.clone()]));
+        let cost = harness.status_bar().spend.unwrap().cost_usd;
+        assert!(cost > 0.0);
+
+        harness.dispatch(Action::UsageUpdated(vec![paid, local]));
+        let spend = harness.status_bar().spend.unwrap();
+        assert_eq!(spend.calls, 5);
+        assert_eq!(spend.cost_usd, cost);
+    }
+
+    // The sidecar startup task is spawned but never polled: the test doesn't
+    // await, so no sidecar process is started.
+    #[tokio::test]
+    async fn setup_reports_a_backend_that_rejects_the_key() {
+        let mut harness = Harness::new();
+        harness.app.start_onboarding();
+        harness.run([Action::NewlineInput, Action::NewlineInput]);
+
+        harness.dispatch(Action::SidecarReady {
+            provider: LlmProvider::Anthropic,
+            api_key: Some(true),
+            ping_error: Some("401 Unauthorized".to_string()),
+        });
+        let screen = harness.render(100, 30);
+        assert!(screen.contains("Could not reach Anthropic: 401 Unauthorized"));
+        assert!(!screen.contains("API key works"));
+
+        harness.dispatch(Action::NewlineInput);
+        assert!(!harness.app().onboarding.visible);
+    }
+
+    #[test]
+    fn quit_stops_the_script() {
+        let mut harness = Harness::new();
+        harness.run([Action::Quit, Action::NextPhase]);
+        assert!(harness.should_quit());
+        assert_eq!(harness.phase(), Phase::ProjectIntake);
+    }
+}
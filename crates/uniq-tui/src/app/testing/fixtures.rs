@@ -0,0 +1,135 @@
+//! Fixed sample data for harness tests.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use uniq_core::benchmark::{ExecutionMetrics, JudgeScores};
+use uniq_core::llm::LlmProvider;
+use uniq_core::project::{DetectedFramework, FrameworkCategory, Language, ProjectProfile};
+use uniq_core::research::{Complexity, PaperMeta, PaperSource, TechniqueCard};
+use uniq_core::variant::{Variant, VariantStatus};
+use uniq_sidecar::protocol::UsageEntry;
+
+const TOPICS: [&str; 4] = [
+    "Temporal Fusion Transformers",
+    "Gradient Boosted Quantile Forecasts",
+    "Neural Basis Expansion",
+    "Probabilistic Reconciliation",
+];
+
+fn topic(i: usize) -> &'static str {
+    TOPICS[i % TOPICS.len()]
+}
+
+pub fn paper(i: usize) -> PaperMeta {
+    PaperMeta {
+        id: format!("arxiv:2401.{:05}", i),
+        title: format!("{} for Time Series", topic(i)),
+        authors: vec!["Ada Lovelace".to_string(), "Alan Turing".to_string()],
+        year: Some(2024),
+        published_date: None,
+        abstract_text: format!(
+            "We study {} and show it improves forecast accuracy on standard benchmarks.",
+            topic(i).to_lowercase()
+        ),
+        citation_count: Some(12 * (i as u32 + 1)),
+        url: format!("https://arxiv.org/abs/2401.{:05}", i),
+        pdf_url: None,
+        doi: None,
+        source: PaperSource::ArXiv,
+        fields: vec!["Computer Science".to_string()],
+        relevance_score: Some(0.9 - i as f64 * 0.1),
+    }
+}
+
+pub fn technique(i: usize) -> TechniqueCard {
+    let paper = paper(i);
+    TechniqueCard {
+        name: topic(i).to_string(),
+        paper_id: paper.id,
+        paper_title: paper.title,
+        methodology: format!(
+            "Apply {} to the forecasting model.",
+            topic(i).to_lowercase()
+        ),
+        key_components: vec!["encoder".to_string(), "attention".to_string()],
+        required_data_format: "Tabular time series".to_string(),
+        implementation_complexity: Complexity::Medium,
+        hardware_requirements: "CPU".to_string(),
+        dependencies: vec!["torch".to_string()],
+        relevance_score: 0.9 - i as f64 * 0.1,
+        integration_approach: "Replace the baseline model in forecast.py.".to_string(),
+        selected: i == 0,
+        related_papers: Vec::new(),
+    }
+}
+
+pub fn profile() -> ProjectProfile {
+    ProjectProfile {
+        path: PathBuf::from("/work/forecaster"),
+        user_request: "Improve forecast accuracy".to_string(),
+        summary: "A demand forecasting service.".to_string(),
+        languages: vec![Language::Python],
+        frameworks: vec![DetectedFramework {
+            name: "pandas".to_string(),
+            version: Some("2.2".to_string()),
+            category: FrameworkCategory::DataProcessing,
+        }],
+        file_count: 42,
+        key_files: vec![PathBuf::from("forecast.py")],
+        integration_points: Vec::new(),
+        file_tree: "forecaster/\n  forecast.py\n".to_string(),
+    }
+}
+
+pub fn variant(i: usize, status: VariantStatus) -> Variant {
+    let mut variant = Variant::from_technique(i + 1, technique(i));
+    variant.status = status;
+    variant.modified_files = vec!["forecast.py".to_string()];
+    variant
+}
+
+pub fn execution(i: usize) -> ExecutionMetrics {
+    ExecutionMetrics {
+        build_success: true,
+        build_error: None,
+        test_pass_rate: Some(1.0 - i as f64 * 0.1),
+        tests_passed: Some(10 - i as u32),
+        tests_total: Some(10),
+        runtime_ms: Some(1200.0 + i as f64 * 150.0),
+        memory_mb: Some(256.0),
+        custom_metrics: HashMap::new(),
+        runtime_samples: Vec::new(),
+        memory_samples: Vec::new(),
+        runtime_std_ms: None,
+        memory_std_mb: None,
+        flaky_tests: Vec::new(),
+    }
+}
+
+pub fn judge(i: usize) -> JudgeScores {
+    let score = 8.0 - i as f64;
+    JudgeScores {
+        code_quality: score,
+        novelty: score,
+        feasibility: score,
+        goal_alignment: score,
+        completeness: score,
+        overall: score,
+        explanation: "Clean integration with the existing model.".to_string(),
+        samples: 1,
+        spread: 0.0,
+        disagreement: false,
+    }
+}
+
+pub fn usage(phase: &str, calls: u64) -> UsageEntry {
+    UsageEntry {
+        phase: phase.to_string(),
+        provider: LlmProvider::Anthropic,
+        model: "claude-sonnet-4-20250514".to_string(),
+        calls,
+        input_tokens: calls * 4_000,
+        output_tokens: calls * 1_500,
+    }
+}
@@ -0,0 +1,246 @@
+//! Golden snapshots of each phase empty, loading and populated, to catch
+//! layout regressions.
+//!
+//! Snapshots live in `crates/uniq-tui/snapshots/`. To write new ones or
+//! accept intended changes, re-run with
+//! `UPDATE_SNAPSHOTS=1 cargo test -p uniq-tui snapshots` and review the diff.
+//! A missing snapshot fails the test otherwise, so one that was never
+//! committed can't pass unnoticed.
+
+use std::path::Path;
+
+use uniq_core::benchmark::BenchmarkStage;
+use uniq_core::variant::VariantStatus;
+
+use super::fixtures::{execution, judge, paper, profile, technique, usage, variant};
+use super::Harness;
+use crate::action::{Action, Phase};
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 30;
+
+/// Compare `harness`'s screen against the snapshot called `name`.
+fn assert_snapshot(name: &str, harness: &mut Harness) {
+    let actual = harness.render(WIDTH, HEIGHT);
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(format!("{}.txt", name));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some_and(|v| v == "1") {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("{}\n", actual)).unwrap();
+        return;
+    }
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!(
+            "no snapshot {} at {} (UPDATE_SNAPSHOTS=1 to write it):\n{}",
+            name,
+            path.display(),
+            actual
+        );
+    };
+    let expected = expected.trim_end_matches('\n');
+    if expected == actual {
+        return;
+    }
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+    for row in 0..expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(row), actual.get(row));
+        if old != new {
+            diff.push_str(&format!(
+                "row {:>2} - {}\n       + {}\n",
+                row,
+                old.unwrap_or(&""),
+                new.unwrap_or(&"")
+            ));
+        }
+    }
+    panic!(
+        "snapshot {} changed (UPDATE_SNAPSHOTS=1 to accept):\n{}",
+        name, diff
+    );
+}
+
+/// Phase 1 with the path field cleared. It starts at `~/`, which would
+/// list the home directory.
+fn blank_intake() -> Harness {
+    let mut harness = Harness::new();
+    harness.run([Action::BackspaceInput, Action::BackspaceInput]);
+    harness
+}
+
+fn on_phase(phase: Phase) -> Harness {
+    let mut harness = Harness::new();
+    harness.dispatch(Action::GoToPhase(phase));
+    harness
+}
+
+#[test]
+fn intake_empty() {
+    assert_snapshot("intake_empty", &mut blank_intake());
+}
+
+#[test]
+fn intake_analyzing() {
+    let mut harness = blank_intake();
+    let intake = harness.project_intake_mut();
+    intake.path_input = "/work/forecaster".to_string();
+    intake.description_input = "Improve forecast accuracy".to_string();
+    intake.analyzing = true;
+    assert_snapshot("intake_analyzing", &mut harness);
+}
+
+#[test]
+fn intake_filled() {
+    let mut harness = blank_intake();
+    harness.run("/work/forecaster".chars().map(Action::CharInput));
+    harness.dispatch(Action::SwitchInputField);
+    harness.run("Improve forecast accuracy".chars().map(Action::CharInput));
+    assert_snapshot("intake_filled", &mut harness);
+}
+
+#[test]
+fn intake_analyzed() {
+    let mut harness = blank_intake();
+    let intake = harness.project_intake_mut();
+    intake.path_input = "/work/forecaster".to_string();
+    intake.description_input = "Improve forecast accuracy".to_string();
+    intake.profile = Some(profile());
+    assert_snapshot("intake_analyzed", &mut harness);
+}
+
+#[test]
+fn research_empty() {
+    assert_snapshot("research_empty", &mut on_phase(Phase::ResearchDiscovery));
+}
+
+#[test]
+fn research_searching() {
+    let mut harness = on_phase(Phase::ResearchDiscovery);
+    harness.research_explorer_mut().searching = true;
+    harness.dispatch(Action::SearchQueryStarted {
+        query: "temporal fusion transformer forecasting".to_string(),
+        query_idx: 1,
+        total_queries: 3,
+    });
+    harness.dispatch(Action::PapersFound(vec![paper(0)]));
+    assert_snapshot("research_searching", &mut harness);
+}
+
+#[test]
+fn research_results() {
+    let mut harness = on_phase(Phase::ResearchDiscovery);
+    harness.dispatch(Action::PapersFound((0..4).map(paper).collect()));
+    harness.dispatch(Action::ResearchComplete);
+    assert_snapshot("research_results", &mut harness);
+}
+
+#[test]
+fn techniques_empty() {
+    assert_snapshot("techniques_empty", &mut on_phase(Phase::TechniqueSelection));
+}
+
+#[test]
+fn techniques_extracting() {
+    let mut harness = on_phase(Phase::TechniqueSelection);
+    let cards = harness.technique_cards_mut();
+    cards.extracting = true;
+    cards.progress = (1, 4);
+    cards.active_papers = vec![paper(1).title];
+    cards.techniques = vec![technique(0)];
+    assert_snapshot("techniques_extracting", &mut harness);
+}
+
+#[test]
+fn techniques_cards() {
+    let mut harness = on_phase(Phase::TechniqueSelection);
+    let cards = harness.technique_cards_mut();
+    cards.extraction_attempted = true;
+    cards.profile = Some(profile());
+    cards.techniques = (0..4).map(technique).collect();
+    assert_snapshot("techniques_cards", &mut harness);
+}
+
+#[test]
+fn variants_empty() {
+    assert_snapshot("variants_empty", &mut on_phase(Phase::VariantGeneration));
+}
+
+#[test]
+fn variants_generating() {
+    let mut harness = on_phase(Phase::VariantGeneration);
+    let builder = harness.variant_builder_mut();
+    builder.generating = true;
+    builder.variants = vec![
+        variant(0, VariantStatus::Ready),
+        variant(1, VariantStatus::Generating),
+        variant(2, VariantStatus::Pending),
+    ];
+    assert_snapshot("variants_generating", &mut harness);
+}
+
+#[test]
+fn variants_ready() {
+    let mut harness = on_phase(Phase::VariantGeneration);
+    harness.variant_builder_mut().variants = vec![
+        variant(0, VariantStatus::Ready),
+        variant(1, VariantStatus::Ready),
+        variant(2, VariantStatus::Failed("Build failed".to_string())),
+    ];
+    assert_snapshot("variants_ready", &mut harness);
+}
+
+#[test]
+fn benchmark_empty() {
+    assert_snapshot("benchmark_empty", &mut on_phase(Phase::Benchmarking));
+}
+
+#[test]
+fn benchmark_running() {
+    let mut harness = on_phase(Phase::Benchmarking);
+    let variants: Vec<_> = (0..3).map(|i| variant(i, VariantStatus::Ready)).collect();
+    let dashboard = harness.benchmark_dashboard_mut();
+    dashboard.benchmarking = true;
+    for (v, stage) in variants.iter().zip([
+        BenchmarkStage::Done,
+        BenchmarkStage::Testing,
+        BenchmarkStage::Queued,
+    ]) {
+        dashboard.stages.insert(v.branch_name.clone(), stage);
+    }
+    dashboard.variants = variants;
+    assert_snapshot("benchmark_running", &mut harness);
+}
+
+#[test]
+fn benchmark_results() {
+    let mut harness = on_phase(Phase::Benchmarking);
+    let variants: Vec<_> = (0..3).map(|i| variant(i, VariantStatus::Ready)).collect();
+    for v in &variants {
+        harness
+            .benchmark_dashboard_mut()
+            .stages
+            .insert(v.branch_name.clone(), BenchmarkStage::Done);
+    }
+    harness.benchmark_dashboard_mut().variants = variants.clone();
+    for (i, v) in variants.iter().enumerate() {
+        harness.dispatch(Action::BenchmarkUpdated {
+            branch: v.branch_name.clone(),
+            execution: Some(Box::new(execution(i))),
+            judge: Some(judge(i)),
+        });
+    }
+    assert_snapshot("benchmark_results", &mut harness);
+}
+
+#[test]
+fn usage_breakdown() {
+    let mut harness = on_phase(Phase::TechniqueSelection);
+    harness.dispatch(Action::UsageUpdated(vec![
+        usage("intake", 1),
+        usage("techniques", 6),
+    ]));
+    harness.dispatch(Action::ToggleUsage);
+    assert_snapshot("usage_breakdown", &mut harness);
+}
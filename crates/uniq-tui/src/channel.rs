@@ -0,0 +1,332 @@
+//! The channel actions reach the main loop on.
+//!
+//! It holds at most [`CAPACITY`] actions, so a burst of results from
+//! background tasks can't pile up without limit or bury key presses behind
+//! thousands of updates: once it's full, [`ActionSender::send`] waits for the
+//! main loop to catch up. Actions that only matter in their latest form
+//! don't take up room: a `Tick` already waiting makes another one pointless,
+//! and so does a `SetStatus` right behind the same text. A different status
+//! is always queued, since each one goes into the status log.
+//!
+//! The main loop can't wait on itself, so actions it sends while handling
+//! one go in with [`ActionSender::send_now`] whether there's room or not.
+
+use std::collections::VecDeque;
+use std::pin::pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tokio::sync::Notify;
+
+use crate::action::Action;
+
+/// How many actions may wait before senders have to.
+pub const CAPACITY: usize = 256;
+
+/// A channel for [`CAPACITY`] actions.
+pub fn action_channel() -> (ActionSender, ActionReceiver) {
+    bounded(CAPACITY)
+}
+
+/// A channel for `capacity` actions.
+pub fn bounded(capacity: usize) -> (ActionSender, ActionReceiver) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            capacity: capacity.max(1),
+            tick_queued: false,
+            senders: 1,
+            closed: false,
+        }),
+        queued: Notify::new(),
+        room: Notify::new(),
+    });
+    (
+        ActionSender {
+            shared: shared.clone(),
+        },
+        ActionReceiver { shared },
+    )
+}
+
+struct Shared {
+    state: Mutex<State>,
+    /// Woken when an action is queued or the last sender goes away.
+    queued: Notify,
+    /// Woken when an action is taken off a full queue or the receiver goes
+    /// away.
+    room: Notify,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+struct State {
+    queue: VecDeque<Action>,
+    capacity: usize,
+    tick_queued: bool,
+    senders: usize,
+    /// The receiver is gone.
+    closed: bool,
+}
+
+/// What became of an action offered to the queue.
+enum Offer {
+    /// Queued, or made unnecessary by one already waiting.
+    Taken,
+    /// The queue is full.
+    Full(Action),
+}
+
+impl State {
+    fn offer(&mut self, action: Action) -> Offer {
+        match action {
+            Action::Tick if self.tick_queued => return Offer::Taken,
+            Action::SetStatus(ref text) => {
+                if let Some(Action::SetStatus(queued)) = self.queue.back() {
+                    if queued == text {
+                        return Offer::Taken;
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.push(action)
+    }
+
+    fn push(&mut self, action: Action) -> Offer {
+        if self.queue.len() >= self.capacity {
+            // Another tick comes soon enough.
+            if matches!(action, Action::Tick) {
+                return Offer::Taken;
+            }
+            return Offer::Full(action);
+        }
+        self.force(action);
+        Offer::Taken
+    }
+
+    /// Queue `action` whether there's room or not.
+    fn force(&mut self, action: Action) {
+        if matches!(action, Action::Tick) {
+            self.tick_queued = true;
+        }
+        self.queue.push_back(action);
+    }
+}
+
+/// The receiver is gone, so nothing will handle the action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Closed;
+
+/// The sending half of an [`action_channel`].
+pub struct ActionSender {
+    shared: Arc<Shared>,
+}
+
+impl ActionSender {
+    /// Queue `action`, waiting for room if the channel is full. Fails only
+    /// if the receiver is gone.
+    pub async fn send(&self, action: Action) -> Result<(), Closed> {
+        let mut action = action;
+        loop {
+            // Registered before looking, so room made in between isn't missed.
+            let mut room = pin!(self.shared.room.notified());
+            room.as_mut().enable();
+            {
+                let mut state = self.shared.lock();
+                if state.closed {
+                    return Err(Closed);
+                }
+                match state.offer(action) {
+                    Offer::Taken => {
+                        drop(state);
+                        self.shared.queued.notify_one();
+                        return Ok(());
+                    }
+                    Offer::Full(returned) => action = returned,
+                }
+            }
+            room.await;
+        }
+    }
+
+    /// [`ActionSender::send`] for threads outside the runtime, such as
+    /// `spawn_blocking` tasks.
+    ///
+    /// # Panics
+    ///
+    /// If called from async code.
+    pub fn blocking_send(&self, action: Action) -> Result<(), Closed> {
+        tokio::runtime::Handle::current().block_on(self.send(action))
+    }
+
+    /// Queue `action` without waiting, even if the channel is full. For the
+    /// main loop, which would otherwise wait for itself.
+    pub fn send_now(&self, action: Action) -> Result<(), Closed> {
+        let mut state = self.shared.lock();
+        if state.closed {
+            return Err(Closed);
+        }
+        if let Offer::Full(action) = state.offer(action) {
+            state.force(action);
+        }
+        drop(state);
+        self.shared.queued.notify_one();
+        Ok(())
+    }
+}
+
+impl Clone for ActionSender {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl Drop for ActionSender {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            self.shared.queued.notify_one();
+        }
+    }
+}
+
+/// The receiving half of an [`action_channel`].
+pub struct ActionReceiver {
+    shared: Arc<Shared>,
+}
+
+impl ActionReceiver {
+    /// The next action, waiting for one if none is queued. `None` once
+    /// every sender is gone and the queue is empty.
+    pub async fn recv(&mut self) -> Option<Action> {
+        loop {
+            let shared = self.shared.clone();
+            let mut queued = pin!(shared.queued.notified());
+            queued.as_mut().enable();
+            if let Some(action) = self.try_recv() {
+                return Some(action);
+            }
+            if self.shared.lock().senders == 0 {
+                return None;
+            }
+            queued.await;
+        }
+    }
+
+    /// The next action if one is queued.
+    pub fn try_recv(&mut self) -> Option<Action> {
+        let mut state = self.shared.lock();
+        let action = state.queue.pop_front()?;
+        if matches!(action, Action::Tick) {
+            state.tick_queued = false;
+        }
+        let had_room = state.queue.len() + 1 < state.capacity;
+        drop(state);
+        if !had_room {
+            self.shared.room.notify_waiters();
+        }
+        Some(action)
+    }
+}
+
+impl Drop for ActionReceiver {
+    fn drop(&mut self) {
+        self.shared.lock().closed = true;
+        self.shared.room.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// What's queued, as text since actions can't be compared.
+    fn drain(rx: &mut ActionReceiver) -> String {
+        let actions: Vec<Action> = std::iter::from_fn(|| rx.try_recv()).collect();
+        format!("{:?}", actions)
+    }
+
+    fn recv(action: Option<Action>) -> String {
+        format!("{:?}", action)
+    }
+
+    #[tokio::test]
+    async fn coalesces_ticks_and_repeated_statuses() {
+        let (tx, mut rx) = bounded(8);
+        tx.send(Action::Tick).await.unwrap();
+        tx.send(Action::SetStatus("a".into())).await.unwrap();
+        tx.send(Action::SetStatus("a".into())).await.unwrap();
+        tx.send(Action::Tick).await.unwrap();
+        tx.send(Action::Redraw).await.unwrap();
+        tx.send_now(Action::SetStatus("c".into())).unwrap();
+        assert_eq!(
+            drain(&mut rx),
+            r#"[Tick, SetStatus("a"), Redraw, SetStatus("c")]"#
+        );
+        // Once the queued tick is taken, the next one goes in.
+        tx.send(Action::Tick).await.unwrap();
+        assert_eq!(drain(&mut rx), "[Tick]");
+    }
+
+    #[tokio::test]
+    async fn keeps_every_distinct_status() {
+        let (tx, mut rx) = bounded(8);
+        tx.send(Action::SetStatus("Generating 2 variants".into()))
+            .await
+            .unwrap();
+        tx.send(Action::SetStatus("variant-1 failed".into()))
+            .await
+            .unwrap();
+        assert_eq!(
+            drain(&mut rx),
+            r#"[SetStatus("Generating 2 variants"), SetStatus("variant-1 failed")]"#
+        );
+    }
+
+    #[tokio::test]
+    async fn send_waits_for_room() {
+        let (tx, mut rx) = bounded(2);
+        tx.send(Action::Redraw).await.unwrap();
+        tx.send(Action::ClearStatus).await.unwrap();
+        // Ticks are dropped rather than waiting; the main loop may go over.
+        tx.send(Action::Tick).await.unwrap();
+        tx.send_now(Action::Quit).unwrap();
+
+        let sender = tx.clone();
+        let waiting = tokio::spawn(async move { sender.send(Action::Redraw).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        assert_eq!(recv(rx.recv().await), "Some(Redraw)");
+        assert_eq!(recv(rx.recv().await), "Some(ClearStatus)");
+        assert_eq!(recv(rx.recv().await), "Some(Quit)");
+        waiting.await.unwrap().unwrap();
+        assert_eq!(recv(rx.recv().await), "Some(Redraw)");
+    }
+
+    #[tokio::test]
+    async fn ends_when_either_side_goes() {
+        let (tx, mut rx) = bounded(2);
+        let other = tx.clone();
+        tx.send(Action::Redraw).await.unwrap();
+        drop(tx);
+        drop(other);
+        assert_eq!(recv(rx.recv().await), "Some(Redraw)");
+        assert!(rx.recv().await.is_none());
+
+        let (tx, rx) = bounded(2);
+        drop(rx);
+        assert!(tx.send(Action::Redraw).await.is_err());
+        assert!(tx.send_now(Action::Redraw).is_err());
+    }
+}
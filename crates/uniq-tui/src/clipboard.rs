@@ -0,0 +1,70 @@
+//! System clipboard access through the platform's copy command, falling back
+//! to the OSC 52 escape sequence so copying also works over SSH.
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Copy commands to try, in order.
+const COPY_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+/// Put `text` on the clipboard. This waits on the copy command, so call
+/// it off the UI thread.
+pub fn copy(text: &str) -> io::Result<()> {
+    for (program, args) in COPY_COMMANDS {
+        if pipe_to(program, args, text).is_ok() {
+            return Ok(());
+        }
+    }
+    copy_osc52(text)
+}
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> io::Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    if child.wait()?.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{} failed", program)))
+    }
+}
+
+/// Ask the terminal itself to set the clipboard. The sequence is written
+/// in one locked write, so it can't land inside one the UI is drawing.
+fn copy_osc52(text: &str) -> io::Result<()> {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
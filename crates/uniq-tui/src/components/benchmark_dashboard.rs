@@ -1,62 +1,943 @@
 //! Phase 5: Benchmark Dashboard — compare variants with multiple metrics.
 
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Row, Table, Wrap};
+use ratatui::widgets::{
+    Bar, BarChart, BarGroup, Block, Borders, Cell, Paragraph, Row, Table, Wrap,
+};
 use ratatui::Frame;
 
-use crate::action::Action;
-use crate::components::Component;
+use std::collections::HashMap;
+
+use crate::action::{Action, PromptKind};
+use crate::components::{
+    bracket_hints, find_next, no_match, resize_pane, truncate, Component, Focus, TextScroll, PAGE,
+};
 use crate::theme::Theme;
 
-use uniq_core::variant::Variant;
+use uniq_core::benchmark::{
+    BenchmarkResults, BenchmarkStage, ExecutionMetrics, JudgeScores, PairwiseComparison,
+    PairwiseWinner, ScoreWeights,
+};
+use uniq_core::config::PaneSizes;
+use uniq_core::leaderboard::Leaderboard;
+use uniq_core::merge::MergeSuggestion;
+use uniq_core::variant::{FileOverlap, Variant, VariantId, VariantStatus};
+
+/// Column the score table is sorted by. Each sorts best-first by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Composite,
+    Quality,
+    Novelty,
+    Tests,
+    Runtime,
+    Elo,
+}
+
+impl SortColumn {
+    fn next(self) -> Self {
+        match self {
+            SortColumn::Composite => SortColumn::Quality,
+            SortColumn::Quality => SortColumn::Novelty,
+            SortColumn::Novelty => SortColumn::Tests,
+            SortColumn::Tests => SortColumn::Runtime,
+            SortColumn::Runtime => SortColumn::Elo,
+            SortColumn::Elo => SortColumn::Composite,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SortColumn::Composite => t!("bench.sort_score"),
+            SortColumn::Quality => t!("bench.sort_quality"),
+            SortColumn::Novelty => t!("bench.sort_novelty"),
+            SortColumn::Tests => t!("bench.sort_tests"),
+            SortColumn::Runtime => t!("bench.sort_runtime"),
+            SortColumn::Elo => t!("bench.sort_elo"),
+        }
+    }
+
+    /// The variant's value in this column, oriented so higher is better.
+    fn key(self, variant: &Variant) -> Option<f64> {
+        let results = variant.benchmark_results.as_ref()?;
+        match self {
+            SortColumn::Composite => results.composite_score,
+            SortColumn::Quality => results.judge.as_ref().map(|j| j.code_quality),
+            SortColumn::Novelty => results.judge.as_ref().map(|j| j.novelty),
+            SortColumn::Tests => results.execution.as_ref()?.test_pass_rate,
+            SortColumn::Runtime => results.execution.as_ref()?.runtime_ms.map(|ms| -ms),
+            SortColumn::Elo => results.elo,
+        }
+    }
+}
+
+/// Two variants shown side by side for a final decision.
+struct Comparison {
+    a: String,
+    b: String,
+    overlap: FileOverlap,
+    diff: String,
+}
 
 pub struct BenchmarkDashboardComponent {
     /// Reference to all variants (shared with VariantBuilder).
     pub variants: Vec<Variant>,
-    /// Currently selected variant.
-    pub selected: usize,
+    /// Variant in the highlighted row. Kept by id so the highlight follows
+    /// the variant when scores arrive and the rows reorder.
+    selected: Option<VariantId>,
+    /// Column the table is sorted by.
+    sort: SortColumn,
+    /// Whether the sort is reversed (worst first).
+    sort_reversed: bool,
     /// Whether benchmarking is in progress.
     pub benchmarking: bool,
+    /// Stage of each variant in the current run, keyed by branch name.
+    pub stages: HashMap<String, BenchmarkStage>,
+    /// Branch the unmodified project was benchmarked from.
+    pub baseline_branch: Option<String>,
+    /// Execution metrics of the unmodified project.
+    pub baseline: Option<ExecutionMetrics>,
+    /// Whether the detail panel lists earlier runs instead of details.
+    showing_history: bool,
+    /// Whether the judge's full explanation replaces the table.
+    showing_explanation: bool,
+    explanation_scroll: TextScroll,
+    /// Variant marked with `c`, waiting for a second to compare against.
+    compare_mark: Option<String>,
+    /// Side-by-side comparison of two variants, when open.
+    comparison: Option<Comparison>,
+    comparison_scroll: TextScroll,
+    /// Every variant benchmarked for this project, across sessions.
+    pub leaderboard: Option<Leaderboard>,
+    /// Whether the leaderboard replaces the table.
+    showing_leaderboard: bool,
+    leaderboard_scroll: TextScroll,
+    /// Composite score weights, for the score breakdown.
+    pub weights: ScoreWeights,
+    /// Verdicts from the latest pairwise tournament.
+    pub comparisons: Vec<PairwiseComparison>,
+    /// Merges worth trying, strongest first.
+    pub suggestions: Vec<MergeSuggestion>,
+    /// Height of the detail panel, in lines.
+    pub detail_height: u16,
+    /// Whether the score table or the detail panel takes navigation keys.
+    focus: Focus,
+    /// Scroll position within the detail panel.
+    detail_scroll: TextScroll,
 }
 
 impl BenchmarkDashboardComponent {
     pub fn new() -> Self {
         Self {
             variants: Vec::new(),
-            selected: 0,
+            selected: None,
+            sort: SortColumn::Composite,
+            sort_reversed: false,
             benchmarking: false,
+            stages: HashMap::new(),
+            baseline_branch: None,
+            baseline: None,
+            showing_history: false,
+            showing_explanation: false,
+            explanation_scroll: TextScroll::default(),
+            compare_mark: None,
+            comparison: None,
+            comparison_scroll: TextScroll::default(),
+            leaderboard: None,
+            showing_leaderboard: false,
+            leaderboard_scroll: TextScroll::default(),
+            weights: ScoreWeights::default(),
+            comparisons: Vec::new(),
+            suggestions: Vec::new(),
+            detail_height: PaneSizes::default().benchmark_detail,
+            focus: Focus::default(),
+            detail_scroll: TextScroll::default(),
         }
     }
+
+    /// Strongest suggested merge involving `variant`.
+    fn suggestion_for(&self, variant: &Variant) -> Option<&MergeSuggestion> {
+        self.suggestions
+            .iter()
+            .find(|s| s.sources.iter().any(|src| src.variant == variant.id))
+    }
+
+    /// Indices into `variants` in table order. Variants without a value
+    /// in the sort column always go last.
+    fn sorted_indices(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.variants.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (ka, kb) = (
+                self.sort.key(&self.variants[a]),
+                self.sort.key(&self.variants[b]),
+            );
+            match (ka, kb) {
+                (Some(ka), Some(kb)) => {
+                    let best_first = kb.total_cmp(&ka);
+                    if self.sort_reversed {
+                        best_first.reverse()
+                    } else {
+                        best_first
+                    }
+                }
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        });
+        order
+    }
+
+    /// Position of the highlighted variant in the current table order,
+    /// or the top row if it's gone.
+    pub fn selected_row(&self) -> usize {
+        self.sorted_indices()
+            .iter()
+            .position(|&i| Some(&self.variants[i].id) == self.selected.as_ref())
+            .unwrap_or(0)
+    }
+
+    /// Highlight the variant at `row` of the current table order,
+    /// clamped to the last row.
+    pub fn select_row(&mut self, row: usize) {
+        let order = self.sorted_indices();
+        self.selected = order
+            .get(row.min(order.len().saturating_sub(1)))
+            .map(|&i| self.variants[i].id.clone());
+    }
+
+    /// The variant in the highlighted row.
+    pub fn selected_variant(&self) -> Option<&Variant> {
+        let index = *self.sorted_indices().get(self.selected_row())?;
+        self.variants.get(index)
+    }
+
+    /// Change the sort, keeping the same variant highlighted.
+    fn set_sort(&mut self, column: SortColumn, reversed: bool) {
+        self.selected = self.selected_variant().map(|v| v.id.clone());
+        self.sort = column;
+        self.sort_reversed = reversed;
+    }
+
+    /// Summary of the unmodified project's metrics that deltas are
+    /// measured against.
+    fn baseline_line(&self) -> Line<'static> {
+        let name = self.baseline_branch.as_deref().unwrap_or(t!("bench.base"));
+        let Some(ref base) = self.baseline else {
+            let state = match self
+                .baseline_branch
+                .as_ref()
+                .and_then(|b| self.stages.get(b))
+            {
+                Some(stage) if stage.is_active() => stage_label(*stage).to_lowercase(),
+                _ => t!("bench.not_measured").to_string(),
+            };
+            return Line::from(Span::styled(
+                t!("bench.baseline_state", name = name, state = state),
+                Theme::dim(),
+            ));
+        };
+        let mut parts = Vec::new();
+        if let Some(runtime) = base.runtime_ms {
+            parts.push(format!("{:.0} ms", runtime));
+        }
+        if let Some(memory) = base.memory_mb {
+            parts.push(format!("{:.1} MB", memory));
+        }
+        if let Some(rate) = base.test_pass_rate {
+            parts.push(t!(
+                "bench.tests_percent",
+                percent = format!("{:.0}", rate * 100.0)
+            ));
+        }
+        if !base.build_success {
+            parts.push(t!("bench.build_fails").to_string());
+        }
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", t!("bench.baseline", name = name)),
+                Theme::header(),
+            ),
+            Span::styled(parts.join(" · "), Theme::dim()),
+        ])
+    }
+
+    /// Live stage during a run, or whether results exist otherwise.
+    fn status_cell(&self, variant: &Variant) -> Cell<'static> {
+        match self.stages.get(&variant.branch_name) {
+            Some(stage) => {
+                let style = match stage {
+                    BenchmarkStage::Done => Style::default().fg(Theme::success()),
+                    BenchmarkStage::Failed => Style::default().fg(Theme::error()),
+                    BenchmarkStage::Queued => Theme::dim(),
+                    _ => Style::default().fg(Theme::warning()),
+                };
+                Cell::from(Span::styled(stage_label(*stage), style))
+            }
+            None if variant.benchmark_results.is_some() => {
+                Cell::from(stage_label(BenchmarkStage::Done))
+            }
+            None => Cell::from(Span::styled("—", Theme::dim())),
+        }
+    }
+
+    /// Variants from every session ranked by composite score. Ones from
+    /// the current session are marked.
+    fn render_leaderboard(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(format!(" {} ", t!("bench.leaderboard")))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let entries = self
+            .leaderboard
+            .as_ref()
+            .map(|l| l.entries.as_slice())
+            .unwrap_or_default();
+        let mut lines = Vec::new();
+        if entries.is_empty() {
+            lines.push(Line::from(Span::styled(
+                t!("bench.leaderboard_empty"),
+                Theme::dim(),
+            )));
+        }
+        for (rank, entry) in entries.iter().enumerate() {
+            let current = self
+                .variants
+                .iter()
+                .any(|v| entry.variant_id.as_ref() == Some(&v.id));
+            let style = if current {
+                Theme::normal()
+            } else {
+                Theme::muted()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>3}. ", rank + 1), Theme::dim()),
+                Span::styled(format!("{:>5.1}  ", entry.composite_score), Theme::header()),
+                Span::styled(truncate(&entry.display_name, 40), style),
+                Span::styled(
+                    format!(
+                        "  {}  {}{}",
+                        entry.branch_name,
+                        entry.benchmarked_at.format("%Y-%m-%d"),
+                        if current {
+                            format!("  ({})", t!("bench.this_session"))
+                        } else {
+                            String::new()
+                        }
+                    ),
+                    Theme::dim(),
+                ),
+            ]));
+            if let Some(ref technique) = entry.technique {
+                lines.push(Line::from(Span::styled(
+                    format!("              {}", technique),
+                    Theme::dim(),
+                )));
+            }
+        }
+
+        self.leaderboard_scroll
+            .render(frame, Paragraph::new(lines).block(block), area);
+    }
+
+    /// Two variants' metrics side by side, the better value of each row
+    /// highlighted, followed by how their changes overlap.
+    fn render_comparison(&self, frame: &mut Frame, area: Rect, comparison: &Comparison) {
+        let find = |id: &str| self.variants.iter().find(|v| v.id.0 == id);
+        let (Some(a), Some(b)) = (find(&comparison.a), find(&comparison.b)) else {
+            return;
+        };
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t!(
+                    "variants.compare_title",
+                    a = a.display_name,
+                    b = b.display_name
+                )
+            ))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let results = |v: &Variant| v.benchmark_results.clone().unwrap_or_default();
+        let (ra, rb) = (results(a), results(b));
+        let exec = |r: &BenchmarkResults, f: fn(&ExecutionMetrics) -> Option<f64>| {
+            r.execution.as_ref().and_then(f)
+        };
+        let judge = |r: &BenchmarkResults, f: fn(&JudgeScores) -> f64| r.judge.as_ref().map(f);
+
+        type Format = fn(f64) -> String;
+        // Label, the two values, how to show them and whether higher wins.
+        type CompareRow<'a> = (&'a str, Option<f64>, Option<f64>, Format, bool);
+        let percent: Format = |v| format!("{:.0}%", v * 100.0);
+        let ms: Format = |v| format!("{:.0} ms", v);
+        let mb: Format = |v| format!("{:.1} MB", v);
+        let score: Format = |v| format!("{:.1}", v);
+        let whole: Format = |v| format!("{:.0}", v);
+        let build =
+            |r: &BenchmarkResults| exec(r, |e| Some(if e.build_success { 1.0 } else { 0.0 }));
+        let rows: [CompareRow; 13] = [
+            (
+                t!("bench.build"),
+                build(&ra),
+                build(&rb),
+                |v| {
+                    if v > 0.0 {
+                        t!("bench.pass")
+                    } else {
+                        t!("bench.fail")
+                    }
+                    .to_string()
+                },
+                true,
+            ),
+            (
+                t!("bench.tests"),
+                exec(&ra, |e| e.test_pass_rate),
+                exec(&rb, |e| e.test_pass_rate),
+                percent,
+                true,
+            ),
+            (
+                t!("bench.runtime"),
+                exec(&ra, |e| e.runtime_ms),
+                exec(&rb, |e| e.runtime_ms),
+                ms,
+                false,
+            ),
+            (
+                t!("bench.memory"),
+                exec(&ra, |e| e.memory_mb),
+                exec(&rb, |e| e.memory_mb),
+                mb,
+                false,
+            ),
+            (
+                t!("bench.code_quality"),
+                judge(&ra, |j| j.code_quality),
+                judge(&rb, |j| j.code_quality),
+                score,
+                true,
+            ),
+            (
+                t!("bench.novelty"),
+                judge(&ra, |j| j.novelty),
+                judge(&rb, |j| j.novelty),
+                score,
+                true,
+            ),
+            (
+                t!("bench.feasibility"),
+                judge(&ra, |j| j.feasibility),
+                judge(&rb, |j| j.feasibility),
+                score,
+                true,
+            ),
+            (
+                t!("bench.goal_alignment"),
+                judge(&ra, |j| j.goal_alignment),
+                judge(&rb, |j| j.goal_alignment),
+                score,
+                true,
+            ),
+            (
+                t!("bench.completeness"),
+                judge(&ra, |j| j.completeness),
+                judge(&rb, |j| j.completeness),
+                score,
+                true,
+            ),
+            (
+                t!("bench.judge_overall"),
+                judge(&ra, |j| j.overall),
+                judge(&rb, |j| j.overall),
+                score,
+                true,
+            ),
+            (
+                t!("bench.your_rating"),
+                ra.user_rating.as_ref().map(|u| u.stars as f64),
+                rb.user_rating.as_ref().map(|u| u.stars as f64),
+                whole,
+                true,
+            ),
+            (t!("bench.elo"), ra.elo, rb.elo, whole, true),
+            (
+                t!("bench.composite"),
+                ra.composite_score,
+                rb.composite_score,
+                score,
+                true,
+            ),
+        ];
+
+        let name_width = 22;
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("{:<16}", ""), Theme::header()),
+            Span::styled(
+                format!("{:<name_width$}", truncate(&a.display_name, name_width - 2)),
+                Theme::header(),
+            ),
+            Span::styled(truncate(&b.display_name, name_width - 2), Theme::header()),
+        ])];
+        let (mut wins_a, mut wins_b) = (0, 0);
+        for (label, va, vb, format, higher_is_better) in rows {
+            if va.is_none() && vb.is_none() {
+                continue;
+            }
+            let winner = match (va, vb) {
+                (Some(x), Some(y)) if x != y => Some((x > y) == higher_is_better),
+                (Some(_), None) => Some(true),
+                (None, Some(_)) => Some(false),
+                _ => None,
+            };
+            match winner {
+                Some(true) => wins_a += 1,
+                Some(false) => wins_b += 1,
+                None => {}
+            }
+            let cell = |value: Option<f64>, wins: bool| {
+                let text = value.map(format).unwrap_or_else(|| "—".to_string());
+                let style = if wins {
+                    Style::default().fg(Theme::success())
+                } else {
+                    Theme::normal()
+                };
+                let marker = if wins { " ✓" } else { "" };
+                Span::styled(
+                    format!("{:<name_width$}", format!("{}{}", text, marker)),
+                    style,
+                )
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<16}", label), Theme::header()),
+                cell(va, winner == Some(true)),
+                cell(vb, winner == Some(false)),
+            ]));
+        }
+        lines.push(Line::from(Span::styled(
+            t!(
+                "bench.wins",
+                a = a.display_name,
+                wins_a = wins_a,
+                b = b.display_name,
+                wins_b = wins_b
+            ),
+            Theme::dim(),
+        )));
+
+        // Diff of diffs: where the two implementations' changes differ.
+        let overlap = &comparison.overlap;
+        let (added, removed) = comparison.diff.lines().fold((0, 0), |(add, del), line| {
+            if line.starts_with('+') && !line.starts_with("+++") {
+                (add + 1, del)
+            } else if line.starts_with('-') && !line.starts_with("---") {
+                (add, del + 1)
+            } else {
+                (add, del)
+            }
+        });
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!("bench.changes"),
+            Theme::header(),
+        )));
+        lines.push(Line::from(Span::styled(
+            t!(
+                "bench.overlap",
+                only_a = overlap.only_a.len(),
+                a = a.display_name,
+                only_b = overlap.only_b.len(),
+                b = b.display_name,
+                both = overlap.both.len(),
+                added = added,
+                removed = removed
+            ),
+            Theme::normal(),
+        )));
+        for line in comparison.diff.lines() {
+            let style = if line.starts_with("+++") || line.starts_with("---") {
+                Theme::header()
+            } else if line.starts_with('+') {
+                Style::default().fg(Theme::success())
+            } else if line.starts_with('-') {
+                Style::default().fg(Theme::error())
+            } else if line.starts_with("@@") {
+                Style::default().fg(Theme::accent())
+            } else {
+                Theme::dim()
+            };
+            lines.push(Line::from(Span::styled(line.to_string(), style)));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!("variants.scroll_footer"),
+            Theme::dim(),
+        )));
+
+        self.comparison_scroll
+            .render(frame, Paragraph::new(lines).block(block), area);
+    }
+
+    /// The judge's scores and full explanation for `variant`, plus its
+    /// pairwise verdicts, scrollable.
+    fn render_explanation(&self, frame: &mut Frame, area: Rect, variant: &Variant) {
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t!("bench.judge_title", name = variant.display_name)
+            ))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let mut lines = Vec::new();
+        let results = variant.benchmark_results.as_ref();
+        if let Some(judge) = results.and_then(|br| br.judge.as_ref()) {
+            let scores = [
+                (t!("bench.code_quality"), judge.code_quality),
+                (t!("bench.novelty"), judge.novelty),
+                (t!("bench.feasibility"), judge.feasibility),
+                (t!("bench.goal_alignment"), judge.goal_alignment),
+                (t!("bench.completeness"), judge.completeness),
+                (t!("bench.overall"), judge.overall),
+            ];
+            for (name, score) in scores {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:<16}", name), Theme::header()),
+                    Span::styled(format!("{:.1}/10", score), Theme::normal()),
+                ]));
+            }
+            if judge.samples > 1 {
+                lines.push(Line::from(Span::styled(
+                    t!(
+                        "bench.samples_spread",
+                        samples = judge.samples,
+                        spread = format!("{:.1}", judge.spread)
+                    ),
+                    Theme::dim(),
+                )));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                t!("variants.explanation"),
+                Theme::header(),
+            )));
+            lines.push(Line::from(Span::styled(
+                judge.explanation.clone(),
+                Theme::normal(),
+            )));
+        }
+
+        let verdicts: Vec<_> = self
+            .comparisons
+            .iter()
+            .filter(|c| c.branch_a == variant.branch_name || c.branch_b == variant.branch_name)
+            .collect();
+        if !verdicts.is_empty() {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                t!("bench.pairwise"),
+                Theme::header(),
+            )));
+            for c in verdicts {
+                let (other, side) = if c.branch_a == variant.branch_name {
+                    (&c.branch_b, PairwiseWinner::A)
+                } else {
+                    (&c.branch_a, PairwiseWinner::B)
+                };
+                let (verdict, style) = match c.winner {
+                    PairwiseWinner::Tie => (t!("bench.tied_with"), Theme::dim()),
+                    w if w == side => (t!("bench.beat"), Style::default().fg(Theme::success())),
+                    _ => (t!("bench.lost_to"), Style::default().fg(Theme::error())),
+                };
+                let other = self
+                    .variants
+                    .iter()
+                    .find(|v| &v.branch_name == other)
+                    .map_or(other.as_str(), |v| v.display_name.as_str());
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{} ", verdict), style),
+                    Span::styled(other.to_string(), Theme::normal()),
+                ]));
+                if !c.explanation.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", c.explanation),
+                        Theme::dim(),
+                    )));
+                }
+            }
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block);
+        self.explanation_scroll.render(frame, paragraph, area);
+    }
+}
+
+impl Default for BenchmarkDashboardComponent {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Component for BenchmarkDashboardComponent {
     fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if self.comparison.is_some() {
+            if self.comparison_scroll.handle(action) {
+                return None;
+            }
+            match action {
+                Action::Confirm | Action::Escape | Action::ToggleComparison => {
+                    self.comparison = None;
+                    return None;
+                }
+                _ => {}
+            }
+        }
+
+        if self.showing_leaderboard {
+            if self.leaderboard_scroll.handle(action) {
+                return None;
+            }
+            match action {
+                Action::Confirm | Action::Escape | Action::ToggleLeaderboard => {
+                    self.showing_leaderboard = false;
+                    return None;
+                }
+                _ => {}
+            }
+        }
+
+        if self.showing_explanation {
+            if self.explanation_scroll.handle(action) {
+                return None;
+            }
+            match action {
+                Action::Confirm | Action::Escape | Action::ToggleJudgeExplanation => {
+                    self.showing_explanation = false;
+                    return None;
+                }
+                _ => {}
+            }
+        }
+
+        // The focused detail panel scrolls instead of moving the selection.
+        if self.focused_panel() == Some("details") {
+            if self.detail_scroll.handle(action) {
+                return None;
+            }
+            if let Action::Escape = action {
+                self.focus = Focus::List;
+                self.detail_scroll.reset();
+                return None;
+            }
+        }
+
         match action {
             Action::ScrollUp | Action::SelectPrev => {
-                if self.selected > 0 {
-                    self.selected -= 1;
-                }
+                self.select_row(self.selected_row().saturating_sub(1));
                 None
             }
             Action::ScrollDown | Action::SelectNext => {
-                if self.selected + 1 < self.variants.len() {
-                    self.selected += 1;
+                self.select_row(self.selected_row() + 1);
+                None
+            }
+            Action::ResizeDetail(delta) => {
+                self.detail_height = resize_pane(self.detail_height, *delta);
+                None
+            }
+            Action::ScrollTop => {
+                self.select_row(0);
+                None
+            }
+            Action::ScrollBottom => {
+                self.select_row(usize::MAX);
+                None
+            }
+            Action::PageUp => {
+                self.select_row(self.selected_row().saturating_sub(PAGE));
+                None
+            }
+            Action::PageDown => {
+                self.select_row(self.selected_row() + PAGE);
+                None
+            }
+            Action::Search(query) => {
+                let order = self.sorted_indices();
+                let names = order
+                    .iter()
+                    .map(|&i| self.variants[i].display_name.as_str());
+                match find_next(names, self.selected_row(), query) {
+                    Some(i) => {
+                        self.select_row(i);
+                        None
+                    }
+                    None => Some(no_match(query)),
+                }
+            }
+            Action::Escape => Some(Action::CancelPhaseTask),
+            Action::Confirm => {
+                let variant = self.selected_variant()?;
+                if variant.status != VariantStatus::Ready {
+                    return Some(Action::SetStatus(t!("bench.rate_ready_only").to_string()));
+                }
+                let rating = variant
+                    .benchmark_results
+                    .as_ref()
+                    .and_then(|br| br.user_rating.as_ref());
+                Some(Action::OpenRatingDialog {
+                    variant_id: variant.id.0.clone(),
+                    variant_name: variant.display_name.clone(),
+                    stars: rating.map_or(3, |r| r.stars),
+                    notes: rating.map(|r| r.notes.clone()).unwrap_or_default(),
+                })
+            }
+            Action::Copy => {
+                let variant = self.selected_variant()?;
+                Some(Action::CopyText {
+                    text: variant.branch_name.clone(),
+                    what: t!("variants.branch_name").to_string(),
+                })
+            }
+            Action::PromoteVariant => {
+                let variant = self.selected_variant()?;
+                if variant.benchmark_results.is_none() {
+                    return Some(Action::SetStatus(
+                        t!("bench.promote_unbenchmarked").to_string(),
+                    ));
                 }
+                Some(Action::OpenPullRequest {
+                    variant_id: variant.id.0.clone(),
+                })
+            }
+            Action::CycleSort => {
+                self.set_sort(self.sort.next(), false);
+                Some(Action::SetStatus(t!(
+                    "bench.sorted_by",
+                    column = self.sort.label()
+                )))
+            }
+            Action::ReverseSort => {
+                self.set_sort(self.sort, !self.sort_reversed);
+                None
+            }
+            Action::ToggleBenchmarkHistory => {
+                self.showing_history = !self.showing_history;
+                self.detail_scroll.reset();
                 None
             }
+            Action::ToggleComparison => {
+                let variant = self.selected_variant()?;
+                if variant.benchmark_results.is_none() {
+                    return Some(Action::SetStatus(
+                        t!("bench.compare_unbenchmarked").to_string(),
+                    ));
+                }
+                let (id, name) = (variant.id.0.clone(), variant.display_name.clone());
+                match self.compare_mark.take() {
+                    Some(mark) if mark == id => None,
+                    Some(mark) => Some(Action::CompareVariants { a: mark, b: id }),
+                    None => {
+                        self.compare_mark = Some(id);
+                        Some(Action::SetStatus(t!("variants.comparing", name = name)))
+                    }
+                }
+            }
+            Action::VariantsCompared {
+                a,
+                b,
+                overlap,
+                diff,
+            } => {
+                self.comparison = Some(Comparison {
+                    a: a.clone(),
+                    b: b.clone(),
+                    overlap: overlap.clone(),
+                    diff: diff.clone(),
+                });
+                self.comparison_scroll.reset();
+                None
+            }
+            Action::TrySuggestedMerge => {
+                let variant = self.selected_variant()?;
+                let Some(suggestion) = self.suggestion_for(variant) else {
+                    return Some(Action::SetStatus(t!("bench.no_suggestion").to_string()));
+                };
+                Some(Action::PrefillMerge {
+                    sources: suggestion.sources.clone(),
+                    guidance: None,
+                    strategy: None,
+                })
+            }
+            Action::ToggleLeaderboard => {
+                self.showing_leaderboard = true;
+                self.leaderboard_scroll.reset();
+                None
+            }
+            Action::ToggleJudgeExplanation => {
+                let variant = self.selected_variant()?;
+                let judged = variant
+                    .benchmark_results
+                    .as_ref()
+                    .is_some_and(|br| br.judge.is_some() || br.elo.is_some());
+                if !judged {
+                    return Some(Action::SetStatus(t!("bench.not_judged").to_string()));
+                }
+                self.showing_explanation = true;
+                self.explanation_scroll.reset();
+                None
+            }
+            Action::OpenTimeoutPrompt => {
+                let variant = self.selected_variant()?;
+                Some(Action::OpenPrompt(PromptKind::BenchmarkTimeout {
+                    variant_id: variant.id.0.clone(),
+                    variant_name: variant.display_name.clone(),
+                }))
+            }
             Action::BenchmarkComplete => {
                 self.benchmarking = false;
-                Some(Action::SetStatus("Benchmarking complete!".to_string()))
+                Some(Action::SetStatus(t!("bench.complete").to_string()))
             }
             _ => None,
         }
     }
 
+    fn cycle_focus(&mut self, _forward: bool) -> bool {
+        if self.focused_panel().is_none() {
+            return false;
+        }
+        self.focus = self.focus.toggled();
+        self.detail_scroll.reset();
+        true
+    }
+
+    fn focused_panel(&self) -> Option<&'static str> {
+        if self.variants.is_empty()
+            || self.showing_leaderboard
+            || self.showing_explanation
+            || self.comparison.is_some()
+        {
+            return None;
+        }
+        Some(match self.focus {
+            Focus::List => "scores",
+            Focus::Detail => "details",
+        })
+    }
+
     fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
-            .title(" Benchmark Dashboard ")
+            .title(format!(" {} ", t!("bench.title")))
             .title_style(Theme::title())
             .borders(Borders::ALL)
             .border_style(Theme::dim());
@@ -64,24 +945,27 @@ impl Component for BenchmarkDashboardComponent {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
+        if self.showing_leaderboard {
+            self.render_leaderboard(frame, inner);
+            return;
+        }
+
         if self.variants.is_empty() {
             let msg = Paragraph::new(vec![
                 Line::from(""),
-                Line::from(Span::styled("No variants to benchmark.", Theme::dim())),
-                Line::from(Span::styled(
-                    "Generate variants in Phase 4 first.",
-                    Theme::dim(),
-                )),
+                Line::from(Span::styled(t!("bench.empty"), Theme::dim())),
+                Line::from(Span::styled(t!("bench.complete_phase_4"), Theme::dim())),
+                Line::from(Span::styled(t!("bench.leaderboard_hint"), Theme::dim())),
             ]);
             frame.render_widget(msg, inner);
             return;
         }
 
         let chunks = Layout::vertical([
-            Constraint::Length(2),  // Summary bar
-            Constraint::Min(8),     // Score table
-            Constraint::Length(10), // Bar chart visualization
-            Constraint::Length(8),  // Detail panel
+            Constraint::Length(2),                  // Summary bar
+            Constraint::Min(8),                     // Score table
+            Constraint::Length(10),                 // Bar chart visualization
+            Constraint::Length(self.detail_height), // Detail panel
         ])
         .split(inner);
 
@@ -91,48 +975,93 @@ impl Component for BenchmarkDashboardComponent {
             .iter()
             .filter(|v| v.benchmark_results.is_some())
             .count();
-        let summary = Paragraph::new(Line::from(vec![
+        let mut counts = vec![
             Span::styled(
-                format!("{}/{} benchmarked", benchmarked_count, self.variants.len()),
+                t!(
+                    "bench.benchmarked",
+                    done = benchmarked_count,
+                    total = self.variants.len()
+                ),
                 Theme::header(),
             ),
             Span::styled("  |  ", Theme::dim()),
-            Span::styled("[m]", Theme::selected()),
-            Span::styled("erge  ", Theme::dim()),
-            Span::styled("[r]", Theme::selected()),
-            Span::styled("un benchmark  ", Theme::dim()),
-            Span::styled("[Enter]", Theme::selected()),
-            Span::styled(" rate variant", Theme::dim()),
-        ]));
+        ];
+        counts.extend(bracket_hints(t!("bench.hints")));
+        let summary = Paragraph::new(vec![Line::from(counts), self.baseline_line()]);
         frame.render_widget(summary, chunks[0]);
 
+        if let Some(ref comparison) = self.comparison {
+            let body = chunks[1].union(chunks[3]);
+            self.render_comparison(frame, body, comparison);
+            return;
+        }
+
+        if let Some(variant) = self.selected_variant().filter(|_| self.showing_explanation) {
+            let body = chunks[1].union(chunks[3]);
+            self.render_explanation(frame, body, variant);
+            return;
+        }
+
         // Score table
+        let arrow = if self.sort_reversed { "▲" } else { "▼" };
+        let sorted = |name: &str, column: SortColumn| {
+            if self.sort == column {
+                format!("{}{}", name, arrow)
+            } else {
+                name.to_string()
+            }
+        };
         let header = Row::new(vec![
-            "#", "Variant", "Type", "Build", "Tests", "Quality", "Novelty", "Score",
+            "#".to_string(),
+            t!("bench.col_variant").to_string(),
+            t!("variants.type").to_string(),
+            t!("variants.status").to_string(),
+            t!("bench.build").to_string(),
+            sorted(t!("bench.tests"), SortColumn::Tests),
+            sorted(t!("bench.col_time"), SortColumn::Runtime),
+            t!("bench.col_memory").to_string(),
+            sorted(t!("bench.quality"), SortColumn::Quality),
+            sorted(t!("bench.novelty"), SortColumn::Novelty),
+            sorted(t!("bench.score"), SortColumn::Composite),
+            sorted(t!("bench.elo"), SortColumn::Elo),
         ])
         .style(Theme::header());
 
+        let selected = self.selected_row();
         let rows: Vec<Row> = self
-            .variants
-            .iter()
+            .sorted_indices()
+            .into_iter()
             .enumerate()
-            .map(|(i, v)| {
-                let row_style = if i == self.selected {
+            .map(|(row, i)| {
+                let v = &self.variants[i];
+                let row_style = if row == selected {
                     Theme::selected()
                 } else {
                     Theme::normal()
                 };
 
-                let (build, tests, quality, novelty, score) =
+                let execution = v
+                    .benchmark_results
+                    .as_ref()
+                    .and_then(|br| br.execution.as_ref());
+                let (runtime_samples, memory_samples) = execution
+                    .map(|e| (e.runtime_samples.as_slice(), e.memory_samples.as_slice()))
+                    .unwrap_or_default();
+                let deltas = execution
+                    .zip(self.baseline.as_ref())
+                    .map(|(exec, base)| exec.deltas_from(base))
+                    .unwrap_or_default();
+
+                let (build, tests, quality, novelty, score, elo) =
                     if let Some(ref br) = v.benchmark_results {
                         let build = br
                             .execution
                             .as_ref()
                             .map(|e| {
                                 if e.build_success {
-                                    "Pass".to_string()
+                                    t!("bench.pass").to_string()
                                 } else {
-                                    "Fail".to_string()
+                                    t!("bench.fail").to_string()
                                 }
                             })
                             .unwrap_or_else(|| "—".to_string());
@@ -141,13 +1070,21 @@ impl Component for BenchmarkDashboardComponent {
                             .execution
                             .as_ref()
                             .and_then(|e| e.test_pass_rate)
-                            .map(|r| format!("{:.0}%", r * 100.0))
+                            .map(|r| match deltas.test_pass_rate_pts {
+                                Some(pts) if pts.abs() >= 0.5 => {
+                                    format!("{:.0}% {:+.0}", r * 100.0, pts)
+                                }
+                                _ => format!("{:.0}%", r * 100.0),
+                            })
                             .unwrap_or_else(|| "—".to_string());
 
                         let quality = br
                             .judge
                             .as_ref()
-                            .map(|j| format!("{:.1}", j.code_quality))
+                            .map(|j| {
+                                let flag = if j.disagreement { "⚠" } else { "" };
+                                format!("{:.1}{}", j.code_quality, flag)
+                            })
                             .unwrap_or_else(|| "—".to_string());
 
                         let novelty = br
@@ -156,12 +1093,23 @@ impl Component for BenchmarkDashboardComponent {
                             .map(|j| format!("{:.1}", j.novelty))
                             .unwrap_or_else(|| "—".to_string());
 
+                        let trend = match v.score_trend() {
+                            Some(d) if d >= 0.5 => "↑",
+                            Some(d) if d <= -0.5 => "↓",
+                            Some(_) => "→",
+                            None => "",
+                        };
                         let score = br
                             .composite_score
-                            .map(|s| format!("{:.1}", s))
+                            .map(|s| format!("{:.1}{}", s, trend))
+                            .unwrap_or_else(|| "—".to_string());
+
+                        let elo = br
+                            .elo
+                            .map(|e| format!("{:.0}", e))
                             .unwrap_or_else(|| "—".to_string());
 
-                        (build, tests, quality, novelty, score)
+                        (build, tests, quality, novelty, score, elo)
                     } else {
                         (
                             "—".to_string(),
@@ -169,20 +1117,34 @@ impl Component for BenchmarkDashboardComponent {
                             "—".to_string(),
                             "—".to_string(),
                             "—".to_string(),
+                            "—".to_string(),
                         )
                     };
 
-                let variant_type = if v.is_merge() { "Merge" } else { "Orig" };
+                let variant_type = if v.is_merge() {
+                    t!("variants.merge")
+                } else {
+                    t!("bench.original")
+                };
+                let status = self.status_cell(v);
 
                 Row::new(vec![
-                    format!("{}", i + 1),
-                    truncate(&v.display_name, 25),
-                    variant_type.to_string(),
-                    build,
-                    tests,
-                    quality,
-                    novelty,
-                    score,
+                    Cell::from(format!("{}", i + 1)),
+                    Cell::from(truncate(&v.display_name, 25)),
+                    Cell::from(variant_type),
+                    status,
+                    Cell::from(build),
+                    Cell::from(tests),
+                    delta_cell(
+                        deltas.runtime_pct,
+                        deltas.runtime_significant,
+                        runtime_samples,
+                    ),
+                    delta_cell(deltas.memory_pct, deltas.memory_significant, memory_samples),
+                    Cell::from(quality),
+                    Cell::from(novelty),
+                    Cell::from(score),
+                    Cell::from(elo),
                 ])
                 .style(row_style)
             })
@@ -194,15 +1156,23 @@ impl Component for BenchmarkDashboardComponent {
                 Constraint::Length(3),
                 Constraint::Min(15),
                 Constraint::Length(6),
+                Constraint::Length(9),
                 Constraint::Length(6),
-                Constraint::Length(6),
+                Constraint::Length(9),
+                Constraint::Length(12),
+                Constraint::Length(12),
                 Constraint::Length(8),
                 Constraint::Length(8),
                 Constraint::Length(8),
+                Constraint::Length(6),
             ],
         )
         .header(header)
-        .block(Block::default().borders(Borders::TOP));
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_style(self.focus.border(Focus::List)),
+        );
 
         frame.render_widget(table, chunks[1]);
 
@@ -225,11 +1195,22 @@ impl Component for BenchmarkDashboardComponent {
             })
             .collect();
 
+        // Judge breakdown for the selected variant sits beside it.
+        let judge = self
+            .selected_variant()
+            .and_then(|v| v.benchmark_results.as_ref()?.judge.as_ref());
+        let chart_areas = if judge.is_some() {
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(chunks[2])
+        } else {
+            Layout::horizontal([Constraint::Percentage(100)]).split(chunks[2])
+        };
+
         if !bars.is_empty() {
             let chart = BarChart::default()
                 .block(
                     Block::default()
-                        .title(" Composite Scores ")
+                        .title(format!(" {} ", t!("bench.composite_scores")))
                         .borders(Borders::ALL)
                         .border_style(Theme::dim()),
                 )
@@ -237,33 +1218,237 @@ impl Component for BenchmarkDashboardComponent {
                 .bar_width(5)
                 .bar_gap(1)
                 .max(100);
-            frame.render_widget(chart, chunks[2]);
+            frame.render_widget(chart, chart_areas[0]);
+        }
+
+        if let Some(judge) = judge {
+            let dimensions = [
+                (t!("bench.quality"), judge.code_quality),
+                (t!("bench.novelty"), judge.novelty),
+                (t!("bench.feasible"), judge.feasibility),
+                (t!("bench.goal_fit"), judge.goal_alignment),
+                (t!("bench.complete_short"), judge.completeness),
+            ];
+            let bars: Vec<Bar> = dimensions
+                .iter()
+                .map(|(label, score)| {
+                    Bar::default()
+                        .value((score * 10.0).round() as u64)
+                        .text_value(format!("{:.1}", score))
+                        .label(Line::from(*label))
+                        .style(Style::default().fg(Theme::score_color(*score, 10.0)))
+                })
+                .collect();
+            let chart = BarChart::default()
+                .block(
+                    Block::default()
+                        .title(format!(" {} ", t!("bench.judge_scores")))
+                        .borders(Borders::ALL)
+                        .border_style(Theme::dim()),
+                )
+                .direction(Direction::Horizontal)
+                .data(BarGroup::default().bars(&bars))
+                .bar_width(1)
+                .bar_gap(0)
+                .max(100);
+            frame.render_widget(chart, chart_areas[1]);
         }
 
         // Detail panel
-        if let Some(variant) = self.variants.get(self.selected) {
+        if let Some(variant) = self.selected_variant().filter(|_| self.showing_history) {
+            let block = Block::default()
+                .title(format!(
+                    " {} ",
+                    t!("bench.history_title", name = variant.display_name)
+                ))
+                .borders(Borders::ALL)
+                .border_style(self.focus.border(Focus::Detail));
+            let history = Paragraph::new(history_lines(variant)).block(block);
+            self.detail_scroll.render(frame, history, chunks[3]);
+        } else if let Some(variant) = self.selected_variant() {
             let detail_block = Block::default()
-                .title(format!(" {} — Details ", variant.display_name))
+                .title(format!(
+                    " {} ",
+                    t!("bench.details_title", name = variant.display_name)
+                ))
                 .borders(Borders::ALL)
-                .border_style(Theme::dim());
+                .border_style(self.focus.border(Focus::Detail));
 
             let mut lines = vec![Line::from(vec![
-                Span::styled("Branch: ", Theme::header()),
+                Span::styled(format!("{} ", t!("variants.branch_label")), Theme::header()),
                 Span::styled(&variant.branch_name, Theme::normal()),
             ])];
 
+            if let Some(timeout) = variant.benchmark_timeout {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{} ", t!("bench.timeout")), Theme::header()),
+                    Span::styled(format!("{}s", timeout), Theme::normal()),
+                ]));
+            }
+
             if let Some(ref br) = variant.benchmark_results {
+                lines.push(score_breakdown_line(br, &self.weights));
+                if let Some(suggestion) = self.suggestion_for(variant) {
+                    let blends = suggestion
+                        .sources
+                        .iter()
+                        .map(|s| {
+                            let name = self
+                                .variants
+                                .iter()
+                                .find(|v| v.id == s.variant)
+                                .map_or(s.variant.0.as_str(), |v| v.display_name.as_str());
+                            format!("{} {}", name, s.blend)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" + ");
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("{} ", t!("bench.try_merging")), Theme::header()),
+                        Span::styled(
+                            format!("{} — {} ", blends, suggestion.reason),
+                            Theme::normal(),
+                        ),
+                        Span::styled("[M]", Theme::key_hint()),
+                    ]));
+                }
+                if let Some(ref exec) = br.execution {
+                    let deltas = self.baseline.as_ref().map(|b| exec.deltas_from(b));
+                    let describe = |value: String,
+                                    samples: &[f64],
+                                    pct: Option<f64>,
+                                    significant: Option<bool>| {
+                        let mut text = value;
+                        if samples.len() > 1 {
+                            text = format!("{} {}", text, sparkline(samples));
+                        }
+                        match (pct, significant) {
+                            (Some(pct), Some(false)) => format!(
+                                "{} ({})",
+                                text,
+                                t!("bench.vs_baseline_noise", pct = format!("{:+.0}", pct))
+                            ),
+                            (Some(pct), _) => format!(
+                                "{} ({})",
+                                text,
+                                t!("bench.vs_baseline", pct = format!("{:+.0}", pct))
+                            ),
+                            (None, _) => text,
+                        }
+                    };
+                    let mut parts = Vec::new();
+                    if let Some(runtime) = exec.runtime_ms {
+                        let pct = deltas.and_then(|d| d.runtime_pct);
+                        let significant = deltas.and_then(|d| d.runtime_significant);
+                        let value = match exec.runtime_std_ms {
+                            Some(std) => format!("{:.0} ± {:.0} ms", runtime, std),
+                            None => format!("{:.0} ms", runtime),
+                        };
+                        parts.push(describe(value, &exec.runtime_samples, pct, significant));
+                    }
+                    if let Some(memory) = exec.memory_mb {
+                        let pct = deltas.and_then(|d| d.memory_pct);
+                        let significant = deltas.and_then(|d| d.memory_significant);
+                        let value = match exec.memory_std_mb {
+                            Some(std) => format!("{:.1} ± {:.1} MB", memory, std),
+                            None => format!("{:.1} MB", memory),
+                        };
+                        parts.push(describe(value, &exec.memory_samples, pct, significant));
+                    }
+                    if !parts.is_empty() {
+                        lines.push(Line::from(vec![
+                            Span::styled(format!("{} ", t!("bench.execution")), Theme::header()),
+                            Span::styled(parts.join(", "), Theme::normal()),
+                        ]));
+                    }
+                    if !exec.custom_metrics.is_empty() {
+                        let mut metrics: Vec<_> = exec.custom_metrics.iter().collect();
+                        metrics.sort_by(|a, b| a.0.cmp(b.0));
+                        let text = metrics
+                            .iter()
+                            .map(|(_, m)| format!("{} {:.0} {}", m.name, m.value, m.unit))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        lines.push(Line::from(vec![
+                            Span::styled(format!("{} ", t!("bench.metrics")), Theme::header()),
+                            Span::styled(text, Theme::normal()),
+                        ]));
+                    }
+                    if !exec.flaky_tests.is_empty() {
+                        lines.push(Line::from(vec![
+                            Span::styled(format!("{} ", t!("bench.flaky_tests")), Theme::header()),
+                            Span::styled(
+                                t!("bench.flaky_note", tests = exec.flaky_tests.join(", ")),
+                                Style::default().fg(Theme::warning()),
+                            ),
+                        ]));
+                    }
+                }
                 if let Some(ref judge) = br.judge {
+                    let mut spans = vec![Span::styled(
+                        format!("{} ", t!("bench.judge")),
+                        Theme::header(),
+                    )];
+                    if judge.samples > 1 {
+                        let style = if judge.disagreement {
+                            Style::default().fg(Theme::warning())
+                        } else {
+                            Theme::normal()
+                        };
+                        let key = if judge.disagreement {
+                            "bench.samples_disagree"
+                        } else {
+                            "bench.samples"
+                        };
+                        spans.push(Span::styled(
+                            format!(
+                                "[{}] ",
+                                t!(
+                                    key,
+                                    samples = judge.samples,
+                                    spread = format!("{:.1}", judge.spread)
+                                )
+                            ),
+                            style,
+                        ));
+                    }
+                    spans.push(Span::styled(&judge.explanation, Theme::dim()));
+                    lines.push(Line::from(spans));
+                }
+                if let Some(elo) = br.elo {
+                    let (mut won, mut lost, mut tied) = (0, 0, 0);
+                    for c in &self.comparisons {
+                        let side = if c.branch_a == variant.branch_name {
+                            PairwiseWinner::A
+                        } else if c.branch_b == variant.branch_name {
+                            PairwiseWinner::B
+                        } else {
+                            continue;
+                        };
+                        match c.winner {
+                            PairwiseWinner::Tie => tied += 1,
+                            w if w == side => won += 1,
+                            _ => lost += 1,
+                        }
+                    }
                     lines.push(Line::from(vec![
-                        Span::styled("Judge: ", Theme::header()),
-                        Span::styled(&judge.explanation, Theme::dim()),
+                        Span::styled(format!("{} ", t!("bench.tournament")), Theme::header()),
+                        Span::styled(
+                            t!(
+                                "bench.tournament_record",
+                                elo = format!("{:.0}", elo),
+                                won = won,
+                                lost = lost,
+                                tied = tied
+                            ),
+                            Theme::normal(),
+                        ),
                     ]));
                 }
                 if let Some(ref user) = br.user_rating {
                     lines.push(Line::from(vec![
-                        Span::styled("Your rating: ", Theme::header()),
+                        Span::styled(format!("{}: ", t!("bench.your_rating")), Theme::header()),
                         Span::styled(
-                            format!("{} — {}", "*".repeat(user.stars as usize), user.notes),
+                            format!("{} — {}", "★".repeat(user.stars as usize), user.notes),
                             Theme::normal(),
                         ),
                     ]));
@@ -273,16 +1458,194 @@ impl Component for BenchmarkDashboardComponent {
             let detail = Paragraph::new(lines)
                 .wrap(Wrap { trim: true })
                 .block(detail_block);
+            self.detail_scroll.render(frame, detail, chunks[3]);
+        }
+    }
+}
+
+/// How a benchmark stage is shown.
+fn stage_label(stage: BenchmarkStage) -> &'static str {
+    match stage {
+        BenchmarkStage::Queued => t!("bench.queued"),
+        BenchmarkStage::Building => t!("bench.building"),
+        BenchmarkStage::Testing => t!("bench.testing"),
+        BenchmarkStage::Running => t!("bench.running"),
+        BenchmarkStage::Judging => t!("bench.judging"),
+        BenchmarkStage::Done => t!("bench.done"),
+        BenchmarkStage::Failed => t!("bench.failed"),
+    }
+}
+
+/// How a score family from [`BenchmarkResults::breakdown`] is shown.
+fn family_label(name: &'static str) -> &'static str {
+    match name {
+        "Execution" => t!("bench.family_execution"),
+        "Judge" => t!("bench.family_judge"),
+        "User" => t!("bench.family_user"),
+        other => other,
+    }
+}
+
+/// How the composite score is made up: each family's score and weight,
+/// with missing families called out since their weight is redistributed.
+fn score_breakdown_line(results: &BenchmarkResults, weights: &ScoreWeights) -> Line<'static> {
+    let components = results.breakdown(weights);
+    let present: f64 = components
+        .iter()
+        .filter(|c| c.score.is_some())
+        .map(|c| c.weight)
+        .sum();
+    let mut spans = vec![Span::styled(
+        format!("{} ", t!("bench.score_label")),
+        Theme::header(),
+    )];
+    for (i, component) in components.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" + ", Theme::dim()));
+        }
+        match component.score {
+            Some(score) => {
+                let share = if present > 0.0 {
+                    component.weight / present
+                } else {
+                    0.0
+                };
+                spans.push(Span::styled(
+                    format!("{} {:.0}×{:.2}", family_label(component.name), score, share),
+                    Theme::normal(),
+                ));
+            }
+            None => spans.push(Span::styled(
+                t!("bench.missing", family = family_label(component.name)),
+                Theme::dim(),
+            )),
+        }
+    }
+    let total = components
+        .iter()
+        .filter_map(|c| Some(c.score? * c.weight))
+        .sum::<f64>();
+    if present > 0.0 {
+        spans.push(Span::styled(
+            format!(" = {:.1}", total / present),
+            Theme::header(),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Earlier benchmark runs of `variant`, newest first, with a sparkline of
+/// composite scores across all of them.
+fn history_lines(variant: &Variant) -> Vec<Line<'static>> {
+    let runs = &variant.benchmark_history;
+    if runs.is_empty() {
+        return vec![Line::from(Span::styled(
+            t!("bench.no_history"),
+            Theme::dim(),
+        ))];
+    }
 
-            frame.render_widget(detail, chunks[3]);
+    let scores: Vec<f64> = runs
+        .iter()
+        .filter_map(|r| r.results.composite_score)
+        .collect();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{}  ", t!("bench.runs", count = runs.len())),
+            Theme::header(),
+        ),
+        Span::styled(sparkline(&scores), Style::default().fg(Theme::accent())),
+    ])];
+
+    for run in runs.iter().rev() {
+        let results = &run.results;
+        let score = results
+            .composite_score
+            .map_or("—".to_string(), |s| format!("{:.1}", s));
+        let mut parts = vec![t!("bench.history_score", score = score)];
+        if let Some(ref exec) = results.execution {
+            parts.push(
+                if exec.build_success {
+                    t!("bench.build_pass")
+                } else {
+                    t!("bench.build_fail")
+                }
+                .to_string(),
+            );
+            if let Some(rate) = exec.test_pass_rate {
+                parts.push(t!(
+                    "bench.tests_percent",
+                    percent = format!("{:.0}", rate * 100.0)
+                ));
+            }
+            if let Some(runtime) = exec.runtime_ms {
+                parts.push(format!("{:.0} ms", runtime));
+            }
+        }
+        if let Some(ref judge) = results.judge {
+            parts.push(t!(
+                "bench.history_judge",
+                score = format!("{:.1}", judge.overall)
+            ));
         }
+        lines.push(Line::from(vec![
+            Span::styled(
+                run.at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M  ")
+                    .to_string(),
+                Theme::dim(),
+            ),
+            Span::styled(parts.join(" · "), Theme::normal()),
+        ]));
     }
+    lines
+}
+
+/// Text sparkline of `values`, scaled between their minimum and maximum.
+fn sparkline(values: &[f64]) -> String {
+    const TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = max - min;
+    values
+        .iter()
+        .map(|v| {
+            if span <= f64::EPSILON {
+                TICKS[3]
+            } else {
+                TICKS[(((v - min) / span) * 7.0).round() as usize]
+            }
+        })
+        .collect()
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
+/// A percent change where lower is better (runtime, memory), followed by
+/// a sparkline of the latest repetitions when there were several. Changes
+/// within run-to-run noise are dimmed and marked with `≈`.
+fn delta_cell(pct: Option<f64>, significant: Option<bool>, samples: &[f64]) -> Cell<'static> {
+    let mut spans = vec![match pct {
+        Some(pct) if significant == Some(false) => {
+            Span::styled(format!("≈{:+.0}%", pct), Theme::dim())
+        }
+        Some(pct) => {
+            let color = if pct <= -1.0 {
+                Theme::success()
+            } else if pct >= 1.0 {
+                Theme::error()
+            } else {
+                Theme::fg()
+            };
+            Span::styled(format!("{:+.0}%", pct), Style::default().fg(color))
+        }
+        None => Span::styled("—", Theme::dim()),
+    }];
+    if samples.len() > 1 {
+        let recent = &samples[samples.len().saturating_sub(5)..];
+        spans.push(Span::styled(
+            format!(" {}", sparkline(recent)),
+            Style::default().fg(Theme::accent()),
+        ));
     }
+    Cell::from(Line::from(spans))
 }
@@ -0,0 +1,191 @@
+//! Command palette — find any bindable action by fuzzy-typing its name or
+//! description, and run it.
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::action::Action;
+use crate::components::Component;
+use crate::keymap::{Binding, Keymap};
+use crate::theme::Theme;
+
+use uniq_core::fuzzy::fuzzy_score;
+
+/// Matches listed at once.
+const MAX_ROWS: usize = 12;
+
+pub struct CommandPaletteComponent {
+    pub visible: bool,
+    query: String,
+    selected: usize,
+    keymap: Keymap,
+}
+
+impl CommandPaletteComponent {
+    pub fn new(keymap: Keymap) -> Self {
+        Self {
+            visible: false,
+            query: String::new(),
+            selected: 0,
+            keymap,
+        }
+    }
+
+    /// Bindings matching the query, best first.
+    fn matches(&self) -> Vec<&Binding> {
+        let mut scored: Vec<(i64, &Binding)> = self
+            .keymap
+            .bindings()
+            .iter()
+            .filter(|b| b.name != "command_palette")
+            .filter_map(|b| {
+                let by_description = fuzzy_score(&self.query, b.description);
+                let by_name = fuzzy_score(&self.query, b.name);
+                Some((by_description.max(by_name)?, b))
+            })
+            .collect();
+        if !self.query.trim().is_empty() {
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        }
+        scored.into_iter().map(|(_, b)| b).collect()
+    }
+
+    fn close(&mut self) {
+        self.visible = false;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    fn run_selected(&mut self) -> Option<Action> {
+        let action = self.matches().get(self.selected).map(|b| b.action());
+        self.close();
+        action
+    }
+
+    fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+        let vertical = Layout::vertical([
+            Constraint::Length(area.height / 6),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+        let horizontal = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(width),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(vertical[1]);
+
+        horizontal[1]
+    }
+}
+
+impl Default for CommandPaletteComponent {
+    fn default() -> Self {
+        Self::new(Keymap::default())
+    }
+}
+
+impl Component for CommandPaletteComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if let Action::OpenCommandPalette = action {
+            self.visible = true;
+            self.query.clear();
+            self.selected = 0;
+            return None;
+        }
+        if !self.visible {
+            return None;
+        }
+
+        match action {
+            Action::CharInput(c) => {
+                self.query.push(*c);
+                self.selected = 0;
+            }
+            Action::BackspaceInput => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            Action::DeleteWord => {
+                self.query.clear();
+                self.selected = 0;
+            }
+            Action::PasteBulk(text) => {
+                self.query.push_str(text.lines().next().unwrap_or(""));
+                self.selected = 0;
+            }
+            Action::ScrollUp => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            Action::ScrollDown | Action::SwitchInputField
+                if self.selected + 1 < self.matches().len() =>
+            {
+                self.selected += 1;
+            }
+            Action::NewlineInput | Action::SubmitForm => return self.run_selected(),
+            Action::Escape => self.close(),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let matches = self.matches();
+        let rows = matches.len().clamp(1, MAX_ROWS);
+        let width = area.width.saturating_sub(4).min(70);
+        let dialog = Self::centered_rect(area, width, rows as u16 + 4);
+        frame.render_widget(Clear, dialog);
+
+        let block = Block::default()
+            .title(format!(" {} ", t!("palette.title")))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let cursor = Span::styled(" ", Style::default().fg(Theme::bg()).bg(Theme::accent()));
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("> ", Theme::selected()),
+                Span::styled(self.query.clone(), Theme::normal()),
+                cursor,
+            ]),
+            Line::from(""),
+        ];
+        if matches.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", t!("palette.empty")),
+                Theme::dim(),
+            )));
+        }
+
+        let skip = (self.selected + 1).saturating_sub(MAX_ROWS);
+        let inner_width = width.saturating_sub(2) as usize;
+        for (i, binding) in matches.iter().enumerate().skip(skip).take(MAX_ROWS) {
+            let keys = self.keymap.key_labels(binding.name).join(" / ");
+            let style = if i == self.selected {
+                Theme::selected()
+            } else {
+                Theme::normal()
+            };
+            let label = format!("  {}", binding.description);
+            let pad = inner_width.saturating_sub(label.chars().count() + keys.chars().count() + 1);
+            lines.push(Line::from(vec![
+                Span::styled(label, style),
+                Span::raw(" ".repeat(pad)),
+                Span::styled(keys, Theme::key_hint()),
+            ]));
+        }
+
+        frame.render_widget(Paragraph::new(lines).block(block), dialog);
+    }
+}
@@ -0,0 +1,364 @@
+//! Conflict viewer — step through the hunks a merge's sources conflict on
+//! in git and pick a side, or have the LLM reconcile them.
+
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::action::Action;
+use crate::components::Component;
+use crate::theme::Theme;
+
+use uniq_core::conflict::{ConflictHunk, FileConflict, Resolution};
+
+/// Clean lines shown around a hunk.
+const CONTEXT_LINES: usize = 3;
+
+pub struct ConflictViewerComponent {
+    /// Whether the viewer is visible.
+    pub visible: bool,
+    /// Merge whose conflicts are shown.
+    pub variant_id: String,
+    /// That merge's branch.
+    pub branch: String,
+    /// Technique names of the merge sources, in spec order.
+    pub source_names: Vec<String>,
+    pub files: Vec<FileConflict>,
+    /// Focused file and hunk within it.
+    file: usize,
+    hunk: usize,
+}
+
+impl ConflictViewerComponent {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            variant_id: String::new(),
+            branch: String::new(),
+            source_names: Vec::new(),
+            files: Vec::new(),
+            file: 0,
+            hunk: 0,
+        }
+    }
+
+    /// The focused file and the index of its focused hunk.
+    pub fn current(&self) -> Option<(&FileConflict, usize)> {
+        self.files.get(self.file).map(|f| (f, self.hunk))
+    }
+
+    fn current_hunk_mut(&mut self) -> Option<&mut ConflictHunk> {
+        self.files.get_mut(self.file)?.hunk_mut(self.hunk)
+    }
+
+    /// Source label: A, B, C, D.
+    fn label(index: usize) -> char {
+        (b'A' + index as u8) as char
+    }
+
+    fn source_name(&self, index: usize) -> String {
+        self.source_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| t!("conflicts.source", label = Self::label(index)))
+    }
+
+    fn next_hunk(&mut self) {
+        let Some(file) = self.files.get(self.file) else {
+            return;
+        };
+        if self.hunk + 1 < file.hunks().count() {
+            self.hunk += 1;
+        } else if self.file + 1 < self.files.len() {
+            self.file += 1;
+            self.hunk = 0;
+        }
+    }
+
+    fn prev_hunk(&mut self) {
+        if self.hunk > 0 {
+            self.hunk -= 1;
+        } else if self.file > 0 {
+            self.file -= 1;
+            self.hunk = self.files[self.file].hunks().count().saturating_sub(1);
+        }
+    }
+
+    /// Commit every file resolved for all the pairs it's listed for.
+    fn apply(&mut self) -> Option<Action> {
+        let resolved = |path: &str| {
+            self.files
+                .iter()
+                .filter(|f| f.path == path)
+                .all(FileConflict::is_resolved)
+        };
+        if !self.files.iter().any(|f| resolved(&f.path)) {
+            return Some(Action::SetStatus(t!("conflicts.unresolved").to_string()));
+        }
+        self.visible = false;
+        Some(Action::ApplyConflictResolutions {
+            variant_id: self.variant_id.clone(),
+            files: self.files.clone(),
+        })
+    }
+
+    fn file_lines(&self) -> Vec<Line<'static>> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let total = f.hunks().count();
+                let resolved = f.hunks().filter(|h| h.resolution.is_some()).count();
+                let (mark, color) = if resolved == total {
+                    ("✓", Theme::success())
+                } else {
+                    ("✗", Theme::error())
+                };
+                let style = if i == self.file {
+                    Theme::selected()
+                } else {
+                    Theme::normal()
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {} ", mark), Style::default().fg(color)),
+                    Span::styled(f.path.clone(), style),
+                    Span::styled(
+                        format!(
+                            "  {}",
+                            t!(
+                                "conflicts.file_progress",
+                                resolved = resolved,
+                                total = total,
+                                a = Self::label(f.sources[0]),
+                                b = Self::label(f.sources[1])
+                            )
+                        ),
+                        Theme::dim(),
+                    ),
+                ])
+            })
+            .collect()
+    }
+
+    fn side_lines(
+        title: String,
+        lines: &[String],
+        color: ratatui::style::Color,
+        chosen: bool,
+    ) -> Vec<Line<'static>> {
+        let mut title_style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+        if chosen {
+            title_style = title_style.add_modifier(Modifier::REVERSED);
+        }
+        let mut out = vec![Line::from(Span::styled(title, title_style))];
+        if lines.is_empty() {
+            out.push(Line::from(Span::styled(
+                format!("  {}", t!("conflicts.nothing")),
+                Theme::dim(),
+            )));
+        }
+        for line in lines {
+            out.push(Line::from(vec![
+                Span::styled("▌ ", Style::default().fg(color)),
+                Span::styled(line.clone(), Theme::normal()),
+            ]));
+        }
+        out
+    }
+
+    fn hunk_lines(&self, file: &FileConflict) -> Vec<Line<'static>> {
+        let Some(hunk) = file.hunks().nth(self.hunk) else {
+            return Vec::new();
+        };
+        let [a, b] = file.sources;
+        let (before, after) = file.context(self.hunk, CONTEXT_LINES);
+        let context = |lines: Vec<String>| {
+            lines
+                .into_iter()
+                .map(|l| Line::from(Span::styled(format!("  {}", l), Theme::dim())))
+                .collect::<Vec<_>>()
+        };
+
+        let mut lines = vec![Line::from(Span::styled(
+            t!(
+                "conflicts.hunk",
+                index = self.hunk + 1,
+                total = file.hunks().count(),
+                path = file.path
+            ),
+            Theme::header(),
+        ))];
+        lines.extend(context(before));
+        lines.extend(Self::side_lines(
+            format!("── {}: {} ──", Self::label(a), self.source_name(a)),
+            &hunk.a,
+            Theme::blend(a),
+            hunk.resolution == Some(Resolution::PreferA),
+        ));
+        if !hunk.base.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("── {} ──", t!("conflicts.base")),
+                Theme::dim(),
+            )));
+            lines.extend(context(hunk.base.clone()));
+        }
+        lines.extend(Self::side_lines(
+            format!("── {}: {} ──", Self::label(b), self.source_name(b)),
+            &hunk.b,
+            Theme::blend(b),
+            hunk.resolution == Some(Resolution::PreferB),
+        ));
+        if let Some(Resolution::Reconciled(ref reconciled)) = hunk.resolution {
+            lines.extend(Self::side_lines(
+                format!("── {} ──", t!("conflicts.reconciled")),
+                reconciled,
+                Theme::success(),
+                true,
+            ));
+        }
+        lines.extend(context(after));
+        lines
+    }
+}
+
+impl Default for ConflictViewerComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for ConflictViewerComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        match action {
+            Action::ConflictsLoaded {
+                variant_id,
+                branch,
+                sources,
+                files,
+            } => {
+                self.visible = true;
+                self.variant_id = variant_id.clone();
+                self.branch = branch.clone();
+                self.source_names = sources.clone();
+                self.files = files.clone();
+                self.file = 0;
+                self.hunk = 0;
+                return None;
+            }
+            Action::HunkReconciled {
+                branch,
+                path,
+                sources,
+                hunk,
+                lines,
+            } => {
+                if *branch != self.branch {
+                    return None;
+                }
+                let file = self
+                    .files
+                    .iter_mut()
+                    .find(|f| f.path == *path && f.sources == *sources);
+                if let Some(file) = file {
+                    if let Some(h) = file.hunk_mut(*hunk) {
+                        h.resolution = Some(Resolution::Reconciled(lines.clone()));
+                    }
+                }
+                return None;
+            }
+            _ => {}
+        }
+
+        if !self.visible {
+            return None;
+        }
+
+        match action {
+            Action::Escape => {
+                self.visible = false;
+                None
+            }
+            Action::ScrollDown | Action::SelectNext => {
+                self.next_hunk();
+                None
+            }
+            Action::ScrollUp | Action::SelectPrev => {
+                self.prev_hunk();
+                None
+            }
+            Action::PrevPhase => {
+                if let Some(hunk) = self.current_hunk_mut() {
+                    hunk.resolution = Some(Resolution::PreferA);
+                }
+                None
+            }
+            Action::NextPhase => {
+                if let Some(hunk) = self.current_hunk_mut() {
+                    hunk.resolution = Some(Resolution::PreferB);
+                }
+                None
+            }
+            Action::Confirm => self.apply(),
+            _ => None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let dialog = area.inner(ratatui::layout::Margin {
+            horizontal: area.width / 10,
+            vertical: area.height / 10,
+        });
+        frame.render_widget(Clear, dialog);
+
+        let block = Block::default()
+            .title(format!(" {} ", t!("conflicts.title")))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+        let inner = block.inner(dialog);
+        frame.render_widget(block, dialog);
+
+        let file_rows = self.files.len().min(6) as u16 + 2;
+        let chunks = Layout::vertical([
+            Constraint::Length(file_rows),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+        let files_block = Block::default()
+            .title(format!(
+                " {} ",
+                t!("conflicts.files", count = self.files.len())
+            ))
+            .borders(Borders::ALL)
+            .border_style(Theme::normal());
+        let skip = (self.file + 1).saturating_sub(6);
+        let file_lines: Vec<Line> = self.file_lines().into_iter().skip(skip).collect();
+        frame.render_widget(Paragraph::new(file_lines).block(files_block), chunks[0]);
+
+        if let Some(file) = self.files.get(self.file) {
+            frame.render_widget(Paragraph::new(self.hunk_lines(file)), chunks[1]);
+        }
+
+        let instructions = Line::from(vec![
+            Span::styled("[Up/Down]", Theme::selected()),
+            Span::styled(format!(" {}  ", t!("hint.hunk")), Theme::dim()),
+            Span::styled("[Left/Right]", Theme::selected()),
+            Span::styled(format!(" {}  ", t!("hint.prefer_side")), Theme::dim()),
+            Span::styled("[R]", Theme::selected()),
+            Span::styled(format!(" {}  ", t!("hint.reconcile")), Theme::dim()),
+            Span::styled("[Enter]", Theme::selected()),
+            Span::styled(format!(" {}  ", t!("hint.apply_resolved")), Theme::dim()),
+            Span::styled("[Esc]", Theme::selected()),
+            Span::styled(format!(" {}", t!("hint.close")), Theme::dim()),
+        ]);
+        frame.render_widget(Paragraph::new(instructions), chunks[2]);
+    }
+}
@@ -0,0 +1,296 @@
+//! Directory tree for browsing to a project in Phase 1, as an alternative
+//! to typing the path with prefix suggestions.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::components::{render_scrollbar, scrollbar_gutter};
+use crate::theme::Theme;
+
+/// One visible directory in the flattened tree.
+#[derive(Debug, Clone)]
+struct TreeRow {
+    path: PathBuf,
+    name: String,
+    depth: usize,
+    expanded: bool,
+}
+
+pub struct DirTree {
+    root: PathBuf,
+    expanded: HashSet<PathBuf>,
+    show_hidden: bool,
+    /// Visible rows, rebuilt whenever a directory opens or closes.
+    rows: Vec<TreeRow>,
+    selected: usize,
+}
+
+impl DirTree {
+    /// A tree rooted at `dir`, opened one level deep.
+    pub fn new(dir: &Path) -> Self {
+        let mut tree = Self {
+            root: dir.to_path_buf(),
+            expanded: HashSet::from([dir.to_path_buf()]),
+            show_hidden: false,
+            rows: Vec::new(),
+            selected: 0,
+        };
+        tree.rebuild();
+        tree
+    }
+
+    /// The highlighted directory.
+    pub fn selected_path(&self) -> Option<&Path> {
+        self.rows.get(self.selected).map(|row| row.path.as_path())
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let last = self.rows.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn select_first(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.rows.len().saturating_sub(1);
+    }
+
+    /// Open the highlighted directory, or step into it if already open.
+    pub fn expand(&mut self) {
+        let Some(row) = self.rows.get(self.selected) else {
+            return;
+        };
+        if row.expanded {
+            self.move_selection(1);
+        } else {
+            self.expanded.insert(row.path.clone());
+            self.rebuild();
+        }
+    }
+
+    /// Close the highlighted directory, or move to its parent. On the root,
+    /// re-root the tree one level up.
+    pub fn collapse(&mut self) {
+        let Some(row) = self.rows.get(self.selected).cloned() else {
+            return;
+        };
+        if row.depth == 0 {
+            if let Some(parent) = self.root.parent().map(Path::to_path_buf) {
+                self.expanded.insert(parent.clone());
+                self.root = parent;
+                self.rebuild();
+                self.select_path(&row.path);
+            }
+        } else if row.expanded {
+            self.expanded.remove(&row.path);
+            self.rebuild();
+        } else if let Some(parent) = row.path.parent() {
+            self.select_path(parent);
+        }
+    }
+
+    pub fn toggle_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        let selected = self.selected_path().map(Path::to_path_buf);
+        self.rebuild();
+        if let Some(path) = selected {
+            self.select_path(&path);
+        }
+    }
+
+    fn select_path(&mut self, path: &Path) {
+        if let Some(i) = self.rows.iter().position(|row| row.path == path) {
+            self.selected = i;
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let mut rows = vec![TreeRow {
+            path: self.root.clone(),
+            name: self.root.display().to_string(),
+            depth: 0,
+            expanded: self.expanded.contains(&self.root),
+        }];
+        if rows[0].expanded {
+            self.push_children(&self.root, 1, &mut rows);
+        }
+        self.rows = rows;
+        self.selected = self.selected.min(self.rows.len() - 1);
+    }
+
+    fn push_children(&self, dir: &Path, depth: usize, rows: &mut Vec<TreeRow>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut children: Vec<(String, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| {
+                (
+                    entry.file_name().to_string_lossy().to_string(),
+                    entry.path(),
+                )
+            })
+            .filter(|(name, _)| self.show_hidden || !name.starts_with('.'))
+            .collect();
+        children.sort_by_key(|(name, _)| name.to_lowercase());
+
+        for (name, path) in children {
+            let expanded = self.expanded.contains(&path);
+            rows.push(TreeRow {
+                path: path.clone(),
+                name,
+                depth,
+                expanded,
+            });
+            if expanded {
+                self.push_children(&path, depth + 1, rows);
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(format!(" {} ", t!("dir_tree.title")))
+            .title_style(Theme::key_hint())
+            .title_bottom(Line::from(Span::styled(
+                if self.show_hidden {
+                    format!(" {} ", t!("dir_tree.hidden_shown"))
+                } else {
+                    format!(" {} ", t!("dir_tree.hidden_not_shown"))
+                },
+                Theme::dim(),
+            )))
+            .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            .border_style(Theme::border());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let list_area = scrollbar_gutter(inner, self.rows.len());
+        let visible = list_area.height as usize;
+        let offset = (self.selected + 1).saturating_sub(visible);
+        let lines: Vec<Line> = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, row)| {
+                let marker = if row.expanded { "▾" } else { "▸" };
+                let slash = if row.name.ends_with('/') { "" } else { "/" };
+                let indent = "  ".repeat(row.depth);
+                let text = format!(" {}{} {}{} ", indent, marker, row.name, slash);
+                let style = if i == self.selected {
+                    Style::default()
+                        .fg(Theme::bg())
+                        .bg(Theme::accent())
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Theme::accent())
+                };
+                Line::from(Span::styled(text, style))
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), list_area);
+        render_scrollbar(frame, inner, self.rows.len(), offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory with `alpha/inner`, `Beta` and `.hidden` in it, and a file.
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["alpha/inner", "Beta", ".hidden"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        dir
+    }
+
+    /// Visible rows below the root, indented by depth.
+    fn rows(tree: &DirTree) -> Vec<String> {
+        tree.rows[1..]
+            .iter()
+            .map(|row| format!("{}{}", "  ".repeat(row.depth - 1), row.name))
+            .collect()
+    }
+
+    fn selected(tree: &DirTree, dir: &tempfile::TempDir) -> PathBuf {
+        let path = tree.selected_path().unwrap();
+        path.strip_prefix(dir.path()).unwrap().to_path_buf()
+    }
+
+    #[test]
+    fn directories_open_and_close_in_place() {
+        let dir = project();
+        let mut tree = DirTree::new(dir.path());
+        assert_eq!(rows(&tree), ["alpha", "Beta"]);
+        assert_eq!(tree.selected_path(), Some(dir.path()));
+
+        tree.move_selection(1);
+        tree.expand();
+        assert_eq!(rows(&tree), ["alpha", "  inner", "Beta"]);
+        assert_eq!(selected(&tree, &dir), Path::new("alpha"));
+
+        // Expanding an open directory steps into it.
+        tree.expand();
+        assert_eq!(selected(&tree, &dir), Path::new("alpha/inner"));
+
+        // Collapsing a closed one moves to its parent, then closes that.
+        tree.collapse();
+        assert_eq!(selected(&tree, &dir), Path::new("alpha"));
+        tree.collapse();
+        assert_eq!(rows(&tree), ["alpha", "Beta"]);
+        assert_eq!(selected(&tree, &dir), Path::new("alpha"));
+    }
+
+    #[test]
+    fn the_selection_stays_within_the_rows() {
+        let dir = project();
+        let mut tree = DirTree::new(dir.path());
+        tree.move_selection(10);
+        assert_eq!(selected(&tree, &dir), Path::new("Beta"));
+        tree.move_selection(-10);
+        assert_eq!(tree.selected_path(), Some(dir.path()));
+        tree.select_last();
+        assert_eq!(selected(&tree, &dir), Path::new("Beta"));
+        tree.select_first();
+        assert_eq!(tree.selected_path(), Some(dir.path()));
+    }
+
+    #[test]
+    fn hidden_directories_show_on_request_and_keep_the_selection() {
+        let dir = project();
+        let mut tree = DirTree::new(dir.path());
+        tree.select_last();
+        tree.toggle_hidden();
+        assert_eq!(rows(&tree), [".hidden", "alpha", "Beta"]);
+        assert_eq!(selected(&tree, &dir), Path::new("Beta"));
+        tree.toggle_hidden();
+        assert_eq!(rows(&tree), ["alpha", "Beta"]);
+    }
+
+    #[test]
+    fn collapsing_the_root_moves_up_a_level() {
+        let dir = project();
+        let root = dir.path().join("alpha");
+        let mut tree = DirTree::new(&root);
+        assert_eq!(rows(&tree), ["inner"]);
+
+        tree.collapse();
+        assert_eq!(tree.selected_path(), Some(root.as_path()));
+        assert_eq!(tree.rows[0].path, dir.path());
+        assert_eq!(rows(&tree), ["alpha", "  inner", "Beta"]);
+    }
+}
@@ -0,0 +1,162 @@
+//! Git setup overlay — shown before generation when the project isn't a
+//! repository variants can branch from, offering to fix that or cancel.
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use uniq_core::git::RepoStatus;
+
+use crate::action::Action;
+use crate::components::Component;
+use crate::theme::Theme;
+
+pub struct GitSetupComponent {
+    /// Whether the dialog is visible.
+    pub visible: bool,
+    /// What's wrong with the repository.
+    status: RepoStatus,
+    /// Project directory, as shown to the user.
+    path: String,
+}
+
+impl GitSetupComponent {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            status: RepoStatus::Clean,
+            path: String::new(),
+        }
+    }
+
+    fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+        let vertical = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(area);
+
+        let horizontal = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(width),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(vertical[1]);
+
+        horizontal[1]
+    }
+
+    fn dirty(&self) -> bool {
+        matches!(self.status, RepoStatus::Dirty(_))
+    }
+}
+
+impl Default for GitSetupComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for GitSetupComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if let Action::OpenGitSetup { status, path } = action {
+            self.visible = true;
+            self.status = status.clone();
+            self.path = path.clone();
+            return None;
+        }
+
+        if !self.visible {
+            return None;
+        }
+
+        match action {
+            Action::NewlineInput | Action::SubmitForm => {
+                self.visible = false;
+                Some(Action::CommitBaseline)
+            }
+            Action::CharInput('c') if self.dirty() => {
+                self.visible = false;
+                Some(Action::GenerateFromLastCommit)
+            }
+            Action::Escape => {
+                self.visible = false;
+                Some(Action::SetStatus(
+                    t!("app.generation_cancelled").to_string(),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let (message, commit) = match &self.status {
+            RepoStatus::NotARepository => (
+                t!("git_setup.not_a_repository", path = self.path),
+                t!("git_setup.init"),
+            ),
+            RepoStatus::NoCommits => (
+                t!("git_setup.no_commits").to_string(),
+                t!("git_setup.commit"),
+            ),
+            _ => (
+                t!("git_setup.dirty").to_string(),
+                t!("git_setup.commit_changes"),
+            ),
+        };
+
+        let mut lines = vec![Line::from(""), Line::from(message)];
+        if let RepoStatus::Dirty(paths) = &self.status {
+            for path in paths {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", path),
+                    Theme::dim(),
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+        let mut hints = vec![
+            Span::styled("enter", Theme::key_hint()),
+            Span::styled(format!(" {}  ", commit), Theme::dim()),
+        ];
+        if self.dirty() {
+            hints.push(Span::styled("c", Theme::key_hint()));
+            hints.push(Span::styled(
+                format!(" {}  ", t!("git_setup.continue")),
+                Theme::dim(),
+            ));
+        }
+        hints.push(Span::styled("esc", Theme::key_hint()));
+        hints.push(Span::styled(
+            format!(" {}", t!("hint.cancel")),
+            Theme::dim(),
+        ));
+        lines.push(Line::from(hints));
+
+        let width = area.width.saturating_sub(4).min(72);
+        let height = (lines.len() as u16 + 4).min(area.height);
+        let dialog = Self::centered_rect(area, width, height);
+        frame.render_widget(Clear, dialog);
+
+        let block = Block::default()
+            .title(format!(" {} ", t!("git_setup.title")))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::warning()));
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            dialog,
+        );
+    }
+}
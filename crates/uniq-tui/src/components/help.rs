@@ -1,4 +1,5 @@
-//! Help overlay — keybinding reference.
+//! Help overlay — the keybindings for the current phase or dialog, read from
+//! the active keymap so rebound keys show up as configured.
 
 use ratatui::layout::{Constraint, Flex, Layout, Rect};
 use ratatui::style::Style;
@@ -6,17 +7,181 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
-use crate::action::Action;
-use crate::components::Component;
+use crate::action::{Action, Phase};
+use crate::components::{Component, TextScroll};
+use crate::keymap::Keymap;
 use crate::theme::Theme;
 
+/// What the help is about: the phase on screen or the dialog over it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpContext {
+    Phase(Phase),
+    MergeDialog,
+    ConflictViewer,
+}
+
+impl HelpContext {
+    fn title(&self) -> String {
+        let context = match self {
+            HelpContext::Phase(phase) => phase.label(),
+            HelpContext::MergeDialog => t!("help.merge_title").to_string(),
+            HelpContext::ConflictViewer => t!("help.conflicts_title").to_string(),
+        };
+        format!(" {} ", t!("help.title", context = context))
+    }
+
+    /// Catalog section for this context's notes and binding descriptions.
+    fn section(&self) -> &'static str {
+        match self {
+            HelpContext::Phase(Phase::ProjectIntake) => "intake",
+            HelpContext::Phase(Phase::ResearchDiscovery) => "research",
+            HelpContext::Phase(Phase::TechniqueSelection) => "techniques",
+            HelpContext::Phase(Phase::VariantGeneration) => "generation",
+            HelpContext::Phase(Phase::Benchmarking) => "benchmark",
+            HelpContext::MergeDialog => "merge",
+            HelpContext::ConflictViewer => "conflicts",
+        }
+    }
+
+    /// Notes that aren't key bindings, e.g. how text fields work.
+    fn notes(&self) -> &'static [&'static str] {
+        match self {
+            HelpContext::Phase(Phase::ProjectIntake) => &["note_1", "note_2", "note_3", "note_4"],
+            HelpContext::Phase(Phase::ResearchDiscovery)
+            | HelpContext::Phase(Phase::VariantGeneration) => &["note_1"],
+            _ => &[],
+        }
+    }
+
+    /// Bindings that matter here, described under this context's section.
+    fn bindings(&self) -> &'static [&'static str] {
+        match self {
+            HelpContext::Phase(Phase::ProjectIntake) => &["dir_tree", "external_editor"],
+            HelpContext::Phase(Phase::ResearchDiscovery) => {
+                &["confirm", "search", "copy", "grow_detail", "shrink_detail"]
+            }
+            HelpContext::Phase(Phase::TechniqueSelection) => &[
+                "confirm",
+                "reextract",
+                "compare",
+                "select_all",
+                "select_none",
+                "invert_selection",
+                "select_top",
+                "complexity_filter",
+                "known_deps_filter",
+                "group_by_paper",
+                "consolidate",
+                "drop_duplicates",
+                "export_markdown",
+                "export_json",
+                "search",
+                "copy",
+                "grow_detail",
+                "shrink_detail",
+            ],
+            HelpContext::Phase(Phase::VariantGeneration) => &[
+                "confirm",
+                "approve_plan",
+                "cancel",
+                "regenerate",
+                "rename",
+                "compare",
+                "export_patches",
+                "delete",
+                "conflicts",
+                "search",
+                "copy",
+            ],
+            HelpContext::Phase(Phase::Benchmarking) => &[
+                "confirm",
+                "promote",
+                "timeout",
+                "history",
+                "judge_explanation",
+                "compare",
+                "leaderboard",
+                "suggested_merge",
+                "sort",
+                "reverse_sort",
+                "reload_weights",
+                "export_report",
+                "export_report_html",
+                "search",
+                "copy",
+                "grow_detail",
+                "shrink_detail",
+            ],
+            HelpContext::MergeDialog => &[
+                "scroll_up",
+                "scroll_down",
+                "prev_phase",
+                "next_phase",
+                "add_merge_source",
+                "remove_merge_source",
+                "merge_components",
+                "confirm",
+                "close",
+            ],
+            HelpContext::ConflictViewer => &[
+                "scroll_up",
+                "scroll_down",
+                "prev_phase",
+                "next_phase",
+                "reconcile",
+                "confirm",
+                "close",
+            ],
+        }
+    }
+
+    /// This context's text for `key`, e.g. a binding's description.
+    fn text(&self, key: &str) -> &'static str {
+        t!(&format!("help.{}.{}", self.section(), key))
+    }
+}
+
+/// Bindings shown in every phase.
+const GLOBAL_BINDINGS: &[&str] = &[
+    "quit",
+    "help",
+    "next_phase",
+    "prev_phase",
+    "focus_next",
+    "focus_prev",
+    "scroll_up",
+    "scroll_down",
+    "scroll_top",
+    "scroll_bottom",
+    "page_up",
+    "page_down",
+    "confirm",
+    "merge",
+    "close",
+    "cycle_theme",
+    "status_log",
+    "timeline",
+    "usage",
+    "sidebar",
+    "command_palette",
+    "command",
+];
+
 pub struct HelpComponent {
     pub visible: bool,
+    pub context: HelpContext,
+    pub keymap: Keymap,
+    scroll: TextScroll,
 }
 
 impl HelpComponent {
-    pub fn new() -> Self {
-        Self { visible: false }
+    pub fn new(keymap: Keymap) -> Self {
+        Self {
+            visible: false,
+            context: HelpContext::Phase(Phase::ProjectIntake),
+            keymap,
+            scroll: TextScroll::default(),
+        }
     }
 
     fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
@@ -38,22 +203,81 @@ impl HelpComponent {
 
         horizontal[1]
     }
+
+    /// A row for `name`, or `None` if it has no keys.
+    fn binding_line(&self, name: &str, description: &str) -> Option<Line<'static>> {
+        let keys = self.keymap.key_labels(name);
+        if keys.is_empty() {
+            return None;
+        }
+        Some(key_line(&keys.join(" / "), description))
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from("")];
+        for note in self.context.notes() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", self.context.text(note)),
+                Theme::dim(),
+            )));
+        }
+        lines.extend(
+            self.context
+                .bindings()
+                .iter()
+                .filter_map(|name| self.binding_line(name, self.context.text(name))),
+        );
+
+        if let HelpContext::Phase(_) = self.context {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("── {} ──", t!("help.everywhere")),
+                Theme::header(),
+            )));
+            lines.push(Line::from(""));
+            let phases: Vec<String> = (1..=Phase::all().len())
+                .filter_map(|n| {
+                    let keys = self.keymap.key_labels(&format!("phase_{}", n));
+                    keys.first().cloned()
+                })
+                .collect();
+            if !phases.is_empty() {
+                lines.push(key_line(&phases.join(" "), t!("help.jump_to_phase")));
+            }
+            for name in GLOBAL_BINDINGS {
+                if let Some(binding) = self.keymap.get(name) {
+                    lines.extend(self.binding_line(name, binding.description));
+                }
+            }
+        }
+        lines
+    }
+}
+
+impl Default for HelpComponent {
+    fn default() -> Self {
+        Self::new(Keymap::default())
+    }
 }
 
 impl Component for HelpComponent {
     fn handle_action(&mut self, action: &Action) -> Option<Action> {
-        match action {
-            Action::ToggleHelp => {
-                self.visible = !self.visible;
-                None
-            }
-            _ if self.visible => {
-                // Any key closes help.
-                self.visible = false;
-                None
-            }
-            _ => None,
+        if let Action::ToggleHelp = action {
+            self.visible = !self.visible;
+            self.scroll.reset();
+            return None;
+        }
+        if !self.visible {
+            return None;
         }
+
+        if self.scroll.handle(action) {
+            return None;
+        }
+        if let Action::Confirm | Action::Escape = action {
+            self.visible = false;
+        }
+        None
     }
 
     fn render(&self, frame: &mut Frame, area: Rect) {
@@ -61,44 +285,44 @@ impl Component for HelpComponent {
             return;
         }
 
-        let dialog = Self::centered_rect(area, 55, 22);
+        let lines = self.lines();
+        let height = (lines.len() as u16 + 3).min(area.height);
+        let dialog = Self::centered_rect(area, 62.min(area.width), height);
         frame.render_widget(Clear, dialog);
 
-        let block = Block::default()
-            .title(" Help — Keybindings ")
+        // Keep the last page full when scrolled to the bottom.
+        let visible = height.saturating_sub(2) as usize;
+        let overflow = lines.len().saturating_sub(visible);
+        let scroll = self.scroll.offset(lines.len(), visible);
+
+        let mut block = Block::default()
+            .title(self.context.title())
             .title_style(Theme::title())
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Theme::accent()));
+        if overflow > 0 {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(
+                        " {} ",
+                        t!("help.scroll", scroll = scroll, overflow = overflow)
+                    ),
+                    Theme::dim(),
+                ))
+                .right_aligned(),
+            );
+        }
 
-        let help_text = vec![
-            Line::from(""),
-            key_line("q / Ctrl+C", "Quit"),
-            key_line("?", "Toggle this help"),
-            key_line("1-5", "Jump to phase"),
-            key_line("Left / Right", "Previous / next phase"),
-            key_line("Tab / Shift+Tab", "Next / previous phase"),
-            key_line("Up / Down / j / k", "Scroll / select"),
-            key_line("Enter", "Confirm / toggle"),
-            key_line("m", "Open merge dialog"),
-            key_line("Esc", "Close dialog"),
-            Line::from(""),
-            Line::from(Span::styled("── Phase-specific ──", Theme::header())),
-            Line::from(""),
-            key_line("Phase 1", "Enter path & description, then Enter"),
-            key_line("Phase 2", "Auto-searches after Phase 1"),
-            key_line("Phase 3", "Space/Enter to toggle technique selection"),
-            key_line("Phase 4", "Auto-generates after Phase 3"),
-            key_line("Phase 5", "View benchmarks, rate variants"),
-        ];
-
-        let paragraph = Paragraph::new(help_text).block(block);
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll as u16, 0));
         frame.render_widget(paragraph, dialog);
     }
 }
 
-fn key_line<'a>(key: &'a str, desc: &'a str) -> Line<'a> {
+fn key_line(key: &str, desc: &str) -> Line<'static> {
     Line::from(vec![
         Span::styled(format!("  {:<22}", key), Theme::selected()),
-        Span::styled(desc, Theme::normal()),
+        Span::styled(desc.to_string(), Theme::normal()),
     ])
 }
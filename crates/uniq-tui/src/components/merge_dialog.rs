@@ -1,4 +1,4 @@
-//! Merge Dialog — overlay for selecting two variants and a blend ratio.
+//! Merge Dialog — overlay for selecting source variants and their blend ratios.
 
 use ratatui::layout::{Constraint, Flex, Layout, Rect};
 use ratatui::style::{Modifier, Style};
@@ -7,37 +7,75 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
 use crate::action::Action;
-use crate::components::Component;
+use crate::components::{truncate, Component};
 use crate::theme::Theme;
 
-use uniq_core::merge::BlendRatio;
-use uniq_core::variant::Variant;
+use uniq_core::merge::{
+    BlendRatio, ComponentBlend, MergePreview, MergeSource, MergeStrategy, MAX_MERGE_SOURCES,
+};
+use uniq_core::variant::{Variant, VariantId};
 
 /// Which field in the merge dialog is focused.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum MergeField {
-    VariantA,
-    VariantB,
-    BlendSlider,
+    /// Variant selector of the source at this index.
+    Variant(usize),
+    /// Blend ratio of the source at this index.
+    Blend(usize),
+    /// Blend of one key component of a source, in advanced mode.
+    Component(usize, usize),
+    /// How the techniques are fitted together.
+    Strategy,
+    /// Free-form guidance text.
+    Guidance,
+}
+
+impl MergeField {
+    fn source(self) -> Option<usize> {
+        match self {
+            MergeField::Variant(i) | MergeField::Blend(i) | MergeField::Component(i, _) => Some(i),
+            MergeField::Strategy | MergeField::Guidance => None,
+        }
+    }
+}
+
+/// A variant that can be picked as a merge source.
+#[derive(Debug, Clone)]
+pub struct MergeCandidate {
+    pub id: String,
+    pub name: String,
+    /// Key components of the variant's technique, blendable one by one.
+    pub components: Vec<String>,
+}
+
+/// One source variant picked in the dialog.
+#[derive(Debug, Clone)]
+pub struct MergeSourceField {
+    /// Index into `available_variants`.
+    pub variant_idx: usize,
+    pub blend: BlendRatio,
+    /// Per-component blends, parallel to the candidate's `components`.
+    pub components: Vec<BlendRatio>,
 }
 
 pub struct MergeDialogComponent {
     /// Whether the dialog is visible.
     pub visible: bool,
     /// Available variants to merge.
-    pub available_variants: Vec<(String, String)>, // (id, display_name)
-    /// Selected index for variant A.
-    pub variant_a_idx: usize,
-    /// Selected index for variant B.
-    pub variant_b_idx: usize,
-    /// Current blend ratio for A.
-    pub blend_a: BlendRatio,
-    /// Current blend ratio for B.
-    pub blend_b: BlendRatio,
+    pub available_variants: Vec<MergeCandidate>,
+    /// Selected sources, between 2 and `MAX_MERGE_SOURCES`.
+    pub sources: Vec<MergeSourceField>,
     /// Which field is focused.
     focused: MergeField,
-    /// Whether a merge is in progress.
-    pub merging: bool,
+    /// Whether key components are blended individually.
+    pub advanced: bool,
+    /// Integration strategy; unset leaves it to the model.
+    pub strategy: Option<MergeStrategy>,
+    /// Instructions for combining the sources, beyond what blends express.
+    pub guidance: String,
+    /// Predicted conflicts for the picked variants. Enter previews first
+    /// and only merges once the preview is showing.
+    pub preview: Option<MergePreview>,
 }
 
 impl MergeDialogComponent {
@@ -45,12 +83,18 @@ impl MergeDialogComponent {
         Self {
             visible: false,
             available_variants: Vec::new(),
-            variant_a_idx: 0,
-            variant_b_idx: 1,
-            blend_a: BlendRatio::Half,
-            blend_b: BlendRatio::Half,
-            focused: MergeField::VariantA,
-            merging: false,
+            sources: (0..2)
+                .map(|i| MergeSourceField {
+                    variant_idx: i,
+                    blend: BlendRatio::Half,
+                    components: Vec::new(),
+                })
+                .collect(),
+            focused: MergeField::Variant(0),
+            advanced: false,
+            strategy: None,
+            guidance: String::new(),
+            preview: None,
         }
     }
 
@@ -58,8 +102,283 @@ impl MergeDialogComponent {
     pub fn set_variants(&mut self, variants: &[Variant]) {
         self.available_variants = variants
             .iter()
-            .map(|v| (v.id.0.clone(), v.display_name.clone()))
+            .map(|v| MergeCandidate {
+                id: v.id.0.clone(),
+                name: v.display_name.clone(),
+                components: v
+                    .technique()
+                    .map(|t| t.key_components.clone())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let last = self.available_variants.len().saturating_sub(1);
+        for i in 0..self.sources.len() {
+            self.sources[i].variant_idx = self.sources[i].variant_idx.min(last);
+            self.reset_components(i);
+        }
+    }
+
+    /// Pick the given sources, with their component blends. Variants that
+    /// aren't available to merge are skipped.
+    fn prefill(&mut self, sources: &[MergeSource]) {
+        let picked: Vec<MergeSourceField> = sources
+            .iter()
+            .take(MAX_MERGE_SOURCES)
+            .filter_map(|source| {
+                let variant_idx = self
+                    .available_variants
+                    .iter()
+                    .position(|c| c.id == source.variant.0)?;
+                let components = self.available_variants[variant_idx]
+                    .components
+                    .iter()
+                    .map(|name| {
+                        source
+                            .components
+                            .iter()
+                            .find(|c| &c.name == name)
+                            .map_or(source.blend, |c| c.blend)
+                    })
+                    .collect();
+                Some(MergeSourceField {
+                    variant_idx,
+                    blend: source.blend,
+                    components,
+                })
+            })
+            .collect();
+        if picked.len() >= 2 {
+            self.advanced = sources.iter().any(|s| !s.components.is_empty());
+            self.sources = picked;
+        }
+    }
+
+    /// Set every component blend of source `index` to its overall blend.
+    fn reset_components(&mut self, index: usize) {
+        let source = &mut self.sources[index];
+        let count = self
+            .available_variants
+            .get(source.variant_idx)
+            .map_or(0, |c| c.components.len());
+        source.components = vec![source.blend; count];
+    }
+
+    /// Component names of source `index`.
+    fn component_names(&self, index: usize) -> &[String] {
+        self.available_variants
+            .get(self.sources[index].variant_idx)
+            .map_or(&[], |c| c.components.as_slice())
+    }
+
+    /// Switch per-component blending on or off.
+    fn toggle_advanced(&mut self) {
+        self.advanced = !self.advanced;
+        if self.advanced {
+            for i in 0..self.sources.len() {
+                self.reset_components(i);
+            }
+        } else if let MergeField::Component(i, _) = self.focused {
+            self.focused = MergeField::Blend(i);
+        }
+    }
+
+    /// Step through "model decides" and each strategy, stopping at the ends.
+    fn cycle_strategy(&mut self, step: isize) {
+        let options: Vec<Option<MergeStrategy>> = std::iter::once(None)
+            .chain(MergeStrategy::ALL.into_iter().map(Some))
             .collect();
+        let current = options
+            .iter()
+            .position(|s| *s == self.strategy)
+            .unwrap_or(0);
+        let next = current.saturating_add_signed(step).min(options.len() - 1);
+        self.strategy = options[next];
+    }
+
+    /// Field after `Blend(i)` when moving down.
+    fn after_blend(&self, index: usize) -> MergeField {
+        if self.advanced && !self.component_names(index).is_empty() {
+            MergeField::Component(index, 0)
+        } else if index + 1 < self.sources.len() {
+            MergeField::Variant(index + 1)
+        } else {
+            MergeField::Strategy
+        }
+    }
+
+    /// Last field of source `index` when moving up.
+    fn last_field(&self, index: usize) -> MergeField {
+        match self.component_names(index).len() {
+            n if self.advanced && n > 0 => MergeField::Component(index, n - 1),
+            _ => MergeField::Blend(index),
+        }
+    }
+
+    /// Source label: A, B, C, D.
+    fn label(index: usize) -> char {
+        (b'A' + index as u8) as char
+    }
+
+    /// Whether the guidance field is focused and takes typed input.
+    pub fn wants_input(&self) -> bool {
+        self.visible && self.focused == MergeField::Guidance
+    }
+
+    /// Ids of the picked variants, in source order.
+    fn picked_ids(&self) -> Vec<String> {
+        self.sources
+            .iter()
+            .filter_map(|s| self.available_variants.get(s.variant_idx))
+            .map(|v| v.id.clone())
+            .collect()
+    }
+
+    /// Preview the merge, or start it once the preview is showing.
+    fn confirm(&mut self) -> Option<Action> {
+        if self.available_variants.len() < 2 {
+            return Some(Action::SetStatus(t!("merge.too_few").to_string()));
+        }
+        let mut picked: Vec<usize> = self.sources.iter().map(|s| s.variant_idx).collect();
+        picked.sort_unstable();
+        picked.dedup();
+        if picked.len() < self.sources.len() {
+            return Some(Action::SetStatus(t!("merge.same_variant").to_string()));
+        }
+        if self.preview.is_none() {
+            return Some(Action::PreviewMerge {
+                variant_ids: self.picked_ids(),
+            });
+        }
+
+        self.visible = false;
+        self.preview = None;
+
+        let guidance = std::mem::take(&mut self.guidance);
+        Some(Action::StartMerge {
+            sources: self
+                .sources
+                .iter()
+                .map(|s| {
+                    let candidate = &self.available_variants[s.variant_idx];
+                    let mut source = MergeSource::new(VariantId::new(&candidate.id), s.blend);
+                    if self.advanced {
+                        source.components = candidate
+                            .components
+                            .iter()
+                            .zip(&s.components)
+                            .map(|(name, blend)| ComponentBlend {
+                                name: name.clone(),
+                                blend: *blend,
+                            })
+                            .collect();
+                    }
+                    source
+                })
+                .collect(),
+            guidance: Some(guidance.trim().to_string()).filter(|g| !g.is_empty()),
+            strategy: self.strategy,
+        })
+    }
+
+    /// Add a source, preferring a variant that isn't already picked.
+    fn add_source(&mut self) {
+        if self.sources.len() >= MAX_MERGE_SOURCES {
+            return;
+        }
+        let variant_idx = (0..self.available_variants.len())
+            .find(|i| !self.sources.iter().any(|s| s.variant_idx == *i))
+            .unwrap_or(0);
+        self.sources.push(MergeSourceField {
+            variant_idx,
+            blend: BlendRatio::Half,
+            components: Vec::new(),
+        });
+        self.reset_components(self.sources.len() - 1);
+        self.focused = MergeField::Variant(self.sources.len() - 1);
+    }
+
+    /// Remove the focused source; a merge always keeps two.
+    fn remove_source(&mut self) {
+        if self.sources.len() <= 2 {
+            return;
+        }
+        let Some(index) = self.focused.source() else {
+            return;
+        };
+        self.sources.remove(index);
+        self.focused = MergeField::Variant(index.min(self.sources.len() - 1));
+    }
+
+    /// Files shown in the preview before collapsing the rest.
+    const PREVIEW_FILES: usize = 5;
+
+    fn preview_height(&self) -> u16 {
+        self.preview.as_ref().map_or(0, |p| {
+            let files = p.shared.len().min(Self::PREVIEW_FILES + 1);
+            3 + files as u16
+        })
+    }
+
+    fn preview_lines(preview: &MergePreview) -> Vec<Line<'static>> {
+        let conflicts = preview.conflicts();
+        let summary_style = if conflicts > 0 {
+            Style::default().fg(Theme::error())
+        } else if preview.shared.is_empty() {
+            Style::default().fg(Theme::success())
+        } else {
+            Style::default().fg(Theme::warning())
+        };
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                format!(
+                    " {}, {}",
+                    if preview.shared.len() == 1 {
+                        t!("merge.shared_file").to_string()
+                    } else {
+                        t!("merge.shared_files", count = preview.shared.len())
+                    },
+                    if conflicts == 1 {
+                        t!("merge.likely_conflict").to_string()
+                    } else {
+                        t!("merge.likely_conflicts", count = conflicts)
+                    },
+                ),
+                summary_style,
+            ),
+            Span::styled(
+                format!("  ({})", t!("merge.unshared", count = preview.unshared)),
+                Theme::dim(),
+            ),
+        ])];
+
+        let shown = if preview.shared.len() > Self::PREVIEW_FILES + 1 {
+            Self::PREVIEW_FILES
+        } else {
+            preview.shared.len()
+        };
+        for file in &preview.shared[..shown] {
+            let (mark, color) = if file.conflict {
+                ("✗", Theme::error())
+            } else {
+                ("~", Theme::warning())
+            };
+            let sources: String = file.sources.iter().map(|i| Self::label(*i)).collect();
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {} ", mark), Style::default().fg(color)),
+                Span::styled(file.path.clone(), Theme::normal()),
+                Span::styled(format!("  {}", sources), Theme::dim()),
+            ]));
+        }
+        if shown < preview.shared.len() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "   {}",
+                    t!("merge.more_files", count = preview.shared.len() - shown)
+                ),
+                Theme::dim(),
+            )));
+        }
+        lines
     }
 
     /// Center a rectangle inside another.
@@ -83,134 +402,225 @@ impl MergeDialogComponent {
         horizontal[1]
     }
 
-    /// Render the blend slider visualization.
-    fn render_blend_bar(
-        blend_a: &BlendRatio,
-        _blend_b: &BlendRatio,
-        width: usize,
-    ) -> Line<'static> {
-        let pct_a = blend_a.as_percent() as usize;
-        let filled_a = (width * pct_a) / 100;
-        let filled_b = width - filled_a;
+    /// Split `width` cells between the blends in proportion to their
+    /// percentages. All-zero blends share the bar equally.
+    fn segment_widths(blends: &[BlendRatio], width: usize) -> Vec<usize> {
+        let percents: Vec<usize> = blends.iter().map(|b| b.as_percent() as usize).collect();
+        let total: usize = percents.iter().sum();
+        if blends.is_empty() {
+            return Vec::new();
+        }
+        let mut widths: Vec<usize> = if total == 0 {
+            vec![width / blends.len(); blends.len()]
+        } else {
+            percents.iter().map(|p| width * p / total).collect()
+        };
+        // Give the cells lost to rounding to the largest segment.
+        let used: usize = widths.iter().sum();
+        if let Some(largest) = (0..widths.len()).max_by_key(|i| (widths[*i], usize::MAX - i)) {
+            widths[largest] += width - used;
+        }
+        widths
+    }
 
-        let bar_a = "\u{2588}".repeat(filled_a);
-        let bar_b = "\u{2591}".repeat(filled_b);
+    /// Render the blend bar: one stacked segment per source.
+    fn render_blend_bar(blends: &[BlendRatio], width: usize) -> Line<'static> {
+        let widths = Self::segment_widths(blends, width);
+        let mut spans = Vec::new();
+        for (i, w) in widths.into_iter().enumerate() {
+            let color = Theme::blend(i);
+            let glyph = if i % 2 == 0 { "\u{2588}" } else { "\u{2593}" };
+            spans.push(Span::styled(glyph.repeat(w), Style::default().fg(color)));
+        }
+        Line::from(spans)
+    }
 
-        Line::from(vec![
-            Span::styled(
-                "A ",
-                Style::default()
-                    .fg(Theme::blend_a())
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(bar_a, Style::default().fg(Theme::blend_a())),
-            Span::styled(bar_b, Style::default().fg(Theme::blend_b())),
-            Span::styled(
-                " B",
-                Style::default()
-                    .fg(Theme::blend_b())
-                    .add_modifier(Modifier::BOLD),
-            ),
-        ])
+    /// Rows for source `index`: its selector plus one per component in
+    /// advanced mode.
+    fn source_height(&self, index: usize) -> u16 {
+        if self.advanced {
+            3 + self.component_names(index).len().max(1) as u16
+        } else {
+            3
+        }
+    }
+
+    fn field_style(&self, field: MergeField) -> Style {
+        if self.focused == field {
+            Style::default()
+                .fg(Theme::accent())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Theme::normal()
+        }
+    }
+}
+
+impl Default for MergeDialogComponent {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Component for MergeDialogComponent {
     fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if let Action::MergePreviewed {
+            variant_ids,
+            preview,
+        } = action
+        {
+            // Previews finish in the background, by which time the user
+            // may have picked other variants.
+            if self.visible && *variant_ids == self.picked_ids() {
+                self.preview = Some(preview.clone());
+            }
+            return None;
+        }
+
         if !self.visible {
-            if matches!(action, Action::OpenMergeDialog) {
-                self.visible = true;
-                self.focused = MergeField::VariantA;
+            match action {
+                Action::OpenMergeDialog => {}
+                Action::PrefillMerge {
+                    sources,
+                    guidance,
+                    strategy,
+                } => {
+                    self.prefill(sources);
+                    self.guidance = guidance.clone().unwrap_or_default();
+                    self.strategy = *strategy;
+                }
+                _ => return None,
             }
+            self.visible = true;
+            self.focused = MergeField::Variant(0);
+            self.preview = None;
             return None;
         }
 
+        let last_source = self.sources.len() - 1;
+        if self.focused == MergeField::Guidance {
+            match action {
+                Action::CharInput(c) => {
+                    self.guidance.push(*c);
+                    return None;
+                }
+                Action::BackspaceInput => {
+                    self.guidance.pop();
+                    return None;
+                }
+                Action::DeleteWord => {
+                    let trimmed = self.guidance.trim_end().len();
+                    self.guidance.truncate(trimmed);
+                    let start = self.guidance.rfind(' ').map(|i| i + 1).unwrap_or(0);
+                    self.guidance.truncate(start);
+                    return None;
+                }
+                Action::PasteBulk(text) => {
+                    self.guidance
+                        .push_str(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+                    return None;
+                }
+                Action::NewlineInput | Action::SubmitForm => return self.confirm(),
+                Action::SwitchInputField => {
+                    self.focused = MergeField::Variant(0);
+                    return None;
+                }
+                _ => {}
+            }
+        }
+
         match action {
-            Action::CloseMergeDialog => {
+            Action::Escape => {
                 self.visible = false;
                 None
             }
+            Action::AddMergeSource => {
+                self.add_source();
+                self.preview = None;
+                None
+            }
+            Action::RemoveMergeSource => {
+                self.remove_source();
+                self.preview = None;
+                None
+            }
+            Action::ToggleMergeComponents => {
+                self.toggle_advanced();
+                None
+            }
             Action::ScrollDown | Action::SelectNext => {
-                match self.focused {
-                    MergeField::VariantA => self.focused = MergeField::VariantB,
-                    MergeField::VariantB => self.focused = MergeField::BlendSlider,
-                    MergeField::BlendSlider => {}
-                }
+                self.focused = match self.focused {
+                    MergeField::Variant(i) => MergeField::Blend(i),
+                    MergeField::Blend(i) => self.after_blend(i),
+                    MergeField::Component(i, k) if k + 1 < self.component_names(i).len() => {
+                        MergeField::Component(i, k + 1)
+                    }
+                    MergeField::Component(i, _) if i < last_source => MergeField::Variant(i + 1),
+                    MergeField::Component(..) => MergeField::Strategy,
+                    MergeField::Strategy => MergeField::Guidance,
+                    field => field,
+                };
                 None
             }
             Action::ScrollUp | Action::SelectPrev => {
-                match self.focused {
-                    MergeField::VariantA => {}
-                    MergeField::VariantB => self.focused = MergeField::VariantA,
-                    MergeField::BlendSlider => self.focused = MergeField::VariantB,
-                }
+                self.focused = match self.focused {
+                    MergeField::Blend(i) => MergeField::Variant(i),
+                    MergeField::Component(i, 0) => MergeField::Blend(i),
+                    MergeField::Component(i, k) => MergeField::Component(i, k - 1),
+                    MergeField::Variant(i) if i > 0 => self.last_field(i - 1),
+                    MergeField::Strategy => self.last_field(last_source),
+                    MergeField::Guidance => MergeField::Strategy,
+                    field => field,
+                };
                 None
             }
             Action::NextPhase => {
                 // Right arrow: cycle selection or increase blend
                 match self.focused {
-                    MergeField::VariantA => {
-                        if self.variant_a_idx + 1 < self.available_variants.len() {
-                            self.variant_a_idx += 1;
+                    MergeField::Variant(i) => {
+                        let source = &mut self.sources[i];
+                        if source.variant_idx + 1 < self.available_variants.len() {
+                            source.variant_idx += 1;
+                            self.reset_components(i);
+                            self.preview = None;
                         }
                     }
-                    MergeField::VariantB => {
-                        if self.variant_b_idx + 1 < self.available_variants.len() {
-                            self.variant_b_idx += 1;
-                        }
+                    MergeField::Blend(i) => {
+                        self.sources[i].blend = self.sources[i].blend.next();
                     }
-                    MergeField::BlendSlider => {
-                        self.blend_a = self.blend_a.next();
-                        self.blend_b = self.blend_a.prev(); // Inverse
+                    MergeField::Component(i, k) => {
+                        let blend = &mut self.sources[i].components[k];
+                        *blend = blend.next();
                     }
+                    MergeField::Strategy => self.cycle_strategy(1),
+                    MergeField::Guidance => {}
                 }
                 None
             }
             Action::PrevPhase => {
                 // Left arrow
                 match self.focused {
-                    MergeField::VariantA => {
-                        if self.variant_a_idx > 0 {
-                            self.variant_a_idx -= 1;
+                    MergeField::Variant(i) => {
+                        let source = &mut self.sources[i];
+                        if source.variant_idx > 0 {
+                            source.variant_idx -= 1;
+                            self.reset_components(i);
+                            self.preview = None;
                         }
                     }
-                    MergeField::VariantB => {
-                        if self.variant_b_idx > 0 {
-                            self.variant_b_idx -= 1;
-                        }
+                    MergeField::Blend(i) => {
+                        self.sources[i].blend = self.sources[i].blend.prev();
                     }
-                    MergeField::BlendSlider => {
-                        self.blend_a = self.blend_a.prev();
-                        self.blend_b = self.blend_a.next();
+                    MergeField::Component(i, k) => {
+                        let blend = &mut self.sources[i].components[k];
+                        *blend = blend.prev();
                     }
+                    MergeField::Strategy => self.cycle_strategy(-1),
+                    MergeField::Guidance => {}
                 }
                 None
             }
-            Action::Confirm => {
-                if self.variant_a_idx == self.variant_b_idx {
-                    return Some(Action::SetStatus(
-                        "Cannot merge a variant with itself".to_string(),
-                    ));
-                }
-                if self.available_variants.len() < 2 {
-                    return Some(Action::SetStatus(
-                        "Need at least 2 variants to merge".to_string(),
-                    ));
-                }
-
-                let va = &self.available_variants[self.variant_a_idx];
-                let vb = &self.available_variants[self.variant_b_idx];
-
-                self.merging = true;
-                self.visible = false;
-
-                Some(Action::StartMerge {
-                    variant_a_id: va.0.clone(),
-                    variant_b_id: vb.0.clone(),
-                    blend_a: self.blend_a.as_percent(),
-                    blend_b: self.blend_b.as_percent(),
-                })
-            }
+            Action::Confirm => self.confirm(),
             _ => None,
         }
     }
@@ -220,13 +630,17 @@ impl Component for MergeDialogComponent {
             return;
         }
 
-        let dialog_area = Self::centered_rect(area, 60, 18);
+        let source_heights: Vec<u16> = (0..self.sources.len())
+            .map(|i| self.source_height(i))
+            .collect();
+        let height = 18 + source_heights.iter().sum::<u16>() + self.preview_height();
+        let dialog_area = Self::centered_rect(area, 60, height.min(area.height));
 
         // Clear the background.
         frame.render_widget(Clear, dialog_area);
 
         let block = Block::default()
-            .title(" Merge Variants ")
+            .title(format!(" {} ", t!("merge.title")))
             .title_style(Theme::title())
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Theme::accent()));
@@ -234,106 +648,202 @@ impl Component for MergeDialogComponent {
         let inner = block.inner(dialog_area);
         frame.render_widget(block, dialog_area);
 
-        let chunks = Layout::vertical([
-            Constraint::Length(3), // Variant A selector
-            Constraint::Length(3), // Variant B selector
-            Constraint::Length(1), // Spacer
-            Constraint::Length(3), // Blend slider
-            Constraint::Length(1), // Blend label
-            Constraint::Length(1), // Spacer
-            Constraint::Length(2), // Instructions
-        ])
-        .split(inner);
+        let mut constraints: Vec<Constraint> = source_heights
+            .iter()
+            .map(|h| Constraint::Length(*h))
+            .collect(); // Source selectors
+        constraints.extend([
+            Constraint::Length(1),                     // Spacer
+            Constraint::Length(3),                     // Blend bar
+            Constraint::Length(1),                     // Blend label
+            Constraint::Length(3),                     // Strategy
+            Constraint::Length(3),                     // Guidance
+            Constraint::Length(self.preview_height()), // Merge preview
+            Constraint::Length(1),                     // Spacer
+            Constraint::Length(2),                     // Instructions
+        ]);
+        let chunks = Layout::vertical(constraints).split(inner);
+        let n = self.sources.len();
 
-        // Variant A selector
-        let a_style = if self.focused == MergeField::VariantA {
-            Style::default()
-                .fg(Theme::accent())
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Theme::normal()
-        };
-        let a_name = self
-            .available_variants
-            .get(self.variant_a_idx)
-            .map(|(_, name)| name.as_str())
-            .unwrap_or("(none)");
-        let va_block = Block::default()
-            .title(" Source A ")
+        // Source selectors: variant and blend ratio side by side.
+        for (i, source) in self.sources.iter().enumerate() {
+            let focused = self.focused.source() == Some(i);
+            let name = self
+                .available_variants
+                .get(source.variant_idx)
+                .map(|c| c.name.as_str())
+                .unwrap_or(t!("merge.no_variant"));
+            let color = Theme::blend(i);
+            let border = if focused {
+                Style::default().fg(Theme::accent())
+            } else {
+                Theme::normal()
+            };
+            let source_block = Block::default()
+                .title(Span::styled(
+                    format!(" {} ", t!("merge.source", label = Self::label(i))),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(border);
+            let mut lines = vec![Line::from(vec![
+                Span::styled(
+                    format!("< {} >", name),
+                    self.field_style(MergeField::Variant(i)),
+                ),
+                Span::styled(format!("  {} ", t!("merge.blend")), Theme::dim()),
+                Span::styled(
+                    format!("< {} >", source.blend),
+                    self.field_style(MergeField::Blend(i)),
+                ),
+            ])];
+            if self.advanced {
+                let names = self.component_names(i);
+                if names.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        format!("  ({})", t!("merge.no_components")),
+                        Theme::dim(),
+                    )));
+                }
+                for (k, (component, blend)) in names.iter().zip(&source.components).enumerate() {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {} ", truncate(component, 36)), Theme::dim()),
+                        Span::styled(
+                            format!("< {} >", blend),
+                            self.field_style(MergeField::Component(i, k)),
+                        ),
+                    ]));
+                }
+            }
+            frame.render_widget(Paragraph::new(lines).block(source_block), chunks[i]);
+        }
+
+        // Blend bar
+        let blends: Vec<BlendRatio> = self.sources.iter().map(|s| s.blend).collect();
+        let bar_width = (chunks[n + 1].width as usize).saturating_sub(2);
+        let blend_bar = Self::render_blend_bar(&blends, bar_width);
+        let bar_block = Block::default()
+            .title(format!(" {} ", t!("merge.integration_blend")))
             .borders(Borders::ALL)
-            .border_style(a_style);
-        let va_text =
-            Paragraph::new(Span::styled(format!("< {} >", a_name), a_style)).block(va_block);
-        frame.render_widget(va_text, chunks[0]);
+            .border_style(Theme::normal());
+        frame.render_widget(Paragraph::new(blend_bar).block(bar_block), chunks[n + 1]);
 
-        // Variant B selector
-        let b_style = if self.focused == MergeField::VariantB {
-            Style::default()
-                .fg(Theme::accent())
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Theme::normal()
-        };
-        let b_name = self
-            .available_variants
-            .get(self.variant_b_idx)
-            .map(|(_, name)| name.as_str())
-            .unwrap_or("(none)");
-        let vb_block = Block::default()
-            .title(" Source B ")
+        // Blend label
+        let mut label = Vec::new();
+        for (i, source) in self.sources.iter().enumerate() {
+            if i > 0 {
+                label.push(Span::styled(" / ", Theme::dim()));
+            }
+            label.push(Span::styled(
+                format!(" {}: {} ", Self::label(i), source.blend),
+                Style::default().fg(Theme::blend(i)),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(label)), chunks[n + 2]);
+
+        // Strategy
+        let strategy_focused = self.focused == MergeField::Strategy;
+        let strategy_block = Block::default()
+            .title(format!(" {} ", t!("merge.strategy")))
             .borders(Borders::ALL)
-            .border_style(b_style);
-        let vb_text =
-            Paragraph::new(Span::styled(format!("< {} >", b_name), b_style)).block(vb_block);
-        frame.render_widget(vb_text, chunks[1]);
+            .border_style(if strategy_focused {
+                Style::default().fg(Theme::accent())
+            } else {
+                Theme::normal()
+            });
+        let strategy_line = Line::from(Span::styled(
+            format!(
+                "< {} >",
+                self.strategy
+                    .map_or(t!("merge.model_decides"), strategy_label)
+            ),
+            self.field_style(MergeField::Strategy),
+        ));
+        frame.render_widget(
+            Paragraph::new(strategy_line).block(strategy_block),
+            chunks[n + 3],
+        );
 
-        // Blend slider
-        let slider_style = if self.focused == MergeField::BlendSlider {
-            Style::default()
-                .fg(Theme::accent())
-                .add_modifier(Modifier::BOLD)
+        // Guidance
+        let guidance_focused = self.focused == MergeField::Guidance;
+        let guidance_block = Block::default()
+            .title(format!(" {} ", t!("merge.guidance")))
+            .borders(Borders::ALL)
+            .border_style(if guidance_focused {
+                Style::default().fg(Theme::accent())
+            } else {
+                Theme::normal()
+            });
+        let guidance_line = if guidance_focused {
+            let max = (chunks[n + 4].width as usize).saturating_sub(3);
+            let start = self.guidance.chars().count().saturating_sub(max);
+            Line::from(vec![
+                Span::styled(
+                    self.guidance.chars().skip(start).collect::<String>(),
+                    Theme::normal(),
+                ),
+                Span::styled(" ", Style::default().fg(Theme::bg()).bg(Theme::accent())),
+            ])
+        } else if self.guidance.is_empty() {
+            Line::from(Span::styled(t!("merge.guidance_placeholder"), Theme::dim()))
         } else {
-            Theme::normal()
+            Line::from(Span::styled(self.guidance.clone(), Theme::normal()))
         };
-        let bar_width = (chunks[3].width as usize).saturating_sub(6);
-        let blend_bar = Self::render_blend_bar(&self.blend_a, &self.blend_b, bar_width);
-
-        let slider_block = Block::default()
-            .title(" Integration Blend ")
-            .borders(Borders::ALL)
-            .border_style(slider_style);
-        let slider_para = Paragraph::new(blend_bar).block(slider_block);
-        frame.render_widget(slider_para, chunks[3]);
+        frame.render_widget(
+            Paragraph::new(guidance_line).block(guidance_block),
+            chunks[n + 4],
+        );
 
-        // Blend label
-        let label = Paragraph::new(Line::from(vec![
-            Span::styled(
-                format!("  A: {} ", self.blend_a),
-                Style::default().fg(Theme::blend_a()),
-            ),
-            Span::styled(" / ", Theme::dim()),
-            Span::styled(
-                format!(" B: {}", self.blend_b),
-                Style::default().fg(Theme::blend_b()),
-            ),
-        ]));
-        frame.render_widget(label, chunks[4]);
+        // Merge preview
+        if let Some(ref preview) = self.preview {
+            let preview_block = Block::default()
+                .title(format!(" {} ", t!("merge.preview")))
+                .borders(Borders::ALL)
+                .border_style(Theme::normal());
+            frame.render_widget(
+                Paragraph::new(Self::preview_lines(preview)).block(preview_block),
+                chunks[n + 5],
+            );
+        }
 
         // Instructions
         let instructions = Paragraph::new(vec![
             Line::from(vec![
                 Span::styled("[Up/Down]", Theme::selected()),
-                Span::styled(" switch field  ", Theme::dim()),
+                Span::styled(format!(" {}  ", t!("hint.switch_field")), Theme::dim()),
                 Span::styled("[Left/Right]", Theme::selected()),
-                Span::styled(" adjust  ", Theme::dim()),
+                Span::styled(format!(" {}  ", t!("hint.adjust")), Theme::dim()),
                 Span::styled("[Enter]", Theme::selected()),
-                Span::styled(" merge", Theme::dim()),
+                Span::styled(
+                    format!(
+                        " {}",
+                        if self.preview.is_some() {
+                            t!("hint.merge")
+                        } else {
+                            t!("hint.preview")
+                        }
+                    ),
+                    Theme::dim(),
+                ),
             ]),
             Line::from(vec![
+                Span::styled("[+/-]", Theme::selected()),
+                Span::styled(format!(" {}  ", t!("hint.add_remove_source")), Theme::dim()),
+                Span::styled("[A]", Theme::selected()),
+                Span::styled(format!(" {}  ", t!("hint.per_component")), Theme::dim()),
                 Span::styled("[Esc]", Theme::selected()),
-                Span::styled(" cancel", Theme::dim()),
+                Span::styled(format!(" {}", t!("hint.cancel")), Theme::dim()),
             ]),
         ]);
-        frame.render_widget(instructions, chunks[6]);
+        frame.render_widget(instructions, chunks[n + 7]);
+    }
+}
+
+/// How `strategy` is shown in the dialog.
+fn strategy_label(strategy: MergeStrategy) -> &'static str {
+    match strategy {
+        MergeStrategy::LayeredIntegration => t!("merge.layered_integration"),
+        MergeStrategy::AdapterPattern => t!("merge.adapter_pattern"),
+        MergeStrategy::FeatureFlagged => t!("merge.feature_flagged"),
     }
 }
@@ -3,18 +3,38 @@
 //! Each component encapsulates rendering and input handling for a phase.
 
 pub mod benchmark_dashboard;
+pub mod command_palette;
+pub mod conflict_viewer;
+pub mod dir_tree;
+pub mod git_setup;
 pub mod help;
 pub mod merge_dialog;
+pub mod onboarding;
+pub mod progress;
 pub mod project_intake;
+pub mod prompt;
+pub mod rating;
 pub mod research_explorer;
+pub mod sidebar;
 pub mod status_bar;
+pub mod status_log;
 pub mod technique_cards;
+pub mod timeline;
+pub mod usage;
 pub mod variant_builder;
 
+use std::cell::Cell;
+
 use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::Span;
+use ratatui::widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState};
 use ratatui::Frame;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::action::Action;
+use crate::theme::Theme;
 
 /// Trait implemented by all TUI components.
 pub trait Component {
@@ -26,4 +46,292 @@ pub trait Component {
 
     /// Render the component into the given area.
     fn render(&self, frame: &mut Frame, area: Rect);
+
+    /// Move focus to the next panel, or the previous one when `forward` is
+    /// false. Returns false if the view has only one panel, so the key can
+    /// do something else.
+    fn cycle_focus(&mut self, forward: bool) -> bool {
+        let _ = forward;
+        false
+    }
+
+    /// Name of the focused panel, for views with more than one.
+    fn focused_panel(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Which half of a list-and-detail view takes navigation keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Focus {
+    #[default]
+    List,
+    Detail,
+}
+
+impl Focus {
+    /// The other panel. With two panels, forward and back are the same.
+    pub fn toggled(self) -> Self {
+        match self {
+            Focus::List => Focus::Detail,
+            Focus::Detail => Focus::List,
+        }
+    }
+
+    /// Border for `panel`: accented while it has focus.
+    pub fn border(self, panel: Focus) -> Style {
+        if self == panel {
+            Style::default().fg(Theme::accent())
+        } else {
+            Theme::border()
+        }
+    }
+}
+
+/// `s` cut to at most `max_width` terminal columns, ending in "..." when
+/// shortened. Wide characters such as CJK count as two columns.
+pub fn truncate(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    if max_width < 4 {
+        return take_width(s, max_width).to_string();
+    }
+    format!("{}...", take_width(s, max_width - 3))
+}
+
+/// The longest prefix of `s` that fits in `width` columns without splitting
+/// a character.
+pub fn take_width(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, grapheme) in s.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > width {
+            return &s[..i];
+        }
+    }
+    s
+}
+
+/// The message for `key` indented two columns and padded to `width`, so
+/// the values after a column of labels line up in any language.
+pub fn field_label(key: &str, width: usize) -> String {
+    let label = t!(key);
+    format!(
+        "  {}{}",
+        label,
+        " ".repeat(width.saturating_sub(label.width()))
+    )
+}
+
+/// Spans for a hint line like `[Enter] transcript  [x] cancel`, with the
+/// bracketed keys highlighted and the rest dim.
+pub fn bracket_hints(text: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        let Some(len) = rest[start..].find(']') else {
+            break;
+        };
+        if start > 0 {
+            spans.push(Span::styled(rest[..start].to_string(), Theme::dim()));
+        }
+        spans.push(Span::styled(
+            rest[start..=start + len].to_string(),
+            Theme::selected(),
+        ));
+        rest = &rest[start + len + 1..];
+    }
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), Theme::dim()));
+    }
+    spans
+}
+
+/// Draw a vertical scrollbar in the rightmost column of `area` if `total`
+/// rows don't fit in it. `offset` is the first row shown.
+pub fn render_scrollbar(frame: &mut Frame, area: Rect, total: usize, offset: usize) {
+    let visible = area.height as usize;
+    if total <= visible || area.width == 0 {
+        return;
+    }
+    let mut state = ScrollbarState::new(total - visible + 1)
+        .viewport_content_length(visible)
+        .position(offset);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Theme::border())
+        .thumb_style(Style::default().fg(Theme::accent()));
+    frame.render_stateful_widget(scrollbar, area, &mut state);
+}
+
+/// `area` without its rightmost column when `total` rows overflow it, so a
+/// list leaves room for its scrollbar.
+pub fn scrollbar_gutter(area: Rect, total: usize) -> Rect {
+    if total > area.height as usize {
+        Rect {
+            width: area.width.saturating_sub(1),
+            ..area
+        }
+    } else {
+        area
+    }
+}
+
+/// Compact token count, e.g. `62k` or `1.2M`.
+pub fn format_tokens(tokens: usize) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1_000 {
+        format!("{}k", tokens / 1_000)
+    } else {
+        tokens.to_string()
+    }
+}
+
+/// `text` broken at spaces into lines at most `max_width` columns wide,
+/// keeping its own line breaks.
+pub fn word_wrap(text: &str, max_width: usize) -> Vec<String> {
+    if max_width == 0 {
+        return vec![text.to_string()];
+    }
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        if paragraph.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        let mut current_line = String::new();
+        for word in paragraph.split_whitespace() {
+            if current_line.is_empty() {
+                current_line = word.to_string();
+            } else if current_line.width() + 1 + word.width() <= max_width {
+                current_line.push(' ');
+                current_line.push_str(word);
+            } else {
+                lines.push(current_line);
+                current_line = word.to_string();
+            }
+        }
+        if !current_line.is_empty() {
+            lines.push(current_line);
+        }
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Scroll position of a list whose cursor must stay on screen. The view
+/// only moves when the cursor would leave it, instead of pinning the cursor
+/// to the bottom row.
+///
+/// The offset lives in a `Cell` because the list's height is only known
+/// while rendering, which takes `&self`.
+#[derive(Debug, Default)]
+pub struct Viewport {
+    offset: Cell<usize>,
+}
+
+impl Viewport {
+    /// The first row to draw for `total` rows in `height` lines, scrolled
+    /// just enough to show row `cursor`.
+    pub fn scroll_to(&self, cursor: usize, height: usize, total: usize) -> usize {
+        let mut offset = self.offset.get().min(total.saturating_sub(height));
+        if cursor < offset {
+            offset = cursor;
+        } else if height > 0 && cursor >= offset + height {
+            offset = cursor + 1 - height;
+        }
+        self.offset.set(offset);
+        offset
+    }
+}
+
+/// Scroll position of a pane of text, such as a transcript or a detail
+/// panel. It stops at the last page, which is only known once the text is
+/// laid out while rendering, so that offset lives in a `Cell`.
+#[derive(Debug, Default)]
+pub struct TextScroll {
+    offset: usize,
+    /// Furthest offset at the last render.
+    max: Cell<usize>,
+}
+
+impl TextScroll {
+    /// Scroll for a navigation action. Returns whether `action` was one.
+    pub fn handle(&mut self, action: &Action) -> bool {
+        let max = self.max.get();
+        let offset = self.offset.min(max);
+        self.offset = match action {
+            Action::ScrollUp | Action::SelectPrev => offset.saturating_sub(1),
+            Action::ScrollDown | Action::SelectNext => (offset + 1).min(max),
+            Action::PageUp => offset.saturating_sub(PAGE),
+            Action::PageDown => (offset + PAGE).min(max),
+            Action::ScrollTop => 0,
+            Action::ScrollBottom => max,
+            _ => return false,
+        };
+        true
+    }
+
+    pub fn reset(&mut self) {
+        self.offset = 0;
+    }
+
+    /// Scroll to the last page once the text is laid out.
+    pub fn to_bottom(&mut self) {
+        self.offset = usize::MAX;
+    }
+
+    /// The first row to draw of `total` rows in `height` lines, keeping the
+    /// last page full.
+    pub fn offset(&self, total: usize, height: usize) -> usize {
+        let max = total.saturating_sub(height);
+        self.max.set(max);
+        self.offset.min(max)
+    }
+
+    /// Draw `paragraph` in `area`, scrolled. Its block, if it has one, must
+    /// have borders all round.
+    pub fn render(&self, frame: &mut Frame, paragraph: Paragraph, area: Rect) {
+        let total = paragraph.line_count(area.width.saturating_sub(2));
+        let offset = self.offset(total, area.height as usize);
+        frame.render_widget(paragraph.scroll((offset as u16, 0)), area);
+    }
+}
+
+/// Rows or lines moved by PageUp / PageDown.
+pub const PAGE: usize = 10;
+
+/// Limits for resizable detail panels, in lines.
+const MIN_PANE: u16 = 3;
+const MAX_PANE: u16 = 40;
+
+/// `height` grown by `delta` lines, within the pane limits.
+pub fn resize_pane(height: u16, delta: i16) -> u16 {
+    height
+        .saturating_add_signed(delta)
+        .clamp(MIN_PANE, MAX_PANE)
+}
+
+/// Position of the first item after `current` (wrapping around) whose text
+/// contains `query`, ignoring case.
+pub fn find_next<'a>(
+    items: impl IntoIterator<Item = &'a str>,
+    current: usize,
+    query: &str,
+) -> Option<usize> {
+    let query = query.to_lowercase();
+    let items: Vec<&str> = items.into_iter().collect();
+    (1..=items.len())
+        .map(|step| (current + step) % items.len())
+        .find(|&i| items[i].to_lowercase().contains(&query))
+}
+
+/// Status shown when a search finds nothing.
+pub fn no_match(query: &str) -> Action {
+    Action::SetStatus(t!("common.no_match", query = query))
 }
@@ -0,0 +1,412 @@
+//! First-run setup — asks for the Anthropic API key and the sidecar's
+//! location, then starts the sidecar to check both before Phase 1.
+
+use std::path::{Path, PathBuf};
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use unicode_width::UnicodeWidthStr;
+
+use crate::action::Action;
+use crate::components::Component;
+use crate::theme::Theme;
+
+use uniq_core::llm::LlmProvider;
+use uniq_sidecar::manager::is_sidecar_dir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    ApiKey,
+    Sidecar,
+    Check,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CheckState {
+    Running,
+    /// The sidecar is up; whether it has the key `provider` needs, if any,
+    /// and why a call to `provider` failed, if it did.
+    Passed {
+        provider: LlmProvider,
+        api_key: Option<bool>,
+        ping_error: Option<String>,
+    },
+    Failed(String),
+}
+
+pub struct OnboardingComponent {
+    pub visible: bool,
+    step: Step,
+    api_key: String,
+    /// `ANTHROPIC_API_KEY` is already set, so the key can be left blank.
+    env_key: bool,
+    sidecar_dir: String,
+    check: CheckState,
+}
+
+impl OnboardingComponent {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            step: Step::ApiKey,
+            api_key: String::new(),
+            env_key: std::env::var("ANTHROPIC_API_KEY").is_ok_and(|key| !key.is_empty()),
+            sidecar_dir: String::new(),
+            check: CheckState::Running,
+        }
+    }
+
+    /// Open setup with `sidecar_dir` as the suggested location.
+    pub fn open(&mut self, sidecar_dir: &Path) {
+        self.visible = true;
+        self.step = Step::ApiKey;
+        self.sidecar_dir = sidecar_dir.display().to_string();
+    }
+
+    fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+        let vertical = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(area);
+
+        let horizontal = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(width),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(vertical[1]);
+
+        horizontal[1]
+    }
+
+    /// The sidecar path as typed, with `~` expanded.
+    fn sidecar_path(&self) -> PathBuf {
+        match (self.sidecar_dir.strip_prefix('~'), dirs::home_dir()) {
+            (Some(rest), Some(home)) => PathBuf::from(home.to_string_lossy().to_string() + rest),
+            _ => PathBuf::from(&self.sidecar_dir),
+        }
+    }
+
+    /// The field the current step edits, if any.
+    fn input_mut(&mut self) -> Option<&mut String> {
+        match self.step {
+            Step::ApiKey => Some(&mut self.api_key),
+            Step::Sidecar => Some(&mut self.sidecar_dir),
+            Step::Check => None,
+        }
+    }
+
+    fn test_connection(&mut self) -> Action {
+        self.step = Step::Check;
+        self.check = CheckState::Running;
+        Action::TestConnection {
+            api_key: self.api_key.trim().to_string(),
+            sidecar_dir: self.sidecar_path(),
+        }
+    }
+
+    fn finish(&mut self) -> Option<Action> {
+        self.visible = false;
+        Some(Action::FinishOnboarding)
+    }
+
+    /// Enter: move on from the current step.
+    fn advance(&mut self) -> Option<Action> {
+        match (self.step, &self.check) {
+            (Step::ApiKey, _) => {
+                self.step = Step::Sidecar;
+                None
+            }
+            (Step::Sidecar, _) => Some(self.test_connection()),
+            (Step::Check, CheckState::Running) => None,
+            (Step::Check, CheckState::Passed { .. }) => self.finish(),
+            (Step::Check, CheckState::Failed(_)) => Some(self.test_connection()),
+        }
+    }
+
+    /// Esc: step back, or skip setup from the first step. Once the sidecar
+    /// is running there's nothing to go back to.
+    fn back(&mut self) -> Option<Action> {
+        match (self.step, &self.check) {
+            (Step::ApiKey, _) | (Step::Check, CheckState::Passed { .. }) => self.finish(),
+            (Step::Sidecar, _) => {
+                self.step = Step::ApiKey;
+                None
+            }
+            (Step::Check, CheckState::Running) => None,
+            (Step::Check, CheckState::Failed(_)) => {
+                self.step = Step::Sidecar;
+                None
+            }
+        }
+    }
+
+    fn step_lines(&self, width: usize) -> Vec<Line<'static>> {
+        match self.step {
+            Step::ApiKey => {
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        format!(" {}", t!("onboarding.intro")),
+                        Theme::normal(),
+                    )),
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        format!(" {}", t!("onboarding.api_key")),
+                        Theme::header(),
+                    )),
+                    input_line(&mask(&self.api_key), "sk-ant-...", width),
+                    Line::from(""),
+                ];
+                lines.push(if self.env_key {
+                    Line::from(Span::styled(
+                        format!(" {}", t!("onboarding.env_key")),
+                        Style::default().fg(Theme::success()),
+                    ))
+                } else {
+                    Line::from(Span::styled(
+                        format!(" {}", t!("onboarding.key_saved")),
+                        Theme::dim(),
+                    ))
+                });
+                lines
+            }
+            Step::Sidecar => {
+                let found = is_sidecar_dir(&self.sidecar_path());
+                vec![
+                    Line::from(Span::styled(
+                        format!(" {}", t!("onboarding.sidecar_intro")),
+                        Theme::normal(),
+                    )),
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        format!(" {}", t!("onboarding.sidecar_dir")),
+                        Theme::header(),
+                    )),
+                    input_line(&self.sidecar_dir, "path/to/uniq/sidecar", width),
+                    Line::from(""),
+                    if found {
+                        Line::from(Span::styled(
+                            format!(" {}", t!("onboarding.sidecar_found")),
+                            Style::default().fg(Theme::success()),
+                        ))
+                    } else {
+                        Line::from(Span::styled(
+                            format!(" {}", t!("onboarding.sidecar_missing")),
+                            Style::default().fg(Theme::warning()),
+                        ))
+                    },
+                ]
+            }
+            Step::Check => {
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        format!(" {}", t!("onboarding.check")),
+                        Theme::header(),
+                    )),
+                    Line::from(""),
+                ];
+                match &self.check {
+                    CheckState::Running => lines.push(Line::from(Span::styled(
+                        format!(" {}", t!("onboarding.starting")),
+                        Theme::muted(),
+                    ))),
+                    CheckState::Passed {
+                        provider,
+                        api_key,
+                        ping_error,
+                    } => {
+                        lines.push(check_line(true, t!("onboarding.started").to_string()));
+                        lines.push(match (api_key, ping_error) {
+                            (Some(false), _) => {
+                                check_line(false, t!("onboarding.no_key", provider = provider))
+                            }
+                            (_, Some(error)) => check_line(
+                                false,
+                                t!("onboarding.unreachable", provider = provider, error = error),
+                            ),
+                            (Some(true), None) => {
+                                check_line(true, t!("onboarding.key_found", provider = provider))
+                            }
+                            (None, None) => check_line(
+                                true,
+                                t!("onboarding.no_key_needed", provider = provider),
+                            ),
+                        });
+                    }
+                    CheckState::Failed(error) => {
+                        lines.push(check_line(false, t!("onboarding.failed").to_string()));
+                        lines.push(Line::from(""));
+                        lines.push(Line::from(Span::styled(
+                            format!(" {}", error),
+                            Theme::dim(),
+                        )));
+                    }
+                }
+                lines
+            }
+        }
+    }
+
+    fn hints(&self) -> Line<'static> {
+        let (enter, esc) = match (self.step, &self.check) {
+            (Step::ApiKey, _) => (t!("hint.next"), t!("hint.skip_setup")),
+            (Step::Sidecar, _) => (t!("hint.test_connection"), t!("hint.back")),
+            (Step::Check, CheckState::Running) => ("", ""),
+            (Step::Check, CheckState::Passed { .. }) => (t!("hint.start"), t!("hint.start")),
+            (Step::Check, CheckState::Failed(_)) => (t!("hint.retry"), t!("hint.back")),
+        };
+        if enter.is_empty() {
+            return Line::from("");
+        }
+        Line::from(vec![
+            Span::styled(" enter", Theme::key_hint()),
+            Span::styled(format!(" {}  ", enter), Theme::dim()),
+            Span::styled("esc", Theme::key_hint()),
+            Span::styled(format!(" {}", esc), Theme::dim()),
+        ])
+    }
+}
+
+impl Default for OnboardingComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for OnboardingComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if !self.visible {
+            return None;
+        }
+
+        match action {
+            Action::SidecarReady {
+                provider,
+                api_key,
+                ping_error,
+            } if self.step == Step::Check => {
+                self.check = CheckState::Passed {
+                    provider: *provider,
+                    api_key: *api_key,
+                    ping_error: ping_error.clone(),
+                };
+                None
+            }
+            Action::SidecarFailed(error) if self.step == Step::Check => {
+                self.check = CheckState::Failed(error.clone());
+                None
+            }
+            Action::CharInput(c) => {
+                if let Some(input) = self.input_mut() {
+                    input.push(*c);
+                }
+                None
+            }
+            Action::BackspaceInput => {
+                if let Some(input) = self.input_mut() {
+                    input.pop();
+                }
+                None
+            }
+            Action::DeleteWord => {
+                if let Some(input) = self.input_mut() {
+                    let trimmed = input.trim_end_matches([' ', '/']).len();
+                    input.truncate(trimmed);
+                    let start = input.rfind([' ', '/']).map_or(0, |i| i + 1);
+                    input.truncate(start);
+                }
+                None
+            }
+            Action::PasteBulk(text) => {
+                if let Some(input) = self.input_mut() {
+                    input.push_str(text.lines().next().unwrap_or("").trim());
+                }
+                None
+            }
+            Action::NewlineInput | Action::SubmitForm | Action::SwitchInputField => self.advance(),
+            Action::Escape => self.back(),
+            _ => None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let width = area.width.saturating_sub(4).min(76);
+        let dialog = Self::centered_rect(area, width, 14);
+        frame.render_widget(Clear, dialog);
+
+        let step = match self.step {
+            Step::ApiKey => 1,
+            Step::Sidecar => 2,
+            Step::Check => 3,
+        };
+        let block = Block::default()
+            .title(format!(" {} ", t!("onboarding.title", step = step)))
+            .title_style(Theme::title())
+            .title_bottom(self.hints())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let mut lines = vec![Line::from("")];
+        lines.extend(self.step_lines(width as usize));
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            dialog,
+        );
+    }
+}
+
+/// All but the last four characters of `key` hidden.
+fn mask(key: &str) -> String {
+    let count = key.chars().count();
+    key.chars()
+        .enumerate()
+        .map(|(i, c)| if i + 4 < count { '•' } else { c })
+        .collect()
+}
+
+/// A text field showing the tail of `text`, with a cursor at the end.
+fn input_line(text: &str, placeholder: &str, width: usize) -> Line<'static> {
+    let cursor = Span::styled(" ", Style::default().fg(Theme::bg()).bg(Theme::accent()));
+    if text.is_empty() {
+        return Line::from(vec![
+            Span::raw(" "),
+            cursor,
+            Span::styled(format!(" {}", placeholder), Theme::dim()),
+        ]);
+    }
+    let max = width.saturating_sub(5);
+    let mut start = 0;
+    while text[start..].width() > max {
+        start += text[start..].chars().next().map_or(1, char::len_utf8);
+    }
+    Line::from(vec![
+        Span::raw(" "),
+        Span::styled(text[start..].to_string(), Theme::normal()),
+        cursor,
+    ])
+}
+
+fn check_line(ok: bool, text: String) -> Line<'static> {
+    let (mark, color) = if ok {
+        ("✓", Theme::success())
+    } else {
+        ("✗", Theme::error())
+    };
+    Line::from(vec![
+        Span::styled(format!(" {} ", mark), Style::default().fg(color)),
+        Span::styled(text, Theme::normal()),
+    ])
+}
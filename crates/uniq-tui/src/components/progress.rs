@@ -0,0 +1,62 @@
+//! Progress indicator shared by the phases that wait on long-running work:
+//! a spinner that also counts elapsed time.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::theme::Theme;
+
+/// Braille spinner frames.
+const FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Shown instead of the spinner when animations are off.
+const STILL: &str = "●";
+
+/// The event loop ticks every 100ms.
+const TICKS_PER_SEC: usize = 10;
+
+/// Animated activity indicator, advanced once per tick while work runs.
+#[derive(Debug, Clone, Default)]
+pub struct Spinner {
+    ticks: usize,
+    /// Show a static marker instead of cycling frames.
+    pub reduced_motion: bool,
+}
+
+impl Spinner {
+    pub fn new(reduced_motion: bool) -> Self {
+        Self {
+            ticks: 0,
+            reduced_motion,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+    }
+
+    pub fn elapsed_secs(&self) -> usize {
+        self.ticks / TICKS_PER_SEC
+    }
+
+    fn frame(&self) -> &'static str {
+        if self.reduced_motion {
+            STILL
+        } else {
+            FRAMES[self.ticks % FRAMES.len()]
+        }
+    }
+
+    /// The spinner followed by `label`, as a heading line.
+    pub fn heading(&self, label: &str) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(
+                format!("  {} ", self.frame()),
+                Style::default()
+                    .fg(Theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(label.to_string(), Theme::header()),
+        ])
+    }
+}
@@ -1,29 +1,66 @@
 //! Phase 1: Project Intake — user provides project path and description.
 //!
 //! Features:
-//! - Path field: single-line with filesystem autocomplete
+//! - Path field: single-line with fuzzy filesystem autocomplete
+//! - Ctrl+O browses to the path in a directory tree instead
 //! - Description field: multi-line text area with scroll viewport
 //! - Tab to accept path suggestions or switch fields
 //! - Enter inserts newlines in description, navigates in path
+//! - Cursor moves and deletes by grapheme cluster, so emoji and combining
+//!   characters edit as one character
 //! - Ctrl+Enter submits the form for analysis
-
+//! - Ctrl+E edits the description in `$EDITOR`
+//! - Large pastes go into the description a chunk per tick, so the screen
+//!   keeps updating, and are cut short past a size limit
+//! - Home/End jump to the ends of the line
+//! - Shift+arrows select text; Ctrl+X cuts and Ctrl+Y copies the selection
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
 use ratatui::Frame;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+use unicode_width::UnicodeWidthStr;
 
-use crate::action::Action;
-use crate::components::Component;
+use crate::action::{Action, CursorMove};
+use crate::components::dir_tree::DirTree;
+use crate::components::{render_scrollbar, take_width, Component};
+use crate::keymap::{self, Keymap};
 use crate::theme::Theme;
 
+use uniq_core::fuzzy::fuzzy_match;
 use uniq_core::project::ProjectProfile;
 
 /// Maximum number of path suggestions to display.
 const MAX_SUGGESTIONS: usize = 8;
 
+/// Best matches kept for each directory typed past the last one that exists.
+const MAX_BRANCHES: usize = 5;
+
+/// Height of the directory tree, border included.
+const DIR_TREE_HEIGHT: u16 = 14;
+
+/// Bytes of a paste inserted per tick.
+const PASTE_CHUNK: usize = 64 * 1024;
+
+/// Longest description accepted, in bytes.
+const MAX_DESCRIPTION: usize = 1024 * 1024;
+
+/// A large paste still being inserted into the description.
+struct PendingPaste {
+    text: String,
+    /// Bytes of `text` inserted so far.
+    inserted: usize,
+    /// Length of the paste before it was cut to fit.
+    original: usize,
+}
+
 /// Which input field is currently focused.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum InputField {
@@ -40,6 +77,8 @@ pub struct ProjectIntakeComponent {
     focused: InputField,
     /// Cursor position (byte offset) within the focused field.
     pub cursor: usize,
+    /// Where the selection started; the selection runs to the cursor.
+    selection_anchor: Option<usize>,
     /// Whether the project has been analyzed.
     pub profile: Option<ProjectProfile>,
     /// Whether analysis is in progress.
@@ -50,6 +89,11 @@ pub struct ProjectIntakeComponent {
     // ── Description viewport ─────────────────────────────────
     /// Scroll offset (line number of the first visible line in the description).
     desc_scroll: usize,
+    /// Paste still going into the description.
+    paste: Option<PendingPaste>,
+    /// The description as last wrapped. Wrapping a long description is too
+    /// slow to repeat every frame.
+    wrapped: RefCell<WrappedText>,
 
     // ── Path suggestions ────────────────────────────────────
     /// Current filesystem suggestions based on path_input.
@@ -59,6 +103,10 @@ pub struct ProjectIntakeComponent {
     /// The path input value that was last used to compute suggestions
     /// (avoids recomputing on every render).
     suggestions_for: String,
+    /// Directory tree, while browsing for the path.
+    dir_tree: Option<DirTree>,
+    /// Bindings, for the keys hints show.
+    pub keymap: Keymap,
 }
 
 /// A single path suggestion entry.
@@ -70,6 +118,39 @@ struct PathSuggestion {
     name: String,
     /// Whether this is a directory.
     is_dir: bool,
+    /// Char indices of `name` that matched what was typed.
+    matched: Vec<usize>,
+}
+
+/// Text wrapped to a width, each logical line into its visual lines.
+#[derive(Default)]
+struct WrappedText {
+    text: String,
+    width: usize,
+    lines: Vec<Vec<String>>,
+}
+
+impl WrappedText {
+    /// Wrap `text` to `width`, unless that's what was wrapped last.
+    fn update(&mut self, text: &str, width: usize) {
+        if self.width == width && self.text == text && !self.lines.is_empty() {
+            return;
+        }
+        self.text = text.to_string();
+        self.width = width;
+        self.lines = text.split('\n').map(|l| wrap_line(l, width)).collect();
+    }
+}
+
+/// A real directory reached by fuzzy-matching typed path segments.
+struct DirMatch {
+    path: PathBuf,
+    /// The matched segments, e.g. `projects/` for `prj/`; empty when the
+    /// typed directory exists.
+    label: String,
+    score: i64,
+    /// Char indices of `label` that matched.
+    matched: Vec<usize>,
 }
 
 impl ProjectIntakeComponent {
@@ -79,18 +160,32 @@ impl ProjectIntakeComponent {
             description_input: String::new(),
             focused: InputField::Path,
             cursor: 2,
+            selection_anchor: None,
             profile: None,
             analyzing: false,
             error: None,
             desc_scroll: 0,
+            paste: None,
+            wrapped: RefCell::default(),
             suggestions: Vec::new(),
             suggestion_index: None,
             suggestions_for: String::new(),
+            dir_tree: None,
+            keymap: Keymap::default(),
         };
         this.refresh_suggestions();
         this
     }
 
+    /// The first key bound to moving on to the next phase.
+    fn next_phase_key(&self) -> String {
+        self.keymap
+            .key_labels("next_phase")
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+
     /// Whether this component wants to capture raw key input.
     pub fn wants_input(&self) -> bool {
         self.profile.is_none() && !self.analyzing
@@ -104,28 +199,69 @@ impl ProjectIntakeComponent {
         }
     }
 
-    /// Clamp cursor to valid range for the focused field.
+    /// Clamp the cursor into the focused field and back onto the start of
+    /// a grapheme cluster, so edits never split a character.
     fn clamp_cursor(&mut self) {
-        let len = self.focused_input().len();
-        if self.cursor > len {
-            self.cursor = len;
-        }
+        self.cursor = boundary_at_or_before(self.focused_input(), self.cursor);
     }
 
     /// Insert a character at the cursor position.
     fn insert_char(&mut self, c: char) {
-        self.clamp_cursor();
-        let cursor = self.cursor;
+        let mut buf = [0u8; 4];
+        self.insert_str(c.encode_utf8(&mut buf));
+    }
+
+    /// The selected byte range of the focused field, if any.
+    fn selection(&self) -> Option<Range<usize>> {
+        let text = self.focused_input();
+        let anchor = boundary_at_or_before(text, self.selection_anchor?);
+        let cursor = boundary_at_or_before(text, self.cursor);
+        match anchor.cmp(&cursor) {
+            Ordering::Less => Some(anchor..cursor),
+            Ordering::Greater => Some(cursor..anchor),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// Delete the selected text. Returns whether there was any.
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.selection_anchor = None;
+        let Some(range) = selection else {
+            return false;
+        };
         let input = match self.focused {
             InputField::Path => &mut self.path_input,
             InputField::Description => &mut self.description_input,
         };
-        input.insert(cursor, c);
-        self.cursor += c.len_utf8();
+        self.cursor = range.start;
+        input.drain(range);
+        true
+    }
+
+    /// Put the selected text on the clipboard, deleting it if `cut`.
+    fn copy_selection(&mut self, cut: bool) -> Option<Action> {
+        let range = self.selection()?;
+        let text = self.focused_input()[range].to_string();
+        if cut {
+            self.delete_selection();
+            if self.focused == InputField::Path {
+                self.refresh_suggestions();
+            } else {
+                self.ensure_cursor_visible();
+            }
+        }
+        Some(Action::CopyText {
+            text,
+            what: t!("intake.selection").to_string(),
+        })
     }
 
-    /// Delete the character before the cursor.
+    /// Delete the grapheme cluster before the cursor.
     fn delete_char(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
         self.clamp_cursor();
         if self.cursor > 0 {
             let cursor = self.cursor;
@@ -133,18 +269,17 @@ impl ProjectIntakeComponent {
                 InputField::Path => &mut self.path_input,
                 InputField::Description => &mut self.description_input,
             };
-            let prev = input[..cursor]
-                .char_indices()
-                .next_back()
-                .map(|(i, _)| i)
-                .unwrap_or(0);
-            input.remove(prev);
+            let prev = prev_boundary(input, cursor);
+            input.drain(prev..cursor);
             self.cursor = prev;
         }
     }
 
     /// Delete the word before the cursor (Ctrl+W).
     fn delete_word(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
         self.clamp_cursor();
         if self.cursor > 0 {
             let cursor = self.cursor;
@@ -152,19 +287,47 @@ impl ProjectIntakeComponent {
                 InputField::Path => &mut self.path_input,
                 InputField::Description => &mut self.description_input,
             };
-            let mut end = cursor;
-            while end > 0 && input.as_bytes().get(end - 1) == Some(&b' ') {
-                end -= 1;
-            }
-            let mut start = end;
-            while start > 0 && input.as_bytes().get(start - 1) != Some(&b' ') {
-                start -= 1;
-            }
+            let end = input[..cursor].trim_end_matches(' ').len();
+            let start = input[..end].rfind(' ').map(|i| i + 1).unwrap_or(0);
             input.drain(start..cursor);
             self.cursor = start;
         }
     }
 
+    /// Move the cursor by one grapheme cluster or line, dropping any
+    /// selection.
+    fn move_cursor(&mut self, movement: CursorMove) {
+        self.selection_anchor = None;
+        self.clamp_cursor();
+        let text = self.focused_input();
+        match movement {
+            CursorMove::Left => self.cursor = prev_boundary(text, self.cursor),
+            CursorMove::Right => self.cursor = next_boundary(text, self.cursor),
+            CursorMove::Up => self.cursor_up(),
+            CursorMove::Down => self.cursor_down(),
+            CursorMove::LineStart => {
+                self.cursor = text[..self.cursor].rfind('\n').map_or(0, |i| i + 1);
+            }
+            CursorMove::LineEnd => {
+                self.cursor = text[self.cursor..]
+                    .find('\n')
+                    .map_or(text.len(), |i| self.cursor + i);
+            }
+        }
+        if self.focused == InputField::Description {
+            self.ensure_cursor_visible();
+        }
+    }
+
+    /// Move the cursor, selecting from where the selection started (or the
+    /// cursor, if nothing is selected yet).
+    fn extend_selection(&mut self, movement: CursorMove) {
+        self.clamp_cursor();
+        let anchor = self.selection_anchor.unwrap_or(self.cursor);
+        self.move_cursor(movement);
+        self.selection_anchor = Some(anchor);
+    }
+
     /// Insert a newline at the cursor position (description field only).
     fn insert_newline(&mut self) {
         if self.focused == InputField::Description {
@@ -173,8 +336,10 @@ impl ProjectIntakeComponent {
         }
     }
 
-    /// Insert a string at the cursor position (for paste).
+    /// Insert a string at the cursor position (for paste), replacing the
+    /// selection.
     fn insert_str(&mut self, s: &str) {
+        self.delete_selection();
         self.clamp_cursor();
         let cursor = self.cursor;
         let input = match self.focused {
@@ -182,13 +347,71 @@ impl ProjectIntakeComponent {
             InputField::Description => &mut self.description_input,
         };
         input.insert_str(cursor, s);
-        self.cursor += s.len();
+        // What was typed may join the cluster after it, e.g. a combining mark.
+        self.cursor = boundary_at_or_after(input, cursor + s.len());
         if self.focused == InputField::Description {
             self.ensure_cursor_visible();
         }
     }
 
-    /// Get the line number and column of the cursor within the description.
+    /// Paste `text` into the focused field: its first line into the path,
+    /// all of it into the description, a chunk at a time if it's large.
+    fn paste(&mut self, text: &str) -> Option<Action> {
+        if self.focused == InputField::Path {
+            self.insert_str(text.lines().next().unwrap_or(""));
+            self.refresh_suggestions();
+            return None;
+        }
+
+        let mut text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let original = text.len();
+        let room = MAX_DESCRIPTION.saturating_sub(self.description_input.len());
+        text.truncate(char_boundary_at_or_before(&text, room));
+        if text.len() <= PASTE_CHUNK {
+            self.insert_str(&text);
+            return (text.len() < original).then(|| paste_status(text.len(), original));
+        }
+
+        self.delete_selection();
+        self.paste = Some(PendingPaste {
+            text,
+            inserted: 0,
+            original,
+        });
+        self.continue_paste()
+    }
+
+    /// Insert the next chunk of a large paste. Returns the status to show
+    /// once the last one is in.
+    fn continue_paste(&mut self) -> Option<Action> {
+        let paste = self.paste.as_mut()?;
+        let end = char_boundary_at_or_before(&paste.text, paste.inserted + PASTE_CHUNK);
+        let chunk = paste.text[paste.inserted..end].to_string();
+        paste.inserted = end;
+        let (total, original) = (paste.text.len(), paste.original);
+        self.insert_str(&chunk);
+
+        if end < total {
+            return None;
+        }
+        self.paste = None;
+        Some(paste_status(total, original))
+    }
+
+    /// Insert the rest of a large paste at once, before anything else edits
+    /// the description.
+    fn finish_paste(&mut self) {
+        while self.paste.is_some() {
+            self.continue_paste();
+        }
+    }
+
+    /// Whether a large paste is still going in.
+    pub fn is_pasting(&self) -> bool {
+        self.paste.is_some()
+    }
+
+    /// Get the line number and byte column of the cursor within the description.
     fn cursor_line_col(&self, text: &str, cursor: usize) -> (usize, usize) {
         let before = &text[..cursor.min(text.len())];
         let line = before.matches('\n').count();
@@ -198,6 +421,7 @@ impl ProjectIntakeComponent {
 
     /// Move cursor up one line in the description.
     fn cursor_up(&mut self) {
+        self.selection_anchor = None;
         if self.focused != InputField::Description {
             return;
         }
@@ -206,16 +430,19 @@ impl ProjectIntakeComponent {
         if line == 0 {
             return; // Already on first line.
         }
+        // Keep the column in characters, not bytes.
+        let col = text[self.cursor - col..self.cursor].graphemes(true).count();
         // Find the start of the previous line.
         let lines: Vec<&str> = text.split('\n').collect();
         let prev_line = lines[line - 1];
         let prev_line_start: usize = lines[..line - 1].iter().map(|l| l.len() + 1).sum();
-        self.cursor = prev_line_start + col.min(prev_line.len());
+        self.cursor = prev_line_start + offset_of_column(prev_line, col);
         self.ensure_cursor_visible();
     }
 
     /// Move cursor down one line in the description.
     fn cursor_down(&mut self) {
+        self.selection_anchor = None;
         if self.focused != InputField::Description {
             return;
         }
@@ -225,9 +452,10 @@ impl ProjectIntakeComponent {
         if line + 1 >= lines.len() {
             return; // Already on last line.
         }
+        let col = text[self.cursor - col..self.cursor].graphemes(true).count();
         let next_line = lines[line + 1];
         let next_line_start: usize = lines[..line + 1].iter().map(|l| l.len() + 1).sum();
-        self.cursor = next_line_start + col.min(next_line.len());
+        self.cursor = next_line_start + offset_of_column(next_line, col);
         self.ensure_cursor_visible();
     }
 
@@ -254,6 +482,7 @@ impl ProjectIntakeComponent {
             InputField::Description => InputField::Path,
         };
         self.cursor = self.focused_input().len();
+        self.selection_anchor = None;
         // Reset suggestions when leaving path field.
         if self.focused != InputField::Path {
             self.suggestions.clear();
@@ -265,6 +494,9 @@ impl ProjectIntakeComponent {
 
     /// Refresh filesystem suggestions based on the current path_input.
     /// Called after every keystroke when the path field is focused.
+    ///
+    /// Names match fuzzily, and so do directories typed after the last one
+    /// that exists: `~/prj/un` suggests `projects/uniq`.
     fn refresh_suggestions(&mut self) {
         // Only compute if the input actually changed.
         if self.path_input == self.suggestions_for {
@@ -279,21 +511,11 @@ impl ProjectIntakeComponent {
             return;
         }
 
-        // Expand ~ to home directory.
-        let expanded = if input.starts_with('~') {
-            if let Some(home) = dirs::home_dir() {
-                home.to_string_lossy().to_string() + &input[1..]
-            } else {
-                input.clone()
-            }
-        } else {
-            input.clone()
-        };
-
+        let expanded = expand_tilde(input);
         let path = Path::new(&expanded);
 
-        // Determine the parent directory to list, and the prefix to filter by.
-        let (search_dir, prefix): (PathBuf, String) =
+        // Determine the directory to list, and the name to match in it.
+        let (search_dir, query): (PathBuf, String) =
             if expanded.ends_with('/') || expanded.ends_with(std::path::MAIN_SEPARATOR) {
                 // User typed a trailing slash — list contents of this directory.
                 (path.to_path_buf(), String::new())
@@ -302,60 +524,48 @@ impl ProjectIntakeComponent {
                 // (But only if it doesn't look like the user is mid-filename.)
                 (path.to_path_buf(), String::new())
             } else {
-                // Partial name — list parent, filter by filename prefix.
+                // Partial name — list parent, match the file name.
                 let parent = path.parent().unwrap_or(Path::new("/"));
-                let file_prefix = path
+                let name = path
                     .file_name()
                     .map(|f| f.to_string_lossy().to_string())
                     .unwrap_or_default();
-                (parent.to_path_buf(), file_prefix)
+                (parent.to_path_buf(), name)
             };
 
-        // Read the directory entries.
-        let entries = match std::fs::read_dir(&search_dir) {
-            Ok(entries) => entries,
-            Err(_) => return,
-        };
-
-        let prefix_lower = prefix.to_lowercase();
-
-        let mut results: Vec<PathSuggestion> = entries
-            .filter_map(|entry| entry.ok())
-            .filter_map(|entry| {
-                let name = entry.file_name().to_string_lossy().to_string();
-
-                // Skip hidden files unless the user is explicitly typing a dot.
-                if name.starts_with('.') && !prefix.starts_with('.') {
-                    return None;
-                }
-
-                // Filter by prefix (case-insensitive).
-                if !prefix.is_empty() && !name.to_lowercase().starts_with(&prefix_lower) {
-                    return None;
-                }
-
-                let full_path = entry.path();
-                let is_dir = full_path.is_dir();
-
-                Some(PathSuggestion {
-                    full_path: full_path.to_string_lossy().to_string(),
-                    name,
-                    is_dir,
-                })
-            })
-            .collect();
+        let mut results: Vec<(i64, PathSuggestion)> = Vec::new();
+        for branch in fuzzy_dirs(&search_dir) {
+            for (name, full_path, is_dir) in dir_entries(&branch.path, &query) {
+                let Some((score, positions)) = fuzzy_match(&query, &name) else {
+                    continue;
+                };
+                let offset = branch.label.chars().count();
+                let mut matched = branch.matched.clone();
+                matched.extend(positions.into_iter().map(|i| i + offset));
+                results.push((
+                    branch.score + score,
+                    PathSuggestion {
+                        full_path: full_path.to_string_lossy().to_string(),
+                        name: branch.label.clone() + &name,
+                        is_dir,
+                        matched,
+                    },
+                ));
+            }
+        }
 
-        // Sort: directories first, then alphabetically.
-        results.sort_by(|a, b| {
-            b.is_dir
-                .cmp(&a.is_dir)
+        // Sort: best match first, then directories, then alphabetically.
+        results.sort_by(|(score_a, a), (score_b, b)| {
+            score_b
+                .cmp(score_a)
+                .then_with(|| b.is_dir.cmp(&a.is_dir))
                 .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
         });
 
         // Truncate to max.
         results.truncate(MAX_SUGGESTIONS);
 
-        self.suggestions = results;
+        self.suggestions = results.into_iter().map(|(_, s)| s).collect();
     }
 
     /// Accept the currently highlighted suggestion (or the first one).
@@ -369,6 +579,7 @@ impl ProjectIntakeComponent {
             }
             self.path_input = new_path;
             self.cursor = self.path_input.len();
+            self.selection_anchor = None;
             // Clear old suggestions so they refresh on next input.
             self.suggestions_for.clear();
             self.suggestions.clear();
@@ -378,6 +589,61 @@ impl ProjectIntakeComponent {
         }
     }
 
+    // ── Directory tree ──────────────────────────────────────
+
+    /// Browse from the directory typed so far, or from home.
+    fn open_dir_tree(&mut self) {
+        if self.focused != InputField::Path {
+            self.switch_field();
+        }
+        self.suggestions.clear();
+        self.suggestion_index = None;
+
+        let typed = PathBuf::from(expand_tilde(&self.path_input));
+        let start = [Some(typed.as_path()), typed.parent()]
+            .into_iter()
+            .flatten()
+            .find(|dir| dir.is_dir())
+            .map(Path::to_path_buf)
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/"));
+        self.dir_tree = Some(DirTree::new(&start));
+    }
+
+    /// Take the tree's highlighted directory as the path and move on to
+    /// the description.
+    fn choose_dir(&mut self) {
+        let Some(tree) = self.dir_tree.take() else {
+            return;
+        };
+        if let Some(path) = tree.selected_path() {
+            self.path_input = path.display().to_string();
+            self.suggestions_for = self.path_input.clone();
+            self.switch_field();
+        }
+    }
+
+    /// Keys while the tree is open. Returns `None` for actions it doesn't
+    /// take, so ticks and async results carry on as usual.
+    fn handle_dir_tree(&mut self, action: &Action) -> Option<Option<Action>> {
+        let tree = self.dir_tree.as_mut()?;
+        match action {
+            Action::ScrollUp | Action::SelectPrev => tree.move_selection(-1),
+            Action::ScrollDown | Action::SelectNext => tree.move_selection(1),
+            Action::MoveCursor(CursorMove::Right) | Action::CharInput(' ') => tree.expand(),
+            Action::MoveCursor(CursorMove::Left) => tree.collapse(),
+            Action::MoveCursor(CursorMove::LineStart) => tree.select_first(),
+            Action::MoveCursor(CursorMove::LineEnd) => tree.select_last(),
+            Action::CharInput('.') => tree.toggle_hidden(),
+            Action::NewlineInput | Action::SwitchInputField => self.choose_dir(),
+            Action::Escape | Action::ToggleDirTree => self.dir_tree = None,
+            // Typing doesn't reach the fields behind the tree.
+            _ if action.is_editing_input() => {}
+            _ => return None,
+        }
+        Some(None)
+    }
+
     /// Try to submit the form. Returns the action to dispatch, or a status message.
     fn try_submit(&mut self) -> Option<Action> {
         // Dismiss any suggestions.
@@ -385,16 +651,7 @@ impl ProjectIntakeComponent {
         self.suggestion_index = None;
 
         if !self.path_input.is_empty() && !self.description_input.is_empty() {
-            // Expand ~ before submitting.
-            let path = if self.path_input.starts_with('~') {
-                if let Some(home) = dirs::home_dir() {
-                    home.to_string_lossy().to_string() + &self.path_input[1..]
-                } else {
-                    self.path_input.clone()
-                }
-            } else {
-                self.path_input.clone()
-            };
+            let path = expand_tilde(&self.path_input);
 
             self.analyzing = true;
             self.error = None;
@@ -403,10 +660,10 @@ impl ProjectIntakeComponent {
                 description: self.description_input.clone(),
             })
         } else if self.path_input.is_empty() {
-            Some(Action::SetStatus("Enter a project path first".to_string()))
+            Some(Action::SetStatus(t!("intake.path_required").to_string()))
         } else {
             Some(Action::SetStatus(
-                "Enter a description of what AI capability to add".to_string(),
+                t!("intake.description_required").to_string(),
             ))
         }
     }
@@ -416,23 +673,16 @@ impl ProjectIntakeComponent {
         self.focused == InputField::Path && !self.suggestions.is_empty()
     }
 
-    /// Render the path input field with cursor.
-    fn render_text_field(
-        text: &str,
-        cursor: usize,
-        is_focused: bool,
-        placeholder: &str,
-        title: &str,
-        frame: &mut Frame,
-        area: Rect,
-    ) {
+    /// Render the path input field with cursor and selection.
+    fn render_path_field(&self, is_focused: bool, frame: &mut Frame, area: Rect) {
+        let text = self.path_input.as_str();
         let border_style = if is_focused {
             Style::default().fg(Theme::accent())
         } else {
             Theme::border()
         };
         let block = Block::default()
-            .title(title)
+            .title(format!(" {} ", t!("intake.path_title")))
             .title_style(if is_focused {
                 Theme::key_hint()
             } else {
@@ -442,28 +692,13 @@ impl ProjectIntakeComponent {
             .border_style(border_style);
 
         let display = if text.is_empty() && !is_focused {
-            Paragraph::new(Span::styled(placeholder, Theme::dim()))
+            Paragraph::new(Span::styled(t!("intake.path_placeholder"), Theme::dim()))
         } else if is_focused {
-            let pos = cursor.min(text.len());
-            let (before, after) = text.split_at(pos);
-            let cursor_char = if after.is_empty() {
-                " ".to_string()
-            } else {
-                after.chars().next().unwrap().to_string()
-            };
-            let rest = if after.len() > cursor_char.len() {
-                &after[cursor_char.len()..]
-            } else {
-                ""
-            };
-            Paragraph::new(Line::from(vec![
-                Span::styled(before, Theme::normal()),
-                Span::styled(
-                    cursor_char,
-                    Style::default().fg(Theme::bg()).bg(Theme::accent()),
-                ),
-                Span::styled(rest, Theme::normal()),
-            ]))
+            Paragraph::new(Line::from(field_spans(
+                text,
+                Some(self.cursor),
+                self.selection(),
+            )))
         } else {
             Paragraph::new(Span::styled(text, Theme::normal()))
         };
@@ -472,8 +707,28 @@ impl ProjectIntakeComponent {
     }
 }
 
+impl Default for ProjectIntakeComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Component for ProjectIntakeComponent {
     fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if let Some(result) = self.handle_dir_tree(action) {
+            return result;
+        }
+
+        if let Action::Tick = action {
+            return self.continue_paste();
+        }
+        // Edits wait for a paste in progress to finish, so they land after it.
+        if self.paste.is_some()
+            && (action.is_editing_input() || matches!(action, Action::EditDescriptionExternally))
+        {
+            self.finish_paste();
+        }
+
         match action {
             // ── Text input ──────────────────────────────────────
             Action::CharInput(c) => {
@@ -493,6 +748,35 @@ impl Component for ProjectIntakeComponent {
                 }
                 None
             }
+            Action::ToggleDirTree => {
+                if self.wants_input() {
+                    self.open_dir_tree();
+                }
+                None
+            }
+            Action::EditDescriptionExternally => {
+                Some(Action::OpenInEditor(self.description_input.clone()))
+            }
+            Action::DescriptionEdited(text) => {
+                self.description_input = text.clone();
+                self.focused = InputField::Description;
+                self.suggestions.clear();
+                self.suggestion_index = None;
+                self.cursor = self.description_input.len();
+                self.selection_anchor = None;
+                self.ensure_cursor_visible();
+                None
+            }
+            Action::MoveCursor(movement) => {
+                self.move_cursor(*movement);
+                None
+            }
+            Action::ExtendSelection(movement) => {
+                self.extend_selection(*movement);
+                None
+            }
+            Action::CutSelection => self.copy_selection(true),
+            Action::CopySelection => self.copy_selection(false),
             Action::DeleteWord => {
                 self.delete_word();
                 if self.focused == InputField::Path {
@@ -505,38 +789,19 @@ impl Component for ProjectIntakeComponent {
             }
             Action::PasteInput => {
                 // Try to read from clipboard via pbpaste (macOS fallback).
-                if let Ok(output) = std::process::Command::new("pbpaste").output() {
-                    if let Ok(text) = String::from_utf8(output.stdout) {
-                        if !text.is_empty() {
-                            let to_paste = if self.focused == InputField::Path {
-                                text.lines().next().unwrap_or("").to_string()
-                            } else {
-                                text
-                            };
-                            self.insert_str(&to_paste);
-                            if self.focused == InputField::Path {
-                                self.refresh_suggestions();
-                            }
-                        }
-                    }
+                let output = std::process::Command::new("pbpaste").output().ok()?;
+                let text = String::from_utf8(output.stdout).ok()?;
+                if text.is_empty() {
+                    return None;
                 }
-                None
+                self.paste(&text)
             }
             Action::PasteBulk(text) => {
                 // Bracketed paste — terminal sent the entire pasted text at once.
-                if !text.is_empty() {
-                    let to_paste = if self.focused == InputField::Path {
-                        // Path field: only first line, no newlines.
-                        text.lines().next().unwrap_or("").to_string()
-                    } else {
-                        text.clone()
-                    };
-                    self.insert_str(&to_paste);
-                    if self.focused == InputField::Path {
-                        self.refresh_suggestions();
-                    }
+                if text.is_empty() {
+                    return None;
                 }
-                None
+                self.paste(text)
             }
 
             // ── Tab: accept suggestion OR switch field ──────────
@@ -627,6 +892,12 @@ impl Component for ProjectIntakeComponent {
                 self.error = Some(err.clone());
                 None
             }
+            Action::Escape => {
+                if self.suggestion_index.take().is_some() {
+                    return None;
+                }
+                Some(Action::CancelPhaseTask)
+            }
             _ => None,
         }
     }
@@ -636,7 +907,9 @@ impl Component for ProjectIntakeComponent {
         let inner = area;
 
         // Calculate suggestion area height.
-        let suggestion_height = if self.has_suggestions() {
+        let suggestion_height = if self.dir_tree.is_some() {
+            DIR_TREE_HEIGHT
+        } else if self.has_suggestions() {
             (self.suggestions.len() as u16 + 2).min(MAX_SUGGESTIONS as u16 + 2) // +2 for border
         } else {
             0
@@ -663,18 +936,12 @@ impl Component for ProjectIntakeComponent {
 
         // ── Path input field ────────────────────────────────────
         let path_focused = self.focused == InputField::Path && self.wants_input();
-        Self::render_text_field(
-            &self.path_input,
-            self.cursor,
-            path_focused,
-            "/path/to/your/project",
-            " Project Path ",
-            frame,
-            chunks[0],
-        );
+        self.render_path_field(path_focused, frame, chunks[0]);
 
-        // ── Suggestions dropdown ────────────────────────────────
-        if self.has_suggestions() {
+        // ── Suggestions dropdown or directory tree ──────────────
+        if let Some(ref tree) = self.dir_tree {
+            tree.render(frame, chunks[1]);
+        } else if self.has_suggestions() {
             let suggestion_block = Block::default()
                 .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
                 .border_style(Theme::border());
@@ -702,10 +969,17 @@ impl Component for ProjectIntakeComponent {
                         }
                     };
 
-                    ListItem::new(Line::from(vec![Span::styled(
-                        format!(" {}{} ", s.name, icon),
-                        style,
-                    )]))
+                    let mut spans = vec![Span::styled(" ", style)];
+                    for (i, c) in s.name.chars().enumerate() {
+                        let style = if s.matched.contains(&i) {
+                            style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                        } else {
+                            style
+                        };
+                        spans.push(Span::styled(c.to_string(), style));
+                    }
+                    spans.push(Span::styled(format!("{} ", icon), style));
+                    ListItem::new(Line::from(spans))
                 })
                 .collect();
 
@@ -718,37 +992,59 @@ impl Component for ProjectIntakeComponent {
         self.render_description_field(desc_focused, frame, chunks[2]);
 
         // ── Instructions ────────────────────────────────────────
-        let instructions = if self.has_suggestions() {
+        let instructions = if self.dir_tree.is_some() {
             Paragraph::new(Line::from(vec![
-                Span::styled("  tab", Theme::key_hint()),
-                Span::styled(" accept  ", Theme::dim()),
+                Span::styled("  ↑↓", Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.navigate")), Theme::dim()),
+                Span::styled("→←", Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.open_close")), Theme::dim()),
+                Span::styled(keymap::editing_key("newline"), Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.choose")), Theme::dim()),
+                Span::styled(".", Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.hidden")), Theme::dim()),
+                Span::styled(keymap::editing_key("close"), Theme::key_hint()),
+                Span::styled(format!(" {}", t!("hint.close")), Theme::dim()),
+            ]))
+        } else if self.has_suggestions() {
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("  {}", keymap::editing_key("switch_field")),
+                    Theme::key_hint(),
+                ),
+                Span::styled(format!(" {}  ", t!("hint.accept")), Theme::dim()),
                 Span::styled("↑↓", Theme::key_hint()),
-                Span::styled(" navigate  ", Theme::dim()),
-                Span::styled("enter", Theme::key_hint()),
-                Span::styled(" next field", Theme::dim()),
+                Span::styled(format!(" {}  ", t!("hint.navigate")), Theme::dim()),
+                Span::styled(keymap::editing_key("newline"), Theme::key_hint()),
+                Span::styled(format!(" {}", t!("hint.next_field")), Theme::dim()),
             ]))
         } else if self.wants_input() && self.focused == InputField::Description {
             Paragraph::new(Line::from(vec![
-                Span::styled("  ctrl+s", Theme::key_hint()),
-                Span::styled(" submit  ", Theme::dim()),
-                Span::styled("tab", Theme::key_hint()),
-                Span::styled(" switch  ", Theme::dim()),
-                Span::styled("ctrl+v", Theme::key_hint()),
-                Span::styled(" paste", Theme::dim()),
+                Span::styled(
+                    format!("  {}", keymap::editing_key("submit")),
+                    Theme::key_hint(),
+                ),
+                Span::styled(format!(" {}  ", t!("hint.submit")), Theme::dim()),
+                Span::styled(keymap::editing_key("switch_field"), Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.switch")), Theme::dim()),
+                Span::styled(keymap::editing_key("paste"), Theme::key_hint()),
+                Span::styled(format!(" {}", t!("hint.paste")), Theme::dim()),
             ]))
         } else if self.wants_input() {
             Paragraph::new(Line::from(vec![
-                Span::styled("  enter", Theme::key_hint()),
-                Span::styled(" next field  ", Theme::dim()),
-                Span::styled("ctrl+s", Theme::key_hint()),
-                Span::styled(" submit  ", Theme::dim()),
-                Span::styled("tab", Theme::key_hint()),
-                Span::styled(" switch", Theme::dim()),
+                Span::styled(
+                    format!("  {}", keymap::editing_key("newline")),
+                    Theme::key_hint(),
+                ),
+                Span::styled(format!(" {}  ", t!("hint.next_field")), Theme::dim()),
+                Span::styled(keymap::editing_key("submit"), Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.submit")), Theme::dim()),
+                Span::styled(keymap::editing_key("switch_field"), Theme::key_hint()),
+                Span::styled(format!(" {}", t!("hint.switch")), Theme::dim()),
             ]))
         } else {
             Paragraph::new(Line::from(vec![
-                Span::styled("  →", Theme::key_hint()),
-                Span::styled(" next phase", Theme::dim()),
+                Span::styled(format!("  {}", self.next_phase_key()), Theme::key_hint()),
+                Span::styled(format!(" {}", t!("hint.next_phase")), Theme::dim()),
             ]))
         };
         frame.render_widget(instructions, chunks[3]);
@@ -758,13 +1054,13 @@ impl Component for ProjectIntakeComponent {
             // No status to show — description fills the space.
         } else if self.analyzing {
             let spinner = Paragraph::new(Span::styled(
-                "Analyzing project...",
+                t!("intake.analyzing"),
                 Style::default().fg(Theme::warning()),
             ));
             frame.render_widget(spinner, chunks[4]);
         } else if let Some(ref error) = self.error {
             let err = Paragraph::new(Span::styled(
-                format!("Error: {}", error),
+                t!("common.error", error = error),
                 Style::default().fg(Theme::error()),
             ))
             .wrap(Wrap { trim: true });
@@ -772,20 +1068,20 @@ impl Component for ProjectIntakeComponent {
         } else if let Some(ref profile) = self.profile {
             let lines = vec![
                 Line::from(vec![
-                    Span::styled("Languages: ", Theme::header()),
+                    Span::styled(format!("{} ", t!("intake.languages")), Theme::header()),
                     Span::styled(format!("{:?}", profile.languages), Theme::normal()),
                 ]),
                 Line::from(vec![
-                    Span::styled("Files: ", Theme::header()),
+                    Span::styled(format!("{} ", t!("intake.files")), Theme::header()),
                     Span::styled(format!("{}", profile.file_count), Theme::normal()),
                 ]),
                 Line::from(vec![
-                    Span::styled("Summary: ", Theme::header()),
+                    Span::styled(format!("{} ", t!("intake.summary")), Theme::header()),
                     Span::styled(&profile.summary, Theme::normal()),
                 ]),
                 Line::from(""),
                 Line::from(Span::styled(
-                    "Press [Right] to proceed to Research Discovery",
+                    t!("intake.proceed", key = self.next_phase_key()),
                     Theme::selected(),
                 )),
             ];
@@ -808,15 +1104,24 @@ impl ProjectIntakeComponent {
         let line_count = text.split('\n').count();
 
         // Title shows line count when content is long.
-        let title = if text.is_empty() {
-            " Describe what AI capability to add (multi-line) ".to_string()
-        } else {
+        let title = if let Some(paste) = &self.paste {
             format!(
-                " Description ({} line{}, {} chars) ",
-                line_count,
-                if line_count == 1 { "" } else { "s" },
-                text.len()
+                " {} ",
+                t!(
+                    "intake.description_pasting",
+                    percent = paste.inserted * 100 / paste.text.len(),
+                    kb = paste.text.len() / 1024
+                )
             )
+        } else if text.is_empty() {
+            format!(" {} ", t!("intake.description_prompt"))
+        } else {
+            let key = if line_count == 1 {
+                "intake.description_one_line"
+            } else {
+                "intake.description_lines"
+            };
+            format!(" {} ", t!(key, lines = line_count, chars = text.len()))
         };
 
         let block = Block::default()
@@ -830,9 +1135,7 @@ impl ProjectIntakeComponent {
         // Placeholder when empty and not focused.
         if text.is_empty() && !is_focused {
             let placeholder = Paragraph::new(Span::styled(
-                "Paste or type a detailed description of the AI capability you want.\n\
-                 The more detail you provide (research concepts, techniques, papers),\n\
-                 the better uniq can search for relevant approaches.",
+                t!("intake.description_placeholder"),
                 Theme::dim(),
             ))
             .wrap(Wrap { trim: true })
@@ -862,25 +1165,32 @@ impl ProjectIntakeComponent {
 
         // Word-wrap each logical line into visual lines.
         // Track: (visual_line_text, is_cursor_on_this_line, cursor_col_in_visual_line)
-        struct VisualLine {
-            text: String,
+        struct VisualLine<'a> {
+            text: &'a str,
+            start: usize,              // Byte offset of `text` in the description
             cursor_col: Option<usize>, // Some(col) if cursor is on this visual line
         }
 
         let logical_lines: Vec<&str> = text.split('\n').collect();
+        let mut wrapped_text = self.wrapped.borrow_mut();
+        wrapped_text.update(text, wrap_width);
+        let wrapped_text = &*wrapped_text;
         let (cursor_logical, cursor_col_in_logical) = self.cursor_line_col(text, self.cursor);
 
         let mut visual_lines: Vec<VisualLine> = Vec::new();
         let mut cursor_visual_line: usize = 0;
 
-        for (li, logical_text) in logical_lines.iter().enumerate() {
+        let mut line_start = 0usize;
+        for (li, (logical_text, wrapped)) in
+            logical_lines.iter().zip(&wrapped_text.lines).enumerate()
+        {
             let is_cursor_logical = li == cursor_logical;
-            let wrapped = wrap_line(logical_text, wrap_width);
 
             if wrapped.is_empty() {
                 // Empty line.
                 let vl = VisualLine {
-                    text: String::new(),
+                    text: "",
+                    start: line_start,
                     cursor_col: if is_cursor_logical { Some(0) } else { None },
                 };
                 if is_cursor_logical {
@@ -889,7 +1199,7 @@ impl ProjectIntakeComponent {
                 visual_lines.push(vl);
             } else {
                 let mut col_offset = 0usize;
-                for segment in &wrapped {
+                for segment in wrapped {
                     let seg_len = segment.len();
                     let cursor_col = if is_cursor_logical {
                         let c = cursor_col_in_logical;
@@ -911,7 +1221,8 @@ impl ProjectIntakeComponent {
                         None
                     };
                     visual_lines.push(VisualLine {
-                        text: segment.clone(),
+                        text: segment,
+                        start: line_start + col_offset,
                         cursor_col,
                     });
                     col_offset += seg_len;
@@ -930,6 +1241,7 @@ impl ProjectIntakeComponent {
                     }
                 }
             }
+            line_start += logical_text.len() + 1;
         }
 
         let total_visual = visual_lines.len();
@@ -947,36 +1259,19 @@ impl ProjectIntakeComponent {
         };
 
         // Build rendered lines for the visible viewport.
+        let selection = self.selection();
         let mut rendered_lines: Vec<Line> = Vec::new();
-        for i in scroll..total_visual {
+        for vl in visual_lines.iter().skip(scroll) {
             if rendered_lines.len() >= viewport_height {
                 break;
             }
-            let vl = &visual_lines[i];
-            if let Some(col) = vl.cursor_col {
-                let col = col.min(vl.text.len());
-                let (before, after) = vl.text.split_at(col);
-                let cursor_char = if after.is_empty() {
-                    " ".to_string()
-                } else {
-                    after.chars().next().unwrap().to_string()
-                };
-                let rest = if after.len() > cursor_char.len() {
-                    &after[cursor_char.len()..]
-                } else {
-                    ""
-                };
-                rendered_lines.push(Line::from(vec![
-                    Span::styled(before, Theme::normal()),
-                    Span::styled(
-                        cursor_char,
-                        Style::default().fg(Theme::bg()).bg(Theme::accent()),
-                    ),
-                    Span::styled(rest, Theme::normal()),
-                ]));
-            } else {
-                rendered_lines.push(Line::from(Span::styled(&vl.text, Theme::normal())));
-            }
+            // The part of the selection on this visual line, relative to it.
+            let end = vl.start + vl.text.len();
+            let selected = selection
+                .clone()
+                .filter(|s| s.start < end && s.end > vl.start)
+                .map(|s| s.start.saturating_sub(vl.start)..s.end.min(end) - vl.start);
+            rendered_lines.push(Line::from(field_spans(vl.text, vl.cursor_col, selected)));
         }
 
         // Show scroll indicator in border if content overflows.
@@ -984,14 +1279,23 @@ impl ProjectIntakeComponent {
         let has_more_below = scroll + viewport_height < total_visual;
         let scroll_hint = if has_more_above && has_more_below {
             format!(
-                " [{}/{} vis.lines] ",
-                scroll + viewport_height,
-                total_visual
+                " [{}] ",
+                t!(
+                    "intake.scroll_position",
+                    shown = scroll + viewport_height,
+                    total = total_visual
+                )
             )
         } else if has_more_below {
-            format!(" [{} more below] ", total_visual - scroll - viewport_height)
+            format!(
+                " [{}] ",
+                t!(
+                    "intake.more_below",
+                    count = total_visual - scroll - viewport_height
+                )
+            )
         } else if has_more_above {
-            format!(" [{} above] ", scroll)
+            format!(" [{}] ", t!("intake.above", count = scroll))
         } else {
             String::new()
         };
@@ -1011,12 +1315,14 @@ impl ProjectIntakeComponent {
 
         let display = Paragraph::new(rendered_lines).block(block);
         frame.render_widget(display, area);
+        render_scrollbar(frame, area.inner(Margin::new(0, 1)), total_visual, scroll);
     }
 }
 
-/// Word-wrap a single logical line to fit within `max_width` columns.
-/// Returns a list of visual line segments. Tries to break at word boundaries;
-/// falls back to hard breaks if a word is longer than the width.
+/// Word-wrap a single logical line to fit within `max_width` terminal
+/// columns, so wide characters such as CJK take two. Returns a list of visual line
+/// segments. Tries to break at word boundaries; falls back to hard breaks if
+/// a word is longer than the width.
 fn wrap_line(text: &str, max_width: usize) -> Vec<String> {
     if max_width == 0 {
         return vec![text.to_string()];
@@ -1024,40 +1330,33 @@ fn wrap_line(text: &str, max_width: usize) -> Vec<String> {
     if text.is_empty() {
         return vec![String::new()];
     }
-    if text.len() <= max_width {
-        return vec![text.to_string()];
-    }
 
     let mut lines = Vec::new();
     let mut remaining = text;
 
     while !remaining.is_empty() {
-        if remaining.len() <= max_width {
+        // Only measure what can fit, so very long lines wrap in linear time.
+        let fits = take_width(remaining, max_width).len();
+        if fits == remaining.len() {
             lines.push(remaining.to_string());
             break;
         }
+        // Byte offset just past what fits, always at least one character.
+        let limit = match fits {
+            0 => next_boundary(remaining, 0),
+            n => n,
+        };
 
         // Find the last space within max_width to break at.
-        let chunk = &remaining[..max_width];
-        let break_pos = if let Some(pos) = chunk.rfind(' ') {
-            // Don't break too early — at least half the width should be used.
-            if pos > max_width / 3 {
+        let chunk = &remaining[..limit];
+        let break_pos = match chunk.rfind(' ') {
+            // Don't break too early — at least a third of the width should be used.
+            Some(pos) if chunk[..pos].width() > max_width / 3 => {
                 pos + 1 // Include the space on the current line.
-            } else {
-                max_width // Hard break.
             }
-        } else {
-            max_width // No space found — hard break.
+            _ => limit, // Hard break.
         };
 
-        // Ensure we don't split in the middle of a multi-byte char.
-        let break_pos = remaining
-            .char_indices()
-            .map(|(i, _)| i)
-            .take_while(|&i| i <= break_pos)
-            .last()
-            .unwrap_or(break_pos);
-
         let (line, rest) = remaining.split_at(break_pos);
         lines.push(line.to_string());
         remaining = rest;
@@ -1065,3 +1364,314 @@ fn wrap_line(text: &str, max_width: usize) -> Vec<String> {
 
     lines
 }
+
+/// Spans for a line of a text field: the cursor as a block on the cluster
+/// under it (or a space past the end), and the selection highlighted.
+/// `cursor` and `selection` are byte offsets into `text`.
+fn field_spans(
+    text: &str,
+    cursor: Option<usize>,
+    selection: Option<Range<usize>>,
+) -> Vec<Span<'static>> {
+    let cursor_style = Style::default().fg(Theme::bg()).bg(Theme::accent());
+    let cursor = cursor.map(|c| boundary_at_or_before(text, c));
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_style = Theme::normal();
+    for (i, grapheme) in text.grapheme_indices(true) {
+        let style = if cursor == Some(i) {
+            cursor_style
+        } else if selection.as_ref().is_some_and(|s| s.contains(&i)) {
+            Theme::normal().patch(Theme::selection())
+        } else {
+            Theme::normal()
+        };
+        if style != run_style && !run.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut run), run_style));
+        }
+        run_style = style;
+        run.push_str(grapheme);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, run_style));
+    }
+    if cursor == Some(text.len()) {
+        spans.push(Span::styled(" ", cursor_style));
+    }
+    spans
+}
+
+/// Directories that `dir` could mean: itself if it exists, otherwise the
+/// best fuzzy matches for each segment after its deepest existing ancestor.
+fn fuzzy_dirs(dir: &Path) -> Vec<DirMatch> {
+    let mut base = PathBuf::new();
+    let mut segments = Vec::new();
+    for component in dir.components() {
+        if segments.is_empty() && base.join(component).is_dir() {
+            base.push(component);
+        } else {
+            segments.push(component.as_os_str().to_string_lossy().to_string());
+        }
+    }
+    if base.as_os_str().is_empty() {
+        return Vec::new();
+    }
+
+    let mut branches = vec![DirMatch {
+        path: base,
+        label: String::new(),
+        score: 0,
+        matched: Vec::new(),
+    }];
+    for segment in segments {
+        let mut next = Vec::new();
+        for branch in &branches {
+            let offset = branch.label.chars().count();
+            for (name, path, is_dir) in dir_entries(&branch.path, &segment) {
+                if !is_dir {
+                    continue;
+                }
+                let Some((score, positions)) = fuzzy_match(&segment, &name) else {
+                    continue;
+                };
+                let mut matched = branch.matched.clone();
+                matched.extend(positions.into_iter().map(|i| i + offset));
+                next.push(DirMatch {
+                    path,
+                    label: format!("{}{}/", branch.label, name),
+                    score: branch.score + score,
+                    matched,
+                });
+            }
+        }
+        next.sort_by_key(|branch| std::cmp::Reverse(branch.score));
+        next.truncate(MAX_BRANCHES);
+        branches = next;
+    }
+    branches
+}
+
+/// Entries of `dir` as (name, path, is_dir), skipping hidden ones unless
+/// `query` starts with a dot.
+fn dir_entries(dir: &Path, query: &str) -> Vec<(String, PathBuf, bool)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            (
+                entry.file_name().to_string_lossy().to_string(),
+                path,
+                is_dir,
+            )
+        })
+        .filter(|(name, _, _)| !name.starts_with('.') || query.starts_with('.'))
+        .collect()
+}
+
+/// `input` with a leading `~` replaced by the home directory.
+fn expand_tilde(input: &str) -> String {
+    match (input.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.to_string_lossy().to_string() + rest,
+        _ => input.to_string(),
+    }
+}
+
+/// Status after pasting `pasted` bytes of `original`.
+fn paste_status(pasted: usize, original: usize) -> Action {
+    Action::SetStatus(if pasted < original {
+        t!(
+            "intake.pasted_truncated",
+            pasted = pasted / 1024,
+            original = original / 1024,
+            limit = MAX_DESCRIPTION / 1024
+        )
+    } else {
+        t!("intake.pasted", kb = pasted / 1024)
+    })
+}
+
+/// The last char boundary at or before byte offset `pos`.
+fn char_boundary_at_or_before(text: &str, pos: usize) -> usize {
+    (0..=pos.min(text.len()))
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+/// The last grapheme cluster boundary at or before byte offset `pos`.
+fn boundary_at_or_before(text: &str, pos: usize) -> usize {
+    let pos = char_boundary_at_or_before(text, pos);
+    let mut cursor = GraphemeCursor::new(pos, text.len(), true);
+    if cursor.is_boundary(text, 0).unwrap_or(true) {
+        return pos;
+    }
+    cursor.prev_boundary(text, 0).ok().flatten().unwrap_or(0)
+}
+
+/// The first grapheme cluster boundary at or after byte offset `pos`.
+fn boundary_at_or_after(text: &str, pos: usize) -> usize {
+    let Some(pos) = (pos..=text.len()).find(|&i| text.is_char_boundary(i)) else {
+        return text.len();
+    };
+    let mut cursor = GraphemeCursor::new(pos, text.len(), true);
+    if cursor.is_boundary(text, 0).unwrap_or(true) {
+        return pos;
+    }
+    cursor
+        .next_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(text.len())
+}
+
+/// Start of the grapheme cluster before boundary `pos`.
+fn prev_boundary(text: &str, pos: usize) -> usize {
+    text[..pos]
+        .grapheme_indices(true)
+        .next_back()
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// End of the grapheme cluster after boundary `pos`.
+fn next_boundary(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .graphemes(true)
+        .next()
+        .map(|g| pos + g.len())
+        .unwrap_or(pos)
+}
+
+/// Byte offset of the `col`-th grapheme cluster in `line`, or its end.
+fn offset_of_column(line: &str, col: usize) -> usize {
+    line.grapheme_indices(true)
+        .nth(col)
+        .map(|(i, _)| i)
+        .unwrap_or(line.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// "é" as "e" plus a combining acute accent.
+    const COMBINED: &str = "e\u{301}";
+    /// Man, woman and girl joined into one family emoji.
+    const FAMILY: &str = "👨\u{200d}👩\u{200d}👧";
+
+    #[test]
+    fn boundaries_never_split_a_cluster() {
+        let text = format!("a{COMBINED}b");
+        assert_eq!(boundary_at_or_before(&text, 2), 1);
+        assert_eq!(boundary_at_or_before(&text, 4), 4);
+        assert_eq!(prev_boundary(&text, 4), 1);
+        assert_eq!(prev_boundary(&text, 1), 0);
+
+        // Inside a three-byte character.
+        let cjk = "日本語";
+        assert_eq!(boundary_at_or_before(cjk, 4), 3);
+        assert_eq!(prev_boundary(cjk, 6), 3);
+
+        let text = format!("{FAMILY}x");
+        for inside in 1..FAMILY.len() {
+            assert_eq!(boundary_at_or_before(&text, inside), 0, "at {inside}");
+        }
+        assert_eq!(prev_boundary(&text, FAMILY.len()), 0);
+        assert_eq!(prev_boundary(&text, text.len()), FAMILY.len());
+    }
+
+    #[test]
+    fn columns_count_clusters() {
+        let text = format!("{COMBINED}{FAMILY}日x");
+        assert_eq!(offset_of_column(&text, 0), 0);
+        assert_eq!(offset_of_column(&text, 1), COMBINED.len());
+        assert_eq!(offset_of_column(&text, 2), COMBINED.len() + FAMILY.len());
+        assert_eq!(offset_of_column(&text, 3), text.len() - 1);
+        assert_eq!(offset_of_column(&text, 9), text.len());
+    }
+
+    #[test]
+    fn wrapping_measures_display_width() {
+        assert_eq!(wrap_line("日本語", 4), ["日本", "語"]);
+        // A wide character never fits a narrower line, but still moves on.
+        assert_eq!(wrap_line("日本", 1), ["日", "本"]);
+        assert_eq!(
+            wrap_line(&COMBINED.repeat(3), 2),
+            [COMBINED.repeat(2), COMBINED.to_string()]
+        );
+
+        let families = FAMILY.repeat(3);
+        let lines = wrap_line(&families, 4);
+        assert_eq!(lines.concat(), families);
+        assert!(lines
+            .iter()
+            .all(|line| !line.is_empty() && line.graphemes(true).all(|g| g == FAMILY)));
+    }
+
+    /// `projects/uniq`, `pr_jobs`, `.prj`, a `prj.txt` file and `misc`.
+    fn root() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        for dir in ["projects/uniq", "pr_jobs", ".prj", "misc"] {
+            std::fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        std::fs::write(root.path().join("prj.txt"), "").unwrap();
+        root
+    }
+
+    fn labels(matches: &[DirMatch]) -> Vec<&str> {
+        matches.iter().map(|m| m.label.as_str()).collect()
+    }
+
+    #[test]
+    fn an_existing_directory_matches_itself() {
+        let root = root();
+        let matches = fuzzy_dirs(&root.path().join("projects"));
+        assert_eq!(labels(&matches), [""]);
+        assert_eq!(matches[0].path, root.path().join("projects"));
+    }
+
+    #[test]
+    fn directories_rank_by_how_well_they_match() {
+        let root = root();
+        let matches = fuzzy_dirs(&root.path().join("prj"));
+        // `j` starts a word in `pr_jobs`, so it beats `projects`.
+        assert_eq!(labels(&matches), ["pr_jobs/", "projects/"]);
+        assert!(matches[0].score > matches[1].score);
+        assert_eq!(matches[1].matched, [0, 1, 3]);
+    }
+
+    #[test]
+    fn later_segments_continue_from_each_match() {
+        let root = root();
+        let matches = fuzzy_dirs(&root.path().join("prj/uq"));
+        assert_eq!(labels(&matches), ["projects/uniq/"]);
+        assert_eq!(matches[0].path, root.path().join("projects/uniq"));
+        assert_eq!(matches[0].matched, [0, 1, 3, 9, 12]);
+    }
+
+    #[test]
+    fn files_and_hidden_directories_are_left_out() {
+        let root = root();
+        let matches = fuzzy_dirs(&root.path().join("prj"));
+        assert!(!labels(&matches).contains(&"prj.txt/"));
+        assert!(!labels(&matches).contains(&".prj/"));
+        assert!(fuzzy_dirs(&root.path().join("zzz")).is_empty());
+
+        // A leading dot asks for hidden directories.
+        let hidden = fuzzy_dirs(&root.path().join(".pj"));
+        assert_eq!(labels(&hidden), [".prj/"]);
+    }
+
+    #[test]
+    fn only_the_best_branches_are_kept() {
+        let root = tempfile::tempdir().unwrap();
+        for i in 0..MAX_BRANCHES + 3 {
+            std::fs::create_dir(root.path().join(format!("dir{i}"))).unwrap();
+        }
+        assert_eq!(fuzzy_dirs(&root.path().join("dr")).len(), MAX_BRANCHES);
+    }
+}
@@ -0,0 +1,169 @@
+//! Prompt overlay — a one-line text input used by actions that need a
+//! short piece of free-form text from the user (guidance, names, notes).
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::action::{Action, PromptKind};
+use crate::components::Component;
+use crate::theme::Theme;
+
+pub struct PromptComponent {
+    /// Whether the prompt is visible.
+    pub visible: bool,
+    /// What the prompt is collecting input for.
+    kind: Option<PromptKind>,
+    /// Current input text.
+    input: String,
+}
+
+impl PromptComponent {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            kind: None,
+            input: String::new(),
+        }
+    }
+
+    fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+        let vertical = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(area);
+
+        let horizontal = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(width),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(vertical[1]);
+
+        horizontal[1]
+    }
+
+    fn close(&mut self) {
+        self.visible = false;
+        self.kind = None;
+        self.input.clear();
+    }
+
+    /// Submit the current input, producing the follow-up action.
+    fn submit(&mut self) -> Option<Action> {
+        let text = self.input.trim().to_string();
+        if text.is_empty() {
+            return Some(Action::SetStatus(t!("prompt.empty").to_string()));
+        }
+        let action = self.kind.as_ref().map(|kind| kind.submit(text));
+        self.close();
+        action
+    }
+}
+
+impl Default for PromptComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for PromptComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if let Action::OpenPrompt(kind) = action {
+            self.visible = true;
+            self.kind = Some(kind.clone());
+            self.input.clear();
+            return None;
+        }
+
+        if !self.visible {
+            return None;
+        }
+
+        match action {
+            Action::CharInput(c) => {
+                self.input.push(*c);
+                None
+            }
+            Action::BackspaceInput => {
+                self.input.pop();
+                None
+            }
+            Action::DeleteWord => {
+                let trimmed = self.input.trim_end().len();
+                self.input.truncate(trimmed);
+                let start = self.input.rfind(' ').map(|i| i + 1).unwrap_or(0);
+                self.input.truncate(start);
+                None
+            }
+            Action::PasteBulk(text) => {
+                self.input.push_str(text.lines().next().unwrap_or(""));
+                None
+            }
+            Action::NewlineInput | Action::SubmitForm => self.submit(),
+            Action::Escape => {
+                self.close();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+        let Some(ref kind) = self.kind else {
+            return;
+        };
+
+        let width = area.width.saturating_sub(4).min(72);
+        let dialog = Self::centered_rect(area, width, 6);
+        frame.render_widget(Clear, dialog);
+
+        let block = Block::default()
+            .title(kind.title())
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let cursor = Span::styled(" ", Style::default().fg(Theme::bg()).bg(Theme::accent()));
+        let input_line = if self.input.is_empty() {
+            Line::from(vec![
+                Span::raw(" "),
+                cursor,
+                Span::styled(format!(" {}", kind.placeholder()), Theme::dim()),
+            ])
+        } else {
+            // Keep the tail of long input visible.
+            let max = (width as usize).saturating_sub(5);
+            let start = self.input.chars().count().saturating_sub(max);
+            let visible: String = self.input.chars().skip(start).collect();
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(visible, Theme::normal()),
+                cursor,
+            ])
+        };
+
+        let lines = vec![
+            Line::from(""),
+            input_line,
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(" enter", Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.submit")), Theme::dim()),
+                Span::styled("esc", Theme::key_hint()),
+                Span::styled(format!(" {}", t!("hint.cancel")), Theme::dim()),
+            ]),
+        ];
+
+        frame.render_widget(Paragraph::new(lines).block(block), dialog);
+    }
+}
@@ -0,0 +1,190 @@
+//! Rating overlay — set 1–5 stars and notes for a benchmarked variant.
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::action::Action;
+use crate::components::Component;
+use crate::theme::Theme;
+
+pub struct RatingComponent {
+    /// Whether the dialog is visible.
+    pub visible: bool,
+    /// Variant being rated.
+    variant_id: String,
+    variant_name: String,
+    /// Star rating, 1-5.
+    stars: u8,
+    /// Free-form notes.
+    notes: String,
+}
+
+impl RatingComponent {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            variant_id: String::new(),
+            variant_name: String::new(),
+            stars: 3,
+            notes: String::new(),
+        }
+    }
+
+    fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+        let vertical = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(area);
+
+        let horizontal = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(width),
+            Constraint::Min(0),
+        ])
+        .flex(Flex::Center)
+        .split(vertical[1]);
+
+        horizontal[1]
+    }
+
+    fn close(&mut self) {
+        self.visible = false;
+        self.notes.clear();
+    }
+}
+
+impl Default for RatingComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for RatingComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if let Action::OpenRatingDialog {
+            variant_id,
+            variant_name,
+            stars,
+            notes,
+        } = action
+        {
+            self.visible = true;
+            self.variant_id = variant_id.clone();
+            self.variant_name = variant_name.clone();
+            self.stars = (*stars).clamp(1, 5);
+            self.notes = notes.clone();
+            return None;
+        }
+
+        if !self.visible {
+            return None;
+        }
+
+        match action {
+            Action::ScrollUp => {
+                self.stars = (self.stars + 1).min(5);
+                None
+            }
+            Action::ScrollDown => {
+                self.stars = self.stars.saturating_sub(1).max(1);
+                None
+            }
+            Action::CharInput(c) => {
+                self.notes.push(*c);
+                None
+            }
+            Action::BackspaceInput => {
+                self.notes.pop();
+                None
+            }
+            Action::DeleteWord => {
+                let trimmed = self.notes.trim_end().len();
+                self.notes.truncate(trimmed);
+                let start = self.notes.rfind(' ').map(|i| i + 1).unwrap_or(0);
+                self.notes.truncate(start);
+                None
+            }
+            Action::PasteBulk(text) => {
+                self.notes.push_str(text.lines().next().unwrap_or(""));
+                None
+            }
+            Action::NewlineInput | Action::SubmitForm => {
+                let action = Action::UserRated {
+                    variant_id: self.variant_id.clone(),
+                    stars: self.stars,
+                    notes: self.notes.trim().to_string(),
+                };
+                self.close();
+                Some(action)
+            }
+            Action::Escape => {
+                self.close();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let width = area.width.saturating_sub(4).min(64);
+        let dialog = Self::centered_rect(area, width, 9);
+        frame.render_widget(Clear, dialog);
+
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t!("rating.title", name = self.variant_name)
+            ))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let stars = Line::from(vec![
+            Span::styled(format!(" {}  ", t!("rating.stars")), Theme::header()),
+            Span::styled(
+                "★".repeat(self.stars as usize),
+                Style::default().fg(Theme::warning()),
+            ),
+            Span::styled("☆".repeat(5 - self.stars as usize), Theme::dim()),
+            Span::styled(format!("  {}/5", self.stars), Theme::dim()),
+        ]);
+
+        let cursor = Span::styled(" ", Style::default().fg(Theme::bg()).bg(Theme::accent()));
+        let max = (width as usize).saturating_sub(12);
+        let start = self.notes.chars().count().saturating_sub(max);
+        let visible: String = self.notes.chars().skip(start).collect();
+        let notes = Line::from(vec![
+            Span::styled(format!(" {}  ", t!("rating.notes")), Theme::header()),
+            Span::styled(visible, Theme::normal()),
+            cursor,
+        ]);
+
+        let lines = vec![
+            Line::from(""),
+            stars,
+            Line::from(""),
+            notes,
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(" ↑/↓", Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.stars")), Theme::dim()),
+                Span::styled("enter", Theme::key_hint()),
+                Span::styled(format!(" {}  ", t!("hint.save")), Theme::dim()),
+                Span::styled("esc", Theme::key_hint()),
+                Span::styled(format!(" {}", t!("hint.cancel")), Theme::dim()),
+            ]),
+        ];
+
+        frame.render_widget(Paragraph::new(lines).block(block), dialog);
+    }
+}
@@ -1,25 +1,29 @@
 //! Phase 2: Research Discovery — search and display academic papers.
 
-use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Modifier, Style};
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::action::Action;
-use crate::components::Component;
+use crate::components::progress::Spinner;
+use crate::components::{
+    field_label, find_next, no_match, render_scrollbar, resize_pane, scrollbar_gutter, truncate,
+    word_wrap, Component, Focus, TextScroll, Viewport, PAGE,
+};
 use crate::theme::Theme;
 
+use uniq_core::config::PaneSizes;
 use uniq_core::research::PaperMeta;
 
-/// Braille spinner frames.
-const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-
 pub struct ResearchExplorerComponent {
     /// All discovered papers.
     pub papers: Vec<PaperMeta>,
     /// Currently selected paper index.
     pub selected: usize,
+    /// Scroll position of the paper list.
+    list_view: Viewport,
     /// Whether search is in progress.
     pub searching: bool,
     /// Progress (papers found so far vs target).
@@ -28,14 +32,18 @@ pub struct ResearchExplorerComponent {
     pub error: Option<String>,
     /// Whether expanded detail view is open.
     detail_expanded: bool,
-    /// Scroll position within the expanded detail view.
-    detail_scroll: u16,
-    /// Spinner animation frame counter.
-    spinner_tick: usize,
+    /// Scroll position within the detail panel or expanded view.
+    detail_scroll: TextScroll,
+    /// Whether the paper list or the detail panel takes navigation keys.
+    focus: Focus,
+    /// Activity indicator shown while work runs.
+    pub spinner: Spinner,
     /// Current query being searched.
     current_query: String,
     /// Query-level progress: (current idx 0-based, total).
     query_progress: (usize, usize),
+    /// Height of the paper detail panel, in lines.
+    pub detail_height: u16,
 }
 
 impl ResearchExplorerComponent {
@@ -43,61 +51,127 @@ impl ResearchExplorerComponent {
         Self {
             papers: Vec::new(),
             selected: 0,
+            list_view: Viewport::default(),
             searching: false,
             progress: (0, 0),
             error: None,
             detail_expanded: false,
-            detail_scroll: 0,
-            spinner_tick: 0,
+            detail_scroll: TextScroll::default(),
+            focus: Focus::default(),
+            spinner: Spinner::default(),
             current_query: String::new(),
             query_progress: (0, 0),
+            detail_height: PaneSizes::default().research_detail,
         }
     }
 }
 
+impl ResearchExplorerComponent {
+    /// Whether navigation keys scroll the paper's details rather than move
+    /// through the list.
+    fn scrolls_detail(&self) -> bool {
+        self.detail_expanded || self.focus == Focus::Detail
+    }
+}
+
+/// Name of the research plugin that found the paper with `id`.
+fn plugin_name(id: &str) -> &str {
+    id.split_once(':').map_or("Plugin", |(name, _)| name)
+}
+
+impl Default for ResearchExplorerComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Component for ResearchExplorerComponent {
     fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        // The expanded view or focused detail panel scrolls instead of
+        // moving the selection.
+        if self.scrolls_detail() && self.detail_scroll.handle(action) {
+            return None;
+        }
         match action {
             Action::Tick => {
                 if self.searching {
-                    self.spinner_tick = self.spinner_tick.wrapping_add(1);
+                    self.spinner.tick();
                 }
                 None
             }
+            Action::Copy => {
+                let paper = self.papers.get(self.selected)?;
+                Some(Action::CopyText {
+                    text: paper.citation(),
+                    what: t!("research.citation").to_string(),
+                })
+            }
             Action::Confirm => {
                 if self.detail_expanded {
                     self.detail_expanded = false;
-                    self.detail_scroll = 0;
+                    self.detail_scroll.reset();
                 } else if !self.papers.is_empty() {
                     self.detail_expanded = true;
-                    self.detail_scroll = 0;
+                    self.detail_scroll.reset();
                 }
                 None
             }
-            Action::CloseMergeDialog => {
+            Action::Escape => {
                 if self.detail_expanded {
                     self.detail_expanded = false;
-                    self.detail_scroll = 0;
+                    self.detail_scroll.reset();
                     return None;
                 }
-                None
+                if self.focus == Focus::Detail {
+                    self.focus = Focus::List;
+                    self.detail_scroll.reset();
+                    return None;
+                }
+                Some(Action::CancelPhaseTask)
             }
             Action::ScrollUp | Action::SelectPrev => {
-                if self.detail_expanded {
-                    self.detail_scroll = self.detail_scroll.saturating_sub(1);
-                } else if self.selected > 0 {
+                if self.selected > 0 {
                     self.selected -= 1;
                 }
                 None
             }
             Action::ScrollDown | Action::SelectNext => {
-                if self.detail_expanded {
-                    self.detail_scroll = self.detail_scroll.saturating_add(1);
-                } else if self.selected + 1 < self.papers.len() {
+                if self.selected + 1 < self.papers.len() {
                     self.selected += 1;
                 }
                 None
             }
+            Action::ResizeDetail(delta) => {
+                self.detail_height = resize_pane(self.detail_height, *delta);
+                None
+            }
+            Action::ScrollTop => {
+                self.selected = 0;
+                None
+            }
+            Action::ScrollBottom => {
+                self.selected = self.papers.len().saturating_sub(1);
+                None
+            }
+            Action::PageUp => {
+                self.selected = self.selected.saturating_sub(PAGE);
+                None
+            }
+            Action::PageDown => {
+                self.selected = (self.selected + PAGE).min(self.papers.len().saturating_sub(1));
+                None
+            }
+            Action::Search(query) => {
+                let titles = self.papers.iter().map(|p| p.title.as_str());
+                match find_next(titles, self.selected, query) {
+                    Some(i) => {
+                        self.selected = i;
+                        self.detail_scroll.reset();
+                        None
+                    }
+                    None => Some(no_match(query)),
+                }
+            }
             Action::SearchQueryStarted {
                 query,
                 query_idx,
@@ -114,9 +188,9 @@ impl Component for ResearchExplorerComponent {
             Action::ResearchComplete => {
                 self.searching = false;
                 self.current_query.clear();
-                Some(Action::SetStatus(format!(
-                    "{} papers found. Enter to view, → for next phase.",
-                    self.papers.len()
+                Some(Action::SetStatus(t!(
+                    "research.complete",
+                    count = self.papers.len()
                 )))
             }
             Action::ResearchFailed(err) => {
@@ -128,6 +202,25 @@ impl Component for ResearchExplorerComponent {
         }
     }
 
+    fn cycle_focus(&mut self, _forward: bool) -> bool {
+        if self.papers.is_empty() || self.searching || self.detail_expanded {
+            return false;
+        }
+        self.focus = self.focus.toggled();
+        self.detail_scroll.reset();
+        true
+    }
+
+    fn focused_panel(&self) -> Option<&'static str> {
+        if self.papers.is_empty() || self.searching || self.detail_expanded {
+            return None;
+        }
+        Some(match self.focus {
+            Focus::List => "papers",
+            Focus::Detail => "details",
+        })
+    }
+
     fn render(&self, frame: &mut Frame, area: Rect) {
         // Empty state.
         if self.papers.is_empty() && !self.searching {
@@ -136,7 +229,7 @@ impl Component for ResearchExplorerComponent {
                     Line::from(""),
                     Line::from(""),
                     Line::from(Span::styled(
-                        format!("  Error: {}", err),
+                        format!("  {}", t!("common.error", error = err)),
                         Style::default().fg(Theme::error()),
                     )),
                 ])
@@ -144,8 +237,14 @@ impl Component for ResearchExplorerComponent {
                 Paragraph::new(vec![
                     Line::from(""),
                     Line::from(""),
-                    Line::from(Span::styled("  No papers loaded yet.", Theme::muted())),
-                    Line::from(Span::styled("  Complete Phase 1 first.", Theme::dim())),
+                    Line::from(Span::styled(
+                        format!("  {}", t!("research.empty")),
+                        Theme::muted(),
+                    )),
+                    Line::from(Span::styled(
+                        format!("  {}", t!("research.complete_phase_1")),
+                        Theme::dim(),
+                    )),
                 ])
             };
             frame.render_widget(msg, area);
@@ -168,9 +267,9 @@ impl Component for ResearchExplorerComponent {
 
         // Normal view: header + table + detail.
         let chunks = Layout::vertical([
-            Constraint::Length(1), // Header
-            Constraint::Min(8),    // Paper list
-            Constraint::Length(7), // Paper detail
+            Constraint::Length(1),                  // Header
+            Constraint::Min(8),                     // Paper list
+            Constraint::Length(self.detail_height), // Paper detail
         ])
         .split(area);
 
@@ -178,12 +277,14 @@ impl Component for ResearchExplorerComponent {
         let header = Line::from(vec![
             Span::styled("  ", Theme::dim()),
             Span::styled(format!("{}", self.papers.len()), Theme::header()),
-            Span::styled(" papers", Theme::muted()),
+            Span::styled(format!(" {}", t!("research.papers")), Theme::muted()),
             Span::styled("    ", Theme::dim()),
             Span::styled("enter", Theme::key_hint()),
-            Span::styled(" details  ", Theme::dim()),
+            Span::styled(format!(" {}  ", t!("hint.details")), Theme::dim()),
+            Span::styled("tab", Theme::key_hint()),
+            Span::styled(format!(" {}  ", t!("hint.focus")), Theme::dim()),
             Span::styled("→", Theme::key_hint()),
-            Span::styled(" next phase", Theme::dim()),
+            Span::styled(format!(" {}", t!("hint.next_phase")), Theme::dim()),
         ]);
         frame.render_widget(Paragraph::new(header), chunks[0]);
 
@@ -196,41 +297,38 @@ impl ResearchExplorerComponent {
     // ── Searching view ──────────────────────────────────────
 
     fn render_searching(&self, frame: &mut Frame, area: Rect) {
-        let spinner = SPINNER[self.spinner_tick % SPINNER.len()];
-        let elapsed_secs = self.spinner_tick / 10;
+        let mut heading = self.spinner.heading(t!("research.searching"));
+        heading.spans.push(Span::styled(
+            format!("  ({}s)", self.spinner.elapsed_secs()),
+            Theme::dim(),
+        ));
 
         let mut lines = vec![
             Line::from(""),
             Line::from(""),
             Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    format!("  {} ", spinner),
-                    Style::default()
-                        .fg(Theme::accent())
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("Searching Semantic Scholar + arXiv", Theme::header()),
-                Span::styled(format!("  ({}s)", elapsed_secs), Theme::dim()),
-            ]),
+            heading,
             Line::from(""),
         ];
 
         if !self.papers.is_empty() {
             lines.push(Line::from(Span::styled(
-                format!("  {} papers found so far", self.papers.len()),
+                format!(
+                    "  {}",
+                    t!("research.found_so_far", count = self.papers.len())
+                ),
                 Style::default().fg(Theme::success()),
             )));
         } else {
             lines.push(Line::from(Span::styled(
-                "  Searching academic databases...",
+                format!("  {}", t!("research.searching_databases")),
                 Theme::muted(),
             )));
         }
 
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "  Typically takes 15-25 seconds.",
+            format!("  {}", t!("research.duration")),
             Theme::dim(),
         )));
 
@@ -251,19 +349,19 @@ impl ResearchExplorerComponent {
 
     // ── Paper list ──────────────────────────────────────────
 
-    fn render_paper_list(&self, frame: &mut Frame, area: Rect) {
+    fn render_paper_list(&self, frame: &mut Frame, full_area: Rect) {
+        let area = scrollbar_gutter(full_area, self.papers.len());
         let visible_height = area.height as usize;
-        let scroll_offset = if self.selected >= visible_height {
-            self.selected - visible_height + 1
-        } else {
-            0
-        };
+        let scroll_offset =
+            self.list_view
+                .scroll_to(self.selected, visible_height, self.papers.len());
 
         let w = area.width as usize;
         let fixed_cols = 6 + 6 + 7 + 6; // num + year + cites + source
         let title_max = w.saturating_sub(fixed_cols).max(10);
 
-        let mut lines: Vec<Line> = Vec::new();
+        // Only the rows on screen are built, however many papers there are.
+        let mut lines: Vec<Line> = Vec::with_capacity(visible_height);
         for (i, paper) in self
             .papers
             .iter()
@@ -275,9 +373,11 @@ impl ResearchExplorerComponent {
             let source_str = match paper.source {
                 uniq_core::research::PaperSource::SemanticScholar => "S2",
                 uniq_core::research::PaperSource::ArXiv => "arXiv",
+                uniq_core::research::PaperSource::Plugin => plugin_name(&paper.id),
             };
 
-            let row_style = if is_selected {
+            let highlight = is_selected && self.focus == Focus::List;
+            let row_style = if highlight {
                 Style::default().fg(Theme::fg()).bg(Theme::selection_bg())
             } else {
                 Style::default()
@@ -291,7 +391,7 @@ impl ResearchExplorerComponent {
                         truncate(&paper.title, title_max),
                         width = title_max
                     ),
-                    if is_selected {
+                    if highlight {
                         Style::default().fg(Theme::fg()).bg(Theme::selection_bg())
                     } else {
                         Theme::normal()
@@ -316,6 +416,7 @@ impl ResearchExplorerComponent {
         }
 
         frame.render_widget(Paragraph::new(lines), area);
+        render_scrollbar(frame, full_area, self.papers.len(), scroll_offset);
     }
 
     // ── Paper detail (compact) ──────────────────────────────
@@ -327,24 +428,27 @@ impl ResearchExplorerComponent {
 
         let detail_block = Block::default()
             .borders(Borders::TOP)
-            .border_style(Theme::border());
+            .border_style(self.focus.border(Focus::Detail));
 
         let inner = detail_block.inner(area);
         frame.render_widget(detail_block, area);
 
         let w = inner.width as usize;
         let authors_text = truncate(&paper.authors.join(", "), w.saturating_sub(12));
-        let abstract_text = truncate(&paper.abstract_text, w * 2);
 
-        let pdf_label = if paper.pdf_url.is_some() { "yes" } else { "no" };
+        let pdf_label = if paper.pdf_url.is_some() {
+            t!("common.yes")
+        } else {
+            t!("common.no")
+        };
 
-        let detail = Paragraph::new(vec![
+        let mut lines = vec![
             Line::from(vec![
-                Span::styled("  Authors  ", Theme::muted()),
+                Span::styled(field_label("research.authors", 9), Theme::muted()),
                 Span::styled(authors_text, Theme::dim()),
             ]),
             Line::from(vec![
-                Span::styled("  PDF      ", Theme::muted()),
+                Span::styled(field_label("research.pdf", 9), Theme::muted()),
                 Span::styled(
                     pdf_label,
                     if paper.pdf_url.is_some() {
@@ -355,14 +459,19 @@ impl ResearchExplorerComponent {
                 ),
             ]),
             Line::from(""),
-            Line::from(vec![
-                Span::styled("  ", Theme::dim()),
-                Span::styled(abstract_text, Theme::dim()),
-            ]),
-        ])
-        .wrap(Wrap { trim: true });
+        ];
+        // The whole abstract; the panel scrolls through it while focused.
+        for wrapped_line in word_wrap(&paper.abstract_text, w.saturating_sub(4).max(1)) {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", wrapped_line),
+                Theme::dim(),
+            )));
+        }
 
-        frame.render_widget(detail, inner);
+        let total = lines.len();
+        let scroll = self.detail_scroll.offset(total, inner.height as usize);
+        frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+        render_scrollbar(frame, inner, total, scroll);
     }
 
     // ── Expanded detail ─────────────────────────────────────
@@ -380,6 +489,7 @@ impl ResearchExplorerComponent {
         let source_str = match paper.source {
             uniq_core::research::PaperSource::SemanticScholar => "Semantic Scholar",
             uniq_core::research::PaperSource::ArXiv => "arXiv",
+            uniq_core::research::PaperSource::Plugin => plugin_name(&paper.id),
         };
 
         let date_str = paper
@@ -395,11 +505,11 @@ impl ResearchExplorerComponent {
             ]),
             Line::from(""),
             Line::from(vec![
-                Span::styled("  Authors   ", Theme::muted()),
+                Span::styled(field_label("research.authors", 10), Theme::muted()),
                 Span::styled(paper.authors.join(", "), Theme::normal()),
             ]),
             Line::from(vec![
-                Span::styled("  Year      ", Theme::muted()),
+                Span::styled(field_label("research.year", 10), Theme::muted()),
                 Span::styled(
                     paper
                         .year
@@ -407,9 +517,9 @@ impl ResearchExplorerComponent {
                         .unwrap_or_else(|| "—".to_string()),
                     Theme::normal(),
                 ),
-                Span::styled("  Published  ", Theme::muted()),
+                Span::styled(field_label("research.published", 11), Theme::muted()),
                 Span::styled(date_str, Theme::normal()),
-                Span::styled("  Citations  ", Theme::muted()),
+                Span::styled(field_label("research.citations", 11), Theme::muted()),
                 Span::styled(
                     paper
                         .citation_count
@@ -419,18 +529,18 @@ impl ResearchExplorerComponent {
                 ),
             ]),
             Line::from(vec![
-                Span::styled("  Source    ", Theme::muted()),
+                Span::styled(field_label("research.source", 10), Theme::muted()),
                 Span::styled(source_str, Theme::normal()),
             ]),
             Line::from(vec![
-                Span::styled("  URL       ", Theme::muted()),
+                Span::styled(field_label("research.url", 10), Theme::muted()),
                 Span::styled(&paper.url, Theme::dim()),
             ]),
         ];
 
         if !paper.fields.is_empty() {
             lines.push(Line::from(vec![
-                Span::styled("  Fields    ", Theme::muted()),
+                Span::styled(field_label("research.fields", 10), Theme::muted()),
                 Span::styled(paper.fields.join(", "), Theme::dim()),
             ]));
         }
@@ -451,59 +561,17 @@ impl ResearchExplorerComponent {
 
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "  enter/esc close  ↑↓ scroll",
+            format!("  {}", t!("research.detail_footer")),
             Theme::dim(),
         )));
 
+        let total = lines.len();
+        let scroll = self.detail_scroll.offset(total, inner.height as usize);
         let para = Paragraph::new(lines)
-            .scroll((self.detail_scroll, 0))
+            .scroll((scroll as u16, 0))
             .block(block);
 
         frame.render_widget(para, area);
-    }
-}
-
-// ── Helpers ─────────────────────────────────────────────────
-
-fn word_wrap(text: &str, max_width: usize) -> Vec<String> {
-    if max_width == 0 {
-        return vec![text.to_string()];
-    }
-    let mut lines = Vec::new();
-    for paragraph in text.split('\n') {
-        if paragraph.is_empty() {
-            lines.push(String::new());
-            continue;
-        }
-        let mut current_line = String::new();
-        for word in paragraph.split_whitespace() {
-            if current_line.is_empty() {
-                current_line = word.to_string();
-            } else if current_line.len() + 1 + word.len() <= max_width {
-                current_line.push(' ');
-                current_line.push_str(word);
-            } else {
-                lines.push(current_line);
-                current_line = word.to_string();
-            }
-        }
-        if !current_line.is_empty() {
-            lines.push(current_line);
-        }
-    }
-    if lines.is_empty() {
-        lines.push(String::new());
-    }
-    lines
-}
-
-fn truncate(s: &str, max_len: usize) -> String {
-    if max_len < 4 {
-        return s.chars().take(max_len).collect();
-    }
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
+        render_scrollbar(frame, area.inner(Margin::new(0, 1)), total, scroll);
     }
 }
@@ -0,0 +1,150 @@
+//! Pipeline summary sidebar — where the whole run stands, visible from any
+//! phase.
+
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::action::Action;
+use crate::components::Component;
+use crate::theme::Theme;
+
+/// Width of the sidebar, border included.
+pub const SIDEBAR_WIDTH: u16 = 28;
+
+/// Counts the sidebar shows, gathered from every phase.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineSummary {
+    /// Project directory name, once analyzed.
+    pub project: Option<String>,
+    pub papers: usize,
+    pub searching: bool,
+    /// (selected, total)
+    pub techniques: (usize, usize),
+    /// (ready, total)
+    pub variants: (usize, usize),
+    pub generating: usize,
+    /// Name and composite score of the best benchmarked variant.
+    pub best: Option<(String, f64)>,
+}
+
+pub struct SidebarComponent {
+    pub visible: bool,
+    pub summary: PipelineSummary,
+}
+
+impl SidebarComponent {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            summary: PipelineSummary::default(),
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let s = &self.summary;
+        let mut lines = vec![
+            label(t!("sidebar.project")),
+            match &s.project {
+                Some(name) => value(name.clone()),
+                None => Line::from(Span::styled(
+                    format!(" {}", t!("sidebar.not_analyzed")),
+                    Theme::dim(),
+                )),
+            },
+            Line::from(""),
+            label(t!("sidebar.papers")),
+        ];
+        let papers = if s.searching {
+            t!("sidebar.papers_searching", count = s.papers)
+        } else {
+            t!("sidebar.papers_found", count = s.papers)
+        };
+        lines.push(value(format!(" {}", papers)));
+        lines.push(Line::from(""));
+        lines.push(label(t!("sidebar.techniques")));
+        lines.push(value(format!(
+            " {}",
+            t!(
+                "sidebar.techniques_selected",
+                selected = s.techniques.0,
+                total = s.techniques.1
+            )
+        )));
+        lines.push(Line::from(""));
+        lines.push(label(t!("sidebar.variants")));
+        lines.push(value(format!(
+            " {}",
+            t!(
+                "sidebar.variants_ready",
+                ready = s.variants.0,
+                total = s.variants.1
+            )
+        )));
+        if s.generating > 0 {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", t!("sidebar.generating", count = s.generating)),
+                Style::default().fg(Theme::warning()),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(label(t!("sidebar.best_score")));
+        lines.push(match &s.best {
+            Some((name, score)) => Line::from(vec![
+                Span::styled(
+                    format!(" {:.1}", score),
+                    Style::default().fg(Theme::score_color(*score, 100.0)),
+                ),
+                Span::styled(format!("  {}", name), Theme::normal()),
+            ]),
+            None => Line::from(Span::styled(
+                format!(" {}", t!("sidebar.not_benchmarked")),
+                Theme::dim(),
+            )),
+        });
+        lines
+    }
+}
+
+impl Default for SidebarComponent {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl Component for SidebarComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if let Action::ToggleSidebar = action {
+            self.visible = !self.visible;
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let block = Block::default()
+            .title(format!(" {} ", t!("sidebar.title")))
+            .title_style(Theme::title())
+            .borders(Borders::LEFT)
+            .border_style(Theme::border());
+        frame.render_widget(
+            Paragraph::new(self.lines())
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+}
+
+fn label(text: &str) -> Line<'static> {
+    Line::from(Span::styled(format!(" {}", text), Theme::header()))
+}
+
+fn value(text: String) -> Line<'static> {
+    Line::from(Span::styled(text, Theme::normal()))
+}
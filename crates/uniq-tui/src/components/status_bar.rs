@@ -4,8 +4,10 @@ use ratatui::layout::Rect;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
+use unicode_width::UnicodeWidthStr;
 
 use crate::action::{Action, Phase};
+use crate::components::usage::Spend;
 use crate::components::Component;
 use crate::theme::Theme;
 
@@ -14,25 +16,26 @@ pub struct StatusBarComponent {
     pub message: String,
     /// Current active phase.
     pub current_phase: Phase,
+    /// Focused panel of the current phase, if it has several.
+    pub focus: Option<&'static str>,
+    /// Claude tokens and cost so far, once there are any.
+    pub spend: Option<Spend>,
 }
 
 impl StatusBarComponent {
     pub fn new() -> Self {
         Self {
-            message: "Welcome to uniq. Set up your project in Phase 1.".to_string(),
+            message: t!("status_bar.welcome").to_string(),
             current_phase: Phase::ProjectIntake,
+            focus: None,
+            spend: None,
         }
     }
+}
 
-    /// Short phase name for the pill badge.
-    fn phase_badge(&self) -> &'static str {
-        match self.current_phase {
-            Phase::ProjectIntake => "Intake",
-            Phase::ResearchDiscovery => "Research",
-            Phase::TechniqueSelection => "Techniques",
-            Phase::VariantGeneration => "Build",
-            Phase::Benchmarking => "Benchmark",
-        }
+impl Default for StatusBarComponent {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -60,11 +63,18 @@ impl Component for StatusBarComponent {
 
         // Right side: compact key hints
         let hints = "q·?·1-5·m";
-        let hints_len = hints.len() + 1; // +1 for trailing space
+        let spend = self
+            .spend
+            .map(|spend| format!("{}  ", spend.label()))
+            .unwrap_or_default();
+        let hints_len = spend.width() + hints.len() + 1; // +1 for trailing space
 
-        // Phase badge
-        let badge = self.phase_badge();
-        let badge_len = badge.len() + 2; // spaces around badge
+        // Phase badge, with the focused panel when there's a choice
+        let badge = match self.focus {
+            Some(panel) => format!("{} › {}", self.current_phase.name(), panel),
+            None => self.current_phase.name().to_string(),
+        };
+        let badge_len = badge.width() + 2; // spaces around badge
 
         // Truncate message to remaining space
         let msg_budget = width
@@ -91,6 +101,7 @@ impl Component for StatusBarComponent {
             Span::styled("  ", Theme::dim()),
             Span::styled(msg, Theme::dim()),
             Span::raw(" ".repeat(pad)),
+            Span::styled(spend, Theme::muted()),
             Span::styled(hints, Theme::key_hint()),
             Span::raw(" "),
         ]);
@@ -0,0 +1,173 @@
+//! Status log — every status bar message with the time it was shown, so
+//! errors that flash by can be read later.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::action::Action;
+use crate::components::{Component, TextScroll};
+use crate::theme::Theme;
+
+/// Messages kept; older ones are dropped.
+const MAX_ENTRIES: usize = 500;
+
+struct StatusEntry {
+    time: DateTime<Local>,
+    message: String,
+    /// How many times in a row the message was shown.
+    repeats: usize,
+}
+
+impl StatusEntry {
+    fn is_error(&self) -> bool {
+        let message = self.message.to_lowercase();
+        t!("status_log.error_words")
+            .split('|')
+            .any(|word| message.contains(word))
+    }
+}
+
+pub struct StatusLogComponent {
+    pub visible: bool,
+    /// Oldest first.
+    entries: VecDeque<StatusEntry>,
+    scroll: TextScroll,
+}
+
+impl StatusLogComponent {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            entries: VecDeque::new(),
+            scroll: TextScroll::default(),
+        }
+    }
+
+    fn record(&mut self, message: &str) {
+        if message.is_empty() {
+            return;
+        }
+        if let Some(last) = self.entries.back_mut() {
+            if last.message == message {
+                last.repeats += 1;
+                last.time = Local::now();
+                return;
+            }
+        }
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(StatusEntry {
+            time: Local::now(),
+            message: message.to_string(),
+            repeats: 1,
+        });
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        self.entries
+            .iter()
+            .rev()
+            .map(|entry| {
+                let color = if entry.is_error() {
+                    Theme::error()
+                } else {
+                    Theme::fg()
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        format!(" {}  ", entry.time.format("%H:%M:%S")),
+                        Theme::dim(),
+                    ),
+                    Span::styled(entry.message.clone(), Style::default().fg(color)),
+                ];
+                if entry.repeats > 1 {
+                    spans.push(Span::styled(format!("  ×{}", entry.repeats), Theme::dim()));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+}
+
+impl Default for StatusLogComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for StatusLogComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        match action {
+            Action::SetStatus(message) => {
+                self.record(message);
+                return None;
+            }
+            Action::ToggleStatusLog => {
+                self.visible = !self.visible;
+                self.scroll.reset();
+                return None;
+            }
+            _ => {}
+        }
+        if !self.visible {
+            return None;
+        }
+
+        if self.scroll.handle(action) {
+            return None;
+        }
+        if let Action::Confirm | Action::Escape = action {
+            self.visible = false;
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let dialog = area.inner(Margin {
+            horizontal: area.width / 8,
+            vertical: area.height / 8,
+        });
+        frame.render_widget(Clear, dialog);
+
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t!("status_log.title", count = self.entries.len())
+            ))
+            .title_style(Theme::title())
+            .title_bottom(Line::from(Span::styled(
+                format!(" {} ", t!("status_log.footer")),
+                Theme::dim(),
+            )))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let lines = if self.entries.is_empty() {
+            vec![Line::from(Span::styled(
+                format!(" {}", t!("status_log.empty")),
+                Theme::dim(),
+            ))]
+        } else {
+            self.lines()
+        };
+        // Entries wrap, so the scroll limit counts wrapped lines.
+        self.scroll.render(
+            frame,
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            dialog,
+        );
+    }
+}
@@ -1,26 +1,49 @@
 //! Phase 3: Technique Selection — view technique cards extracted from papers
 //! and select which ones to generate variants for.
 
+use std::collections::{HashMap, HashSet};
+
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
-use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
 use ratatui::Frame;
 
-use crate::action::Action;
-use crate::components::Component;
+use crate::action::{Action, PromptKind};
+use crate::components::progress::Spinner;
+use crate::components::{
+    field_label, find_next, format_tokens, no_match, render_scrollbar, resize_pane,
+    scrollbar_gutter, truncate, word_wrap, Component, Focus, TextScroll, Viewport, PAGE,
+};
 use crate::theme::Theme;
 
-use uniq_core::research::TechniqueCard;
+use uniq_core::config::{GenerationConfig, PaneSizes};
+use uniq_core::estimate::estimate_generation;
+use uniq_core::llm::PipelineStage;
+use uniq_core::project::ProjectProfile;
+use uniq_core::research::{
+    consolidate_techniques, find_duplicate_techniques, Complexity, TechniqueCard,
+};
+
+/// How many techniques fit side by side in the comparison matrix.
+const MAX_COMPARED: usize = 4;
 
-/// Braille spinner frames.
-const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// One line of the technique list.
+#[derive(Debug, Clone, PartialEq)]
+enum ListRow {
+    /// A source-paper header (grouped mode only).
+    Paper { paper_id: String },
+    /// A technique card, by index into `techniques`.
+    Technique(usize),
+}
 
 pub struct TechniqueCardsComponent {
     /// All extracted technique cards.
     pub techniques: Vec<TechniqueCard>,
     /// Currently highlighted technique.
     pub selected: usize,
+    /// Scroll position of the technique list.
+    list_view: Viewport,
     /// Whether extraction is in progress.
     pub extracting: bool,
     /// Whether extraction was already attempted (prevents re-trigger loops).
@@ -29,12 +52,36 @@ pub struct TechniqueCardsComponent {
     pub progress: (usize, usize),
     /// Errors during extraction.
     pub errors: Vec<(String, String)>,
-    /// Spinner animation frame counter.
-    spinner_tick: usize,
+    /// Activity indicator shown while work runs.
+    pub spinner: Spinner,
     /// Title of the paper currently being extracted.
     current_paper: String,
     /// Titles of papers being processed concurrently.
     pub active_papers: Vec<String>,
+    /// Whether the side-by-side comparison matrix is shown.
+    comparing: bool,
+    /// Only list techniques of this complexity.
+    complexity_filter: Option<Complexity>,
+    /// Only list techniques whose dependencies the project already has.
+    only_known_deps: bool,
+    /// The analyzed project, used to tell existing dependencies from new ones.
+    pub profile: Option<ProjectProfile>,
+    /// Whether techniques are nested under their source paper.
+    grouped: bool,
+    /// Paper ids whose techniques are hidden in grouped mode.
+    collapsed: HashSet<String>,
+    /// Paper header the cursor is on (grouped mode), instead of a technique.
+    cursor_paper: Option<String>,
+    /// Keys of techniques that already have a generated variant.
+    pub generated: HashSet<String>,
+    /// Generation settings, for the cost estimate.
+    pub generation_config: GenerationConfig,
+    /// Height of the detail panel, in lines.
+    pub detail_height: u16,
+    /// Whether the list or the detail panel takes navigation keys.
+    focus: Focus,
+    /// Scroll position within the detail panel.
+    detail_scroll: TextScroll,
 }
 
 impl TechniqueCardsComponent {
@@ -42,14 +89,230 @@ impl TechniqueCardsComponent {
         Self {
             techniques: Vec::new(),
             selected: 0,
+            list_view: Viewport::default(),
             extracting: false,
             extraction_attempted: false,
             progress: (0, 0),
             errors: Vec::new(),
-            spinner_tick: 0,
+            spinner: Spinner::default(),
             current_paper: String::new(),
             active_papers: Vec::new(),
+            comparing: false,
+            complexity_filter: None,
+            only_known_deps: false,
+            profile: None,
+            grouped: false,
+            collapsed: HashSet::new(),
+            cursor_paper: None,
+            generated: HashSet::new(),
+            generation_config: GenerationConfig::default(),
+            detail_height: PaneSizes::default().technique_detail,
+            focus: Focus::default(),
+            detail_scroll: TextScroll::default(),
+        }
+    }
+
+    /// The rows of the technique list, honoring filters and grouping.
+    fn list_rows(&self) -> Vec<ListRow> {
+        let visible = self.visible_indices();
+        if !self.grouped {
+            return visible.into_iter().map(ListRow::Technique).collect();
+        }
+
+        // Papers appear in order of their most relevant technique.
+        let mut papers: Vec<(&str, Vec<usize>)> = Vec::new();
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for i in visible {
+            let paper_id = self.techniques[i].paper_id.as_str();
+            match positions.get(paper_id) {
+                Some(&at) => papers[at].1.push(i),
+                None => {
+                    positions.insert(paper_id, papers.len());
+                    papers.push((paper_id, vec![i]));
+                }
+            }
+        }
+
+        let mut rows = Vec::new();
+        for (paper_id, members) in papers {
+            rows.push(ListRow::Paper {
+                paper_id: paper_id.to_string(),
+            });
+            if !self.collapsed.contains(paper_id) {
+                rows.extend(members.into_iter().map(ListRow::Technique));
+            }
+        }
+        rows
+    }
+
+    /// The row the cursor is on.
+    fn cursor_row(&self) -> ListRow {
+        match self.cursor_paper {
+            Some(ref paper_id) => ListRow::Paper {
+                paper_id: paper_id.clone(),
+            },
+            None => ListRow::Technique(self.selected),
+        }
+    }
+
+    /// Move the cursor by one row up (`-1`) or down (`1`).
+    fn move_cursor(&mut self, delta: isize) {
+        let rows = self.list_rows();
+        let Some(pos) = rows.iter().position(|r| *r == self.cursor_row()) else {
+            return;
+        };
+        let target = pos.saturating_add_signed(delta).min(rows.len() - 1);
+        self.set_cursor(rows[target].clone());
+    }
+
+    fn set_cursor(&mut self, row: ListRow) {
+        match row {
+            ListRow::Paper { paper_id } => self.cursor_paper = Some(paper_id),
+            ListRow::Technique(i) => {
+                self.cursor_paper = None;
+                self.selected = i;
+            }
+        }
+    }
+
+    /// Move the cursor to the next technique whose name matches `query`.
+    fn search(&mut self, query: &str) -> Option<Action> {
+        let techniques: Vec<usize> = self
+            .list_rows()
+            .into_iter()
+            .filter_map(|row| match row {
+                ListRow::Technique(i) => Some(i),
+                ListRow::Paper { .. } => None,
+            })
+            .collect();
+        let current = techniques
+            .iter()
+            .position(|&i| i == self.selected)
+            .unwrap_or(0);
+        let names = techniques.iter().map(|&i| self.techniques[i].name.as_str());
+        match find_next(names, current, query) {
+            Some(pos) => {
+                self.set_cursor(ListRow::Technique(techniques[pos]));
+                None
+            }
+            None => Some(no_match(query)),
+        }
+    }
+
+    /// Dependencies of `tech` that the project doesn't already have.
+    fn new_dependencies<'a>(&self, tech: &'a TechniqueCard) -> Vec<&'a str> {
+        tech.dependencies
+            .iter()
+            .filter(|d| !self.profile.as_ref().is_some_and(|p| p.has_dependency(d)))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Whether `tech` passes the active filters.
+    fn matches_filters(&self, tech: &TechniqueCard) -> bool {
+        if let Some(ref complexity) = self.complexity_filter {
+            if &tech.implementation_complexity != complexity {
+                return false;
+            }
+        }
+        !self.only_known_deps || self.new_dependencies(tech).is_empty()
+    }
+
+    /// Indices into `techniques` of the cards passing the filters.
+    fn visible_indices(&self) -> Vec<usize> {
+        self.techniques
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| self.matches_filters(t))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Move the highlight onto a visible card after the filters change.
+    fn snap_to_visible(&mut self) {
+        self.cursor_paper = None;
+        let visible = self.visible_indices();
+        if !visible.contains(&self.selected) {
+            self.selected = visible.first().copied().unwrap_or(0);
+        }
+    }
+
+    /// Short description of the active filters for the header.
+    fn filter_label(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(ref complexity) = self.complexity_filter {
+            parts.push(t!(
+                "techniques.complexity_filter",
+                complexity = complexity_label(complexity)
+            ));
+        }
+        if self.only_known_deps {
+            parts.push(t!("techniques.no_new_deps").to_string());
         }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// How many groups of duplicate techniques there are.
+    fn duplicate_count(&self) -> usize {
+        find_duplicate_techniques(&self.techniques).len()
+    }
+
+    /// Merge the duplicates of the technique under the cursor into one
+    /// card, or with `merge` false keep that card and drop the others.
+    fn resolve_duplicates(&mut self, merge: bool) -> Option<Action> {
+        let groups = find_duplicate_techniques(&self.techniques);
+        if groups.is_empty() {
+            return Some(Action::SetStatus(
+                t!("techniques.no_duplicates").to_string(),
+            ));
+        }
+        let group = match self.cursor_row() {
+            ListRow::Technique(i) => groups.into_iter().find(|g| g.contains(&i)),
+            ListRow::Paper { .. } => None,
+        };
+        let Some(group) = group else {
+            return Some(Action::SetStatus(
+                t!("techniques.not_duplicate").to_string(),
+            ));
+        };
+
+        let mut members = Vec::new();
+        let mut rest = Vec::new();
+        for (i, card) in std::mem::take(&mut self.techniques).into_iter().enumerate() {
+            if group.contains(&i) {
+                members.push((i, card));
+            } else {
+                rest.push(card);
+            }
+        }
+        let others = members.len() - 1;
+        let card = if merge {
+            consolidate_techniques(members.into_iter().map(|(_, card)| card).collect())
+        } else {
+            let kept = members.iter().position(|(i, _)| *i == self.selected);
+            members.swap_remove(kept.unwrap_or(0)).1
+        };
+        let status = if merge {
+            t!(
+                "techniques.consolidated",
+                cards = others + 1,
+                name = card.name
+            )
+        } else {
+            t!(
+                "techniques.dropped_duplicates",
+                count = others,
+                name = card.name
+            )
+        };
+        // Keep the list in order of relevance, with the cursor on the card.
+        let position = rest.partition_point(|t| t.relevance_score >= card.relevance_score);
+        rest.insert(position, card);
+        self.techniques = rest;
+        self.selected = position;
+        self.cursor_paper = None;
+        self.comparing = false;
+        Some(Action::SetStatus(status))
     }
 
     /// Get the number of selected techniques.
@@ -58,27 +321,88 @@ impl TechniqueCardsComponent {
     }
 }
 
+impl Default for TechniqueCardsComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Component for TechniqueCardsComponent {
     fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        // The focused detail panel scrolls instead of moving the cursor.
+        if self.focused_panel() == Some("details") {
+            if self.detail_scroll.handle(action) {
+                return None;
+            }
+            if let Action::Escape = action {
+                self.focus = Focus::List;
+                self.detail_scroll.reset();
+                return None;
+            }
+        }
+
         match action {
             Action::Tick => {
                 if self.extracting {
-                    self.spinner_tick = self.spinner_tick.wrapping_add(1);
+                    self.spinner.tick();
                 }
                 None
             }
             Action::ScrollUp | Action::SelectPrev => {
-                if self.selected > 0 {
-                    self.selected -= 1;
-                }
+                self.move_cursor(-1);
                 None
             }
             Action::ScrollDown | Action::SelectNext => {
-                if self.selected + 1 < self.techniques.len() {
-                    self.selected += 1;
+                self.move_cursor(1);
+                None
+            }
+            Action::PageUp => {
+                self.move_cursor(-(PAGE as isize));
+                None
+            }
+            Action::PageDown => {
+                self.move_cursor(PAGE as isize);
+                None
+            }
+            Action::ResizeDetail(delta) => {
+                self.detail_height = resize_pane(self.detail_height, *delta);
+                None
+            }
+            Action::ScrollTop => {
+                if let Some(row) = self.list_rows().first() {
+                    self.set_cursor(row.clone());
+                }
+                None
+            }
+            Action::ScrollBottom => {
+                if let Some(row) = self.list_rows().last() {
+                    self.set_cursor(row.clone());
                 }
                 None
             }
+            Action::Search(query) => self.search(query),
+            Action::ConsolidateDuplicates => self.resolve_duplicates(true),
+            Action::DropDuplicates => self.resolve_duplicates(false),
+            Action::ToggleGroupByPaper => {
+                self.grouped = !self.grouped;
+                self.cursor_paper = None;
+                None
+            }
+            Action::CycleComplexityFilter => {
+                self.complexity_filter = match self.complexity_filter {
+                    None => Some(Complexity::Low),
+                    Some(Complexity::Low) => Some(Complexity::Medium),
+                    Some(Complexity::Medium) => Some(Complexity::High),
+                    Some(Complexity::High) => None,
+                };
+                self.snap_to_visible();
+                None
+            }
+            Action::ToggleKnownDepsFilter => {
+                self.only_known_deps = !self.only_known_deps;
+                self.snap_to_visible();
+                None
+            }
             Action::ToggleTechnique(idx) => {
                 if let Some(tech) = self.techniques.get_mut(*idx) {
                     tech.selected = !tech.selected;
@@ -86,12 +410,126 @@ impl Component for TechniqueCardsComponent {
                 None
             }
             Action::Confirm => {
+                // On a paper header, expand/collapse it.
+                if let Some(ref paper_id) = self.cursor_paper {
+                    if !self.collapsed.remove(paper_id) {
+                        self.collapsed.insert(paper_id.clone());
+                    }
+                    return None;
+                }
                 // Toggle the currently selected technique.
                 if let Some(tech) = self.techniques.get_mut(self.selected) {
                     tech.selected = !tech.selected;
                 }
                 None
             }
+            // Bulk selection only touches the cards passing the filters.
+            Action::SelectAllTechniques => {
+                for i in self.visible_indices() {
+                    self.techniques[i].selected = true;
+                }
+                None
+            }
+            Action::SelectNoTechniques => {
+                for i in self.visible_indices() {
+                    self.techniques[i].selected = false;
+                }
+                self.comparing = false;
+                None
+            }
+            Action::InvertTechniqueSelection => {
+                for i in self.visible_indices() {
+                    self.techniques[i].selected = !self.techniques[i].selected;
+                }
+                None
+            }
+            Action::OpenSelectTopPrompt => {
+                if self.extracting || self.techniques.is_empty() {
+                    return None;
+                }
+                Some(Action::OpenPrompt(PromptKind::SelectTopTechniques {
+                    available: self.visible_indices().len(),
+                }))
+            }
+            Action::SelectTopTechniques(n) => {
+                // Cards are kept sorted by relevance, so the top N are the first N.
+                let visible = self.visible_indices();
+                for (rank, &i) in visible.iter().enumerate() {
+                    self.techniques[i].selected = rank < *n;
+                }
+                Some(Action::SetStatus(t!(
+                    "techniques.selected_top",
+                    count = (*n).min(visible.len())
+                )))
+            }
+            Action::ToggleComparison => {
+                if self.comparing {
+                    self.comparing = false;
+                    return None;
+                }
+                let count = self.selected_count();
+                if !(2..=MAX_COMPARED).contains(&count) {
+                    return Some(Action::SetStatus(t!(
+                        "techniques.compare_count",
+                        max = MAX_COMPARED,
+                        count = count
+                    )));
+                }
+                self.comparing = true;
+                None
+            }
+            Action::Escape => {
+                if self.comparing {
+                    self.comparing = false;
+                    return None;
+                }
+                Some(Action::CancelPhaseTask)
+            }
+            Action::Copy => {
+                if self.cursor_paper.is_some() {
+                    return None;
+                }
+                let tech = self.techniques.get(self.selected)?;
+                Some(Action::CopyText {
+                    text: tech.summary(),
+                    what: t!("techniques.summary").to_string(),
+                })
+            }
+            Action::OpenReExtractPrompt => {
+                if self.extracting || self.cursor_paper.is_some() {
+                    return None;
+                }
+                self.techniques.get(self.selected).map(|tech| {
+                    Action::OpenPrompt(PromptKind::ReExtractGuidance {
+                        index: self.selected,
+                        technique_name: tech.name.clone(),
+                    })
+                })
+            }
+            Action::TechniqueReExtracted {
+                paper_id,
+                name,
+                card,
+            } => {
+                // The list may have changed while the model was working.
+                let Some(tech) = self
+                    .techniques
+                    .iter_mut()
+                    .find(|t| t.paper_id == *paper_id && t.name == *name)
+                else {
+                    return Some(Action::SetStatus(t!(
+                        "techniques.reextract_stale",
+                        name = name
+                    )));
+                };
+                let was_selected = tech.selected;
+                *tech = *card.clone();
+                tech.selected = was_selected;
+                Some(Action::SetStatus(t!(
+                    "techniques.reextracted",
+                    name = card.name
+                )))
+            }
             Action::ExtractionStarted { paper_title } => {
                 self.current_paper = paper_title.clone();
                 None
@@ -99,7 +537,7 @@ impl Component for TechniqueCardsComponent {
             Action::TechniqueExtracted(card) => {
                 self.techniques.push(*card.clone());
                 self.techniques
-                    .sort_by(|a, b| b.relevance_score.partial_cmp(&a.relevance_score).unwrap());
+                    .sort_by(|a, b| b.relevance_score.total_cmp(&a.relevance_score));
                 None
             }
             Action::TechniqueExtractionFailed { paper_id, error } => {
@@ -114,15 +552,42 @@ impl Component for TechniqueCardsComponent {
                 for tech in self.techniques.iter_mut() {
                     tech.selected = true;
                 }
-                Some(Action::SetStatus(format!(
-                    "{} techniques found. Toggle with Enter, then → to generate variants.",
-                    self.techniques.len()
+                let duplicates = self.duplicate_count();
+                if duplicates > 0 {
+                    return Some(Action::SetStatus(t!(
+                        "techniques.found_with_duplicates",
+                        count = self.techniques.len(),
+                        duplicates = duplicates
+                    )));
+                }
+                Some(Action::SetStatus(t!(
+                    "techniques.found",
+                    count = self.techniques.len()
                 )))
             }
             _ => None,
         }
     }
 
+    fn cycle_focus(&mut self, _forward: bool) -> bool {
+        if self.focused_panel().is_none() {
+            return false;
+        }
+        self.focus = self.focus.toggled();
+        self.detail_scroll.reset();
+        true
+    }
+
+    fn focused_panel(&self) -> Option<&'static str> {
+        if self.techniques.is_empty() || self.extracting || self.comparing {
+            return None;
+        }
+        Some(match self.focus {
+            Focus::List => "techniques",
+            Focus::Detail => "details",
+        })
+    }
+
     fn render(&self, frame: &mut Frame, area: Rect) {
         // No outer border — just use the space directly.
         // Empty state (not extracting).
@@ -130,7 +595,10 @@ impl Component for TechniqueCardsComponent {
             let mut lines = vec![Line::from(""), Line::from("")];
             if !self.errors.is_empty() {
                 lines.push(Line::from(Span::styled(
-                    format!("  Extraction failed for {} paper(s).", self.errors.len()),
+                    format!(
+                        "  {}",
+                        t!("techniques.extraction_failed", count = self.errors.len())
+                    ),
                     Style::default().fg(Theme::error()),
                 )));
                 if let Some((_id, err)) = self.errors.first() {
@@ -142,16 +610,16 @@ impl Component for TechniqueCardsComponent {
                 }
                 lines.push(Line::from(""));
                 lines.push(Line::from(Span::styled(
-                    "  Check ANTHROPIC_API_KEY and try again.",
+                    format!("  {}", t!("techniques.check_key")),
                     Theme::muted(),
                 )));
             } else {
                 lines.push(Line::from(Span::styled(
-                    "  No techniques extracted yet.",
+                    format!("  {}", t!("techniques.empty")),
                     Theme::muted(),
                 )));
                 lines.push(Line::from(Span::styled(
-                    "  Complete Phase 2 first, then extraction begins automatically.",
+                    format!("  {}", t!("techniques.complete_phase_2")),
                     Theme::dim(),
                 )));
             }
@@ -165,32 +633,103 @@ impl Component for TechniqueCardsComponent {
             return;
         }
 
+        if self.comparing {
+            self.render_comparison(frame, area);
+            return;
+        }
+
         // Normal view: technique list + detail.
+        let filter_height = u16::from(self.filter_label().is_some());
         let chunks = Layout::vertical([
-            Constraint::Length(1), // Header
-            Constraint::Min(8),    // Technique list
-            Constraint::Length(9), // Detail panel
+            Constraint::Length(1),                  // Header
+            Constraint::Length(filter_height),      // Active filters
+            Constraint::Min(8),                     // Technique list
+            Constraint::Length(2),                  // Cost estimate
+            Constraint::Length(self.detail_height), // Detail panel
         ])
         .split(area);
 
         // Header.
+        let generations = self.selected_count();
         let header = Line::from(vec![
             Span::styled("  ", Theme::dim()),
             Span::styled(
                 format!("{}/{}", self.selected_count(), self.techniques.len()),
                 Theme::header(),
             ),
-            Span::styled(" selected", Theme::muted()),
+            Span::styled(format!(" {}", t!("techniques.selected")), Theme::muted()),
+            Span::styled(
+                if generations == 1 {
+                    format!(" = {}", t!("techniques.one_generation"))
+                } else {
+                    format!(" = {}", t!("techniques.generations", count = generations))
+                },
+                Theme::dim(),
+            ),
             Span::styled("    ", Theme::dim()),
             Span::styled("enter", Theme::key_hint()),
-            Span::styled(" toggle  ", Theme::dim()),
+            Span::styled(format!(" {}  ", t!("hint.toggle")), Theme::dim()),
+            Span::styled("a/n/i/t", Theme::key_hint()),
+            Span::styled(format!(" {}  ", t!("hint.select_sets")), Theme::dim()),
+            Span::styled("f/d", Theme::key_hint()),
+            Span::styled(format!(" {}  ", t!("hint.filter")), Theme::dim()),
+            Span::styled("g", Theme::key_hint()),
+            Span::styled(format!(" {}  ", t!("hint.group")), Theme::dim()),
+            Span::styled("e", Theme::key_hint()),
+            Span::styled(format!(" {}  ", t!("hint.reextract")), Theme::dim()),
+            Span::styled("tab", Theme::key_hint()),
+            Span::styled(format!(" {}  ", t!("hint.focus")), Theme::dim()),
             Span::styled("→", Theme::key_hint()),
-            Span::styled(" generate variants", Theme::dim()),
+            Span::styled(format!(" {}", t!("hint.generate_variants")), Theme::dim()),
         ]);
+        let duplicates = self.duplicate_count();
+        let header = if duplicates > 0 {
+            let mut spans = header.spans;
+            spans.push(Span::styled(
+                format!(
+                    "    {} ",
+                    t!("techniques.duplicate_groups", count = duplicates)
+                ),
+                Style::default().fg(Theme::warning()),
+            ));
+            spans.push(Span::styled("u", Theme::key_hint()));
+            spans.push(Span::styled(
+                format!(" {}  ", t!("hint.consolidate")),
+                Theme::dim(),
+            ));
+            spans.push(Span::styled("U", Theme::key_hint()));
+            spans.push(Span::styled(
+                format!(" {}", t!("hint.drop_duplicates")),
+                Theme::dim(),
+            ));
+            Line::from(spans)
+        } else {
+            header
+        };
         frame.render_widget(Paragraph::new(header), chunks[0]);
 
-        self.render_technique_list(frame, chunks[1]);
-        self.render_technique_detail(frame, chunks[2]);
+        if let Some(label) = self.filter_label() {
+            let filter = Line::from(vec![
+                Span::styled(format!("  {} ", t!("techniques.filter")), Theme::muted()),
+                Span::styled(label, Style::default().fg(Theme::accent())),
+                Span::styled(
+                    format!(
+                        "  ({})",
+                        t!(
+                            "techniques.shown",
+                            shown = self.visible_indices().len(),
+                            total = self.techniques.len()
+                        )
+                    ),
+                    Theme::dim(),
+                ),
+            ]);
+            frame.render_widget(Paragraph::new(filter), chunks[1]);
+        }
+
+        self.render_technique_list(frame, chunks[2]);
+        self.render_estimate(frame, chunks[3]);
+        self.render_technique_detail(frame, chunks[4]);
     }
 }
 
@@ -198,44 +737,44 @@ impl TechniqueCardsComponent {
     // ── Extraction progress view ────────────────────────────
 
     fn render_extracting(&self, frame: &mut Frame, area: Rect) {
-        let spinner = SPINNER[self.spinner_tick % SPINNER.len()];
-        let elapsed_secs = self.spinner_tick / 10;
+        let elapsed_secs = self.spinner.elapsed_secs();
 
         let lines = vec![
             Line::from(""),
             Line::from(""),
             Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    format!("  {} ", spinner),
-                    Style::default()
-                        .fg(Theme::accent())
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("Analyzing paper abstracts with Claude", Theme::header()),
-            ]),
+            self.spinner.heading(t!("techniques.analyzing")),
             Line::from(""),
             Line::from(Span::styled(
                 format!(
-                    "  Extracting techniques from {} papers...  ({}s)",
-                    self.progress.1.max(1) * 8, // approximate paper count
+                    "  {}  ({}s)",
+                    t!(
+                        "techniques.extracting",
+                        count = self.progress.1.max(1) * 8 // approximate paper count
+                    ),
                     elapsed_secs
                 ),
                 Theme::muted(),
             )),
             Line::from(""),
             Line::from(Span::styled(
-                "  This uses abstracts instead of PDFs — typically 10-20 seconds.",
+                format!("  {}", t!("techniques.duration")),
                 Theme::dim(),
             )),
             Line::from(""),
             if !self.techniques.is_empty() {
                 Line::from(Span::styled(
-                    format!("  {} techniques found so far", self.techniques.len()),
+                    format!(
+                        "  {}",
+                        t!("techniques.found_so_far", count = self.techniques.len())
+                    ),
                     Style::default().fg(Theme::success()),
                 ))
             } else {
-                Line::from(Span::styled("  Waiting for results...", Theme::dim()))
+                Line::from(Span::styled(
+                    format!("  {}", t!("techniques.waiting")),
+                    Theme::dim(),
+                ))
             },
         ];
 
@@ -244,129 +783,462 @@ impl TechniqueCardsComponent {
 
     // ── Technique list ──────────────────────────────────────
 
-    fn render_technique_list(&self, frame: &mut Frame, area: Rect) {
+    fn render_technique_list(&self, frame: &mut Frame, full_area: Rect) {
+        let rows = self.list_rows();
+        let area = scrollbar_gutter(full_area, rows.len());
+        if rows.is_empty() {
+            let empty = Line::from(Span::styled(
+                format!("  {}", t!("techniques.no_match_filter")),
+                Theme::muted(),
+            ));
+            frame.render_widget(Paragraph::new(vec![Line::from(""), empty]), area);
+            return;
+        }
+
         let visible_height = area.height as usize;
-        let scroll_offset = if self.selected >= visible_height {
-            self.selected - visible_height + 1
-        } else {
-            0
-        };
+        let cursor = self.cursor_row();
+        let position = rows.iter().position(|r| *r == cursor).unwrap_or(0);
+        let scroll_offset = self
+            .list_view
+            .scroll_to(position, visible_height, rows.len());
+        let duplicates: HashSet<usize> = find_duplicate_techniques(&self.techniques)
+            .into_iter()
+            .flatten()
+            .collect();
 
-        let mut lines: Vec<Line> = Vec::new();
-        for (i, tech) in self
-            .techniques
+        let lines: Vec<Line> = rows
             .iter()
-            .enumerate()
             .skip(scroll_offset)
             .take(visible_height)
-        {
-            let is_selected = i == self.selected;
-            let checkbox = if tech.selected { "◉" } else { "○" };
-            let checkbox_style = if tech.selected {
-                Style::default().fg(Theme::success())
-            } else {
-                Theme::dim()
-            };
+            .map(|row| {
+                let is_cursor = *row == cursor;
+                match row {
+                    ListRow::Paper { paper_id } => {
+                        self.render_paper_row(paper_id, is_cursor, area.width)
+                    }
+                    ListRow::Technique(i) => {
+                        self.render_technique_row(*i, is_cursor, duplicates.contains(i), area.width)
+                    }
+                }
+            })
+            .collect();
 
-            let relevance = format!("{:.0}%", tech.relevance_score * 100.0);
-            let complexity_style = match tech.implementation_complexity.to_string().as_str() {
-                "Low" => Style::default().fg(Theme::success()),
-                "High" => Style::default().fg(Theme::warning()),
-                _ => Theme::muted(),
-            };
+        frame.render_widget(Paragraph::new(lines), area);
+        render_scrollbar(frame, full_area, rows.len(), scroll_offset);
+    }
 
-            let row_style = if is_selected {
-                Style::default().fg(Theme::fg()).bg(Theme::selection_bg())
-            } else {
-                Style::default()
-            };
+    fn render_paper_row(&self, paper_id: &str, is_cursor: bool, width: u16) -> Line<'_> {
+        let members: Vec<&TechniqueCard> = self
+            .techniques
+            .iter()
+            .filter(|t| t.paper_id == paper_id)
+            .collect();
+        let title = members
+            .first()
+            .map(|t| t.paper_title.as_str())
+            .unwrap_or("");
+        let picked = members.iter().filter(|t| t.selected).count();
+
+        let row_style = if is_cursor && self.focus == Focus::List {
+            Style::default().fg(Theme::fg()).bg(Theme::selection_bg())
+        } else {
+            Style::default()
+        };
+        let fold = if self.collapsed.contains(paper_id) {
+            "▸"
+        } else {
+            "▾"
+        };
+        // More than one pick from the same paper is usually redundant.
+        let picked_style = match picked {
+            0 => Theme::dim(),
+            1 => Style::default().fg(Theme::success()),
+            _ => Style::default().fg(Theme::warning()),
+        };
+
+        let title_width = (width as usize).saturating_sub(22);
+        Line::from(vec![
+            Span::styled(if is_cursor { " ▸ " } else { "   " }, row_style),
+            Span::styled(format!("{} ", fold), Theme::muted()),
+            Span::styled(
+                format!(
+                    "{:<width$}",
+                    truncate(title, title_width),
+                    width = title_width
+                ),
+                row_style.add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    " {}",
+                    t!("techniques.picked", picked = picked, total = members.len())
+                ),
+                picked_style,
+            ),
+        ])
+    }
+
+    fn render_technique_row(
+        &self,
+        i: usize,
+        is_cursor: bool,
+        duplicate: bool,
+        width: u16,
+    ) -> Line<'_> {
+        let tech = &self.techniques[i];
+        let checkbox = if tech.selected { "◉" } else { "○" };
+        let checkbox_style = if tech.selected {
+            Style::default().fg(Theme::success())
+        } else {
+            Theme::dim()
+        };
+
+        let relevance = format!("{:.0}%", tech.relevance_score * 100.0);
+
+        let highlight = is_cursor && self.focus == Focus::List;
+        let row_style = if highlight {
+            Style::default().fg(Theme::fg()).bg(Theme::selection_bg())
+        } else {
+            Style::default()
+        };
+
+        // Nest techniques under their paper header when grouped.
+        let indent = if self.grouped { "  " } else { "" };
+        // Cards with a duplicate from another paper are marked before
+        // their name.
+        let marker = if duplicate { "≈ " } else { "" };
+        let name_width =
+            (width as usize).saturating_sub(26 + indent.len() + marker.chars().count());
+        let built = if self.generated.contains(&tech.key()) {
+            format!("{:<6}", t!("techniques.built"))
+        } else {
+            "      ".to_string()
+        };
+        Line::from(vec![
+            Span::styled(if is_cursor { " ▸ " } else { "   " }, row_style),
+            Span::raw(indent),
+            Span::styled(format!("{} ", checkbox), checkbox_style),
+            Span::styled(marker, Style::default().fg(Theme::warning())),
+            Span::styled(
+                format!(
+                    "{:<width$}",
+                    truncate(&tech.name, name_width),
+                    width = name_width
+                ),
+                if highlight {
+                    Style::default()
+                        .fg(Theme::fg())
+                        .bg(Theme::selection_bg())
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Theme::normal()
+                },
+            ),
+            Span::styled(built, Style::default().fg(Theme::success())),
+            Span::styled(
+                format!("{:<4}", complexity_label(&tech.implementation_complexity)),
+                complexity_style(&tech.implementation_complexity),
+            ),
+            Span::styled(format!(" {:>3}", relevance), Theme::muted()),
+        ])
+    }
 
-            let name_width = (area.width as usize).saturating_sub(20);
-            lines.push(Line::from(vec![
-                Span::styled(if is_selected { " ▸ " } else { "   " }, row_style),
-                Span::styled(format!("{} ", checkbox), checkbox_style),
+    // ── Cost estimate ───────────────────────────────────────
+
+    fn render_estimate(&self, frame: &mut Frame, area: Rect) {
+        // Techniques with a variant from an earlier run won't be regenerated.
+        let pending = self
+            .techniques
+            .iter()
+            .filter(|t| t.selected && !self.generated.contains(&t.key()));
+        let estimate = estimate_generation(pending, &self.generation_config);
+
+        let line = if estimate.variants == 0 {
+            Line::from(Span::styled(
+                format!("  {}", t!("techniques.nothing_new")),
+                Theme::dim(),
+            ))
+        } else {
+            let mut line = Line::from(vec![
+                Span::styled(format!("  {}  ", t!("techniques.estimate")), Theme::muted()),
                 Span::styled(
-                    format!(
-                        "{:<width$}",
-                        truncate(&tech.name, name_width),
-                        width = name_width
+                    t!(
+                        "techniques.tokens",
+                        tokens = format_tokens(estimate.input_tokens + estimate.output_tokens)
                     ),
-                    if is_selected {
-                        Style::default()
-                            .fg(Theme::fg())
-                            .bg(Theme::selection_bg())
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Theme::normal()
-                    },
+                    Theme::normal(),
                 ),
+                Span::styled("  ·  ", Theme::dim()),
                 Span::styled(
-                    format!("{:<4}", tech.implementation_complexity),
-                    complexity_style,
+                    format!("~${:.2}", estimate.cost_usd),
+                    Style::default().fg(Theme::accent()),
                 ),
-                Span::styled(format!(" {:>3}", relevance), Theme::muted()),
-            ]));
-        }
+                Span::styled("  ·  ", Theme::dim()),
+                Span::styled(
+                    format!("~{}", format_duration(estimate.wall_seconds)),
+                    Theme::normal(),
+                ),
+                Span::styled(
+                    format!(
+                        "  ({})",
+                        self.generation_config
+                            .llm_routes()
+                            .for_stage(PipelineStage::Generation)
+                            .model
+                    ),
+                    Theme::dim(),
+                ),
+            ]);
+            if !self.generation_config.styles.is_empty() {
+                line.push_span(Span::styled(
+                    format!(
+                        "  {}",
+                        t!(
+                            "techniques.styles",
+                            techniques = estimate.variants / self.generation_config.styles.len(),
+                            styles = self.generation_config.styles.len()
+                        )
+                    ),
+                    Theme::dim(),
+                ));
+            }
+            line
+        };
 
-        frame.render_widget(Paragraph::new(lines), area);
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .border_style(Theme::border());
+        frame.render_widget(Paragraph::new(line).block(block), area);
+    }
+
+    // ── Comparison matrix ───────────────────────────────────
+
+    fn render_comparison(&self, frame: &mut Frame, area: Rect) {
+        let compared: Vec<&TechniqueCard> = self
+            .techniques
+            .iter()
+            .filter(|t| t.selected)
+            .take(MAX_COMPARED)
+            .collect();
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Header
+            Constraint::Min(8),    // Matrix
+        ])
+        .split(area);
+
+        let header = Line::from(vec![
+            Span::styled(format!("  {} ", t!("techniques.comparing")), Theme::muted()),
+            Span::styled(format!("{}", compared.len()), Theme::header()),
+            Span::styled(
+                format!(" {}    ", t!("techniques.techniques")),
+                Theme::muted(),
+            ),
+            Span::styled("c/esc", Theme::key_hint()),
+            Span::styled(format!(" {}", t!("hint.back_to_list")), Theme::dim()),
+        ]);
+        frame.render_widget(Paragraph::new(header), chunks[0]);
+
+        let label_width = 12u16;
+        let col_width = (chunks[1].width.saturating_sub(label_width) as usize
+            / compared.len().max(1))
+        .saturating_sub(1)
+        .max(8);
+
+        // Each matrix row is one dimension; each column one technique.
+        let row = |label: &'static str, texts: Vec<Text<'static>>| {
+            let height = texts.iter().map(|t| t.height()).max().unwrap_or(1).max(1);
+            let mut cells = vec![Cell::from(Span::styled(label, Theme::muted()))];
+            cells.extend(texts.into_iter().map(Cell::from));
+            Row::new(cells).height(height as u16)
+        };
+        let wrapped = |f: &dyn Fn(&TechniqueCard) -> String| -> Vec<Text<'static>> {
+            compared
+                .iter()
+                .map(|t| wrap_cell(&f(t), col_width))
+                .collect()
+        };
+
+        let names = compared
+            .iter()
+            .map(|t| wrap_cell(&t.name, col_width).style(Theme::header()))
+            .collect();
+        let complexity = compared
+            .iter()
+            .map(|t| {
+                Text::from(complexity_label(&t.implementation_complexity))
+                    .style(complexity_style(&t.implementation_complexity))
+            })
+            .collect();
+        let relevance = compared
+            .iter()
+            .map(|t| {
+                Text::from(format!("{:.0}%", t.relevance_score * 100.0))
+                    .style(Style::default().fg(Theme::score_color(t.relevance_score, 1.0)))
+            })
+            .collect();
+        let deps = wrapped(&|t| {
+            if t.dependencies.is_empty() {
+                t!("techniques.no_deps").to_string()
+            } else {
+                t.dependencies.join(", ")
+            }
+        });
+        let hardware = wrapped(&|t| t.hardware_requirements.clone());
+        let data = wrapped(&|t| t.required_data_format.clone());
+        let components = wrapped(&|t| {
+            t.key_components
+                .iter()
+                .map(|c| format!("• {}", c))
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+
+        let rows = vec![
+            row(t!("techniques.complexity"), complexity),
+            row(t!("techniques.relevance"), relevance),
+            row(t!("techniques.deps"), deps),
+            row(t!("techniques.hardware"), hardware),
+            row(t!("techniques.data"), data),
+            row(t!("techniques.components"), components),
+        ];
+
+        let mut widths = vec![Constraint::Length(label_width)];
+        widths.extend(
+            compared
+                .iter()
+                .map(|_| Constraint::Length(col_width as u16)),
+        );
+
+        let table = Table::new(rows, widths)
+            .header(row("", names).bottom_margin(1))
+            .column_spacing(1)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Theme::border()),
+            );
+        frame.render_widget(table, chunks[1]);
     }
 
     // ── Technique detail ────────────────────────────────────
 
     fn render_technique_detail(&self, frame: &mut Frame, area: Rect) {
-        let Some(tech) = self.techniques.get(self.selected) else {
+        let Some(tech) = self
+            .techniques
+            .get(self.selected)
+            .filter(|t| self.matches_filters(t))
+        else {
             return;
         };
 
+        let new_deps = self.new_dependencies(tech);
+        let mut dep_spans = vec![Span::styled(
+            field_label("techniques.deps", 7),
+            Theme::muted(),
+        )];
+        for (i, dep) in tech.dependencies.iter().enumerate() {
+            if i > 0 {
+                dep_spans.push(Span::styled(", ", Theme::dim()));
+            }
+            // New dependencies stand out; ones the project already has are dim.
+            let style = if new_deps.contains(&dep.as_str()) {
+                Style::default().fg(Theme::warning())
+            } else {
+                Theme::dim()
+            };
+            dep_spans.push(Span::styled(dep.as_str(), style));
+        }
+
         let detail_block = Block::default()
             .borders(Borders::TOP)
-            .border_style(Theme::border());
+            .border_style(self.focus.border(Focus::Detail));
 
         let inner = detail_block.inner(area);
         frame.render_widget(detail_block, area);
 
-        let detail = Paragraph::new(vec![
+        let text_width = (inner.width as usize).saturating_sub(4).max(1);
+        let mut lines = vec![
             Line::from(vec![
-                Span::styled("  Paper  ", Theme::muted()),
+                Span::styled(field_label("techniques.paper", 7), Theme::muted()),
                 Span::styled(&tech.paper_title, Theme::normal()),
-            ]),
-            Line::from(vec![
-                Span::styled("  Deps   ", Theme::muted()),
-                Span::styled(tech.dependencies.join(", "), Theme::dim()),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  ", Theme::dim()),
                 Span::styled(
-                    truncate(&tech.methodology, (inner.width as usize).saturating_sub(4)),
+                    if tech.related_papers.is_empty() {
+                        String::new()
+                    } else {
+                        format!(
+                            "  ({})",
+                            t!("techniques.more_papers", count = tech.related_papers.len())
+                        )
+                    },
                     Theme::dim(),
                 ),
             ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  ", Theme::dim()),
-                Span::styled(
-                    truncate(
-                        &tech.integration_approach,
-                        (inner.width as usize).saturating_sub(4),
-                    ),
-                    Theme::muted(),
-                ),
-            ]),
-        ])
-        .wrap(Wrap { trim: true });
+            Line::from(dep_spans),
+        ];
+        // Both texts in full; the panel scrolls through them while focused.
+        for (text, style) in [
+            (&tech.methodology, Theme::dim()),
+            (&tech.integration_approach, Theme::muted()),
+        ] {
+            lines.push(Line::from(""));
+            for wrapped_line in word_wrap(text, text_width) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", wrapped_line),
+                    style,
+                )));
+            }
+        }
 
-        frame.render_widget(detail, inner);
+        let total = lines.len();
+        let scroll = self.detail_scroll.offset(total, inner.height as usize);
+        frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+        render_scrollbar(frame, inner, total, scroll);
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if max_len < 4 {
-        return s.chars().take(max_len).collect();
+/// How a complexity is shown.
+fn complexity_label(complexity: &Complexity) -> &'static str {
+    match complexity {
+        Complexity::Low => t!("techniques.low"),
+        Complexity::Medium => t!("techniques.medium"),
+        Complexity::High => t!("techniques.high"),
     }
-    if s.len() <= max_len {
-        s.to_string()
+}
+
+/// Style for a complexity label (low is good, high is a warning).
+fn complexity_style(complexity: &Complexity) -> Style {
+    match complexity {
+        Complexity::Low => Style::default().fg(Theme::success()),
+        Complexity::High => Style::default().fg(Theme::warning()),
+        Complexity::Medium => Theme::muted(),
+    }
+}
+
+/// Word-wrap text (respecting explicit newlines) into a table cell.
+fn wrap_cell(text: &str, width: usize) -> Text<'static> {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            if !current.is_empty() && current.len() + 1 + word.len() > width {
+                lines.push(Line::from(std::mem::take(&mut current)));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        lines.push(Line::from(current));
+    }
+    Text::from(lines).style(Theme::normal())
+}
+
+/// Compact duration, e.g. `45s` or `3m`.
+fn format_duration(seconds: u64) -> String {
+    if seconds < 60 {
+        format!("{}s", seconds)
     } else {
-        format!("{}...", &s[..max_len - 3])
+        format!("{}m", seconds.div_ceil(60))
     }
 }
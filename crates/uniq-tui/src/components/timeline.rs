@@ -0,0 +1,323 @@
+//! Session timeline — every significant event of the run (queries, papers,
+//! extractions, generations, merges, benchmarks) with when it happened and
+//! how long it took.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::action::{Action, Phase};
+use crate::components::{render_scrollbar, Component, TextScroll};
+use crate::theme::Theme;
+
+struct TimelineEvent {
+    time: DateTime<Local>,
+    phase: Phase,
+    text: String,
+    /// How long the step took, for events that end one.
+    duration: Option<Duration>,
+    failed: bool,
+}
+
+pub struct TimelineComponent {
+    pub visible: bool,
+    /// Oldest first.
+    events: Vec<TimelineEvent>,
+    scroll: TextScroll,
+    /// When each running step started, keyed by what it's doing. Variant
+    /// generations and merges are keyed by the id of the variant they make,
+    /// so each finish is matched with its own start, in whatever order
+    /// they run.
+    started: HashMap<String, Instant>,
+    /// Papers found by the current search.
+    papers: usize,
+    /// Techniques extracted by the current extraction.
+    techniques: usize,
+}
+
+impl TimelineComponent {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            events: Vec::new(),
+            scroll: TextScroll::default(),
+            started: HashMap::new(),
+            papers: 0,
+            techniques: 0,
+        }
+    }
+
+    fn push(&mut self, phase: Phase, text: String, duration: Option<Duration>, failed: bool) {
+        self.events.push(TimelineEvent {
+            time: Local::now(),
+            phase,
+            text,
+            duration,
+            failed,
+        });
+    }
+
+    fn start(&mut self, key: &str) {
+        self.started.insert(key.to_string(), Instant::now());
+    }
+
+    fn finish(&mut self, key: &str) -> Option<Duration> {
+        self.started.remove(key).map(|start| start.elapsed())
+    }
+
+    /// Record `action` if it's a significant event. `variant_name` looks up
+    /// a variant's display name by id or branch.
+    pub fn observe(&mut self, action: &Action, variant_name: impl Fn(&str) -> Option<String>) {
+        let name = |id: &str| variant_name(id).unwrap_or_else(|| id.to_string());
+        match action {
+            Action::SubmitProject { path, .. } => {
+                self.start("analysis");
+                self.push(
+                    Phase::ProjectIntake,
+                    t!("timeline.analyzing", path = path),
+                    None,
+                    false,
+                );
+            }
+            Action::ProjectAnalyzed(profile) => {
+                let duration = self.finish("analysis");
+                let text = t!("timeline.analyzed", files = profile.file_count);
+                self.push(Phase::ProjectIntake, text, duration, false);
+            }
+            Action::ProjectAnalysisFailed(error) => {
+                let duration = self.finish("analysis");
+                let text = t!("timeline.analysis_failed", error = error);
+                self.push(Phase::ProjectIntake, text, duration, true);
+            }
+            Action::SearchQueryStarted { query, .. } => {
+                self.start("search");
+                self.papers = 0;
+                self.push(
+                    Phase::ResearchDiscovery,
+                    t!("timeline.searching", query = query),
+                    None,
+                    false,
+                );
+            }
+            Action::PapersFound(papers) => {
+                self.papers += papers.len();
+                let text = t!("timeline.papers_found", count = papers.len());
+                self.push(Phase::ResearchDiscovery, text, None, false);
+            }
+            Action::ResearchComplete => {
+                let duration = self.finish("search");
+                let text = t!("timeline.search_finished", count = self.papers);
+                self.push(Phase::ResearchDiscovery, text, duration, false);
+            }
+            Action::ResearchFailed(error) => {
+                let duration = self.finish("search");
+                let text = t!("timeline.search_failed", error = error);
+                self.push(Phase::ResearchDiscovery, text, duration, true);
+            }
+            Action::StartExtraction(papers) => {
+                self.start("extraction");
+                self.techniques = 0;
+                let text = t!("timeline.extracting", count = papers.len());
+                self.push(Phase::TechniqueSelection, text, None, false);
+            }
+            Action::TechniqueExtracted(_) => self.techniques += 1,
+            Action::TechniqueExtractionFailed { paper_id, error } => {
+                let text = t!(
+                    "timeline.extraction_failed",
+                    paper = paper_id,
+                    error = error
+                );
+                self.push(Phase::TechniqueSelection, text, None, true);
+            }
+            Action::ExtractionComplete => {
+                let duration = self.finish("extraction");
+                let text = t!("timeline.extraction_finished", count = self.techniques);
+                self.push(Phase::TechniqueSelection, text, duration, false);
+            }
+            Action::VariantGenerationStarted { variant_id } => {
+                self.start(variant_id);
+                let text = t!("timeline.generating", name = name(variant_id));
+                self.push(Phase::VariantGeneration, text, None, false);
+            }
+            Action::VariantGenerated(variant) => {
+                let duration = self.finish(&variant.id.0);
+                let text = t!("timeline.generated", name = variant.display_name);
+                self.push(Phase::VariantGeneration, text, duration, false);
+            }
+            Action::VariantGenerationFailed { variant_id, error } => {
+                let duration = self.finish(variant_id);
+                let text = t!(
+                    "timeline.generation_failed",
+                    name = name(variant_id),
+                    error = error
+                );
+                self.push(Phase::VariantGeneration, text, duration, true);
+            }
+            Action::StartMerge { sources, .. } => {
+                let names: Vec<String> = sources.iter().map(|s| name(&s.variant.0)).collect();
+                let text = t!("timeline.merging", names = names.join(" + "));
+                self.push(Phase::VariantGeneration, text, None, false);
+            }
+            Action::MergeComplete(variant) => {
+                let duration = self.finish(&variant.id.0);
+                let text = t!("timeline.merged", name = variant.display_name);
+                self.push(Phase::VariantGeneration, text, duration, false);
+            }
+            Action::MergeFailed { variant_id, error } => {
+                let duration = self.finish(variant_id);
+                let text = t!("timeline.merge_failed", error = error);
+                self.push(Phase::VariantGeneration, text, duration, true);
+            }
+            Action::StartBenchmark => {
+                self.start("benchmark");
+                self.push(
+                    Phase::Benchmarking,
+                    t!("timeline.benchmarking").to_string(),
+                    None,
+                    false,
+                );
+            }
+            Action::BenchmarkUpdated {
+                branch,
+                execution,
+                judge,
+            } => {
+                let key = match (execution, judge) {
+                    (Some(_), Some(_)) => "timeline.benchmark_and_judge",
+                    (Some(_), None) => "timeline.benchmark_results",
+                    (None, Some(_)) => "timeline.judge_scores",
+                    (None, None) => return,
+                };
+                let text = t!(key, name = name(branch));
+                self.push(Phase::Benchmarking, text, None, false);
+            }
+            Action::BenchmarkCancelled => {
+                self.finish("benchmark");
+            }
+            Action::BenchmarkComplete => {
+                let duration = self.finish("benchmark");
+                self.push(
+                    Phase::Benchmarking,
+                    t!("timeline.benchmarks_finished").to_string(),
+                    duration,
+                    false,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        self.events
+            .iter()
+            .map(|event| {
+                let color = if event.failed {
+                    Theme::error()
+                } else {
+                    Theme::fg()
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        format!(" {}  ", event.time.format("%H:%M:%S")),
+                        Theme::dim(),
+                    ),
+                    Span::styled(format!("{:<10}", event.phase.label()), Theme::muted()),
+                    Span::styled(event.text.clone(), Style::default().fg(color)),
+                ];
+                if let Some(duration) = event.duration {
+                    spans.push(Span::styled(
+                        format!("  ({})", format_elapsed(duration)),
+                        Theme::dim(),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+}
+
+impl Default for TimelineComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for TimelineComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if let Action::ToggleTimeline = action {
+            self.visible = !self.visible;
+            // Open on the latest events.
+            self.scroll.to_bottom();
+            return None;
+        }
+        if !self.visible {
+            return None;
+        }
+
+        if self.scroll.handle(action) {
+            return None;
+        }
+        if let Action::Confirm | Action::Escape = action {
+            self.visible = false;
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let dialog = area.inner(Margin {
+            horizontal: area.width / 10,
+            vertical: area.height / 8,
+        });
+        frame.render_widget(Clear, dialog);
+
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t!("timeline.title", count = self.events.len())
+            ))
+            .title_style(Theme::title())
+            .title_bottom(Line::from(Span::styled(
+                format!(" {} ", t!("timeline.footer")),
+                Theme::dim(),
+            )))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+        let inner = block.inner(dialog);
+        frame.render_widget(block, dialog);
+
+        let lines = if self.events.is_empty() {
+            vec![Line::from(Span::styled(
+                format!(" {}", t!("timeline.empty")),
+                Theme::dim(),
+            ))]
+        } else {
+            self.lines()
+        };
+        let total = lines.len();
+        let offset = self.scroll.offset(total, inner.height as usize);
+        frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), inner);
+        render_scrollbar(frame, inner, total, offset);
+    }
+}
+
+/// Duration for the timeline, e.g. `850ms`, `12.4s` or `3m 05s`.
+fn format_elapsed(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else if secs >= 1 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
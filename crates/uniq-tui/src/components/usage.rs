@@ -0,0 +1,212 @@
+//! Token usage — LLM tokens and what they cost so far this session, by
+//! phase and by model, as reported by the sidecar.
+
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+use uniq_core::estimate::token_cost;
+use uniq_sidecar::protocol::UsageEntry;
+
+use crate::action::{Action, Phase};
+use crate::components::{format_tokens, render_scrollbar, Component, TextScroll};
+use crate::theme::Theme;
+
+/// Sidecar phase keys in display order, with their labels.
+const PHASES: [(&str, Option<Phase>); 5] = [
+    ("intake", Some(Phase::ProjectIntake)),
+    ("techniques", Some(Phase::TechniqueSelection)),
+    ("generation", Some(Phase::VariantGeneration)),
+    ("benchmark", Some(Phase::Benchmarking)),
+    ("other", None),
+];
+
+/// Tokens and cost summed over some usage entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Spend {
+    pub calls: u64,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    pub cost_usd: f64,
+}
+
+impl Spend {
+    fn add(&mut self, entry: &UsageEntry) {
+        let (input, output) = (entry.input_tokens as usize, entry.output_tokens as usize);
+        self.calls += entry.calls;
+        self.input_tokens += input;
+        self.output_tokens += output;
+        if entry.provider.is_metered() {
+            self.cost_usd += token_cost(entry.provider, &entry.model, input, output);
+        }
+    }
+
+    fn sum<'a>(entries: impl IntoIterator<Item = &'a UsageEntry>) -> Self {
+        let mut spend = Self::default();
+        for entry in entries {
+            spend.add(entry);
+        }
+        spend
+    }
+
+    /// Compact total for the status bar, e.g. `62k tok $0.41`.
+    pub fn label(&self) -> String {
+        t!(
+            "usage.label",
+            tokens = format_tokens(self.input_tokens + self.output_tokens),
+            cost = format!("{:.2}", self.cost_usd)
+        )
+    }
+}
+
+pub struct UsageComponent {
+    pub visible: bool,
+    entries: Vec<UsageEntry>,
+    scroll: TextScroll,
+}
+
+impl UsageComponent {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            entries: Vec::new(),
+            scroll: TextScroll::default(),
+        }
+    }
+
+    /// Everything used so far, or `None` before the first Claude call.
+    pub fn total(&self) -> Option<Spend> {
+        let total = Spend::sum(&self.entries);
+        (total.calls > 0).then_some(total)
+    }
+
+    fn row(label: &str, spend: &Spend, style: Style) -> Line<'static> {
+        Line::from(Span::styled(
+            format!(
+                " {:<28} {:>6} {:>8} {:>8} {:>9}",
+                label,
+                spend.calls,
+                format_tokens(spend.input_tokens),
+                format_tokens(spend.output_tokens),
+                format!("${:.2}", spend.cost_usd)
+            ),
+            style,
+        ))
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let header = |title: &str| {
+            Line::from(Span::styled(
+                format!(
+                    " {:<28} {:>6} {:>8} {:>8} {:>9}",
+                    title,
+                    t!("usage.calls"),
+                    t!("usage.input"),
+                    t!("usage.output"),
+                    t!("usage.cost")
+                ),
+                Theme::header(),
+            ))
+        };
+        let mut lines = vec![header(t!("usage.phase"))];
+        for (key, phase) in PHASES {
+            let spend = Spend::sum(self.entries.iter().filter(|e| e.phase == key));
+            if spend.calls > 0 {
+                let label = phase.map_or(t!("usage.other"), |p| p.name());
+                lines.push(Self::row(label, &spend, Theme::normal()));
+            }
+        }
+        lines.push(Self::row(
+            t!("usage.total"),
+            &Spend::sum(&self.entries),
+            Theme::normal().add_modifier(Modifier::BOLD),
+        ));
+
+        let mut models: Vec<&str> = self.entries.iter().map(|e| e.model.as_str()).collect();
+        models.sort_unstable();
+        models.dedup();
+        lines.push(Line::from(""));
+        lines.push(header(t!("usage.model")));
+        for model in models {
+            let spend = Spend::sum(self.entries.iter().filter(|e| e.model == model));
+            lines.push(Self::row(model, &spend, Theme::normal()));
+        }
+        lines
+    }
+}
+
+impl Default for UsageComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for UsageComponent {
+    fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        match action {
+            Action::UsageUpdated(entries) => {
+                self.entries = entries.clone();
+                return None;
+            }
+            Action::ToggleUsage => {
+                self.visible = !self.visible;
+                self.scroll.reset();
+                return None;
+            }
+            _ => {}
+        }
+        if !self.visible {
+            return None;
+        }
+
+        if self.scroll.handle(action) {
+            return None;
+        }
+        if let Action::Confirm | Action::Escape = action {
+            self.visible = false;
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let dialog = area.inner(Margin {
+            horizontal: area.width / 10,
+            vertical: area.height / 8,
+        });
+        frame.render_widget(Clear, dialog);
+
+        let title = match self.total() {
+            Some(total) => format!(" {} — {} ", t!("usage.title"), total.label()),
+            None => format!(" {} ", t!("usage.title")),
+        };
+        let block = Block::default()
+            .title(title)
+            .title_style(Theme::title())
+            .title_bottom(Line::from(Span::styled(
+                format!(" {} ", t!("usage.footer")),
+                Theme::dim(),
+            )))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+        let inner = block.inner(dialog);
+        frame.render_widget(block, dialog);
+
+        let lines = if self.entries.is_empty() {
+            vec![Line::from(Span::styled(
+                format!(" {}", t!("usage.empty")),
+                Theme::dim(),
+            ))]
+        } else {
+            self.lines()
+        };
+        let total = lines.len();
+        let offset = self.scroll.offset(total, inner.height as usize);
+        frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), inner);
+        render_scrollbar(frame, inner, total, offset);
+    }
+}
@@ -1,16 +1,20 @@
 //! Phase 4: Variant Generation — show progress of generating each variant.
 
+use std::collections::HashMap;
+
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Row, Table, Wrap};
 use ratatui::Frame;
 
-use crate::action::Action;
-use crate::components::Component;
+use crate::action::{Action, PromptKind};
+use crate::components::{
+    bracket_hints, find_next, no_match, truncate, Component, TextScroll, PAGE,
+};
 use crate::theme::Theme;
 
-use uniq_core::variant::{Variant, VariantStatus};
+use uniq_core::variant::{FileOverlap, ImplementationPlan, Variant, VariantOrigin, VariantStatus};
 
 pub struct VariantBuilderComponent {
     /// All variants (original + merged).
@@ -19,6 +23,28 @@ pub struct VariantBuilderComponent {
     pub selected: usize,
     /// Whether generation is in progress.
     pub generating: bool,
+    /// Whether the selected variant's generation transcript is shown.
+    showing_transcript: bool,
+    /// Scroll offset of the transcript panel.
+    transcript_scroll: TextScroll,
+    /// What each running generation is doing, keyed by branch name.
+    progress: HashMap<String, String>,
+    /// Whether `D` was pressed once and deleting awaits a second press.
+    confirming_delete: bool,
+    /// Variant marked as the first side of a comparison.
+    compare_mark: Option<String>,
+    /// File-level comparison of two variants, while shown.
+    comparison: Option<Comparison>,
+    /// Scroll offset of the comparison panel.
+    comparison_scroll: TextScroll,
+}
+
+/// Two variants' touched files and the diff of the files both modified.
+struct Comparison {
+    a_name: String,
+    b_name: String,
+    overlap: FileOverlap,
+    diff: String,
 }
 
 impl VariantBuilderComponent {
@@ -27,28 +53,213 @@ impl VariantBuilderComponent {
             variants: Vec::new(),
             selected: 0,
             generating: false,
+            showing_transcript: false,
+            transcript_scroll: TextScroll::default(),
+            progress: HashMap::new(),
+            confirming_delete: false,
+            compare_mark: None,
+            comparison: None,
+            comparison_scroll: TextScroll::default(),
         }
     }
 
     fn status_display(status: &VariantStatus) -> (String, Style) {
         match status {
-            VariantStatus::Pending => ("Pending".to_string(), Theme::dim()),
+            VariantStatus::Pending => (t!("variants.pending").to_string(), Theme::dim()),
             VariantStatus::Generating => (
-                "Generating...".to_string(),
+                t!("variants.generating").to_string(),
                 Style::default().fg(Theme::warning()),
             ),
-            VariantStatus::Ready => ("Ready".to_string(), Style::default().fg(Theme::success())),
+            VariantStatus::AwaitingApproval => (
+                t!("variants.review_plan").to_string(),
+                Style::default().fg(Theme::accent()),
+            ),
+            VariantStatus::Ready => (
+                t!("variants.ready").to_string(),
+                Style::default().fg(Theme::success()),
+            ),
             VariantStatus::Failed(err) => (
-                format!("Failed: {}", truncate(err, 30)),
+                t!("variants.failed", error = truncate(err, 30)),
                 Style::default().fg(Theme::error()),
             ),
         }
     }
 }
 
+impl Default for VariantBuilderComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Component for VariantBuilderComponent {
     fn handle_action(&mut self, action: &Action) -> Option<Action> {
+        if self.comparison.is_some() {
+            if self.comparison_scroll.handle(action) {
+                return None;
+            }
+            match action {
+                Action::Confirm | Action::Escape | Action::ToggleComparison => {
+                    self.comparison = None;
+                    return None;
+                }
+                _ => {}
+            }
+        }
+
+        if self.showing_transcript {
+            if self.transcript_scroll.handle(action) {
+                return None;
+            }
+            match action {
+                Action::Confirm | Action::Escape => {
+                    self.showing_transcript = false;
+                    return None;
+                }
+                _ => {}
+            }
+        }
+
+        // Any other key cancels a pending delete confirmation.
+        if self.confirming_delete && action.is_navigation_input() {
+            self.confirming_delete = false;
+            return Some(Action::SetStatus(t!("variants.kept").to_string()));
+        }
+
         match action {
+            Action::ToggleComparison => {
+                let variant = self.variants.get(self.selected)?;
+                if variant.status != VariantStatus::Ready {
+                    return Some(Action::SetStatus(
+                        t!("variants.compare_ready_only").to_string(),
+                    ));
+                }
+                match self.compare_mark.take() {
+                    Some(ref mark) if *mark == variant.id.0 => None,
+                    Some(mark) => Some(Action::CompareVariants {
+                        a: mark,
+                        b: variant.id.0.clone(),
+                    }),
+                    None => {
+                        self.compare_mark = Some(variant.id.0.clone());
+                        Some(Action::SetStatus(t!(
+                            "variants.comparing",
+                            name = variant.display_name
+                        )))
+                    }
+                }
+            }
+            Action::VariantsCompared {
+                a,
+                b,
+                overlap,
+                diff,
+            } => {
+                let name = |id: &str| {
+                    self.variants
+                        .iter()
+                        .find(|v| v.id.0 == id)
+                        .map(|v| v.display_name.clone())
+                        .unwrap_or_else(|| id.to_string())
+                };
+                self.comparison = Some(Comparison {
+                    a_name: name(a),
+                    b_name: name(b),
+                    overlap: overlap.clone(),
+                    diff: diff.clone(),
+                });
+                self.comparison_scroll.reset();
+                None
+            }
+            Action::DeleteVariant => {
+                let variant = self.variants.get(self.selected)?;
+                if matches!(
+                    variant.status,
+                    VariantStatus::Pending | VariantStatus::Generating
+                ) {
+                    return Some(Action::SetStatus(t!("variants.cancel_first").to_string()));
+                }
+                if self.confirming_delete {
+                    self.confirming_delete = false;
+                    return Some(Action::DiscardVariant {
+                        variant_id: variant.id.0.clone(),
+                    });
+                }
+                self.confirming_delete = true;
+                Some(Action::SetStatus(t!(
+                    "variants.confirm_delete",
+                    name = variant.display_name,
+                    branch = variant.branch_name
+                )))
+            }
+            Action::Escape => Some(Action::CancelPhaseTask),
+            Action::CancelVariantGeneration => {
+                let variant = self.variants.get(self.selected)?;
+                if !matches!(
+                    variant.status,
+                    VariantStatus::Pending
+                        | VariantStatus::Generating
+                        | VariantStatus::AwaitingApproval
+                ) {
+                    return None;
+                }
+                Some(Action::CancelGeneration {
+                    variant_id: variant.id.0.clone(),
+                    branch_name: variant.branch_name.clone(),
+                })
+            }
+            Action::Copy => {
+                let variant = self.variants.get(self.selected)?;
+                Some(Action::CopyText {
+                    text: variant.branch_name.clone(),
+                    what: t!("variants.branch_name").to_string(),
+                })
+            }
+            Action::OpenRenamePrompt => {
+                let variant = self.variants.get(self.selected)?;
+                Some(Action::OpenPrompt(PromptKind::RenameVariant {
+                    variant_id: variant.id.0.clone(),
+                    variant_name: variant.display_name.clone(),
+                }))
+            }
+            Action::OpenConflictViewer => {
+                let variant = self.variants.get(self.selected)?;
+                if variant.merge_conflicts.is_empty() {
+                    return Some(Action::SetStatus(t!("variants.no_conflicts").to_string()));
+                }
+                Some(Action::ShowConflicts {
+                    variant_id: variant.id.0.clone(),
+                })
+            }
+            Action::OpenRegeneratePrompt => {
+                let variant = self.variants.get(self.selected)?;
+                if let VariantOrigin::Merge { ref spec } = variant.origin {
+                    // Tweak and re-merge: the dialog merges into a new
+                    // sibling branch, leaving this one as it is.
+                    return Some(Action::PrefillMerge {
+                        sources: spec.sources.clone(),
+                        guidance: spec.guidance.clone(),
+                        strategy: spec.strategy,
+                    });
+                }
+                if !matches!(
+                    variant.status,
+                    VariantStatus::Ready | VariantStatus::Failed(_)
+                ) {
+                    return Some(Action::SetStatus(t!("variants.wait").to_string()));
+                }
+                Some(Action::OpenPrompt(PromptKind::RegenerateFeedback {
+                    variant_id: variant.id.0.clone(),
+                    variant_name: variant.display_name.clone(),
+                }))
+            }
+            Action::Confirm => {
+                if self.variants.get(self.selected).is_some() {
+                    self.showing_transcript = true;
+                    self.transcript_scroll.reset();
+                }
+                None
+            }
             Action::ScrollUp | Action::SelectPrev => {
                 if self.selected > 0 {
                     self.selected -= 1;
@@ -61,6 +272,64 @@ impl Component for VariantBuilderComponent {
                 }
                 None
             }
+            Action::ScrollTop => {
+                self.selected = 0;
+                None
+            }
+            Action::ScrollBottom => {
+                self.selected = self.variants.len().saturating_sub(1);
+                None
+            }
+            Action::PageUp => {
+                self.selected = self.selected.saturating_sub(PAGE);
+                None
+            }
+            Action::PageDown => {
+                self.selected = (self.selected + PAGE).min(self.variants.len().saturating_sub(1));
+                None
+            }
+            Action::Search(query) => {
+                let names = self.variants.iter().map(|v| v.display_name.as_str());
+                match find_next(names, self.selected, query) {
+                    Some(i) => {
+                        self.selected = i;
+                        None
+                    }
+                    None => Some(no_match(query)),
+                }
+            }
+            Action::VariantGenerationStarted { variant_id } => {
+                if let Some(variant) = self.variants.iter_mut().find(|v| v.id.0 == *variant_id) {
+                    if variant.status == VariantStatus::Pending {
+                        variant.status = VariantStatus::Generating;
+                    }
+                }
+                None
+            }
+            Action::VariantPlanned { variant_id, plan } => {
+                let variant = self.variants.iter_mut().find(|v| v.id.0 == *variant_id)?;
+                variant.status = VariantStatus::AwaitingApproval;
+                variant.plan = Some(*plan.clone());
+                Some(Action::SetStatus(t!(
+                    "variants.plan_ready",
+                    name = variant.display_name
+                )))
+            }
+            Action::ApprovePlan => {
+                let variant = self.variants.get_mut(self.selected)?;
+                if variant.status != VariantStatus::AwaitingApproval {
+                    return None;
+                }
+                variant.status = VariantStatus::Pending;
+                self.showing_transcript = false;
+                Some(Action::ApproveVariantPlan {
+                    variant_id: variant.id.0.clone(),
+                })
+            }
+            Action::GenerationProgress(progress) => {
+                self.progress = progress.clone();
+                None
+            }
             Action::VariantGenerated(variant) => {
                 // Update existing or add new.
                 if let Some(existing) = self.variants.iter_mut().find(|v| v.id == variant.id) {
@@ -83,23 +352,19 @@ impl Component for VariantBuilderComponent {
                     .iter()
                     .filter(|v| v.status == VariantStatus::Ready)
                     .count();
-                Some(Action::SetStatus(format!(
-                    "{}/{} variants generated. Press [Right] to benchmark.",
-                    ready,
-                    self.variants.len()
+                Some(Action::SetStatus(t!(
+                    "variants.complete",
+                    ready = ready,
+                    total = self.variants.len()
                 )))
             }
-            Action::MergeComplete(variant) => {
-                self.variants.push(*variant.clone());
-                None
-            }
             _ => None,
         }
     }
 
     fn render(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
-            .title(" Variant Generation ")
+            .title(format!(" {} ", t!("variants.title")))
             .title_style(Theme::title())
             .borders(Borders::ALL)
             .border_style(Theme::dim());
@@ -110,11 +375,8 @@ impl Component for VariantBuilderComponent {
         if self.variants.is_empty() {
             let msg = Paragraph::new(vec![
                 Line::from(""),
-                Line::from(Span::styled("No variants yet.", Theme::dim())),
-                Line::from(Span::styled(
-                    "Select techniques in Phase 3 first.",
-                    Theme::dim(),
-                )),
+                Line::from(Span::styled(t!("variants.empty"), Theme::dim())),
+                Line::from(Span::styled(t!("variants.complete_phase_3"), Theme::dim())),
             ]);
             frame.render_widget(msg, inner);
             return;
@@ -133,37 +395,88 @@ impl Component for VariantBuilderComponent {
             .iter()
             .filter(|v| v.status == VariantStatus::Ready)
             .count();
-        let summary = Paragraph::new(Line::from(vec![
+        let mut summary = vec![
             Span::styled(
-                format!("{}/{} variants ready", ready_count, self.variants.len()),
+                t!(
+                    "variants.ready_count",
+                    ready = ready_count,
+                    total = self.variants.len()
+                ),
                 Theme::header(),
             ),
             Span::styled("  |  ", Theme::dim()),
-            Span::styled("[m]", Theme::selected()),
-            Span::styled("erge variants  ", Theme::dim()),
-            Span::styled("[Right]", Theme::selected()),
-            Span::styled(" benchmark", Theme::dim()),
-        ]));
+        ];
+        summary.extend(bracket_hints(t!("variants.hints")));
+        let summary = Paragraph::new(Line::from(summary));
         frame.render_widget(summary, chunks[0]);
 
+        if let Some(ref comparison) = self.comparison {
+            let body = chunks[1].union(chunks[2]);
+            self.render_comparison(frame, body, comparison);
+            return;
+        }
+
+        if self.showing_transcript {
+            if let Some(variant) = self.variants.get(self.selected) {
+                let body = chunks[1].union(chunks[2]);
+                self.render_transcript(frame, body, variant);
+                return;
+            }
+        }
+
         // Variant table
-        let header = Row::new(vec!["#", "Name", "Type", "Branch", "Status"]).style(Theme::header());
+        let header = Row::new(vec![
+            "#",
+            t!("variants.name"),
+            t!("variants.type"),
+            t!("variants.branch"),
+            t!("variants.status"),
+        ])
+        .style(Theme::header());
 
+        // Pending variants wait for a generation slot in table order;
+        // merges queue separately, one at a time.
+        let mut queued = 0;
+        let mut merges_queued = 0;
         let rows: Vec<Row> = self
             .variants
             .iter()
             .enumerate()
             .map(|(i, v)| {
-                let (status_text, _status_style) = Self::status_display(&v.status);
+                let (mut status_text, _status_style) = Self::status_display(&v.status);
+                match v.status {
+                    VariantStatus::Pending if v.is_merge() => {
+                        merges_queued += 1;
+                        status_text = t!("variants.merge_queued", position = merges_queued);
+                    }
+                    VariantStatus::Pending if self.generating => {
+                        queued += 1;
+                        status_text = t!("variants.queued", position = queued);
+                    }
+                    VariantStatus::Generating => {
+                        if let Some(step) = self.progress.get(&v.branch_name) {
+                            status_text = truncate(step, 28);
+                        }
+                    }
+                    VariantStatus::Ready if !v.dependencies_ok() => {
+                        status_text = t!("variants.deps_failed").to_string();
+                    }
+                    _ => {}
+                }
                 let row_style = if i == self.selected {
                     Theme::selected()
                 } else {
                     Theme::normal()
                 };
-                let variant_type = if v.is_merge() { "Merge" } else { "Research" };
+                let variant_type = if v.is_merge() {
+                    t!("variants.merge")
+                } else {
+                    t!("variants.research")
+                };
 
+                let marked = self.compare_mark.as_deref() == Some(v.id.0.as_str());
                 Row::new(vec![
-                    format!("{}", i + 1),
+                    format!("{}{}", i + 1, if marked { " ◆" } else { "" }),
                     truncate(&v.display_name, 35),
                     variant_type.to_string(),
                     truncate(&v.branch_name, 30),
@@ -180,7 +493,7 @@ impl Component for VariantBuilderComponent {
                 Constraint::Min(20),
                 Constraint::Length(10),
                 Constraint::Length(32),
-                Constraint::Length(20),
+                Constraint::Length(28),
             ],
         )
         .header(header)
@@ -197,26 +510,70 @@ impl Component for VariantBuilderComponent {
 
             let mut lines = vec![
                 Line::from(vec![
-                    Span::styled("Branch: ", Theme::header()),
+                    Span::styled(format!("{} ", t!("variants.branch_label")), Theme::header()),
                     Span::styled(&variant.branch_name, Theme::normal()),
                 ]),
                 Line::from(vec![
-                    Span::styled("Modified files: ", Theme::header()),
+                    Span::styled(
+                        format!("{} ", t!("variants.modified_files")),
+                        Theme::header(),
+                    ),
                     Span::styled(format!("{}", variant.modified_files.len()), Theme::normal()),
                 ]),
                 Line::from(vec![
-                    Span::styled("New deps: ", Theme::header()),
+                    Span::styled(format!("{} ", t!("variants.new_deps")), Theme::header()),
                     Span::styled(variant.new_dependencies.join(", "), Theme::normal()),
                 ]),
             ];
 
+            if let Some(ref check) = variant.dependency_check {
+                let mut spans = vec![
+                    Span::styled(format!("{} ", t!("variants.installed")), Theme::header()),
+                    Span::styled(
+                        t!(
+                            "variants.installed_count",
+                            installed = check.installed.len(),
+                            total = check.installed.len() + check.failed.len(),
+                            installer = check.installer
+                        ),
+                        Theme::normal(),
+                    ),
+                ];
+                if !check.failed.is_empty() {
+                    let names: Vec<&str> = check.failed.iter().map(|f| f.name.as_str()).collect();
+                    spans.push(Span::styled(
+                        format!("  {}", t!("variants.failed_deps", names = names.join(", "))),
+                        Style::default().fg(Theme::error()),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+
+            if let Some(ref revision) = variant.revision {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{} ", t!("variants.revises")), Theme::header()),
+                    Span::styled(&revision.original_name, Theme::normal()),
+                    Span::styled(format!("  \"{}\"", revision.feedback), Theme::dim()),
+                ]));
+            }
+
             if variant.is_merge() {
                 lines.push(Line::from(Span::styled(
-                    "Type: Merged variant",
+                    t!("variants.merged_type"),
                     Style::default().fg(Theme::accent_secondary()),
                 )));
             }
 
+            if !variant.merge_conflicts.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        t!("variants.conflicts", count = variant.merge_conflicts.len()),
+                        Style::default().fg(Theme::warning()),
+                    ),
+                    Span::styled(format!("  {}", t!("variants.resolve_hint")), Theme::dim()),
+                ]));
+            }
+
             let detail = Paragraph::new(lines)
                 .wrap(Wrap { trim: true })
                 .block(detail_block);
@@ -226,10 +583,229 @@ impl Component for VariantBuilderComponent {
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
+impl VariantBuilderComponent {
+    // ── Generation transcript ───────────────────────────────
+
+    /// The approved (or proposed) implementation plan, as transcript lines.
+    fn plan_lines(plan: &ImplementationPlan, awaiting: bool) -> Vec<Line<'static>> {
+        let title = if awaiting {
+            t!("variants.proposed_plan")
+        } else {
+            t!("variants.approved_plan")
+        };
+        let mut lines = vec![Line::from(Span::styled(title, Theme::header()))];
+        for (i, step) in plan.steps.iter().enumerate() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>2}. ", i + 1), Theme::muted()),
+                Span::styled(step.clone(), Theme::normal()),
+            ]));
+        }
+        for file in &plan.files {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} {}", file.action, file.path), Theme::normal()),
+                Span::styled(format!("  {}", file.purpose), Theme::dim()),
+            ]));
+        }
+        if !plan.new_modules.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {}",
+                    t!(
+                        "variants.new_modules",
+                        modules = plan.new_modules.join(", ")
+                    )
+                ),
+                Theme::normal(),
+            )));
+        }
+        if !plan.dependencies.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {}",
+                    t!("variants.dependencies", deps = plan.dependencies.join(", "))
+                ),
+                Theme::normal(),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines
+    }
+
+    fn render_transcript(&self, frame: &mut Frame, area: Rect, variant: &Variant) {
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t!("variants.transcript", name = variant.display_name)
+            ))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let plan_lines = variant
+            .plan
+            .as_ref()
+            .map(|plan| Self::plan_lines(plan, variant.status == VariantStatus::AwaitingApproval));
+        if variant.transcript.is_none() {
+            if let Some(lines) = plan_lines {
+                let paragraph = Paragraph::new(lines)
+                    .wrap(Wrap { trim: false })
+                    .block(block);
+                self.transcript_scroll.render(frame, paragraph, area);
+                return;
+            }
+        }
+
+        let Some(ref transcript) = variant.transcript else {
+            let msg = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(t!("variants.no_transcript"), Theme::dim())),
+            ])
+            .block(block);
+            frame.render_widget(msg, area);
+            return;
+        };
+
+        let mut lines = plan_lines.unwrap_or_default();
+        lines.push(Line::from(Span::styled(
+            t!("variants.explanation"),
+            Theme::header(),
+        )));
+        lines.push(Line::from(Span::styled(
+            if transcript.explanation.is_empty() {
+                t!("variants.no_explanation").to_string()
+            } else {
+                transcript.explanation.clone()
+            },
+            Theme::normal(),
+        )));
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!("variants.plan"),
+            Theme::header(),
+        )));
+        for (i, step) in transcript.plan.iter().enumerate() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>2}. ", i + 1), Theme::muted()),
+                Span::styled(step.as_str(), Theme::normal()),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!("variants.files_written"),
+            Theme::header(),
+        )));
+        for file in &transcript.files_written {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", file),
+                Theme::normal(),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!("variants.log"),
+            Theme::header(),
+        )));
+        for entry in &transcript.log {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", entry),
+                Theme::dim(),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!("variants.scroll_footer"),
+            Theme::dim(),
+        )));
+
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block);
+        self.transcript_scroll.render(frame, paragraph, area);
+    }
+
+    fn render_comparison(&self, frame: &mut Frame, area: Rect, comparison: &Comparison) {
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t!(
+                    "variants.compare_title",
+                    a = comparison.a_name,
+                    b = comparison.b_name
+                )
+            ))
+            .title_style(Theme::title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::accent()));
+
+        let mut lines = Vec::new();
+        let sections = [
+            (
+                t!("variants.only_in", name = comparison.a_name),
+                &comparison.overlap.only_a,
+            ),
+            (
+                t!("variants.only_in", name = comparison.b_name),
+                &comparison.overlap.only_b,
+            ),
+            (
+                t!("variants.modified_by_both").to_string(),
+                &comparison.overlap.both,
+            ),
+        ];
+        for (title, files) in sections {
+            lines.push(Line::from(Span::styled(
+                format!("{} ({})", title, files.len()),
+                Theme::header(),
+            )));
+            for file in files {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", file),
+                    Theme::normal(),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+
+        lines.push(Line::from(Span::styled(
+            t!(
+                "variants.shared_diff",
+                a = comparison.a_name,
+                b = comparison.b_name
+            ),
+            Theme::header(),
+        )));
+        if comparison.diff.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("  ({})", t!("variants.no_overlap")),
+                Theme::dim(),
+            )));
+        }
+        for line in comparison.diff.lines() {
+            let style = if line.starts_with("+++") || line.starts_with("---") {
+                Theme::header()
+            } else if line.starts_with('+') {
+                Style::default().fg(Theme::success())
+            } else if line.starts_with('-') {
+                Style::default().fg(Theme::error())
+            } else if line.starts_with("@@") {
+                Style::default().fg(Theme::accent())
+            } else {
+                Theme::dim()
+            };
+            lines.push(Line::from(Span::styled(line.to_string(), style)));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!("variants.scroll_footer"),
+            Theme::dim(),
+        )));
+
+        self.comparison_scroll
+            .render(frame, Paragraph::new(lines).block(block), area);
     }
 }
@@ -6,16 +6,29 @@
 //! - Editing: keys are forwarded as raw CharInput/BackspaceInput so text
 //!   fields can receive typed characters.
 //!
+//! Normal-mode keys come from the [`Keymap`], which users can rebind in
+//! the `[keys]` config section. With `ui.keymap = "vim"` a vim layer sits
+//! on top: h/l switch phases (or columns in dialogs), `gg`/`G` jump to the
+//! ends of a list, `/` searches and `:` opens the command line.
+//!
 //! The current InputMode is shared between the App and EventHandler via
-//! an Arc<AtomicU8>.
+//! an Arc<AtomicU8>. A second flag pauses reading altogether while the App
+//! has handed the terminal to an external program such as `$EDITOR`; it is
+//! a watch channel, so the handler can wait on events and on the flag at
+//! once instead of polling.
+//!
+//! Ticks don't come from here: the App's main loop keeps its own frame
+//! clock, so animation doesn't depend on input arriving.
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyModifiers};
+use futures_util::StreamExt;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::watch;
 
-use crate::action::{Action, InputMode};
+use crate::action::{Action, CursorMove, InputMode};
+use crate::channel::ActionSender;
+use crate::keymap::{self, Keymap};
 
 /// Encode InputMode as u8 for atomic sharing.
 const MODE_NORMAL: u8 = 0;
@@ -28,6 +41,14 @@ pub fn new_input_mode_flag() -> InputModeFlag {
     Arc::new(AtomicU8::new(MODE_NORMAL))
 }
 
+/// Shared flag the App sets while another program owns the terminal, so
+/// the EventHandler doesn't steal its input.
+pub type PauseFlag = watch::Sender<bool>;
+
+pub fn new_pause_flag() -> PauseFlag {
+    watch::Sender::new(false)
+}
+
 pub fn set_input_mode(flag: &InputModeFlag, mode: InputMode) {
     let val = match mode {
         InputMode::Normal => MODE_NORMAL,
@@ -45,71 +66,84 @@ fn get_input_mode(flag: &InputModeFlag) -> InputMode {
 
 /// Event loop that reads terminal events and sends Actions.
 pub struct EventHandler {
-    tx: mpsc::UnboundedSender<Action>,
-    tick_rate: Duration,
+    tx: ActionSender,
     mode_flag: InputModeFlag,
+    paused: watch::Receiver<bool>,
+    keymap: Keymap,
+    /// A `g` waiting for its second half (vim layer).
+    pending_g: bool,
 }
 
 impl EventHandler {
     pub fn new(
-        tx: mpsc::UnboundedSender<Action>,
-        tick_rate: Duration,
+        tx: ActionSender,
         mode_flag: InputModeFlag,
+        paused: watch::Receiver<bool>,
+        keymap: Keymap,
     ) -> Self {
         Self {
             tx,
-            tick_rate,
             mode_flag,
+            paused,
+            keymap,
+            pending_g: false,
         }
     }
 
     /// Run the event loop. This blocks and should be spawned in a task.
-    pub async fn run(&self) {
-        let mut interval = tokio::time::interval(self.tick_rate);
-
+    pub async fn run(&mut self) {
         loop {
-            let action = tokio::select! {
-                _ = interval.tick() => {
-                    Some(Action::Tick)
-                }
-                result = tokio::task::spawn_blocking({
-                    || {
-                        if event::poll(Duration::from_millis(50)).unwrap_or(false) {
-                            event::read().ok()
-                        } else {
-                            None
+            if self.paused.wait_for(|paused| !paused).await.is_err() {
+                break;
+            }
+            // Dropping the stream stops its reader, so a pause hands every
+            // key to the other program.
+            let mut events = EventStream::new();
+            loop {
+                let event = tokio::select! {
+                    changed = self.paused.changed() => {
+                        if changed.is_err() {
+                            return;
                         }
+                        if *self.paused.borrow_and_update() {
+                            break;
+                        }
+                        continue;
                     }
-                }) => {
-                    match result {
-                        Ok(Some(event)) => self.map_event(event),
-                        _ => None,
-                    }
-                }
-            };
-
-            if let Some(action) = action {
-                if self.tx.send(action).is_err() {
-                    break;
+                    event = events.next() => match event {
+                        Some(Ok(event)) => event,
+                        Some(Err(_)) => continue,
+                        None => return,
+                    },
+                };
+                let Some(action) = self.map_event(event) else {
+                    continue;
+                };
+                if self.tx.send(action).await.is_err() {
+                    return;
                 }
             }
         }
     }
 
-    fn map_event(&self, event: Event) -> Option<Action> {
+    fn map_event(&mut self, event: Event) -> Option<Action> {
         match event {
             Event::Key(key) => self.map_key(key),
             Event::Paste(text) => Some(Action::PasteBulk(text)),
-            Event::Resize(_, _) => Some(Action::Tick),
+            Event::Resize(_, _) => Some(Action::Redraw),
             _ => None,
         }
     }
 
-    fn map_key(&self, key: KeyEvent) -> Option<Action> {
+    fn map_key(&mut self, key: KeyEvent) -> Option<Action> {
         // Ctrl+C always quits regardless of mode.
         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
             return Some(Action::Quit);
         }
+        // Some bindings (e.g. switching themes) also work while typing.
+        if let Some(binding) = self.keymap.lookup(&key).filter(|b| b.global) {
+            return Some(binding.action());
+        }
 
         match get_input_mode(&self.mode_flag) {
             InputMode::Editing => self.map_key_editing(key),
@@ -120,33 +154,41 @@ impl EventHandler {
     /// Key mapping when a text field is focused. Most keys become character
     /// input; only a few are reserved for navigation.
     fn map_key_editing(&self, key: KeyEvent) -> Option<Action> {
-        // Ctrl shortcuts that work in editing mode.
+        // Submitting, switching fields, clipboard shortcuts and the like.
+        // Alt+Enter also submits (some terminals send this instead of
+        // Ctrl+Enter).
+        if let Some(action) = keymap::editing_action(&key) {
+            return Some(action);
+        }
+        // Other Ctrl shortcuts don't type anything.
         if key.modifiers.contains(KeyModifiers::CONTROL) {
-            return match key.code {
-                KeyCode::Char('w') => Some(Action::DeleteWord),
-                KeyCode::Char('v') => Some(Action::PasteInput),
-                KeyCode::Char('s') => Some(Action::SubmitForm),
-                KeyCode::Enter => Some(Action::SubmitForm),
-                _ => None,
-            };
+            return None;
         }
 
-        // Alt+Enter also submits (some terminals send this instead of Ctrl+Enter).
-        if key.modifiers.contains(KeyModifiers::ALT) && key.code == KeyCode::Enter {
-            return Some(Action::SubmitForm);
+        // Shift+arrows select text.
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
+            let movement = match key.code {
+                KeyCode::Left => Some(CursorMove::Left),
+                KeyCode::Right => Some(CursorMove::Right),
+                KeyCode::Up => Some(CursorMove::Up),
+                KeyCode::Down => Some(CursorMove::Down),
+                KeyCode::Home => Some(CursorMove::LineStart),
+                KeyCode::End => Some(CursorMove::LineEnd),
+                _ => None,
+            };
+            if let Some(movement) = movement {
+                return Some(Action::ExtendSelection(movement));
+            }
         }
 
         match key.code {
-            // Escape exits editing mode and returns to normal.
-            KeyCode::Esc => Some(Action::CloseMergeDialog), // re-used; App interprets contextually
-            // Tab switches between input fields (not phase navigation).
-            KeyCode::Tab | KeyCode::BackTab => Some(Action::SwitchInputField),
-            // Enter inserts newline (multi-line) or navigates (single-line).
-            // The component decides what to do based on which field is focused.
-            KeyCode::Enter => Some(Action::NewlineInput),
             // Arrow up/down scroll / navigate.
             KeyCode::Up => Some(Action::ScrollUp),
             KeyCode::Down => Some(Action::ScrollDown),
+            KeyCode::Left => Some(Action::MoveCursor(CursorMove::Left)),
+            KeyCode::Right => Some(Action::MoveCursor(CursorMove::Right)),
+            KeyCode::Home => Some(Action::MoveCursor(CursorMove::LineStart)),
+            KeyCode::End => Some(Action::MoveCursor(CursorMove::LineEnd)),
             // Backspace deletes.
             KeyCode::Backspace => Some(Action::BackspaceInput),
             // Any printable character is forwarded.
@@ -155,27 +197,22 @@ impl EventHandler {
         }
     }
 
-    /// Key mapping in normal mode — global shortcuts.
-    fn map_key_normal(&self, key: KeyEvent) -> Option<Action> {
-        match key.code {
-            KeyCode::Char('q') => Some(Action::Quit),
-            KeyCode::Char('?') => Some(Action::ToggleHelp),
-            KeyCode::Right | KeyCode::Tab => Some(Action::NextPhase),
-            KeyCode::Left | KeyCode::BackTab => Some(Action::PrevPhase),
-            KeyCode::Up | KeyCode::Char('k') => Some(Action::ScrollUp),
-            KeyCode::Down | KeyCode::Char('j') => Some(Action::ScrollDown),
-            KeyCode::Enter => Some(Action::Confirm),
-            KeyCode::Char('m') => Some(Action::OpenMergeDialog),
-            KeyCode::Esc => Some(Action::CloseMergeDialog),
-
-            // Number keys for direct phase navigation.
-            KeyCode::Char('1') => Some(Action::GoToPhase(crate::action::Phase::ProjectIntake)),
-            KeyCode::Char('2') => Some(Action::GoToPhase(crate::action::Phase::ResearchDiscovery)),
-            KeyCode::Char('3') => Some(Action::GoToPhase(crate::action::Phase::TechniqueSelection)),
-            KeyCode::Char('4') => Some(Action::GoToPhase(crate::action::Phase::VariantGeneration)),
-            KeyCode::Char('5') => Some(Action::GoToPhase(crate::action::Phase::Benchmarking)),
-
-            _ => None,
+    /// Key mapping in normal mode — the configurable keymap, plus the vim
+    /// `gg` sequence when that profile is selected.
+    fn map_key_normal(&mut self, key: KeyEvent) -> Option<Action> {
+        let pending_g = std::mem::take(&mut self.pending_g);
+        if self.keymap.g_prefix()
+            && key.code == KeyCode::Char('g')
+            && !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            if !pending_g {
+                self.pending_g = true;
+                return None;
+            }
+            return self.keymap.sequence("gg").map(|b| b.action());
         }
+        self.keymap.lookup(&key).map(|b| b.action())
     }
 }
@@ -0,0 +1,218 @@
+//! UI text in the user's language.
+//!
+//! Everything the TUI shows comes from the message catalogs in
+//! `crates/uniq-tui/locales/`, one TOML file per locale, looked up by
+//! dotted key with `t!`. English is complete; other locales fall back to
+//! it for any key they don't have. Values may name arguments in braces,
+//! like `Rated {stars}/5`.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::sync::{Mutex, OnceLock, RwLock};
+
+/// A language the UI can be shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    De,
+    Es,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::De, Locale::Es];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Es => "es",
+        }
+    }
+
+    /// The locale for a code like `de`, `es-MX` or `de_DE.UTF-8`.
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        Self::ALL.into_iter().find(|l| l.code() == language)
+    }
+
+    /// The configured locale, else the one from `LC_ALL`, `LC_MESSAGES`
+    /// or `LANG`, else English.
+    pub fn detect(configured: Option<&str>) -> Locale {
+        let from_env = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty());
+        configured
+            .map(str::to_string)
+            .or(from_env)
+            .and_then(|code| Self::from_code(&code))
+            .unwrap_or(Locale::En)
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.toml"),
+            Locale::De => include_str!("../locales/de.toml"),
+            Locale::Es => include_str!("../locales/es.toml"),
+        }
+    }
+
+    /// This locale's messages, keyed like `help.title`.
+    fn catalog(self) -> &'static HashMap<String, String> {
+        static CATALOGS: [OnceLock<HashMap<String, String>>; 3] =
+            [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+        CATALOGS[self as usize].get_or_init(|| {
+            let table: toml::Table = self
+                .source()
+                .parse()
+                .unwrap_or_else(|e| panic!("locales/{}.toml is invalid: {}", self.code(), e));
+            let mut messages = HashMap::new();
+            flatten(&table, "", &mut messages);
+            messages
+        })
+    }
+}
+
+fn flatten(table: &toml::Table, prefix: &str, out: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let key = format!("{}{}", prefix, key);
+        match value {
+            toml::Value::String(text) => {
+                out.insert(key, text.clone());
+            }
+            toml::Value::Table(nested) => flatten(nested, &format!("{}.", key), out),
+            _ => {}
+        }
+    }
+}
+
+app_global! {
+    /// Locale in use, set at startup.
+    fn current_locale: RwLock<Locale> = RwLock::new(Locale::En);
+}
+
+pub fn set_locale(locale: Locale) {
+    current_locale(|current| {
+        if let Ok(mut current) = current.write() {
+            *current = locale;
+        }
+    });
+}
+
+pub fn locale() -> Locale {
+    current_locale(|current| current.read().map(|l| *l).unwrap_or(Locale::En))
+}
+
+/// The message for `key` in the current locale. Unknown keys show as
+/// themselves.
+pub fn text(key: &str) -> &'static str {
+    let found = locale()
+        .catalog()
+        .get(key)
+        .or_else(|| Locale::En.catalog().get(key));
+    match found {
+        Some(message) => message,
+        None => {
+            debug_assert!(false, "no message for {}", key);
+            intern(key)
+        }
+    }
+}
+
+/// `key` as a `'static` string, allocated the first time it's missing and
+/// reused after, so a missing key redrawn every frame doesn't leak.
+fn intern(key: &str) -> &'static str {
+    static MISSING: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut missing = MISSING
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match missing.get(key) {
+        Some(interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(key.to_string().into_boxed_str());
+            missing.insert(interned);
+            interned
+        }
+    }
+}
+
+/// The message for `key` with each `{name}` replaced by its argument.
+pub fn format(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = text(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
+
+/// Look up UI text: `t!("help.title")` or `t!("status.rated", stars = 4)`.
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::text($key)
+    };
+    // The arguments aren't `Send`, so they're dropped before the message
+    // is used, which may be across an `.await`.
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let message = $crate::i18n::format(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        );
+        message
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `{name}` arguments in `message`, sorted.
+    fn arguments(message: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn translations_match_english() {
+        let english = Locale::En.catalog();
+        for locale in Locale::ALL {
+            for (key, message) in locale.catalog() {
+                let Some(source) = english.get(key) else {
+                    panic!("{}: {} is not an English key", locale.code(), key);
+                };
+                assert_eq!(
+                    arguments(message),
+                    arguments(source),
+                    "{}: arguments of {}",
+                    locale.code(),
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn missing_keys_are_allocated_once() {
+        let first = intern("nowhere.to_be_found");
+        let second = intern(&String::from("nowhere.to_be_found"));
+        assert_eq!(first, "nowhere.to_be_found");
+        assert!(std::ptr::eq(first, second));
+    }
+
+    #[test]
+    fn codes_parse_from_environment_values() {
+        assert_eq!(Locale::from_code("de_DE.UTF-8"), Some(Locale::De));
+        assert_eq!(Locale::from_code("es-MX"), Some(Locale::Es));
+        assert_eq!(Locale::from_code("C"), None);
+        assert_eq!(Locale::detect(Some("es")), Locale::Es);
+    }
+}
@@ -0,0 +1,572 @@
+//! Key bindings — the default chord for every normal-mode action, with
+//! overrides from the `[keys]` config section.
+//!
+//! Chords are written like `q`, `ctrl+t`, `shift+tab`, `pagedown` or `f2`.
+//! Overriding an action replaces all of its default chords, and a chord
+//! claimed by an override is taken away from whichever action had it.
+//! Overrides apply over the vim layer as well as the defaults.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::warn;
+
+use crate::action::{Action, Phase, PromptKind};
+use uniq_core::config::{KeyBinding, KeymapProfile};
+use uniq_core::export::ExportFormat;
+use uniq_core::report::ReportFormat;
+
+/// A key plus modifiers, compared the way crossterm reports them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is already part of an uppercase character or BackTab, and
+        // terminals disagree on whether they report it.
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+}
+
+impl From<&KeyEvent> for KeyChord {
+    fn from(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // "+" and "ctrl++" bind the plus key itself.
+        let (mods, key) = match s.strip_suffix('+') {
+            Some(rest) if rest.is_empty() || rest.ends_with('+') => {
+                (rest.trim_end_matches('+'), "+")
+            }
+            _ => match s.rsplit_once('+') {
+                Some((mods, key)) => (mods, key),
+                None => ("", s),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mods.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}' in '{}'", other, s)),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                other => match other.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}' in '{}'", key, s)),
+                },
+            },
+        };
+
+        // "shift+a" means 'A'.
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "shift+tab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Insert => write!(f, "insert"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// An action that can be bound, with its current chords.
+#[derive(Debug, Clone)]
+pub struct Binding {
+    /// Name used in the `[keys]` config section.
+    pub name: &'static str,
+    pub description: &'static str,
+    pub keys: Vec<KeyChord>,
+    /// Also active while a text field has focus.
+    pub global: bool,
+    action: fn() -> Action,
+}
+
+impl Binding {
+    pub fn action(&self) -> Action {
+        (self.action)()
+    }
+}
+
+/// Default bindings: (name, chords, action). Descriptions are the `keys`
+/// messages of the same name.
+#[allow(clippy::type_complexity)]
+const DEFAULTS: &[(&str, &[&str], fn() -> Action)] = &[
+    ("quit", &["q"], || Action::Quit),
+    ("help", &["?"], || Action::ToggleHelp),
+    ("next_phase", &["right"], || Action::NextPhase),
+    ("prev_phase", &["left"], || Action::PrevPhase),
+    ("focus_next", &["tab"], || Action::FocusNext),
+    ("focus_prev", &["shift+tab"], || Action::FocusPrev),
+    ("scroll_up", &["up", "k"], || Action::ScrollUp),
+    ("scroll_down", &["down", "j"], || Action::ScrollDown),
+    ("scroll_top", &["home", "g"], || Action::ScrollTop),
+    ("scroll_bottom", &["end", "G"], || Action::ScrollBottom),
+    ("page_up", &["pageup"], || Action::PageUp),
+    ("page_down", &["pagedown"], || Action::PageDown),
+    ("grow_detail", &[">", "ctrl+up"], || Action::ResizeDetail(1)),
+    ("shrink_detail", &["<", "ctrl+down"], || {
+        Action::ResizeDetail(-1)
+    }),
+    ("confirm", &["enter"], || Action::Confirm),
+    ("close", &["esc"], || Action::Escape),
+    ("phase_1", &["1"], || {
+        Action::GoToPhase(Phase::ProjectIntake)
+    }),
+    ("phase_2", &["2"], || {
+        Action::GoToPhase(Phase::ResearchDiscovery)
+    }),
+    ("phase_3", &["3"], || {
+        Action::GoToPhase(Phase::TechniqueSelection)
+    }),
+    ("phase_4", &["4"], || {
+        Action::GoToPhase(Phase::VariantGeneration)
+    }),
+    ("phase_5", &["5"], || Action::GoToPhase(Phase::Benchmarking)),
+    ("cycle_theme", &["ctrl+t"], || Action::CycleTheme),
+    ("status_log", &["I"], || Action::ToggleStatusLog),
+    ("timeline", &["v"], || Action::ToggleTimeline),
+    ("usage", &["$"], || Action::ToggleUsage),
+    ("external_editor", &["ctrl+e"], || {
+        Action::EditDescriptionExternally
+    }),
+    ("dir_tree", &["ctrl+o"], || Action::ToggleDirTree),
+    ("copy", &["Y"], || Action::Copy),
+    ("sidebar", &["b"], || Action::ToggleSidebar),
+    ("command_palette", &["ctrl+p"], || {
+        Action::OpenCommandPalette
+    }),
+    ("search", &[], || Action::OpenPrompt(PromptKind::Search)),
+    ("command", &[], || Action::OpenPrompt(PromptKind::Command)),
+    ("reextract", &["e"], || Action::OpenReExtractPrompt),
+    ("select_all", &["a"], || Action::SelectAllTechniques),
+    ("select_none", &["n"], || Action::SelectNoTechniques),
+    ("invert_selection", &["i"], || {
+        Action::InvertTechniqueSelection
+    }),
+    ("select_top", &["t"], || Action::OpenSelectTopPrompt),
+    ("complexity_filter", &["f"], || {
+        Action::CycleComplexityFilter
+    }),
+    ("known_deps_filter", &["d"], || {
+        Action::ToggleKnownDepsFilter
+    }),
+    ("group_by_paper", &["ctrl+g"], || Action::ToggleGroupByPaper),
+    ("consolidate", &["u"], || Action::ConsolidateDuplicates),
+    ("drop_duplicates", &["U"], || Action::DropDuplicates),
+    ("export_markdown", &["o"], || {
+        Action::ExportTechniques(ExportFormat::Markdown)
+    }),
+    ("export_json", &["O"], || {
+        Action::ExportTechniques(ExportFormat::Json)
+    }),
+    ("compare", &["c"], || Action::ToggleComparison),
+    ("merge", &["m"], || Action::OpenMergeDialog),
+    ("add_merge_source", &["+"], || Action::AddMergeSource),
+    ("remove_merge_source", &["-"], || Action::RemoveMergeSource),
+    ("merge_components", &["A"], || Action::ToggleMergeComponents),
+    ("conflicts", &["C"], || Action::OpenConflictViewer),
+    ("reconcile", &["R"], || Action::ReconcileConflictHunk),
+    ("cancel", &["x"], || Action::CancelVariantGeneration),
+    ("regenerate", &["r"], || Action::OpenRegeneratePrompt),
+    ("rename", &["N"], || Action::OpenRenamePrompt),
+    ("delete", &["D"], || Action::DeleteVariant),
+    ("export_patches", &["p"], || Action::ExportVariantPatches),
+    ("approve_plan", &["y"], || Action::ApprovePlan),
+    ("promote", &["P"], || Action::PromoteVariant),
+    ("timeout", &["T"], || Action::OpenTimeoutPrompt),
+    ("history", &["H"], || Action::ToggleBenchmarkHistory),
+    ("judge_explanation", &["E"], || {
+        Action::ToggleJudgeExplanation
+    }),
+    ("leaderboard", &["L"], || Action::ToggleLeaderboard),
+    ("suggested_merge", &["M"], || Action::TrySuggestedMerge),
+    ("sort", &["s"], || Action::CycleSort),
+    ("reverse_sort", &["S"], || Action::ReverseSort),
+    ("reload_weights", &["W"], || Action::ReloadScoreWeights),
+    ("export_report", &["X"], || {
+        Action::ExportReport(ReportFormat::Markdown)
+    }),
+    ("export_report_html", &[], || {
+        Action::ExportReport(ReportFormat::Html)
+    }),
+];
+
+/// Keys the vim profile layers over the defaults: (keys, binding name).
+/// `gg` is a two-key sequence; the event handler remembers the first `g`.
+const VIM_KEYS: &[(&str, &str)] = &[
+    ("h", "prev_phase"),
+    ("l", "next_phase"),
+    ("gg", "scroll_top"),
+    ("G", "scroll_bottom"),
+    ("/", "search"),
+    (":", "command"),
+];
+
+/// Bindings that stay active while typing.
+const GLOBAL: &[&str] = &[
+    "cycle_theme",
+    "command_palette",
+    "external_editor",
+    "dir_tree",
+];
+
+/// Keys that act on a text field rather than type into it: (name, chords,
+/// action). These can't be rebound; hints show the first chord.
+#[allow(clippy::type_complexity)]
+const EDITING: &[(&str, &[&str], fn() -> Action)] = &[
+    ("submit", &["ctrl+s", "ctrl+enter", "alt+enter"], || {
+        Action::SubmitForm
+    }),
+    ("paste", &["ctrl+v"], || Action::PasteInput),
+    ("cut", &["ctrl+x"], || Action::CutSelection),
+    ("copy", &["ctrl+y"], || Action::CopySelection),
+    ("delete_word", &["ctrl+w"], || Action::DeleteWord),
+    ("switch_field", &["tab", "shift+tab"], || {
+        Action::SwitchInputField
+    }),
+    // A newline in the description, the next field from the path.
+    ("newline", &["enter", "shift+enter"], || {
+        Action::NewlineInput
+    }),
+    ("close", &["esc"], || Action::Escape),
+];
+
+/// The action `key` has in a text field, if it isn't typed.
+pub fn editing_action(key: &KeyEvent) -> Option<Action> {
+    let chord = KeyChord::from(key);
+    EDITING
+        .iter()
+        .find(|(_, keys, _)| keys.iter().any(|k| k.parse::<KeyChord>() == Ok(chord)))
+        .map(|(_, _, action)| action())
+}
+
+/// The key hints show for text-field action `name`.
+pub fn editing_key(name: &str) -> String {
+    EDITING
+        .iter()
+        .find(|(n, _, _)| *n == name)
+        .and_then(|(_, keys, _)| keys.first()?.parse::<KeyChord>().ok())
+        .map(|chord| chord.to_string())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    /// Layout layered over the defaults.
+    pub profile: KeymapProfile,
+    bindings: Vec<Binding>,
+    /// Whether `g` starts the vim `gg` sequence rather than being looked
+    /// up on its own: only under the vim profile, and not once `[keys]`
+    /// binds `g` to something.
+    g_prefix: bool,
+}
+
+impl Keymap {
+    /// Default bindings under the `profile` layer, with `overrides` (from
+    /// `[keys]`) applied last.
+    pub fn new(profile: KeymapProfile, overrides: &HashMap<String, KeyBinding>) -> Self {
+        let mut bindings: Vec<Binding> = DEFAULTS
+            .iter()
+            .map(|&(name, keys, action)| Binding {
+                name,
+                description: t!(&format!("keys.{}", name)),
+                keys: keys.iter().filter_map(|k| k.parse().ok()).collect(),
+                global: GLOBAL.contains(&name),
+                action,
+            })
+            .collect();
+
+        let g = KeyChord::new(KeyCode::Char('g'), KeyModifiers::NONE);
+        let mut g_prefix = profile == KeymapProfile::Vim;
+        if g_prefix {
+            for binding in &mut bindings {
+                binding.keys.retain(|&k| k != g);
+            }
+            // Sequences like `gg` aren't chords; the event handler matches them.
+            for (keys, name) in VIM_KEYS {
+                let Ok(chord) = keys.parse::<KeyChord>() else {
+                    continue;
+                };
+                for binding in &mut bindings {
+                    binding.keys.retain(|&k| k != chord);
+                    if binding.name == *name {
+                        binding.keys.push(chord);
+                    }
+                }
+            }
+        }
+
+        let mut overrides: Vec<_> = overrides.iter().collect();
+        overrides.sort_by_key(|(name, _)| name.as_str());
+        for (name, binding) in overrides {
+            let Some(index) = bindings.iter().position(|b| b.name == name) else {
+                warn!("Ignoring key binding for unknown action '{}'", name);
+                continue;
+            };
+            let keys: Vec<KeyChord> = binding
+                .chords()
+                .iter()
+                .filter_map(|chord| match chord.parse() {
+                    Ok(chord) => Some(chord),
+                    Err(e) => {
+                        warn!("Ignoring key binding for '{}': {}", name, e);
+                        None
+                    }
+                })
+                .collect();
+            g_prefix &= !keys.contains(&g);
+            for (i, other) in bindings.iter_mut().enumerate() {
+                if i != index {
+                    other.keys.retain(|k| !keys.contains(k));
+                }
+            }
+            bindings[index].keys = keys;
+        }
+
+        Self {
+            profile,
+            bindings,
+            g_prefix,
+        }
+    }
+
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Binding> {
+        self.bindings.iter().find(|b| b.name == name)
+    }
+
+    /// Whether `g` waits for a second `g` (see [`Keymap::sequence`]).
+    pub fn g_prefix(&self) -> bool {
+        self.g_prefix
+    }
+
+    /// The binding a vim key sequence like `gg` triggers.
+    pub fn sequence(&self, keys: &str) -> Option<&Binding> {
+        if !self.g_prefix {
+            return None;
+        }
+        let (_, name) = VIM_KEYS.iter().find(|(k, _)| *k == keys)?;
+        self.get(name)
+    }
+
+    /// Every chord that triggers `name`, including vim sequences.
+    pub fn key_labels(&self, name: &str) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .get(name)
+            .map(|b| b.keys.iter().map(ToString::to_string).collect())
+            .unwrap_or_default();
+        // Ctrl+C is hard-wired in the event handler.
+        if name == "quit" {
+            labels.push("ctrl+c".to_string());
+        }
+        if self.g_prefix {
+            labels.extend(
+                VIM_KEYS
+                    .iter()
+                    .filter(|(keys, binding)| *binding == name && keys.parse::<KeyChord>().is_err())
+                    .map(|(keys, _)| keys.to_string()),
+            );
+        }
+        labels
+    }
+
+    /// The binding `key` triggers, if any.
+    pub fn lookup(&self, key: &KeyEvent) -> Option<&Binding> {
+        let chord = KeyChord::from(key);
+        self.bindings.iter().find(|b| b.keys.contains(&chord))
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(KeymapProfile::default(), &HashMap::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn bound(keymap: &Keymap, c: char) -> Option<&'static str> {
+        keymap.lookup(&key(c)).map(|b| b.name)
+    }
+
+    fn chord(code: KeyCode, modifiers: KeyModifiers) -> Result<KeyChord, String> {
+        Ok(KeyChord::new(code, modifiers))
+    }
+
+    #[test]
+    fn chords_parse_from_config_strings() {
+        let none = KeyModifiers::NONE;
+        assert_eq!("q".parse(), chord(KeyCode::Char('q'), none));
+        assert_eq!(" Q ".parse(), chord(KeyCode::Char('Q'), none));
+        assert_eq!("shift+q".parse(), chord(KeyCode::Char('Q'), none));
+        assert_eq!(
+            "ctrl+t".parse(),
+            chord(KeyCode::Char('t'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            "Control+Alt+Enter".parse(),
+            chord(KeyCode::Enter, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(
+            "meta+x".parse(),
+            chord(KeyCode::Char('x'), KeyModifiers::ALT)
+        );
+        assert_eq!("shift+tab".parse(), chord(KeyCode::BackTab, none));
+        assert_eq!("backtab".parse(), chord(KeyCode::BackTab, none));
+        assert_eq!("space".parse(), chord(KeyCode::Char(' '), none));
+        assert_eq!("PageDown".parse(), chord(KeyCode::PageDown, none));
+        assert_eq!("f12".parse(), chord(KeyCode::F(12), none));
+        assert_eq!("+".parse(), chord(KeyCode::Char('+'), none));
+        assert_eq!(
+            "ctrl++".parse(),
+            chord(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+
+        assert!("hyper+x".parse::<KeyChord>().is_err());
+        assert!("f13".parse::<KeyChord>().is_err());
+        assert!("gg".parse::<KeyChord>().is_err());
+        assert!("".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn chords_display_as_they_parse() {
+        for text in [
+            "q",
+            "ctrl+t",
+            "ctrl+alt+enter",
+            "shift+tab",
+            "space",
+            "f2",
+            "ctrl++",
+        ] {
+            let chord: KeyChord = text.parse().unwrap();
+            assert_eq!(chord.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn an_override_takes_its_keys_from_other_actions() {
+        let overrides: HashMap<String, KeyBinding> =
+            toml::from_str("search = [\"q\", \"ctrl+t\"]\nnot_an_action = \"x\"").unwrap();
+        let keymap = Keymap::new(KeymapProfile::Default, &overrides);
+        assert_eq!(bound(&keymap, 'q'), Some("search"));
+        assert!(keymap.get("quit").unwrap().keys.is_empty());
+        assert!(keymap.get("cycle_theme").unwrap().keys.is_empty());
+        assert_eq!(keymap.key_labels("quit"), ["ctrl+c"]);
+        // An unknown action is ignored, and takes nothing.
+        assert!(keymap.get("not_an_action").is_none());
+        assert_eq!(bound(&keymap, 'x'), Some("cancel"));
+
+        // Overriding replaces an action's defaults rather than adding to them.
+        let overrides: HashMap<String, KeyBinding> = toml::from_str("next_phase = \"n\"").unwrap();
+        let keymap = Keymap::new(KeymapProfile::Default, &overrides);
+        assert_eq!(keymap.key_labels("next_phase"), ["n"]);
+    }
+
+    #[test]
+    fn user_keys_apply_over_the_vim_layer() {
+        let vim = Keymap::new(KeymapProfile::Vim, &HashMap::new());
+        assert_eq!(bound(&vim, 'h'), Some("prev_phase"));
+        assert_eq!(bound(&vim, 'G'), Some("scroll_bottom"));
+        assert!(vim.g_prefix());
+        assert_eq!(bound(&vim, 'g'), None);
+        assert_eq!(vim.sequence("gg").map(|b| b.name), Some("scroll_top"));
+        assert_eq!(vim.key_labels("scroll_top"), ["home", "gg"]);
+
+        let overrides: HashMap<String, KeyBinding> =
+            toml::from_str("help = \"h\"\nscroll_bottom = [\"g\", \"end\"]").unwrap();
+        let vim = Keymap::new(KeymapProfile::Vim, &overrides);
+        assert_eq!(bound(&vim, 'h'), Some("help"));
+        assert!(!vim.key_labels("prev_phase").contains(&"h".to_string()));
+        assert_eq!(bound(&vim, 'g'), Some("scroll_bottom"));
+        assert!(!vim.g_prefix());
+        assert!(vim.sequence("gg").is_none());
+
+        let default = Keymap::default();
+        assert_eq!(bound(&default, 'g'), Some("scroll_top"));
+        assert!(!default.g_prefix());
+    }
+}
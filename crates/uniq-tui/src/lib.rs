@@ -1,7 +1,38 @@
+/// A function giving access to one value shared by the whole app: a
+/// `static`, except in tests, where each test thread gets its own so that
+/// tests switching locales or themes don't leak into each other.
+macro_rules! app_global {
+    ($(#[$attr:meta])* fn $name:ident: $ty:ty = $init:expr;) => {
+        $(#[$attr])*
+        #[cfg(not(test))]
+        fn $name<R>(f: impl FnOnce(&$ty) -> R) -> R {
+            static VALUE: $ty = $init;
+            f(&VALUE)
+        }
+
+        $(#[$attr])*
+        #[cfg(test)]
+        fn $name<R>(f: impl FnOnce(&$ty) -> R) -> R {
+            thread_local! {
+                static VALUE: $ty = const { $init };
+            }
+            VALUE.with(f)
+        }
+    };
+}
+
+#[macro_use]
+pub mod i18n;
+
 pub mod action;
 pub mod app;
+pub mod channel;
+pub mod clipboard;
 pub mod components;
 pub mod event;
+pub mod keymap;
+pub mod metrics;
+pub mod terminal;
 pub mod theme;
 
 pub use app::App;
@@ -0,0 +1,284 @@
+//! Feeds the opt-in local usage metrics from the actions the app handles,
+//! saving after every finished step so nothing is lost if the app is
+//! killed. Saves are written on a thread of their own, off the UI thread.
+
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Instant;
+
+use tracing::warn;
+use uniq_core::benchmark::BenchmarkStage;
+use uniq_core::metrics::{Step, UsageMetrics};
+
+use crate::action::{Action, Phase};
+
+pub struct MetricsRecorder {
+    metrics: UsageMetrics,
+    /// Writes saved snapshots; `None` when nothing is saved.
+    writer: Option<Writer>,
+    /// When each running step started. Variant generations and merges are
+    /// keyed by the id of the variant they make.
+    started: HashMap<String, Instant>,
+    /// Papers found so far by the current search query.
+    query_papers: Option<usize>,
+    /// Techniques and failures of the current extraction.
+    extracted: usize,
+    extraction_errors: usize,
+    /// Whether any branch of the current benchmark run failed.
+    benchmark_failed: bool,
+}
+
+/// The thread that saves the metrics, and the channel feeding it.
+struct Writer {
+    snapshots: mpsc::Sender<UsageMetrics>,
+    thread: JoinHandle<()>,
+}
+
+impl Writer {
+    fn spawn() -> Self {
+        let (snapshots, rx) = mpsc::channel::<UsageMetrics>();
+        let thread = std::thread::spawn(move || {
+            while let Ok(metrics) = rx.recv() {
+                // Only the latest of the snapshots waiting needs writing.
+                let mut metrics = rx.try_iter().last().unwrap_or(metrics);
+                if let Err(e) = metrics.save() {
+                    warn!("Could not save usage metrics: {}", e);
+                }
+            }
+        });
+        Self { snapshots, thread }
+    }
+}
+
+impl MetricsRecorder {
+    /// Pick up the metrics recorded so far, or start new ones.
+    pub fn load() -> Self {
+        let metrics = UsageMetrics::load().unwrap_or_else(|e| {
+            warn!("Could not load usage metrics, starting over: {}", e);
+            UsageMetrics::new()
+        });
+        let mut recorder = Self::new(metrics);
+        recorder.writer = Some(Writer::spawn());
+        recorder
+    }
+
+    /// Record on top of `metrics`, without saving.
+    fn new(metrics: UsageMetrics) -> Self {
+        Self {
+            metrics,
+            writer: None,
+            started: HashMap::new(),
+            query_papers: None,
+            extracted: 0,
+            extraction_errors: 0,
+            benchmark_failed: false,
+        }
+    }
+
+    fn start(&mut self, key: &str) {
+        self.started.insert(key.to_string(), Instant::now());
+    }
+
+    /// Record the end of the step started under `key`, if it was started.
+    fn finish(&mut self, key: &str, step: Step, succeeded: bool) {
+        if let Some(start) = self.started.remove(key) {
+            self.metrics.record(step, start.elapsed(), succeeded);
+            self.save();
+        }
+    }
+
+    fn finish_query(&mut self) {
+        if let Some(papers) = self.query_papers.take() {
+            self.metrics.record_query(papers);
+        }
+    }
+
+    fn save(&mut self) {
+        if let Some(ref writer) = self.writer {
+            let _ = writer.snapshots.send(self.metrics.clone());
+        }
+    }
+
+    pub fn observe(&mut self, action: &Action) {
+        match action {
+            Action::SubmitProject { .. } => self.start("analysis"),
+            Action::ProjectAnalyzed(_) => self.finish("analysis", Step::Analysis, true),
+            Action::ProjectAnalysisFailed(_) => self.finish("analysis", Step::Analysis, false),
+            Action::SearchQueryStarted { .. } => {
+                self.finish_query();
+                self.query_papers = Some(0);
+                if !self.started.contains_key("search") {
+                    self.start("search");
+                }
+            }
+            Action::PapersFound(papers) => {
+                if let Some(ref mut found) = self.query_papers {
+                    *found += papers.len();
+                }
+            }
+            Action::ResearchComplete => {
+                self.finish_query();
+                self.finish("search", Step::Search, true);
+            }
+            Action::ResearchFailed(_) => {
+                // A failed query found nothing worth counting.
+                self.query_papers = None;
+                self.finish("search", Step::Search, false);
+            }
+            Action::StartExtraction(_) => {
+                self.extracted = 0;
+                self.extraction_errors = 0;
+                self.start("extraction");
+            }
+            Action::TechniqueExtracted(_) => self.extracted += 1,
+            Action::TechniqueExtractionFailed { .. } => self.extraction_errors += 1,
+            Action::ExtractionComplete => {
+                let succeeded = self.extracted > 0 || self.extraction_errors == 0;
+                self.finish("extraction", Step::Extraction, succeeded);
+            }
+            Action::VariantGenerationStarted { variant_id } => self.start(variant_id),
+            Action::VariantGenerated(variant) => self.finish(&variant.id.0, Step::Generation, true),
+            Action::VariantGenerationFailed { variant_id, .. } => {
+                self.finish(variant_id, Step::Generation, false)
+            }
+            Action::MergeComplete(variant) => self.finish(&variant.id.0, Step::Merge, true),
+            Action::MergeFailed { variant_id, .. } => self.finish(variant_id, Step::Merge, false),
+            Action::StartBenchmark => {
+                self.benchmark_failed = false;
+                self.start("benchmark");
+            }
+            Action::BenchmarkProgress {
+                stage: BenchmarkStage::Failed,
+                ..
+            } => self.benchmark_failed = true,
+            Action::BenchmarkComplete => {
+                let succeeded = !self.benchmark_failed;
+                self.finish("benchmark", Step::Benchmark, succeeded);
+            }
+            // Cancelled steps neither succeeded nor failed, so they aren't
+            // recorded at all.
+            Action::CancelGeneration { variant_id, .. } => {
+                self.started.remove(variant_id);
+            }
+            Action::BenchmarkCancelled => {
+                self.started.remove("benchmark");
+            }
+            Action::PhaseTaskCancelled(phase) => {
+                let key = match phase {
+                    Phase::ProjectIntake => "analysis",
+                    Phase::ResearchDiscovery => {
+                        self.query_papers = None;
+                        "search"
+                    }
+                    Phase::TechniqueSelection => "extraction",
+                    Phase::VariantGeneration | Phase::Benchmarking => return,
+                };
+                self.started.remove(key);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Drop for MetricsRecorder {
+    /// Wait for the last snapshot to be written, so the final step of a run
+    /// isn't lost on quitting.
+    fn drop(&mut self) {
+        if let Some(Writer { snapshots, thread }) = self.writer.take() {
+            drop(snapshots);
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uniq_core::merge::{BlendRatio, MergeSpec};
+    use uniq_core::metrics::StepStats;
+    use uniq_core::variant::{Variant, VariantId};
+
+    fn merge(index: usize) -> Variant {
+        let spec = MergeSpec::new(
+            VariantId::new("variant-1"),
+            VariantId::new("variant-2"),
+            BlendRatio::Half,
+            BlendRatio::Half,
+        );
+        Variant::from_merge(index, spec, "Hybrid".to_string())
+    }
+
+    fn stats(recorder: &MetricsRecorder, step: Step) -> StepStats {
+        recorder
+            .metrics
+            .steps
+            .get(&step)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn started(id: &str) -> Action {
+        Action::VariantGenerationStarted {
+            variant_id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn merges_are_matched_to_their_own_start() {
+        let mut recorder = MetricsRecorder::new(UsageMetrics::new());
+        let (first, second) = (merge(1), merge(2));
+        recorder.observe(&started(&first.id.0));
+        recorder.observe(&started(&second.id.0));
+        recorder.observe(&Action::MergeFailed {
+            variant_id: second.id.0.clone(),
+            error: "boom".to_string(),
+        });
+        recorder.observe(&Action::MergeComplete(Box::new(first)));
+
+        let merges = stats(&recorder, Step::Merge);
+        assert_eq!((merges.succeeded, merges.failed), (1, 1));
+        assert_eq!(stats(&recorder, Step::Generation).runs(), 0);
+        assert!(recorder.started.is_empty());
+    }
+
+    #[test]
+    fn a_benchmark_with_a_failed_branch_failed() {
+        let mut recorder = MetricsRecorder::new(UsageMetrics::new());
+        recorder.observe(&Action::StartBenchmark);
+        recorder.observe(&Action::BenchmarkProgress {
+            branch: "uniq/variant-1".to_string(),
+            stage: BenchmarkStage::Failed,
+        });
+        recorder.observe(&Action::BenchmarkComplete);
+        recorder.observe(&Action::StartBenchmark);
+        recorder.observe(&Action::BenchmarkComplete);
+
+        let benchmarks = stats(&recorder, Step::Benchmark);
+        assert_eq!((benchmarks.succeeded, benchmarks.failed), (1, 1));
+    }
+
+    #[test]
+    fn cancelled_steps_are_not_recorded() {
+        let mut recorder = MetricsRecorder::new(UsageMetrics::new());
+        recorder.observe(&started("variant-1"));
+        recorder.observe(&Action::CancelGeneration {
+            variant_id: "variant-1".to_string(),
+            branch_name: "uniq/variant-1".to_string(),
+        });
+        recorder.observe(&Action::VariantGenerationFailed {
+            variant_id: "variant-1".to_string(),
+            error: "cancelled".to_string(),
+        });
+        recorder.observe(&Action::StartBenchmark);
+        recorder.observe(&Action::BenchmarkCancelled);
+        recorder.observe(&Action::SubmitProject {
+            path: "/src/shop".to_string(),
+            description: String::new(),
+        });
+        recorder.observe(&Action::PhaseTaskCancelled(Phase::ProjectIntake));
+
+        assert!(recorder.metrics.steps.is_empty());
+        assert!(recorder.started.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+//! Terminal window title and tab progress indicator, so a long run can be
+//! followed from the tab or taskbar while the window is in the background.
+//!
+//! Progress uses the OSC 9;4 sequence understood by Windows Terminal,
+//! WezTerm, Ghostty, ConEmu and recent iTerm2; other terminals ignore it.
+
+use std::io::{self, Write};
+
+use crossterm::queue;
+use crossterm::terminal::SetTitle;
+
+/// What the tab's progress indicator shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Progress {
+    #[default]
+    Hidden,
+    /// Busy, with no way to tell how far along.
+    Indeterminate,
+    /// Percent complete, 0 to 100.
+    Percent(u8),
+}
+
+impl Progress {
+    /// `done` of `total` as a percentage, or indeterminate if nothing is
+    /// counted yet.
+    pub fn fraction(done: usize, total: usize) -> Self {
+        match (done.min(total) * 100).checked_div(total) {
+            Some(percent) => Progress::Percent(percent as u8),
+            None => Progress::Indeterminate,
+        }
+    }
+
+    fn sequence(self) -> String {
+        let (state, percent) = match self {
+            Progress::Hidden => (0, 0),
+            Progress::Percent(percent) => (1, percent.min(100)),
+            Progress::Indeterminate => (3, 0),
+        };
+        format!("\x1b]9;4;{};{}\x07", state, percent)
+    }
+}
+
+/// Keeps the terminal's title and progress indicator in step with the app,
+/// writing only when either changes.
+#[derive(Debug, Default)]
+pub struct TerminalStatus {
+    title: String,
+    progress: Progress,
+    /// Whether to send progress sequences at all.
+    show_progress: bool,
+}
+
+impl TerminalStatus {
+    pub fn new(show_progress: bool) -> Self {
+        Self {
+            show_progress,
+            ..Self::default()
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        out: &mut impl Write,
+        title: &str,
+        progress: Progress,
+    ) -> io::Result<()> {
+        if title != self.title {
+            queue!(out, SetTitle(title))?;
+            self.title = title.to_string();
+        }
+        if self.show_progress && progress != self.progress {
+            write!(out, "{}", progress.sequence())?;
+            self.progress = progress;
+        }
+        out.flush()
+    }
+
+    /// Take the progress indicator down, e.g. before exiting.
+    pub fn clear_progress(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.show_progress && self.progress != Progress::Hidden {
+            write!(out, "{}", Progress::Hidden.sequence())?;
+            self.progress = Progress::Hidden;
+        }
+        out.flush()
+    }
+}
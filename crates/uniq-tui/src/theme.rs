@@ -1,59 +1,353 @@
 //! Color scheme and styling for the TUI.
 
+use std::str::FromStr;
+use std::sync::RwLock;
+
 use ratatui::style::{Color, Modifier, Style};
+use uniq_core::config::UiConfig;
+
+/// The colors a theme sets. Every style in `Theme` derives from these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub name: &'static str,
+    pub bg: Color,
+    pub fg: Color,
+    pub fg_dim: Color,
+    pub fg_muted: Color,
+    pub accent: Color,
+    pub accent_secondary: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub border: Color,
+    pub selection_bg: Color,
+}
+
+impl Palette {
+    /// RGB colors for a professional, subdued look inspired by
+    /// Claude Code, lazygit, and similar modern terminal UIs.
+    pub const DARK: Palette = Palette {
+        name: "dark",
+        bg: Color::Reset,
+        fg: Color::Rgb(200, 200, 200),
+        fg_dim: Color::Rgb(100, 100, 100),
+        fg_muted: Color::Rgb(140, 140, 140),
+        accent: Color::Rgb(110, 170, 255),
+        accent_secondary: Color::Rgb(180, 130, 240),
+        success: Color::Rgb(80, 200, 120),
+        warning: Color::Rgb(230, 180, 80),
+        error: Color::Rgb(240, 80, 80),
+        border: Color::Rgb(60, 60, 60),
+        selection_bg: Color::Rgb(40, 40, 60),
+    };
+
+    pub const LIGHT: Palette = Palette {
+        name: "light",
+        bg: Color::Reset,
+        fg: Color::Rgb(40, 40, 40),
+        fg_dim: Color::Rgb(150, 150, 150),
+        fg_muted: Color::Rgb(100, 100, 100),
+        accent: Color::Rgb(20, 100, 210),
+        accent_secondary: Color::Rgb(130, 60, 190),
+        success: Color::Rgb(30, 140, 70),
+        warning: Color::Rgb(170, 110, 0),
+        error: Color::Rgb(200, 40, 40),
+        border: Color::Rgb(200, 200, 200),
+        selection_bg: Color::Rgb(215, 225, 245),
+    };
+
+    pub const SOLARIZED: Palette = Palette {
+        name: "solarized",
+        bg: Color::Reset,
+        fg: Color::Rgb(147, 161, 161),
+        fg_dim: Color::Rgb(88, 110, 117),
+        fg_muted: Color::Rgb(101, 123, 131),
+        accent: Color::Rgb(38, 139, 210),
+        accent_secondary: Color::Rgb(108, 113, 196),
+        success: Color::Rgb(133, 153, 0),
+        warning: Color::Rgb(181, 137, 0),
+        error: Color::Rgb(220, 50, 47),
+        border: Color::Rgb(7, 54, 66),
+        selection_bg: Color::Rgb(7, 54, 66),
+    };
+
+    pub const HIGH_CONTRAST: Palette = Palette {
+        name: "high-contrast",
+        bg: Color::Reset,
+        fg: Color::Rgb(255, 255, 255),
+        fg_dim: Color::Rgb(190, 190, 190),
+        fg_muted: Color::Rgb(220, 220, 220),
+        accent: Color::Rgb(0, 200, 255),
+        accent_secondary: Color::Rgb(255, 120, 255),
+        success: Color::Rgb(0, 255, 100),
+        warning: Color::Rgb(255, 220, 0),
+        error: Color::Rgb(255, 60, 60),
+        border: Color::Rgb(160, 160, 160),
+        selection_bg: Color::Rgb(0, 60, 120),
+    };
+
+    pub const PRESETS: [Palette; 4] = [
+        Palette::DARK,
+        Palette::LIGHT,
+        Palette::SOLARIZED,
+        Palette::HIGH_CONTRAST,
+    ];
+
+    pub fn preset(name: &str) -> Option<Palette> {
+        Self::PRESETS.into_iter().find(|p| p.name == name)
+    }
+
+    /// The configured preset with its color overrides applied.
+    pub fn from_config(config: &UiConfig) -> Result<Palette, String> {
+        let mut palette = Self::preset(&config.theme)
+            .ok_or_else(|| format!("unknown theme '{}'", config.theme))?;
+        for (slot, value) in &config.colors {
+            let color = Color::from_str(value).map_err(|_| format!("invalid color '{}'", value))?;
+            let target = match slot.as_str() {
+                "bg" => &mut palette.bg,
+                "fg" => &mut palette.fg,
+                "fg_dim" => &mut palette.fg_dim,
+                "fg_muted" => &mut palette.fg_muted,
+                "accent" => &mut palette.accent,
+                "accent_secondary" => &mut palette.accent_secondary,
+                "success" => &mut palette.success,
+                "warning" => &mut palette.warning,
+                "error" => &mut palette.error,
+                "border" => &mut palette.border,
+                "selection_bg" => &mut palette.selection_bg,
+                _ => return Err(format!("unknown color slot '{}'", slot)),
+            };
+            *target = color;
+        }
+        Ok(palette)
+    }
+}
+
+/// How many colors the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    /// `NO_COLOR` is set or the terminal is dumb: text attributes only.
+    None,
+}
+
+impl ColorDepth {
+    /// Work out the depth from `NO_COLOR`, `COLORTERM` and `TERM`.
+    pub fn detect() -> ColorDepth {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let term = var("TERM");
+        let colorterm = var("COLORTERM");
+        if !var("NO_COLOR").is_empty() || term == "dumb" {
+            ColorDepth::None
+        } else if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Nearest color this depth can show.
+    pub fn degrade(self, color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else {
+            return if self == ColorDepth::None {
+                Color::Reset
+            } else {
+                color
+            };
+        };
+        match self {
+            ColorDepth::TrueColor => color,
+            ColorDepth::Ansi256 => Color::Indexed(ansi256(r, g, b)),
+            ColorDepth::Ansi16 => ansi16(r, g, b),
+            ColorDepth::None => Color::Reset,
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Closest entry of the xterm 6×6×6 color cube or grayscale ramp.
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |c: u8| {
+        (0..6)
+            .min_by_key(|i| (LEVELS[*i] as i32 - c as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = ((average.saturating_sub(8)) / 10).min(23) as u8;
+    let gray = 8 + gray_step * 10;
+
+    if distance((gray, gray, gray), (r, g, b)) < distance(cube, (r, g, b)) {
+        232 + gray_step
+    } else {
+        cube_index as u8
+    }
+}
+
+/// Nearest of the 16 standard ANSI colors. Grays go by brightness, so dim
+/// text doesn't vanish into a black background. Other colors keep their
+/// hue: the channels well above the weakest one pick it, and the strongest
+/// one picks the bright variant. Going by RGB distance would turn muted
+/// colors gray.
+fn ansi16(r: u8, g: u8, b: u8) -> Color {
+    let (min, max) = (r.min(g).min(b), r.max(g).max(b));
+    if max - min < 32 {
+        return match (r as u32 + g as u32 + b as u32) / 3 {
+            0..=49 => Color::Black,
+            50..=159 => Color::DarkGray,
+            160..=219 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+    let on = |c: u8| (c - min) as u32 * 5 >= (max - min) as u32 * 3;
+    let bright = max >= 220;
+    match (on(r), on(g), on(b), bright) {
+        (true, false, false, false) => Color::Red,
+        (true, false, false, true) => Color::LightRed,
+        (false, true, false, false) => Color::Green,
+        (false, true, false, true) => Color::LightGreen,
+        (true, true, false, false) => Color::Yellow,
+        (true, true, false, true) => Color::LightYellow,
+        (false, false, true, false) => Color::Blue,
+        (false, false, true, true) => Color::LightBlue,
+        (true, false, true, false) => Color::Magenta,
+        (true, false, true, true) => Color::LightMagenta,
+        (false, true, true, false) => Color::Cyan,
+        (false, true, true, true) => Color::LightCyan,
+        // The strongest channel is always on, and the weakest never is.
+        _ => Color::Reset,
+    }
+}
+
+impl Palette {
+    /// This palette with every color shown at `depth`.
+    pub fn degraded(self, depth: ColorDepth) -> Palette {
+        let d = |c| depth.degrade(c);
+        Palette {
+            name: self.name,
+            bg: d(self.bg),
+            fg: d(self.fg),
+            fg_dim: d(self.fg_dim),
+            fg_muted: d(self.fg_muted),
+            accent: d(self.accent),
+            accent_secondary: d(self.accent_secondary),
+            success: d(self.success),
+            warning: d(self.warning),
+            error: d(self.error),
+            border: d(self.border),
+            selection_bg: d(self.selection_bg),
+        }
+    }
+}
+
+app_global! {
+    /// Palette in use, set at startup and replaced when switching themes.
+    /// Already degraded to the color depth.
+    fn current_palette: RwLock<Palette> = RwLock::new(Palette::DARK);
+}
+
+app_global! {
+    fn current_depth: RwLock<ColorDepth> = RwLock::new(ColorDepth::TrueColor);
+}
 
 /// The color palette for uniq's TUI.
-///
-/// Uses RGB colors for a professional, subdued look inspired by
-/// Claude Code, lazygit, and similar modern terminal UIs.
 pub struct Theme;
 
 impl Theme {
+    /// Use `palette` from the next frame on.
+    pub fn set_palette(palette: Palette) {
+        let palette = palette.degraded(Self::color_depth());
+        current_palette(|current| {
+            if let Ok(mut current) = current.write() {
+                *current = palette;
+            }
+        });
+    }
+
+    /// Show colors at `depth`. Set before the palette.
+    pub fn set_color_depth(depth: ColorDepth) {
+        current_depth(|current| {
+            if let Ok(mut current) = current.write() {
+                *current = depth;
+            }
+        });
+    }
+
+    pub fn color_depth() -> ColorDepth {
+        current_depth(|depth| depth.read().map(|d| *d).unwrap_or(ColorDepth::TrueColor))
+    }
+
+    pub fn palette() -> Palette {
+        current_palette(|palette| palette.read().map(|p| *p).unwrap_or(Palette::DARK))
+    }
+
+    /// Switch to the preset after the current one. Returns its name.
+    pub fn cycle() -> &'static str {
+        let current = Self::palette().name;
+        let presets = Palette::PRESETS;
+        let index = presets.iter().position(|p| p.name == current).unwrap_or(0);
+        let next = presets[(index + 1) % presets.len()];
+        Self::set_palette(next);
+        next.name
+    }
+
     // ── Base colors ─────────────────────────────────────────
     pub fn bg() -> Color {
-        Color::Reset
+        Self::palette().bg
     }
 
     pub fn fg() -> Color {
-        Color::Rgb(200, 200, 200)
+        Self::palette().fg
     }
 
     pub fn fg_dim() -> Color {
-        Color::Rgb(100, 100, 100)
+        Self::palette().fg_dim
     }
 
     pub fn fg_muted() -> Color {
-        Color::Rgb(140, 140, 140)
+        Self::palette().fg_muted
     }
 
     // ── Accent colors ───────────────────────────────────────
     pub fn accent() -> Color {
-        Color::Rgb(110, 170, 255)
+        Self::palette().accent
     }
 
     pub fn accent_secondary() -> Color {
-        Color::Rgb(180, 130, 240)
+        Self::palette().accent_secondary
     }
 
     pub fn success() -> Color {
-        Color::Rgb(80, 200, 120)
+        Self::palette().success
     }
 
     pub fn warning() -> Color {
-        Color::Rgb(230, 180, 80)
+        Self::palette().warning
     }
 
     pub fn error() -> Color {
-        Color::Rgb(240, 80, 80)
+        Self::palette().error
     }
 
     // ── Structural colors ───────────────────────────────────
     pub fn border_color() -> Color {
-        Color::Rgb(60, 60, 60)
+        Self::palette().border
     }
 
     pub fn selection_bg() -> Color {
-        Color::Rgb(40, 40, 60)
+        Self::palette().selection_bg
     }
 
     // ── Phase tab colors ────────────────────────────────────
@@ -78,6 +372,24 @@ impl Theme {
         Self::accent_secondary()
     }
 
+    pub fn blend_c() -> Color {
+        Self::warning()
+    }
+
+    pub fn blend_d() -> Color {
+        Self::success()
+    }
+
+    /// Color of the `index`th merge source.
+    pub fn blend(index: usize) -> Color {
+        match index % 4 {
+            0 => Self::blend_a(),
+            1 => Self::blend_b(),
+            2 => Self::blend_c(),
+            _ => Self::blend_d(),
+        }
+    }
+
     // ── Composite styles ────────────────────────────────────
 
     pub fn title() -> Style {
@@ -117,6 +429,10 @@ impl Theme {
     }
 
     pub fn selection() -> Style {
+        // Without enough colors a background can't mark the selection.
+        if matches!(Self::color_depth(), ColorDepth::Ansi16 | ColorDepth::None) {
+            return Style::default().add_modifier(Modifier::REVERSED);
+        }
         Style::default().bg(Self::selection_bg())
     }
 
@@ -151,3 +467,70 @@ impl Theme {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ansi16_keeps_hues_and_sorts_grays_by_brightness() {
+        let d = |c| ColorDepth::Ansi16.degrade(c);
+        assert_eq!(d(Color::Rgb(255, 0, 0)), Color::LightRed);
+        assert_eq!(d(Color::Rgb(0, 0, 160)), Color::Blue);
+        // Muted colors keep their hue rather than going gray.
+        assert_eq!(d(Palette::DARK.success), Color::Green);
+        assert_eq!(d(Palette::DARK.warning), Color::LightYellow);
+        assert_eq!(d(Palette::DARK.error), Color::LightRed);
+        assert_eq!(d(Palette::LIGHT.accent), Color::Blue);
+        assert_eq!(d(Color::Rgb(180, 60, 200)), Color::Magenta);
+        // Dim text stays visible on a black background.
+        assert_eq!(d(Palette::DARK.fg_dim), Color::DarkGray);
+        assert_eq!(d(Palette::DARK.fg), Color::Gray);
+        assert_eq!(d(Color::Rgb(10, 10, 10)), Color::Black);
+        assert_eq!(d(Color::Rgb(250, 250, 250)), Color::White);
+    }
+
+    #[test]
+    fn ansi256_picks_the_cube_or_the_gray_ramp() {
+        let d = |c| ColorDepth::Ansi256.degrade(c);
+        assert_eq!(d(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(d(Color::Rgb(0, 135, 255)), Color::Indexed(33));
+        assert_eq!(d(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(d(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+    }
+
+    #[test]
+    fn named_colors_pass_through_unless_color_is_off() {
+        for depth in [
+            ColorDepth::TrueColor,
+            ColorDepth::Ansi256,
+            ColorDepth::Ansi16,
+        ] {
+            assert_eq!(depth.degrade(Color::Reset), Color::Reset);
+            assert_eq!(depth.degrade(Color::Cyan), Color::Cyan);
+        }
+        let none = Palette::HIGH_CONTRAST.degraded(ColorDepth::None);
+        assert_eq!(none.accent, Color::Reset);
+        assert_eq!(none.selection_bg, Color::Reset);
+        assert_eq!(none.name, "high-contrast");
+    }
+
+    #[test]
+    fn config_overrides_apply_and_bad_ones_are_rejected() {
+        let mut config = UiConfig {
+            theme: "light".to_string(),
+            ..Default::default()
+        };
+        config
+            .colors
+            .insert("accent".to_string(), "#ff0000".to_string());
+        let palette = Palette::from_config(&config).unwrap();
+        assert_eq!(palette.accent, Color::Rgb(255, 0, 0));
+        assert_eq!(palette.fg, Palette::LIGHT.fg);
+
+        config.colors.insert("glow".to_string(), "red".to_string());
+        assert!(Palette::from_config(&config).is_err());
+        config.theme = "neon".to_string();
+        assert!(Palette::from_config(&config).is_err());
+    }
+}
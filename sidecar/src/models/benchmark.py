@@ -2,9 +2,18 @@
 
 from __future__ import annotations
 
+from typing import Literal
+
 from pydantic import BaseModel
 
 
+class MetricValue(BaseModel):
+    name: str
+    value: float
+    unit: str
+    higher_is_better: bool
+
+
 class ExecutionMetrics(BaseModel):
     build_success: bool
     build_error: str | None = None
@@ -13,7 +22,14 @@ class ExecutionMetrics(BaseModel):
     tests_total: int | None = None
     runtime_ms: float | None = None
     memory_mb: float | None = None
-    custom_metrics: dict[str, float] = {}
+    custom_metrics: dict[str, MetricValue] = {}
+    runtime_samples: list[float] = []
+    memory_samples: list[float] = []
+    # Standard deviations of the samples; runtime_ms/memory_mb are their means.
+    runtime_std_ms: float | None = None
+    memory_std_mb: float | None = None
+    # Tests that both passed and failed across repetitions.
+    flaky_tests: list[str] = []
 
 
 class JudgeScores(BaseModel):
@@ -24,6 +40,21 @@ class JudgeScores(BaseModel):
     completeness: float
     overall: float
     explanation: str
+    # Number of judge samples aggregated into these scores.
+    samples: int = 1
+    # Range (max - min) of the samples' overall scores.
+    spread: float = 0.0
+    disagreement: bool = False
+
+
+class JudgeEnsemble(BaseModel):
+    """Which judges score each variant and how their scores are combined."""
+
+    models: list[str] = []
+    samples: int = 1
+    aggregate: Literal["mean", "median"] = "mean"
+    # "pairwise" compares every pair of variants instead of scoring each one.
+    mode: Literal["absolute", "pairwise"] = "absolute"
 
 
 class BenchmarkRequest(BaseModel):
@@ -31,7 +62,34 @@ class BenchmarkRequest(BaseModel):
     project_path: str
     metrics: list[str] = []
     timeout_seconds: int = 300
+    # Per-branch overrides of timeout_seconds.
+    timeouts: dict[str, int] = {}
+    # Times to repeat the measured (test) command per branch.
+    repetitions: int = 1
 
 
 class BenchmarkResult(BaseModel):
     results: dict[str, ExecutionMetrics]
+
+
+class BenchmarkProgressResponse(BaseModel):
+    """Current stage of each branch in a running benchmark."""
+
+    progress: dict[str, str] = {}
+
+
+class LlmJudgeResponse(BaseModel):
+    scores: dict[str, JudgeScores]
+
+
+class PairwiseComparison(BaseModel):
+    """The judge's verdict on one pair of variants."""
+
+    branch_a: str
+    branch_b: str
+    winner: Literal["a", "b", "tie"]
+    explanation: str = ""
+
+
+class PairwiseJudgeResponse(BaseModel):
+    comparisons: list[PairwiseComparison]
@@ -0,0 +1,24 @@
+"""LLM backend selection, sent by the client with every request that calls an LLM."""
+
+from __future__ import annotations
+
+from typing import Literal
+
+from pydantic import BaseModel
+
+
+class LlmBackend(BaseModel):
+    provider: Literal["anthropic", "openai", "ollama"] = "anthropic"
+    model: str
+    # API root; the provider's usual one when unset.
+    base_url: str | None = None
+
+
+class PingRequest(BaseModel):
+    llm: LlmBackend
+
+
+class PingResponse(BaseModel):
+    ok: bool
+    # Why the backend couldn't be reached, when it couldn't.
+    error: str | None = None
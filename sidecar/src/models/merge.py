@@ -4,17 +4,47 @@ from __future__ import annotations
 
 from typing import Any
 
-from pydantic import BaseModel
+from pydantic import BaseModel, Field
 
+from src.models.llm import LlmBackend
 from src.models.project import ProjectProfile
 
 
+class ComponentBlend(BaseModel):
+    name: str  # one of the technique's key components
+    blend: int  # 0, 25, 50, 75, 100
+
+
+class MergeSource(BaseModel):
+    branch: str
+    technique: Any  # TechniqueCard or merge lineage
+    blend: int  # 0, 25, 50, 75, 100
+    components: list[ComponentBlend] = []  # overrides `blend` for these parts
+
+
+class ReconcileHunkRequest(BaseModel):
+    path: str
+    a: list[str]  # lines from the first conflicting source
+    b: list[str]  # lines from the second
+    base: list[str] = []  # common ancestor, when known
+    before: list[str] = []  # clean lines around the hunk
+    after: list[str] = []
+    sources: list[str] = Field(min_length=2, max_length=2)  # technique names
+    guidance: str | None = None
+    project: ProjectProfile
+    llm: LlmBackend | None = None
+
+
+class ReconcileHunkResponse(BaseModel):
+    lines: list[str]
+
+
 class MergeRequest(BaseModel):
-    variant_a_branch: str
-    variant_a_technique: Any  # TechniqueCard or merge lineage
-    variant_b_branch: str
-    variant_b_technique: Any
-    blend_a: int  # 0, 25, 50, 75, 100
-    blend_b: int
+    sources: list[MergeSource] = Field(min_length=2, max_length=4)
+    guidance: str | None = None  # e.g. "take A's retrieval pipeline but B's caching"
+    strategy: str | None = None  # directive for how to fit the techniques together
     project: ProjectProfile
     target_branch: str
+    llm: LlmBackend | None = None
+    # Replaces the built-in prompt; see src/services/prompts.py.
+    prompt_template: str | None = None
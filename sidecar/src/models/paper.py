@@ -10,6 +10,7 @@ from pydantic import BaseModel
 class PaperSource(str, Enum):
     SEMANTIC_SCHOLAR = "SemanticScholar"
     ARXIV = "ArXiv"
+    PLUGIN = "Plugin"
 
 
 class PaperMeta(BaseModel):
@@ -36,6 +37,11 @@ class SearchRequest(BaseModel):
     prefer_open_access: bool = True
 
 
+class CitedPaper(BaseModel):
+    id: str
+    title: str
+
+
 class TechniqueCard(BaseModel):
     name: str
     paper_id: str
@@ -49,3 +55,4 @@ class TechniqueCard(BaseModel):
     relevance_score: float
     integration_approach: str
     selected: bool = False
+    related_papers: list[CitedPaper] = []
@@ -4,10 +4,13 @@ from __future__ import annotations
 
 from pydantic import BaseModel
 
+from src.models.llm import LlmBackend
+
 
 class AnalyzeProjectRequest(BaseModel):
     path: str
     description: str
+    llm: LlmBackend | None = None
 
 
 class DetectedFramework(BaseModel):
@@ -4,14 +4,86 @@ from __future__ import annotations
 
 from pydantic import BaseModel
 
+from src.models.llm import LlmBackend
 from src.models.paper import TechniqueCard
 from src.models.project import ProjectProfile
 
 
+class PlannedFile(BaseModel):
+    path: str
+    action: str = "modify"
+    purpose: str = ""
+
+
+class ImplementationPlan(BaseModel):
+    steps: list[str] = []
+    files: list[PlannedFile] = []
+    new_modules: list[str] = []
+    dependencies: list[str] = []
+
+
+class PlanVariantRequest(BaseModel):
+    technique: TechniqueCard
+    project: ProjectProfile
+    feedback: str | None = None
+    style: str | None = None
+    llm: LlmBackend | None = None
+    # Replaces the built-in prompt; see src/services/prompts.py.
+    prompt_template: str | None = None
+
+
+class PlanVariantResponse(BaseModel):
+    success: bool
+    plan: ImplementationPlan | None = None
+    error: str | None = None
+
+
 class GenerateVariantRequest(BaseModel):
     technique: TechniqueCard
     project: ProjectProfile
     branch_name: str
+    feedback: str | None = None
+    # An approved plan the generated code must follow.
+    plan: ImplementationPlan | None = None
+    # Style directive to implement the technique in.
+    style: str | None = None
+    # Install new dependencies on the branch before committing. Opt-in, as
+    # the packages are whatever the model asked for.
+    install_dependencies: bool = False
+    llm: LlmBackend | None = None
+    # Replaces the built-in prompt; see src/services/prompts.py.
+    prompt_template: str | None = None
+
+
+class CancelGenerationRequest(BaseModel):
+    branch_name: str
+
+
+class CancelGenerationResponse(BaseModel):
+    cancelled: bool
+
+
+class GenerationProgressResponse(BaseModel):
+    # Current step (e.g. "writing src/model.py") per running branch.
+    progress: dict[str, str] = {}
+
+
+class GenerationTranscript(BaseModel):
+    plan: list[str] = []
+    files_written: list[str] = []
+    explanation: str = ""
+    log: list[str] = []
+
+
+class FailedDependency(BaseModel):
+    name: str
+    error: str
+
+
+class DependencyCheck(BaseModel):
+    installer: str
+    installed: list[str] = []
+    failed: list[FailedDependency] = []
 
 
 class VariantResult(BaseModel):
@@ -19,3 +91,6 @@ class VariantResult(BaseModel):
     modified_files: list[str] = []
     new_dependencies: list[str] = []
     error: str | None = None
+    transcript: GenerationTranscript | None = None
+    dependency_check: DependencyCheck | None = None
+    conflicts: list[str] = []  # merges: files the sources conflict on in git
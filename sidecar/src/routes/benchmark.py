@@ -5,11 +5,23 @@ from __future__ import annotations
 from fastapi import APIRouter
 from pydantic import BaseModel
 
-from src.models.benchmark import BenchmarkRequest, BenchmarkResult, JudgeScores
-from src.services.benchmarker import run_benchmarks, run_llm_judge
+from src.models.benchmark import (
+    BenchmarkProgressResponse,
+    BenchmarkRequest,
+    BenchmarkResult,
+    JudgeEnsemble,
+    LlmJudgeResponse,
+    PairwiseJudgeResponse,
+)
+from src.models.llm import LlmBackend
+from src.services.benchmarker import run_benchmarks, run_llm_judge, run_pairwise_judge
+from src.services.claude_client import use_backend
 
 router = APIRouter()
 
+# Current stage of each branch in a running benchmark, keyed by branch name.
+_stages: dict[str, str] = {}
+
 
 class LlmJudgeRequest(BaseModel):
     """Request body for LLM judge evaluation."""
@@ -17,6 +29,10 @@ class LlmJudgeRequest(BaseModel):
     variant_branches: list[str]
     project_path: str
     user_request: str
+    ensemble: JudgeEnsemble = JudgeEnsemble()
+    llm: LlmBackend | None = None
+    # Replaces the built-in prompt; see src/services/prompts.py.
+    prompt_template: str | None = None
 
 
 @router.post("/run-benchmark", response_model=BenchmarkResult)
@@ -27,16 +43,43 @@ async def benchmark(req: BenchmarkRequest) -> BenchmarkResult:
         project_path=req.project_path,
         metrics=req.metrics,
         timeout_seconds=req.timeout_seconds,
+        timeouts=req.timeouts,
+        repetitions=req.repetitions,
+        on_stage=_stages.__setitem__,
     )
+    for branch in req.variant_branches:
+        _stages.pop(branch, None)
     return BenchmarkResult(results=results)
 
 
-@router.post("/llm-judge", response_model=dict[str, JudgeScores])
-async def llm_judge(req: LlmJudgeRequest) -> dict[str, JudgeScores]:
+@router.get("/benchmark-progress", response_model=BenchmarkProgressResponse)
+async def benchmark_progress() -> BenchmarkProgressResponse:
+    """Report which stage each branch being benchmarked is in."""
+    return BenchmarkProgressResponse(progress=dict(_stages))
+
+
+@router.post("/llm-judge", response_model=LlmJudgeResponse)
+async def llm_judge(req: LlmJudgeRequest) -> LlmJudgeResponse:
     """Run LLM-as-judge evaluation on variants."""
+    use_backend(req.llm)
     scores = await run_llm_judge(
         variant_branches=req.variant_branches,
         project_path=req.project_path,
         user_request=req.user_request,
+        ensemble=req.ensemble,
+        template=req.prompt_template,
+    )
+    return LlmJudgeResponse(scores=scores)
+
+
+@router.post("/llm-judge-pairwise", response_model=PairwiseJudgeResponse)
+async def llm_judge_pairwise(req: LlmJudgeRequest) -> PairwiseJudgeResponse:
+    """Have the LLM judge compare every pair of variants."""
+    use_backend(req.llm)
+    comparisons = await run_pairwise_judge(
+        variant_branches=req.variant_branches,
+        project_path=req.project_path,
+        user_request=req.user_request,
+        ensemble=req.ensemble,
     )
-    return scores
+    return PairwiseJudgeResponse(comparisons=comparisons)
@@ -2,26 +2,105 @@
 
 from __future__ import annotations
 
+import asyncio
+
 from fastapi import APIRouter
 
-from src.models.variant import GenerateVariantRequest, VariantResult
-from src.services.code_generator import generate_variant_code
+from src.models.variant import (
+    CancelGenerationRequest,
+    CancelGenerationResponse,
+    GenerateVariantRequest,
+    GenerationProgressResponse,
+    PlanVariantRequest,
+    PlanVariantResponse,
+    VariantResult,
+)
+from src.services.claude_client import RATE_LIMIT_ERRORS, use_backend
+from src.services.code_generator import generate_variant_code, plan_variant_code
 
 router = APIRouter()
 
+# Generations in flight, keyed by branch name, so they can be cancelled.
+_running: dict[str, asyncio.Task[VariantResult]] = {}
+
+# Latest progress step of each running generation, keyed by branch name.
+_progress: dict[str, str] = {}
+
+# Branches whose generation /cancel-generation stopped. Any other
+# cancellation is the request's own (e.g. the client went away) and has to
+# propagate.
+_cancelled: set[str] = set()
+
 
 @router.post("/generate-variant", response_model=VariantResult)
 async def generate_variant(req: GenerateVariantRequest) -> VariantResult:
     """Generate a project variant by applying a technique."""
-    try:
-        result = await generate_variant_code(
+    use_backend(req.llm)
+    task = asyncio.create_task(
+        generate_variant_code(
             technique=req.technique,
             project=req.project,
             branch_name=req.branch_name,
+            feedback=req.feedback,
+            plan=req.plan,
+            style=req.style,
+            install_deps=req.install_dependencies,
+            template=req.prompt_template,
+            on_progress=lambda step: _progress.__setitem__(req.branch_name, step),
         )
-        return result
+    )
+    _running[req.branch_name] = task
+    try:
+        return await task
+    except asyncio.CancelledError:
+        if req.branch_name not in _cancelled:
+            raise
+        return VariantResult(success=False, error="cancelled")
+    except RATE_LIMIT_ERRORS:
+        # Answered as a 429 by the server, so the TUI waits and retries.
+        raise
     except Exception as e:
         return VariantResult(
             success=False,
             error=str(e),
         )
+    finally:
+        _running.pop(req.branch_name, None)
+        _progress.pop(req.branch_name, None)
+        _cancelled.discard(req.branch_name)
+
+
+@router.get("/generation-progress", response_model=GenerationProgressResponse)
+async def generation_progress() -> GenerationProgressResponse:
+    """Report what each running generation is currently doing."""
+    return GenerationProgressResponse(progress=dict(_progress))
+
+
+@router.post("/plan-variant", response_model=PlanVariantResponse)
+async def plan_variant(req: PlanVariantRequest) -> PlanVariantResponse:
+    """Draft an implementation plan for a technique without writing code."""
+    use_backend(req.llm)
+    try:
+        plan = await plan_variant_code(
+            technique=req.technique,
+            project=req.project,
+            feedback=req.feedback,
+            style=req.style,
+            template=req.prompt_template,
+        )
+        return PlanVariantResponse(success=True, plan=plan)
+    except RATE_LIMIT_ERRORS:
+        raise
+    except Exception as e:
+        return PlanVariantResponse(success=False, error=str(e))
+
+
+@router.post("/cancel-generation", response_model=CancelGenerationResponse)
+async def cancel_generation(req: CancelGenerationRequest) -> CancelGenerationResponse:
+    """Cancel a running variant generation."""
+    task = _running.get(req.branch_name)
+    if task is None or task.done():
+        return CancelGenerationResponse(cancelled=False)
+    _cancelled.add(req.branch_name)
+    task.cancel()
+    return CancelGenerationResponse(cancelled=True)
@@ -2,32 +2,60 @@
 
 from __future__ import annotations
 
+import asyncio
+
 from fastapi import APIRouter
 
-from src.models.merge import MergeRequest
+from src.models.merge import MergeRequest, ReconcileHunkRequest, ReconcileHunkResponse
 from src.models.variant import VariantResult
-from src.services.merger import merge_variant_code
+from src.routes.generate import _cancelled, _progress, _running
+from src.services.claude_client import RATE_LIMIT_ERRORS, use_backend
+from src.services.merger import merge_variant_code, reconcile_hunk
 
 router = APIRouter()
 
+# Merges share the generation registries, so they report progress through
+# /generation-progress and can be stopped with /cancel-generation.
+
 
 @router.post("/merge-variants", response_model=VariantResult)
 async def merge_variants(req: MergeRequest) -> VariantResult:
-    """Merge two variants with specified blend ratios."""
-    try:
-        result = await merge_variant_code(
-            variant_a_branch=req.variant_a_branch,
-            variant_a_technique=req.variant_a_technique,
-            variant_b_branch=req.variant_b_branch,
-            variant_b_technique=req.variant_b_technique,
-            blend_a=req.blend_a,
-            blend_b=req.blend_b,
+    """Merge two or more variants with per-source blend ratios."""
+    use_backend(req.llm)
+    task = asyncio.create_task(
+        merge_variant_code(
+            sources=req.sources,
+            guidance=req.guidance,
+            strategy=req.strategy,
             project=req.project,
             target_branch=req.target_branch,
+            template=req.prompt_template,
+            on_progress=lambda step: _progress.__setitem__(req.target_branch, step),
         )
-        return result
+    )
+    _running[req.target_branch] = task
+    try:
+        return await task
+    except asyncio.CancelledError:
+        if req.target_branch not in _cancelled:
+            raise
+        return VariantResult(success=False, error="cancelled")
+    except RATE_LIMIT_ERRORS:
+        # Answered as a 429 by the server, so the TUI waits and retries.
+        raise
     except Exception as e:
         return VariantResult(
             success=False,
             error=str(e),
         )
+    finally:
+        _running.pop(req.target_branch, None)
+        _progress.pop(req.target_branch, None)
+        _cancelled.discard(req.target_branch)
+
+
+@router.post("/reconcile-hunk", response_model=ReconcileHunkResponse)
+async def reconcile_conflict_hunk(req: ReconcileHunkRequest) -> ReconcileHunkResponse:
+    """Write one version of a hunk two merge sources conflict on."""
+    use_backend(req.llm)
+    return ReconcileHunkResponse(lines=await reconcile_hunk(req))
@@ -5,7 +5,7 @@ from __future__ import annotations
 from fastapi import APIRouter, HTTPException
 
 from src.models.project import AnalyzeProjectRequest, ProjectProfile
-from src.services.claude_client import get_claude_client
+from src.services.claude_client import get_claude_client, use_backend
 
 router = APIRouter()
 
@@ -13,6 +13,7 @@ router = APIRouter()
 @router.post("/analyze-project", response_model=ProjectProfile)
 async def analyze_project(req: AnalyzeProjectRequest) -> ProjectProfile:
     """Analyze a project directory and return a structured profile."""
+    use_backend(req.llm)
     import os
     from pathlib import Path
 
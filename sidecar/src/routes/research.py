@@ -9,10 +9,12 @@ import re
 from fastapi import APIRouter, HTTPException
 from pydantic import BaseModel, ValidationError
 
+from src.models.llm import LlmBackend
 from src.models.paper import PaperMeta, SearchRequest, TechniqueCard
-from src.services.claude_client import get_claude_client
+from src.services.claude_client import RATE_LIMIT_ERRORS, get_claude_client, use_backend
 from src.services.paper_search import search_all_sources
 from src.services.pdf_extractor import extract_pdf_text
+from src.services.prompts import render, uses
 
 logger = logging.getLogger(__name__)
 
@@ -28,6 +30,9 @@ class ExtractTechniqueRequest(BaseModel):
     project_summary: str
     user_request: str
     doi: str | None = None
+    llm: LlmBackend | None = None
+    # Replaces the built-in prompt; see src/services/prompts.py.
+    prompt_template: str | None = None
 
 
 class BatchExtractRequest(BaseModel):
@@ -37,6 +42,10 @@ class BatchExtractRequest(BaseModel):
     project_summary: str
     user_request: str
     max_techniques: int = 8
+    guidance: str | None = None
+    llm: LlmBackend | None = None
+    # Replaces the built-in prompt; see src/services/prompts.py.
+    prompt_template: str | None = None
 
 
 @router.post("/search-papers", response_model=list[PaperMeta])
@@ -55,6 +64,7 @@ async def search_papers(req: SearchRequest) -> list[PaperMeta]:
 @router.post("/extract-technique", response_model=TechniqueCard)
 async def extract_technique(req: ExtractTechniqueRequest) -> TechniqueCard:
     """Extract a technique card from a paper PDF."""
+    use_backend(req.llm)
     # Validate that we have at least one way to get the PDF.
     if not req.pdf_url and not req.doi:
         raise HTTPException(
@@ -79,16 +89,29 @@ async def extract_technique(req: ExtractTechniqueRequest) -> TechniqueCard:
             detail="Claude API client not available. Set ANTHROPIC_API_KEY env var.",
         )
 
-    prompt = f"""You are analyzing an academic paper to extract a specific technique that can be applied to a software project.
-
-Paper title: {req.paper_title}
+    paper_block = f"""Paper title: {req.paper_title}
 Paper ID: {req.paper_id}
 
+Paper content (extracted from PDF):
+{pdf_text[:15000]}"""
+
+    if req.prompt_template:
+        instructions = render(
+            req.prompt_template,
+            papers=paper_block,
+            project_summary=req.project_summary,
+            user_request=req.user_request,
+            guidance="",
+        )
+    else:
+        instructions = f"""You are analyzing an academic paper to extract a specific technique that can be applied to a software project.
+
 Project context: {req.project_summary}
 User's goal: {req.user_request}
 
-Paper content (extracted from PDF):
-{pdf_text[:15000]}
+{paper_block}"""
+
+    prompt = f"""{instructions}
 
 Extract the most relevant technique from this paper and respond with ONLY a JSON object (no markdown, no explanation):
 {{
@@ -107,6 +130,9 @@ Extract the most relevant technique from this paper and respond with ONLY a JSON
 
     try:
         result = await claude.analyze(prompt)
+    except RATE_LIMIT_ERRORS:
+        # Answered as a 429 by the server, so the TUI waits and retries.
+        raise
     except Exception as e:
         logger.error(f"Claude API call failed for paper {req.paper_id}: {e}")
         raise HTTPException(
@@ -159,6 +185,7 @@ Extract the most relevant technique from this paper and respond with ONLY a JSON
 @router.post("/batch-extract-techniques", response_model=list[TechniqueCard])
 async def batch_extract_techniques(req: BatchExtractRequest) -> list[TechniqueCard]:
     """Extract technique cards from multiple papers using their abstracts in a single Claude call."""
+    use_backend(req.llm)
     if not req.papers:
         return []
 
@@ -182,14 +209,34 @@ async def batch_extract_techniques(req: BatchExtractRequest) -> list[TechniqueCa
         )
     papers_block = "\n".join(paper_entries)
 
-    prompt = f"""You are analyzing academic paper abstracts to extract techniques relevant to a software project.
+    guidance_block = ""
+    if req.guidance:
+        guidance_block = (
+            f"\nAdditional guidance from the user (follow it closely): {req.guidance}\n"
+        )
+
+    if req.prompt_template:
+        instructions = render(
+            req.prompt_template,
+            papers=papers_block,
+            project_summary=req.project_summary,
+            user_request=req.user_request,
+            guidance=req.guidance or "",
+        )
+        # Guidance the template has no place for goes after it.
+        if not uses(req.prompt_template, "guidance"):
+            instructions += guidance_block
+    else:
+        instructions = f"""You are analyzing academic paper abstracts to extract techniques relevant to a software project.
 
 Project context: {req.project_summary}
 User's goal: {req.user_request}
-
+{guidance_block}
 Below are {len(req.papers)} paper abstracts. Rank them by relevance to the project and extract a technique card for each of the top {req.max_techniques} most relevant papers. Skip papers that are not relevant.
 
-{papers_block}
+{papers_block}"""
+
+    prompt = f"""{instructions}
 
 Respond with ONLY a JSON array (no markdown fences, no explanation). Each element must have this exact schema:
 {{
@@ -210,6 +257,9 @@ Order the array from most relevant to least relevant. Return at most {req.max_te
 
     try:
         result = await claude.analyze(prompt)
+    except RATE_LIMIT_ERRORS:
+        # Answered as a 429 by the server, so the TUI waits and retries.
+        raise
     except Exception as e:
         logger.error(f"Claude API call failed for batch extraction: {e}")
         raise HTTPException(
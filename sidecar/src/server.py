@@ -20,14 +20,19 @@ _env_file = _workspace_root / ".env"
 if _env_file.exists():
     load_dotenv(_env_file)
 
+import anthropic
 import uvicorn
-from fastapi import FastAPI
+from fastapi import FastAPI, Request
+from fastapi.responses import JSONResponse
 
 from src.routes.benchmark import router as benchmark_router
 from src.routes.generate import router as generate_router
 from src.routes.merge import router as merge_router
 from src.routes.project import router as project_router
 from src.routes.research import router as research_router
+from src.models.llm import PingRequest, PingResponse
+from src.services import correlation, usage
+from src.services.claude_client import BackendRateLimitError, get_claude_client, ping
 
 app = FastAPI(
     title="uniq-sidecar",
@@ -43,10 +48,59 @@ app.include_router(merge_router, prefix="/api")
 app.include_router(benchmark_router, prefix="/api")
 
 
+@app.middleware("http")
+async def tag_phase(request: Request, call_next):
+    """Count Claude calls made for this request against its phase."""
+    usage.current_phase.set(usage.phase_for_path(request.url.path))
+    return await call_next(request)
+
+
+app.middleware("http")(correlation.tag_request)
+
+
+@app.exception_handler(anthropic.RateLimitError)
+async def rate_limited(request: Request, exc: anthropic.RateLimitError):
+    """Pass Claude's rate limit on as a 429 the client can wait out."""
+    retry_after = exc.response.headers.get("retry-after", "10")
+    return JSONResponse(
+        status_code=429,
+        content={"detail": "Claude API rate limit reached"},
+        headers={"Retry-After": retry_after},
+    )
+
+
+@app.exception_handler(BackendRateLimitError)
+async def backend_rate_limited(request: Request, exc: BackendRateLimitError):
+    """Pass an OpenAI-style backend's rate limit on the same way."""
+    return JSONResponse(
+        status_code=429,
+        content={"detail": str(exc)},
+        headers={"Retry-After": exc.retry_after},
+    )
+
+
 @app.get("/api/health")
 async def health():
-    """Health check endpoint."""
-    return {"status": "ok", "version": "0.1.0"}
+    """Health check endpoint, with which API keys the sidecar has."""
+    return {
+        "status": "ok",
+        "version": "0.1.0",
+        "claude": get_claude_client() is not None,
+        "openai": bool(os.environ.get("OPENAI_API_KEY")),
+    }
+
+
+@app.post("/api/ping", response_model=PingResponse)
+async def ping_backend(req: PingRequest) -> PingResponse:
+    """Check that the backend can be reached with the key the sidecar has."""
+    error = await ping(req.llm)
+    return PingResponse(ok=error is None, error=error)
+
+
+@app.get("/api/usage")
+async def get_usage():
+    """Tokens used since startup, by phase, provider and model."""
+    return {"entries": usage.snapshot()}
 
 
 @app.post("/api/shutdown")
@@ -63,6 +117,8 @@ def main():
     parser.add_argument("--host", type=str, default="127.0.0.1", help="Host to bind to")
     args = parser.parse_args()
 
+    correlation.configure_logging()
+
     # Print ready signal for the Rust side.
     print(f"SIDECAR_READY port={args.port}", flush=True)
 
@@ -2,74 +2,113 @@
 
 from __future__ import annotations
 
+import asyncio
 import contextlib
+import itertools
 import json
 import logging
+import os
+import statistics
 import subprocess
+import sys
+import tempfile
 import time
+from collections.abc import Callable
 from pathlib import Path
 
-from src.models.benchmark import ExecutionMetrics, JudgeScores
-from src.services.claude_client import get_claude_client
+
+from src.models.benchmark import (
+    ExecutionMetrics,
+    JudgeEnsemble,
+    JudgeScores,
+    PairwiseComparison,
+)
+from src.services.claude_client import RATE_LIMIT_ERRORS, get_claude_client
+from src.services.prompts import render
+from src.services.worktree import checkout_worktree
 
 logger = logging.getLogger(__name__)
 
+# Variants the LLM judge evaluates at once. Each sends one request per
+# ensemble sample, so this bounds the requests in flight.
+MAX_CONCURRENT_JUDGEMENTS = 4
+
 
 async def run_benchmarks(
     variant_branches: list[str],
     project_path: str,
     metrics: list[str],
     timeout_seconds: int = 300,
+    timeouts: dict[str, int] | None = None,
+    repetitions: int = 1,
+    on_stage: Callable[[str, str], None] | None = None,
 ) -> dict[str, ExecutionMetrics]:
     """Run automated benchmarks on each variant branch.
 
     For each variant:
-    1. Checkout the branch.
+    1. Check the branch out in a temporary worktree.
     2. Attempt to build.
     3. Attempt to run tests.
     4. Measure runtime and memory usage.
-    5. Switch back to the default branch.
+
+    The user's checkout is never switched, so uncommitted changes there
+    neither block the run nor leak into a variant's build.
+
+    ``timeouts`` overrides ``timeout_seconds`` for particular branches.
+    With ``repetitions`` above one, the tests are run that many times and
+    their mean duration reported as the runtime.
+    ``on_stage(branch, stage)`` is called as each variant moves through
+    "queued", "building" and "testing".
     """
     results: dict[str, ExecutionMetrics] = {}
     path = Path(project_path)
-    base_branch = _get_default_branch(path)
+
+    def report(branch: str, stage: str) -> None:
+        if on_stage:
+            on_stage(branch, stage)
 
     for branch in variant_branches:
-        logger.info(f"Benchmarking variant: {branch}")
-        metrics_result = await _benchmark_single_variant(path, branch, base_branch, timeout_seconds)
-        results[branch] = metrics_result
+        report(branch, "queued")
 
-    # Ensure we're back on the base branch.
-    with contextlib.suppress(Exception):
-        subprocess.run(
-            ["git", "checkout", base_branch],
-            cwd=path,
-            capture_output=True,
+    for branch in variant_branches:
+        logger.info(f"Benchmarking variant: {branch}")
+        # Off the event loop, so progress can be polled while it runs.
+        metrics_result = await asyncio.to_thread(
+            _benchmark_single_variant,
+            path,
+            branch,
+            (timeouts or {}).get(branch, timeout_seconds),
+            repetitions,
+            lambda stage, branch=branch: report(branch, stage),
         )
+        results[branch] = metrics_result
 
     return results
 
 
-async def _benchmark_single_variant(
+def _benchmark_single_variant(
     project_path: Path,
     branch: str,
-    base_branch: str,
     timeout: int,
+    repetitions: int,
+    on_stage: Callable[[str], None],
 ) -> ExecutionMetrics:
-    """Benchmark a single variant."""
-    # Checkout the branch.
-    try:
-        subprocess.run(
-            ["git", "checkout", branch],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
-    except subprocess.CalledProcessError as e:
-        return ExecutionMetrics(
-            build_success=False,
-            build_error=f"Failed to checkout branch: {e.stderr.decode()}",
-        )
+    """Benchmark a single variant, in a worktree of its branch."""
+    with contextlib.ExitStack() as stack:
+        try:
+            worktree = stack.enter_context(checkout_worktree(project_path, branch))
+        except RuntimeError as e:
+            return ExecutionMetrics(build_success=False, build_error=str(e))
+        return _measure_variant(worktree, timeout, repetitions, on_stage)
+
+
+def _measure_variant(
+    worktree: Path,
+    timeout: int,
+    repetitions: int,
+    on_stage: Callable[[str], None],
+) -> ExecutionMetrics:
+    """Build and test the checkout at `worktree`."""
 
     # Detect project type and run appropriate build/test commands.
     build_success = True
@@ -79,15 +118,22 @@ async def _benchmark_single_variant(
     tests_total = None
     runtime_ms = None
     memory_mb = None
+    runtime_samples: list[float] = []
+    memory_samples: list[float] = []
+    runtime_std_ms = None
+    memory_std_mb = None
+    test_runs: list[tuple[int, set[str]]] = []
+    flaky_tests: list[str] = []
 
     # Try to build.
-    build_cmd = _detect_build_command(project_path)
+    build_cmd = _detect_build_command(worktree)
     if build_cmd:
+        on_stage("building")
         start = time.time()
         try:
             result = subprocess.run(
                 build_cmd,
-                cwd=project_path,
+                cwd=worktree,
                 capture_output=True,
                 text=True,
                 timeout=timeout,
@@ -105,29 +151,43 @@ async def _benchmark_single_variant(
         runtime_ms = (time.time() - start) * 1000
 
     # Try to run tests.
-    test_cmd = _detect_test_command(project_path)
+    test_cmd = _detect_test_command(worktree)
     if test_cmd and build_success:
-        try:
-            result = subprocess.run(
-                test_cmd,
-                cwd=project_path,
-                capture_output=True,
-                text=True,
-                timeout=timeout,
-                shell=True,
-            )
+        on_stage("testing")
+        for _ in range(max(repetitions, 1)):
+            start = time.time()
+            try:
+                returncode, output, peak_mb = _run_measured(test_cmd, worktree, timeout)
+            except Exception:
+                break
+            # Failed runs can stop early, so only passing ones are timed.
+            if returncode == 0:
+                runtime_samples.append((time.time() - start) * 1000)
+                if peak_mb is not None:
+                    memory_samples.append(peak_mb)
+            test_runs.append((returncode, _failed_tests(output)))
+
+        # Tests that failed in some runs and passed in others are flaky, and
+        # don't count against the variant.
+        if len(test_runs) > 1:
+            failed_sets = [failed for _, failed in test_runs]
+            flaky_tests = sorted(set().union(*failed_sets) - set.intersection(*failed_sets))
+        if test_runs:
             # Basic pass/fail detection.
-            test_pass_rate = 1.0 if result.returncode == 0 else 0.0
-        except Exception:
-            pass
-
-    # Switch back.
-    with contextlib.suppress(Exception):
-        subprocess.run(
-            ["git", "checkout", base_branch],
-            cwd=project_path,
-            capture_output=True,
-        )
+            passed = all(
+                code == 0 or (failed and failed <= set(flaky_tests)) for code, failed in test_runs
+            )
+            test_pass_rate = 1.0 if passed else 0.0
+
+    # The passing test runs' mean, with their spread when there are several.
+    if test_runs and not runtime_samples:
+        runtime_ms = None
+    if runtime_samples:
+        runtime_ms, runtime_std_ms = _mean_std(runtime_samples)
+    if memory_samples:
+        memory_mb, memory_std_mb = _mean_std(memory_samples)
+    if len(runtime_samples) < 2:
+        runtime_samples, memory_samples = [], []
 
     return ExecutionMetrics(
         build_success=build_success,
@@ -137,6 +197,11 @@ async def _benchmark_single_variant(
         tests_total=tests_total,
         runtime_ms=runtime_ms,
         memory_mb=memory_mb,
+        runtime_samples=runtime_samples,
+        memory_samples=memory_samples,
+        runtime_std_ms=runtime_std_ms,
+        memory_std_mb=memory_std_mb,
+        flaky_tests=flaky_tests,
     )
 
 
@@ -144,36 +209,45 @@ async def run_llm_judge(
     variant_branches: list[str],
     project_path: str,
     user_request: str,
+    ensemble: JudgeEnsemble | None = None,
+    template: str | None = None,
 ) -> dict[str, JudgeScores]:
-    """Run LLM-as-judge evaluation on variants."""
+    """Run LLM-as-judge evaluation on variants.
+
+    With an ensemble, every listed model is sampled ``ensemble.samples``
+    times and the scores are aggregated, since a single sample is noisy
+    enough to flip rankings. A user ``template`` replaces the instructions
+    before the response format.
+    """
+    ensemble = ensemble or JudgeEnsemble()
+    judges = [model or None for model in ensemble.models] or [None]
+    judges = [model for model in judges for _ in range(max(ensemble.samples, 1))]
     claude = get_claude_client()
     if not claude:
         raise RuntimeError("Claude API client not available.")
 
-    results: dict[str, JudgeScores] = {}
     path = Path(project_path)
     base_branch = _get_default_branch(path)
+    slots = asyncio.Semaphore(MAX_CONCURRENT_JUDGEMENTS)
 
-    for branch in variant_branches:
-        # Read the diff for this variant.
-        try:
-            diff_result = subprocess.run(
-                ["git", "diff", f"{base_branch}...{branch}"],
-                cwd=path,
-                capture_output=True,
-                text=True,
-            )
-            diff_text = diff_result.stdout[:10000]
-        except Exception:
-            diff_text = "(Could not read diff)"
+    async def judge(branch: str) -> JudgeScores:
+        async with slots:
+            diff_text = await asyncio.to_thread(_read_diff, path, base_branch, branch)
 
-        prompt = f"""You are evaluating a code implementation. Rate it on these criteria (0-10 scale):
+            if template:
+                instructions = render(
+                    template, diff=diff_text, user_request=user_request, branch=branch
+                )
+            else:
+                instructions = f"""You are evaluating a code implementation. Rate it on these criteria (0-10 scale):
 
 User's goal: {user_request}
 Branch: {branch}
 
 Code changes (git diff):
-{diff_text}
+{diff_text}"""
+
+            prompt = f"""{instructions}
 
 Evaluate and respond in JSON:
 {{
@@ -186,23 +260,228 @@ Evaluate and respond in JSON:
   "explanation": "2-3 sentence evaluation"
 }}"""
 
-        try:
-            result_text = await claude.analyze(prompt)
-            scores_data = json.loads(result_text)
-            results[branch] = JudgeScores(**scores_data)
-        except Exception as e:
-            logger.error(f"LLM judge failed for {branch}: {e}")
-            results[branch] = JudgeScores(
-                code_quality=0,
-                novelty=0,
-                feasibility=0,
-                goal_alignment=0,
-                completeness=0,
-                overall=0,
-                explanation=f"Evaluation failed: {e}",
+            async def sample(model: str | None) -> JudgeScores:
+                result_text = await claude.analyze(prompt, model=model)
+                return JudgeScores(**json.loads(result_text))
+
+            outcomes = await asyncio.gather(
+                *(sample(model) for model in judges), return_exceptions=True
+            )
+        _raise_rate_limit(outcomes)
+        samples = [o for o in outcomes if isinstance(o, JudgeScores)]
+        errors = [o for o in outcomes if isinstance(o, BaseException)]
+        for error in errors:
+            logger.error(f"LLM judge sample failed for {branch}: {error}")
+
+        if samples:
+            return _aggregate_judge_scores(samples, ensemble.aggregate)
+        e = errors[0] if errors else "no judges ran"
+        return JudgeScores(
+            code_quality=0,
+            novelty=0,
+            feasibility=0,
+            goal_alignment=0,
+            completeness=0,
+            overall=0,
+            explanation=f"Evaluation failed: {e}",
+        )
+
+    # Variants are judged concurrently, a few at a time.
+    scores = await asyncio.gather(*(judge(branch) for branch in variant_branches))
+    return dict(zip(variant_branches, scores))
+
+
+async def run_pairwise_judge(
+    variant_branches: list[str],
+    project_path: str,
+    user_request: str,
+    ensemble: JudgeEnsemble | None = None,
+) -> list[PairwiseComparison]:
+    """Have the LLM judge compare every pair of variants.
+
+    Relative judgements tend to be more consistent than absolute 0-10
+    scores. Each pair is judged by every sample in the ensemble and the
+    majority verdict kept.
+    """
+    ensemble = ensemble or JudgeEnsemble()
+    judges = [model or None for model in ensemble.models] or [None]
+    judges = [model for model in judges for _ in range(max(ensemble.samples, 1))]
+    claude = get_claude_client()
+    if not claude:
+        raise RuntimeError("Claude API client not available.")
+
+    path = Path(project_path)
+    base_branch = _get_default_branch(path)
+    diffs = {branch: _read_diff(path, base_branch, branch, 6000) for branch in variant_branches}
+
+    comparisons: list[PairwiseComparison] = []
+    for branch_a, branch_b in itertools.combinations(variant_branches, 2):
+        prompt = f"""You are comparing two implementations of the same goal.
+
+User's goal: {user_request}
+
+Implementation A (git diff):
+{diffs[branch_a]}
+
+Implementation B (git diff):
+{diffs[branch_b]}
+
+Which diff better implements the goal, considering correctness, code quality
+and completeness? Respond in JSON:
+{{
+  "winner": "a" | "b" | "tie",
+  "explanation": "1-2 sentence justification"
+}}"""
+
+        async def sample(model: str | None, prompt: str = prompt) -> dict:
+            return json.loads(await claude.analyze(prompt, model=model))
+
+        outcomes = await asyncio.gather(
+            *(sample(model) for model in judges), return_exceptions=True
+        )
+        _raise_rate_limit(outcomes)
+        verdicts = [
+            o for o in outcomes if isinstance(o, dict) and o.get("winner") in ("a", "b", "tie")
+        ]
+        for error in (o for o in outcomes if isinstance(o, BaseException)):
+            logger.error(f"Pairwise judge failed for {branch_a} vs {branch_b}: {error}")
+        if not verdicts:
+            continue
+
+        votes = {side: sum(v["winner"] == side for v in verdicts) for side in ("a", "b")}
+        if votes["a"] > votes["b"]:
+            winner = "a"
+        elif votes["b"] > votes["a"]:
+            winner = "b"
+        else:
+            winner = "tie"
+        explanation = next(
+            (v.get("explanation", "") for v in verdicts if v["winner"] == winner),
+            verdicts[0].get("explanation", ""),
+        )
+        comparisons.append(
+            PairwiseComparison(
+                branch_a=branch_a,
+                branch_b=branch_b,
+                winner=winner,
+                explanation=explanation,
             )
+        )
 
-    return results
+    return comparisons
+
+
+def _run_measured(command: str, cwd: Path, timeout: int) -> tuple[int, str, float | None]:
+    """Run ``command`` in a shell, returning its exit code, its output and
+    the peak memory in MB of it and the processes it waited for.
+
+    The peak is None where the platform doesn't report it. Raises
+    ``subprocess.TimeoutExpired`` after ``timeout`` seconds.
+    """
+    if not hasattr(os, "wait4"):
+        result = subprocess.run(
+            command, cwd=cwd, capture_output=True, text=True, timeout=timeout, shell=True
+        )
+        return result.returncode, result.stdout + result.stderr, None
+
+    # Output goes to a file rather than a pipe, so the child can be reaped
+    # with wait4, which is what reports its memory.
+    with tempfile.TemporaryFile() as out:
+        proc = subprocess.Popen(command, cwd=cwd, stdout=out, stderr=out, shell=True)
+        deadline = time.monotonic() + timeout
+        while True:
+            pid, status, usage = os.wait4(proc.pid, os.WNOHANG)
+            if pid:
+                break
+            if time.monotonic() > deadline:
+                proc.kill()
+                os.wait4(proc.pid, 0)
+                proc.returncode = -9
+                raise subprocess.TimeoutExpired(command, timeout)
+            time.sleep(0.02)
+        proc.returncode = os.waitstatus_to_exitcode(status)
+        out.seek(0)
+        output = out.read().decode(errors="replace")
+    # ru_maxrss is in bytes on macOS and kilobytes elsewhere.
+    peak_bytes = usage.ru_maxrss * (1 if sys.platform == "darwin" else 1024)
+    return proc.returncode, output, peak_bytes / (1024 * 1024)
+
+
+def _mean_std(samples: list[float]) -> tuple[float, float | None]:
+    """Mean of ``samples``, and their standard deviation if there are two or more."""
+    std = statistics.stdev(samples) if len(samples) > 1 else None
+    return statistics.fmean(samples), std
+
+
+def _raise_rate_limit(outcomes: list[object]) -> None:
+    """Re-raise a judge sample's rate limit rather than scoring without it.
+
+    The server answers it as a 429, so the TUI waits and retries.
+    """
+    for outcome in outcomes:
+        if isinstance(outcome, RATE_LIMIT_ERRORS):
+            raise outcome
+
+
+def _failed_tests(output: str) -> set[str]:
+    """Names of failed tests in cargo or pytest output.
+
+    Cargo names them on `test ... FAILED` lines, which quiet or captured
+    runs leave out, and again in the indented list under the last
+    `failures:` heading before each `test result:`.
+    """
+    failed = set()
+    in_failures = False
+    for raw in output.splitlines():
+        line = raw.strip()
+        if line == "failures:":
+            in_failures = True
+        elif in_failures and raw.startswith("    ") and line:
+            failed.add(line)
+        elif in_failures and line:
+            in_failures = False
+        if line.startswith("test ") and line.endswith(" ... FAILED"):
+            failed.add(line[len("test ") : -len(" ... FAILED")])
+        elif line.startswith("FAILED "):
+            failed.add(line[len("FAILED ") :].split(" - ")[0].strip())
+    return failed
+
+
+def _read_diff(project_path: Path, base_branch: str, branch: str, limit: int = 10000) -> str:
+    """The variant's changes against the base branch, truncated to ``limit``."""
+    try:
+        diff_result = subprocess.run(
+            ["git", "diff", f"{base_branch}...{branch}"],
+            cwd=project_path,
+            capture_output=True,
+            text=True,
+        )
+        return diff_result.stdout[:limit]
+    except Exception:
+        return "(Could not read diff)"
+
+
+# Overall scores further apart than this (on the 0-10 scale) are flagged.
+_DISAGREEMENT_SPREAD = 2.0
+
+
+def _aggregate_judge_scores(samples: list[JudgeScores], method: str) -> JudgeScores:
+    """Combine several judge samples into one set of scores."""
+    combine = statistics.median if method == "median" else statistics.fmean
+    fields = ("code_quality", "novelty", "feasibility", "goal_alignment", "completeness", "overall")
+    combined = {field: combine([getattr(s, field) for s in samples]) for field in fields}
+
+    overall = [s.overall for s in samples]
+    spread = max(overall) - min(overall)
+    # Explain with the sample closest to the combined verdict.
+    closest = min(samples, key=lambda s: abs(s.overall - combined["overall"]))
+    return JudgeScores(
+        **combined,
+        explanation=closest.explanation,
+        samples=len(samples),
+        spread=spread,
+        disagreement=spread >= _DISAGREEMENT_SPREAD,
+    )
 
 
 def _detect_build_command(project_path: Path) -> str | None:
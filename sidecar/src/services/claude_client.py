@@ -1,11 +1,16 @@
-"""Claude API client wrapper."""
+"""LLM API clients: Claude, and OpenAI-compatible APIs such as Ollama's."""
 
 from __future__ import annotations
 
 import logging
 import os
+from contextvars import ContextVar
 
 import anthropic
+import httpx
+
+from src.models.llm import LlmBackend
+from src.services import usage
 
 logger = logging.getLogger(__name__)
 
@@ -15,38 +20,40 @@ _client: ClaudeClient | None = None
 class ClaudeClient:
     """Wrapper around the Anthropic SDK for uniq-specific operations."""
 
-    def __init__(self, api_key: str, model: str = "claude-sonnet-4-20250514"):
-        self.client = anthropic.AsyncAnthropic(api_key=api_key)
+    def __init__(
+        self,
+        api_key: str,
+        model: str = "claude-sonnet-4-20250514",
+        base_url: str | None = None,
+    ):
+        self.client = anthropic.AsyncAnthropic(api_key=api_key, base_url=base_url)
         self.model = model
 
-    async def analyze(self, prompt: str, max_tokens: int = 4096) -> str:
+    async def analyze(
+        self,
+        prompt: str,
+        max_tokens: int = 4096,
+        model: str | None = None,
+    ) -> str:
         """Send a prompt to Claude and return the text response.
 
         The prompt should request JSON output. This method returns the raw
-        text response — the caller is responsible for parsing.
+        text response — the caller is responsible for parsing. ``model``
+        overrides the client's default model for this call.
         """
         try:
             message = await self.client.messages.create(
-                model=self.model,
+                model=model or self.model,
                 max_tokens=max_tokens,
                 messages=[{"role": "user", "content": prompt}],
             )
+            _record_usage(message)
             # Extract text from the response.
             text = ""
             for block in message.content:
                 if block.type == "text":
                     text += block.text
-
-            # Try to extract JSON if wrapped in markdown code blocks.
-            text = text.strip()
-            if text.startswith("```json"):
-                text = text[7:]
-            elif text.startswith("```"):
-                text = text[3:]
-            if text.endswith("```"):
-                text = text[:-3]
-
-            return text.strip()
+            return _strip_fence(text)
         except Exception as e:
             logger.error(f"Claude API error: {e}")
             raise
@@ -65,6 +72,7 @@ class ClaudeClient:
                 system=system_prompt,
                 messages=[{"role": "user", "content": user_prompt}],
             )
+            _record_usage(message)
             text = ""
             for block in message.content:
                 if block.type == "text":
@@ -74,30 +82,162 @@ class ClaudeClient:
             logger.error(f"Claude API error during code generation: {e}")
             raise
 
+    async def ping(self) -> None:
+        """Check the key and the connection with a call that costs nothing."""
+        await self.client.models.list(limit=1)
+
+
+class OpenAIClient:
+    """Client for OpenAI's chat completions API, or a server with the same
+    API such as Ollama, with the same methods as ``ClaudeClient``."""
+
+    def __init__(self, api_key: str, model: str, base_url: str, provider: str = "openai"):
+        headers = {"Authorization": f"Bearer {api_key}"} if api_key else {}
+        self.client = httpx.AsyncClient(base_url=base_url, headers=headers, timeout=600)
+        self.model = model
+        # Usage is reported per provider, since Ollama's is free.
+        self.provider = provider
+
+    async def _complete(self, messages: list[dict[str, str]], max_tokens: int, model: str) -> str:
+        response = await self.client.post(
+            "/chat/completions",
+            json={"model": model, "max_tokens": max_tokens, "messages": messages},
+        )
+        if response.status_code == 429:
+            raise BackendRateLimitError(response.headers.get("retry-after", "10"))
+        response.raise_for_status()
+        data = response.json()
+        tokens = data.get("usage") or {}
+        usage.record(
+            self.provider,
+            data.get("model", model),
+            tokens.get("prompt_tokens", 0),
+            tokens.get("completion_tokens", 0),
+        )
+        return data["choices"][0]["message"].get("content") or ""
+
+    async def analyze(
+        self,
+        prompt: str,
+        max_tokens: int = 4096,
+        model: str | None = None,
+    ) -> str:
+        """Send a prompt and return the text response, without a code fence."""
+        try:
+            text = await self._complete(
+                [{"role": "user", "content": prompt}], max_tokens, model or self.model
+            )
+            return _strip_fence(text)
+        except Exception as e:
+            logger.error(f"LLM API error: {e}")
+            raise
+
+    async def generate_code(
+        self,
+        system_prompt: str,
+        user_prompt: str,
+        max_tokens: int = 8192,
+    ) -> str:
+        """Generate code with a system prompt for context."""
+        try:
+            text = await self._complete(
+                [
+                    {"role": "system", "content": system_prompt},
+                    {"role": "user", "content": user_prompt},
+                ],
+                max_tokens,
+                self.model,
+            )
+            return text.strip()
+        except Exception as e:
+            logger.error(f"LLM API error during code generation: {e}")
+            raise
+
+    async def ping(self) -> None:
+        """Check the key and the connection by listing the models."""
+        response = await self.client.get("/models")
+        response.raise_for_status()
+
+
+class BackendRateLimitError(Exception):
+    """An OpenAI-style backend answered 429."""
+
+    def __init__(self, retry_after: str):
+        super().__init__("LLM API rate limit reached")
+        self.retry_after = retry_after
+
+
+LlmClient = ClaudeClient | OpenAIClient
+
+# What a rate-limited call to any backend raises. Routes let these through,
+# so the server answers 429 and the TUI waits and retries.
+RATE_LIMIT_ERRORS = (anthropic.RateLimitError, BackendRateLimitError)
+
+# API roots used when a request's backend doesn't name one.
+_DEFAULT_BASE_URLS = {
+    "openai": "https://api.openai.com/v1",
+    "ollama": "http://localhost:11434/v1",
+}
+
+# Backend of the request being handled; the default Claude client when unset.
+current_backend: ContextVar[LlmBackend | None] = ContextVar("current_backend", default=None)
+
+# Clients for request backends, keyed by (provider, model, base URL).
+_clients: dict[tuple[str, str, str | None], LlmClient] = {}
+
+
+def use_backend(backend: LlmBackend | None) -> None:
+    """Run the rest of this request, and tasks it starts, on ``backend``."""
+    current_backend.set(backend)
 
-def get_claude_client() -> ClaudeClient | None:
-    """Get or create the global Claude client.
 
-    Returns None if no API key is configured.
+async def ping(backend: LlmBackend) -> str | None:
+    """Make a call to ``backend`` that costs nothing, so a bad key or an
+    unreachable server shows up before the first real call. Returns why it
+    failed, or None if it didn't."""
+    use_backend(backend)
+    client = get_claude_client()
+    if client is None:
+        return f"No API key for {backend.provider}"
+    try:
+        await client.ping()
+    except Exception as e:
+        logger.warning(f"Ping to {backend.provider} failed: {e}")
+        return str(e) or type(e).__name__
+    return None
+
+
+def _strip_fence(text: str) -> str:
+    """Strip a markdown code block around a (JSON) response, if any."""
+    text = text.strip()
+    if text.startswith("```json"):
+        text = text[7:]
+    elif text.startswith("```"):
+        text = text[3:]
+    if text.endswith("```"):
+        text = text[:-3]
+    return text.strip()
+
+
+def _record_usage(message: anthropic.types.Message) -> None:
+    usage.record("anthropic", message.model, message.usage.input_tokens, message.usage.output_tokens)
+
+
+def get_claude_client() -> LlmClient | None:
+    """Get or create the client for the current request's backend, or the
+    global Claude client if the request didn't name one.
+
+    Returns None if the backend needs an API key and none is configured.
     """
+    backend = current_backend.get()
+    if backend is not None:
+        return _backend_client(backend)
+
     global _client
     if _client is not None:
         return _client
 
     api_key = os.environ.get("ANTHROPIC_API_KEY", "")
-    if not api_key:
-        # Try loading from uniq config.
-        config_path = os.path.expanduser("~/.config/uniq/config.toml")
-        if os.path.exists(config_path):
-            try:
-                import tomllib
-
-                with open(config_path, "rb") as f:
-                    config = tomllib.load(f)
-                api_key = config.get("api_keys", {}).get("anthropic", "")
-            except Exception:
-                pass
-
     if not api_key:
         logger.warning("No Anthropic API key found. Claude features will be unavailable.")
         return None
@@ -105,3 +245,29 @@ def get_claude_client() -> ClaudeClient | None:
     model = os.environ.get("UNIQ_CLAUDE_MODEL", "claude-sonnet-4-20250514")
     _client = ClaudeClient(api_key=api_key, model=model)
     return _client
+
+
+def _backend_client(backend: LlmBackend) -> LlmClient | None:
+    key = (backend.provider, backend.model, backend.base_url)
+    if key in _clients:
+        return _clients[key]
+
+    if backend.provider == "anthropic":
+        api_key = os.environ.get("ANTHROPIC_API_KEY", "")
+        if not api_key:
+            logger.warning("No Anthropic API key found. Claude features will be unavailable.")
+            return None
+        client: LlmClient = ClaudeClient(api_key, backend.model, backend.base_url)
+    elif backend.provider == "openai":
+        api_key = os.environ.get("OPENAI_API_KEY", "")
+        if not api_key and not backend.base_url:
+            logger.warning("No OpenAI API key found. LLM features will be unavailable.")
+            return None
+        base_url = backend.base_url or _DEFAULT_BASE_URLS["openai"]
+        client = OpenAIClient(api_key, backend.model, base_url)
+    else:
+        base_url = backend.base_url or _DEFAULT_BASE_URLS["ollama"]
+        client = OpenAIClient("", backend.model, base_url, provider="ollama")
+
+    _clients[key] = client
+    return client
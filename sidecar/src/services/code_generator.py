@@ -2,58 +2,184 @@
 
 from __future__ import annotations
 
-import contextlib
+import asyncio
 import json
 import logging
 import subprocess
+from collections.abc import Callable
 from pathlib import Path
 
+
 from src.models.paper import TechniqueCard
 from src.models.project import ProjectProfile
-from src.models.variant import VariantResult
-from src.services.claude_client import get_claude_client
+from src.models.variant import GenerationTranscript, ImplementationPlan, VariantResult
+from src.services.claude_client import RATE_LIMIT_ERRORS, get_claude_client
+from src.services.dependency_installer import install_dependencies, manifest_files
+from src.services.prompts import render
+from src.services.worktree import new_branch_worktree, write_file
 
 logger = logging.getLogger(__name__)
 
 
+def _project_context(project: ProjectProfile) -> str:
+    return f"""Project details:
+- Languages: {", ".join(project.languages)}
+- Summary: {project.summary}
+- File tree:
+{project.file_tree[:3000]}"""
+
+
+def _technique_brief(
+    technique: TechniqueCard,
+    project: ProjectProfile,
+    feedback: str | None,
+    style: str | None = None,
+) -> str:
+    brief = f"""Technique: {technique.name}
+Paper: {technique.paper_title} ({technique.paper_id})
+
+Methodology:
+{technique.methodology}
+
+Key Components: {", ".join(technique.key_components)}
+
+Required Data Format: {technique.required_data_format}
+
+Integration Approach:
+{technique.integration_approach}
+
+User's Goal: {project.user_request}"""
+
+    if style:
+        brief += f"""
+
+Implementation style: {style}"""
+
+    if feedback:
+        brief += f"""
+
+This is a regeneration of an earlier attempt. Follow these additional
+instructions from the user exactly:
+{feedback}"""
+    return brief
+
+
+def _technique_request(
+    intro: str,
+    technique: TechniqueCard,
+    project: ProjectProfile,
+    feedback: str | None,
+    style: str | None,
+    template: str | None,
+) -> str:
+    """The user prompt describing what to implement, from the user's
+    template if they sent one."""
+    brief = _technique_brief(technique, project, feedback, style)
+    if template:
+        return render(
+            template,
+            technique=brief,
+            project=_project_context(project),
+            user_request=project.user_request,
+        )
+    return f"""{intro}
+
+{brief}"""
+
+
+def _extract_json(text: str) -> str:
+    """Strip a markdown code fence around a JSON response, if any."""
+    if "```json" in text:
+        return text.split("```json")[1].split("```")[0]
+    if "```" in text:
+        return text.split("```")[1].split("```")[0]
+    return text
+
+
+async def plan_variant_code(
+    technique: TechniqueCard,
+    project: ProjectProfile,
+    feedback: str | None = None,
+    style: str | None = None,
+    template: str | None = None,
+) -> ImplementationPlan:
+    """Ask Claude for an implementation plan, without touching the project."""
+    claude = get_claude_client()
+    if not claude:
+        raise RuntimeError("Claude API client not available. Set ANTHROPIC_API_KEY.")
+
+    system_prompt = f"""You are an expert software engineer planning how to implement a research technique in an existing codebase.
+
+{_project_context(project)}
+
+Do not write any code yet. Describe the changes you would make.
+
+Respond ONLY in JSON format:
+{{
+  "steps": ["step 1", "step 2"],
+  "files": [
+    {{"path": "...", "action": "create|modify", "purpose": "..."}}
+  ],
+  "new_modules": ["..."],
+  "dependencies": ["lib1", "lib2"]
+}}"""
+
+    user_prompt = _technique_request(
+        "Plan the implementation of the following research technique:",
+        technique,
+        project,
+        feedback,
+        style,
+        template,
+    )
+
+    result_text = await claude.generate_code(system_prompt, user_prompt)
+    return ImplementationPlan.model_validate_json(_extract_json(result_text))
+
+
 async def generate_variant_code(
     technique: TechniqueCard,
     project: ProjectProfile,
     branch_name: str,
+    feedback: str | None = None,
+    plan: ImplementationPlan | None = None,
+    style: str | None = None,
+    install_deps: bool = False,
+    template: str | None = None,
+    on_progress: Callable[[str], None] | None = None,
 ) -> VariantResult:
     """Generate a project variant by applying a technique using Claude.
 
-    1. Create a git branch.
+    1. Create a git branch, checked out in a temporary worktree.
     2. Ask Claude to generate the code modifications.
     3. Apply the modifications to the branch.
-    4. Commit the changes.
+    4. Install any new dependencies.
+    5. Commit the changes.
     """
     claude = get_claude_client()
     if not claude:
         raise RuntimeError("Claude API client not available. Set ANTHROPIC_API_KEY.")
 
     project_path = Path(project.path)
+    transcript = GenerationTranscript()
 
-    # Create a new git branch.
-    try:
-        subprocess.run(
-            ["git", "checkout", "-b", branch_name],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
-    except subprocess.CalledProcessError as e:
-        raise RuntimeError(f"Failed to create branch {branch_name}: {e.stderr.decode()}") from e
+    def progress(step: str) -> None:
+        if on_progress:
+            on_progress(step)
+
+    progress("creating branch")
 
     try:
-        # Build the code generation prompt.
-        system_prompt = f"""You are an expert software engineer implementing a research technique into an existing codebase.
+        # Everything is written in a worktree of its own, branched from the
+        # last commit, so neither the user's checkout nor anything
+        # uncommitted in it is touched.
+        with new_branch_worktree(project_path, branch_name) as worktree:
+            transcript.log.append(f"Created branch {branch_name}")
 
-Project details:
-- Languages: {", ".join(project.languages)}
-- Summary: {project.summary}
-- File tree:
-{project.file_tree[:3000]}
+            # Build the code generation prompt.
+            system_prompt = f"""You are an expert software engineer implementing a research technique into an existing codebase.
+
+{_project_context(project)}
 
 Your task is to implement the research technique described below into this project.
 Generate file modifications as a JSON array of objects, each with:
@@ -61,10 +187,12 @@ Generate file modifications as a JSON array of objects, each with:
 - "content": the complete file content
 - "action": "create" or "modify"
 
-Also list any new dependencies needed.
+Also list any new dependencies needed, and start with a short step-by-step
+plan of the changes you are about to make.
 
 Respond ONLY in JSON format:
 {{
+  "plan": ["step 1", "step 2"],
   "files": [
     {{"path": "...", "content": "...", "action": "create|modify"}}
   ],
@@ -72,48 +200,67 @@ Respond ONLY in JSON format:
   "technique_summary": "Brief description of what was implemented"
 }}"""
 
-        user_prompt = f"""Implement the following research technique:
-
-Technique: {technique.name}
-Paper: {technique.paper_title} ({technique.paper_id})
-
-Methodology:
-{technique.methodology}
-
-Key Components: {", ".join(technique.key_components)}
-
-Required Data Format: {technique.required_data_format}
-
-Integration Approach:
-{technique.integration_approach}
-
-User's Goal: {project.user_request}
-
-Generate the implementation now."""
-
-        result_text = await claude.generate_code(system_prompt, user_prompt)
-
-        # Parse the JSON response.
-        # Try to extract JSON if wrapped in markdown.
-        if "```json" in result_text:
-            result_text = result_text.split("```json")[1].split("```")[0]
-        elif "```" in result_text:
-            result_text = result_text.split("```")[1].split("```")[0]
-
-        result_data = json.loads(result_text)
-
-        modified_files = []
-        new_dependencies = result_data.get("dependencies", [])
-
-        # Apply file changes.
-        for file_change in result_data.get("files", []):
-            file_path = project_path / file_change["path"]
-            file_path.parent.mkdir(parents=True, exist_ok=True)
-            file_path.write_text(file_change["content"])
-            modified_files.append(file_change["path"])
+            user_prompt = _technique_request(
+                "Implement the following research technique:",
+                technique,
+                project,
+                feedback,
+                style,
+                template,
+            )
 
-        # Write a TECHNIQUE.md file.
-        technique_md = f"""# {technique.name}
+            if plan:
+                user_prompt += f"""
+
+The user approved this implementation plan. Follow it; do not touch
+files outside it unless strictly required:
+{plan.model_dump_json(indent=2)}"""
+
+            user_prompt += "\n\nGenerate the implementation now."
+
+            transcript.log.append(f"Requested implementation of '{technique.name}' from Claude")
+            progress("waiting for Claude")
+            result_text = await claude.generate_code(system_prompt, user_prompt)
+            transcript.log.append(f"Received response ({len(result_text)} chars)")
+
+            # Parse the JSON response.
+            result_data = json.loads(_extract_json(result_text))
+            transcript.plan = [str(step) for step in result_data.get("plan", [])]
+            transcript.explanation = result_data.get("technique_summary", "")
+
+            modified_files = []
+            new_dependencies = result_data.get("dependencies", [])
+
+            # Apply file changes. Written off the event loop, which also lets
+            # /generation-progress report each file as it's written.
+            for file_change in result_data.get("files", []):
+                file_path = worktree / file_change["path"]
+                progress(f"writing {file_change['path']}")
+                await asyncio.to_thread(write_file, file_path, file_change["content"])
+                modified_files.append(file_change["path"])
+                transcript.files_written.append(
+                    f"{file_change.get('action', 'modify')} {file_change['path']}"
+                )
+            transcript.log.append(f"Wrote {len(modified_files)} files")
+
+            # Install new dependencies so unresolvable packages surface now
+            # rather than at benchmark time.
+            dependency_check = None
+            if install_deps and new_dependencies:
+                progress("installing dependencies")
+                dependency_check = await asyncio.to_thread(
+                    install_dependencies, worktree, new_dependencies, project_path
+                )
+                if dependency_check:
+                    transcript.log.append(
+                        f"Installed {len(dependency_check.installed)}/{len(new_dependencies)} "
+                        f"dependencies with {dependency_check.installer}"
+                    )
+                    for failed in dependency_check.failed:
+                        transcript.log.append(f"Could not install {failed.name}: {failed.error}")
+
+            # Write a TECHNIQUE.md file.
+            technique_md = f"""# {technique.name}
 
 **Source Paper:** {technique.paper_title}
 **Paper ID:** {technique.paper_id}
@@ -139,49 +286,50 @@ Generate the implementation now."""
 
 {chr(10).join(f"- {f}" for f in modified_files)}
 """
-        (project_path / "TECHNIQUE.md").write_text(technique_md)
-        modified_files.append("TECHNIQUE.md")
-
-        # Git add and commit.
-        subprocess.run(
-            ["git", "add", "-A"],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
-        subprocess.run(
-            [
-                "git",
-                "commit",
-                "-m",
-                f"uniq: Apply technique '{technique.name}' from {technique.paper_id}",
-            ],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
+            (worktree / "TECHNIQUE.md").write_text(technique_md)
+            modified_files.append("TECHNIQUE.md")
 
-        # Switch back to the original branch.
-        subprocess.run(
-            ["git", "checkout", "-"],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
+            # Stage what was written and the dependency manifests, never
+            # what an installer left around. Git skips (and complains about)
+            # ignored paths but stages the rest.
+            progress("committing")
+            subprocess.run(
+                ["git", "add", "--", *modified_files, *manifest_files(worktree)],
+                cwd=worktree,
+                capture_output=True,
+            )
+            subprocess.run(
+                [
+                    "git",
+                    "commit",
+                    "-m",
+                    f"uniq: Apply technique '{technique.name}' from {technique.paper_id}",
+                ],
+                cwd=worktree,
+                check=True,
+                capture_output=True,
+            )
+            transcript.log.append(f"Committed changes to {branch_name}")
 
         return VariantResult(
             success=True,
             modified_files=modified_files,
             new_dependencies=new_dependencies,
+            transcript=transcript,
+            dependency_check=dependency_check,
         )
 
+    except asyncio.CancelledError:
+        # The worktree, with whatever was written to it, and the branch are
+        # already gone; the user's checkout was never touched.
+        logger.info(f"Variant generation cancelled: {branch_name}")
+        raise
+
+    except RATE_LIMIT_ERRORS:
+        # Answered as a 429 by the server, so the TUI waits and retries.
+        raise
+
     except Exception as e:
-        # Attempt to switch back to the original branch on failure.
-        with contextlib.suppress(Exception):
-            subprocess.run(
-                ["git", "checkout", "-"],
-                cwd=project_path,
-                capture_output=True,
-            )
         logger.error(f"Variant generation failed: {e}")
-        return VariantResult(success=False, error=str(e))
+        transcript.log.append(f"Failed: {e}")
+        return VariantResult(success=False, error=str(e), transcript=transcript)
@@ -0,0 +1,45 @@
+"""Correlation IDs from the Rust side, so the sidecar's log lines for one
+pipeline operation can be matched with the TUI's.
+
+The TUI sends the ID of the operation a request belongs to in the
+``X-Correlation-ID`` header. It's kept for the request and any tasks it
+starts, and every log record carries it as ``correlation_id``.
+"""
+
+from __future__ import annotations
+
+import logging
+from contextvars import ContextVar
+
+HEADER = "X-Correlation-ID"
+
+# ID of the operation the request being handled belongs to.
+current_id: ContextVar[str] = ContextVar("correlation_id", default="-")
+
+LOG_FORMAT = "%(asctime)s %(levelname)s %(name)s [%(correlation_id)s] %(message)s"
+
+
+class CorrelationFilter(logging.Filter):
+    """Adds the current correlation ID to each record."""
+
+    def filter(self, record: logging.LogRecord) -> bool:
+        record.correlation_id = current_id.get()
+        return True
+
+
+async def tag_request(request, call_next):
+    """Middleware: tag a request's log lines with the TUI operation it
+    belongs to."""
+    current_id.set(request.headers.get(HEADER, "-"))
+    return await call_next(request)
+
+
+def configure_logging(level: int = logging.INFO) -> None:
+    """Log to stderr, which the TUI copies into its own log, with the
+    correlation ID on every line."""
+    handler = logging.StreamHandler()
+    handler.addFilter(CorrelationFilter())
+    handler.setFormatter(logging.Formatter(LOG_FORMAT))
+    root = logging.getLogger()
+    root.addHandler(handler)
+    root.setLevel(level)
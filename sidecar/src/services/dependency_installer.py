@@ -0,0 +1,144 @@
+"""Dependency installer — adds a variant's new dependencies on its branch."""
+
+from __future__ import annotations
+
+import logging
+import subprocess
+import sys
+from dataclasses import dataclass
+from pathlib import Path
+
+from src.models.variant import DependencyCheck, FailedDependency
+
+logger = logging.getLogger(__name__)
+
+
+@dataclass(frozen=True)
+class Installer:
+    """How one package manager adds a dependency."""
+
+    name: str
+    # Marker file the project is recognised by.
+    marker: str
+    # Command that adds one dependency (appended) to the project.
+    command: tuple[str, ...]
+    # Files the installer records dependencies in; only these are committed,
+    # never what it installs (node_modules, .venv, ...).
+    manifests: tuple[str, ...]
+
+
+# In order of preference when a project has several markers. Every command
+# records the dependency in the manifest and lockfile without installing
+# anything into the worktree; pip has no such mode, so it installs into the
+# project's own virtualenv and the requirement is written by hand.
+INSTALLERS = (
+    Installer("cargo", "Cargo.toml", ("cargo", "add"), ("Cargo.toml", "Cargo.lock")),
+    Installer(
+        "npm",
+        "package.json",
+        ("npm", "install", "--package-lock-only"),
+        ("package.json", "package-lock.json"),
+    ),
+    Installer("uv", "pyproject.toml", ("uv", "add", "--no-sync"), ("pyproject.toml", "uv.lock")),
+    Installer("pip", "requirements.txt", ("-m", "pip", "install"), ("requirements.txt",)),
+    Installer("go", "go.mod", ("go", "get"), ("go.mod", "go.sum")),
+)
+
+# Where a project's virtualenv is looked for, relative to the project.
+VENV_DIRS = (".venv", "venv")
+
+
+def install_dependencies(
+    worktree: Path,
+    dependencies: list[str],
+    project_path: Path,
+    timeout_seconds: int = 300,
+) -> DependencyCheck | None:
+    """Add each dependency with the project's package manager.
+
+    Runs in the variant's worktree, so the manifest changes are committed
+    along with the generated code; `manifest_files` says what to stage.
+    pip installs into the virtualenv of the project at `project_path`.
+    Returns None when there is nothing to install or no supported package
+    manager.
+    """
+    if not dependencies:
+        return None
+    installer = detect_installer(worktree)
+    if installer is None:
+        return None
+
+    check = DependencyCheck(installer=installer.name)
+    command = list(installer.command)
+    if installer.name == "pip":
+        python = project_python(project_path)
+        if python is None:
+            error = f"No virtualenv ({' or '.join(VENV_DIRS)}) in the project to install into"
+            check.failed = [FailedDependency(name=dep, error=error) for dep in dependencies]
+            return check
+        command = [str(python), *command]
+
+    for dep in dependencies:
+        try:
+            result = subprocess.run(
+                [*command, dep],
+                cwd=worktree,
+                capture_output=True,
+                text=True,
+                timeout=timeout_seconds,
+            )
+            if result.returncode == 0:
+                check.installed.append(dep)
+            else:
+                error = (result.stderr or result.stdout).strip()[-500:]
+                check.failed.append(FailedDependency(name=dep, error=error))
+        except subprocess.TimeoutExpired:
+            check.failed.append(
+                FailedDependency(name=dep, error=f"Timed out after {timeout_seconds}s")
+            )
+        except Exception as e:
+            check.failed.append(FailedDependency(name=dep, error=str(e)))
+
+    if installer.name == "pip" and check.installed:
+        _add_requirements(worktree / "requirements.txt", check.installed)
+
+    logger.info(
+        f"Installed {len(check.installed)}/{len(dependencies)} dependencies "
+        f"with {check.installer}"
+    )
+    return check
+
+
+def detect_installer(project_path: Path) -> Installer | None:
+    """The package manager of the project at `project_path`, if supported."""
+    return next((i for i in INSTALLERS if (project_path / i.marker).exists()), None)
+
+
+def manifest_files(project_path: Path) -> list[str]:
+    """The dependency manifests and lockfiles present in the project."""
+    installer = detect_installer(project_path)
+    if installer is None:
+        return []
+    return [name for name in installer.manifests if (project_path / name).exists()]
+
+
+def project_python(project_path: Path) -> Path | None:
+    """The interpreter of the project's virtualenv, if it has one."""
+    bin_dir = "Scripts" if sys.platform == "win32" else "bin"
+    for venv in VENV_DIRS:
+        python = project_path / venv / bin_dir / "python"
+        if python.exists():
+            return python
+    return None
+
+
+def _add_requirements(requirements: Path, dependencies: list[str]) -> None:
+    """Append the dependencies `requirements` doesn't list yet."""
+    text = requirements.read_text() if requirements.exists() else ""
+    listed = {line.strip().lower() for line in text.splitlines()}
+    missing = [dep for dep in dependencies if dep.strip().lower() not in listed]
+    if not missing:
+        return
+    if text and not text.endswith("\n"):
+        text += "\n"
+    requirements.write_text(text + "".join(f"{dep}\n" for dep in missing))
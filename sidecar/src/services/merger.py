@@ -1,16 +1,22 @@
-"""Variant merge service — combines two variants with configurable blend ratios."""
+"""Variant merge service — combines variants with configurable blend ratios."""
 
 from __future__ import annotations
 
+import asyncio
 import json
 import logging
 import subprocess
+from collections.abc import Callable
 from pathlib import Path
 from typing import Any
 
+
+from src.models.merge import MergeSource, ReconcileHunkRequest
 from src.models.project import ProjectProfile
 from src.models.variant import VariantResult
-from src.services.claude_client import get_claude_client
+from src.services.claude_client import RATE_LIMIT_ERRORS, get_claude_client
+from src.services.prompts import render, uses
+from src.services.worktree import new_branch_worktree, write_file
 
 logger = logging.getLogger(__name__)
 
@@ -24,20 +30,19 @@ BLEND_DESCRIPTIONS = {
 
 
 async def merge_variant_code(
-    variant_a_branch: str,
-    variant_a_technique: Any,
-    variant_b_branch: str,
-    variant_b_technique: Any,
-    blend_a: int,
-    blend_b: int,
+    sources: list[MergeSource],
     project: ProjectProfile,
     target_branch: str,
+    guidance: str | None = None,
+    strategy: str | None = None,
+    template: str | None = None,
+    on_progress: Callable[[str], None] | None = None,
 ) -> VariantResult:
-    """Merge two variants by having Claude create a hybrid implementation.
+    """Merge variants by having Claude create a hybrid implementation.
 
-    This is NOT a git merge — it's a semantic merge where Claude reads both
-    variant codebases and creates a new hybrid implementation based on the
-    specified blend ratios.
+    This is NOT a git merge — it's a semantic merge where Claude reads every
+    source variant's codebase and creates a new hybrid implementation based on
+    each source's blend ratio.
     """
     claude = get_claude_client()
     if not claude:
@@ -45,53 +50,32 @@ async def merge_variant_code(
 
     project_path = Path(project.path)
 
-    # Read the code from both variant branches.
-    code_a = _read_branch_code(project_path, variant_a_branch)
-    code_b = _read_branch_code(project_path, variant_b_branch)
+    def progress(step: str) -> None:
+        if on_progress:
+            on_progress(step)
 
-    # Create the target branch from the original (non-variant) branch.
-    # Determine the base branch (usually main or master).
-    base_branch = _get_default_branch(project_path)
+    # Read the code from every source branch, splitting the prompt budget.
+    labels = [chr(ord("A") + i) for i in range(len(sources))]
+    code_budget = 16000 // len(sources)
+    progress("reading sources")
+    codes = [_read_branch_code(project_path, s.branch)[:code_budget] for s in sources]
 
-    try:
-        subprocess.run(
-            ["git", "checkout", base_branch],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
-        subprocess.run(
-            ["git", "checkout", "-b", target_branch],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
-    except subprocess.CalledProcessError as e:
-        raise RuntimeError(f"Failed to create merge branch: {e.stderr.decode()}") from e
+    # The merge branches from the original (non-variant) branch, usually
+    # main or master.
+    base_branch = _get_default_branch(project_path)
 
     try:
-        blend_a_desc = BLEND_DESCRIPTIONS.get(blend_a, f"{blend_a}% integration")
-        blend_b_desc = BLEND_DESCRIPTIONS.get(blend_b, f"{blend_b}% integration")
+        descs = [BLEND_DESCRIPTIONS.get(s.blend, f"{s.blend}% integration") for s in sources]
+        count = len(sources)
 
-        technique_a_str = (
-            json.dumps(variant_a_technique, indent=2)
-            if isinstance(variant_a_technique, dict)
-            else str(variant_a_technique)
-        )
-        technique_b_str = (
-            json.dumps(variant_b_technique, indent=2)
-            if isinstance(variant_b_technique, dict)
-            else str(variant_b_technique)
-        )
-
-        system_prompt = f"""You are an expert software engineer creating a hybrid implementation that merges two different AI techniques into a single codebase.
+        system_prompt = f"""You are an expert software engineer creating a hybrid implementation that merges {count} different AI techniques into a single codebase.
 
 Project details:
 - Languages: {", ".join(project.languages)}
 - Summary: {project.summary}
 - User's goal: {project.user_request}
 
-You must create a NEW implementation that combines both techniques according to the specified blend ratios. This is a semantic merge — not a mechanical merge of code.
+You must create a NEW implementation that combines all the techniques according to the specified blend ratios. This is a semantic merge — not a mechanical merge of code.
 
 Generate file modifications as a JSON array:
 {{
@@ -99,32 +83,60 @@ Generate file modifications as a JSON array:
     {{"path": "...", "content": "...", "action": "create|modify"}}
   ],
   "dependencies": ["lib1", "lib2"],
-  "merge_summary": "How the two techniques were combined"
+  "merge_summary": "How the techniques were combined"
 }}"""
 
-        user_prompt = f"""Create a hybrid implementation combining these two techniques:
+        technique_sections = "\n".join(
+            f"""=== TECHNIQUE {label} (blend: {s.blend}% — {desc}) ===
+{_technique_str(s.technique)}
 
-=== TECHNIQUE A (blend: {blend_a}% — {blend_a_desc}) ===
-{technique_a_str}
+Code from variant {label} branch ({s.branch}):
+{code}
+"""
+            for label, s, desc, code in zip(labels, sources, descs, codes)
+        )
+        blend_instructions = "\n".join(
+            f"- Technique {label} should be {desc} ({s.blend}%)"
+            + _component_lines(label, s, "  - Use {blend}% of {label}'s {name}")
+            for label, s, desc in zip(labels, sources, descs)
+        )
 
-Code from variant A branch ({variant_a_branch}):
-{code_a[:8000]}
+        strategy_line = f"- Integration strategy: {strategy}\n" if strategy else ""
 
-=== TECHNIQUE B (blend: {blend_b}% — {blend_b_desc}) ===
-{technique_b_str}
+        guidance_section = ""
+        if guidance:
+            guidance_section = f"""
+=== USER GUIDANCE ===
+Follow these instructions from the user exactly; where they are more specific
+than the blend ratios, they take precedence:
+{guidance}
+"""
 
-Code from variant B branch ({variant_b_branch}):
-{code_b[:8000]}
+        if template:
+            user_prompt = render(
+                template,
+                techniques=technique_sections,
+                instructions=f"{blend_instructions}\n{strategy_line}".rstrip(),
+                project=project.summary,
+                user_request=project.user_request,
+                guidance=guidance or "",
+            )
+            # Guidance the template has no place for goes after it.
+            if not uses(template, "guidance"):
+                user_prompt += guidance_section
+        else:
+            user_prompt = f"""Create a hybrid implementation combining these {count} techniques:
 
+{technique_sections}
 === MERGE INSTRUCTIONS ===
-- Technique A should be {blend_a_desc} ({blend_a}%)
-- Technique B should be {blend_b_desc} ({blend_b}%)
-- Create a cohesive implementation that intelligently combines both approaches
-- Resolve any conflicts between the two techniques
+{blend_instructions}
+{strategy_line}- Create a cohesive implementation that intelligently combines all approaches
+- Resolve any conflicts between the techniques
 - Ensure the merged code is functional and well-structured
-
+{guidance_section}
 Generate the merged implementation now."""
 
+        progress("waiting for Claude")
         result_text = await claude.generate_code(system_prompt, user_prompt)
 
         # Parse JSON response.
@@ -138,19 +150,33 @@ Generate the merged implementation now."""
         modified_files = []
         new_dependencies = result_data.get("dependencies", [])
 
-        # Apply file changes.
-        for file_change in result_data.get("files", []):
-            file_path = project_path / file_change["path"]
-            file_path.parent.mkdir(parents=True, exist_ok=True)
-            file_path.write_text(file_change["content"])
-            modified_files.append(file_change["path"])
-
-        # Write TECHNIQUE.md for the merge.
-        merge_summary = result_data.get("merge_summary", "Hybrid merge of two techniques")
-        technique_md = f"""# Merged Variant
+        # The merge is written in a worktree of its own, branched from the
+        # base branch, so the user's checkout is never touched and nothing
+        # of a cancelled or failed merge is left behind.
+        progress("creating branch")
+        with new_branch_worktree(project_path, target_branch, base_branch) as worktree:
+            # Apply file changes.
+            for file_change in result_data.get("files", []):
+                progress(f"writing {file_change['path']}")
+                file_path = worktree / file_change["path"]
+                await asyncio.to_thread(write_file, file_path, file_change["content"])
+                modified_files.append(file_change["path"])
+
+            # Write TECHNIQUE.md for the merge.
+            merge_summary = result_data.get("merge_summary", f"Hybrid merge of {count} techniques")
+            source_lines = "\n".join(
+                f"**Source {label}:** {s.branch} ({s.blend}%)  " for label, s in zip(labels, sources)
+            )
+            blend_lines = "\n".join(
+                f"- **{label} ({s.blend}%):** {desc}"
+                + _component_lines(label, s, "  - {name}: {blend}%")
+                for label, s, desc in zip(labels, sources, descs)
+            )
+            guidance_md = f"\n## Guidance\n\n{guidance}\n" if guidance else ""
+            strategy_md = f"\n## Strategy\n\n{strategy}\n" if strategy else ""
+            technique_md = f"""# Merged Variant
 
-**Source A:** {variant_a_branch} ({blend_a}%)
-**Source B:** {variant_b_branch} ({blend_b}%)
+{source_lines}
 
 ## Merge Summary
 
@@ -158,9 +184,8 @@ Generate the merged implementation now."""
 
 ## Blend Ratios
 
-- **A ({blend_a}%):** {blend_a_desc}
-- **B ({blend_b}%):** {blend_b_desc}
-
+{blend_lines}
+{strategy_md}{guidance_md}
 ## New Dependencies
 
 {chr(10).join(f"- {d}" for d in new_dependencies)}
@@ -169,55 +194,132 @@ Generate the merged implementation now."""
 
 {chr(10).join(f"- {f}" for f in modified_files)}
 """
-        (project_path / "TECHNIQUE.md").write_text(technique_md)
-        modified_files.append("TECHNIQUE.md")
-
-        # Git add and commit.
-        subprocess.run(
-            ["git", "add", "-A"],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
-        subprocess.run(
-            [
-                "git",
-                "commit",
-                "-m",
-                f"uniq: Merge {variant_a_branch} ({blend_a}%) + {variant_b_branch} ({blend_b}%)",
-            ],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
+            (worktree / "TECHNIQUE.md").write_text(technique_md)
+            modified_files.append("TECHNIQUE.md")
 
-        # Switch back.
-        subprocess.run(
-            ["git", "checkout", base_branch],
-            cwd=project_path,
-            check=True,
-            capture_output=True,
-        )
+            # Git add and commit.
+            progress("committing")
+            subprocess.run(
+                ["git", "add", "-A"],
+                cwd=worktree,
+                check=True,
+                capture_output=True,
+            )
+            subprocess.run(
+                [
+                    "git",
+                    "commit",
+                    "-m",
+                    "uniq: Merge " + " + ".join(f"{s.branch} ({s.blend}%)" for s in sources),
+                ],
+                cwd=worktree,
+                check=True,
+                capture_output=True,
+            )
 
         return VariantResult(
             success=True,
             modified_files=modified_files,
             new_dependencies=new_dependencies,
+            # Files the merge wrote itself are resolved already.
+            conflicts=[
+                path
+                for path in _find_conflicts(project_path, [s.branch for s in sources])
+                if path not in modified_files
+            ],
         )
 
+    except asyncio.CancelledError:
+        # The worktree, with whatever was written to it, and the branch are
+        # already gone.
+        logger.info(f"Merge cancelled: {target_branch}")
+        raise
+
+    except RATE_LIMIT_ERRORS:
+        # Answered as a 429 by the server, so the TUI waits and retries.
+        raise
+
     except Exception as e:
-        # Attempt recovery.
-        try:
-            base = _get_default_branch(project_path)
-            subprocess.run(
-                ["git", "checkout", base],
+        logger.error(f"Merge failed: {e}")
+        return VariantResult(success=False, error=str(e))
+
+
+async def reconcile_hunk(req: ReconcileHunkRequest) -> list[str]:
+    """Ask Claude for one version of a hunk two merge sources disagree on."""
+    claude = get_claude_client()
+    if not claude:
+        raise RuntimeError("Claude API client not available. Set ANTHROPIC_API_KEY.")
+
+    system_prompt = f"""You are an expert software engineer resolving a git merge conflict \
+in a project that combines different AI techniques.
+
+Project details:
+- Languages: {", ".join(req.project.languages)}
+- User's goal: {req.project.user_request}
+
+Reply with ONLY the lines that replace the conflicting region, in a single code block. \
+Do not repeat the surrounding context."""
+
+    def block(lines: list[str]) -> str:
+        return "\n".join(lines) if lines else "(empty)"
+
+    base_section = f"\n=== COMMON ANCESTOR ===\n{block(req.base)}\n" if req.base else ""
+    guidance_section = f"\n=== USER GUIDANCE ===\n{req.guidance}\n" if req.guidance else ""
+    user_prompt = f"""Reconcile this conflict in {req.path}.
+
+=== CONTEXT BEFORE ===
+{block(req.before)}
+
+=== SIDE A ({req.sources[0]}) ===
+{block(req.a)}
+
+=== SIDE B ({req.sources[1]}) ===
+{block(req.b)}
+{base_section}
+=== CONTEXT AFTER ===
+{block(req.after)}
+{guidance_section}
+Combine both sides so the intent of each technique is kept and the code is correct."""
+
+    text = await claude.generate_code(system_prompt, user_prompt, max_tokens=4096)
+    if "```" in text:
+        text = text.split("```")[1]
+        # Drop the language tag on the opening fence.
+        text = text.split("\n", 1)[1] if "\n" in text else ""
+    return text.rstrip("\n").split("\n")
+
+
+def _find_conflicts(project_path: Path, branches: list[str]) -> list[str]:
+    """Files that any two of `branches` conflict on when merged with git."""
+    conflicts: set[str] = set()
+    for i, a in enumerate(branches):
+        for b in branches[i + 1 :]:
+            result = subprocess.run(
+                ["git", "merge-tree", "--write-tree", "--name-only", "--no-messages", a, b],
                 cwd=project_path,
                 capture_output=True,
+                text=True,
             )
-        except Exception:
-            pass
-        logger.error(f"Merge failed: {e}")
-        return VariantResult(success=False, error=str(e))
+            # Exit code 1 means the merge has conflicts; the conflicted
+            # paths follow the tree id.
+            if result.returncode == 1:
+                conflicts.update(p for p in result.stdout.splitlines()[1:] if p)
+    return sorted(conflicts)
+
+
+def _component_lines(label: str, source: MergeSource, template: str) -> str:
+    """Per-component blend lines for a source, each prefixed with a newline."""
+    return "".join(
+        "\n" + template.format(label=label, name=c.name, blend=c.blend)
+        for c in source.components
+    )
+
+
+def _technique_str(technique: Any) -> str:
+    """Render a source's technique card (or merge lineage) for the prompt."""
+    if isinstance(technique, dict):
+        return json.dumps(technique, indent=2)
+    return str(technique)
 
 
 def _read_branch_code(project_path: Path, branch_name: str) -> str:
@@ -0,0 +1,25 @@
+"""User prompt templates sent by the client in place of the built-in prompts.
+
+The client checks a template's placeholders before sending it, so filling
+them in only has to replace the known `{name}`s and leave every other brace
+alone (templates may quote JSON).
+"""
+
+from __future__ import annotations
+
+import re
+
+_PLACEHOLDER = re.compile(r"\{([a-z0-9_]+)\}")
+
+
+def render(template: str, **values: str) -> str:
+    """Fill the placeholders of ``template`` that ``values`` names."""
+    return _PLACEHOLDER.sub(
+        lambda m: str(values[m.group(1)]) if m.group(1) in values else m.group(0),
+        template,
+    )
+
+
+def uses(template: str, name: str) -> bool:
+    """Whether ``template`` has a ``{name}`` placeholder."""
+    return any(m.group(1) == name for m in _PLACEHOLDER.finditer(template))
@@ -0,0 +1,69 @@
+"""Running tally of LLM API token usage, by pipeline phase and model.
+
+Each request is tagged with the phase it serves (see ``phase_for_path``),
+and every Claude call made while handling it, including from tasks it
+starts, is counted against that phase.
+"""
+
+from __future__ import annotations
+
+from contextvars import ContextVar
+from dataclasses import dataclass
+
+# Phase of the request being handled.
+current_phase: ContextVar[str] = ContextVar("current_phase", default="other")
+
+# Route prefixes and the phase their Claude calls belong to.
+_PHASES = [
+    ("/api/analyze-project", "intake"),
+    ("/api/extract-technique", "techniques"),
+    ("/api/batch-extract-techniques", "techniques"),
+    ("/api/generate-variant", "generation"),
+    ("/api/plan-variant", "generation"),
+    ("/api/merge-variants", "generation"),
+    ("/api/reconcile-hunk", "generation"),
+    ("/api/llm-judge", "benchmark"),
+    ("/api/run-benchmark", "benchmark"),
+]
+
+
+@dataclass
+class UsageTotals:
+    calls: int = 0
+    input_tokens: int = 0
+    output_tokens: int = 0
+
+
+# Totals since the sidecar started, keyed by (phase, provider, model).
+_totals: dict[tuple[str, str, str], UsageTotals] = {}
+
+
+def phase_for_path(path: str) -> str:
+    """The phase whose Claude calls a request to ``path`` makes."""
+    for prefix, phase in _PHASES:
+        if path.startswith(prefix):
+            return phase
+    return "other"
+
+
+def record(provider: str, model: str, input_tokens: int, output_tokens: int) -> None:
+    """Count one call to ``provider`` against the current request's phase."""
+    totals = _totals.setdefault((current_phase.get(), provider, model), UsageTotals())
+    totals.calls += 1
+    totals.input_tokens += input_tokens
+    totals.output_tokens += output_tokens
+
+
+def snapshot() -> list[dict]:
+    """Totals so far, one entry per phase, provider and model."""
+    return [
+        {
+            "phase": phase,
+            "provider": provider,
+            "model": model,
+            "calls": totals.calls,
+            "input_tokens": totals.input_tokens,
+            "output_tokens": totals.output_tokens,
+        }
+        for (phase, provider, model), totals in sorted(_totals.items())
+    ]
@@ -0,0 +1,94 @@
+"""Temporary git worktrees, so variants are written without touching the
+user's checkout."""
+
+from __future__ import annotations
+
+import contextlib
+import shutil
+import subprocess
+import tempfile
+from collections.abc import Iterator
+from pathlib import Path
+
+
+@contextlib.contextmanager
+def new_branch_worktree(project_path: Path, branch: str, start: str = "HEAD") -> Iterator[Path]:
+    """Create `branch` from `start` and check it out in a temporary worktree.
+
+    The worktree is removed on the way out, whatever was written to it. The
+    branch is kept only if the body finishes; on an error or cancellation it
+    is deleted too, so nothing of a failed variant is left behind. The
+    user's own working tree is never read or changed, so uncommitted work
+    there stays put and stays out of the variant.
+    """
+    scratch = Path(tempfile.mkdtemp(prefix="uniq-worktree-"))
+    worktree = scratch / branch.replace("/", "-")
+    try:
+        subprocess.run(
+            ["git", "worktree", "add", "-b", branch, str(worktree), start],
+            cwd=project_path,
+            check=True,
+            capture_output=True,
+        )
+    except subprocess.CalledProcessError as e:
+        shutil.rmtree(scratch, ignore_errors=True)
+        raise RuntimeError(f"Failed to create branch {branch}: {e.stderr.decode()}") from e
+
+    finished = False
+    try:
+        yield worktree
+        finished = True
+    finally:
+        _remove(project_path, worktree, scratch)
+        if not finished:
+            with contextlib.suppress(Exception):
+                subprocess.run(
+                    ["git", "branch", "-D", branch], cwd=project_path, capture_output=True
+                )
+
+
+@contextlib.contextmanager
+def checkout_worktree(project_path: Path, ref: str) -> Iterator[Path]:
+    """Check `ref` out, detached, in a temporary worktree.
+
+    For reading or building a branch without switching the user's checkout,
+    which may have uncommitted changes. The worktree is removed on the way
+    out; the branch is left as it was.
+    """
+    scratch = Path(tempfile.mkdtemp(prefix="uniq-worktree-"))
+    worktree = scratch / ref.replace("/", "-")
+    try:
+        subprocess.run(
+            ["git", "worktree", "add", "--detach", str(worktree), ref],
+            cwd=project_path,
+            check=True,
+            capture_output=True,
+        )
+    except subprocess.CalledProcessError as e:
+        shutil.rmtree(scratch, ignore_errors=True)
+        raise RuntimeError(f"Failed to check out {ref}: {e.stderr.decode()}") from e
+
+    try:
+        yield worktree
+    finally:
+        _remove(project_path, worktree, scratch)
+
+
+def write_file(path: Path, content: str) -> None:
+    """Write `content` to `path` in a worktree, creating its directories."""
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(content)
+
+
+def _remove(project_path: Path, worktree: Path, scratch: Path) -> None:
+    """Remove `worktree`, and the temporary directory holding it, from git
+    and from disk."""
+    with contextlib.suppress(Exception):
+        subprocess.run(
+            ["git", "worktree", "remove", "--force", str(worktree)],
+            cwd=project_path,
+            capture_output=True,
+        )
+    shutil.rmtree(scratch, ignore_errors=True)
+    with contextlib.suppress(Exception):
+        subprocess.run(["git", "worktree", "prune"], cwd=project_path, capture_output=True)
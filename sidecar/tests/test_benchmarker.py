@@ -0,0 +1,215 @@
+"""Execution benchmarks and LLM judge evaluation."""
+
+from __future__ import annotations
+
+import asyncio
+import json
+import subprocess
+from collections.abc import Callable
+from pathlib import Path
+
+import anthropic
+import httpx
+import pytest
+
+from src.models.benchmark import JudgeScores
+from src.services import benchmarker
+from src.services.benchmarker import run_llm_judge, run_pairwise_judge
+from src.services.claude_client import BackendRateLimitError
+
+
+def claude_rate_limit() -> Exception:
+    request = httpx.Request("POST", "https://api.anthropic.com/v1/messages")
+    return anthropic.RateLimitError(
+        "rate limited", response=httpx.Response(429, request=request), body=None
+    )
+
+
+def backend_rate_limit() -> Exception:
+    return BackendRateLimitError("10")
+
+
+def project(tmp_path: Path) -> Path:
+    for args in (["init", "-q", "-b", "main"], ["commit", "-q", "--allow-empty", "-m", "Base"]):
+        subprocess.run(
+            ["git", "-c", "user.name=Test", "-c", "user.email=test@example.com", *args],
+            cwd=tmp_path,
+            check=True,
+            capture_output=True,
+        )
+    return tmp_path
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("rate_limit", [claude_rate_limit, backend_rate_limit])
+async def test_judges_pass_rate_limits_on(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch, rate_limit: Callable[[], Exception]
+) -> None:
+    class RateLimited:
+        """A client whose every call is rate limited."""
+
+        async def analyze(self, prompt: str, model: str | None = None) -> str:
+            raise rate_limit()
+
+    repo = str(project(tmp_path))
+    monkeypatch.setattr(benchmarker, "get_claude_client", RateLimited)
+
+    with pytest.raises(type(rate_limit())):
+        await run_llm_judge(["main"], repo, "goal")
+    with pytest.raises(type(rate_limit())):
+        await run_pairwise_judge(["main", "main"], repo, "goal")
+
+
+
+@pytest.mark.asyncio
+async def test_variants_are_judged_a_few_at_a_time(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    in_flight = peak = 0
+
+    class Slow:
+        """A client that answers every judgement after a moment."""
+
+        async def analyze(self, prompt: str, model: str | None = None) -> str:
+            nonlocal in_flight, peak
+            in_flight += 1
+            peak = max(peak, in_flight)
+            await asyncio.sleep(0.01)
+            in_flight -= 1
+            scores = dict.fromkeys(
+                ["code_quality", "novelty", "feasibility", "goal_alignment", "completeness"], 5
+            )
+            return json.dumps({**scores, "overall": 5, "explanation": "fine"})
+
+    repo = str(project(tmp_path))
+    monkeypatch.setattr(benchmarker, "get_claude_client", Slow)
+    branches = [f"uniq/variant-{i}" for i in range(10)]
+
+    results = await run_llm_judge(branches, repo, "goal")
+
+    assert list(results) == branches
+    assert all(scores.overall == 5 for scores in results.values())
+    assert 1 < peak <= benchmarker.MAX_CONCURRENT_JUDGEMENTS
+
+def test_test_runs_report_their_peak_memory(tmp_path: Path) -> None:
+    command = "python3 -c 'x = bytearray(64 * 1024 * 1024); print(\"done\")'; exit 3"
+    code, output, peak_mb = benchmarker._run_measured(command, tmp_path, 30)
+    assert code == 3
+    assert output.strip() == "done"
+    assert peak_mb is not None and peak_mb >= 64
+
+    with pytest.raises(subprocess.TimeoutExpired):
+        benchmarker._run_measured("sleep 5", tmp_path, 0)
+
+
+@pytest.mark.parametrize("repetitions", [1, 3])
+def test_runtime_and_memory_come_from_the_test_runs(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch, repetitions: int
+) -> None:
+    monkeypatch.setattr(benchmarker, "_detect_build_command", lambda _: None)
+    monkeypatch.setattr(benchmarker, "_detect_test_command", lambda _: "true")
+
+    metrics = benchmarker._measure_variant(tmp_path, 30, repetitions, lambda _: None)
+
+    assert metrics.test_pass_rate == 1.0
+    assert metrics.runtime_ms is not None and metrics.memory_mb is not None
+    if repetitions == 1:
+        assert metrics.runtime_samples == [] and metrics.runtime_std_ms is None
+    else:
+        assert len(metrics.runtime_samples) == len(metrics.memory_samples) == 3
+        assert metrics.runtime_std_ms is not None and metrics.memory_std_mb is not None
+
+
+def judged(overall: float, quality: float = 7.0) -> JudgeScores:
+    return JudgeScores(
+        code_quality=quality,
+        novelty=5.0,
+        feasibility=5.0,
+        goal_alignment=5.0,
+        completeness=5.0,
+        overall=overall,
+        explanation=f"Scored {overall}",
+    )
+
+
+def test_judge_samples_are_averaged() -> None:
+    scores = benchmarker._aggregate_judge_scores(
+        [judged(6.0, 4.0), judged(7.0, 6.0), judged(9.0, 11.0)], "mean"
+    )
+    assert scores.overall == pytest.approx(22.0 / 3)
+    assert scores.code_quality == pytest.approx(7.0)
+    assert scores.novelty == 5.0
+    assert scores.samples == 3
+    assert scores.spread == 3.0
+    # Explained by the sample nearest the combined verdict.
+    assert scores.explanation == "Scored 7.0"
+
+
+def test_median_ignores_an_outlying_judge() -> None:
+    scores = benchmarker._aggregate_judge_scores([judged(8.0), judged(7.5), judged(1.0)], "median")
+    assert scores.overall == 7.5
+    assert scores.explanation == "Scored 7.5"
+    assert scores.spread == 7.0
+
+
+@pytest.mark.parametrize(
+    "overall, disagreement",
+    [([7.0], False), ([6.0, 7.5], False), ([6.0, 8.0], True), ([3.0, 6.0, 9.0], True)],
+)
+def test_disagreement_is_a_wide_spread_of_overall_scores(
+    overall: list[float], disagreement: bool
+) -> None:
+    scores = benchmarker._aggregate_judge_scores([judged(o) for o in overall], "mean")
+    assert scores.disagreement is disagreement
+    assert scores.samples == len(overall)
+
+
+CARGO_FAILURES = """
+running 3 tests
+...F
+failures:
+
+---- tests::rounds_up stdout ----
+thread 'tests::rounds_up' panicked at src/lib.rs:9:5:
+assertion `left == right` failed
+    indented output from the test
+
+failures:
+    tests::rounds_up
+    tests::parses_empty
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out
+
+running 1 test
+test it_works ... FAILED
+"""
+
+
+def test_failed_tests_are_read_from_cargo_and_pytest() -> None:
+    assert benchmarker._failed_tests(CARGO_FAILURES) == {
+        "tests::rounds_up",
+        "tests::parses_empty",
+        "it_works",
+    }
+    pytest_output = "FAILED tests/test_io.py::test_read - assert 1 == 2\n1 failed, 3 passed"
+    assert benchmarker._failed_tests(pytest_output) == {"tests/test_io.py::test_read"}
+    assert benchmarker._failed_tests("test result: ok. 4 passed") == set()
+
+
+def test_failed_runs_are_left_out_of_the_samples(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    # Fails its first run only.
+    command = "test -e ran && exit 0; touch ran; sleep 0.2; exit 1"
+    monkeypatch.setattr(benchmarker, "_detect_build_command", lambda _: None)
+    monkeypatch.setattr(benchmarker, "_detect_test_command", lambda _: command)
+
+    metrics = benchmarker._measure_variant(tmp_path, 30, 3, lambda _: None)
+
+    assert len(metrics.runtime_samples) == 2
+    assert metrics.runtime_ms is not None and metrics.runtime_ms < 200
+    assert metrics.test_pass_rate == 0.0
+
+    monkeypatch.setattr(benchmarker, "_detect_test_command", lambda _: "exit 1")
+    metrics = benchmarker._measure_variant(tmp_path, 30, 2, lambda _: None)
+    assert metrics.runtime_ms is None and metrics.runtime_samples == []
@@ -0,0 +1,40 @@
+"""Pinging a backend reports whether it can really be reached."""
+
+from __future__ import annotations
+
+import pytest
+
+from src.models.llm import LlmBackend
+from src.services import claude_client
+from src.services.claude_client import ping
+
+BACKEND = LlmBackend(provider="openai", model="gpt-4o")
+
+
+class Client:
+    def __init__(self, error: Exception | None = None):
+        self.error = error
+
+    async def ping(self) -> None:
+        if self.error:
+            raise self.error
+
+
+@pytest.mark.asyncio
+async def test_a_reachable_backend_passes(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(claude_client, "get_claude_client", Client)
+    assert await ping(BACKEND) is None
+
+
+@pytest.mark.asyncio
+async def test_a_rejected_key_fails_with_the_reason(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(
+        claude_client, "get_claude_client", lambda: Client(RuntimeError("401 invalid key"))
+    )
+    assert await ping(BACKEND) == "401 invalid key"
+
+
+@pytest.mark.asyncio
+async def test_a_missing_key_fails_without_a_call(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.delenv("OPENAI_API_KEY", raising=False)
+    assert await ping(BACKEND) == "No API key for openai"
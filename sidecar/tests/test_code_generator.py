@@ -0,0 +1,84 @@
+"""Variant generation reports its progress as it goes."""
+
+from __future__ import annotations
+
+import asyncio
+import json
+import subprocess
+from pathlib import Path
+
+import pytest
+
+from src.models.paper import TechniqueCard
+from src.models.project import ProjectProfile
+from src.services import code_generator
+from src.services.code_generator import generate_variant_code
+
+
+class Writer:
+    """A client that asks for two files to be written."""
+
+    async def generate_code(self, system_prompt: str, user_prompt: str) -> str:
+        files = [{"path": name, "content": "x = 1\n", "action": "create"} for name in "ab"]
+        return json.dumps({"plan": [], "files": files, "dependencies": []})
+
+
+def project(tmp_path: Path) -> ProjectProfile:
+    for args in (
+        ["init", "-q"],
+        ["config", "user.name", "Test"],
+        ["config", "user.email", "test@example.com"],
+        ["commit", "-q", "--allow-empty", "-m", "Baseline"],
+    ):
+        subprocess.run(["git", *args], cwd=tmp_path, check=True, capture_output=True)
+    return ProjectProfile(
+        path=str(tmp_path),
+        user_request="goal",
+        summary="",
+        languages=["Python"],
+        frameworks=[],
+        file_count=0,
+        key_files=[],
+        integration_points=[],
+        file_tree="",
+    )
+
+
+def technique() -> TechniqueCard:
+    return TechniqueCard(
+        name="Technique",
+        paper_id="p1",
+        paper_title="Paper",
+        methodology="",
+        key_components=[],
+        required_data_format="",
+        implementation_complexity="Low",
+        hardware_requirements="",
+        dependencies=[],
+        relevance_score=0.5,
+        integration_approach="",
+    )
+
+
+@pytest.mark.asyncio
+async def test_each_written_file_can_be_seen(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setattr(code_generator, "get_claude_client", Writer)
+    current: list[str] = []
+    seen: list[str] = []
+
+    async def poll() -> None:
+        while True:
+            if current and current[-1] not in seen:
+                seen.append(current[-1])
+            await asyncio.sleep(0)
+
+    poller = asyncio.create_task(poll())
+    result = await generate_variant_code(
+        technique(), project(tmp_path), "uniq/variant", on_progress=current.append
+    )
+    poller.cancel()
+
+    assert result.success, result.error
+    assert "writing a" in seen and "writing b" in seen
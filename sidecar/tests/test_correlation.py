@@ -0,0 +1,52 @@
+"""Log lines carry the correlation ID of the request they were logged for."""
+
+from __future__ import annotations
+
+import asyncio
+import io
+import logging
+from types import SimpleNamespace
+
+import pytest
+
+from src.services import correlation
+
+
+def capture() -> tuple[logging.Logger, io.StringIO]:
+    """A logger formatting records the way the sidecar does, into a buffer."""
+    stream = io.StringIO()
+    handler = logging.StreamHandler(stream)
+    handler.addFilter(correlation.CorrelationFilter())
+    handler.setFormatter(logging.Formatter("%(correlation_id)s %(message)s"))
+    logger = logging.getLogger("test_correlation")
+    logger.handlers = [handler]
+    logger.propagate = False
+    logger.setLevel(logging.INFO)
+    return logger, stream
+
+
+@pytest.mark.asyncio
+async def test_requests_and_their_tasks_log_the_header_id():
+    logger, stream = capture()
+
+    async def handler(request):
+        logger.info("handling")
+        await asyncio.create_task(asyncio.to_thread(logger.info, "in a task"))
+        return "ok"
+
+    async def request(headers):
+        # Each request runs in its own context, as under the server.
+        return await asyncio.create_task(
+            correlation.tag_request(SimpleNamespace(headers=headers), handler)
+        )
+
+    assert await request({correlation.HEADER: "gen-3"}) == "ok"
+    await request({})
+
+    assert stream.getvalue().splitlines() == [
+        "gen-3 handling",
+        "gen-3 in a task",
+        "- handling",
+        "- in a task",
+    ]
+    assert correlation.current_id.get() == "-"
@@ -0,0 +1,97 @@
+"""Dependencies go into the project's manifests, never into the sidecar."""
+
+from __future__ import annotations
+
+import subprocess
+import sys
+from pathlib import Path
+
+import pytest
+
+from src.models.variant import GenerateVariantRequest
+from src.services import dependency_installer
+from src.services.dependency_installer import install_dependencies, manifest_files
+
+
+class Recorder:
+    """Stands in for subprocess.run, failing for packages named `bad`."""
+
+    def __init__(self) -> None:
+        self.commands: list[list[str]] = []
+
+    def __call__(self, command: list[str], **kwargs: object) -> subprocess.CompletedProcess:
+        self.commands.append(command)
+        code = 1 if command[-1] == "bad" else 0
+        return subprocess.CompletedProcess(command, code, stdout="", stderr="not found")
+
+
+@pytest.mark.parametrize(
+    "marker, command",
+    [
+        ("Cargo.toml", ["cargo", "add"]),
+        ("package.json", ["npm", "install", "--package-lock-only"]),
+        ("pyproject.toml", ["uv", "add", "--no-sync"]),
+        ("go.mod", ["go", "get"]),
+    ],
+)
+def test_records_dependencies_without_installing_them(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch, marker: str, command: list[str]
+) -> None:
+    (tmp_path / marker).write_text("")
+    run = Recorder()
+    monkeypatch.setattr(dependency_installer.subprocess, "run", run)
+
+    check = install_dependencies(tmp_path, ["good", "bad"], tmp_path)
+
+    assert run.commands == [[*command, "good"], [*command, "bad"]]
+    assert check is not None
+    assert check.installed == ["good"]
+    assert [f.name for f in check.failed] == ["bad"]
+
+
+def test_pip_installs_into_the_project_venv(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    worktree, project = tmp_path / "worktree", tmp_path / "project"
+    worktree.mkdir()
+    (worktree / "requirements.txt").write_text("numpy")
+    bin_dir = project / ".venv" / ("Scripts" if sys.platform == "win32" else "bin")
+    bin_dir.mkdir(parents=True)
+    (bin_dir / "python").write_text("")
+    run = Recorder()
+    monkeypatch.setattr(dependency_installer.subprocess, "run", run)
+
+    check = install_dependencies(worktree, ["torch", "bad", "numpy"], project)
+
+    python = str(bin_dir / "python")
+    assert run.commands[0] == [python, "-m", "pip", "install", "torch"]
+    assert sys.executable not in {c[0] for c in run.commands}
+    assert check is not None and check.installed == ["torch", "numpy"]
+    assert (worktree / "requirements.txt").read_text() == "numpy\ntorch\n"
+
+
+def test_pip_without_a_venv_installs_nothing(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    (tmp_path / "requirements.txt").write_text("")
+    run = Recorder()
+    monkeypatch.setattr(dependency_installer.subprocess, "run", run)
+
+    check = install_dependencies(tmp_path, ["torch"], tmp_path)
+
+    assert run.commands == []
+    assert check is not None and check.installed == []
+    assert "virtualenv" in check.failed[0].error
+
+
+def test_only_manifests_and_lockfiles_are_staged(tmp_path: Path) -> None:
+    for name in ["package.json", "package-lock.json", "index.js"]:
+        (tmp_path / name).write_text("")
+    (tmp_path / "node_modules").mkdir()
+
+    assert manifest_files(tmp_path) == ["package.json", "package-lock.json"]
+    assert manifest_files(tmp_path / "node_modules") == []
+
+
+def test_installing_is_opt_in() -> None:
+    assert GenerateVariantRequest.model_fields["install_dependencies"].default is False
@@ -0,0 +1,104 @@
+"""A merge that does not finish leaves nothing behind."""
+
+from __future__ import annotations
+
+import json
+import subprocess
+from pathlib import Path
+
+import pytest
+
+from src.models.merge import MergeSource
+from src.models.project import ProjectProfile
+from src.services import merger
+from src.services.merger import merge_variant_code
+
+
+class Writer:
+    """A client that asks for two files to be written."""
+
+    prompts: list[str] = []
+
+    async def generate_code(self, system_prompt: str, user_prompt: str) -> str:
+        self.prompts.append(user_prompt)
+        files = [{"path": name, "content": "x = 1\n", "action": "create"} for name in "ab"]
+        return json.dumps({"files": files, "dependencies": []})
+
+
+def git(cwd: Path, *args: str) -> str:
+    return subprocess.run(
+        ["git", *args], cwd=cwd, check=True, capture_output=True, text=True
+    ).stdout.strip()
+
+
+def project(tmp_path: Path) -> ProjectProfile:
+    git(tmp_path, "init", "-q", "-b", "main")
+    git(tmp_path, "config", "user.name", "Test")
+    git(tmp_path, "config", "user.email", "test@example.com")
+    git(tmp_path, "commit", "-q", "--allow-empty", "-m", "Baseline")
+    for name in ("uniq/a", "uniq/b"):
+        git(tmp_path, "branch", name)
+    return ProjectProfile(
+        path=str(tmp_path),
+        user_request="goal",
+        summary="",
+        languages=["Python"],
+        frameworks=[],
+        file_count=0,
+        key_files=[],
+        integration_points=[],
+        file_tree="",
+    )
+
+
+@pytest.mark.asyncio
+async def test_failed_merge_leaves_the_checkout_alone(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setattr(merger, "get_claude_client", Writer)
+    written: list[Path] = []
+
+    def write_file(path: Path, content: str) -> None:
+        if written:
+            raise OSError("disk full")
+        path.write_text(content)
+        written.append(path)
+
+    monkeypatch.setattr(merger, "write_file", write_file)
+    profile = project(tmp_path)
+    sources = [
+        MergeSource(branch=name, technique={"name": name}, blend=50)
+        for name in ("uniq/a", "uniq/b")
+    ]
+
+    result = await merge_variant_code(sources, profile, "uniq/merge")
+
+    assert not result.success
+    assert written and not written[0].exists()
+    assert git(tmp_path, "branch", "--show-current") == "main"
+    assert git(tmp_path, "status", "--porcelain") == ""
+    assert git(tmp_path, "branch", "--list", "uniq/merge") == ""
+
+
+@pytest.mark.asyncio
+async def test_guidance_is_kept_when_the_template_has_no_place_for_it(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setattr(merger, "get_claude_client", Writer)
+    monkeypatch.setattr(Writer, "prompts", [])
+    sources = [
+        MergeSource(branch=name, technique={"name": name}, blend=50)
+        for name in ("uniq/a", "uniq/b")
+    ]
+
+    await merge_variant_code(
+        sources,
+        project(tmp_path),
+        "uniq/merge",
+        guidance="keep the old API",
+        template="Merge {techniques} as follows:\n{instructions}",
+    )
+
+    [prompt] = Writer.prompts
+    assert prompt.startswith("Merge ")
+    assert "keep the old API" in prompt
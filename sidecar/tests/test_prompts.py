@@ -0,0 +1,29 @@
+"""User prompt templates are filled in without touching anything else."""
+
+from __future__ import annotations
+
+from src.services.prompts import render, uses
+
+
+def test_placeholders_are_filled_in():
+    assert render("Apply {technique} to {project}.", technique="LoRA", project="an API") == (
+        "Apply LoRA to an API."
+    )
+
+
+def test_other_braces_are_left_alone():
+    template = 'Reply with {"score": 1-10} for {diff}; {unknown} and {Mixed} stay.'
+    assert render(template, diff="the diff") == (
+        'Reply with {"score": 1-10} for the diff; {unknown} and {Mixed} stay.'
+    )
+
+
+def test_values_are_not_expanded_again():
+    rendered = render("{papers} / {guidance}", papers="uses {guidance} and {}", guidance="g")
+    assert rendered == "uses {guidance} and {} / g"
+
+
+def test_uses_matches_whole_placeholders_only():
+    assert uses("Follow {guidance}.", "guidance")
+    assert not uses("Follow the guidance.", "guidance")
+    assert not uses("Follow {guidance_notes}.", "guidance")
@@ -0,0 +1,142 @@
+"""Check the Pydantic models against the protocol the Rust client is built on.
+
+protocol.schema.json is written by `uniq schema` from the Rust types, and a
+Rust test fails when it falls behind them. Here every model is compared with
+its Rust counterpart field by field, so a field added, renamed or retyped on
+one side only fails these tests instead of a request at runtime.
+"""
+
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from typing import Any
+
+import pytest
+from pydantic import BaseModel
+
+from src.models import benchmark, llm, merge, paper, project, variant
+from src.routes.benchmark import LlmJudgeRequest
+from src.routes.research import BatchExtractRequest, ExtractTechniqueRequest
+
+SCHEMA = json.loads((Path(__file__).parent.parent / "protocol.schema.json").read_text())
+DEFINITIONS: dict[str, Any] = SCHEMA["definitions"]
+
+# Rust protocol type -> the model the sidecar reads or writes it with.
+MODELS: dict[str, type[BaseModel]] = {
+    "LlmBackend": llm.LlmBackend,
+    "PingRequest": llm.PingRequest,
+    "PingResponse": llm.PingResponse,
+    "AnalyzeProjectRequest": project.AnalyzeProjectRequest,
+    "ProjectProfile": project.ProjectProfile,
+    "DetectedFramework": project.DetectedFramework,
+    "IntegrationPoint": project.IntegrationPoint,
+    "SearchPapersRequest": paper.SearchRequest,
+    "PaperMeta": paper.PaperMeta,
+    "CitedPaper": paper.CitedPaper,
+    "TechniqueCard": paper.TechniqueCard,
+    "ExtractTechniqueRequest": ExtractTechniqueRequest,
+    "BatchExtractRequest": BatchExtractRequest,
+    "PlannedFile": variant.PlannedFile,
+    "ImplementationPlan": variant.ImplementationPlan,
+    "PlanVariantRequest": variant.PlanVariantRequest,
+    "PlanVariantResponse": variant.PlanVariantResponse,
+    "GenerateVariantRequest": variant.GenerateVariantRequest,
+    "GenerateVariantResponse": variant.VariantResult,
+    "GenerationTranscript": variant.GenerationTranscript,
+    "DependencyCheck": variant.DependencyCheck,
+    "FailedDependency": variant.FailedDependency,
+    "CancelGenerationRequest": variant.CancelGenerationRequest,
+    "CancelGenerationResponse": variant.CancelGenerationResponse,
+    "GenerationProgressResponse": variant.GenerationProgressResponse,
+    "MergeVariantsRequest": merge.MergeRequest,
+    "MergeSourceRequest": merge.MergeSource,
+    "ComponentBlendRequest": merge.ComponentBlend,
+    "ReconcileHunkRequest": merge.ReconcileHunkRequest,
+    "ReconcileHunkResponse": merge.ReconcileHunkResponse,
+    "RunBenchmarkRequest": benchmark.BenchmarkRequest,
+    "RunBenchmarkResponse": benchmark.BenchmarkResult,
+    "ExecutionMetrics": benchmark.ExecutionMetrics,
+    "MetricValue": benchmark.MetricValue,
+    "BenchmarkProgressResponse": benchmark.BenchmarkProgressResponse,
+    "JudgeEnsemble": benchmark.JudgeEnsemble,
+    "JudgeScores": benchmark.JudgeScores,
+    "LlmJudgeRequest": LlmJudgeRequest,
+    "LlmJudgeResponse": benchmark.LlmJudgeResponse,
+    "PairwiseComparison": benchmark.PairwiseComparison,
+    "PairwiseJudgeResponse": benchmark.PairwiseJudgeResponse,
+}
+
+# Objects the sidecar builds as plain dicts in server.py.
+UNMODELLED = {"HealthResponse", "UsageResponse", "UsageEntry"}
+
+
+def resolve(schema: Any, definitions: dict[str, Any]) -> Any:
+    """Follow a `$ref`, and drop the `null` an optional field also allows."""
+    if isinstance(schema, dict) and "$ref" in schema:
+        schema = definitions[schema["$ref"].rsplit("/", 1)[-1]]
+    if not isinstance(schema, dict):
+        return schema
+    options = schema.get("anyOf")
+    if options:
+        options = [o for o in options if o.get("type") != "null"]
+        if len(options) == 1:
+            return resolve(options[0], definitions)
+    # Rust enums with documented variants list each one separately.
+    variants = schema.get("oneOf")
+    if variants and all("enum" in v for v in variants):
+        return {"type": "string", "enum": [value for v in variants for value in v["enum"]]}
+    kind = schema.get("type")
+    if isinstance(kind, list):
+        kinds = [k for k in kind if k != "null"]
+        schema = {**schema, "type": kinds[0] if len(kinds) == 1 else kinds}
+    return schema
+
+
+def differences(rust: Any, python: Any, python_defs: dict[str, Any], at: str) -> list[str]:
+    """Where values valid for `rust` and for `python` could disagree."""
+    rust = resolve(rust, DEFINITIONS)
+    python = resolve(python, python_defs)
+    # `true` or `{}` accepts anything, e.g. serde_json::Value or Any.
+    if rust in (True, {}) or python in (True, {}):
+        return []
+
+    rust_kind, python_kind = rust.get("type"), python.get("type")
+    if rust_kind != python_kind and not (rust_kind == "integer" and python_kind == "number"):
+        return [f"{at}: {rust_kind} in Rust, {python_kind} in Python"]
+
+    if "enum" in rust and "enum" in python and set(rust["enum"]) != set(python["enum"]):
+        return [f"{at}: values {sorted(rust['enum'])} in Rust, {sorted(python['enum'])} in Python"]
+
+    found = []
+    if rust_kind == "array":
+        found += differences(rust.get("items", True), python.get("items", True), python_defs, f"{at}[]")
+    if rust_kind == "object":
+        rust_fields = rust.get("properties", {})
+        python_fields = python.get("properties", {})
+        for name in sorted(rust_fields.keys() - python_fields.keys()):
+            found.append(f"{at}.{name}: only in Rust")
+        for name in sorted(python_fields.keys() - rust_fields.keys()):
+            found.append(f"{at}.{name}: only in Python")
+        for name in sorted(rust_fields.keys() & python_fields.keys()):
+            found += differences(rust_fields[name], python_fields[name], python_defs, f"{at}.{name}")
+        if "additionalProperties" in rust or "additionalProperties" in python:
+            found += differences(
+                rust.get("additionalProperties", True),
+                python.get("additionalProperties", True),
+                python_defs,
+                f"{at}{{}}",
+            )
+    return found
+
+
+def test_every_protocol_object_has_a_model():
+    objects = {name for name, schema in DEFINITIONS.items() if schema.get("type") == "object"}
+    assert sorted(objects - MODELS.keys() - UNMODELLED) == []
+    assert sorted(MODELS.keys() - objects) == []
+
+
+@pytest.mark.parametrize("name", sorted(MODELS))
+def test_model_matches_protocol(name: str):
+    python = MODELS[name].model_json_schema()
+    assert differences(DEFINITIONS[name], python, python.get("$defs", {}), name) == []
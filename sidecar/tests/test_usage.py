@@ -0,0 +1,70 @@
+"""Token usage is tallied against the phase of the request that made it."""
+
+from __future__ import annotations
+
+import asyncio
+
+import pytest
+
+from src.services import usage
+
+
+def test_requests_map_to_their_phase():
+    assert usage.phase_for_path("/api/generate-variant") == "generation"
+    assert usage.phase_for_path("/api/batch-extract-techniques") == "techniques"
+    assert usage.phase_for_path("/api/llm-judge/pairwise") == "benchmark"
+    assert usage.phase_for_path("/api/health") == "other"
+
+
+def test_calls_add_up_by_phase_provider_and_model(monkeypatch):
+    monkeypatch.setattr(usage, "_totals", {})
+    usage.record("anthropic", "sonnet", 100, 10)
+    token = usage.current_phase.set("generation")
+    try:
+        usage.record("anthropic", "sonnet", 200, 20)
+        usage.record("anthropic", "sonnet", 300, 30)
+        usage.record("openai", "gpt-4o", 1, 2)
+    finally:
+        usage.current_phase.reset(token)
+
+    assert usage.snapshot() == [
+        {
+            "phase": "generation",
+            "provider": "anthropic",
+            "model": "sonnet",
+            "calls": 2,
+            "input_tokens": 500,
+            "output_tokens": 50,
+        },
+        {
+            "phase": "generation",
+            "provider": "openai",
+            "model": "gpt-4o",
+            "calls": 1,
+            "input_tokens": 1,
+            "output_tokens": 2,
+        },
+        {
+            "phase": "other",
+            "provider": "anthropic",
+            "model": "sonnet",
+            "calls": 1,
+            "input_tokens": 100,
+            "output_tokens": 10,
+        },
+    ]
+
+
+@pytest.mark.asyncio
+async def test_tasks_count_against_the_phase_that_started_them(monkeypatch):
+    monkeypatch.setattr(usage, "_totals", {})
+
+    async def handle_request():
+        usage.current_phase.set("benchmark")
+        await asyncio.create_task(asyncio.to_thread(usage.record, "anthropic", "haiku", 5, 1))
+
+    await asyncio.create_task(handle_request())
+
+    [entry] = usage.snapshot()
+    assert entry["phase"] == "benchmark"
+    assert usage.current_phase.get() == "other"
@@ -0,0 +1,78 @@
+"""Variants are written in a worktree of their own, never in the user's checkout."""
+
+from __future__ import annotations
+
+import subprocess
+from pathlib import Path
+
+import pytest
+
+from src.services.worktree import checkout_worktree, new_branch_worktree
+
+
+def git(repo: Path, *args: str) -> str:
+    return subprocess.run(
+        ["git", *args], cwd=repo, check=True, capture_output=True, text=True
+    ).stdout
+
+
+def project(tmp_path: Path) -> Path:
+    """A repository with one commit and uncommitted changes on top."""
+    repo = tmp_path / "project"
+    repo.mkdir()
+    git(repo, "init", "-q")
+    git(repo, "config", "user.name", "Test")
+    git(repo, "config", "user.email", "test@example.com")
+    (repo / "main.py").write_text("print('hi')\n")
+    git(repo, "add", "main.py")
+    git(repo, "commit", "-q", "-m", "Baseline")
+    (repo / "main.py").write_text("print('work in progress')\n")
+    (repo / ".env").write_text("API_KEY=secret\n")
+    return repo
+
+
+def test_finished_variants_keep_their_branch_only(tmp_path: Path) -> None:
+    repo = project(tmp_path)
+    head = git(repo, "rev-parse", "--abbrev-ref", "HEAD")
+
+    with new_branch_worktree(repo, "uniq/variant") as worktree:
+        assert (worktree / "main.py").read_text() == "print('hi')\n"
+        assert not (worktree / ".env").exists()
+        (worktree / "model.py").write_text("x = 1\n")
+        git(worktree, "add", "model.py")
+        git(worktree, "commit", "-q", "-m", "Variant")
+
+    assert not worktree.exists()
+    assert git(repo, "rev-parse", "--abbrev-ref", "HEAD") == head
+    assert git(repo, "show", "uniq/variant:model.py") == "x = 1\n"
+    assert (repo / "main.py").read_text() == "print('work in progress')\n"
+    assert (repo / ".env").exists()
+    assert not (repo / "model.py").exists()
+
+
+def test_failed_variants_leave_nothing_behind(tmp_path: Path) -> None:
+    repo = project(tmp_path)
+
+    with pytest.raises(RuntimeError), new_branch_worktree(repo, "uniq/variant") as worktree:
+        (worktree / "model.py").write_text("x = 1\n")
+        raise RuntimeError("cancelled halfway")
+
+    assert not worktree.exists()
+    assert "uniq/variant" not in git(repo, "branch", "--list")
+    assert "model.py" not in git(repo, "status", "--porcelain")
+    assert len(git(repo, "worktree", "list").splitlines()) == 1
+
+
+def test_checking_out_a_branch_leaves_the_checkout_alone(tmp_path: Path) -> None:
+    repo = project(tmp_path)
+    head = git(repo, "rev-parse", "--abbrev-ref", "HEAD")
+    git(repo, "branch", "uniq/variant")
+
+    with checkout_worktree(repo, "uniq/variant") as worktree:
+        assert (worktree / "main.py").read_text() == "print('hi')\n"
+
+    assert not worktree.exists()
+    assert git(repo, "rev-parse", "--abbrev-ref", "HEAD") == head
+    assert "uniq/variant" in git(repo, "branch", "--list")
+    assert (repo / "main.py").read_text() == "print('work in progress')\n"
+    assert len(git(repo, "worktree", "list").splitlines()) == 1